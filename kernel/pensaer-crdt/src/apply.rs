@@ -0,0 +1,462 @@
+//! Operation replay.
+//!
+//! Rebuilds model state from logged operations. Operations are applied in
+//! a deterministic causal order, so every replica that holds the same set of
+//! operations reconstructs the same state, however the logs were merged.
+//!
+//! Concurrent writes to the same property (or position) are resolved like
+//! [`LWWRegister`](crate::LWWRegister): the higher timestamp wins, with ties
+//! broken by replica ID. A write that causally follows the current value
+//! always replaces it.
+//!
+//! Deletes leave tombstones. Updates and moves of a deleted element are
+//! dropped, as are writes concurrent with the delete, and a `Create` only
+//! brings the element back if it causally follows the delete. Dropped
+//! operations are recorded in [`ModelState::dropped`].
+//!
+//! # Example
+//!
+//! ```
+//! use pensaer_crdt::apply::replay;
+//! use pensaer_crdt::{OperationType, ReplicaId, Session};
+//!
+//! let mut session = Session::new(ReplicaId::new("user-1"));
+//! let create = session.operation(
+//!     "op-1",
+//!     OperationType::Create {
+//!         element_type: "wall".to_string(),
+//!         element_id: "wall-1".to_string(),
+//!     },
+//! );
+//!
+//! let state = replay(&[&create]);
+//! assert_eq!(state.element("wall-1").unwrap().element_type, "wall");
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::{Operation, OperationType, VectorClock};
+
+/// State of one element after replay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementState {
+    /// Element type given at creation.
+    pub element_type: String,
+    /// Latest value of each property.
+    pub properties: HashMap<String, String>,
+    /// Latest position, if the element was moved.
+    pub position: Option<(f64, f64, f64)>,
+}
+
+/// Model state reconstructed from operations: element id → element state.
+///
+/// Deleted elements are not present.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelState {
+    elements: HashMap<String, ElementState>,
+    dropped: Vec<String>,
+}
+
+impl ModelState {
+    /// Get an element's state.
+    pub fn element(&self, element_id: &str) -> Option<&ElementState> {
+        self.elements.get(element_id)
+    }
+
+    /// Get the latest value of an element property.
+    pub fn property(&self, element_id: &str, property: &str) -> Option<&str> {
+        self.elements
+            .get(element_id)
+            .and_then(|e| e.properties.get(property))
+            .map(String::as_str)
+    }
+
+    /// Iterate over live elements.
+    pub fn elements(&self) -> impl Iterator<Item = (&String, &ElementState)> {
+        self.elements.iter()
+    }
+
+    /// Number of live elements.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Check if there are no live elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// IDs of operations discarded because of a delete, in replay order.
+    pub fn dropped(&self) -> &[String] {
+        &self.dropped
+    }
+}
+
+/// Replay operations into a model state.
+///
+/// The input order does not matter: operations are sorted into a causal
+/// order first. Updates and moves of elements that were never created are
+/// ignored.
+pub fn replay(ops: &[&Operation]) -> ModelState {
+    replay_with_sources(ops).0
+}
+
+/// Replay operations, also returning the operations the result depends on.
+///
+/// These are the `Create` of each live element, the operation that wrote
+/// each of its current values, and the deletes behind each tombstone.
+/// Replaying just them (in any order) gives the same elements.
+pub(crate) fn replay_with_sources<'a>(ops: &[&'a Operation]) -> (ModelState, Vec<&'a Operation>) {
+    let mut ordered = ops.to_vec();
+    ordered.sort_by(|a, b| causal_order(a, b));
+
+    let mut state = ModelState::default();
+    // Merged clock of the deletes of each deleted element
+    let mut tombstones: HashMap<String, VectorClock> = HashMap::new();
+    // Operation that wrote the current value of each (element, property),
+    // with `None` standing for the position
+    let mut writers: HashMap<(String, Option<String>), &Operation> = HashMap::new();
+    // Create of each live element and deletes since its last create
+    let mut creators: HashMap<String, &Operation> = HashMap::new();
+    let mut deletes: HashMap<String, Vec<&Operation>> = HashMap::new();
+
+    for op in ordered {
+        match &op.op_type {
+            OperationType::Create {
+                element_type,
+                element_id,
+            } => {
+                if let Some(tombstone) = tombstones.get(element_id) {
+                    if !tombstone.happened_before(&op.clock) {
+                        state.dropped.push(op.id.clone());
+                        continue;
+                    }
+                    tombstones.remove(element_id);
+                    deletes.remove(element_id);
+                } else if state.elements.contains_key(element_id) {
+                    continue;
+                }
+                writers.retain(|(id, _), _| id != element_id);
+                creators.insert(element_id.clone(), op);
+                state.elements.insert(
+                    element_id.clone(),
+                    ElementState {
+                        element_type: element_type.clone(),
+                        properties: HashMap::new(),
+                        position: None,
+                    },
+                );
+            }
+            OperationType::Update {
+                element_id,
+                property,
+                new_value,
+                ..
+            } => {
+                if tombstones.contains_key(element_id) {
+                    state.dropped.push(op.id.clone());
+                    continue;
+                }
+                let Some(element) = state.elements.get_mut(element_id) else {
+                    continue;
+                };
+                let slot = (element_id.clone(), Some(property.clone()));
+                if wins(op, writers.get(&slot)) {
+                    element
+                        .properties
+                        .insert(property.clone(), new_value.clone());
+                    writers.insert(slot, op);
+                }
+            }
+            OperationType::Move { element_id, to, .. } => {
+                if tombstones.contains_key(element_id) {
+                    state.dropped.push(op.id.clone());
+                    continue;
+                }
+                let Some(element) = state.elements.get_mut(element_id) else {
+                    continue;
+                };
+                let slot = (element_id.clone(), None);
+                if wins(op, writers.get(&slot)) {
+                    element.position = Some(*to);
+                    writers.insert(slot, op);
+                }
+            }
+            OperationType::Delete { element_id } => {
+                tombstones
+                    .entry(element_id.clone())
+                    .or_default()
+                    .merge(&op.clock);
+                deletes.entry(element_id.clone()).or_default().push(op);
+                creators.remove(element_id);
+                if state.elements.remove(element_id).is_some() {
+                    // Values written concurrently with the delete are lost
+                    let mut lost: Vec<String> = writers
+                        .iter()
+                        .filter(|((id, _), w)| id == element_id && w.is_concurrent(op))
+                        .map(|(_, w)| w.id.clone())
+                        .collect();
+                    lost.sort();
+                    state.dropped.extend(lost);
+                    writers.retain(|(id, _), _| id != element_id);
+                }
+            }
+        }
+    }
+
+    let sources = creators
+        .into_values()
+        .chain(writers.into_values())
+        .chain(deletes.into_values().flatten())
+        .collect();
+    (state, sources)
+}
+
+/// Whether `op` replaces the value written by `current`.
+fn wins(op: &Operation, current: Option<&&Operation>) -> bool {
+    let Some(current) = current else {
+        return true;
+    };
+    if current.happened_before(op) {
+        return true;
+    }
+    if op.happened_before(current) {
+        return false;
+    }
+    // Concurrent: same rule as LWWRegister::set
+    let timestamp = op.clock.get(&op.replica_id);
+    let current_timestamp = current.clock.get(&current.replica_id);
+    timestamp > current_timestamp
+        || (timestamp == current_timestamp && op.replica_id.0 > current.replica_id.0)
+}
+
+/// Total order consistent with happened-before, for any payload.
+///
+/// The sum of a clock's entries grows along every causal chain, so sorting
+/// by it first keeps causes before effects; the rest breaks ties
+/// deterministically. Replicas that sort the same operations with it apply
+/// them in the same order.
+pub fn causal_order<P>(a: &Operation<P>, b: &Operation<P>) -> Ordering {
+    clock_sum(a)
+        .cmp(&clock_sum(b))
+        .then_with(|| a.wall_time.cmp(&b.wall_time))
+        .then_with(|| a.replica_id.0.cmp(&b.replica_id.0))
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+fn clock_sum<P>(op: &Operation<P>) -> u64 {
+    op.clock
+        .clocks
+        .values()
+        .fold(0u64, |sum, &t| sum.saturating_add(t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OperationLog, ReplicaId, VectorClock};
+
+    fn update(id: &str, replica: &ReplicaId, clock: &VectorClock, value: &str) -> Operation {
+        Operation::with_wall_time(
+            id,
+            OperationType::Update {
+                element_id: "wall-1".to_string(),
+                property: "height".to_string(),
+                old_value: String::new(),
+                new_value: value.to_string(),
+            },
+            replica.clone(),
+            clock.clone(),
+            0,
+        )
+    }
+
+    fn create(replica: &ReplicaId, clock: &VectorClock) -> Operation {
+        create_with_id("op-create", replica, clock)
+    }
+
+    fn create_with_id(id: &str, replica: &ReplicaId, clock: &VectorClock) -> Operation {
+        Operation::with_wall_time(
+            id,
+            OperationType::Create {
+                element_type: "wall".to_string(),
+                element_id: "wall-1".to_string(),
+            },
+            replica.clone(),
+            clock.clone(),
+            0,
+        )
+    }
+
+    fn delete(id: &str, replica: &ReplicaId, clock: &VectorClock) -> Operation {
+        Operation::with_wall_time(
+            id,
+            OperationType::Delete {
+                element_id: "wall-1".to_string(),
+            },
+            replica.clone(),
+            clock.clone(),
+            0,
+        )
+    }
+
+    #[test]
+    fn create_then_update() {
+        let replica = ReplicaId::new("user-1");
+        let mut clock = VectorClock::new();
+
+        clock.increment(&replica);
+        let create = create(&replica, &clock);
+        clock.increment(&replica);
+        let first = update("op-2", &replica, &clock, "3.0");
+        clock.increment(&replica);
+        let second = update("op-3", &replica, &clock, "3.5");
+        clock.increment(&replica);
+        let moved = Operation::with_wall_time(
+            "op-4",
+            OperationType::Move {
+                element_id: "wall-1".to_string(),
+                from: (0.0, 0.0, 0.0),
+                to: (1.0, 2.0, 0.0),
+            },
+            replica.clone(),
+            clock.clone(),
+            0,
+        );
+
+        // Input order is irrelevant
+        let state = replay(&[&second, &moved, &first, &create]);
+        assert_eq!(state.len(), 1);
+        assert_eq!(state.property("wall-1", "height"), Some("3.5"));
+        let wall = state.element("wall-1").unwrap();
+        assert_eq!(wall.element_type, "wall");
+        assert_eq!(wall.position, Some((1.0, 2.0, 0.0)));
+
+        clock.increment(&replica);
+        let delete = delete("op-5", &replica, &clock);
+        assert!(replay(&[&create, &first, &delete]).is_empty());
+    }
+
+    #[test]
+    fn concurrent_updates_converge_regardless_of_merge_order() {
+        let alice = ReplicaId::new("alice");
+        let bob = ReplicaId::new("bob");
+
+        let mut base = VectorClock::new();
+        base.increment(&alice);
+        let create = create(&alice, &base);
+
+        let mut alice_clock = base.clone();
+        alice_clock.increment(&alice);
+        let from_alice = update("op-alice", &alice, &alice_clock, "2.7");
+
+        let mut bob_clock = base.clone();
+        bob_clock.increment(&bob);
+        let from_bob = update("op-bob", &bob, &bob_clock, "3.2");
+        assert!(from_alice.is_concurrent(&from_bob));
+
+        let mut alice_log = OperationLog::new();
+        alice_log.add(create.clone());
+        alice_log.add(from_alice.clone());
+        let mut bob_log = OperationLog::new();
+        bob_log.add(create);
+        bob_log.add(from_bob);
+
+        let mut merged_at_alice = OperationLog::new();
+        merged_at_alice.merge(&alice_log);
+        merged_at_alice.merge(&bob_log);
+        let mut merged_at_bob = OperationLog::new();
+        merged_at_bob.merge(&bob_log);
+        merged_at_bob.merge(&alice_log);
+
+        let a = replay(&merged_at_alice.operations_ordered());
+        let b = replay(&merged_at_bob.operations_ordered());
+        assert_eq!(a, b);
+
+        // Alice's timestamp (2) beats Bob's (1)
+        assert_eq!(a.property("wall-1", "height"), Some("2.7"));
+
+        // A later update that has seen both replaces the winner
+        let mut after = alice_clock.clone();
+        after.merge(&bob_clock);
+        after.increment(&bob);
+        let resolved = update("op-resolve", &bob, &after, "3.0");
+        let mut ops = merged_at_bob.operations_ordered();
+        ops.push(&resolved);
+        assert_eq!(replay(&ops).property("wall-1", "height"), Some("3.0"));
+    }
+
+    #[test]
+    fn delete_drops_concurrent_updates() {
+        let alice = ReplicaId::new("alice");
+        let bob = ReplicaId::new("bob");
+
+        let mut base = VectorClock::new();
+        base.increment(&bob);
+        let create = create(&bob, &base);
+
+        let mut bob_clock = base.clone();
+        bob_clock.increment(&bob);
+        let deleted = delete("op-delete", &bob, &bob_clock);
+
+        // Alice keeps editing without having seen the delete; her first edit
+        // replays before it and her second after it
+        let mut alice_clock = base.clone();
+        alice_clock.increment(&alice);
+        let first = update("op-alice-1", &alice, &alice_clock, "2.7");
+        alice_clock.increment(&alice);
+        let second = update("op-alice-2", &alice, &alice_clock, "3.0");
+
+        let mut log = OperationLog::new();
+        for op in [&create, &first, &second, &deleted] {
+            log.add(op.clone());
+        }
+        assert!(log.is_deleted("wall-1"));
+
+        let state = replay(&log.operations_ordered());
+        assert!(state.element("wall-1").is_none());
+        assert_eq!(state.dropped(), ["op-alice-1", "op-alice-2"]);
+        assert_eq!(state, replay(&[&second, &deleted, &first, &create]));
+    }
+
+    #[test]
+    fn causally_later_create_resurrects() {
+        let alice = ReplicaId::new("alice");
+        let bob = ReplicaId::new("bob");
+
+        let mut clock = VectorClock::new();
+        clock.increment(&alice);
+        let create = create(&alice, &clock);
+        let mut bob_clock = clock.clone();
+        clock.increment(&alice);
+        let deleted = delete("op-delete", &alice, &clock);
+
+        // Bob re-creates concurrently with the delete: ignored
+        bob_clock.increment(&bob);
+        let concurrent = create_with_id("op-bob", &bob, &bob_clock);
+
+        let mut log = OperationLog::new();
+        for op in [&create, &deleted, &concurrent] {
+            log.add(op.clone());
+        }
+        assert!(log.is_deleted("wall-1"));
+        let state = replay(&log.operations_ordered());
+        assert!(state.is_empty());
+        assert_eq!(state.dropped(), ["op-bob"]);
+
+        // Alice re-creates after seeing both
+        clock.merge(&bob_clock);
+        clock.increment(&alice);
+        let recreate = create_with_id("op-recreate", &alice, &clock);
+        clock.increment(&alice);
+        let height = update("op-height", &alice, &clock, "3.0");
+        log.add(recreate);
+        log.add(height);
+        assert!(!log.is_deleted("wall-1"));
+
+        let state = replay(&log.operations_ordered());
+        assert_eq!(state.property("wall-1", "height"), Some("3.0"));
+        assert_eq!(state.dropped(), ["op-bob"]);
+    }
+}
@@ -8,8 +8,19 @@
 //!
 //! - VectorClock for causal ordering of operations
 //! - LWW (Last-Writer-Wins) register for simple value conflict resolution
+//! - Observed-remove set ([`ORSet`]) for collaborative membership
+//! - Last-writer-wins map ([`LWWMap`]) for per-property conflict resolution
 //! - MergeResult tracking for audit and debugging
 //! - Self-healing merge operations with overflow protection
+//! - Operation replay into model state ([`apply`])
+//! - Serde serialization of operations for sync, with exact `f64` round-trips
+//!   and deterministic JSON
+//! - Typed operation payloads ([`Payload`]) sharing the log's ordering,
+//!   dedup, and tombstones
+//! - Log compaction below a clock every replica has observed, and
+//!   [`LogSnapshot`]s to persist a compacted log
+//! - Injectable wall-clock sources ([`Clock`]) for operation timestamps,
+//!   owned by a replica's editing [`Session`]
 //!
 //! # Example
 //!
@@ -24,11 +35,23 @@
 //! register.set("new_value".to_string(), &replica_id, &clock);
 //! ```
 
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt;
 
+pub mod apply;
+mod lww_map;
+mod orset;
+mod session;
+mod wall_clock;
+
+pub use lww_map::LWWMap;
+pub use orset::{Dot, ORSet};
+pub use session::Session;
+pub use wall_clock::{Clock, ManualClock, SystemClock};
+
 /// Unique identifier for a replica (user/session).
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct ReplicaId(String);
 
 impl ReplicaId {
@@ -54,16 +77,19 @@ impl fmt::Display for ReplicaId {
 /// Each replica maintains a counter. When comparing two clocks:
 /// - If all counters in A <= B and at least one <, then A happened-before B
 /// - If some counters in A > B and some <, the events are concurrent
-#[derive(Debug, Clone, Default)]
+///
+/// Counters are kept sorted by replica, so a clock always serializes to the
+/// same JSON.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct VectorClock {
-    clocks: HashMap<String, u64>,
+    clocks: BTreeMap<String, u64>,
 }
 
 impl VectorClock {
     /// Create a new empty vector clock.
     pub fn new() -> Self {
         Self {
-            clocks: HashMap::new(),
+            clocks: BTreeMap::new(),
         }
     }
 
@@ -259,7 +285,7 @@ impl<T: Clone> LWWRegister<T> {
 }
 
 /// Operation type for the operation log.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OperationType {
     /// Create a new element
     Create {
@@ -283,35 +309,99 @@ pub enum OperationType {
     },
 }
 
+/// Payload carried by an [`Operation`].
+///
+/// The log only needs to know which element an operation targets and
+/// whether it creates or deletes it, so typed payloads (such as geometry
+/// operations) can share its ordering, dedup, and tombstones.
+pub trait Payload {
+    /// ID of the element the operation targets.
+    fn element_id(&self) -> &str;
+
+    /// Whether the operation creates its element.
+    fn is_create(&self) -> bool;
+
+    /// Whether the operation deletes its element.
+    fn is_delete(&self) -> bool;
+}
+
+impl Payload for OperationType {
+    fn element_id(&self) -> &str {
+        match self {
+            Self::Create { element_id, .. }
+            | Self::Update { element_id, .. }
+            | Self::Delete { element_id }
+            | Self::Move { element_id, .. } => element_id,
+        }
+    }
+
+    fn is_create(&self) -> bool {
+        matches!(self, Self::Create { .. })
+    }
+
+    fn is_delete(&self) -> bool {
+        matches!(self, Self::Delete { .. })
+    }
+}
+
 /// An operation in the CRDT log.
-#[derive(Debug, Clone)]
-pub struct Operation {
+///
+/// The payload defaults to the string-valued [`OperationType`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Operation<P = OperationType> {
     /// Unique operation ID
     pub id: String,
     /// The operation type and data
-    pub op_type: OperationType,
+    pub op_type: P,
     /// Vector clock at time of operation
     pub clock: VectorClock,
     /// Replica that created this operation
     pub replica_id: ReplicaId,
-    /// Unix timestamp (for ordering within same vector clock)
+    /// Unix timestamp in milliseconds (for ordering concurrent operations)
     pub wall_time: u64,
 }
 
-impl Operation {
-    /// Create a new operation.
+impl<P> Operation<P> {
+    /// Create a new operation, timestamped by the [`SystemClock`].
+    ///
+    /// Prefer making operations through a [`Session`], which owns the
+    /// clock source.
+    #[deprecated(note = "use with_clock_source/with_wall_time")]
     pub fn new(
         id: impl Into<String>,
-        op_type: OperationType,
+        op_type: P,
         replica_id: ReplicaId,
         clock: VectorClock,
+    ) -> Self {
+        Self::with_clock_source(id, op_type, replica_id, clock, &SystemClock)
+    }
+
+    /// Create a new operation, timestamped by `source`.
+    pub fn with_clock_source(
+        id: impl Into<String>,
+        op_type: P,
+        replica_id: ReplicaId,
+        clock: VectorClock,
+        source: &dyn Clock,
+    ) -> Self {
+        Self::with_wall_time(id, op_type, replica_id, clock, source.now_millis())
+    }
+
+    /// Create an operation with a known timestamp, e.g. one read back from
+    /// storage or received from a peer.
+    pub fn with_wall_time(
+        id: impl Into<String>,
+        op_type: P,
+        replica_id: ReplicaId,
+        clock: VectorClock,
+        wall_time: u64,
     ) -> Self {
         Self {
             id: id.into(),
             op_type,
             clock,
             replica_id,
-            wall_time: 0, // Would use actual time in production
+            wall_time,
         }
     }
 
@@ -326,35 +416,82 @@ impl Operation {
     }
 }
 
+impl<P: Serialize> Operation<P> {
+    /// Serialize to JSON for sync.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+}
+
+impl<P: serde::de::DeserializeOwned> Operation<P> {
+    /// Deserialize from JSON produced by [`Operation::to_json`].
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
 /// Operation log for storing and merging operations.
-#[derive(Debug, Default)]
-pub struct OperationLog {
-    operations: Vec<Operation>,
+#[derive(Debug)]
+pub struct OperationLog<P = OperationType> {
+    operations: Vec<Operation<P>>,
     /// Tracks which operations have been seen (for deduplication)
     seen_ids: std::collections::HashSet<String>,
+    /// Tombstones: merged clock of all deletes of each deleted element
+    tombstones: HashMap<String, VectorClock>,
+}
+
+impl<P> Default for OperationLog<P> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl OperationLog {
+impl<P> OperationLog<P> {
     /// Create a new empty operation log.
     pub fn new() -> Self {
         Self {
             operations: Vec::new(),
             seen_ids: std::collections::HashSet::new(),
+            tombstones: HashMap::new(),
         }
     }
+}
 
+impl<P: Payload + Clone> OperationLog<P> {
     /// Add an operation to the log.
     ///
     /// Returns false if operation was already seen (duplicate).
-    pub fn add(&mut self, op: Operation) -> bool {
+    pub fn add(&mut self, op: Operation<P>) -> bool {
         if self.seen_ids.contains(&op.id) {
             return false; // Already seen, self-healing deduplication
         }
         self.seen_ids.insert(op.id.clone());
+        if op.op_type.is_delete() {
+            self.tombstones
+                .entry(op.op_type.element_id().to_string())
+                .or_default()
+                .merge(&op.clock);
+        }
         self.operations.push(op);
         true
     }
 
+    /// Check if an element is deleted.
+    ///
+    /// An element stays deleted until it is re-created by a `Create` that
+    /// causally follows every delete of it; operations concurrent with a
+    /// delete cannot resurrect it.
+    pub fn is_deleted(&self, element_id: &str) -> bool {
+        let Some(tombstone) = self.tombstones.get(element_id) else {
+            return false;
+        };
+        !self.operations.iter().any(|op| {
+            op.op_type.is_create()
+                && op.op_type.element_id() == element_id
+                && tombstone.happened_before(&op.clock)
+        })
+    }
+
     /// Merge operations from another log.
     ///
     /// Returns the number of new operations added.
@@ -372,7 +509,7 @@ impl OperationLog {
     ///
     /// Operations are sorted such that if A happened-before B, A comes first.
     /// Concurrent operations are ordered by wall time, then by replica ID.
-    pub fn operations_ordered(&self) -> Vec<&Operation> {
+    pub fn operations_ordered(&self) -> Vec<&Operation<P>> {
         let mut ops: Vec<_> = self.operations.iter().collect();
         ops.sort_by(|a, b| {
             if a.happened_before(b) {
@@ -400,17 +537,98 @@ impl OperationLog {
     }
 
     /// Get operations for a specific element.
-    pub fn operations_for_element(&self, element_id: &str) -> Vec<&Operation> {
+    pub fn operations_for_element(&self, element_id: &str) -> Vec<&Operation<P>> {
         self.operations
             .iter()
-            .filter(|op| match &op.op_type {
-                OperationType::Create { element_id: id, .. } => id == element_id,
-                OperationType::Update { element_id: id, .. } => id == element_id,
-                OperationType::Delete { element_id: id } => id == element_id,
-                OperationType::Move { element_id: id, .. } => id == element_id,
-            })
+            .filter(|op| op.op_type.element_id() == element_id)
             .collect()
     }
+
+    /// Capture the log, typically right after [`compact`](OperationLog::compact).
+    pub fn snapshot(&self) -> LogSnapshot<P> {
+        LogSnapshot {
+            operations: self.operations_ordered().into_iter().cloned().collect(),
+            seen_ids: self.seen_ids.iter().cloned().collect(),
+            tombstones: self
+                .tombstones
+                .iter()
+                .map(|(id, clock)| (id.clone(), clock.clone()))
+                .collect(),
+        }
+    }
+
+    /// Rebuild a log from a snapshot plus the operations added after it.
+    ///
+    /// Operations the snapshot had compacted away are still deduplicated.
+    pub fn from_snapshot(
+        snapshot: LogSnapshot<P>,
+        tail_ops: impl IntoIterator<Item = Operation<P>>,
+    ) -> Self {
+        let mut log = Self {
+            operations: Vec::new(),
+            seen_ids: snapshot.seen_ids.into_iter().collect(),
+            tombstones: snapshot.tombstones.into_iter().collect(),
+        };
+        for op in snapshot.operations {
+            log.seen_ids.insert(op.id.clone());
+            log.operations.push(op);
+        }
+        for op in tail_ops {
+            log.add(op);
+        }
+        log
+    }
+}
+
+/// Persistable state of an [`OperationLog`].
+///
+/// Holds the operations still in the log and the IDs of every operation the
+/// log has seen, including those removed by compaction, so a log rebuilt
+/// with [`OperationLog::from_snapshot`] rejects them if a lagging peer sends
+/// them again. Collections are sorted, so a snapshot serializes to the same
+/// JSON on every replica holding the same log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogSnapshot<P = OperationType> {
+    /// Operations in the log, in causal order
+    pub operations: Vec<Operation<P>>,
+    /// IDs of every operation seen, kept or compacted
+    pub seen_ids: BTreeSet<String>,
+    /// Merged clock of all deletes of each deleted element
+    pub tombstones: BTreeMap<String, VectorClock>,
+}
+
+impl OperationLog<OperationType> {
+    /// Compact operations every replica has already observed.
+    ///
+    /// `stable_clock` must be known to be observed by all replicas. The
+    /// operations at or before it are collapsed into a snapshot: only those
+    /// holding the latest value of each element property or position, the
+    /// create of each live element, and the deletes behind each tombstone are
+    /// kept. Replaying the log gives the same elements as before, though
+    /// operations dropped by deletes before the clock are no longer reported
+    /// in [`ModelState::dropped`](apply::ModelState::dropped).
+    ///
+    /// IDs of removed operations stay in the dedup set, so they are still
+    /// rejected if a peer sends them again.
+    ///
+    /// Returns the number of operations removed.
+    pub fn compact(&mut self, stable_clock: &VectorClock) -> usize {
+        let stable: Vec<&Operation> = self
+            .operations
+            .iter()
+            .filter(|op| op.clock.happened_before(stable_clock) || op.clock == *stable_clock)
+            .collect();
+        let (_, sources) = apply::replay_with_sources(&stable);
+        let keep: std::collections::HashSet<&str> =
+            sources.iter().map(|op| op.id.as_str()).collect();
+        let discard: std::collections::HashSet<String> = stable
+            .iter()
+            .filter(|op| !keep.contains(op.id.as_str()))
+            .map(|op| op.id.clone())
+            .collect();
+        self.operations.retain(|op| !discard.contains(&op.id));
+        discard.len()
+    }
 }
 
 #[cfg(test)]
@@ -530,17 +748,14 @@ mod tests {
     #[test]
     fn operation_log_deduplication() {
         let mut log = OperationLog::new();
-        let replica = ReplicaId::new("user-1");
-        let clock = VectorClock::new();
+        let mut session = Session::new(ReplicaId::new("user-1"));
 
-        let op = Operation::new(
+        let op = session.operation(
             "op-1",
             OperationType::Create {
                 element_type: "wall".to_string(),
                 element_id: "wall-1".to_string(),
             },
-            replica.clone(),
-            clock.clone(),
         );
 
         assert!(log.add(op.clone())); // First add succeeds
@@ -552,32 +767,352 @@ mod tests {
     fn operation_log_merge() {
         let mut log1 = OperationLog::new();
         let mut log2 = OperationLog::new();
-        let replica1 = ReplicaId::new("user-1");
-        let replica2 = ReplicaId::new("user-2");
-        let clock = VectorClock::new();
+        let mut session1 = Session::new(ReplicaId::new("user-1"));
+        let mut session2 = Session::new(ReplicaId::new("user-2"));
 
-        log1.add(Operation::new(
+        log1.add(session1.operation(
             "op-1",
             OperationType::Create {
                 element_type: "wall".to_string(),
                 element_id: "wall-1".to_string(),
             },
-            replica1,
-            clock.clone(),
         ));
 
-        log2.add(Operation::new(
+        log2.add(session2.operation(
             "op-2",
             OperationType::Create {
                 element_type: "door".to_string(),
                 element_id: "door-1".to_string(),
             },
-            replica2,
-            clock,
         ));
 
         let added = log1.merge(&log2);
         assert_eq!(added, 1);
         assert_eq!(log1.len(), 2);
     }
+
+    #[test]
+    fn concurrent_operations_sort_by_wall_time() {
+        let alice = ReplicaId::new("alice");
+        let bob = ReplicaId::new("bob");
+        let time = ManualClock::new(1_000);
+        let mut alice_clock = VectorClock::new();
+        let mut bob_clock = VectorClock::new();
+        alice_clock.increment(&alice);
+        bob_clock.increment(&bob);
+
+        // Alice's edit is later, so it sorts after Bob's despite her ID
+        let bob_op = Operation::with_clock_source(
+            "op-bob",
+            OperationType::Delete {
+                element_id: "wall-1".to_string(),
+            },
+            bob,
+            bob_clock,
+            &time,
+        );
+        time.advance(500);
+        let alice_op = Operation::with_clock_source(
+            "op-alice",
+            OperationType::Delete {
+                element_id: "wall-2".to_string(),
+            },
+            alice,
+            alice_clock,
+            &time,
+        );
+        assert!(alice_op.is_concurrent(&bob_op));
+        assert_eq!((bob_op.wall_time, alice_op.wall_time), (1_000, 1_500));
+
+        let mut log = OperationLog::new();
+        log.add(alice_op);
+        log.add(bob_op);
+        let ids: Vec<&str> = log
+            .operations_ordered()
+            .iter()
+            .map(|op| op.id.as_str())
+            .collect();
+        assert_eq!(ids, ["op-bob", "op-alice"]);
+
+        // Equal wall times fall back to replica ID
+        let mut carol_clock = VectorClock::new();
+        carol_clock.increment(&ReplicaId::new("carol"));
+        log.add(Operation::with_wall_time(
+            "op-carol",
+            OperationType::Delete {
+                element_id: "wall-3".to_string(),
+            },
+            ReplicaId::new("carol"),
+            carol_clock,
+            1_000,
+        ));
+        let ids: Vec<&str> = log
+            .operations_ordered()
+            .iter()
+            .map(|op| op.id.as_str())
+            .collect();
+        assert_eq!(ids, ["op-bob", "op-carol", "op-alice"]);
+    }
+
+    #[test]
+    fn operations_round_trip_through_json() {
+        let alice = ReplicaId::new("alice");
+        let bob = ReplicaId::new("bob");
+        let mut clock = VectorClock::new();
+
+        clock.increment(&alice);
+        let create = Operation::with_wall_time(
+            "op-1",
+            OperationType::Create {
+                element_type: "wall".to_string(),
+                element_id: "wall-1".to_string(),
+            },
+            alice.clone(),
+            clock.clone(),
+            1_000,
+        );
+        clock.increment(&bob);
+        let update = Operation::with_wall_time(
+            "op-2",
+            OperationType::Update {
+                element_id: "wall-1".to_string(),
+                property: "height".to_string(),
+                old_value: "3.0".to_string(),
+                new_value: "3.5".to_string(),
+            },
+            bob,
+            clock.clone(),
+            2_000,
+        );
+        clock.increment(&alice);
+        let to = (0.1 + 0.2, std::f64::consts::PI, -1.0e-17);
+        let moved = Operation::with_wall_time(
+            "op-3",
+            OperationType::Move {
+                element_id: "wall-1".to_string(),
+                from: (0.0, 0.0, 0.0),
+                to,
+            },
+            alice,
+            clock,
+            3_000,
+        );
+
+        let mut log = OperationLog::new();
+        for op in [&moved, &create, &update] {
+            log.add(op.clone());
+        }
+        let wire: Vec<String> = log
+            .operations_ordered()
+            .iter()
+            .map(|op| op.to_json().unwrap())
+            .collect();
+
+        let mut received = OperationLog::new();
+        for json in wire.iter().rev() {
+            received.add(Operation::from_json(json).unwrap());
+        }
+
+        let expected = log.operations_ordered();
+        let actual = received.operations_ordered();
+        assert_eq!(actual.len(), 3);
+        for (a, b) in expected.iter().zip(&actual) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.replica_id, b.replica_id);
+            assert_eq!(a.clock, b.clock);
+            assert_eq!(a.wall_time, b.wall_time);
+        }
+
+        match &actual[2].op_type {
+            OperationType::Move { to: parsed, .. } => {
+                assert_eq!(parsed.0.to_bits(), to.0.to_bits());
+                assert_eq!(parsed.1.to_bits(), to.1.to_bits());
+                assert_eq!(parsed.2.to_bits(), to.2.to_bits());
+            }
+            other => panic!("expected a move, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn compaction_preserves_replayed_state() {
+        let alice = ReplicaId::new("alice");
+        let bob = ReplicaId::new("bob");
+        let mut clock = VectorClock::new();
+        let mut ops = Vec::new();
+        let mut next = |replica: &ReplicaId, clock: &mut VectorClock, op_type| {
+            clock.increment(replica);
+            let op = Operation::with_wall_time(
+                format!("op-{}", ops.len() + 1),
+                op_type,
+                replica.clone(),
+                clock.clone(),
+                ops.len() as u64,
+            );
+            ops.push(op.clone());
+            op
+        };
+        let create = |id: &str| OperationType::Create {
+            element_type: "wall".to_string(),
+            element_id: id.to_string(),
+        };
+        let height = |value: usize| OperationType::Update {
+            element_id: "wall-1".to_string(),
+            property: "height".to_string(),
+            old_value: String::new(),
+            new_value: value.to_string(),
+        };
+
+        next(&alice, &mut clock, create("wall-1"));
+        next(&alice, &mut clock, create("wall-2"));
+        for value in 0..20 {
+            let replica = if value % 2 == 0 { &alice } else { &bob };
+            next(replica, &mut clock, height(value));
+        }
+        next(
+            &bob,
+            &mut clock,
+            OperationType::Move {
+                element_id: "wall-1".to_string(),
+                from: (0.0, 0.0, 0.0),
+                to: (1.0, 0.0, 0.0),
+            },
+        );
+        next(
+            &bob,
+            &mut clock,
+            OperationType::Delete {
+                element_id: "wall-2".to_string(),
+            },
+        );
+        let stable = clock.clone();
+
+        // Past the stable clock: a later edit and a re-create of the deleted wall
+        next(&alice, &mut clock, height(99));
+        let mut bob_clock = stable.clone();
+        next(&bob, &mut bob_clock, create("wall-2"));
+
+        let mut log = OperationLog::new();
+        for op in &ops {
+            log.add(op.clone());
+        }
+        let before = apply::replay(&log.operations_ordered());
+        let len = log.len();
+
+        let removed = log.compact(&stable);
+        // Kept: the live wall's create, its last height and move, the delete
+        assert_eq!(removed, 20);
+        assert_eq!(log.len(), len - removed);
+        assert_eq!(apply::replay(&log.operations_ordered()), before);
+        assert_eq!(before.property("wall-1", "height"), Some("99"));
+        assert!(before.element("wall-2").is_some());
+        assert!(!log.is_deleted("wall-2"));
+
+        // Compacted operations are still deduplicated
+        assert!(!log.add(ops[3].clone()));
+        assert_eq!(log.compact(&stable), 0);
+    }
+
+    /// Alice and Bob share `shared`; each then makes `tail` more edits to
+    /// wall-1's height, concurrently.
+    fn diverged_logs(shared: usize, tail: usize) -> (OperationLog, OperationLog, VectorClock) {
+        let alice = ReplicaId::new("alice");
+        let bob = ReplicaId::new("bob");
+        let time = ManualClock::new(0);
+        let height = |replica: &ReplicaId, clock: &VectorClock, n: usize| {
+            time.advance(1);
+            Operation::with_clock_source(
+                format!("{}-{}", replica, n),
+                OperationType::Update {
+                    element_id: "wall-1".to_string(),
+                    property: "height".to_string(),
+                    old_value: String::new(),
+                    new_value: n.to_string(),
+                },
+                replica.clone(),
+                clock.clone(),
+                &time,
+            )
+        };
+
+        let mut clock = VectorClock::new();
+        clock.increment(&alice);
+        let create = Operation::with_clock_source(
+            "create",
+            OperationType::Create {
+                element_type: "wall".to_string(),
+                element_id: "wall-1".to_string(),
+            },
+            alice.clone(),
+            clock.clone(),
+            &time,
+        );
+        let mut at_alice = OperationLog::new();
+        at_alice.add(create);
+        for n in 0..shared {
+            let replica = if n % 2 == 0 { &alice } else { &bob };
+            clock.increment(replica);
+            at_alice.add(height(replica, &clock, n));
+        }
+        let mut at_bob = OperationLog::new();
+        at_bob.merge(&at_alice);
+        let stable = clock.clone();
+
+        let mut bob_clock = stable.clone();
+        for n in shared..shared + tail {
+            clock.increment(&alice);
+            at_alice.add(height(&alice, &clock, n));
+            bob_clock.increment(&bob);
+            at_bob.add(height(&bob, &bob_clock, n + tail));
+        }
+        (at_alice, at_bob, stable)
+    }
+
+    #[test]
+    fn compacted_log_merges_with_lagging_replica() {
+        let (mut compacted, lagging, stable) = diverged_logs(10, 3);
+        let (mut plain, _, _) = diverged_logs(10, 3);
+
+        assert_eq!(compacted.compact(&stable), 9);
+
+        // The compacted log only takes the lagging replica's new edits
+        assert_eq!(compacted.merge(&lagging), 3);
+        assert_eq!(plain.merge(&lagging), 3);
+        let mut caught_up = lagging;
+        assert_eq!(caught_up.merge(&compacted), 3);
+
+        let expected = apply::replay(&plain.operations_ordered());
+        assert_eq!(apply::replay(&compacted.operations_ordered()), expected);
+        assert_eq!(apply::replay(&caught_up.operations_ordered()), expected);
+
+        // Merging the uncompacted history again changes nothing
+        assert_eq!(compacted.merge(&plain), 0);
+    }
+
+    #[test]
+    fn snapshot_round_trip_keeps_dedup() {
+        let (mut log, lagging, stable) = diverged_logs(10, 3);
+        log.compact(&stable);
+        let snapshot = log.snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(serde_json::to_string(&log.snapshot()).unwrap(), json);
+
+        let restored: LogSnapshot = serde_json::from_str(&json).unwrap();
+        let tail: Vec<Operation> = lagging
+            .operations_ordered()
+            .into_iter()
+            .filter(|op| op.replica_id.as_str() == "bob")
+            .cloned()
+            .collect();
+        let mut rebuilt = OperationLog::from_snapshot(restored, tail);
+
+        // Bob's shared edits were compacted away; only his tail is new
+        assert_eq!(rebuilt.len(), log.len() + 3);
+        assert_eq!(rebuilt.merge(&lagging), 0);
+
+        log.merge(&lagging);
+        assert_eq!(
+            apply::replay(&rebuilt.operations_ordered()),
+            apply::replay(&log.operations_ordered())
+        );
+    }
 }
@@ -0,0 +1,290 @@
+//! Last-writer-wins map.
+//!
+//! Each key resolves on its own, like an [`LWWRegister`](crate::LWWRegister)
+//! per key, so concurrent edits to different properties of an element both
+//! survive. A write is stamped with the sum of the writer's vector clock,
+//! so a write made after seeing another always has the higher stamp. The
+//! higher stamp wins and equal stamps are broken by replica ID. Removes leave
+//! a stamped tombstone, so a remove and a concurrent set resolve by the same
+//! rule on every replica.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{MergeResult, ReplicaId, VectorClock};
+
+/// One key's latest write: a value, or a tombstone when removed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Entry<V> {
+    value: Option<V>,
+    timestamp: u64,
+    replica_id: ReplicaId,
+}
+
+impl<V> Entry<V> {
+    /// Check if this write wins over `other`.
+    fn beats(&self, other: &Self) -> bool {
+        (self.timestamp, &self.replica_id) > (other.timestamp, &other.replica_id)
+    }
+}
+
+/// Last-writer-wins map, e.g. of a wall's properties by name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LWWMap<K: Eq + Hash, V> {
+    entries: HashMap<K, Entry<V>>,
+}
+
+impl<K: Eq + Hash, V> Default for LWWMap<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> LWWMap<K, V> {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a key, stamped with the replica's current clock.
+    ///
+    /// Increment the clock for the replica before each write. Ignored if the key already holds a later write or tombstone.
+    pub fn set(&mut self, key: K, value: V, replica_id: &ReplicaId, clock: &VectorClock) {
+        self.write(key, Some(value), replica_id, clock);
+    }
+
+    /// Remove a key, leaving a tombstone stamped like a set.
+    ///
+    /// The tombstone is recorded even if the key is absent, so a concurrent
+    /// earlier set that arrives later stays removed.
+    pub fn remove(&mut self, key: K, replica_id: &ReplicaId, clock: &VectorClock) {
+        self.write(key, None, replica_id, clock);
+    }
+
+    fn write(&mut self, key: K, value: Option<V>, replica_id: &ReplicaId, clock: &VectorClock) {
+        let entry = Entry {
+            value,
+            timestamp: clock
+                .clocks
+                .values()
+                .fold(0u64, |sum, &n| sum.saturating_add(n)),
+            replica_id: replica_id.clone(),
+        };
+        match self.entries.get_mut(&key) {
+            Some(current) if !entry.beats(current) => {}
+            Some(current) => *current = entry,
+            None => {
+                self.entries.insert(key, entry);
+            }
+        }
+    }
+
+    /// Get the live value of a key.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).and_then(|e| e.value.as_ref())
+    }
+
+    /// Check if a key has a live value.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Iterate over live keys and values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries
+            .iter()
+            .filter_map(|(k, e)| e.value.as_ref().map(|v| (k, v)))
+    }
+
+    /// Number of live keys.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Check if no keys are live.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Merge with another replica's map.
+    ///
+    /// Returns one result per key the other map holds, with the key's
+    /// resolved value (`None` when removed). Writes at the same timestamp
+    /// from different replicas are reported as conflicts and resolved by
+    /// replica ID.
+    pub fn merge(&mut self, other: &Self) -> Vec<(K, MergeResult<Option<V>>)> {
+        let mut results = Vec::with_capacity(other.entries.len());
+        for (key, theirs) in &other.entries {
+            let conflict = match self.entries.get_mut(key) {
+                Some(ours) => {
+                    let conflict =
+                        ours.timestamp == theirs.timestamp && ours.replica_id != theirs.replica_id;
+                    if theirs.beats(ours) {
+                        *ours = theirs.clone();
+                    }
+                    conflict
+                }
+                None => {
+                    self.entries.insert(key.clone(), theirs.clone());
+                    false
+                }
+            };
+
+            let resolved = &self.entries[key];
+            let result = if conflict {
+                MergeResult::Conflict {
+                    resolved: resolved.value.clone(),
+                    description: format!(
+                        "Concurrent writes at timestamp {}. Resolved by replica ID ordering.",
+                        resolved.timestamp
+                    ),
+                }
+            } else {
+                MergeResult::Clean(resolved.value.clone())
+            };
+            results.push((key.clone(), result));
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replica(name: &str) -> (ReplicaId, VectorClock) {
+        (ReplicaId::new(name), VectorClock::new())
+    }
+
+    #[test]
+    fn concurrent_edits_to_different_keys_merge_cleanly() {
+        let (alice, mut alice_clock) = replica("alice");
+        let (bob, mut bob_clock) = replica("bob");
+
+        let mut at_alice = LWWMap::new();
+        alice_clock.increment(&alice);
+        at_alice.set("height", 2700.0, &alice, &alice_clock);
+        at_alice.set("thickness", 200.0, &alice, &alice_clock);
+        let mut at_bob = at_alice.clone();
+        bob_clock.merge(&alice_clock);
+
+        // Alice edits the height while Bob edits the thickness
+        alice_clock.increment(&alice);
+        at_alice.set("height", 3000.0, &alice, &alice_clock);
+        bob_clock.increment(&bob);
+        at_bob.set("thickness", 250.0, &bob, &bob_clock);
+
+        assert!(at_alice.merge(&at_bob).iter().all(|(_, r)| r.is_clean()));
+        assert!(at_bob.merge(&at_alice).iter().all(|(_, r)| r.is_clean()));
+        for map in [&at_alice, &at_bob] {
+            assert_eq!(map.get(&"height"), Some(&3000.0));
+            assert_eq!(map.get(&"thickness"), Some(&250.0));
+            assert_eq!(map.len(), 2);
+        }
+    }
+
+    #[test]
+    fn same_key_resolves_by_timestamp_then_replica() {
+        let (alice, mut alice_clock) = replica("alice");
+        let (bob, mut bob_clock) = replica("bob");
+
+        let mut at_alice = LWWMap::new();
+        let mut at_bob = LWWMap::new();
+        alice_clock.increment(&alice);
+        at_alice.set("height", 2700.0, &alice, &alice_clock);
+        bob_clock.increment(&bob);
+        at_bob.set("height", 3000.0, &bob, &bob_clock);
+
+        // Same timestamp: "bob" > "alice" wins on both sides
+        let mut ab = at_alice.clone();
+        let results = ab.merge(&at_bob);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, "height");
+        assert!(results[0].1.conflict_description().is_some());
+        let mut ba = at_bob.clone();
+        assert!(!ba.merge(&at_alice)[0].1.is_clean());
+        assert_eq!(ab.get(&"height"), Some(&3000.0));
+        assert_eq!(ba.get(&"height"), Some(&3000.0));
+
+        // A later timestamp beats the replica ordering
+        alice_clock.increment(&alice);
+        at_alice.set("height", 2400.0, &alice, &alice_clock);
+        let results = ab.merge(&at_alice);
+        assert!(results[0].1.is_clean());
+        assert_eq!(ab.get(&"height"), Some(&2400.0));
+        ba.merge(&at_alice);
+        assert_eq!(ba.get(&"height"), Some(&2400.0));
+    }
+
+    #[test]
+    fn remove_and_concurrent_set_follow_lww() {
+        let (alice, mut alice_clock) = replica("alice");
+        let (bob, mut bob_clock) = replica("bob");
+
+        let mut base = LWWMap::new();
+        alice_clock.increment(&alice);
+        base.set("fire_rating", "EI 30".to_string(), &alice, &alice_clock);
+        base.set("finish", "paint".to_string(), &alice, &alice_clock);
+        bob_clock.merge(&alice_clock);
+
+        // A later set beats a remove
+        let mut at_alice = base.clone();
+        let mut at_bob = base.clone();
+        alice_clock.increment(&alice);
+        at_alice.remove("fire_rating", &alice, &alice_clock);
+        for _ in 0..3 {
+            bob_clock.increment(&bob);
+        }
+        at_bob.set("fire_rating", "EI 60".to_string(), &bob, &bob_clock);
+
+        // A later remove beats a set
+        for _ in 0..3 {
+            alice_clock.increment(&alice);
+        }
+        at_alice.remove("finish", &alice, &alice_clock);
+        at_bob.set("finish", "tile".to_string(), &bob, &bob_clock);
+
+        at_alice.merge(&at_bob);
+        at_bob.merge(&at_alice);
+        for map in [&at_alice, &at_bob] {
+            assert_eq!(map.get(&"fire_rating"), Some(&"EI 60".to_string()));
+            assert!(!map.contains_key(&"finish"));
+            assert_eq!(map.len(), 1);
+        }
+
+        // A stale copy cannot bring the removed key back
+        at_alice.merge(&base);
+        assert!(!at_alice.contains_key(&"finish"));
+
+        // A tombstone for an absent key still outranks an older set
+        let mut empty = LWWMap::new();
+        empty.remove("finish", &alice, &alice_clock);
+        empty.merge(&base);
+        assert!(!empty.contains_key(&"finish"));
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let (alice, mut clock) = replica("alice");
+        let mut map = LWWMap::new();
+        clock.increment(&alice);
+        map.set("height".to_string(), 2700.0, &alice, &clock);
+        map.remove("thickness".to_string(), &alice, &clock);
+
+        let json = serde_json::to_string(&map).unwrap();
+        let back: LWWMap<String, f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.get(&"height".to_string()), Some(&2700.0));
+        assert_eq!(back.len(), 1);
+
+        // The tombstone survives the trip
+        let mut stale = LWWMap::new();
+        stale.set("thickness".to_string(), 200.0, &alice, &VectorClock::new());
+        let mut back = back;
+        back.merge(&stale);
+        assert!(!back.contains_key(&"thickness".to_string()));
+    }
+}
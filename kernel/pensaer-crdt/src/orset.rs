@@ -0,0 +1,220 @@
+//! Observed-remove set.
+//!
+//! Each add is tagged with a unique dot, `(replica, counter)`. A remove
+//! only deletes the dots it has observed, so when an add and a remove of the
+//! same value are concurrent, the add's dot is unseen by the remove and the
+//! value stays ("add wins"). Removed dots are remembered, so merging an older
+//! copy of the set cannot bring them back.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{MergeResult, ReplicaId, VectorClock};
+
+/// Unique tag of one add: the replica and its clock entry at the time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Dot {
+    /// Replica that made the add.
+    pub replica_id: ReplicaId,
+    /// The replica's clock entry when it added.
+    pub counter: u64,
+}
+
+/// Observed-remove set, e.g. of the element IDs in a building.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ORSet<T: Eq + Hash> {
+    /// Live dots of each value; a value is present while it has any.
+    entries: HashMap<T, HashSet<Dot>>,
+    /// Dots deleted by removes.
+    removed: HashSet<Dot>,
+}
+
+impl<T: Eq + Hash> Default for ORSet<T> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            removed: HashSet::new(),
+        }
+    }
+}
+
+impl<T: Eq + Hash + Clone> ORSet<T> {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a value, tagged with the replica's current clock entry.
+    ///
+    /// Increment the clock for the replica before each add, so every add
+    /// gets its own dot; a dot that was already removed is not re-added.
+    pub fn add(&mut self, value: T, replica_id: &ReplicaId, clock: &VectorClock) {
+        let dot = Dot {
+            replica_id: replica_id.clone(),
+            counter: clock.get(replica_id),
+        };
+        if self.removed.contains(&dot) {
+            return;
+        }
+        self.entries.entry(value).or_default().insert(dot);
+    }
+
+    /// Remove a value, deleting every add of it this replica has seen.
+    ///
+    /// Returns false if the value was not present.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let Some(dots) = self.entries.remove(value) else {
+            return false;
+        };
+        self.removed.extend(dots);
+        true
+    }
+
+    /// Check if a value is present.
+    pub fn contains(&self, value: &T) -> bool {
+        self.entries.contains_key(value)
+    }
+
+    /// Iterate over the present values, in no particular order.
+    pub fn elements(&self) -> impl Iterator<Item = &T> {
+        self.entries.keys()
+    }
+
+    /// Number of present values.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no values are present.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Merge with another replica's set.
+    ///
+    /// Dots removed on either side are dropped, and all other dots are
+    /// kept. The result is the same in whichever order replicas merge.
+    /// Returns a conflict when a value the other side removed survives
+    /// because of an add that remove had not seen.
+    pub fn merge(&mut self, other: &Self) -> MergeResult<()> {
+        for (value, dots) in &other.entries {
+            let unseen = dots.iter().filter(|d| !self.removed.contains(d)).cloned();
+            let entry = self.entries.entry(value.clone()).or_default();
+            entry.extend(unseen);
+            if entry.is_empty() {
+                self.entries.remove(value);
+            }
+        }
+
+        let mut survivors = 0;
+        self.entries.retain(|_, dots| {
+            let before = dots.len();
+            dots.retain(|d| !other.removed.contains(d));
+            if dots.len() < before && !dots.is_empty() {
+                survivors += 1;
+            }
+            !dots.is_empty()
+        });
+        self.removed.extend(other.removed.iter().cloned());
+
+        if survivors == 0 {
+            MergeResult::Clean(())
+        } else {
+            MergeResult::Conflict {
+                resolved: (),
+                description: format!(
+                    "{} removed value(s) kept by concurrent adds. Resolved as add-wins.",
+                    survivors
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(set: &ORSet<String>) -> Vec<String> {
+        let mut values: Vec<String> = set.elements().cloned().collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn concurrent_add_wins_over_remove() {
+        let alice = ReplicaId::new("alice");
+        let mut alice_clock = VectorClock::new();
+
+        let mut at_alice = ORSet::new();
+        alice_clock.increment(&alice);
+        at_alice.add("wall-1".to_string(), &alice, &alice_clock);
+        alice_clock.increment(&alice);
+        at_alice.add("wall-2".to_string(), &alice, &alice_clock);
+
+        let mut at_bob = at_alice.clone();
+
+        // Bob removes both walls; concurrently Alice re-adds wall-1
+        assert!(at_bob.remove(&"wall-1".to_string()));
+        assert!(at_bob.remove(&"wall-2".to_string()));
+        assert!(!at_bob.remove(&"wall-3".to_string()));
+        alice_clock.increment(&alice);
+        at_alice.add("wall-1".to_string(), &alice, &alice_clock);
+
+        let result = at_alice.merge(&at_bob);
+        assert!(!result.is_clean());
+        let result = at_bob.merge(&at_alice);
+        assert!(result.is_clean());
+
+        // Only the add Bob had not seen survives
+        assert_eq!(sorted(&at_alice), ["wall-1"]);
+        assert_eq!(sorted(&at_bob), ["wall-1"]);
+
+        // A stale copy cannot resurrect removed dots
+        let mut stale_clock = VectorClock::new();
+        stale_clock.increment(&alice);
+        stale_clock.increment(&alice);
+        let mut stale = ORSet::new();
+        stale.add("wall-2".to_string(), &alice, &stale_clock);
+        at_bob.merge(&stale);
+        assert!(!at_bob.contains(&"wall-2".to_string()));
+    }
+
+    #[test]
+    fn merge_is_commutative() {
+        let alice = ReplicaId::new("alice");
+        let bob = ReplicaId::new("bob");
+        let mut alice_clock = VectorClock::new();
+        let mut bob_clock = VectorClock::new();
+        let mut a = ORSet::new();
+        let mut b = ORSet::new();
+
+        for i in 0..6 {
+            alice_clock.increment(&alice);
+            a.add(format!("wall-{}", i), &alice, &alice_clock);
+            bob_clock.increment(&bob);
+            b.add(format!("wall-{}", i + 3), &bob, &bob_clock);
+        }
+        a.remove(&"wall-4".to_string());
+        b.remove(&"wall-4".to_string());
+        b.remove(&"wall-8".to_string());
+
+        let mut ab = a.clone();
+        ab.merge(&b);
+        let mut ba = b.clone();
+        ba.merge(&a);
+
+        assert_eq!(sorted(&ab), sorted(&ba));
+        assert_eq!(
+            sorted(&ab),
+            ["wall-0", "wall-1", "wall-2", "wall-3", "wall-5", "wall-6", "wall-7"]
+        );
+
+        // Merging again changes nothing
+        let before = sorted(&ab);
+        assert!(ab.merge(&ba).is_clean());
+        assert_eq!(sorted(&ab), before);
+    }
+}
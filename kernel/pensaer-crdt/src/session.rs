@@ -0,0 +1,113 @@
+//! A replica's editing session.
+//!
+//! A [`Session`] owns everything needed to stamp a new operation: the
+//! replica ID, its vector clock, and the wall-clock source. Making
+//! operations through one keeps the clock ticking once per operation and
+//! every timestamp coming from the same source, where
+//! [`Operation::with_clock_source`] leaves both to the caller.
+
+use crate::wall_clock::{Clock, SystemClock};
+use crate::{Operation, ReplicaId, VectorClock};
+
+/// Makes a replica's operations, timestamped by a [`Clock`] it owns.
+///
+/// The source defaults to the [`SystemClock`]; tests can hand in a
+/// [`ManualClock`](crate::ManualClock), or a reference to one shared by
+/// several sessions.
+#[derive(Debug)]
+pub struct Session<C: Clock = SystemClock> {
+    replica_id: ReplicaId,
+    clock: VectorClock,
+    source: C,
+}
+
+impl Session {
+    /// Start a session timestamped by the [`SystemClock`].
+    pub fn new(replica_id: ReplicaId) -> Self {
+        Self::with_clock_source(replica_id, SystemClock)
+    }
+}
+
+impl<C: Clock> Session<C> {
+    /// Start a session timestamped by `source`.
+    pub fn with_clock_source(replica_id: ReplicaId, source: C) -> Self {
+        Self {
+            replica_id,
+            clock: VectorClock::new(),
+            source,
+        }
+    }
+
+    /// Make the next operation: tick this replica's clock and stamp the
+    /// operation with it and the current wall time.
+    pub fn operation<P>(&mut self, id: impl Into<String>, op_type: P) -> Operation<P> {
+        self.clock.increment(&self.replica_id);
+        Operation::with_clock_source(
+            id,
+            op_type,
+            self.replica_id.clone(),
+            self.clock.clone(),
+            &self.source,
+        )
+    }
+
+    /// Take in the clock of operations received from other replicas, so
+    /// later operations causally follow them.
+    pub fn observe(&mut self, clock: &VectorClock) {
+        self.clock.merge(clock);
+    }
+
+    /// This session's replica.
+    pub fn replica_id(&self) -> &ReplicaId {
+        &self.replica_id
+    }
+
+    /// The clock of the latest operation made or observed.
+    pub fn clock(&self) -> &VectorClock {
+        &self.clock
+    }
+
+    /// The wall-clock source.
+    pub fn source(&self) -> &C {
+        &self.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ManualClock, OperationType};
+
+    fn delete(id: &str) -> OperationType {
+        OperationType::Delete {
+            element_id: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn operations_tick_the_clock_and_read_the_source() {
+        let time = ManualClock::new(1_000);
+        let mut alice = Session::with_clock_source(ReplicaId::new("alice"), &time);
+        let mut bob = Session::with_clock_source(ReplicaId::new("bob"), &time);
+
+        let first = alice.operation("op-1", delete("wall-1"));
+        time.advance(500);
+        let second = alice.operation("op-2", delete("wall-2"));
+        assert_eq!((first.wall_time, second.wall_time), (1_000, 1_500));
+        assert!(first.happened_before(&second));
+        assert_eq!(alice.clock().get(alice.replica_id()), 2);
+
+        let concurrent = bob.operation("op-3", delete("wall-3"));
+        assert!(concurrent.is_concurrent(&second));
+        bob.observe(&second.clock);
+        let after = bob.operation("op-4", delete("wall-4"));
+        assert!(second.happened_before(&after));
+    }
+
+    #[test]
+    fn default_source_is_system_time() {
+        let mut session = Session::new(ReplicaId::new("carol"));
+        let op = session.operation("op-now", delete("wall-3"));
+        assert!(op.wall_time > 1_600_000_000_000);
+    }
+}
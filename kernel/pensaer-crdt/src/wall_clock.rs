@@ -0,0 +1,73 @@
+//! Wall-clock sources for operation timestamps.
+//!
+//! Wall time only breaks ties between concurrent operations in
+//! [`OperationLog::operations_ordered`](crate::OperationLog::operations_ordered);
+//! causality always comes from the vector clock. Sources are injectable so
+//! tests can pin timestamps with a [`ManualClock`].
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A source of wall-clock time.
+pub trait Clock: Send + Sync {
+    /// Milliseconds since the Unix epoch.
+    fn now_millis(&self) -> u64;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now_millis(&self) -> u64 {
+        (**self).now_millis()
+    }
+}
+
+/// Latest time handed out by [`SystemClock`], so it never runs backwards.
+static LAST_SYSTEM_MILLIS: AtomicU64 = AtomicU64::new(0);
+
+/// The system clock.
+///
+/// Readings never decrease within a process, even if the system time is
+/// set back; they hold at the latest reading until the clock catches up.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let last = LAST_SYSTEM_MILLIS.fetch_max(now, Ordering::Relaxed);
+        now.max(last)
+    }
+}
+
+/// A clock that only moves when told to, for tests.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    millis: AtomicU64,
+}
+
+impl ManualClock {
+    /// Create a clock reading `millis`.
+    pub fn new(millis: u64) -> Self {
+        Self {
+            millis: AtomicU64::new(millis),
+        }
+    }
+
+    /// Set the reading.
+    pub fn set(&self, millis: u64) {
+        self.millis.store(millis, Ordering::Relaxed);
+    }
+
+    /// Move the reading forward.
+    pub fn advance(&self, millis: u64) {
+        self.millis.fetch_add(millis, Ordering::Relaxed);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_millis(&self) -> u64 {
+        self.millis.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,199 @@
+//! Synthetic fixtures for the benchmarks.
+//!
+//! Every generator takes a seed, so a benchmark measures the same model on
+//! every run and on every machine. Element models are in meters; topology
+//! graphs are in millimeters, like the kernel's wall network.
+
+use pensaer_geometry::elements::{OpeningType, Wall, WallOpening};
+use pensaer_geometry::spatial::{ClashElement, EdgeIndex};
+use pensaer_geometry::topology::{EdgeData, TopologyGraph};
+use pensaer_geometry::Element;
+use pensaer_math::Point2;
+
+/// Seed shared by the benchmarks. Change it only together with the baseline.
+pub const SEED: u64 = 0x5EED_BE4C;
+
+/// SplitMix64: tiny, fast, and reproducible without a dependency.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[lo, hi)`.
+    pub fn range(&mut self, lo: f64, hi: f64) -> f64 {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        lo + (hi - lo) * unit
+    }
+}
+
+/// A 3 m high, 0.2 m thick wall of the given length along +X.
+pub fn straight_wall(length: f64) -> Wall {
+    Wall::new(Point2::new(0.0, 0.0), Point2::new(length, 0.0), 3.0, 0.2).unwrap()
+}
+
+/// A 10 m wall with a door and two windows.
+pub fn wall_with_three_openings() -> Wall {
+    let mut wall = straight_wall(10.0);
+    for opening in [
+        WallOpening::new(2.0, 0.0, 0.9, 2.1, OpeningType::Door),
+        WallOpening::new(5.0, 0.9, 1.5, 1.2, OpeningType::Window),
+        WallOpening::new(8.0, 0.9, 1.5, 1.2, OpeningType::Window),
+    ] {
+        wall.add_opening(opening).unwrap();
+    }
+    wall
+}
+
+/// Openings of a wall as `(x, y, width, height)` cuts for
+/// [`extrude_wall_with_openings`](pensaer_geometry::extrude_wall_with_openings),
+/// which times the cutting alone, without `Wall::to_mesh`'s end caps and
+/// placement in world space.
+pub fn opening_cuts(wall: &Wall) -> Vec<(f64, f64, f64, f64)> {
+    wall.openings
+        .iter()
+        .map(|o| (o.start_offset(), o.base_height, o.width, o.height))
+        .collect()
+}
+
+/// `n` walls scattered over a 200 m square, 1-8 m long, about a third of
+/// them with a window.
+pub fn scattered_walls(n: usize, seed: u64) -> Vec<Wall> {
+    let mut rng = Rng::new(seed);
+    (0..n)
+        .map(|_| {
+            let start = Point2::new(rng.range(0.0, 200.0), rng.range(0.0, 200.0));
+            let angle = rng.range(0.0, std::f64::consts::TAU);
+            let length = rng.range(1.0, 8.0);
+            let end = Point2::new(
+                start.x + length * angle.cos(),
+                start.y + length * angle.sin(),
+            );
+            let mut wall = Wall::new(start, end, 3.0, rng.range(0.1, 0.3)).unwrap();
+            if length > 2.0 && rng.range(0.0, 3.0) < 1.0 {
+                let window = WallOpening::new(length / 2.0, 0.9, 1.0, 1.2, OpeningType::Window);
+                wall.add_opening(window).unwrap();
+            }
+            wall
+        })
+        .collect()
+}
+
+/// Wall segments of a `cols` x `rows` grid of rooms with jittered cell
+/// sizes, as (start, end) pairs in meters. Shared walls appear once.
+pub fn room_grid(cols: usize, rows: usize, seed: u64) -> Vec<(Point2, Point2)> {
+    let mut rng = Rng::new(seed);
+    let mut xs = vec![0.0];
+    for _ in 0..cols {
+        xs.push(xs.last().unwrap() + rng.range(3.0, 6.0));
+    }
+    let mut ys = vec![0.0];
+    for _ in 0..rows {
+        ys.push(ys.last().unwrap() + rng.range(3.0, 6.0));
+    }
+
+    let mut segments = Vec::new();
+    for &y in &ys {
+        for pair in xs.windows(2) {
+            segments.push((Point2::new(pair[0], y), Point2::new(pair[1], y)));
+        }
+    }
+    for &x in &xs {
+        for pair in ys.windows(2) {
+            segments.push((Point2::new(x, pair[0]), Point2::new(x, pair[1])));
+        }
+    }
+    segments
+}
+
+/// Walls along a [`room_grid`].
+pub fn room_grid_walls(cols: usize, rows: usize, seed: u64) -> Vec<Wall> {
+    room_grid(cols, rows, seed)
+        .into_iter()
+        .map(|(a, b)| Wall::new(a, b, 3.0, 0.2).unwrap())
+        .collect()
+}
+
+/// Topology graph of a [`room_grid`], in millimeters.
+pub fn room_grid_graph(cols: usize, rows: usize, seed: u64) -> TopologyGraph {
+    let mut graph = TopologyGraph::new();
+    for (a, b) in room_grid(cols, rows, seed) {
+        graph.add_edge(
+            [a.x * 1000.0, a.y * 1000.0],
+            [b.x * 1000.0, b.y * 1000.0],
+            EdgeData::wall(200.0, 3000.0),
+        );
+    }
+    graph
+}
+
+/// `n` horizontal and `n` vertical walls crossing each other, 1 m apart.
+/// Nothing is split yet: healing has `n * n` crossings to resolve.
+pub fn crossing_grid(n: usize) -> TopologyGraph {
+    let mut graph = TopologyGraph::new();
+    let span = (n + 1) as f64 * 1000.0;
+    for i in 1..=n {
+        let c = i as f64 * 1000.0;
+        graph.add_edge([0.0, c], [span, c], EdgeData::wall(200.0, 2700.0));
+        graph.add_edge([c, 0.0], [c, span], EdgeData::wall(200.0, 2700.0));
+    }
+    graph
+}
+
+/// `n` rows of `n - 1` walls, 1 m long, plus `n / 10` short walls that
+/// start 1 mm off a grid node. Snapping at 2 mm has one merge per short
+/// wall to find.
+pub fn snap_grid(n: usize) -> TopologyGraph {
+    let mut graph = TopologyGraph::with_tolerance(2.0);
+    for row in 0..n {
+        let y = row as f64 * 1000.0;
+        for col in 0..n - 1 {
+            let x = col as f64 * 1000.0;
+            graph.add_edge([x, y], [x + 1000.0, y], EdgeData::wall(200.0, 2700.0));
+        }
+    }
+    for k in 0..n / 10 {
+        let (col, row) = ((k * 10 * n / 3) % n, (k * 10 * n / 3) / n);
+        let (x, y) = (col as f64 * 1000.0, row as f64 * 1000.0);
+        graph.add_edge(
+            [x + 1.0, y],
+            [x + 1.0, y + 500.0],
+            EdgeData::wall(200.0, 2700.0),
+        );
+    }
+    graph
+}
+
+/// Edge index of the baselines of [`scattered_walls`], in millimeters.
+pub fn scattered_edge_index(n: usize, seed: u64) -> EdgeIndex {
+    let edges = scattered_walls(n, seed)
+        .iter()
+        .enumerate()
+        .map(|(i, wall)| {
+            let (a, b) = (wall.baseline.start, wall.baseline.end);
+            (
+                format!("e{}", i),
+                [a.x * 1000.0, a.y * 1000.0],
+                [b.x * 1000.0, b.y * 1000.0],
+            )
+        })
+        .collect();
+    EdgeIndex::bulk_load(edges)
+}
+
+/// Clash elements for the bounding boxes of [`scattered_walls`].
+pub fn clash_elements(n: usize, seed: u64) -> Vec<ClashElement> {
+    scattered_walls(n, seed)
+        .iter()
+        .map(|wall| ClashElement::new(wall.id, "Wall", wall.bounding_box().unwrap()))
+        .collect()
+}
@@ -0,0 +1,194 @@
+//! Kernel benchmark suite.
+//!
+//! Run with `cargo bench -p pensaer-geometry --bench kernel`, then compare
+//! against the committed baseline with `scripts/bench_gate.py` (see that
+//! script for re-recording). Add `--features parallel` to include the
+//! parallel batch meshing benchmark. Benchmark IDs are the keys of
+//! `benches/baseline.json`, so renaming one needs a baseline update.
+//!
+//! Two groups:
+//!
+//! - `targets`: one benchmark per published performance target in the
+//!   crate docs. The gate fails when these exceed their target, whatever
+//!   the baseline says.
+//! - `paths`: heavier paths the kernel relies on, gated only against the
+//!   baseline.
+//!
+//! All models come from the seeded generators in `fixtures`. To cover a
+//! new path, add a fixture there (or reuse one) and a function here, rather
+//! than timing the same operation under a second name.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+use pensaer_geometry::fixup::{heal_all, Delta};
+use pensaer_geometry::io::to_deterministic_json;
+use pensaer_geometry::joins::JoinDetector;
+use pensaer_geometry::spatial::{ClashDetector, SnapEngine, SnapGrid};
+use pensaer_geometry::{extrude_wall_with_openings, Element, Wall};
+use pensaer_math::Point2;
+
+mod fixtures;
+
+use fixtures::SEED;
+
+/// Published targets: wall creation, wall meshing with and without
+/// openings, room detection, join detection, nearest-edge queries, and
+/// cursor snapping.
+fn targets(c: &mut Criterion) {
+    let mut group = c.benchmark_group("targets");
+
+    // Target < 1 ms: validate and build one wall
+    group.bench_function("wall_creation", |b| {
+        b.iter(|| {
+            Wall::new(
+                black_box(Point2::new(0.0, 0.0)),
+                black_box(Point2::new(5.0, 0.0)),
+                3.0,
+                0.2,
+            )
+        })
+    });
+
+    // Target < 5 ms: box mesh of a plain wall
+    let wall = fixtures::straight_wall(5.0);
+    group.bench_function("wall_mesh", |b| b.iter(|| black_box(&wall).to_mesh()));
+
+    // Target < 10 ms: a wall with a door and two windows cut out, which
+    // triangulates the face around the holes
+    let wall = fixtures::wall_with_three_openings();
+    let cuts = fixtures::opening_cuts(&wall);
+    group.bench_function("wall_mesh_3_openings", |b| {
+        b.iter(|| {
+            extrude_wall_with_openings(wall.length(), wall.height, wall.thickness, black_box(&cuts))
+        })
+    });
+
+    // Target < 50 ms: full room rebuild of a 4 x 2 room grid. Its 22 walls
+    // are a little over the 20 in the target.
+    group.bench_function("room_detection_20_walls", |b| {
+        b.iter_batched(
+            || fixtures::room_grid_graph(4, 2, SEED),
+            |mut graph| graph.rebuild_rooms(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    // Target < 10 ms: joins of a row of 3 rooms (10 walls)
+    let walls = fixtures::room_grid_walls(3, 1, SEED);
+    let refs: Vec<&Wall> = walls.iter().collect();
+    let detector = JoinDetector::new(0.001, 0.1);
+    group.bench_function("join_detection_10_walls", |b| {
+        b.iter(|| detector.detect_all(black_box(&refs)))
+    });
+
+    // Target < 1 ms: the 3 wall edges nearest a cursor among 10,000
+    let index = fixtures::scattered_edge_index(10_000, SEED);
+    group.bench_function("nearest_edge_10k", |b| {
+        b.iter(|| index.nearest(black_box([100_000.0, 100_000.0]), 3).len())
+    });
+
+    // Target < 1 ms: one mouse move over a 50 x 50 room grid (5,100 walls)
+    let graph = fixtures::room_grid_graph(50, 50, SEED);
+    let engine = SnapEngine::new().with_grid(SnapGrid::new(500.0));
+    group.bench_function("snap_point_5k_edges", |b| {
+        b.iter(|| engine.snap(&graph, black_box([112_503.0, 112_497.0])))
+    });
+
+    group.finish();
+}
+
+/// Heavier paths: healing, snap merging, batch meshing, clash detection,
+/// and output.
+fn paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("paths");
+    group.sample_size(10);
+
+    // Snap, split 2,500 crossings, merge, and rebuild rooms from scratch
+    group.bench_function("heal_all_grid_50x50", |b| {
+        b.iter_batched(
+            || fixtures::crossing_grid(50),
+            |mut graph| heal_all(&mut graph, &Delta::new()),
+            BatchSize::LargeInput,
+        )
+    });
+
+    // Merge the nodes of a 100 x 100 grid within the snap tolerance
+    group.bench_function("snap_merge_grid_100x100", |b| {
+        b.iter_batched(
+            || fixtures::snap_grid(100),
+            |mut graph| graph.snap_merge_nodes(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    // Mesh 1,000 scattered walls, cutting the window in a third of them
+    let walls: Vec<(Wall, Vec<_>)> = fixtures::scattered_walls(1_000, SEED)
+        .into_iter()
+        .map(|w| {
+            let cuts = fixtures::opening_cuts(&w);
+            (w, cuts)
+        })
+        .collect();
+    group.bench_function("mesh_1k_walls", |b| {
+        b.iter(|| {
+            walls
+                .iter()
+                .map(|(w, cuts)| {
+                    let mesh = if cuts.is_empty() {
+                        w.to_mesh()
+                    } else {
+                        extrude_wall_with_openings(w.length(), w.height, w.thickness, cuts)
+                    };
+                    mesh.unwrap().triangle_count()
+                })
+                .sum::<usize>()
+        })
+    });
+
+    // Mesh 500 scattered walls on every core and merge them into one mesh;
+    // should stay under 250 ms on 8 cores
+    #[cfg(feature = "parallel")]
+    {
+        use pensaer_geometry::mesh::{generate_meshes, merge_element_meshes};
+
+        let walls = fixtures::scattered_walls(500, SEED);
+        let elements: Vec<&dyn Element> = walls.iter().map(|w| w as &dyn Element).collect();
+        group.bench_function("batch_mesh_500_walls", |b| {
+            b.iter(|| {
+                let meshes = generate_meshes(black_box(&elements));
+                merge_element_meshes(
+                    walls
+                        .iter()
+                        .zip(&meshes)
+                        .map(|(w, m)| (w.id, m.as_ref().unwrap())),
+                )
+                .triangle_count()
+            })
+        });
+    }
+
+    // Alignment guide: first of 10,000 edges hit by a ray across the site
+    let index = fixtures::scattered_edge_index(10_000, SEED);
+    group.bench_function("raycast_10k_edges", |b| {
+        b.iter(|| index.raycast(black_box([0.0, 100_000.0]), [1.0, 0.1], 200_000.0))
+    });
+
+    // Broad phase over 500 wall bounding boxes
+    let elements = fixtures::clash_elements(500, SEED);
+    let detector = ClashDetector::new(0.001);
+    group.bench_function("clash_500_elements", |b| {
+        b.iter(|| detector.detect_clashes_in_list(black_box(&elements)))
+    });
+
+    // Quantize and serialize a 10,000-wall document
+    let walls = fixtures::scattered_walls(10_000, SEED);
+    let document = serde_json::json!({ "walls": walls });
+    group.bench_function("deterministic_json_10k", |b| {
+        b.iter(|| to_deterministic_json(black_box(&document)).len())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, targets, paths);
+criterion_main!(benches);
@@ -0,0 +1,7 @@
+//! Model analysis and reporting.
+//!
+//! - `quantities` - Quantity takeoff per element and per element type
+
+pub mod quantities;
+
+pub use quantities::{quantities_for, ElementQuantities, QuantityReport, QuantityRollup};
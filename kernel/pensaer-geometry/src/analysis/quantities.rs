@@ -0,0 +1,431 @@
+//! Quantity takeoff: gross and net areas and volumes per element.
+//!
+//! Every element gets the same set of quantities; the ones that don't apply
+//! to its type are zero:
+//!
+//! - Walls: length, one side face as gross area, the face less its openings
+//!   as net area, and volume = net area × thickness
+//! - Floors: boundary area as gross, less holes as net, perimeter, and
+//!   volume = net area × thickness
+//! - Roofs: sloped surface area, perimeter, and volume = area × thickness
+//! - Rooms: floor area, perimeter, surface area of the enclosing walls
+//!   (perimeter × height), and volume
+//! - Doors and windows: opening area
+//!
+//! Only wall openings lying fully inside the wall face are subtracted, so
+//! an opening hanging past a wall end or above its top does not reduce the
+//! net area.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::element::ElementType;
+use crate::elements::{Building, Door, Floor, Roof, Room, Wall, Window};
+use crate::io::to_plain_decimal_json;
+use crate::report::{NumberFormat, QuantityKind};
+
+/// Tolerance when checking that an opening lies inside its wall face.
+const FACE_TOLERANCE: f64 = 1e-9;
+
+/// Element types covered by the takeoff, in report order.
+const REPORT_TYPES: [ElementType; 6] = [
+    ElementType::Wall,
+    ElementType::Floor,
+    ElementType::Roof,
+    ElementType::Room,
+    ElementType::Door,
+    ElementType::Window,
+];
+
+/// Quantities of one element.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElementQuantities {
+    /// ID of the element.
+    pub element_id: Uuid,
+    /// Type of the element.
+    pub element_type: ElementType,
+    /// Length in meters.
+    pub length: f64,
+    /// Gross area in square meters.
+    pub gross_area: f64,
+    /// Net area in square meters.
+    pub net_area: f64,
+    /// Perimeter in meters.
+    pub perimeter: f64,
+    /// Surface area of a room's enclosing walls in square meters.
+    pub wall_area: f64,
+    /// Volume in cubic meters.
+    pub volume: f64,
+}
+
+impl ElementQuantities {
+    fn new(element_id: Uuid, element_type: ElementType) -> Self {
+        Self {
+            element_id,
+            element_type,
+            length: 0.0,
+            gross_area: 0.0,
+            net_area: 0.0,
+            perimeter: 0.0,
+            wall_area: 0.0,
+            volume: 0.0,
+        }
+    }
+
+    /// Quantities of a wall.
+    pub fn wall(wall: &Wall) -> Self {
+        let length = wall.length();
+        let gross_area = length * wall.height;
+        let opening_area: f64 = wall
+            .openings
+            .iter()
+            .filter(|o| {
+                o.start_offset() >= -FACE_TOLERANCE
+                    && o.end_offset() <= length + FACE_TOLERANCE
+                    && o.base_height >= -FACE_TOLERANCE
+                    && o.top_height() <= wall.height + FACE_TOLERANCE
+            })
+            .map(|o| o.width * o.height)
+            .sum();
+        let net_area = gross_area - opening_area;
+        Self {
+            length,
+            gross_area,
+            net_area,
+            volume: net_area * wall.thickness,
+            ..Self::new(wall.id, ElementType::Wall)
+        }
+    }
+
+    /// Quantities of a floor slab.
+    pub fn floor(floor: &Floor) -> Self {
+        let net_area = floor.area();
+        Self {
+            gross_area: floor.boundary.area(),
+            net_area,
+            perimeter: floor.perimeter(),
+            volume: net_area * floor.thickness,
+            ..Self::new(floor.id, ElementType::Floor)
+        }
+    }
+
+    /// Quantities of a roof.
+    pub fn roof(roof: &Roof) -> Self {
+        let area = roof.surface_area();
+        Self {
+            gross_area: area,
+            net_area: area,
+            perimeter: roof.perimeter(),
+            volume: area * roof.thickness,
+            ..Self::new(roof.id, ElementType::Roof)
+        }
+    }
+
+    /// Quantities of a room.
+    pub fn room(room: &Room) -> Self {
+        let area = room.area();
+        let perimeter = room.perimeter();
+        Self {
+            gross_area: area,
+            net_area: area,
+            perimeter,
+            wall_area: perimeter * room.height,
+            volume: room.volume(),
+            ..Self::new(room.id, ElementType::Room)
+        }
+    }
+
+    /// Quantities of a door.
+    pub fn door(door: &Door) -> Self {
+        let area = door.width * door.height;
+        Self {
+            gross_area: area,
+            net_area: area,
+            ..Self::new(door.id, ElementType::Door)
+        }
+    }
+
+    /// Quantities of a window.
+    pub fn window(window: &Window) -> Self {
+        let area = window.width * window.height;
+        Self {
+            gross_area: area,
+            net_area: area,
+            ..Self::new(window.id, ElementType::Window)
+        }
+    }
+
+    /// Values in column order, with the kind of each.
+    fn columns(&self) -> [(&'static str, f64, QuantityKind); 6] {
+        [
+            ("length", self.length, QuantityKind::Length),
+            ("gross_area", self.gross_area, QuantityKind::Area),
+            ("net_area", self.net_area, QuantityKind::Area),
+            ("perimeter", self.perimeter, QuantityKind::Length),
+            ("wall_area", self.wall_area, QuantityKind::Area),
+            ("volume", self.volume, QuantityKind::Volume),
+        ]
+    }
+}
+
+/// Summed quantities of all elements of one type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuantityRollup {
+    /// Element type summed.
+    pub element_type: ElementType,
+    /// Number of elements.
+    pub count: usize,
+    /// Total length in meters.
+    pub length: f64,
+    /// Total gross area in square meters.
+    pub gross_area: f64,
+    /// Total net area in square meters.
+    pub net_area: f64,
+    /// Total perimeter in meters.
+    pub perimeter: f64,
+    /// Total room wall surface area in square meters.
+    pub wall_area: f64,
+    /// Total volume in cubic meters.
+    pub volume: f64,
+}
+
+/// Quantity takeoff of a set of elements.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuantityReport {
+    /// Per-element quantities, grouped by type in report order.
+    pub elements: Vec<ElementQuantities>,
+}
+
+impl QuantityReport {
+    /// Quantities of one element, if it is in the report.
+    pub fn element(&self, id: Uuid) -> Option<&ElementQuantities> {
+        self.elements.iter().find(|q| q.element_id == id)
+    }
+
+    /// Unrounded totals for one element type.
+    pub fn rollup(&self, element_type: ElementType) -> QuantityRollup {
+        let mut rollup = QuantityRollup {
+            element_type,
+            count: 0,
+            length: 0.0,
+            gross_area: 0.0,
+            net_area: 0.0,
+            perimeter: 0.0,
+            wall_area: 0.0,
+            volume: 0.0,
+        };
+        for q in self
+            .elements
+            .iter()
+            .filter(|q| q.element_type == element_type)
+        {
+            rollup.count += 1;
+            rollup.length += q.length;
+            rollup.gross_area += q.gross_area;
+            rollup.net_area += q.net_area;
+            rollup.perimeter += q.perimeter;
+            rollup.wall_area += q.wall_area;
+            rollup.volume += q.volume;
+        }
+        rollup
+    }
+
+    /// Totals for each element type present, in report order.
+    pub fn rollups(&self) -> Vec<QuantityRollup> {
+        REPORT_TYPES
+            .iter()
+            .map(|t| self.rollup(*t))
+            .filter(|r| r.count > 0)
+            .collect()
+    }
+
+    /// Export as a JSON value with rounded numbers.
+    ///
+    /// Type totals follow the format's totals policy, so by default they add
+    /// up the rounded element values exactly.
+    pub fn to_json(&self, format: &NumberFormat) -> Value {
+        let elements: Vec<Value> = self
+            .elements
+            .iter()
+            .map(|q| {
+                let mut row = json!({
+                    "id": q.element_id.to_string(),
+                    "type": q.element_type.name(),
+                });
+                for (name, value, kind) in q.columns() {
+                    row[name] = json!(format.round(value, kind));
+                }
+                row
+            })
+            .collect();
+
+        let mut by_type = serde_json::Map::new();
+        for element_type in REPORT_TYPES {
+            let rows: Vec<&ElementQuantities> = self
+                .elements
+                .iter()
+                .filter(|q| q.element_type == element_type)
+                .collect();
+            if rows.is_empty() {
+                continue;
+            }
+            let mut totals = json!({ "count": rows.len() });
+            for (i, (name, _, kind)) in rows[0].columns().into_iter().enumerate() {
+                let values = rows.iter().map(|q| q.columns()[i].1);
+                totals[name] = json!(format.total(values, kind));
+            }
+            by_type.insert(element_type.name().to_string(), totals);
+        }
+
+        json!({
+            "elements": elements,
+            "by_type": by_type,
+            "totals_policy": format.totals,
+        })
+    }
+
+    /// Serialize to deterministic JSON with plain decimal numbers.
+    pub fn to_deterministic_json(&self, format: &NumberFormat) -> String {
+        to_plain_decimal_json(&self.to_json(format))
+    }
+}
+
+/// Take off quantities for the walls, floors, roofs, rooms, doors, and
+/// windows of a building.
+pub fn quantities_for(building: &Building) -> QuantityReport {
+    let elements = building
+        .walls
+        .iter()
+        .map(ElementQuantities::wall)
+        .chain(building.floors.iter().map(ElementQuantities::floor))
+        .chain(building.roofs.iter().map(ElementQuantities::roof))
+        .chain(building.rooms.iter().map(ElementQuantities::room))
+        .chain(building.doors.iter().map(ElementQuantities::door))
+        .chain(building.windows.iter().map(ElementQuantities::window))
+        .collect();
+    QuantityReport { elements }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{OpeningType, WallOpening};
+    use pensaer_math::Point2;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    /// 10 m x 8 m box: four 3 m walls 0.2 m thick, a 0.25 m slab, one room,
+    /// a door and a window in the south wall.
+    fn sample_building() -> (Building, Uuid) {
+        let mut building = Building::new("Sample");
+        let corners = [
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 8.0),
+            Point2::new(0.0, 8.0),
+        ];
+        let mut south = Uuid::nil();
+        for i in 0..4 {
+            let mut wall = Wall::new(corners[i], corners[(i + 1) % 4], 3.0, 0.2).unwrap();
+            if i == 0 {
+                south = wall.id;
+                let door = Door::new(wall.id, 0.9, 2.1, 3.0).unwrap();
+                let window = Window::new(wall.id, 1.5, 1.2, 0.9, 7.0).unwrap();
+                let mut opening = WallOpening::new(3.0, 0.0, 0.9, 2.1, OpeningType::Door);
+                opening.hosted_element_id = Some(door.id);
+                wall.add_opening(opening).unwrap();
+                let mut opening = WallOpening::new(7.0, 0.9, 1.5, 1.2, OpeningType::Window);
+                opening.hosted_element_id = Some(window.id);
+                wall.add_opening(opening).unwrap();
+                building.add_door(door);
+                building.add_window(window);
+            }
+            building.add_wall(wall);
+        }
+        building.add_floor(Floor::rectangle(corners[0], corners[2], 0.25).unwrap());
+        building.add_room(Room::rectangle("Hall", "1", corners[0], corners[2], 3.0).unwrap());
+        (building, south)
+    }
+
+    #[test]
+    fn sample_building_quantities() {
+        let (building, south) = sample_building();
+        let report = quantities_for(&building);
+        assert_eq!(report.elements.len(), 8);
+
+        // South wall: 30 m² face less 1.89 m² door and 1.8 m² window
+        let wall = report.element(south).unwrap();
+        assert_close(wall.length, 10.0);
+        assert_close(wall.gross_area, 30.0);
+        assert_close(wall.net_area, 26.31);
+        assert_close(wall.volume, 5.262);
+
+        // Walls: 2 x 30 + 2 x 24 = 108 m² gross, 104.31 m² net
+        let walls = report.rollup(ElementType::Wall);
+        assert_eq!(walls.count, 4);
+        assert_close(walls.length, 36.0);
+        assert_close(walls.gross_area, 108.0);
+        assert_close(walls.net_area, 104.31);
+        assert_close(walls.volume, 20.862);
+
+        let floor = report.rollup(ElementType::Floor);
+        assert_close(floor.net_area, 80.0);
+        assert_close(floor.perimeter, 36.0);
+        assert_close(floor.volume, 20.0);
+
+        let room = report.rollup(ElementType::Room);
+        assert_close(room.gross_area, 80.0);
+        assert_close(room.wall_area, 108.0);
+        assert_close(room.volume, 240.0);
+
+        let doors = report.rollup(ElementType::Door);
+        assert_eq!(doors.count, 1);
+        assert_close(doors.gross_area, 1.89);
+        let windows = report.rollup(ElementType::Window);
+        assert_eq!(windows.count, 1);
+        assert_close(windows.gross_area, 1.8);
+
+        let types: Vec<ElementType> = report.rollups().iter().map(|r| r.element_type).collect();
+        assert_eq!(
+            types,
+            [
+                ElementType::Wall,
+                ElementType::Floor,
+                ElementType::Room,
+                ElementType::Door,
+                ElementType::Window
+            ]
+        );
+
+        let json = report.to_deterministic_json(&NumberFormat::default());
+        assert!(json.contains(r#""Wall":{"count":4,"gross_area":108,"length":36,"net_area":104.31,"perimeter":0,"volume":20.862,"wall_area":0}"#));
+        assert_eq!(json, report.to_deterministic_json(&NumberFormat::default()));
+    }
+
+    #[test]
+    fn openings_outside_the_face_are_not_subtracted() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), 3.0, 0.2).unwrap();
+        // Inside, touching the wall end
+        wall.openings
+            .push(WallOpening::new(3.5, 0.0, 1.0, 2.0, OpeningType::Door));
+        // Past the wall start
+        wall.openings
+            .push(WallOpening::new(0.25, 1.0, 1.0, 1.0, OpeningType::Window));
+        // Above the wall top
+        wall.openings
+            .push(WallOpening::new(2.0, 2.5, 1.0, 1.0, OpeningType::Generic));
+
+        let q = ElementQuantities::wall(&wall);
+        assert_close(q.gross_area, 12.0);
+        assert_close(q.net_area, 10.0);
+        assert_close(q.volume, 2.0);
+    }
+}
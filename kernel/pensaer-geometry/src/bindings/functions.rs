@@ -3,18 +3,43 @@
 //! These functions provide a high-level API for creating and manipulating
 //! BIM elements from Python, designed for use with MCP tool servers.
 
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use std::collections::{HashMap, HashSet};
+
+use pyo3::exceptions::{PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
 use pyo3::IntoPy;
 
-use crate::elements::{OpeningType, Wall, WallOpening};
-use crate::joins::JoinResolver;
-use crate::mesh::TriangleMesh;
+use pensaer_math::{BoundingBox3, Line2, LineSegment2, Point2, Point3, Vector2, Vector3};
+
+use crate::catalog::Catalog;
+use crate::edit::{self, AlignAxis, EditReport};
+use crate::element::{set_id_mode, PlanTransform, TransformableElement};
+use crate::elements::{
+    floors_from_rooms, offset_walls, wall_graph, Ceiling, ContextMesh, ContextMeshOptions,
+    OffsetSide, OpeningType, RailProfile, Railing, Roof, RoofType, Room, SplitPolicy, Wall,
+    WallOpening, Window,
+};
+use crate::error::GeometryResult;
+use crate::fixup::{self, Delta};
+use crate::ids::{IdMode, ELEMENT_ID_NAMESPACE};
+use crate::io::{self, model::ModelDocument};
+use crate::joins::{JoinResolver, JoinType, TrimKind, TrimPolicy, WallEnd};
+use crate::mesh::{pick_mesh, section_building, Ray, RayHit, SectionPlane, TriangleMesh};
+use crate::properties::PropertySchemaRegistry;
+use crate::spatial::{Clash, ClashDetector, ClashElement, ClashFilter, SnapEngine, SnapGrid};
+use crate::templates::{self, TemplatePlacement};
+use crate::topology::corridor::{self, classify_corridors, CirculationNodeKind, CorridorCriteria};
 use crate::topology::{EdgeData, TopologyGraph};
+use crate::validation;
 
+#[cfg(feature = "parallel")]
+use super::types::PyMeshWorker;
 use super::types::{
-    PyDoor, PyFloor, PyRoof, PyRoom, PyTriangleMesh, PyWall, PyWallJoin, PyWallOpening, PyWindow,
+    geometry_error, join_type_name, parse_uuids, set_property_schema, PyBuilding, PyCatalog,
+    PyCeiling, PyContextMesh, PyDoor, PyFloor, PyLevel, PyModel, PyRailing, PyRoof, PyRoofOpening,
+    PyRoom, PySkylight, PyStair, PyTemplate, PyTopologyGraph, PyTriangleMesh, PyWall, PyWallJoin,
+    PyWallOpening, PyWindow,
 };
 
 /// Create a new wall element.
@@ -71,6 +96,47 @@ pub fn create_floor(
     PyFloor::rectangle(min_point, max_point, thickness, floor_type)
 }
 
+/// Create one floor slab per room enclosed by a set of walls.
+///
+/// Rooms are detected from the wall baselines, so each slab spans to the
+/// wall centerlines and sits at elevation 0. Rooms too small to floor are
+/// skipped and reported in 'warnings'.
+///
+/// Args:
+///     walls: Walls enclosing the rooms
+///     thickness: Floor thickness
+///
+/// Returns:
+///     dict: Contains 'floors' (list of PyFloor), 'room_floors' mapping each
+///         room ID to its floor ID, and 'warnings' for skipped rooms
+///
+/// Example:
+///     >>> walls = create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
+///     >>> result = create_floors_from_walls(walls, 0.25)
+///     >>> result['floors'][0].area()
+///     80.0
+#[pyfunction]
+pub fn create_floors_from_walls(walls: Vec<PyWall>, thickness: f64) -> PyResult<Py<PyDict>> {
+    let walls: Vec<&Wall> = walls.iter().map(|w| &w.inner).collect();
+    let graph = wall_graph(&walls);
+    let result = floors_from_rooms(&graph, thickness).map_err(geometry_error)?;
+
+    Python::with_gil(|py| {
+        let room_floors = PyDict::new_bound(py);
+        let mut floors = Vec::with_capacity(result.floors.len());
+        for (room_id, floor) in result.floors {
+            room_floors.set_item(room_id.0.to_string(), floor.id.to_string())?;
+            floors.push(PyFloor { inner: floor }.into_py(py));
+        }
+
+        let dict = PyDict::new_bound(py);
+        dict.set_item("floors", floors)?;
+        dict.set_item("room_floors", room_floors)?;
+        dict.set_item("warnings", result.warnings)?;
+        Ok(dict.unbind())
+    })
+}
+
 /// Create a rectangular room element.
 ///
 /// Args:
@@ -99,17 +165,102 @@ pub fn create_room(
     PyRoom::rectangle(name, number, min_point, max_point, height)
 }
 
+/// Create a room from an arbitrary boundary polygon.
+///
+/// Args:
+///     name: Room name (e.g., "Living Room")
+///     number: Room number (e.g., "101")
+///     boundary: Boundary vertices as a list of (x, y) tuples, in either winding
+///     height: Room height
+///
+/// Returns:
+///     PyRoom: The created room element
+///
+/// Example:
+///     >>> room = create_room_from_boundary(
+///     ...     "Lounge", "109", [(0, 0), (6, 0), (6, 2), (2, 2), (2, 5), (0, 5)], height=2.5
+///     ... )
+///     >>> room.area()
+///     18.0
+#[pyfunction]
+#[pyo3(signature = (name, number, boundary, height))]
+pub fn create_room_from_boundary(
+    name: &str,
+    number: &str,
+    boundary: Vec<(f64, f64)>,
+    height: f64,
+) -> PyResult<PyRoom> {
+    PyRoom::from_boundary(name, number, boundary, height)
+}
+
+/// Create a ceiling covering a room.
+///
+/// The ceiling takes the room boundary and level; its underside sits
+/// `offset` below the top of the room.
+///
+/// Args:
+///     room: The room to cover
+///     offset: Distance from the room top down to the ceiling underside
+///     thickness: Ceiling thickness
+///
+/// Returns:
+///     PyCeiling: The created ceiling element
+///
+/// Example:
+///     >>> room = create_room("Kitchen", "102", (0, 0), (4, 3), height=2.7)
+///     >>> ceiling = create_ceiling_from_room(room, offset=0.3, thickness=0.02)
+///     >>> ceiling.elevation
+///     2.4
+#[pyfunction]
+#[pyo3(signature = (room, offset, thickness))]
+pub fn create_ceiling_from_room(room: &PyRoom, offset: f64, thickness: f64) -> PyResult<PyCeiling> {
+    Ceiling::from_room(&room.inner, offset, thickness)
+        .map(|c| PyCeiling { inner: c })
+        .map_err(geometry_error)
+}
+
+/// Check that an element is given either explicit dimensions or a catalog
+/// type, and look the type up.
+fn catalog_type<'a, T>(
+    kind: &str,
+    type_name: Option<&str>,
+    catalog: Option<&'a PyCatalog>,
+    explicit: bool,
+    lookup: impl FnOnce(&'a PyCatalog, &str) -> Option<&'a T>,
+) -> PyResult<Option<&'a T>> {
+    let Some(name) = type_name else {
+        return Ok(None);
+    };
+    if explicit {
+        return Err(PyValueError::new_err(format!(
+            "give either {} dimensions or a type_name, not both",
+            kind
+        )));
+    }
+    let catalog =
+        catalog.ok_or_else(|| PyValueError::new_err("type_name needs a catalog".to_string()))?;
+    lookup(catalog, name)
+        .map(Some)
+        .ok_or_else(|| PyValueError::new_err(format!("unknown {} type: {}", kind, name)))
+}
+
 /// Place a door in a wall.
 ///
 /// This function creates both a door element and adds an opening to the wall.
+/// The door is sized either by explicit dimensions or by a catalog type.
 ///
 /// Args:
 ///     wall: The wall to place the door in (will be modified)
 ///     offset: Distance from wall start to door center
-///     width: Door width
-///     height: Door height
+///     width: Door width (omit when using type_name)
+///     height: Door height (omit when using type_name)
 ///     door_type: Optional door type ("single", "double", "sliding", "folding", "revolving")
 ///     swing: Optional swing direction ("left", "right", "both", "none")
+///     opening_side: Optional side of the wall the door opens to ("left" of the
+///         wall direction, the default, or "right")
+///     type_name: Optional catalog door type supplying the size, door type,
+///         swing, and frame
+///     catalog: Catalog to look type_name up in
 ///
 /// Returns:
 ///     dict: Contains 'door' (PyDoor) and 'opening' (PyWallOpening)
@@ -118,31 +269,65 @@ pub fn create_room(
 ///     >>> wall = create_wall((0, 0), (5, 0), 3.0, 0.2)
 ///     >>> result = place_door(wall, offset=2.5, width=0.9, height=2.1)
 ///     >>> door = result['door']
+///     >>> typed = place_door(wall, offset=1.0, type_name="Door 838x1981",
+///     ...                    catalog=default_catalog())['door']
 #[pyfunction]
-#[pyo3(signature = (wall, offset, width, height, door_type=None, swing=None))]
+#[pyo3(signature = (wall, offset, width=None, height=None, door_type=None, swing=None, opening_side=None, type_name=None, catalog=None))]
+#[allow(clippy::too_many_arguments)]
 pub fn place_door(
     wall: &mut PyWall,
     offset: f64,
-    width: f64,
-    height: f64,
+    width: Option<f64>,
+    height: Option<f64>,
     door_type: Option<&str>,
     swing: Option<&str>,
+    opening_side: Option<&str>,
+    type_name: Option<&str>,
+    catalog: Option<&PyCatalog>,
 ) -> PyResult<Py<PyDict>> {
+    let explicit = width.is_some() || height.is_some() || door_type.is_some() || swing.is_some();
+    let def = catalog_type("door", type_name, catalog, explicit, |c, name| {
+        c.inner.door_type(name)
+    })?;
+
+    // Create door element
+    let door = match def {
+        Some(def) => {
+            let mut door = PyDoor::new(
+                &wall.inner.id.to_string(),
+                def.width,
+                def.height,
+                offset,
+                None,
+                None,
+                opening_side,
+            )?;
+            door.inner.apply_type(def).map_err(geometry_error)?;
+            door
+        }
+        None => {
+            let (Some(width), Some(height)) = (width, height) else {
+                return Err(PyValueError::new_err(
+                    "place_door needs a width and height, or a type_name".to_string(),
+                ));
+            };
+            PyDoor::new(
+                &wall.inner.id.to_string(),
+                width,
+                height,
+                offset,
+                door_type,
+                swing,
+                opening_side,
+            )?
+        }
+    };
+
     // Create opening in wall
-    let opening = WallOpening::new(offset, 0.0, width, height, OpeningType::Door);
+    let opening = door.inner.wall_opening();
     wall.inner
         .add_opening(opening.clone())
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
-
-    // Create door element
-    let door = PyDoor::new(
-        &wall.inner.id.to_string(),
-        width,
-        height,
-        offset,
-        door_type,
-        swing,
-    )?;
+        .map_err(geometry_error)?;
 
     // Return both as dict
     Python::with_gil(|py| {
@@ -157,14 +342,18 @@ pub fn place_door(
 /// Place a window in a wall.
 ///
 /// This function creates both a window element and adds an opening to the wall.
+/// The window is sized either by explicit dimensions or by a catalog type.
 ///
 /// Args:
 ///     wall: The wall to place the window in (will be modified)
 ///     offset: Distance from wall start to window center
-///     width: Window width
-///     height: Window height
-///     sill_height: Height from floor to window sill
+///     width: Window width (omit when using type_name)
+///     height: Window height (omit when using type_name)
+///     sill_height: Height from floor to window sill (omit when using type_name)
 ///     window_type: Optional window type ("fixed", "casement", "double_hung", "sliding", "awning")
+///     type_name: Optional catalog window type supplying the size, sill
+///         height, and window type
+///     catalog: Catalog to look type_name up in
 ///
 /// Returns:
 ///     dict: Contains 'window' (PyWindow) and 'opening' (PyWallOpening)
@@ -174,30 +363,54 @@ pub fn place_door(
 ///     >>> result = place_window(wall, offset=1.0, width=1.2, height=1.0, sill_height=0.9)
 ///     >>> window = result['window']
 #[pyfunction]
-#[pyo3(signature = (wall, offset, width, height, sill_height, window_type=None))]
+#[pyo3(signature = (wall, offset, width=None, height=None, sill_height=None, window_type=None, type_name=None, catalog=None))]
+#[allow(clippy::too_many_arguments)]
 pub fn place_window(
     wall: &mut PyWall,
     offset: f64,
-    width: f64,
-    height: f64,
-    sill_height: f64,
+    width: Option<f64>,
+    height: Option<f64>,
+    sill_height: Option<f64>,
     window_type: Option<&str>,
+    type_name: Option<&str>,
+    catalog: Option<&PyCatalog>,
 ) -> PyResult<Py<PyDict>> {
+    let explicit =
+        width.is_some() || height.is_some() || sill_height.is_some() || window_type.is_some();
+    let def = catalog_type("window", type_name, catalog, explicit, |c, name| {
+        c.inner.window_type(name)
+    })?;
+
+    // Create window element
+    let window = match def {
+        Some(def) => {
+            let window = Window::from_type(def, wall.inner.id, offset).map_err(geometry_error)?;
+            PyWindow { inner: window }
+        }
+        None => {
+            let (Some(width), Some(height), Some(sill_height)) = (width, height, sill_height)
+            else {
+                return Err(PyValueError::new_err(
+                    "place_window needs a width, height, and sill_height, or a type_name"
+                        .to_string(),
+                ));
+            };
+            PyWindow::new(
+                &wall.inner.id.to_string(),
+                width,
+                height,
+                sill_height,
+                offset,
+                window_type,
+            )?
+        }
+    };
+
     // Create opening in wall
-    let opening = WallOpening::new(offset, sill_height, width, height, OpeningType::Window);
+    let opening = window.inner.wall_opening();
     wall.inner
         .add_opening(opening.clone())
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
-
-    // Create window element
-    let window = PyWindow::new(
-        &wall.inner.id.to_string(),
-        width,
-        height,
-        sill_height,
-        offset,
-        window_type,
-    )?;
+        .map_err(geometry_error)?;
 
     // Return both as dict
     Python::with_gil(|py| {
@@ -209,6 +422,43 @@ pub fn place_window(
     })
 }
 
+/// Load a door and window type catalog from JSON.
+///
+/// Args:
+///     json: JSON text with optional "doors" and "windows" lists. Door types
+///         have "name", "width", "height", and optional "door_type", "swing"
+///         and "frame_depth"; window types have "name", "width", "height",
+///         "sill_height", and optional "window_type"
+///
+/// Returns:
+///     PyCatalog: The loaded catalog
+///
+/// Example:
+///     >>> catalog = load_catalog('{"doors": [{"name": "D1", "width": 0.9, "height": 2.1}]}')
+///     >>> catalog.door_types
+///     ['D1']
+#[pyfunction]
+pub fn load_catalog(json: &str) -> PyResult<PyCatalog> {
+    Catalog::from_json(json)
+        .map(|inner| PyCatalog { inner })
+        .map_err(geometry_error)
+}
+
+/// A catalog of standard door and window sizes.
+///
+/// Returns:
+///     PyCatalog: 762, 838, and 926 mm single doors and three casement windows
+///
+/// Example:
+///     >>> default_catalog().door_types
+///     ['Door 762x1981', 'Door 838x1981', 'Door 926x2040']
+#[pyfunction]
+pub fn default_catalog() -> PyCatalog {
+    PyCatalog {
+        inner: Catalog::standard(),
+    }
+}
+
 /// Detect joins between walls.
 ///
 /// Analyzes a set of walls and detects where they meet, classifying
@@ -250,21 +500,26 @@ pub fn detect_joins(walls: Vec<PyWall>, tolerance: f64) -> Vec<PyWallJoin> {
 ///     walls: Walls participating in the join
 ///     join: The detected wall join
 ///     tolerance: Distance tolerance (default 0.001)
+///     heal_openings: Shrink openings reaching into the join's trimmed wall
+///         ends instead of raising (default False)
 ///
 /// Returns:
-///     dict: Join geometry data including modified wall profiles
+///     dict: Join geometry data including modified wall profiles, and
+///         'opening_adjustments' listing openings shrunk to clear the join.
+///         The adjustments are not applied to the walls.
 ///
 /// Example:
 ///     >>> joins = detect_joins([wall1, wall2])
 ///     >>> geometry = compute_join_geometry([wall1, wall2], joins[0])
 #[pyfunction]
-#[pyo3(signature = (walls, join, tolerance=0.001))]
+#[pyo3(signature = (walls, join, tolerance=0.001, heal_openings=false))]
 pub fn compute_join_geometry(
     walls: Vec<PyWall>,
     join: &PyWallJoin,
     tolerance: f64,
+    heal_openings: bool,
 ) -> PyResult<Py<PyDict>> {
-    let resolver = JoinResolver::new(tolerance);
+    let resolver = JoinResolver::new(tolerance).with_opening_heal(heal_openings);
     let wall_refs: Vec<&Wall> = walls.iter().map(|w| &w.inner).collect();
 
     let geometry = resolver
@@ -292,6 +547,18 @@ pub fn compute_join_geometry(
             .collect();
         dict.set_item("profiles", profiles)?;
 
+        let adjustments = PyList::empty_bound(py);
+        for a in &geometry.opening_adjustments {
+            let ad = PyDict::new_bound(py);
+            ad.set_item("wall_id", a.wall_id.to_string())?;
+            ad.set_item("opening_id", a.opening_id.to_string())?;
+            ad.set_item("offset_along_wall", a.offset_along_wall)?;
+            ad.set_item("width", a.width)?;
+            ad.set_item("shrink", a.shrink)?;
+            adjustments.append(ad)?;
+        }
+        dict.set_item("opening_adjustments", adjustments)?;
+
         Ok(dict.unbind())
     })
 }
@@ -315,6 +582,182 @@ pub fn mesh_to_obj(mesh: &PyTriangleMesh) -> String {
     mesh.inner.to_obj()
 }
 
+/// Import an OBJ or PLY file as a read-only context mesh.
+///
+/// Meshes over the triangle budget are decimated while reading. The mesh
+/// is split into spatial chunks for culling and clash detection.
+///
+/// Args:
+///     path: Path to a .obj or .ply file
+///     max_triangles: Triangle budget (default: 2,000,000)
+///
+/// Returns:
+///     PyContextMesh: The imported context mesh
+///
+/// Example:
+///     >>> site = import_context_mesh("survey/terrain.ply")
+///     >>> site.triangle_count <= 2_000_000
+///     True
+#[pyfunction]
+#[pyo3(signature = (path, max_triangles=2_000_000))]
+pub fn import_context_mesh(path: &str, max_triangles: usize) -> PyResult<PyContextMesh> {
+    let options = ContextMeshOptions {
+        max_triangles,
+        ..Default::default()
+    };
+    ContextMesh::import(path, options)
+        .map(|inner| PyContextMesh { inner })
+        .map_err(geometry_error)
+}
+
+/// Load the office property schema used by `set_property` on elements.
+///
+/// Args:
+///     schema: Dict with an optional "mode" ("lenient" or "strict") and
+///         "schemas", mapping element type names (e.g. "Wall") to lists of
+///         property definitions with "key", "type" ("text", "integer",
+///         "number", "boolean"), and optional "required", "allowed_values",
+///         "min", "max", "aliases" and "ifc" ({"pset": ..., "name": ...})
+///
+/// Example:
+///     >>> load_property_schema({"schemas": {"Wall": [
+///     ...     {"key": "FireRating", "type": "integer"}]}})
+///     >>> wall = create_wall((0, 0), (5, 0), 3.0, 0.2)
+///     >>> wall.set_property("fire_rating", 60)
+///     'FireRating'
+#[pyfunction]
+pub fn load_property_schema(py: Python<'_>, schema: &Bound<'_, PyDict>) -> PyResult<()> {
+    let json: String = py
+        .import_bound("json")?
+        .call_method1("dumps", (schema,))?
+        .extract()?;
+    let registry = PropertySchemaRegistry::from_json(&json).map_err(geometry_error)?;
+    set_property_schema(registry);
+    Ok(())
+}
+
+/// Derive IDs of new elements from their parameters, for reproducible runs.
+///
+/// Elements created after this call get UUIDv5s hashed from their type,
+/// host, and quantized dimensions under `namespace`, so running the same
+/// script again gives the same IDs.
+///
+/// Args:
+///     namespace: A UUID string, or any other string to hash into one.
+///         None switches back to random IDs.
+///
+/// Example:
+///     >>> set_deterministic_ids("my-project")
+///     >>> a = create_wall((0, 0), (5, 0), 3.0, 0.2).id
+///     >>> set_deterministic_ids("my-project")
+///     >>> a == create_wall((0, 0), (5, 0), 3.0, 0.2).id
+///     True
+#[pyfunction]
+#[pyo3(signature = (namespace=None))]
+pub fn set_deterministic_ids(namespace: Option<&str>) {
+    let mode = match namespace {
+        Some(namespace) => IdMode::ContentHash {
+            namespace: uuid::Uuid::parse_str(namespace).unwrap_or_else(|_| {
+                uuid::Uuid::new_v5(&ELEMENT_ID_NAMESPACE, namespace.as_bytes())
+            }),
+        },
+        None => IdMode::Random,
+    };
+    set_id_mode(mode);
+}
+
+/// Cast a ray against a mesh.
+///
+/// Args:
+///     mesh: The mesh to test
+///     origin: Ray origin as (x, y, z)
+///     direction: Ray direction as (dx, dy, dz); need not be unit length
+///
+/// Returns:
+///     dict | None: The closest hit with 'distance', 'point', 'triangle_index'
+///         and 'normal' (facing the ray origin), or None if the ray misses
+///
+/// Example:
+///     >>> wall = create_wall((0, 0), (5, 0), 3.0, 0.2)
+///     >>> hit = raycast_mesh(wall.to_mesh(), (1, -5, 1), (0, 1, 0))
+///     >>> round(hit["distance"], 3)
+///     4.9
+#[pyfunction]
+pub fn raycast_mesh(
+    mesh: &PyTriangleMesh,
+    origin: (f64, f64, f64),
+    direction: (f64, f64, f64),
+) -> PyResult<Option<Py<PyDict>>> {
+    let ray = make_ray(origin, direction)?;
+    mesh.inner
+        .raycast_ray(&ray)
+        .map(|hit| Python::with_gil(|py| ray_hit_dict(py, &hit).map(Bound::unbind)))
+        .transpose()
+}
+
+/// Pick the element closest along a ray.
+///
+/// Meshes whose bounding boxes the ray misses are skipped without testing
+/// their triangles. Hits at the same distance go to the lowest element ID.
+///
+/// Args:
+///     elements: List of tuples (element_id, mesh)
+///     origin: Ray origin as (x, y, z)
+///     direction: Ray direction as (dx, dy, dz)
+///
+/// Returns:
+///     dict | None: The hit as from `raycast_mesh`, plus 'element_id', or None
+///
+/// Example:
+///     >>> walls = [create_wall((0, y), (5, y), 3.0, 0.2) for y in (0, 3)]
+///     >>> elements = [(str(w.id), w.to_mesh()) for w in walls]
+///     >>> pick(elements, (1, -5, 1), (0, 1, 0))["element_id"] == str(walls[0].id)
+///     True
+#[pyfunction]
+pub fn pick(
+    elements: Vec<(String, PyTriangleMesh)>,
+    origin: (f64, f64, f64),
+    direction: (f64, f64, f64),
+) -> PyResult<Option<Py<PyDict>>> {
+    let ray = make_ray(origin, direction)?;
+    let elements = elements
+        .into_iter()
+        .map(|(id, mesh)| {
+            uuid::Uuid::parse_str(&id)
+                .map(|id| (id, mesh.inner))
+                .map_err(|_| PyValueError::new_err(format!("Invalid element id: {}", id)))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let refs: Vec<(uuid::Uuid, &TriangleMesh)> =
+        elements.iter().map(|(id, mesh)| (*id, mesh)).collect();
+
+    let Some(picked) = pick_mesh(&refs, &ray) else {
+        return Ok(None);
+    };
+    Python::with_gil(|py| {
+        let dict = ray_hit_dict(py, &picked.hit)?;
+        dict.set_item("element_id", picked.element_id.to_string())?;
+        Ok(Some(dict.unbind()))
+    })
+}
+
+fn make_ray(origin: (f64, f64, f64), direction: (f64, f64, f64)) -> PyResult<Ray> {
+    Ray::new(
+        Point3::new(origin.0, origin.1, origin.2),
+        Vector3::new(direction.0, direction.1, direction.2),
+    )
+    .ok_or_else(|| PyValueError::new_err("Ray direction must be non-zero"))
+}
+
+fn ray_hit_dict<'py>(py: Python<'py>, hit: &RayHit) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("distance", hit.distance)?;
+    dict.set_item("point", (hit.point.x, hit.point.y, hit.point.z))?;
+    dict.set_item("triangle_index", hit.triangle_index)?;
+    dict.set_item("normal", (hit.normal.x, hit.normal.y, hit.normal.z))?;
+    Ok(dict)
+}
+
 /// Validate a triangle mesh.
 ///
 /// Checks that a mesh is valid (no degenerate triangles, valid indices, etc.).
@@ -445,6 +888,183 @@ pub fn create_simple_building(
     })
 }
 
+/// Serialize a whole model to canonical JSON.
+///
+/// Keys are sorted, elements ordered by id, and floats quantized, so saving
+/// a loaded model gives the same text.
+///
+/// Args:
+///     walls: Walls in the model
+///     floors: Floors in the model
+///     roofs: Roofs in the model
+///     doors: Doors in the model
+///     windows: Windows in the model
+///     rooms: Rooms in the model
+///     joins: Wall joins in the model
+///
+/// Returns:
+///     str: Model document JSON, with a schema_version
+///
+/// Example:
+///     >>> building = create_simple_building((0, 0), (10, 8), 3.0, 0.2, 0.3, "Hall", "001")
+///     >>> text = save_model(walls=building["walls"], floors=[building["floor"]],
+///     ...                   rooms=[building["room"]], joins=building["joins"])
+#[pyfunction]
+#[pyo3(signature = (walls=vec![], floors=vec![], roofs=vec![], doors=vec![], windows=vec![], rooms=vec![], joins=vec![]))]
+pub fn save_model(
+    walls: Vec<PyWall>,
+    floors: Vec<PyFloor>,
+    roofs: Vec<PyRoof>,
+    doors: Vec<PyDoor>,
+    windows: Vec<PyWindow>,
+    rooms: Vec<PyRoom>,
+    joins: Vec<PyWallJoin>,
+) -> String {
+    let document = ModelDocument {
+        walls: walls.into_iter().map(|w| w.inner).collect(),
+        floors: floors.into_iter().map(|f| f.inner).collect(),
+        roofs: roofs.into_iter().map(|r| r.inner).collect(),
+        doors: doors.into_iter().map(|d| d.inner).collect(),
+        windows: windows.into_iter().map(|w| w.inner).collect(),
+        rooms: rooms.into_iter().map(|r| r.inner).collect(),
+        joins: joins.into_iter().map(|j| j.inner).collect(),
+        ..ModelDocument::new()
+    };
+    io::model::save_model(&document)
+}
+
+/// Load a model saved by save_model.
+///
+/// Unknown fields are ignored, so documents from newer builds load as long
+/// as their schema version matches.
+///
+/// Args:
+///     json: Model document JSON
+///
+/// Returns:
+///     dict: Element lists keyed walls, floors, roofs, doors, windows,
+///         rooms, and joins, plus schema_version
+///
+/// Raises:
+///     ValueError: If the JSON is invalid or has another schema version
+///
+/// Example:
+///     >>> model = load_model(text)
+///     >>> len(model["walls"])
+///     4
+#[pyfunction]
+pub fn load_model(json: &str) -> PyResult<Py<PyDict>> {
+    let document = io::model::load_model(json).map_err(geometry_error)?;
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("schema_version", document.schema_version)?;
+        let walls: Vec<PyWall> = document
+            .walls
+            .into_iter()
+            .map(|inner| PyWall { inner })
+            .collect();
+        dict.set_item("walls", walls.into_py(py))?;
+        let floors: Vec<PyFloor> = document
+            .floors
+            .into_iter()
+            .map(|inner| PyFloor { inner })
+            .collect();
+        dict.set_item("floors", floors.into_py(py))?;
+        let roofs: Vec<PyRoof> = document
+            .roofs
+            .into_iter()
+            .map(|inner| PyRoof { inner })
+            .collect();
+        dict.set_item("roofs", roofs.into_py(py))?;
+        let doors: Vec<PyDoor> = document
+            .doors
+            .into_iter()
+            .map(|inner| PyDoor { inner })
+            .collect();
+        dict.set_item("doors", doors.into_py(py))?;
+        let windows: Vec<PyWindow> = document
+            .windows
+            .into_iter()
+            .map(|inner| PyWindow { inner })
+            .collect();
+        dict.set_item("windows", windows.into_py(py))?;
+        let rooms: Vec<PyRoom> = document
+            .rooms
+            .into_iter()
+            .map(|inner| PyRoom { inner })
+            .collect();
+        dict.set_item("rooms", rooms.into_py(py))?;
+        let joins: Vec<PyWallJoin> = document
+            .joins
+            .into_iter()
+            .map(|inner| PyWallJoin { inner })
+            .collect();
+        dict.set_item("joins", joins.into_py(py))?;
+        Ok(dict.unbind())
+    })
+}
+
+/// Lint a whole model before export.
+///
+/// Runs every validation rule and reports all the problems found rather
+/// than stopping at the first.
+///
+/// Args:
+///     walls: Walls in the model
+///     floors: Floors in the model
+///     roofs: Roofs in the model
+///     doors: Doors in the model
+///     windows: Windows in the model
+///     rooms: Rooms in the model
+///     graph: The model's TopologyGraph, used to find unclosed walls when
+///         there are rooms (default: check the wall ends directly)
+///
+/// Returns:
+///     dict: 'issues', a list of dicts with severity ("error", "warning",
+///         or "info"), rule (a stable id such as "missing_host_wall"),
+///         element_ids, and message
+///
+/// Example:
+///     >>> building = create_simple_building((0, 0), (10, 8), 3.0, 0.2, 0.3, "Hall", "001")
+///     >>> report = validate_model(walls=building["walls"], rooms=[building["room"]])
+///     >>> report["issues"]
+///     []
+#[pyfunction]
+#[pyo3(signature = (walls=vec![], floors=vec![], roofs=vec![], doors=vec![], windows=vec![], rooms=vec![], graph=None))]
+pub fn validate_model(
+    walls: Vec<PyWall>,
+    floors: Vec<PyFloor>,
+    roofs: Vec<PyRoof>,
+    doors: Vec<PyDoor>,
+    windows: Vec<PyWindow>,
+    rooms: Vec<PyRoom>,
+    graph: Option<PyRef<PyTopologyGraph>>,
+) -> PyResult<Py<PyDict>> {
+    let walls: Vec<_> = walls.into_iter().map(|w| w.inner).collect();
+    let floors: Vec<_> = floors.into_iter().map(|f| f.inner).collect();
+    let roofs: Vec<_> = roofs.into_iter().map(|r| r.inner).collect();
+    let doors: Vec<_> = doors.into_iter().map(|d| d.inner).collect();
+    let windows: Vec<_> = windows.into_iter().map(|w| w.inner).collect();
+    let rooms: Vec<_> = rooms.into_iter().map(|r| r.inner).collect();
+    let report = validation::validate_model(
+        &walls,
+        &floors,
+        &roofs,
+        &doors,
+        &windows,
+        &rooms,
+        graph.as_ref().map(|g| &g.inner),
+    );
+
+    Python::with_gil(|py| {
+        let report = py
+            .import_bound("json")?
+            .call_method1("loads", (report.to_json(),))?;
+        Ok(report.downcast_into::<PyDict>()?.unbind())
+    })
+}
+
 /// Merge multiple meshes into one.
 ///
 /// Combines multiple triangle meshes into a single mesh for efficient rendering.
@@ -468,44 +1088,182 @@ pub fn merge_meshes(meshes: Vec<PyTriangleMesh>) -> PyTriangleMesh {
     PyTriangleMesh { inner: combined }
 }
 
-/// Create a roof element.
+/// One mesh's section polylines as lists of (u, v) tuples.
+type SectionLoops = Vec<Vec<(f64, f64)>>;
+
+/// Cut meshes with a plane for a section drawing.
 ///
-/// Creates a roof that can be attached to walls. Supports multiple roof types:
-/// flat, gable, hip, shed, and mansard.
+/// Each mesh is sliced where it crosses the plane and the pieces are
+/// chained into polylines, in the plane's 2D coordinates: seen from the
+/// side the normal points to, u runs right and v up (v is world Z on a
+/// vertical plane). Closed loops end where they start.
 ///
 /// Args:
-///     min_point: Minimum corner as (x, y) tuple for rectangular roof
-///     max_point: Maximum corner as (x, y) tuple for rectangular roof
-///     thickness: Roof thickness
-///     roof_type: Roof type ("flat", "gable", "hip", "shed", "mansard")
-///     slope_degrees: Slope angle in degrees (default 30.0)
-///     ridge_along_x: For gable/shed: ridge along X axis (True) or Y axis (False)
-///     eave_overhang: Overhang at eaves in model units (default 0.3)
+///     meshes: Meshes to cut, in meters
+///     origin: (x, y, z) point on the plane, the origin of (u, v)
+///     normal: (x, y, z) plane normal
 ///
 /// Returns:
-///     PyRoof: The created roof element
+///     list[list[list[tuple]]]: For each mesh, its polylines as lists of
+///         (u, v) points
+///
+/// Raises:
+///     ValueError: If the normal is zero
 ///
 /// Example:
-///     >>> roof = create_roof((0, 0), (10, 8), 0.25, roof_type="gable", slope_degrees=35.0)
-///     >>> roof.footprint_area()
-///     80.0
+///     >>> wall = create_wall((0, 0), (5, 0), 3.0, 0.2)
+///     >>> [outline] = section_meshes([wall.to_mesh()], (0, 0, 0), (0, -1, 0))[0]
 #[pyfunction]
-#[pyo3(signature = (min_point, max_point, thickness, roof_type="flat", slope_degrees=30.0, ridge_along_x=true, eave_overhang=0.3))]
-pub fn create_roof(
-    min_point: (f64, f64),
-    max_point: (f64, f64),
-    thickness: f64,
-    roof_type: &str,
-    slope_degrees: f64,
-    ridge_along_x: bool,
-    eave_overhang: f64,
-) -> PyResult<PyRoof> {
-    let mut roof = match roof_type.to_lowercase().as_str() {
-        "flat" => PyRoof::rectangle(min_point, max_point, thickness, Some("flat"), Some(0.0))?,
-        "gable" => PyRoof::gable(
-            min_point,
-            max_point,
-            thickness,
+pub fn section_meshes(
+    meshes: Vec<PyTriangleMesh>,
+    origin: (f64, f64, f64),
+    normal: (f64, f64, f64),
+) -> PyResult<Vec<SectionLoops>> {
+    let plane = SectionPlane::new(
+        Point3::new(origin.0, origin.1, origin.2),
+        Vector3::new(normal.0, normal.1, normal.2),
+    )
+    .ok_or_else(|| PyValueError::new_err("Section plane normal must be non-zero"))?;
+
+    Ok(meshes
+        .iter()
+        .map(|mesh| {
+            section_building([(uuid::Uuid::nil(), &mesh.inner)], &plane)
+                .into_iter()
+                .map(|curve| curve.points.iter().map(|p| (p.x, p.y)).collect())
+                .collect()
+        })
+        .collect())
+}
+
+/// Mesh walls together with the doors and windows they host.
+///
+/// Each wall is followed by its hosted doors and then windows, each as a
+/// separate named mesh, so a viewer can show, hide, or style them apart.
+///
+/// Args:
+///     walls: Walls to mesh
+///     doors: Doors hosted in those walls (default none)
+///     windows: Windows hosted in those walls (default none)
+///
+/// Returns:
+///     list[dict]: One entry per mesh with 'name' ("wall_0", "wall_0/door_0",
+///         "wall_0/window_0", ...), 'element_id', 'element_type' ("wall",
+///         "door" or "window"), 'host_wall_id' (None for walls) and 'mesh'
+///
+/// Raises:
+///     ValueError: If a door or window's host is not among the walls
+///
+/// Example:
+///     >>> wall = create_wall((0, 0), (5, 0), 3.0, 0.2)
+///     >>> door = place_door(wall, offset=2.5, width=0.9, height=2.1)['door']
+///     >>> [m['name'] for m in building_meshes([wall], doors=[door])]
+///     ['wall_0', 'wall_0/door_0']
+#[pyfunction]
+#[pyo3(signature = (walls, doors=Vec::new(), windows=Vec::new()))]
+pub fn building_meshes(
+    walls: Vec<PyWall>,
+    doors: Vec<PyDoor>,
+    windows: Vec<PyWindow>,
+) -> PyResult<Py<PyList>> {
+    let wall_ids: HashSet<_> = walls.iter().map(|w| w.inner.id).collect();
+    let orphan = doors
+        .iter()
+        .map(|d| (d.inner.id, d.inner.host_wall_id))
+        .chain(windows.iter().map(|w| (w.inner.id, w.inner.host_wall_id)))
+        .find(|(_, host)| !wall_ids.contains(host));
+    if let Some((id, host)) = orphan {
+        return Err(PyValueError::new_err(format!(
+            "element {} is hosted by wall {}, which was not given",
+            id, host
+        )));
+    }
+
+    // (name, element id, element type, host wall id, mesh)
+    let mut meshes = Vec::new();
+    for (i, wall) in walls.iter().enumerate() {
+        let host = &wall.inner;
+        let wall_name = format!("wall_{}", i);
+        let mesh = host.to_mesh_with_openings();
+        meshes.push((wall_name.clone(), host.id, "wall", None, mesh));
+
+        let hosted_doors = doors.iter().filter(|d| d.inner.host_wall_id == host.id);
+        for (j, door) in hosted_doors.enumerate() {
+            let name = format!("{}/door_{}", wall_name, j);
+            let mesh = door.inner.to_mesh_in_host(host);
+            meshes.push((name, door.inner.id, "door", Some(host.id), mesh));
+        }
+        let hosted_windows = windows.iter().filter(|w| w.inner.host_wall_id == host.id);
+        for (j, window) in hosted_windows.enumerate() {
+            let name = format!("{}/window_{}", wall_name, j);
+            let mesh = window.inner.to_mesh_in_host(host);
+            meshes.push((name, window.inner.id, "window", Some(host.id), mesh));
+        }
+    }
+
+    Python::with_gil(|py| {
+        let entries = meshes
+            .into_iter()
+            .map(|(name, id, kind, host, mesh)| {
+                let mesh = mesh.map_err(geometry_error)?;
+                let dict = PyDict::new_bound(py);
+                dict.set_item("name", name)?;
+                dict.set_item("element_id", id.to_string())?;
+                dict.set_item("element_type", kind)?;
+                dict.set_item("host_wall_id", host.map(|h: uuid::Uuid| h.to_string()))?;
+                dict.set_item("mesh", PyTriangleMesh { inner: mesh }.into_py(py))?;
+                Ok(dict.unbind())
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        Ok(PyList::new_bound(py, entries).unbind())
+    })
+}
+
+/// Create a roof element.
+///
+/// Creates a roof that can be attached to walls. Supports multiple roof types:
+/// flat, gable, hip, shed, and mansard.
+///
+/// Args:
+///     min_point: Minimum corner as (x, y) tuple for rectangular roof
+///     max_point: Maximum corner as (x, y) tuple for rectangular roof
+///     thickness: Roof thickness
+///     roof_type: Roof type ("flat", "gable", "hip", "shed", "mansard")
+///     slope_degrees: Slope angle in degrees (default 30.0)
+///     ridge_along_x: For gable/shed: ridge along X axis (True) or Y axis (False)
+///     eave_overhang: Overhang at eaves in model units (default 0.3)
+///     lower_slope: For mansard: pitch of the lower slopes in degrees (default 70.0);
+///         slope_degrees is then the upper slope
+///     break_inset: For mansard: distance from the eaves to the break line (default 1.2)
+///
+/// Returns:
+///     PyRoof: The created roof element
+///
+/// Example:
+///     >>> roof = create_roof((0, 0), (10, 8), 0.25, roof_type="gable", slope_degrees=35.0)
+///     >>> roof.footprint_area()
+///     80.0
+#[pyfunction]
+#[pyo3(signature = (min_point, max_point, thickness, roof_type="flat", slope_degrees=30.0, ridge_along_x=true, eave_overhang=0.3, lower_slope=70.0, break_inset=1.2))]
+#[allow(clippy::too_many_arguments)]
+pub fn create_roof(
+    min_point: (f64, f64),
+    max_point: (f64, f64),
+    thickness: f64,
+    roof_type: &str,
+    slope_degrees: f64,
+    ridge_along_x: bool,
+    eave_overhang: f64,
+    lower_slope: f64,
+    break_inset: f64,
+) -> PyResult<PyRoof> {
+    let mut roof = match roof_type.to_lowercase().as_str() {
+        "flat" => PyRoof::rectangle(min_point, max_point, thickness, Some("flat"), Some(0.0))?,
+        "gable" => PyRoof::gable(
+            min_point,
+            max_point,
+            thickness,
             slope_degrees,
             ridge_along_x,
         )?,
@@ -518,14 +1276,18 @@ pub fn create_roof(
             ridge_along_x,
         )?,
         "mansard" => {
-            // Mansard uses gable with steeper slope
-            PyRoof::gable(
+            let mut roof = PyRoof::rectangle(
                 min_point,
                 max_point,
                 thickness,
-                slope_degrees.max(60.0),
-                ridge_along_x,
-            )?
+                Some("mansard"),
+                Some(slope_degrees),
+            )?;
+            roof.inner.set_lower_slope(lower_slope);
+            roof.inner
+                .set_break_inset(break_inset)
+                .map_err(geometry_error)?;
+            roof
         }
         _ => {
             return Err(PyValueError::new_err(format!(
@@ -541,6 +1303,67 @@ pub fn create_roof(
     Ok(roof)
 }
 
+/// Create a roof fitted to the outer loop of a set of walls.
+///
+/// The roof boundary follows the wall baselines around the outside of the
+/// plan; interior partitions and dangling walls are left out. The roof sits
+/// on top of the tallest wall and is attached to the walls it rests on.
+/// Gable and shed ridges run along the longest boundary edge.
+///
+/// Args:
+///     walls: Walls enclosing the plan
+///     thickness: Roof thickness
+///     roof_type: Roof type ("flat", "gable", "hip", "shed", "mansard")
+///     slope_degrees: Slope angle in degrees (default 30.0; ignored for flat)
+///     eave_overhang: Overhang at eaves in model units (default 0.3)
+///
+/// Returns:
+///     dict: Contains 'roof' (PyRoof) and 'uses_bounding_box', which is True
+///         when a hip or mansard roof over a non-rectangular plan is meshed
+///         over the plan's bounding rectangle
+///
+/// Example:
+///     >>> walls = create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
+///     >>> roof = create_roof_from_walls(walls, 0.25, roof_type="gable")['roof']
+///     >>> roof.base_elevation
+///     3.0
+#[pyfunction]
+#[pyo3(signature = (walls, thickness, roof_type="flat", slope_degrees=30.0, eave_overhang=0.3))]
+pub fn create_roof_from_walls(
+    walls: Vec<PyWall>,
+    thickness: f64,
+    roof_type: &str,
+    slope_degrees: f64,
+    eave_overhang: f64,
+) -> PyResult<Py<PyDict>> {
+    let roof_type = match roof_type.to_lowercase().as_str() {
+        "flat" => RoofType::Flat,
+        "gable" => RoofType::Gable,
+        "hip" => RoofType::Hip,
+        "shed" => RoofType::Shed,
+        "mansard" => RoofType::Mansard,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "Unknown roof type: {}. Valid types: flat, gable, hip, shed, mansard",
+                roof_type
+            )))
+        }
+    };
+    let walls: Vec<&Wall> = walls.iter().map(|w| &w.inner).collect();
+
+    let mut roof =
+        Roof::from_walls(&walls, thickness, roof_type, slope_degrees).map_err(geometry_error)?;
+    roof.set_eave_overhang(eave_overhang);
+    let uses_bounding_box = roof.uses_bounding_box_mesh();
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("roof", PyRoof { inner: roof }.into_py(py))?;
+        dict.set_item("uses_bounding_box", uses_bounding_box)?;
+        Ok(dict.unbind())
+    })
+}
+
 /// Attach a roof to multiple walls.
 ///
 /// This function associates a roof with the walls it rests on,
@@ -577,6 +1400,89 @@ pub fn attach_roof_to_walls(mut roof: PyRoof, walls: Vec<PyWall>) -> PyResult<Py
     })
 }
 
+/// Cut an opening through a roof.
+///
+/// The opening is an axis-aligned rectangle in plan. It must lie inside the
+/// roof boundary (not the eave overhang), clear the other openings, and on a
+/// pitched roof stay on one slope: openings across a ridge or hip raise.
+///
+/// Args:
+///     roof: The roof to cut (will be modified)
+///     center: Plan centre of the opening as (x, y) tuple
+///     width: Opening extent along X
+///     depth: Opening extent along Y
+///     opening_type: Type of opening ("skylight", "chimney", "generic")
+///
+/// Returns:
+///     dict: Contains 'opening' (PyRoofOpening) and 'roof_id'
+///
+/// Example:
+///     >>> roof = create_roof((0, 0), (10, 8), 0.25, roof_type="gable")
+///     >>> result = add_roof_opening(roof, (5, 2), 0.6, 0.6, opening_type="chimney")
+///     >>> opening = result['opening']
+#[pyfunction]
+#[pyo3(signature = (roof, center, width, depth, opening_type="generic"))]
+pub fn add_roof_opening(
+    roof: &mut PyRoof,
+    center: (f64, f64),
+    width: f64,
+    depth: f64,
+    opening_type: &str,
+) -> PyResult<Py<PyDict>> {
+    let opening = PyRoofOpening::new(center, width, depth, opening_type)?;
+    roof.inner
+        .add_opening(opening.inner.clone())
+        .map_err(geometry_error)?;
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("opening", opening.into_py(py))?;
+        dict.set_item("roof_id", roof.inner.id.to_string())?;
+        Ok(dict.unbind())
+    })
+}
+
+/// Place a skylight in a roof.
+///
+/// Creates a skylight and cuts its opening through the roof, like
+/// place_window does for walls.
+///
+/// Args:
+///     roof: The roof to place the skylight in (will be modified)
+///     center: Plan centre of the skylight as (x, y) tuple
+///     width: Skylight extent along X
+///     depth: Skylight extent along Y
+///
+/// Returns:
+///     dict: Contains 'skylight' (PySkylight), 'opening' (PyRoofOpening), and 'roof_id'
+///
+/// Example:
+///     >>> roof = create_roof((0, 0), (10, 8), 0.25, roof_type="gable")
+///     >>> skylight = place_skylight(roof, (5, 2), 1.0, 1.2)['skylight']
+///     >>> mesh = skylight.to_mesh(roof)
+#[pyfunction]
+pub fn place_skylight(
+    roof: &mut PyRoof,
+    center: (f64, f64),
+    width: f64,
+    depth: f64,
+) -> PyResult<Py<PyDict>> {
+    let skylight = PySkylight::new(&roof.inner.id.to_string(), center, width, depth)?;
+
+    let opening = skylight.inner.roof_opening();
+    roof.inner
+        .add_opening(opening.clone())
+        .map_err(geometry_error)?;
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("skylight", skylight.into_py(py))?;
+        dict.set_item("opening", PyRoofOpening { inner: opening }.into_py(py))?;
+        dict.set_item("roof_id", roof.inner.id.to_string())?;
+        Ok(dict.unbind())
+    })
+}
+
 /// Create a generic opening in a wall.
 ///
 /// This function creates a rectangular opening (cut) in a wall at a specified
@@ -621,7 +1527,7 @@ pub fn create_opening(
     // Add to wall
     wall.inner
         .add_opening(opening.clone())
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        .map_err(geometry_error)?;
 
     // Return opening info
     Python::with_gil(|py| {
@@ -633,143 +1539,1005 @@ pub fn create_opening(
     })
 }
 
-/// Detect rooms from a set of walls using topology graph analysis.
-///
-/// This function builds a topology graph from wall elements and detects
-/// enclosed regions (rooms) using boundary tracing. The algorithm:
-/// 1. Creates a planar graph with wall endpoints as nodes
-/// 2. Adds wall segments as edges
-/// 3. Traces closed boundaries using the "turn-right" rule
-/// 4. Returns interior rooms (excluding exterior unbounded region)
+/// Split a wall in two, keeping its openings on the correct half.
 ///
 /// Args:
-///     walls: List of wall elements forming the building layout
-///     tolerance: Distance tolerance for node merging (default 0.0005 = 0.5mm)
+///     wall: Wall to split
+///     offset: Distance along the baseline from the wall start
+///     point: Split at this (x, y) point's projection onto the baseline instead
+///     policy: "reject" to refuse splitting through an opening, or "shift" to
+///         move the opening onto the half containing its center (default: "reject")
 ///
 /// Returns:
-///     list[dict]: Detected rooms, each containing:
-///         - id: Unique room identifier
-///         - area: Room area in square model units
-///         - centroid: Center point as (x, y) tuple
-///         - boundary_count: Number of boundary edges
-///         - is_exterior: Always False for returned rooms (exterior filtered out)
+///     dict: Split result containing:
+///         - first: Half from the wall start to the split point (PyWall)
+///         - second: Half from the split point to the wall end (PyWall)
+///         - openings: One dict per opening with opening_id, half ("first"
+///           or "second"), old_offset, new_offset, and shifted
 ///
 /// Example:
-///     >>> walls = create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
-///     >>> rooms = detect_rooms(walls)
-///     >>> len(rooms)
-///     1
-///     >>> rooms[0]['area']
-///     80.0
+///     >>> wall = create_wall((0, 0), (10, 0), 3.0, 0.2)
+///     >>> result = split_wall(wall, offset=4.0)
+///     >>> result['second'].length()
+///     6.0
 #[pyfunction]
-#[pyo3(signature = (walls, tolerance=0.0005))]
-pub fn detect_rooms(walls: Vec<PyWall>, tolerance: f64) -> PyResult<Py<PyList>> {
-    // Create topology graph
-    let mut graph = TopologyGraph::with_tolerance(tolerance);
+#[pyo3(signature = (wall, offset=None, point=None, policy="reject"))]
+pub fn split_wall(
+    wall: &PyWall,
+    offset: Option<f64>,
+    point: Option<(f64, f64)>,
+    policy: &str,
+) -> PyResult<Py<PyDict>> {
+    let policy = match policy.to_lowercase().as_str() {
+        "reject" => SplitPolicy::Reject,
+        "shift" | "shift_to_center_side" => SplitPolicy::ShiftToCenterSide,
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "unknown split policy: {}",
+                other
+            )))
+        }
+    };
+    let offset = match (offset, point) {
+        (Some(offset), None) => offset,
+        (None, Some((x, y))) => wall.inner.offset_of_point(Point2::new(x, y)),
+        _ => {
+            return Err(PyValueError::new_err(
+                "exactly one of offset or point is required",
+            ))
+        }
+    };
 
-    // Add walls as edges
-    for wall in &walls {
-        let start = [wall.inner.baseline.start.x, wall.inner.baseline.start.y];
-        let end = [wall.inner.baseline.end.x, wall.inner.baseline.end.y];
+    let (first, second) = wall
+        .inner
+        .split_at_with_policy(offset, policy)
+        .map_err(geometry_error)?;
 
-        // Create edge data from wall properties
-        let edge_data = EdgeData::wall(wall.inner.thickness, wall.inner.height);
+    Python::with_gil(|py| {
+        let report = PyList::empty_bound(py);
+        for original in &wall.inner.openings {
+            let (half, base, opening) = match first.openings.iter().find(|o| o.id == original.id) {
+                Some(o) => ("first", 0.0, o),
+                None => match second.openings.iter().find(|o| o.id == original.id) {
+                    Some(o) => ("second", offset, o),
+                    None => continue,
+                },
+            };
+            let entry = PyDict::new_bound(py);
+            entry.set_item("opening_id", original.id.to_string())?;
+            entry.set_item("half", half)?;
+            entry.set_item("old_offset", original.offset_along_wall)?;
+            entry.set_item("new_offset", opening.offset_along_wall)?;
+            entry.set_item(
+                "shifted",
+                (opening.offset_along_wall + base - original.offset_along_wall).abs() > 1e-9,
+            )?;
+            report.append(entry)?;
+        }
 
-        graph.add_edge(start, end, edge_data);
-    }
+        let dict = PyDict::new_bound(py);
+        dict.set_item("first", PyWall { inner: first }.into_py(py))?;
+        dict.set_item("second", PyWall { inner: second }.into_py(py))?;
+        dict.set_item("openings", report)?;
+        Ok(dict.unbind())
+    })
+}
 
-    // Detect rooms
-    graph.rebuild_rooms();
+/// Trim or extend a wall so one end lands on another wall's baseline.
+///
+/// Args:
+///     wall: Wall to modify (changed in place)
+///     end: Which end moves ("start" or "end")
+///     target: Wall whose baseline (or its extension) the end moves to
+///     max_extension: Maximum distance the end may move when extending (default: 10.0)
+///
+/// Returns:
+///     dict: Change containing:
+///         - old_point: Endpoint before the change as (x, y)
+///         - new_point: Endpoint after the change as (x, y)
+///         - kind: "trim", "extend", or "unchanged"
+///
+/// Example:
+///     >>> wall = create_wall((0, 0), (4, 0), 3.0, 0.2)
+///     >>> target = create_wall((6, -2), (6, 2), 3.0, 0.2)
+///     >>> trim_wall_to(wall, "end", target)['kind']
+///     'extend'
+#[pyfunction]
+#[pyo3(signature = (wall, end, target, max_extension=10.0))]
+pub fn trim_wall_to(
+    wall: &mut PyWall,
+    end: &str,
+    target: &PyWall,
+    max_extension: f64,
+) -> PyResult<Py<PyDict>> {
+    let end = parse_wall_end(end)?;
+    let policy = TrimPolicy {
+        max_extension,
+        ..Default::default()
+    };
 
-    // Get interior rooms only (filter out exterior unbounded region)
-    let interior_rooms = graph.interior_rooms();
+    let result = JoinResolver::default()
+        .trim_or_extend(&mut wall.inner, end, &target.inner, policy)
+        .map_err(geometry_error)?;
 
-    // Convert to Python list of dicts
     Python::with_gil(|py| {
-        let room_list: Vec<Py<PyDict>> = interior_rooms
-            .iter()
-            .map(|room| {
-                let dict = PyDict::new_bound(py);
-                dict.set_item("id", room.id.0.to_string()).ok();
-                dict.set_item("area", room.area()).ok();
-                dict.set_item("signed_area", room.signed_area).ok();
-                dict.set_item("centroid", (room.centroid[0], room.centroid[1]))
-                    .ok();
-                dict.set_item("boundary_count", room.boundary_nodes.len())
-                    .ok();
-                dict.set_item("is_exterior", room.is_exterior).ok();
-                dict.unbind()
-            })
-            .collect();
-
-        Ok(PyList::new_bound(py, room_list).unbind())
+        let dict = PyDict::new_bound(py);
+        dict.set_item("old_point", (result.old_point.x, result.old_point.y))?;
+        dict.set_item("new_point", (result.new_point.x, result.new_point.y))?;
+        let kind = match result.kind {
+            TrimKind::Trim => "trim",
+            TrimKind::Extend => "extend",
+            TrimKind::Unchanged => "unchanged",
+        };
+        dict.set_item("kind", kind)?;
+        Ok(dict.unbind())
     })
 }
 
-/// Analyze wall network topology and return detailed graph information.
+/// Change a corner between butt and miter without changing overall dimensions.
 ///
-/// This function performs a comprehensive analysis of how walls connect
-/// and form enclosed regions.
+/// At a butt corner the join's first wall runs through. Switching extends
+/// or trims the wall ends so the two walls cover the same footprint, keeping
+/// openings in place.
 ///
 /// Args:
-///     walls: List of wall elements to analyze
-///     tolerance: Distance tolerance for node merging (default 0.0005 = 0.5mm)
+///     join: Corner join from detect_joins (updated unless previewing)
+///     wall_a: First wall of the join
+///     wall_b: Second wall of the join
+///     join_type: "butt", "miter", or "l_join"
+///     preview: Report the adjustments without changing anything (default: False)
+///     max_extension: Maximum distance an end may move when extending (default: 10.0)
 ///
 /// Returns:
-///     dict: Topology analysis containing:
-///         - node_count: Number of unique connection points
-///         - edge_count: Number of wall segments
-///         - room_count: Total detected rooms (including exterior)
-///         - interior_room_count: Number of enclosed interior rooms
-///         - rooms: List of room data dicts
-///         - is_connected: Whether all walls form a connected graph
+///     dict: Change containing:
+///         - from: Join type before the change
+///         - to: Join type after the change
+///         - adjustments: List of {wall_id, end, delta_mm}; positive extends
 ///
 /// Example:
-///     >>> walls = create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
-///     >>> analysis = analyze_wall_topology(walls)
-///     >>> analysis['node_count']
-///     4
-///     >>> analysis['interior_room_count']
-///     1
+///     >>> a = create_wall((0, 0), (10, 0), 3.0, 0.2)
+///     >>> b = create_wall((10, 0), (10, 8), 3.0, 0.2)
+///     >>> join = detect_joins([a, b])[0]
+///     >>> round(change_join_type(join, a, b, "butt")['adjustments'][0]['delta_mm'], 3)
+///     100.0
 #[pyfunction]
-#[pyo3(signature = (walls, tolerance=0.0005))]
-pub fn analyze_wall_topology(walls: Vec<PyWall>, tolerance: f64) -> PyResult<Py<PyDict>> {
-    // Create topology graph
-    let mut graph = TopologyGraph::with_tolerance(tolerance);
+#[pyo3(signature = (join, wall_a, wall_b, join_type, preview=false, max_extension=10.0))]
+pub fn change_join_type(
+    join: &mut PyWallJoin,
+    wall_a: &mut PyWall,
+    wall_b: &mut PyWall,
+    join_type: &str,
+    preview: bool,
+    max_extension: f64,
+) -> PyResult<Py<PyDict>> {
+    let join_type = match join_type.to_lowercase().as_str() {
+        "butt" => JoinType::Butt,
+        "miter" => JoinType::Miter,
+        "l_join" => JoinType::LJoin,
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "unsupported corner join type: {}",
+                other
+            )))
+        }
+    };
+    let policy = TrimPolicy {
+        max_extension,
+        ..Default::default()
+    };
 
-    // Add walls as edges
-    for wall in &walls {
-        let start = [wall.inner.baseline.start.x, wall.inner.baseline.start.y];
-        let end = [wall.inner.baseline.end.x, wall.inner.baseline.end.y];
+    let resolver = JoinResolver::default();
+    let change = if preview {
+        resolver.preview_join_change(&join.inner, &wall_a.inner, &wall_b.inner, join_type, policy)
+    } else {
+        resolver.change_join_type(
+            &mut join.inner,
+            &mut wall_a.inner,
+            &mut wall_b.inner,
+            join_type,
+            policy,
+        )
+    }
+    .map_err(geometry_error)?;
+
+    Python::with_gil(|py| {
+        let adjustments = PyList::empty_bound(py);
+        for adjustment in &change.adjustments {
+            let entry = PyDict::new_bound(py);
+            entry.set_item("wall_id", adjustment.wall_id.to_string())?;
+            entry.set_item("end", wall_end_name(adjustment.end))?;
+            // Walls are in meters
+            entry.set_item("delta_mm", adjustment.delta * 1000.0)?;
+            adjustments.append(entry)?;
+        }
+
+        let dict = PyDict::new_bound(py);
+        dict.set_item("from", join_type_name(change.from))?;
+        dict.set_item("to", join_type_name(change.to))?;
+        dict.set_item("adjustments", adjustments)?;
+        Ok(dict.unbind())
+    })
+}
+
+/// Create a parallel copy of a wall, e.g. the second leaf of a cavity wall.
+///
+/// Args:
+///     wall: Wall to offset
+///     distance: Offset distance measured towards `side`
+///     side: "left" or "right" of the wall direction (default: "left")
+///     copy_openings: Copy the wall's openings onto the new wall (default: False)
+///
+/// Returns:
+///     PyWall: The offset wall with a new ID
+///
+/// Example:
+///     >>> wall = create_wall((0, 0), (5, 0), 3.0, 0.1)
+///     >>> inner = offset_wall(wall, 0.15, side="right")
+///     >>> inner.start().y
+///     -0.15
+#[pyfunction]
+#[pyo3(signature = (wall, distance, side="left", copy_openings=false))]
+pub fn offset_wall(
+    wall: &PyWall,
+    distance: f64,
+    side: &str,
+    copy_openings: bool,
+) -> PyResult<PyWall> {
+    let side = match side.to_lowercase().as_str() {
+        "left" => OffsetSide::Left,
+        "right" => OffsetSide::Right,
+        other => {
+            return Err(PyValueError::new_err(format!(
+                "unknown offset side: {}",
+                other
+            )))
+        }
+    };
+
+    let inner = if copy_openings {
+        wall.inner.offset_with_openings(distance, side)
+    } else {
+        wall.inner.offset(distance, side)
+    }
+    .map_err(geometry_error)?;
+
+    Ok(PyWall { inner })
+}
+
+/// Offset a chain of connected walls, re-joining the corners.
+///
+/// Args:
+///     walls: Walls in order, each ending where the next starts. If the last
+///         wall ends where the first starts, the chain is treated as closed.
+///     distance: Offset distance; positive is to the left of each wall
+///
+/// Returns:
+///     list[PyWall]: Offset walls in the same order
+///
+/// Example:
+///     >>> walls = create_rectangular_walls((0, 0), (10, 8), 3.0, 0.2)
+///     >>> outer = offset_wall_chain(walls, -0.1)
+///     >>> outer[0].start().x
+///     -0.1
+#[pyfunction]
+pub fn offset_wall_chain(walls: Vec<PyWall>, distance: f64) -> PyResult<Vec<PyWall>> {
+    let walls: Vec<Wall> = walls.into_iter().map(|w| w.inner).collect();
+    let offset = offset_walls(&walls, distance).map_err(geometry_error)?;
+    Ok(offset.into_iter().map(|inner| PyWall { inner }).collect())
+}
+
+/// Move parallel walls onto a common line.
+///
+/// Walls are only translated across their direction, so lengths, positions
+/// along the line, and openings are kept.
+///
+/// Args:
+///     walls: Parallel walls to align (changed in place)
+///     axis: "average" to use the walls' mean line, or "first" to align to
+///         the first wall (default: "average")
+///
+/// Returns:
+///     dict: Edit containing:
+///         - moves: List of {wall_id, end, from, to} for each moved endpoint
+///
+/// Raises:
+///     ValueError: If the walls are not parallel
+///
+/// Example:
+///     >>> a = create_wall((0, 0), (4, 0), 3.0, 0.2)
+///     >>> b = create_wall((5, 0.3), (9, 0.3), 3.0, 0.2)
+///     >>> len(align_walls([a, b], "first")['moves'])
+///     2
+#[pyfunction]
+#[pyo3(signature = (walls, axis="average"))]
+pub fn align_walls(walls: Vec<Bound<'_, PyWall>>, axis: &str) -> PyResult<Py<PyDict>> {
+    let axis = parse_align_axis(axis)?;
+    edit_walls(&walls, |inner| edit::align_walls(inner, axis))
+}
+
+/// Space parallel walls at equal intervals between the outermost two.
+///
+/// The outermost walls stay put; the others keep their order and move
+/// across their direction only.
+///
+/// Args:
+///     walls: Parallel walls to distribute (changed in place)
+///     axis: Direction spacing is measured across: "average" of the walls,
+///         or "first" wall's (default: "average")
+///
+/// Returns:
+///     dict: Edit containing:
+///         - moves: List of {wall_id, end, from, to} for each moved endpoint
+///
+/// Raises:
+///     ValueError: If the walls are not parallel
+///
+/// Example:
+///     >>> walls = [create_wall((x, 0), (x, 3), 3.0, 0.2) for x in (0, 1, 6)]
+///     >>> _ = distribute_walls_evenly(walls)
+///     >>> walls[1].start.x
+///     3.0
+#[pyfunction]
+#[pyo3(signature = (walls, axis="average"))]
+pub fn distribute_walls_evenly(walls: Vec<Bound<'_, PyWall>>, axis: &str) -> PyResult<Py<PyDict>> {
+    let axis = parse_align_axis(axis)?;
+    edit_walls(&walls, |inner| edit::distribute_walls_evenly(inner, axis))
+}
+
+/// Rotate a wall about one end so it is at 90 degrees to another wall.
+///
+/// The wall keeps its length and turns the shorter way round; openings keep
+/// their offsets.
+///
+/// Args:
+///     wall: Wall to rotate (changed in place)
+///     reference: Wall to square up to
+///     end: End that stays fixed, "start" or "end" (default: "start")
+///
+/// Returns:
+///     dict: Edit containing:
+///         - moves: List of {wall_id, end, from, to} for each moved endpoint
+///
+/// Raises:
+///     ValueError: If the wall is parallel to the reference
+///
+/// Example:
+///     >>> reference = create_wall((0, 0), (10, 0), 3.0, 0.2)
+///     >>> wall = create_wall((4, 0), (4.5, 3), 3.0, 0.2)
+///     >>> make_perpendicular(wall, reference)['moves'][0]['end']
+///     'end'
+#[pyfunction]
+#[pyo3(signature = (wall, reference, end="start"))]
+pub fn make_perpendicular(
+    wall: &mut PyWall,
+    reference: &PyWall,
+    end: &str,
+) -> PyResult<Py<PyDict>> {
+    let end = parse_wall_end(end)?;
+    let report =
+        edit::make_perpendicular(&mut wall.inner, &reference.inner, end).map_err(geometry_error)?;
+    Python::with_gil(|py| edit_report_dict(py, &report))
+}
+
+/// Run an edit on copies of the walls, writing them back only on success.
+fn edit_walls(
+    walls: &[Bound<'_, PyWall>],
+    apply: impl FnOnce(&mut [Wall]) -> GeometryResult<EditReport>,
+) -> PyResult<Py<PyDict>> {
+    let mut inner: Vec<Wall> = walls.iter().map(|w| w.borrow().inner.clone()).collect();
+    let report = apply(&mut inner).map_err(geometry_error)?;
+    for (wall, edited) in walls.iter().zip(inner) {
+        wall.borrow_mut().inner = edited;
+    }
+    Python::with_gil(|py| edit_report_dict(py, &report))
+}
+
+/// Describe an edit's moved endpoints for Python.
+fn edit_report_dict(py: Python<'_>, report: &EditReport) -> PyResult<Py<PyDict>> {
+    let moves = PyList::empty_bound(py);
+    for mv in &report.moves {
+        let entry = PyDict::new_bound(py);
+        entry.set_item("wall_id", mv.wall_id.to_string())?;
+        entry.set_item("end", wall_end_name(mv.end))?;
+        entry.set_item("from", (mv.from.x, mv.from.y))?;
+        entry.set_item("to", (mv.to.x, mv.to.y))?;
+        moves.append(entry)?;
+    }
+
+    let dict = PyDict::new_bound(py);
+    dict.set_item("moves", moves)?;
+    Ok(dict.unbind())
+}
+
+fn parse_align_axis(axis: &str) -> PyResult<AlignAxis> {
+    match axis.to_lowercase().as_str() {
+        "average" => Ok(AlignAxis::Average),
+        "first" => Ok(AlignAxis::First),
+        other => Err(PyValueError::new_err(format!(
+            "unknown align axis: {}",
+            other
+        ))),
+    }
+}
+
+fn parse_wall_end(end: &str) -> PyResult<WallEnd> {
+    match end.to_lowercase().as_str() {
+        "start" => Ok(WallEnd::Start),
+        "end" => Ok(WallEnd::End),
+        other => Err(PyValueError::new_err(format!(
+            "unknown wall end: {}",
+            other
+        ))),
+    }
+}
+
+fn wall_end_name(end: WallEnd) -> &'static str {
+    match end {
+        WallEnd::Start => "start",
+        WallEnd::End => "end",
+    }
+}
+
+/// Apply a plan transform to a list of element objects in place.
+///
+/// Walls, floors, roofs, and rooms are transformed. Doors and windows are
+/// positioned by their host wall, so they only change when mirrored, and
+/// then their host wall must be in the list too.
+fn transform_selection(
+    elements: Vec<Bound<'_, PyAny>>,
+    transform: &PlanTransform,
+) -> PyResult<Py<PyDict>> {
+    // Check the whole selection before changing anything
+    let mut wall_ids = HashSet::new();
+    let mut hosts = Vec::new();
+    for element in &elements {
+        if let Ok(wall) = element.downcast::<PyWall>() {
+            wall_ids.insert(wall.borrow().inner.id);
+        } else if let Ok(door) = element.downcast::<PyDoor>() {
+            hosts.push(door.borrow().inner.host_wall_id);
+        } else if let Ok(window) = element.downcast::<PyWindow>() {
+            hosts.push(window.borrow().inner.host_wall_id);
+        } else if !(element.is_instance_of::<PyFloor>()
+            || element.is_instance_of::<PyRoof>()
+            || element.is_instance_of::<PyRoom>())
+        {
+            return Err(PyTypeError::new_err(format!(
+                "cannot transform {}",
+                element.get_type().name()?
+            )));
+        }
+    }
+    if transform.is_mirror() {
+        if let Some(host) = hosts.iter().find(|id| !wall_ids.contains(id)) {
+            return Err(PyValueError::new_err(format!(
+                "host wall {} is not in the selection",
+                host
+            )));
+        }
+    }
+
+    let mut delta = Delta::new();
+    let mut walls = HashMap::new();
+    for element in &elements {
+        let id = if let Ok(wall) = element.downcast::<PyWall>() {
+            let mut wall = wall.borrow_mut();
+            wall.inner.apply_transform(transform);
+            walls.insert(wall.inner.id, wall.inner.clone());
+            wall.inner.id
+        } else if let Ok(floor) = element.downcast::<PyFloor>() {
+            let mut floor = floor.borrow_mut();
+            floor.inner.apply_transform(transform);
+            floor.inner.id
+        } else if let Ok(roof) = element.downcast::<PyRoof>() {
+            let mut roof = roof.borrow_mut();
+            roof.inner.apply_transform(transform);
+            roof.inner.id
+        } else if let Ok(room) = element.downcast::<PyRoom>() {
+            let mut room = room.borrow_mut();
+            room.inner.apply_transform(transform);
+            room.inner.id
+        } else {
+            continue;
+        };
+        delta.modified.push(id.to_string());
+    }
+
+    if transform.is_mirror() {
+        for element in &elements {
+            let result = if let Ok(door) = element.downcast::<PyDoor>() {
+                let mut door = door.borrow_mut();
+                let host = &walls[&door.inner.host_wall_id];
+                door.inner.mirror_in_host(host).map(|_| door.inner.id)
+            } else if let Ok(window) = element.downcast::<PyWindow>() {
+                let mut window = window.borrow_mut();
+                let host = &walls[&window.inner.host_wall_id];
+                window.inner.mirror_in_host(host).map(|_| window.inner.id)
+            } else {
+                continue;
+            };
+            let id = result.map_err(geometry_error)?;
+            delta.modified.push(id.to_string());
+        }
+    }
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("modified", delta.modified)?;
+        Ok(dict.unbind())
+    })
+}
+
+/// Move elements by an offset.
+///
+/// Args:
+///     elements: Walls, floors, roofs, rooms, doors, and windows (modified in place)
+///     offset: Displacement as (dx, dy)
+///
+/// Returns:
+///     dict: 'modified' lists the IDs of the elements that changed
+///
+/// Example:
+///     >>> walls = create_rectangular_walls((0, 0), (10, 8), 3.0, 0.2)
+///     >>> move_elements(walls, (5, 0))["modified"] == [w.id for w in walls]
+///     True
+#[pyfunction]
+pub fn move_elements(elements: Vec<Bound<'_, PyAny>>, offset: (f64, f64)) -> PyResult<Py<PyDict>> {
+    transform_selection(
+        elements,
+        &PlanTransform::Translate(Vector2::new(offset.0, offset.1)),
+    )
+}
+
+/// Rotate elements counter-clockwise about a point.
+///
+/// A roof's ridge direction follows the nearest quarter turn.
+///
+/// Args:
+///     elements: Walls, floors, roofs, rooms, doors, and windows (modified in place)
+///     center: Center of rotation as (x, y)
+///     angle: Rotation angle in radians
+///
+/// Returns:
+///     dict: 'modified' lists the IDs of the elements that changed
+///
+/// Example:
+///     >>> wall = create_wall((0, 0), (4, 0), 3.0, 0.2)
+///     >>> _ = rotate_elements([wall], (0, 0), math.pi / 2)
+///     >>> round(wall.end.y, 9)
+///     4.0
+#[pyfunction]
+pub fn rotate_elements(
+    elements: Vec<Bound<'_, PyAny>>,
+    center: (f64, f64),
+    angle: f64,
+) -> PyResult<Py<PyDict>> {
+    transform_selection(
+        elements,
+        &PlanTransform::Rotate {
+            center: Point2::new(center.0, center.1),
+            angle,
+        },
+    )
+}
+
+/// Mirror elements across the line through two points.
+///
+/// Wall baselines are reversed so faces keep their side; opening offsets
+/// are measured from the new start and door swings change hand. Doors and
+/// windows need their host wall in the same list.
+///
+/// Args:
+///     elements: Walls, floors, roofs, rooms, doors, and windows (modified in place)
+///     axis_start: First point on the mirror line as (x, y)
+///     axis_end: Second point on the mirror line as (x, y)
+///
+/// Returns:
+///     dict: 'modified' lists the IDs of the elements that changed
+///
+/// Example:
+///     >>> wall = create_wall((0, 0), (5, 0), 3.0, 0.2)
+///     >>> door = place_door(wall, offset=1.0, width=0.9, height=2.1, swing="left")["door"]
+///     >>> _ = mirror_elements([wall, door], (0, 0), (0, 1))
+///     >>> door.swing, door.offset_along_wall
+///     ('right', 4.0)
+#[pyfunction]
+pub fn mirror_elements(
+    elements: Vec<Bound<'_, PyAny>>,
+    axis_start: (f64, f64),
+    axis_end: (f64, f64),
+) -> PyResult<Py<PyDict>> {
+    let axis = Line2::from_points(
+        Point2::new(axis_start.0, axis_start.1),
+        Point2::new(axis_end.0, axis_end.1),
+    )
+    .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+    transform_selection(elements, &PlanTransform::Mirror(axis))
+}
+
+/// Create a straight stair.
+///
+/// The riser count is the total rise divided by the riser height, rounded to
+/// the nearest whole number; the riser height is adjusted to divide the rise
+/// evenly.
+///
+/// Args:
+///     start: Foot of the first riser, at the middle of the stair width, as (x, y)
+///     direction: Walking direction as (dx, dy)
+///     total_rise: Height climbed, e.g. the floor-to-floor height
+///     tread_depth: Tread depth (default: 0.28)
+///     riser_height: Target riser height (default: 0.18)
+///     width: Clear width (default: 1.0)
+///     landings: Optional list of (step, depth) replacing treads with landings
+///     stringers: Add stringers along both sides (default: False)
+///
+/// Returns:
+///     PyStair: The created stair
+///
+/// Example:
+///     >>> stair = create_stair((0, 0), (1, 0), 3.0)
+///     >>> stair.riser_count
+///     17
+///     >>> stair.to_mesh().is_valid()
+///     True
+#[pyfunction]
+#[pyo3(signature = (
+    start,
+    direction,
+    total_rise,
+    tread_depth=0.28,
+    riser_height=0.18,
+    width=1.0,
+    landings=None,
+    stringers=false
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn create_stair(
+    start: (f64, f64),
+    direction: (f64, f64),
+    total_rise: f64,
+    tread_depth: f64,
+    riser_height: f64,
+    width: f64,
+    landings: Option<Vec<(usize, f64)>>,
+    stringers: bool,
+) -> PyResult<PyStair> {
+    let mut stair = PyStair::new(
+        start,
+        direction,
+        total_rise,
+        tread_depth,
+        riser_height,
+        width,
+    )?;
+    let mut inner = stair.inner;
+    for (at_step, depth) in landings.unwrap_or_default() {
+        inner = inner.with_landing(at_step, depth).map_err(geometry_error)?;
+    }
+    if stringers {
+        inner = inner.with_stringers(RailProfile::new(0.05, 0.25));
+    }
+    stair.inner = inner;
+    Ok(stair)
+}
+
+/// Create a railing along one edge of a floor slab.
+///
+/// Args:
+///     floor_or_stair: Host floor (stairs and ramps use Railing(path) instead)
+///     edge_selector: Edge index, or an (x, y) point picking the nearest edge
+///     height: Handrail height above the slab (default: 1.1)
+///     spacing: Maximum clear gap between balusters (default: 0.1)
+///
+/// Returns:
+///     PyRailing: Railing on the top face of the slab edge
+///
+/// Example:
+///     >>> floor = create_floor((0, 0), (6, 4), thickness=0.2)
+///     >>> railing = create_railing_on_edge(floor, (6, 2))
+///     >>> railing.takeoff()['rail_length']
+///     4.0
+#[pyfunction]
+#[pyo3(signature = (floor_or_stair, edge_selector, height=1.1, spacing=0.1))]
+pub fn create_railing_on_edge(
+    floor_or_stair: &PyFloor,
+    edge_selector: &Bound<'_, PyAny>,
+    height: f64,
+    spacing: f64,
+) -> PyResult<PyRailing> {
+    let floor = &floor_or_stair.inner;
+    let edge_index = if let Ok(index) = edge_selector.extract::<usize>() {
+        index
+    } else if let Ok((x, y)) = edge_selector.extract::<(f64, f64)>() {
+        nearest_edge(&floor.boundary.vertices, Point2::new(x, y))
+    } else {
+        return Err(PyValueError::new_err(
+            "edge_selector must be an edge index or an (x, y) point",
+        ));
+    };
+
+    Railing::on_floor_edge(floor, edge_index, height, spacing)
+        .map(|r| PyRailing { inner: r })
+        .map_err(geometry_error)
+}
+
+/// Index of the polygon edge nearest to a point.
+fn nearest_edge(vertices: &[Point2], point: Point2) -> usize {
+    (0..vertices.len())
+        .map(|i| {
+            let edge = LineSegment2::new(vertices[i], vertices[(i + 1) % vertices.len()]);
+            (i, edge.distance_to_point(&point))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(i, _)| i)
+}
+
+/// Detect rooms from a set of walls using topology graph analysis.
+///
+/// This function builds a topology graph from wall elements and detects
+/// enclosed regions (rooms) using boundary tracing. The algorithm:
+/// 1. Creates a planar graph with wall endpoints as nodes
+/// 2. Adds wall segments as edges
+/// 3. Traces closed boundaries using the "turn-right" rule
+/// 4. Returns interior rooms (excluding exterior unbounded region)
+///
+/// Args:
+///     walls: List of wall elements forming the building layout
+///     tolerance: Distance tolerance for node merging (default 0.0005 = 0.5mm)
+///
+/// Returns:
+///     list[dict]: Detected rooms, each containing:
+///         - id: Unique room identifier
+///         - area: Room area in square model units
+///         - centroid: Center point as (x, y) tuple
+///         - boundary_count: Number of boundary edges
+///         - is_exterior: Always False for returned rooms (exterior filtered out)
+///
+/// Example:
+///     >>> walls = create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
+///     >>> rooms = detect_rooms(walls)
+///     >>> len(rooms)
+///     1
+///     >>> rooms[0]['area']
+///     80.0
+#[pyfunction]
+#[pyo3(signature = (walls, tolerance=0.0005))]
+pub fn detect_rooms(walls: Vec<PyWall>, tolerance: f64) -> PyResult<Py<PyList>> {
+    // Create topology graph
+    let mut graph = TopologyGraph::with_tolerance(tolerance);
+
+    // Add walls as edges
+    for wall in &walls {
+        let start = [wall.inner.baseline.start.x, wall.inner.baseline.start.y];
+        let end = [wall.inner.baseline.end.x, wall.inner.baseline.end.y];
+
+        // Create edge data from wall properties
+        let edge_data = EdgeData::wall(wall.inner.thickness, wall.inner.height);
+
+        graph.add_edge(start, end, edge_data);
+    }
+
+    // Detect rooms
+    graph.rebuild_rooms();
+
+    // Get interior rooms only (filter out exterior unbounded region)
+    let interior_rooms = graph.interior_rooms();
+
+    // Convert to Python list of dicts
+    Python::with_gil(|py| {
+        let room_list: Vec<Py<PyDict>> = interior_rooms
+            .iter()
+            .map(|room| {
+                let dict = PyDict::new_bound(py);
+                dict.set_item("id", room.id.0.to_string()).ok();
+                dict.set_item("area", room.area()).ok();
+                dict.set_item("signed_area", room.signed_area).ok();
+                dict.set_item("centroid", (room.centroid[0], room.centroid[1]))
+                    .ok();
+                dict.set_item("boundary_count", room.boundary_nodes.len())
+                    .ok();
+                dict.set_item("is_exterior", room.is_exterior).ok();
+                dict.unbind()
+            })
+            .collect();
+
+        Ok(PyList::new_bound(py, room_list).unbind())
+    })
+}
+
+/// Detect rooms from a set of walls and return them as room elements.
+///
+/// Like `detect_rooms`, but each enclosed region becomes a full Room with
+/// its actual boundary polygon. Rooms are named "Room 1", "Room 2", ... in
+/// order of their centroids from left to right, and numbered to match.
+///
+/// Args:
+///     walls: List of wall elements forming the building layout
+///     height: Room height (default: the tallest wall's height)
+///     tolerance: Distance tolerance for node merging (default 0.0005 = 0.5mm)
+///
+/// Returns:
+///     list[PyRoom]: One room per enclosed region
+///
+/// Example:
+///     >>> walls = create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
+///     >>> rooms = detect_rooms_as_elements(walls)
+///     >>> rooms[0].area()
+///     80.0
+#[pyfunction]
+#[pyo3(signature = (walls, height=None, tolerance=0.0005))]
+pub fn detect_rooms_as_elements(
+    walls: Vec<PyWall>,
+    height: Option<f64>,
+    tolerance: f64,
+) -> PyResult<Vec<PyRoom>> {
+    // The topology graph works in millimeters and walls are in meters
+    let mut graph = TopologyGraph::with_tolerance(tolerance * 1000.0);
+    for wall in &walls {
+        let start = [
+            wall.inner.baseline.start.x * 1000.0,
+            wall.inner.baseline.start.y * 1000.0,
+        ];
+        let end = [
+            wall.inner.baseline.end.x * 1000.0,
+            wall.inner.baseline.end.y * 1000.0,
+        ];
+        let edge_data = EdgeData::wall(wall.inner.thickness * 1000.0, wall.inner.height * 1000.0);
+        graph.add_edge(start, end, edge_data);
+    }
+    graph.rebuild_rooms();
+
+    let height = height.unwrap_or_else(|| walls.iter().map(|w| w.inner.height).fold(0.0, f64::max));
+    // Number rooms in a stable order: by centroid, left to right then bottom to top
+    let mut interior = graph.interior_rooms();
+    interior.sort_by(|a, b| {
+        a.centroid[0]
+            .total_cmp(&b.centroid[0])
+            .then(a.centroid[1].total_cmp(&b.centroid[1]))
+    });
+    let room_ids: Vec<_> = interior.iter().map(|r| r.id).collect();
+
+    room_ids
+        .into_iter()
+        .enumerate()
+        .map(|(i, id)| {
+            let number = (i + 1).to_string();
+            Room::from_topo_room(&graph, id, format!("Room {}", number), number, height)
+                .map(|inner| PyRoom { inner })
+                .map_err(geometry_error)
+        })
+        .collect()
+}
+
+/// Analyze wall network topology and return detailed graph information.
+///
+/// This function performs a comprehensive analysis of how walls connect
+/// and form enclosed regions.
+///
+/// Args:
+///     walls: List of wall elements to analyze
+///     tolerance: Distance tolerance for node merging (default 0.0005 = 0.5mm)
+///
+/// Returns:
+///     dict: Topology analysis containing:
+///         - node_count: Number of unique connection points
+///         - edge_count: Number of wall segments
+///         - room_count: Total detected rooms (including exterior)
+///         - interior_room_count: Number of enclosed interior rooms
+///         - rooms: List of room data dicts
+///         - is_connected: Whether all walls form a connected graph
+///
+/// Example:
+///     >>> walls = create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
+///     >>> analysis = analyze_wall_topology(walls)
+///     >>> analysis['node_count']
+///     4
+///     >>> analysis['interior_room_count']
+///     1
+#[pyfunction]
+#[pyo3(signature = (walls, tolerance=0.0005))]
+pub fn analyze_wall_topology(walls: Vec<PyWall>, tolerance: f64) -> PyResult<Py<PyDict>> {
+    // Create topology graph
+    let mut graph = TopologyGraph::with_tolerance(tolerance);
+
+    // Add walls as edges
+    for wall in &walls {
+        let start = [wall.inner.baseline.start.x, wall.inner.baseline.start.y];
+        let end = [wall.inner.baseline.end.x, wall.inner.baseline.end.y];
+        let edge_data = EdgeData::wall(wall.inner.thickness, wall.inner.height);
+        graph.add_edge(start, end, edge_data);
+    }
+
+    // Detect rooms
+    graph.rebuild_rooms();
+
+    // Gather statistics
+    let node_count = graph.node_count();
+    let edge_count = graph.edge_count();
+    let room_count = graph.room_count();
+    let interior_rooms = graph.interior_rooms();
+    let interior_room_count = interior_rooms.len();
+
+    let is_connected = graph.is_connected();
+
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("node_count", node_count)?;
+        dict.set_item("edge_count", edge_count)?;
+        dict.set_item("room_count", room_count)?;
+        dict.set_item("interior_room_count", interior_room_count)?;
+        dict.set_item("is_connected", is_connected)?;
+
+        // Add detailed room data
+        let room_list: Vec<Py<PyDict>> = interior_rooms
+            .iter()
+            .map(|room| {
+                let rd = PyDict::new_bound(py);
+                rd.set_item("id", room.id.0.to_string()).ok();
+                rd.set_item("area", room.area()).ok();
+                rd.set_item("centroid", (room.centroid[0], room.centroid[1]))
+                    .ok();
+                rd.set_item("boundary_count", room.boundary_nodes.len())
+                    .ok();
+                rd.unbind()
+            })
+            .collect();
+
+        dict.set_item("rooms", PyList::new_bound(py, room_list))?;
+
+        Ok(dict.unbind())
+    })
+}
+
+/// Build a topology graph from walls and run the healing passes on it.
+///
+/// The passes run in the kernel's order: snap-merge nodes, split crossings,
+/// merge colinear edges, then rebuild rooms. Each pass reports how much it
+/// changed, so callers can tell the user what was fixed.
+///
+/// Args:
+///     walls: List of wall elements forming the building layout
+///     tolerance: Distance tolerance for node merging (default 0.0005 = 0.5mm)
+///
+/// Returns:
+///     dict: Healing summary containing:
+///         - merged_nodes: Nodes merged by the snap pass
+///         - split_crossings: Edge splits at crossings and T-junctions
+///         - merged_colinear: Colinear edge pairs merged
+///         - room_count: Rooms after rebuild, including the exterior
+///         - node_count, edge_count: Size of the healed graph
+///         - rooms: Interior rooms, each with id, area, centroid, and boundary_count
+///
+/// Example:
+///     >>> walls = [create_wall((0, 2), (4, 2), 3.0, 0.2), create_wall((2, 0), (2, 4), 3.0, 0.2)]
+///     >>> heal_graph(walls)['split_crossings']
+///     1
+#[pyfunction]
+#[pyo3(signature = (walls, tolerance=0.0005))]
+pub fn heal_graph(walls: Vec<PyWall>, tolerance: f64) -> PyResult<Py<PyDict>> {
+    let mut graph = TopologyGraph::with_tolerance(tolerance);
+    for wall in &walls {
+        let start = [wall.inner.baseline.start.x, wall.inner.baseline.start.y];
+        let end = [wall.inner.baseline.end.x, wall.inner.baseline.end.y];
         let edge_data = EdgeData::wall(wall.inner.thickness, wall.inner.height);
         graph.add_edge(start, end, edge_data);
     }
 
-    // Detect rooms
-    graph.rebuild_rooms();
-
-    // Gather statistics
-    let node_count = graph.node_count();
-    let edge_count = graph.edge_count();
-    let room_count = graph.room_count();
-    let interior_rooms = graph.interior_rooms();
-    let interior_room_count = interior_rooms.len();
-
-    // Check connectivity: a connected graph has all nodes reachable
-    // For a simple check: connected if node_count <= edge_count + 1 for tree,
-    // or more edges for cyclic graphs
-    let is_connected = node_count > 0 && edge_count >= node_count - 1;
+    let merged_nodes = fixup::snap_merge_nodes(&mut graph, tolerance);
+    let split_crossings = fixup::split_crossings(&mut graph);
+    let merged_colinear = fixup::merge_colinear(&mut graph);
+    let room_count = fixup::rooms_rebuild_dirty(&mut graph, &Delta::new());
 
     Python::with_gil(|py| {
         let dict = PyDict::new_bound(py);
-        dict.set_item("node_count", node_count)?;
-        dict.set_item("edge_count", edge_count)?;
+        dict.set_item("merged_nodes", merged_nodes)?;
+        dict.set_item("split_crossings", split_crossings)?;
+        dict.set_item("merged_colinear", merged_colinear)?;
         dict.set_item("room_count", room_count)?;
-        dict.set_item("interior_room_count", interior_room_count)?;
-        dict.set_item("is_connected", is_connected)?;
+        dict.set_item("node_count", graph.node_count())?;
+        dict.set_item("edge_count", graph.edge_count())?;
 
-        // Add detailed room data
-        let room_list: Vec<Py<PyDict>> = interior_rooms
+        let room_list: Vec<Py<PyDict>> = graph
+            .interior_rooms()
             .iter()
             .map(|room| {
                 let rd = PyDict::new_bound(py);
@@ -782,13 +2550,125 @@ pub fn analyze_wall_topology(walls: Vec<PyWall>, tolerance: f64) -> PyResult<Py<
                 rd.unbind()
             })
             .collect();
-
         dict.set_item("rooms", PyList::new_bound(py, room_list))?;
 
         Ok(dict.unbind())
     })
 }
 
+/// Snap a cursor position to the wall network.
+///
+/// Candidates are nodes, free wall ends, wall midpoints, apparent
+/// intersections of wall extensions, horizontal/vertical alignment with
+/// nearby nodes, perpendicular feet on walls, and grid points. The best is
+/// chosen by kind priority, then distance; all lie within `tolerance`.
+///
+/// Args:
+///     walls_or_graph: A TopologyGraph, or a list of walls
+///     point: Cursor position (x, y)
+///     tolerance: Snap distance (default 0.01 = 10mm)
+///     grid_spacing: Snap grid spacing, or None for no grid
+///     grid_origin: A point where grid lines cross
+///     alignment_range: How far away a node can be and still align
+///
+/// Returns:
+///     dict with kind, point, sources (node and edge IDs), priority, and
+///     distance, or None if nothing is within tolerance
+///
+/// Example:
+///     >>> walls = [create_wall((0, 0), (4, 0), 3.0, 0.2)]
+///     >>> snap_point(walls, (2.003, 0.004))['kind']
+///     'midpoint'
+#[pyfunction]
+#[pyo3(signature = (walls_or_graph, point, tolerance=0.01, grid_spacing=None, grid_origin=(0.0, 0.0), alignment_range=10.0))]
+pub fn snap_point(
+    walls_or_graph: &Bound<'_, PyAny>,
+    point: (f64, f64),
+    tolerance: f64,
+    grid_spacing: Option<f64>,
+    grid_origin: (f64, f64),
+    alignment_range: f64,
+) -> PyResult<Option<Py<PyDict>>> {
+    let shared = walls_or_graph.extract::<PyRef<PyTopologyGraph>>().ok();
+    let built;
+    let graph = match &shared {
+        Some(graph) => &graph.inner,
+        None => {
+            let walls: Vec<PyWall> = walls_or_graph
+                .extract()
+                .map_err(|_| PyTypeError::new_err("expected a TopologyGraph or a list of walls"))?;
+            let mut graph = TopologyGraph::with_tolerance(0.0005);
+            for wall in &walls {
+                let start = [wall.inner.baseline.start.x, wall.inner.baseline.start.y];
+                let end = [wall.inner.baseline.end.x, wall.inner.baseline.end.y];
+                graph.add_edge(
+                    start,
+                    end,
+                    EdgeData::wall(wall.inner.thickness, wall.inner.height),
+                );
+            }
+            built = graph;
+            &built
+        }
+    };
+
+    let mut engine = SnapEngine::new()
+        .with_tolerance(tolerance)
+        .with_alignment_range(alignment_range);
+    if let Some(spacing) = grid_spacing {
+        engine =
+            engine.with_grid(SnapGrid::new(spacing).with_origin([grid_origin.0, grid_origin.1]));
+    }
+
+    let Some(snap) = engine.snap(graph, [point.0, point.1]) else {
+        return Ok(None);
+    };
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("kind", snap.kind.name())?;
+        dict.set_item("point", (snap.point[0], snap.point[1]))?;
+        dict.set_item("sources", snap.sources)?;
+        dict.set_item("priority", snap.priority)?;
+        dict.set_item("distance", snap.distance)?;
+        Ok(Some(dict.unbind()))
+    })
+}
+
+/// An element as a Python (id, type, bbox_min, bbox_max) tuple.
+pub(super) type ClashBoxTuple = (String, String, (f64, f64, f64), (f64, f64, f64));
+
+/// Convert Python element tuples to clash elements, giving unparseable IDs
+/// a fresh one.
+pub(super) fn clash_elements(items: Vec<ClashBoxTuple>) -> Vec<ClashElement> {
+    items
+        .into_iter()
+        .map(|(id_str, element_type, min, max)| {
+            let id = uuid::Uuid::parse_str(&id_str).unwrap_or_else(|_| uuid::Uuid::new_v4());
+            let bbox = BoundingBox3::new(
+                Point3::new(min.0, min.1, min.2),
+                Point3::new(max.0, max.1, max.2),
+            );
+            ClashElement::new(id, element_type, bbox)
+        })
+        .collect()
+}
+
+/// Build a clash detector from the Python keyword arguments.
+pub(super) fn clash_detector(
+    tolerance: f64,
+    clearance: f64,
+    ignore_same_type: bool,
+) -> ClashDetector {
+    let mut filter = ClashFilter::new();
+    if clearance > 0.0 {
+        filter = filter.with_clearance(clearance);
+    }
+    if ignore_same_type {
+        filter = filter.ignore_same_type();
+    }
+    ClashDetector::new(tolerance).with_filter(filter)
+}
+
 /// Detect clashes (geometric intersections) between BIM elements.
 ///
 /// This function identifies where elements occupy the same space (hard clashes),
@@ -815,6 +2695,8 @@ pub fn analyze_wall_topology(walls: Vec<PyWall>, tolerance: f64) -> PyResult<Py<
 ///         - clash_point: (x, y, z) approximate location of clash
 ///         - distance: Penetration depth or clearance gap
 ///         - overlap_volume: Volume of overlap region (for hard clashes)
+///         - overlap_min: (x, y, z) minimum corner of the overlap box, or None
+///         - overlap_max: (x, y, z) maximum corner of the overlap box, or None
 ///
 /// Example:
 ///     >>> walls = create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
@@ -825,59 +2707,81 @@ pub fn analyze_wall_topology(walls: Vec<PyWall>, tolerance: f64) -> PyResult<Py<
 #[pyfunction]
 #[pyo3(signature = (elements, tolerance=0.001, clearance=0.0, ignore_same_type=false))]
 pub fn detect_clashes(
-    elements: Vec<(String, String, (f64, f64, f64), (f64, f64, f64))>,
+    elements: Vec<ClashBoxTuple>,
+    tolerance: f64,
+    clearance: f64,
+    ignore_same_type: bool,
+) -> PyResult<Py<PyList>> {
+    let clash_elements = clash_elements(elements);
+    let detector = clash_detector(tolerance, clearance, ignore_same_type);
+    let clashes = detector.detect_clashes_in_list(&clash_elements);
+
+    // Convert to Python list of dicts
+    Python::with_gil(|py| {
+        let clash_list = clashes
+            .iter()
+            .map(|clash| clash_to_dict(py, clash))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        Ok(PyList::new_bound(py, clash_list).unbind())
+    })
+}
+
+/// Detect clashes between meshed elements.
+///
+/// Like `detect_clashes`, but overlapping bounding boxes are only reported as
+/// hard clashes if the meshes actually intersect, which avoids false positives
+/// for diagonal elements.
+///
+/// Args:
+///     elements: List of tuples (element_id, element_type, mesh)
+///         - element_id: UUID string identifying the element
+///         - element_type: Type name (e.g., "wall", "door", "floor")
+///         - mesh: The element's TriangleMesh
+///     tolerance: Distance tolerance for overlap detection (default 0.001 = 1mm)
+///     clearance: Minimum clearance for soft clash detection (default 0.0 = disabled)
+///     ignore_same_type: Whether to ignore clashes between same element types (default False)
+///
+/// Returns:
+///     list[dict]: List of detected clashes, in the same form as `detect_clashes`.
+///         For hard clashes, clash_point is an estimated penetration point.
+///
+/// Example:
+///     >>> a = create_wall((0, 0), (4, 4), 3.0, 0.2)
+///     >>> b = create_wall((0, 1.5), (4, 5.5), 3.0, 0.2)
+///     >>> elements = [(str(w.id()), "wall", w.to_mesh()) for w in (a, b)]
+///     >>> len(detect_mesh_clashes(elements))
+///     0
+#[pyfunction]
+#[pyo3(signature = (elements, tolerance=0.001, clearance=0.0, ignore_same_type=false))]
+pub fn detect_mesh_clashes(
+    elements: Vec<(String, String, PyTriangleMesh)>,
     tolerance: f64,
     clearance: f64,
     ignore_same_type: bool,
 ) -> PyResult<Py<PyList>> {
-    use crate::spatial::{ClashDetector, ClashElement, ClashFilter};
-    use pensaer_math::{BoundingBox3, Point3};
     use uuid::Uuid;
 
-    // Convert input to ClashElement list
-    let clash_elements: Vec<ClashElement> = elements
+    let elements: Vec<(Uuid, String, TriangleMesh)> = elements
         .into_iter()
-        .map(|(id_str, element_type, min, max)| {
+        .map(|(id_str, element_type, mesh)| {
             let id = Uuid::parse_str(&id_str).unwrap_or_else(|_| Uuid::new_v4());
-            let bbox = BoundingBox3::new(
-                Point3::new(min.0, min.1, min.2),
-                Point3::new(max.0, max.1, max.2),
-            );
-            ClashElement::new(id, element_type, bbox)
+            (id, element_type, mesh.inner)
         })
         .collect();
+    let refs: Vec<(Uuid, String, &TriangleMesh)> = elements
+        .iter()
+        .map(|(id, element_type, mesh)| (*id, element_type.clone(), mesh))
+        .collect();
 
-    // Create filter
-    let mut filter = ClashFilter::new();
-    if clearance > 0.0 {
-        filter = filter.with_clearance(clearance);
-    }
-    if ignore_same_type {
-        filter = filter.ignore_same_type();
-    }
-
-    // Create detector and run
-    let detector = ClashDetector::new(tolerance).with_filter(filter);
-    let clashes = detector.detect_clashes_in_list(&clash_elements);
+    let detector = clash_detector(tolerance, clearance, ignore_same_type);
+    let clashes = detector.detect_clashes_meshes(&refs);
 
-    // Convert to Python list of dicts
     Python::with_gil(|py| {
-        let clash_list: Vec<Py<PyDict>> = clashes
+        let clash_list = clashes
             .iter()
-            .map(|clash| {
-                let dict = PyDict::new_bound(py);
-                dict.set_item("id", clash.id.to_string()).ok();
-                dict.set_item("element_a_id", clash.element_a_id.to_string()).ok();
-                dict.set_item("element_b_id", clash.element_b_id.to_string()).ok();
-                dict.set_item("element_a_type", &clash.element_a_type).ok();
-                dict.set_item("element_b_type", &clash.element_b_type).ok();
-                dict.set_item("clash_type", clash.clash_type.name()).ok();
-                dict.set_item("clash_point", clash.clash_point).ok();
-                dict.set_item("distance", clash.distance).ok();
-                dict.set_item("overlap_volume", clash.overlap_volume).ok();
-                dict.unbind()
-            })
-            .collect();
+            .map(|clash| clash_to_dict(py, clash))
+            .collect::<PyResult<Vec<_>>>()?;
 
         Ok(PyList::new_bound(py, clash_list).unbind())
     })
@@ -903,63 +2807,440 @@ pub fn detect_clashes(
 #[pyfunction]
 #[pyo3(signature = (set_a, set_b, tolerance=0.001, clearance=0.0))]
 pub fn detect_clashes_between_sets(
-    set_a: Vec<(String, String, (f64, f64, f64), (f64, f64, f64))>,
-    set_b: Vec<(String, String, (f64, f64, f64), (f64, f64, f64))>,
+    set_a: Vec<ClashBoxTuple>,
+    set_b: Vec<ClashBoxTuple>,
     tolerance: f64,
     clearance: f64,
 ) -> PyResult<Py<PyList>> {
-    use crate::spatial::{ClashDetector, ClashElement, ClashFilter};
-    use pensaer_math::{BoundingBox3, Point3};
-    use uuid::Uuid;
+    let elements_a = clash_elements(set_a);
+    let elements_b = clash_elements(set_b);
+    let detector = clash_detector(tolerance, clearance, false);
+    let clashes = detector.detect_clashes_between(&elements_a, &elements_b);
 
-    // Convert inputs to ClashElement lists
-    let convert = |items: Vec<(String, String, (f64, f64, f64), (f64, f64, f64))>| {
-        items
-            .into_iter()
-            .map(|(id_str, element_type, min, max)| {
-                let id = Uuid::parse_str(&id_str).unwrap_or_else(|_| Uuid::new_v4());
-                let bbox = BoundingBox3::new(
-                    Point3::new(min.0, min.1, min.2),
-                    Point3::new(max.0, max.1, max.2),
-                );
-                ClashElement::new(id, element_type, bbox)
-            })
-            .collect::<Vec<_>>()
+    // Convert to Python list of dicts
+    Python::with_gil(|py| {
+        let clash_list = clashes
+            .iter()
+            .map(|clash| clash_to_dict(py, clash))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        Ok(PyList::new_bound(py, clash_list).unbind())
+    })
+}
+
+/// Describe a clash for Python.
+pub(super) fn clash_to_dict<'py>(py: Python<'py>, clash: &Clash) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("id", clash.id.to_string())?;
+    dict.set_item("element_a_id", clash.element_a_id.to_string())?;
+    dict.set_item("element_b_id", clash.element_b_id.to_string())?;
+    dict.set_item("element_a_type", &clash.element_a_type)?;
+    dict.set_item("element_b_type", &clash.element_b_type)?;
+    dict.set_item("clash_type", clash.clash_type.name())?;
+    dict.set_item("clash_point", clash.clash_point)?;
+    dict.set_item("distance", clash.distance)?;
+    dict.set_item("overlap_volume", clash.overlap_volume)?;
+    let min = clash.overlap_bbox.map(|b| (b.min.x, b.min.y, b.min.z));
+    let max = clash.overlap_bbox.map(|b| (b.max.x, b.max.y, b.max.z));
+    dict.set_item("overlap_min", min)?;
+    dict.set_item("overlap_max", max)?;
+    Ok(dict)
+}
+
+/// Create a building level (storey).
+///
+/// Args:
+///     name: Level name (e.g., "Level 1")
+///     elevation: Elevation of the level datum
+///     height: Floor-to-floor height (default: 3.0)
+///
+/// Returns:
+///     PyLevel: The created level
+///
+/// Example:
+///     >>> level = create_level("Level 2", elevation=3.0)
+///     >>> level.height
+///     3.0
+#[pyfunction]
+#[pyo3(signature = (name, elevation, height=3.0))]
+pub fn create_level(name: &str, elevation: f64, height: f64) -> PyResult<PyLevel> {
+    PyLevel::new(name, elevation, height)
+}
+
+/// Place a building element on a level.
+///
+/// Args:
+///     building: Building containing the element and level
+///     element_id: UUID of the element
+///     level_id: UUID of the level
+///
+/// Example:
+///     >>> building = Building("Office")
+///     >>> level_id = building.add_level(create_level("Level 2", 3.0))
+///     >>> wall_id = building.add_wall(create_wall((0, 0), (5, 0), 3.0, 0.2))
+///     >>> assign_to_level(building, wall_id, level_id)
+#[pyfunction]
+pub fn assign_to_level(
+    mut building: PyRefMut<'_, PyBuilding>,
+    element_id: &str,
+    level_id: &str,
+) -> PyResult<()> {
+    building.assign_to_level(element_id, level_id)
+}
+
+/// Save a group of walls as a reusable template.
+///
+/// The walls are captured with their openings, the doors in them, and
+/// their metadata.
+///
+/// Args:
+///     model: Model containing the walls
+///     ids: IDs of walls to include, or of doors selecting their walls
+///     origin: Template origin as (x, y) tuple
+///     axis: Template axis direction as (x, y) tuple
+///     name: Template name (default: "Template")
+///
+/// Returns:
+///     PyTemplate: Template with positions relative to origin and axis
+///
+/// Example:
+///     >>> t = create_template(model, ids, origin=(0, 0), axis=(1, 0))
+///     >>> t.version
+///     1
+#[pyfunction]
+#[pyo3(signature = (model, ids, origin, axis, name="Template"))]
+pub fn create_template(
+    model: &PyModel,
+    ids: Vec<String>,
+    origin: (f64, f64),
+    axis: (f64, f64),
+    name: &str,
+) -> PyResult<PyTemplate> {
+    let element_ids = parse_uuids(&ids)?;
+    templates::create_template(
+        &model.inner,
+        &element_ids,
+        [origin.0, origin.1],
+        [axis.0, axis.1],
+        name,
+    )
+    .map(|t| PyTemplate { inner: t })
+    .map_err(geometry_error)
+}
+
+/// Stamp a template into a model with fresh IDs, then heal the model.
+///
+/// Args:
+///     model: Model to place the template in
+///     template: Template to place
+///     at: Position of the template origin as (x, y) tuple
+///     rotate: Counter-clockwise rotation in degrees (default: 0)
+///     mirror: Mirror across the template axis (default: False)
+///
+/// Returns:
+///     dict: Placement containing:
+///         - instance_id: ID of the placed instance
+///         - template_id: ID of the template
+///         - template_version: Template version used
+///         - id_map: Template-local ID -> list of new model IDs; a wall
+///           maps to the walls covering it after healing, in order
+///
+/// Example:
+///     >>> result = place_template(model, t, at=(12, 4), rotate=90, mirror=False)
+///     >>> len(result['id_map'])
+///     4
+#[pyfunction]
+#[pyo3(signature = (model, template, at, rotate=0.0, mirror=false))]
+pub fn place_template(
+    mut model: PyRefMut<'_, PyModel>,
+    template: &PyTemplate,
+    at: (f64, f64),
+    rotate: f64,
+    mirror: bool,
+) -> PyResult<Py<PyDict>> {
+    let placement = TemplatePlacement {
+        at: [at.0, at.1],
+        rotation_deg: rotate,
+        mirror,
     };
+    let instance = templates::place_template(&mut model.inner, &template.inner, &placement)
+        .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))?;
 
-    let elements_a = convert(set_a);
-    let elements_b = convert(set_b);
+    Python::with_gil(|py| {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("instance_id", instance.id.to_string())?;
+        dict.set_item("template_id", instance.template_id.to_string())?;
+        dict.set_item("template_version", instance.template_version)?;
+
+        let id_map = PyDict::new_bound(py);
+        for (local_id, model_ids) in &instance.id_map {
+            let model_ids: Vec<String> = model_ids.iter().map(|id| id.to_string()).collect();
+            id_map.set_item(local_id.to_string(), model_ids)?;
+        }
+        dict.set_item("id_map", id_map)?;
 
-    // Create filter
-    let filter = if clearance > 0.0 {
-        ClashFilter::new().with_clearance(clearance)
-    } else {
-        ClashFilter::new()
+        Ok(dict.unbind())
+    })
+}
+
+/// Find the rooms of a model that are corridors.
+///
+/// A corridor is a room no wider than `max_width`, at least
+/// `min_aspect_ratio` times as long as it is wide, with doors into at least
+/// `connects_n_rooms` other rooms. Rooms are rebuilt first.
+///
+/// Args:
+///     model: Model whose rooms are classified
+///     max_width: Maximum corridor width (default: 2.4)
+///     min_aspect_ratio: Minimum length-to-width ratio (default: 3.0)
+///     connects_n_rooms: Minimum number of rooms reached through doors (default: 2)
+///
+/// Returns:
+///     list[dict]: Corridors, each containing:
+///         - room_id: ID of the corridor room
+///         - width: Estimated clear width
+///         - length: End-to-end length
+///         - connected_rooms: IDs of rooms reached through doors
+///         - centerline: Centerline branches as lists of (x, y) points
+///         - centerline_length: Total centerline length, door to door
+///
+/// Example:
+///     >>> found = corridors(model)
+///     >>> len(found[0]['connected_rooms'])
+///     6
+#[pyfunction]
+#[pyo3(signature = (model, max_width=2.4, min_aspect_ratio=3.0, connects_n_rooms=2))]
+pub fn corridors(
+    mut model: PyRefMut<'_, PyModel>,
+    max_width: f64,
+    min_aspect_ratio: f64,
+    connects_n_rooms: usize,
+) -> PyResult<Py<PyList>> {
+    let criteria = CorridorCriteria {
+        max_width,
+        min_aspect_ratio,
+        connects_n_rooms,
     };
+    model.inner.graph.rebuild_rooms();
+    let found = classify_corridors(&model.inner.graph, &criteria);
 
-    // Create detector and run
-    let detector = ClashDetector::new(tolerance).with_filter(filter);
-    let clashes = detector.detect_clashes_between(&elements_a, &elements_b);
+    Python::with_gil(|py| {
+        let list = PyList::empty_bound(py);
+        for corridor in &found {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("room_id", corridor.room_id.0.to_string())?;
+            dict.set_item("width", corridor.width)?;
+            dict.set_item("length", corridor.length)?;
+            let connected: Vec<String> = corridor
+                .connected_rooms
+                .iter()
+                .map(|id| id.0.to_string())
+                .collect();
+            dict.set_item("connected_rooms", connected)?;
+            let branches: Vec<Vec<(f64, f64)>> = corridor
+                .centerline
+                .edges
+                .iter()
+                .map(|e| e.path.iter().map(|p| (p[0], p[1])).collect())
+                .collect();
+            dict.set_item("centerline", branches)?;
+            dict.set_item("centerline_length", corridor.centerline.length())?;
+            list.append(dict)?;
+        }
+        Ok(list.unbind())
+    })
+}
+
+/// Build the walkable circulation network of a model.
+///
+/// Corridor centerlines run between doors and junctions; every other room
+/// has a node joined to each of its doors by a straight path. Rooms are
+/// rebuilt first.
+///
+/// Args:
+///     model: Model to analyze
+///     max_width: Maximum corridor width (default: 2.4)
+///     min_aspect_ratio: Minimum corridor length-to-width ratio (default: 3.0)
+///     connects_n_rooms: Minimum number of rooms a corridor reaches (default: 2)
+///
+/// Returns:
+///     dict: Network containing:
+///         - nodes: List of dicts with kind ("door", "junction", "end", "room"),
+///           position (x, y), and id (door or room ID, None otherwise)
+///         - edges: List of dicts with from, to (node indices), length,
+///           room_id, and path
+///         - total_length: Sum of edge lengths
+///         - is_connected: Whether every node is reachable from every other
+///
+/// Example:
+///     >>> network = circulation_network(model)
+///     >>> network['is_connected']
+///     True
+#[pyfunction]
+#[pyo3(signature = (model, max_width=2.4, min_aspect_ratio=3.0, connects_n_rooms=2))]
+pub fn circulation_network(
+    mut model: PyRefMut<'_, PyModel>,
+    max_width: f64,
+    min_aspect_ratio: f64,
+    connects_n_rooms: usize,
+) -> PyResult<Py<PyDict>> {
+    let criteria = CorridorCriteria {
+        max_width,
+        min_aspect_ratio,
+        connects_n_rooms,
+    };
+    model.inner.graph.rebuild_rooms();
+    let network = corridor::circulation_network(&model.inner.graph, &criteria);
 
-    // Convert to Python list of dicts
     Python::with_gil(|py| {
-        let clash_list: Vec<Py<PyDict>> = clashes
-            .iter()
-            .map(|clash| {
-                let dict = PyDict::new_bound(py);
-                dict.set_item("id", clash.id.to_string()).ok();
-                dict.set_item("element_a_id", clash.element_a_id.to_string()).ok();
-                dict.set_item("element_b_id", clash.element_b_id.to_string()).ok();
-                dict.set_item("element_a_type", &clash.element_a_type).ok();
-                dict.set_item("element_b_type", &clash.element_b_type).ok();
-                dict.set_item("clash_type", clash.clash_type.name()).ok();
-                dict.set_item("clash_point", clash.clash_point).ok();
-                dict.set_item("distance", clash.distance).ok();
-                dict.set_item("overlap_volume", clash.overlap_volume).ok();
-                dict.unbind()
-            })
-            .collect();
+        let nodes = PyList::empty_bound(py);
+        for node in &network.nodes {
+            let (kind, id) = match node.kind {
+                CirculationNodeKind::Door(id) => ("door", Some(id.to_string())),
+                CirculationNodeKind::Junction => ("junction", None),
+                CirculationNodeKind::End => ("end", None),
+                CirculationNodeKind::Room(id) => ("room", Some(id.0.to_string())),
+            };
+            let dict = PyDict::new_bound(py);
+            dict.set_item("kind", kind)?;
+            dict.set_item("position", (node.position[0], node.position[1]))?;
+            dict.set_item("id", id)?;
+            nodes.append(dict)?;
+        }
 
-        Ok(PyList::new_bound(py, clash_list).unbind())
+        let edges = PyList::empty_bound(py);
+        for edge in &network.edges {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("from", edge.from)?;
+            dict.set_item("to", edge.to)?;
+            dict.set_item("length", edge.length)?;
+            dict.set_item("room_id", edge.room_id.0.to_string())?;
+            let path: Vec<(f64, f64)> = edge.path.iter().map(|p| (p[0], p[1])).collect();
+            dict.set_item("path", path)?;
+            edges.append(dict)?;
+        }
+
+        let dict = PyDict::new_bound(py);
+        dict.set_item("nodes", nodes)?;
+        dict.set_item("edges", edges)?;
+        dict.set_item("total_length", network.total_length())?;
+        dict.set_item("is_connected", network.is_connected())?;
+        Ok(dict.unbind())
     })
 }
+
+/// Start a background mesh worker for a building.
+///
+/// Args:
+///     model: Building whose elements will be meshed
+///     threads: Number of worker threads (default: available parallelism)
+///
+/// Returns:
+///     PyMeshWorker: Worker accepting enqueue(ids, priorities), invalidate(ids)
+///         after model edits, and poll()
+///
+/// Example:
+///     >>> worker = start_mesh_worker(building)
+///     >>> worker.enqueue([wall_id], [0.0])
+///     1
+///     >>> ready = worker.poll()
+#[cfg(feature = "parallel")]
+#[pyfunction]
+#[pyo3(signature = (model, threads=None))]
+pub fn start_mesh_worker(model: Py<PyBuilding>, threads: Option<usize>) -> PyMeshWorker {
+    let threads = threads.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+    PyMeshWorker {
+        inner: Some(crate::mesh::MeshWorker::new(threads)),
+        building: model,
+    }
+}
+
+/// Mesh walls, floors, roofs, doors, and windows in parallel.
+///
+/// Doors and windows are placed in their host wall's opening when the host
+/// is among `walls`. The GIL is released while meshing and merging.
+///
+/// Args:
+///     walls: Walls to mesh
+///     floors: Floors to mesh (optional)
+///     roofs: Roofs to mesh (optional)
+///     doors: Doors to mesh (optional)
+///     windows: Windows to mesh (optional)
+///     merge: Merge everything into one mesh (default True)
+///
+/// Returns:
+///     dict: With merge, 'mesh' (PyTriangleMesh) and 'ranges' mapping each
+///         element ID to its (start, end) triangle range in the mesh, for
+///         picking; without, 'meshes' mapping element IDs to their meshes.
+///         'errors' maps the IDs of elements that failed to mesh to why.
+///
+/// Example:
+///     >>> walls = create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
+///     >>> result = generate_building_mesh(walls)
+///     >>> len(result['ranges'])
+///     4
+#[cfg(feature = "parallel")]
+#[pyfunction]
+#[pyo3(signature = (walls, floors=None, roofs=None, doors=None, windows=None, merge=true))]
+pub fn generate_building_mesh(
+    py: Python<'_>,
+    walls: Vec<PyWall>,
+    floors: Option<Vec<PyFloor>>,
+    roofs: Option<Vec<PyRoof>>,
+    doors: Option<Vec<PyDoor>>,
+    windows: Option<Vec<PyWindow>>,
+    merge: bool,
+) -> PyResult<Py<PyDict>> {
+    use crate::elements::Building;
+    use crate::mesh::{generate_building_meshes, merge_element_meshes};
+
+    let mut building = Building::new("batch");
+    for wall in walls {
+        building.add_wall(wall.inner);
+    }
+    for floor in floors.unwrap_or_default() {
+        building.add_floor(floor.inner);
+    }
+    for roof in roofs.unwrap_or_default() {
+        building.add_roof(roof.inner);
+    }
+    for door in doors.unwrap_or_default() {
+        building.add_door(door.inner);
+    }
+    for window in windows.unwrap_or_default() {
+        building.add_window(window.inner);
+    }
+
+    let results = py.allow_threads(|| generate_building_meshes(&building));
+    let errors = PyDict::new_bound(py);
+    let mut meshes = Vec::with_capacity(results.len());
+    for (id, result) in results {
+        match result {
+            Ok(mesh) => meshes.push((id, mesh)),
+            Err(e) => errors.set_item(id.to_string(), e.to_string())?,
+        }
+    }
+
+    let dict = PyDict::new_bound(py);
+    if merge {
+        let mesh = py.allow_threads(|| merge_element_meshes(meshes.iter().map(|(id, m)| (*id, m))));
+        let ranges = PyDict::new_bound(py);
+        for (name, range) in &mesh.groups {
+            ranges.set_item(name, (range.start, range.end))?;
+        }
+        dict.set_item("mesh", PyTriangleMesh { inner: mesh }.into_py(py))?;
+        dict.set_item("ranges", ranges)?;
+    } else {
+        let by_id = PyDict::new_bound(py);
+        for (id, mesh) in meshes {
+            by_id.set_item(id.to_string(), PyTriangleMesh { inner: mesh }.into_py(py))?;
+        }
+        dict.set_item("meshes", by_id)?;
+    }
+    dict.set_item("errors", errors)?;
+    Ok(dict.unbind())
+}
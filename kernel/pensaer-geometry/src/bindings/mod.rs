@@ -1,8 +1,9 @@
-// Allow common clippy warnings in bindings (PyO3 boilerplate)
+// Allow common clippy and cfg warnings in bindings (PyO3 boilerplate)
 #![allow(
     clippy::useless_conversion,
     clippy::wildcard_in_or_patterns,
-    clippy::new_without_default
+    clippy::new_without_default,
+    unexpected_cfgs
 )]
 
 //! PyO3 Python bindings for the Pensaer geometry kernel.
@@ -42,30 +43,60 @@ use pyo3::prelude::*;
 /// Python module for Pensaer geometry operations.
 ///
 /// This module exposes:
-/// - Math primitives: Point2, Point3, Vector2, Vector3, BoundingBox3
-/// - BIM elements: Wall, Floor, Door, Window, Room
-/// - Mesh operations: TriangleMesh
+/// - Math primitives: Point2, Point3, Vector2, Vector3, BoundingBox3, Transform
+/// - BIM elements: Wall, Floor, Ceiling, Roof, Door, Window, Skylight, Room, Railing, Stair
+/// - Context meshes: ContextMesh (imported OBJ/PLY reference geometry)
+/// - Levels: Level, Building
+/// - Templates: Model, Template
+/// - Topology: TopologyGraph
+/// - Mesh operations: TriangleMesh, MeshArray (numpy buffers)
 /// - Utility functions: create_wall, create_floor, place_door, etc.
+/// - Errors: PensaerGeometryError (a ValueError with `code` and `details`)
 #[pymodule]
 fn pensaer_geometry(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add(
+        "PensaerGeometryError",
+        m.py().get_type_bound::<PensaerGeometryError>(),
+    )?;
+
     // Math types
     m.add_class::<PyPoint2>()?;
     m.add_class::<PyPoint3>()?;
     m.add_class::<PyVector2>()?;
     m.add_class::<PyVector3>()?;
     m.add_class::<PyBoundingBox3>()?;
+    m.add_class::<PyTransform3>()?;
 
     // Geometry elements
     m.add_class::<PyWall>()?;
     m.add_class::<PyFloor>()?;
+    m.add_class::<PyCeiling>()?;
     m.add_class::<PyRoof>()?;
     m.add_class::<PyDoor>()?;
     m.add_class::<PyWindow>()?;
+    m.add_class::<PyCatalog>()?;
     m.add_class::<PyRoom>()?;
+    m.add_class::<PyRailing>()?;
+    m.add_class::<PyStair>()?;
+    m.add_class::<PyContextMesh>()?;
     m.add_class::<PyWallOpening>()?;
+    m.add_class::<PyRoofOpening>()?;
+    m.add_class::<PySkylight>()?;
+
+    // Levels
+    m.add_class::<PyLevel>()?;
+    m.add_class::<PyBuilding>()?;
+
+    // Wall network model and templates
+    m.add_class::<PyModel>()?;
+    m.add_class::<PyTemplate>()?;
+    m.add_class::<PyTopologyGraph>()?;
+    #[cfg(feature = "parallel")]
+    m.add_class::<PyMeshWorker>()?;
 
     // Mesh
     m.add_class::<PyTriangleMesh>()?;
+    m.add_class::<PyMeshArray>()?;
 
     // Join types
     m.add_class::<PyWallJoin>()?;
@@ -74,24 +105,69 @@ fn pensaer_geometry(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Functions
     m.add_function(wrap_pyfunction!(create_wall, m)?)?;
     m.add_function(wrap_pyfunction!(create_floor, m)?)?;
+    m.add_function(wrap_pyfunction!(create_floors_from_walls, m)?)?;
     m.add_function(wrap_pyfunction!(create_room, m)?)?;
+    m.add_function(wrap_pyfunction!(create_room_from_boundary, m)?)?;
+    m.add_function(wrap_pyfunction!(create_ceiling_from_room, m)?)?;
     m.add_function(wrap_pyfunction!(place_door, m)?)?;
     m.add_function(wrap_pyfunction!(place_window, m)?)?;
+    m.add_function(wrap_pyfunction!(load_catalog, m)?)?;
+    m.add_function(wrap_pyfunction!(default_catalog, m)?)?;
     m.add_function(wrap_pyfunction!(detect_joins, m)?)?;
     m.add_function(wrap_pyfunction!(compute_join_geometry, m)?)?;
     m.add_function(wrap_pyfunction!(mesh_to_obj, m)?)?;
     m.add_function(wrap_pyfunction!(validate_mesh, m)?)?;
+    m.add_function(wrap_pyfunction!(import_context_mesh, m)?)?;
+    m.add_function(wrap_pyfunction!(raycast_mesh, m)?)?;
+    m.add_function(wrap_pyfunction!(pick, m)?)?;
+    m.add_function(wrap_pyfunction!(load_property_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(set_deterministic_ids, m)?)?;
     m.add_function(wrap_pyfunction!(create_rectangular_walls, m)?)?;
     m.add_function(wrap_pyfunction!(create_simple_building, m)?)?;
+    m.add_function(wrap_pyfunction!(save_model, m)?)?;
+    m.add_function(wrap_pyfunction!(load_model, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_model, m)?)?;
     m.add_function(wrap_pyfunction!(merge_meshes, m)?)?;
+    m.add_function(wrap_pyfunction!(section_meshes, m)?)?;
+    m.add_function(wrap_pyfunction!(building_meshes, m)?)?;
     m.add_function(wrap_pyfunction!(create_roof, m)?)?;
+    m.add_function(wrap_pyfunction!(create_roof_from_walls, m)?)?;
     m.add_function(wrap_pyfunction!(attach_roof_to_walls, m)?)?;
+    m.add_function(wrap_pyfunction!(add_roof_opening, m)?)?;
+    m.add_function(wrap_pyfunction!(place_skylight, m)?)?;
     m.add_function(wrap_pyfunction!(create_opening, m)?)?;
+    m.add_function(wrap_pyfunction!(split_wall, m)?)?;
+    m.add_function(wrap_pyfunction!(trim_wall_to, m)?)?;
+    m.add_function(wrap_pyfunction!(change_join_type, m)?)?;
+    m.add_function(wrap_pyfunction!(offset_wall, m)?)?;
+    m.add_function(wrap_pyfunction!(offset_wall_chain, m)?)?;
+    m.add_function(wrap_pyfunction!(align_walls, m)?)?;
+    m.add_function(wrap_pyfunction!(distribute_walls_evenly, m)?)?;
+    m.add_function(wrap_pyfunction!(make_perpendicular, m)?)?;
+    m.add_function(wrap_pyfunction!(move_elements, m)?)?;
+    m.add_function(wrap_pyfunction!(rotate_elements, m)?)?;
+    m.add_function(wrap_pyfunction!(mirror_elements, m)?)?;
+    m.add_function(wrap_pyfunction!(create_railing_on_edge, m)?)?;
+    m.add_function(wrap_pyfunction!(create_stair, m)?)?;
     m.add_function(wrap_pyfunction!(detect_rooms, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_rooms_as_elements, m)?)?;
     m.add_function(wrap_pyfunction!(analyze_wall_topology, m)?)?;
+    m.add_function(wrap_pyfunction!(heal_graph, m)?)?;
+    m.add_function(wrap_pyfunction!(snap_point, m)?)?;
+    m.add_function(wrap_pyfunction!(create_level, m)?)?;
+    m.add_function(wrap_pyfunction!(assign_to_level, m)?)?;
+    m.add_function(wrap_pyfunction!(create_template, m)?)?;
+    m.add_function(wrap_pyfunction!(place_template, m)?)?;
+    m.add_function(wrap_pyfunction!(corridors, m)?)?;
+    m.add_function(wrap_pyfunction!(circulation_network, m)?)?;
+    #[cfg(feature = "parallel")]
+    m.add_function(wrap_pyfunction!(start_mesh_worker, m)?)?;
+    #[cfg(feature = "parallel")]
+    m.add_function(wrap_pyfunction!(generate_building_mesh, m)?)?;
 
     // Clash detection
     m.add_function(wrap_pyfunction!(detect_clashes, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_mesh_clashes, m)?)?;
     m.add_function(wrap_pyfunction!(detect_clashes_between_sets, m)?)?;
 
     Ok(())
@@ -3,20 +3,130 @@
 //! This module defines PyO3 wrapper types for all core geometry primitives
 //! and BIM elements, making them accessible from Python.
 
+use std::sync::RwLock;
+
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyBool, PyDict};
 use uuid::Uuid;
 
-use pensaer_math::{BoundingBox3, Point2, Point3, Vector2, Vector3};
+use pensaer_math::{
+    BoundingBox3, Point2, Point3, Polygon2, Quaternion, Transform3, Vector2, Vector3,
+};
 
-use crate::element::Element;
+use crate::analysis::quantities_for;
+use crate::catalog::{Catalog, DoorTypeDef, WindowTypeDef};
+use crate::element::{Element, ElementType, Level};
 use crate::elements::{
-    Door, DoorSwing, DoorType, Floor, FloorType, OpeningType, RidgeDirection, Roof, RoofType, Room,
-    Wall, WallOpening, WallType, Window, WindowType,
+    Building, Ceiling, ContextMesh, Door, DoorSwing, DoorType, Floor, FloorType, InfillType,
+    OffsetSide, OpeningType, RailProfile, Railing, RailingFinding, RailingRules, RidgeDirection,
+    Roof, RoofOpening, RoofOpeningType, RoofType, Room, Skylight, Stair, StairFinding, StairRules,
+    Wall, WallEndCap, WallOpening, WallType, Window, WindowType, DEFAULT_FRAME_THICKNESS,
+    DEFAULT_SWING_SEGMENTS,
 };
+use crate::error::GeometryError;
+use crate::exec::Context;
+use crate::fixup::{heal_all_tracked, Delta, RemovedRoomPolicy};
+use crate::hooks::{HookHandle, KernelEventKind};
 use crate::joins::{JoinResolver, JoinType, WallJoin};
+use crate::labels::{LabelKind, LabelLayoutOptions};
+#[cfg(feature = "parallel")]
+use crate::mesh::MeshWorker;
 use crate::mesh::TriangleMesh;
+use crate::properties::{FindingSeverity, PropertyFinding, PropertySchemaRegistry};
+use crate::report::{NumberFormat, TotalsPolicy};
+use crate::templates::{outdated_instances, WallTemplate};
+use crate::topology::{EdgeData, EdgeId, OpeningRef, RoomId, TopologyGraph};
+
+use super::functions::{clash_detector, clash_elements, clash_to_dict, ClashBoxTuple};
+
+pyo3::create_exception!(
+    pensaer_geometry,
+    PensaerGeometryError,
+    PyValueError,
+    "Kernel error with a stable `code` and a `details` dict of the offending values."
+);
+
+/// Convert a kernel error into a `PensaerGeometryError`.
+///
+/// The message is the error's `Display`; `code` and `details` come from
+/// [`GeometryError::error_code`] and [`GeometryError::details`].
+pub(crate) fn geometry_error(e: GeometryError) -> PyErr {
+    Python::with_gil(|py| {
+        let err = PensaerGeometryError::new_err(format!("{}", e));
+        let value = err.value_bound(py);
+        let details = py
+            .import_bound("json")
+            .and_then(|json| json.call_method1("loads", (e.details().to_string(),)));
+        if let Ok(details) = details {
+            let _ = value.setattr("details", details);
+        }
+        let _ = value.setattr("code", e.error_code());
+        err
+    })
+}
+
+/// Property schema loaded with `load_property_schema`, shared by all elements.
+static PROPERTY_SCHEMA: RwLock<Option<PropertySchemaRegistry>> = RwLock::new(None);
+
+/// Replace the shared property schema.
+pub(crate) fn set_property_schema(registry: PropertySchemaRegistry) {
+    let mut schema = PROPERTY_SCHEMA.write().unwrap_or_else(|e| e.into_inner());
+    *schema = Some(registry);
+}
+
+/// Run `f` with the shared property schema, or an empty lenient one.
+fn with_property_schema<R>(f: impl FnOnce(&PropertySchemaRegistry) -> R) -> R {
+    let schema = PROPERTY_SCHEMA.read().unwrap_or_else(|e| e.into_inner());
+    match schema.as_ref() {
+        Some(registry) => f(registry),
+        None => f(&PropertySchemaRegistry::default()),
+    }
+}
+
+/// Convert a Python property value to its stored string form.
+fn property_value_string(value: &Bound<'_, PyAny>) -> PyResult<String> {
+    if let Ok(flag) = value.downcast::<PyBool>() {
+        return Ok(flag.is_true().to_string());
+    }
+    Ok(value.str()?.to_string())
+}
+
+fn property_finding_dict(py: Python<'_>, finding: &PropertyFinding) -> PyResult<Py<PyDict>> {
+    let dict = PyDict::new_bound(py);
+    let kind = match finding {
+        PropertyFinding::UnknownKey { .. } => "unknown_key",
+        PropertyFinding::WrongType { .. } => "wrong_type",
+        PropertyFinding::NotAllowed { .. } => "not_allowed",
+        PropertyFinding::OutOfRange { .. } => "out_of_range",
+        PropertyFinding::MissingRequired { .. } => "missing_required",
+    };
+    dict.set_item("kind", kind)?;
+    dict.set_item("key", finding.key())?;
+    let severity = match finding.severity() {
+        FindingSeverity::Warning => "warning",
+        FindingSeverity::Error => "error",
+    };
+    dict.set_item("severity", severity)?;
+    match finding {
+        PropertyFinding::WrongType {
+            expected, value, ..
+        } => {
+            dict.set_item("expected", expected.name())?;
+            dict.set_item("value", value)?;
+        }
+        PropertyFinding::NotAllowed { value, .. } => dict.set_item("value", value)?,
+        PropertyFinding::OutOfRange {
+            value, min, max, ..
+        } => {
+            dict.set_item("value", value)?;
+            dict.set_item("min", min)?;
+            dict.set_item("max", max)?;
+        }
+        _ => {}
+    }
+    Ok(dict.unbind())
+}
 
 // =============================================================================
 // Math Primitive Wrappers
@@ -278,6 +388,27 @@ impl PyVector3 {
             .map_err(|e| PyValueError::new_err(format!("{}", e)))
     }
 
+    /// Reflect about a plane with the given normal.
+    fn reflect(&self, normal: &PyVector3) -> PyResult<PyVector3> {
+        self.inner
+            .reflect(&normal.inner)
+            .map(|v| PyVector3 { inner: v })
+            .map_err(|e| PyValueError::new_err(format!("{}", e)))
+    }
+
+    /// Project onto another vector.
+    fn project_onto(&self, other: &PyVector3) -> PyResult<PyVector3> {
+        self.inner
+            .project_onto(&other.inner)
+            .map(|v| PyVector3 { inner: v })
+            .map_err(|e| PyValueError::new_err(format!("{}", e)))
+    }
+
+    /// Unsigned angle to another vector in radians (0 to pi).
+    fn angle_between(&self, other: &PyVector3) -> f64 {
+        self.inner.angle_between(&other.inner)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "Vector3({}, {}, {})",
@@ -312,6 +443,164 @@ impl PyVector3 {
     }
 }
 
+/// 3D affine transform. Angles are in radians.
+#[pyclass(name = "Transform")]
+#[derive(Clone)]
+pub struct PyTransform3 {
+    pub inner: Transform3,
+}
+
+#[pymethods]
+impl PyTransform3 {
+    /// Create the identity transform.
+    #[new]
+    fn new() -> Self {
+        Self {
+            inner: Transform3::identity(),
+        }
+    }
+
+    #[staticmethod]
+    fn translation(dx: f64, dy: f64, dz: f64) -> Self {
+        Self {
+            inner: Transform3::translation(dx, dy, dz),
+        }
+    }
+
+    #[staticmethod]
+    fn scale(sx: f64, sy: f64, sz: f64) -> Self {
+        Self {
+            inner: Transform3::scale(sx, sy, sz),
+        }
+    }
+
+    #[staticmethod]
+    fn rotation_x(angle: f64) -> Self {
+        Self {
+            inner: Transform3::rotation_x(angle),
+        }
+    }
+
+    #[staticmethod]
+    fn rotation_y(angle: f64) -> Self {
+        Self {
+            inner: Transform3::rotation_y(angle),
+        }
+    }
+
+    #[staticmethod]
+    fn rotation_z(angle: f64) -> Self {
+        Self {
+            inner: Transform3::rotation_z(angle),
+        }
+    }
+
+    /// Rotation about an axis through `center` (default: the origin).
+    #[staticmethod]
+    #[pyo3(signature = (axis, angle, center=(0.0, 0.0, 0.0)))]
+    fn rotation_about(
+        axis: (f64, f64, f64),
+        angle: f64,
+        center: (f64, f64, f64),
+    ) -> PyResult<Self> {
+        Transform3::rotation_about(
+            Vector3::new(axis.0, axis.1, axis.2),
+            angle,
+            Point3::new(center.0, center.1, center.2),
+        )
+        .map(|inner| Self { inner })
+        .map_err(|e| PyValueError::new_err(format!("{}", e)))
+    }
+
+    /// Scale, then rotate, then translate.
+    ///
+    /// `rotation` is either Euler angles (x, y, z), applied about the fixed
+    /// X, Y, then Z axes, or a quaternion (w, x, y, z).
+    #[staticmethod]
+    #[pyo3(signature = (translation, rotation, scale=(1.0, 1.0, 1.0)))]
+    fn from_trs(
+        translation: (f64, f64, f64),
+        rotation: Vec<f64>,
+        scale: (f64, f64, f64),
+    ) -> PyResult<Self> {
+        let rotation = match rotation[..] {
+            [x, y, z] => Quaternion::from_euler(x, y, z),
+            [w, x, y, z] => Quaternion::new(w, x, y, z)
+                .normalize()
+                .map_err(|e| PyValueError::new_err(format!("{}", e)))?,
+            _ => {
+                return Err(PyValueError::new_err(
+                    "rotation must be Euler angles (x, y, z) or a quaternion (w, x, y, z)",
+                ))
+            }
+        };
+        Ok(Self {
+            inner: Transform3::from_trs(
+                Vector3::new(translation.0, translation.1, translation.2),
+                rotation,
+                Vector3::new(scale.0, scale.1, scale.2),
+            ),
+        })
+    }
+
+    /// Split into (translation, quaternion (w, x, y, z), scale), inverting
+    /// from_trs. A mirror shows up as a negative X scale.
+    fn decompose(&self) -> PyResult<(Vec3Tuple, QuatTuple, Vec3Tuple)> {
+        let (t, r, s) = self
+            .inner
+            .decompose()
+            .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        Ok(((t.x, t.y, t.z), (r.w, r.x, r.y, r.z), (s.x, s.y, s.z)))
+    }
+
+    /// Apply this transform, then `other`.
+    fn compose(&self, other: &PyTransform3) -> PyTransform3 {
+        PyTransform3 {
+            inner: self.inner.compose(&other.inner),
+        }
+    }
+
+    fn inverse(&self) -> PyResult<PyTransform3> {
+        self.inner
+            .inverse()
+            .map(|inner| PyTransform3 { inner })
+            .map_err(|e| PyValueError::new_err(format!("{}", e)))
+    }
+
+    fn determinant(&self) -> f64 {
+        self.inner.determinant()
+    }
+
+    /// Whether the transform only rotates and translates.
+    fn is_rigid(&self) -> bool {
+        self.inner.is_rigid()
+    }
+
+    fn transform_point(&self, point: (f64, f64, f64)) -> (f64, f64, f64) {
+        let p = self
+            .inner
+            .transform_point(Point3::new(point.0, point.1, point.2));
+        (p.x, p.y, p.z)
+    }
+
+    fn transform_vector(&self, vector: (f64, f64, f64)) -> (f64, f64, f64) {
+        let v = self
+            .inner
+            .transform_vector(Vector3::new(vector.0, vector.1, vector.2));
+        (v.x, v.y, v.z)
+    }
+
+    /// Matrix rows, top to bottom.
+    fn rows(&self) -> [[f64; 4]; 4] {
+        let m = &self.inner.m;
+        [0, 1, 2, 3].map(|row| [m[0][row], m[1][row], m[2][row], m[3][row]])
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Transform(rows={:?})", self.rows())
+    }
+}
+
 /// 3D axis-aligned bounding box.
 #[pyclass(name = "BoundingBox3")]
 #[derive(Clone)]
@@ -483,7 +772,7 @@ impl PyWall {
             height,
             thickness,
         )
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        .map_err(geometry_error)?;
 
         if let Some(wt) = wall_type {
             wall.wall_type = match wt.to_lowercase().as_str() {
@@ -536,6 +825,24 @@ impl PyWall {
         }
     }
 
+    #[getter]
+    fn start_cap(&self) -> String {
+        end_cap_name(self.inner.start_cap)
+    }
+
+    #[getter]
+    fn end_cap(&self) -> String {
+        end_cap_name(self.inner.end_cap)
+    }
+
+    /// Set both end caps: "flush", "square", or "round" with `segments`
+    /// arc segments. Joined ends stay flush in join geometry.
+    #[pyo3(signature = (cap, segments=8))]
+    fn set_end_caps(&mut self, cap: &str, segments: u32) -> PyResult<()> {
+        self.inner.set_end_caps(parse_end_cap(cap, segments)?);
+        Ok(())
+    }
+
     fn length(&self) -> f64 {
         self.inner.length()
     }
@@ -547,6 +854,55 @@ impl PyWall {
             .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
     }
 
+    /// Set a metadata property through the loaded property schema.
+    ///
+    /// Declared keys are stored under their canonical spelling, which is
+    /// returned. In strict mode undeclared keys and invalid values raise
+    /// ValueError.
+    fn set_property(&mut self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<String> {
+        let value = property_value_string(value)?;
+        with_property_schema(|registry| {
+            registry
+                .set_property(ElementType::Wall, &mut self.inner.metadata, key, value)
+                .map(|rewrite| rewrite.map_or_else(|| key.to_string(), |r| r.to))
+                .map_err(geometry_error)
+        })
+    }
+
+    /// Get a metadata property, matching keys like `set_property`.
+    fn get_property(&self, key: &str) -> Option<String> {
+        with_property_schema(|registry| {
+            let key = registry
+                .canonical_key(ElementType::Wall, key)
+                .unwrap_or(key);
+            self.inner.metadata.get_property(key).cloned()
+        })
+    }
+
+    /// Metadata properties as (key, value) pairs in schema order.
+    fn properties(&self) -> Vec<(String, String)> {
+        with_property_schema(|registry| {
+            registry
+                .ordered_properties(ElementType::Wall, &self.inner.metadata)
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        })
+    }
+
+    /// Check metadata against the loaded property schema.
+    fn validate_properties(&self) -> PyResult<Vec<Py<PyDict>>> {
+        let findings = with_property_schema(|registry| {
+            registry.validate(ElementType::Wall, &self.inner.metadata)
+        });
+        Python::with_gil(|py| {
+            findings
+                .iter()
+                .map(|finding| property_finding_dict(py, finding))
+                .collect()
+        })
+    }
+
     fn normal(&self) -> PyResult<PyVector2> {
         self.inner
             .normal()
@@ -557,7 +913,7 @@ impl PyWall {
     fn add_opening(&mut self, opening: &PyWallOpening) -> PyResult<()> {
         self.inner
             .add_opening(opening.inner.clone())
-            .map_err(|e| PyValueError::new_err(format!("{}", e)))
+            .map_err(geometry_error)
     }
 
     fn remove_opening(&mut self, opening_id: &str) -> PyResult<bool> {
@@ -624,6 +980,23 @@ impl PyWall {
     }
 }
 
+fn end_cap_name(cap: WallEndCap) -> String {
+    match cap {
+        WallEndCap::Flush => "flush".to_string(),
+        WallEndCap::Square => "square".to_string(),
+        WallEndCap::Round { .. } => "round".to_string(),
+    }
+}
+
+fn parse_end_cap(cap: &str, segments: u32) -> PyResult<WallEndCap> {
+    match cap.to_lowercase().as_str() {
+        "flush" | "butt" => Ok(WallEndCap::Flush),
+        "square" => Ok(WallEndCap::Square),
+        "round" => Ok(WallEndCap::Round { segments }),
+        other => Err(PyValueError::new_err(format!("unknown end cap: {}", other))),
+    }
+}
+
 /// Floor BIM element.
 #[pyclass(name = "Floor")]
 #[derive(Clone)]
@@ -647,7 +1020,7 @@ impl PyFloor {
             Point2::new(max_point.0, max_point.1),
             thickness,
         )
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        .map_err(geometry_error)?;
 
         if let Some(ft) = floor_type {
             floor.floor_type = match ft.to_lowercase().as_str() {
@@ -722,6 +1095,127 @@ impl PyFloor {
     }
 }
 
+/// Ceiling BIM element.
+#[pyclass(name = "Ceiling")]
+#[derive(Clone)]
+pub struct PyCeiling {
+    pub inner: Ceiling,
+}
+
+#[pymethods]
+impl PyCeiling {
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    #[getter]
+    fn thickness(&self) -> f64 {
+        self.inner.thickness
+    }
+
+    /// Elevation of the underside (soffit).
+    #[getter]
+    fn elevation(&self) -> f64 {
+        self.inner.elevation
+    }
+
+    #[getter]
+    fn room_id(&self) -> Option<String> {
+        self.inner.room_id.map(|id| id.to_string())
+    }
+
+    #[getter]
+    fn opening_count(&self) -> usize {
+        self.inner.openings.len()
+    }
+
+    /// Surface area, excluding openings.
+    fn area(&self) -> f64 {
+        self.inner.area()
+    }
+
+    /// Cut an opening (skylight, hatch) given as a list of (x, y) points.
+    fn add_opening(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        let opening = Polygon2::new(points.iter().map(|p| Point2::new(p.0, p.1)).collect())
+            .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        self.inner.add_opening(opening).map_err(geometry_error)
+    }
+
+    fn to_mesh(&self) -> PyResult<PyTriangleMesh> {
+        self.inner
+            .to_mesh()
+            .map(|m| PyTriangleMesh { inner: m })
+            .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
+    }
+
+    fn to_dict(&self) -> PyResult<Py<PyDict>> {
+        Python::with_gil(|py| {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("id", self.inner.id.to_string())?;
+            dict.set_item("thickness", self.inner.thickness)?;
+            dict.set_item("elevation", self.inner.elevation)?;
+            dict.set_item("room_id", self.room_id())?;
+            dict.set_item("area", self.inner.area())?;
+            dict.set_item("opening_count", self.inner.openings.len())?;
+            Ok(dict.unbind())
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Ceiling(id={}, area={:.2}, elevation={})",
+            self.inner.id,
+            self.inner.area(),
+            self.inner.elevation
+        )
+    }
+}
+
+/// A vector as a Python (x, y, z) tuple.
+type Vec3Tuple = (f64, f64, f64);
+
+/// A quaternion as a Python (w, x, y, z) tuple.
+type QuatTuple = (f64, f64, f64, f64);
+
+/// A door leaf's hinge, open leaf end, and swing arc as Python tuples.
+type SwingTuple = ((f64, f64), (f64, f64), Vec<(f64, f64)>);
+
+/// Parse a door type name, defaulting to a single door.
+fn parse_door_type(door_type: &str) -> DoorType {
+    match door_type.to_lowercase().as_str() {
+        "double" => DoorType::Double,
+        "sliding" => DoorType::Sliding,
+        "folding" => DoorType::Folding,
+        "revolving" => DoorType::Revolving,
+        "pocket" => DoorType::Pocket,
+        "single" | _ => DoorType::Single,
+    }
+}
+
+/// Parse a door swing name, defaulting to a left swing.
+fn parse_door_swing(swing: &str) -> DoorSwing {
+    match swing.to_lowercase().as_str() {
+        "right" => DoorSwing::Right,
+        "both" => DoorSwing::Both,
+        "none" => DoorSwing::None,
+        "left" | _ => DoorSwing::Left,
+    }
+}
+
+/// Parse a window type name, defaulting to a fixed window.
+fn parse_window_type(window_type: &str) -> WindowType {
+    match window_type.to_lowercase().as_str() {
+        "casement" => WindowType::Casement,
+        "double_hung" => WindowType::DoubleHung,
+        "sliding" => WindowType::Sliding,
+        "awning" => WindowType::Awning,
+        "hopper" => WindowType::Hopper,
+        "pivot" => WindowType::Pivot,
+        "fixed" | _ => WindowType::Fixed,
+    }
+}
+
 /// Door BIM element.
 #[pyclass(name = "Door")]
 #[derive(Clone)]
@@ -732,7 +1226,7 @@ pub struct PyDoor {
 #[pymethods]
 impl PyDoor {
     #[new]
-    #[pyo3(signature = (host_wall_id, width, height, offset_along_wall, door_type=None, swing=None))]
+    #[pyo3(signature = (host_wall_id, width, height, offset_along_wall, door_type=None, swing=None, opening_side=None))]
     pub fn new(
         host_wall_id: &str,
         width: f64,
@@ -740,30 +1234,32 @@ impl PyDoor {
         offset_along_wall: f64,
         door_type: Option<&str>,
         swing: Option<&str>,
+        opening_side: Option<&str>,
     ) -> PyResult<Self> {
         let wall_id = Uuid::parse_str(host_wall_id)
             .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
 
-        let mut door = Door::new(wall_id, width, height, offset_along_wall)
-            .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        let mut door =
+            Door::new(wall_id, width, height, offset_along_wall).map_err(geometry_error)?;
 
         if let Some(dt) = door_type {
-            door.door_type = match dt.to_lowercase().as_str() {
-                "double" => DoorType::Double,
-                "sliding" => DoorType::Sliding,
-                "folding" => DoorType::Folding,
-                "revolving" => DoorType::Revolving,
-                "pocket" => DoorType::Pocket,
-                "single" | _ => DoorType::Single,
-            };
+            door.door_type = parse_door_type(dt);
         }
 
         if let Some(s) = swing {
-            door.swing = match s.to_lowercase().as_str() {
-                "right" => DoorSwing::Right,
-                "both" => DoorSwing::Both,
-                "none" => DoorSwing::None,
-                "left" | _ => DoorSwing::Left,
+            door.swing = parse_door_swing(s);
+        }
+
+        if let Some(side) = opening_side {
+            door.opening_side = match side.to_lowercase().as_str() {
+                "left" => OffsetSide::Left,
+                "right" => OffsetSide::Right,
+                other => {
+                    return Err(PyValueError::new_err(format!(
+                        "unknown opening side: {}",
+                        other
+                    )))
+                }
             };
         }
 
@@ -817,6 +1313,69 @@ impl PyDoor {
         }
     }
 
+    #[getter]
+    fn opening_side(&self) -> String {
+        match self.inner.opening_side {
+            OffsetSide::Left => "left".to_string(),
+            OffsetSide::Right => "right".to_string(),
+        }
+    }
+
+    #[getter]
+    fn frame_thickness(&self) -> f64 {
+        self.inner.frame_thickness
+    }
+
+    #[getter]
+    fn type_name(&self) -> Option<String> {
+        self.inner.type_name.clone()
+    }
+
+    /// Plan swing symbols in the host wall, one per leaf, as
+    /// (hinge_point, leaf_end, arc_points) tuples of (x, y) points.
+    #[pyo3(signature = (wall, segments=DEFAULT_SWING_SEGMENTS))]
+    fn swing_geometry(&self, wall: &PyWall, segments: usize) -> PyResult<Vec<SwingTuple>> {
+        let leaves = self
+            .inner
+            .swing_geometry_with_segments(&wall.inner, segments)
+            .map_err(geometry_error)?;
+        Ok(leaves
+            .iter()
+            .map(|leaf| {
+                (
+                    (leaf.hinge_point.x, leaf.hinge_point.y),
+                    (leaf.leaf_end.x, leaf.leaf_end.y),
+                    leaf.arc_points.iter().map(|p| (p.x, p.y)).collect(),
+                )
+            })
+            .collect())
+    }
+
+    /// Swing arc in the host wall as one polyline of (x, y) points; empty
+    /// for a door with no swing.
+    fn swing_arc(&self, wall: &PyWall) -> PyResult<Vec<(f64, f64)>> {
+        self.inner
+            .swing_arc(&wall.inner)
+            .map(|arc| arc.iter().map(|p| (p.x, p.y)).collect())
+            .map_err(geometry_error)
+    }
+
+    /// Mesh of the door placed in its host wall's opening.
+    fn to_mesh(&self, wall: &PyWall) -> PyResult<PyTriangleMesh> {
+        self.inner
+            .to_mesh_in_host(&wall.inner)
+            .map(|m| PyTriangleMesh { inner: m })
+            .map_err(geometry_error)
+    }
+
+    /// Bounding box of the door placed in its host wall.
+    fn bounding_box(&self, wall: &PyWall) -> PyResult<PyBoundingBox3> {
+        self.inner
+            .bounding_box_in_host(&wall.inner)
+            .map(|b| PyBoundingBox3 { inner: b })
+            .map_err(geometry_error)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "Door(id={}, width={}, height={}, type={})",
@@ -851,18 +1410,10 @@ impl PyWindow {
             .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
 
         let mut window = Window::new(wall_id, width, height, sill_height, offset_along_wall)
-            .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+            .map_err(geometry_error)?;
 
         if let Some(wt) = window_type {
-            window.window_type = match wt.to_lowercase().as_str() {
-                "casement" => WindowType::Casement,
-                "double_hung" => WindowType::DoubleHung,
-                "sliding" => WindowType::Sliding,
-                "awning" => WindowType::Awning,
-                "hopper" => WindowType::Hopper,
-                "pivot" => WindowType::Pivot,
-                "fixed" | _ => WindowType::Fixed,
-            };
+            window.window_type = parse_window_type(wt);
         }
 
         Ok(Self { inner: window })
@@ -911,6 +1462,41 @@ impl PyWindow {
         }
     }
 
+    #[getter]
+    fn frame_thickness(&self) -> f64 {
+        self.inner.frame_thickness
+    }
+
+    #[getter]
+    fn type_name(&self) -> Option<String> {
+        self.inner.type_name.clone()
+    }
+
+    /// Mesh of the window placed in its host wall's opening.
+    fn to_mesh(&self, wall: &PyWall) -> PyResult<PyTriangleMesh> {
+        self.inner
+            .to_mesh_in_host(&wall.inner)
+            .map(|m| PyTriangleMesh { inner: m })
+            .map_err(geometry_error)
+    }
+
+    /// Bounding box of the window placed in its host wall.
+    fn bounding_box(&self, wall: &PyWall) -> PyResult<PyBoundingBox3> {
+        self.inner
+            .bounding_box_in_host(&wall.inner)
+            .map(|b| PyBoundingBox3 { inner: b })
+            .map_err(geometry_error)
+    }
+
+    /// Mesh of the window's frame and mullion alone, placed in its host
+    /// wall's opening.
+    fn to_frame_mesh(&self, wall: &PyWall) -> PyResult<PyTriangleMesh> {
+        self.inner
+            .to_frame_mesh(&wall.inner)
+            .map(|m| PyTriangleMesh { inner: m })
+            .map_err(geometry_error)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "Window(id={}, width={}, height={}, sill={}, type={})",
@@ -923,33 +1509,150 @@ impl PyWindow {
     }
 }
 
-/// Room BIM element.
-#[pyclass(name = "Room")]
+/// Catalog of named door and window types.
+#[pyclass(name = "Catalog")]
 #[derive(Clone)]
-pub struct PyRoom {
-    pub inner: Room,
+pub struct PyCatalog {
+    pub inner: Catalog,
 }
 
 #[pymethods]
-impl PyRoom {
-    /// Create a rectangular room.
-    #[staticmethod]
-    #[pyo3(signature = (name, number, min_point, max_point, height))]
-    pub fn rectangle(
-        name: &str,
-        number: &str,
-        min_point: (f64, f64),
-        max_point: (f64, f64),
-        height: f64,
-    ) -> PyResult<Self> {
-        let room = Room::rectangle(
+impl PyCatalog {
+    #[new]
+    pub fn new() -> Self {
+        Self {
+            inner: Catalog::new(),
+        }
+    }
+
+    /// Door type names, in catalog order.
+    #[getter]
+    fn door_types(&self) -> Vec<String> {
+        self.inner
+            .door_types()
+            .iter()
+            .map(|d| d.name.clone())
+            .collect()
+    }
+
+    /// Window type names, in catalog order.
+    #[getter]
+    fn window_types(&self) -> Vec<String> {
+        self.inner
+            .window_types()
+            .iter()
+            .map(|w| w.name.clone())
+            .collect()
+    }
+
+    /// Add a door type, replacing any type with the same name.
+    #[pyo3(signature = (name, width, height, door_type=None, swing=None, frame_depth=DEFAULT_FRAME_THICKNESS))]
+    fn add_door_type(
+        &mut self,
+        name: &str,
+        width: f64,
+        height: f64,
+        door_type: Option<&str>,
+        swing: Option<&str>,
+        frame_depth: f64,
+    ) -> PyResult<()> {
+        let mut def = DoorTypeDef::new(name, width, height);
+        def.door_type = door_type.map(parse_door_type).unwrap_or_default();
+        def.swing = swing.map(parse_door_swing).unwrap_or_default();
+        def.frame_depth = frame_depth;
+        self.inner
+            .add_door_type(def)
+            .map(|_| ())
+            .map_err(geometry_error)
+    }
+
+    /// Add a window type, replacing any type with the same name.
+    #[pyo3(signature = (name, width, height, sill_height, window_type=None))]
+    fn add_window_type(
+        &mut self,
+        name: &str,
+        width: f64,
+        height: f64,
+        sill_height: f64,
+        window_type: Option<&str>,
+    ) -> PyResult<()> {
+        let mut def = WindowTypeDef::new(name, width, height, sill_height);
+        def.window_type = window_type.map(parse_window_type).unwrap_or_default();
+        self.inner
+            .add_window_type(def)
+            .map(|_| ())
+            .map_err(geometry_error)
+    }
+
+    /// Serialize the catalog to JSON.
+    fn to_json(&self) -> String {
+        self.inner.to_json()
+    }
+
+    /// Resize the building's typed doors and windows, and their openings,
+    /// to match the catalog. Returns the number of instances changed.
+    fn apply_updates(&self, building: &mut PyBuilding) -> PyResult<usize> {
+        self.inner
+            .apply_updates(&mut building.inner)
+            .map_err(geometry_error)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Catalog(door_types={}, window_types={})",
+            self.inner.door_types().len(),
+            self.inner.window_types().len()
+        )
+    }
+}
+
+/// Room BIM element.
+#[pyclass(name = "Room")]
+#[derive(Clone)]
+pub struct PyRoom {
+    pub inner: Room,
+}
+
+#[pymethods]
+impl PyRoom {
+    /// Create a rectangular room.
+    #[staticmethod]
+    #[pyo3(signature = (name, number, min_point, max_point, height))]
+    pub fn rectangle(
+        name: &str,
+        number: &str,
+        min_point: (f64, f64),
+        max_point: (f64, f64),
+        height: f64,
+    ) -> PyResult<Self> {
+        let room = Room::rectangle(
             name,
             number,
             Point2::new(min_point.0, min_point.1),
             Point2::new(max_point.0, max_point.1),
             height,
         )
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        .map_err(geometry_error)?;
+
+        Ok(Self { inner: room })
+    }
+
+    /// Create a room from an arbitrary boundary in either winding.
+    #[staticmethod]
+    #[pyo3(signature = (name, number, boundary, height))]
+    pub fn from_boundary(
+        name: &str,
+        number: &str,
+        boundary: Vec<(f64, f64)>,
+        height: f64,
+    ) -> PyResult<Self> {
+        let vertices = boundary
+            .into_iter()
+            .map(|(x, y)| Point2::new(x, y))
+            .collect();
+        let polygon =
+            Polygon2::new(vertices).map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        let room = Room::from_polygon(name, number, polygon, height).map_err(geometry_error)?;
 
         Ok(Self { inner: room })
     }
@@ -974,6 +1677,12 @@ impl PyRoom {
         self.inner.height
     }
 
+    /// Whether the room's enclosing walls are gone.
+    #[getter]
+    fn unplaced(&self) -> bool {
+        self.inner.unplaced
+    }
+
     fn area(&self) -> f64 {
         self.inner.area()
     }
@@ -1020,6 +1729,7 @@ impl PyRoom {
             dict.set_item("area", self.inner.area())?;
             dict.set_item("perimeter", self.inner.perimeter())?;
             dict.set_item("volume", self.inner.volume())?;
+            dict.set_item("unplaced", self.inner.unplaced)?;
             Ok(dict.unbind())
         })
     }
@@ -1039,6 +1749,129 @@ impl PyRoom {
 // Mesh Wrapper
 // =============================================================================
 
+/// Elements of a [`PyMeshArray`], in one contiguous row-major allocation.
+enum MeshArrayData {
+    F64(Vec<f64>),
+    U32(Vec<u32>),
+}
+
+/// A 2-D mesh buffer that numpy wraps without copying.
+///
+/// Exposes the buffer protocol, so `numpy.asarray` makes a read-only
+/// ndarray over the Rust allocation and keeps this object alive as the
+/// array's base.
+#[pyclass(name = "MeshArray")]
+pub struct PyMeshArray {
+    data: MeshArrayData,
+    shape: [isize; 2],
+    strides: [isize; 2],
+}
+
+impl PyMeshArray {
+    fn new(data: MeshArrayData, columns: usize) -> Self {
+        let (len, itemsize) = match &data {
+            MeshArrayData::F64(values) => (values.len(), std::mem::size_of::<f64>()),
+            MeshArrayData::U32(values) => (values.len(), std::mem::size_of::<u32>()),
+        };
+        Self {
+            data,
+            shape: [(len / columns) as isize, columns as isize],
+            strides: [(columns * itemsize) as isize, itemsize as isize],
+        }
+    }
+
+    /// Wrap as a numpy ndarray sharing this allocation.
+    fn into_ndarray(self, py: Python<'_>) -> PyResult<PyObject> {
+        let numpy = py.import_bound("numpy")?;
+        let buffer = Bound::new(py, self)?;
+        Ok(numpy.call_method1("asarray", (buffer,))?.unbind())
+    }
+}
+
+#[pymethods]
+impl PyMeshArray {
+    unsafe fn __getbuffer__(
+        slf: Bound<'_, Self>,
+        view: *mut pyo3::ffi::Py_buffer,
+        flags: std::os::raw::c_int,
+    ) -> PyResult<()> {
+        use pyo3::exceptions::PyBufferError;
+        use pyo3::ffi;
+        use std::os::raw::{c_char, c_void};
+
+        if view.is_null() {
+            return Err(PyBufferError::new_err("view is null"));
+        }
+        // Arrays are copies of the mesh's data, so refuse writes rather
+        // than let edits silently miss the mesh
+        if flags & ffi::PyBUF_WRITABLE == ffi::PyBUF_WRITABLE {
+            return Err(PyBufferError::new_err("mesh arrays are read-only"));
+        }
+        if flags & ffi::PyBUF_ND != ffi::PyBUF_ND {
+            return Err(PyBufferError::new_err("mesh arrays need a shaped buffer"));
+        }
+        let this = slf.borrow();
+        let (buf, format) = match &this.data {
+            MeshArrayData::F64(values) => (values.as_ptr() as *mut c_void, b"d\0"),
+            MeshArrayData::U32(values) => (values.as_ptr() as *mut c_void, b"I\0"),
+        };
+
+        // The data never moves or changes size once exported
+        (*view).buf = buf;
+        (*view).len = this.shape[0] * this.strides[0];
+        (*view).itemsize = this.strides[1];
+        (*view).readonly = 1;
+        (*view).ndim = 2;
+        (*view).format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+            format.as_ptr() as *mut c_char
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).shape = this.shape.as_ptr() as *mut isize;
+        (*view).strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+            this.strides.as_ptr() as *mut isize
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).suboffsets = std::ptr::null_mut();
+        (*view).internal = std::ptr::null_mut();
+        drop(this);
+        (*view).obj = slf.into_any().into_ptr();
+        Ok(())
+    }
+
+    unsafe fn __releasebuffer__(&self, _view: *mut pyo3::ffi::Py_buffer) {}
+
+    fn __repr__(&self) -> String {
+        format!("MeshArray(shape=({}, {}))", self.shape[0], self.shape[1])
+    }
+}
+
+/// Rows of an `(n, columns)` array-like, flattened.
+///
+/// Anything numpy can convert to `dtype` is accepted when numpy is
+/// installed; otherwise the object must export a buffer of `T`.
+fn array_rows<T: pyo3::buffer::Element>(
+    array: &Bound<'_, PyAny>,
+    dtype: &str,
+    columns: usize,
+) -> PyResult<Vec<T>> {
+    let py = array.py();
+    let array = match py.import_bound("numpy") {
+        Ok(numpy) => numpy.call_method1("ascontiguousarray", (array, dtype))?,
+        Err(_) => array.clone(),
+    };
+    let buffer = pyo3::buffer::PyBuffer::<T>::get_bound(&array)?;
+    if buffer.dimensions() != 2 || buffer.shape()[1] != columns {
+        return Err(PyValueError::new_err(format!(
+            "expected an (n, {}) array, got shape {:?}",
+            columns,
+            buffer.shape()
+        )));
+    }
+    buffer.to_vec(py)
+}
+
 /// Triangle mesh for 3D visualization.
 #[pyclass(name = "TriangleMesh")]
 #[derive(Clone)]
@@ -1083,6 +1916,107 @@ impl PyTriangleMesh {
         self.inner.normals.iter().map(|v| (v.x, v.y, v.z)).collect()
     }
 
+    /// Get texture coordinates (in meters) as list of (u, v) tuples.
+    fn uvs(&self) -> Vec<(f64, f64)> {
+        self.inner.uvs.clone()
+    }
+
+    /// Get vertices as a flat list [x0, y0, z0, x1, ...].
+    ///
+    /// Cheaper than vertices() for large meshes; vertices_array() skips the
+    /// list altogether.
+    fn vertices_flat(&self) -> Vec<f64> {
+        self.inner
+            .vertices
+            .iter()
+            .flat_map(|p| [p.x, p.y, p.z])
+            .collect()
+    }
+
+    /// Get normals as a flat list [x0, y0, z0, x1, ...].
+    fn normals_flat(&self) -> Vec<f64> {
+        self.inner
+            .normals
+            .iter()
+            .flat_map(|v| [v.x, v.y, v.z])
+            .collect()
+    }
+
+    /// Get triangle indices as a flat list [a0, b0, c0, a1, ...].
+    fn indices_flat(&self) -> Vec<u32> {
+        self.inner.indices.iter().flatten().copied().collect()
+    }
+
+    /// Get vertices as a numpy float64 array of shape (n, 3).
+    ///
+    /// The array is filled in one allocation and wrapped without copying
+    /// or creating a Python object per row, so prefer it to vertices() for
+    /// large meshes. Like the other mesh arrays it is read-only; copy it to
+    /// edit. Requires numpy.
+    fn vertices_array(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let data = self.inner.vertices.iter().flat_map(|p| [p.x, p.y, p.z]);
+        PyMeshArray::new(MeshArrayData::F64(data.collect()), 3).into_ndarray(py)
+    }
+
+    /// Get normals as a numpy float64 array of shape (n, 3). Requires numpy.
+    fn normals_array(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let data = self.inner.normals.iter().flat_map(|v| [v.x, v.y, v.z]);
+        PyMeshArray::new(MeshArrayData::F64(data.collect()), 3).into_ndarray(py)
+    }
+
+    /// Get triangle indices as a numpy uint32 array of shape (m, 3).
+    /// Requires numpy.
+    fn indices_array(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let data = self.inner.indices.iter().flatten().copied();
+        PyMeshArray::new(MeshArrayData::U32(data.collect()), 3).into_ndarray(py)
+    }
+
+    /// Build a mesh from arrays, e.g. those of vertices_array() and
+    /// indices_array().
+    ///
+    /// Args:
+    ///     vertices: (n, 3) array of positions, converted to float64
+    ///     indices: (m, 3) array of vertex indices, converted to uint32
+    ///     normals: Optional (n, 3) array of vertex normals, or an empty
+    ///         one, as normals_array() gives for a mesh without normals
+    ///
+    /// Raises:
+    ///     ValueError: If an array has the wrong shape, there is not one
+    ///         normal per vertex, or an index is out of range
+    #[staticmethod]
+    #[pyo3(signature = (vertices, indices, normals=None))]
+    fn from_arrays(
+        vertices: &Bound<'_, PyAny>,
+        indices: &Bound<'_, PyAny>,
+        normals: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        let points = |values: Vec<f64>| -> Vec<Point3> {
+            values
+                .chunks_exact(3)
+                .map(|c| Point3::new(c[0], c[1], c[2]))
+                .collect()
+        };
+        let vertices = points(array_rows::<f64>(vertices, "float64", 3)?);
+        let indices = array_rows::<u32>(indices, "uint32", 3)?
+            .chunks_exact(3)
+            .map(|c| [c[0], c[1], c[2]])
+            .collect();
+        let mut mesh = TriangleMesh::from_vertices_indices(vertices, indices);
+        if let Some(normals) = normals {
+            let normals = points(array_rows::<f64>(normals, "float64", 3)?);
+            if !normals.is_empty() && normals.len() != mesh.vertex_count() {
+                return Err(PyValueError::new_err(format!(
+                    "expected {} normals, one per vertex, got {}",
+                    mesh.vertex_count(),
+                    normals.len()
+                )));
+            }
+            mesh.normals = normals.iter().map(Point3::to_vector).collect();
+        }
+        mesh.validate().map_err(geometry_error)?;
+        Ok(Self { inner: mesh })
+    }
+
     fn bounding_box(&self) -> Option<PyBoundingBox3> {
         self.inner
             .bounding_box()
@@ -1094,6 +2028,13 @@ impl PyTriangleMesh {
         self.inner.to_obj()
     }
 
+    /// Create a transformed copy of the mesh.
+    fn transformed(&self, transform: &PyTransform3) -> PyTriangleMesh {
+        PyTriangleMesh {
+            inner: self.inner.transformed(&transform.inner),
+        }
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "TriangleMesh(vertices={}, triangles={}, valid={})",
@@ -1108,6 +2049,18 @@ impl PyTriangleMesh {
 // Wall Join Wrappers
 // =============================================================================
 
+/// Python name of a join type.
+pub(crate) fn join_type_name(join_type: JoinType) -> &'static str {
+    match join_type {
+        JoinType::Butt => "butt",
+        JoinType::Miter => "miter",
+        JoinType::LJoin => "l_join",
+        JoinType::TJoin => "t_join",
+        JoinType::CrossJoin => "cross_join",
+        JoinType::None => "none",
+    }
+}
+
 /// Detected wall join.
 #[pyclass(name = "WallJoin")]
 #[derive(Clone)]
@@ -1124,14 +2077,7 @@ impl PyWallJoin {
 
     #[getter]
     fn join_type(&self) -> String {
-        match self.inner.join_type {
-            JoinType::Butt => "butt".to_string(),
-            JoinType::Miter => "miter".to_string(),
-            JoinType::LJoin => "l_join".to_string(),
-            JoinType::TJoin => "t_join".to_string(),
-            JoinType::CrossJoin => "cross_join".to_string(),
-            JoinType::None => "none".to_string(),
-        }
+        join_type_name(self.inner.join_type).to_string()
     }
 
     #[getter]
@@ -1219,7 +2165,7 @@ impl PyRoof {
             Point2::new(max_point.0, max_point.1),
             thickness,
         )
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        .map_err(geometry_error)?;
 
         if let Some(rt) = roof_type {
             let rtype = match rt.to_lowercase().as_str() {
@@ -1262,7 +2208,7 @@ impl PyRoof {
             slope_degrees,
             ridge_direction,
         )
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        .map_err(geometry_error)?;
 
         Ok(Self { inner: roof })
     }
@@ -1282,7 +2228,7 @@ impl PyRoof {
             thickness,
             slope_degrees,
         )
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        .map_err(geometry_error)?;
 
         Ok(Self { inner: roof })
     }
@@ -1310,7 +2256,7 @@ impl PyRoof {
             slope_degrees,
             ridge_direction,
         )
-        .map_err(|e| PyValueError::new_err(format!("{}", e)))?;
+        .map_err(geometry_error)?;
 
         Ok(Self { inner: roof })
     }
@@ -1340,6 +2286,16 @@ impl PyRoof {
         self.inner.eave_overhang
     }
 
+    #[getter]
+    fn lower_slope_degrees(&self) -> f64 {
+        self.inner.lower_slope_degrees
+    }
+
+    #[getter]
+    fn break_inset(&self) -> f64 {
+        self.inner.break_inset
+    }
+
     #[getter]
     fn roof_type(&self) -> String {
         self.inner.roof_type.name().to_lowercase()
@@ -1353,6 +2309,16 @@ impl PyRoof {
         self.inner.set_eave_overhang(overhang);
     }
 
+    /// Set the pitch of a mansard roof's lower slopes in degrees.
+    fn set_lower_slope(&mut self, degrees: f64) {
+        self.inner.set_lower_slope(degrees);
+    }
+
+    /// Set the distance from the eaves to a mansard roof's break line.
+    fn set_break_inset(&mut self, inset: f64) -> PyResult<()> {
+        self.inner.set_break_inset(inset).map_err(geometry_error)
+    }
+
     fn footprint_area(&self) -> f64 {
         self.inner.footprint_area()
     }
@@ -1373,6 +2339,30 @@ impl PyRoof {
         self.inner.top_elevation()
     }
 
+    /// Openings cut through the roof.
+    #[getter]
+    fn openings(&self) -> Vec<PyRoofOpening> {
+        self.inner
+            .openings
+            .iter()
+            .map(|o| PyRoofOpening { inner: o.clone() })
+            .collect()
+    }
+
+    /// Remove an opening by its UUID string.
+    fn remove_opening(&mut self, opening_id: &str) -> PyResult<bool> {
+        let uuid = Uuid::parse_str(opening_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
+        Ok(self.inner.remove_opening(uuid))
+    }
+
+    /// Elevation of the roof's top surface above a plan point.
+    fn surface_elevation(&self, point: (f64, f64)) -> PyResult<f64> {
+        self.inner
+            .surface_elevation(Point2::new(point.0, point.1))
+            .map_err(geometry_error)
+    }
+
     /// Attach roof to a wall by its UUID string.
     fn attach_to_wall(&mut self, wall_id: &str) -> PyResult<()> {
         let uuid = Uuid::parse_str(wall_id)
@@ -1419,6 +2409,16 @@ impl PyRoof {
             .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
     }
 
+    /// Generate a mesh for rendering, with per-face normals and UVs.
+    ///
+    /// Pitched roof faces are not welded, and carry slope-developed UVs.
+    fn to_textured_mesh(&self) -> PyResult<PyTriangleMesh> {
+        self.inner
+            .to_textured_mesh()
+            .map(|m| PyTriangleMesh { inner: m })
+            .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
+    }
+
     fn to_dict(&self) -> PyResult<Py<PyDict>> {
         Python::with_gil(|py| {
             let dict = PyDict::new_bound(py);
@@ -1428,10 +2428,15 @@ impl PyRoof {
             dict.set_item("roof_type", self.roof_type())?;
             dict.set_item("slope_degrees", self.inner.slope_degrees)?;
             dict.set_item("eave_overhang", self.inner.eave_overhang)?;
+            if self.inner.roof_type == RoofType::Mansard {
+                dict.set_item("lower_slope_degrees", self.inner.lower_slope_degrees)?;
+                dict.set_item("break_inset", self.inner.break_inset)?;
+            }
             dict.set_item("footprint_area", self.inner.footprint_area())?;
             dict.set_item("surface_area", self.inner.surface_area())?;
             dict.set_item("ridge_height", self.inner.ridge_height())?;
             dict.set_item("attached_wall_ids", self.attached_wall_ids())?;
+            dict.set_item("opening_count", self.inner.openings.len())?;
             Ok(dict.unbind())
         })
     }
@@ -1446,3 +2451,1434 @@ impl PyRoof {
         )
     }
 }
+
+/// Rectangular opening through a roof.
+#[pyclass(name = "RoofOpening")]
+#[derive(Clone)]
+pub struct PyRoofOpening {
+    pub inner: RoofOpening,
+}
+
+#[pymethods]
+impl PyRoofOpening {
+    #[new]
+    #[pyo3(signature = (center, width, depth, opening_type="generic"))]
+    pub fn new(center: (f64, f64), width: f64, depth: f64, opening_type: &str) -> PyResult<Self> {
+        let otype = match opening_type.to_lowercase().as_str() {
+            "skylight" => RoofOpeningType::Skylight,
+            "chimney" => RoofOpeningType::Chimney,
+            "generic" | _ => RoofOpeningType::Generic,
+        };
+
+        Ok(Self {
+            inner: RoofOpening::new(Point2::new(center.0, center.1), width, depth, otype),
+        })
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    #[getter]
+    fn center(&self) -> (f64, f64) {
+        (self.inner.center.x, self.inner.center.y)
+    }
+
+    #[getter]
+    fn width(&self) -> f64 {
+        self.inner.width
+    }
+
+    #[getter]
+    fn depth(&self) -> f64 {
+        self.inner.depth
+    }
+
+    #[getter]
+    fn opening_type(&self) -> String {
+        match self.inner.opening_type {
+            RoofOpeningType::Skylight => "skylight".to_string(),
+            RoofOpeningType::Chimney => "chimney".to_string(),
+            RoofOpeningType::Generic => "generic".to_string(),
+        }
+    }
+
+    #[getter]
+    fn hosted_element_id(&self) -> Option<String> {
+        self.inner.hosted_element_id.map(|id| id.to_string())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "RoofOpening(type={}, center=({}, {}), width={}, depth={})",
+            self.opening_type(),
+            self.inner.center.x,
+            self.inner.center.y,
+            self.inner.width,
+            self.inner.depth
+        )
+    }
+}
+
+/// Skylight BIM element, hosted in a roof opening.
+#[pyclass(name = "Skylight")]
+#[derive(Clone)]
+pub struct PySkylight {
+    pub inner: Skylight,
+}
+
+#[pymethods]
+impl PySkylight {
+    #[new]
+    pub fn new(host_roof_id: &str, center: (f64, f64), width: f64, depth: f64) -> PyResult<Self> {
+        let roof_id = Uuid::parse_str(host_roof_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
+
+        let skylight = Skylight::new(roof_id, Point2::new(center.0, center.1), width, depth)
+            .map_err(geometry_error)?;
+
+        Ok(Self { inner: skylight })
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    #[getter]
+    fn host_roof_id(&self) -> String {
+        self.inner.host_roof_id.to_string()
+    }
+
+    #[getter]
+    fn center(&self) -> (f64, f64) {
+        (self.inner.center.x, self.inner.center.y)
+    }
+
+    #[getter]
+    fn width(&self) -> f64 {
+        self.inner.width
+    }
+
+    #[getter]
+    fn depth(&self) -> f64 {
+        self.inner.depth
+    }
+
+    #[getter]
+    fn upstand_height(&self) -> f64 {
+        self.inner.upstand_height
+    }
+
+    /// Mesh of the skylight placed in its host roof's opening.
+    fn to_mesh(&self, roof: &PyRoof) -> PyResult<PyTriangleMesh> {
+        self.inner
+            .to_mesh_in_host(&roof.inner)
+            .map(|m| PyTriangleMesh { inner: m })
+            .map_err(geometry_error)
+    }
+
+    /// Bounding box of the skylight placed in its host roof.
+    fn bounding_box(&self, roof: &PyRoof) -> PyResult<PyBoundingBox3> {
+        self.inner
+            .bounding_box_in_host(&roof.inner)
+            .map(|b| PyBoundingBox3 { inner: b })
+            .map_err(geometry_error)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Skylight(id={}, width={}, depth={})",
+            self.inner.id, self.inner.width, self.inner.depth
+        )
+    }
+}
+
+/// Railing (balustrade) BIM element.
+#[pyclass(name = "Railing")]
+#[derive(Clone)]
+pub struct PyRailing {
+    pub inner: Railing,
+}
+
+#[pymethods]
+impl PyRailing {
+    /// Create a railing along a 3D path of (x, y, z) points.
+    #[new]
+    #[pyo3(signature = (path, height=1.1, spacing=0.1, infill="balusters"))]
+    pub fn new(
+        path: Vec<(f64, f64, f64)>,
+        height: f64,
+        spacing: f64,
+        infill: &str,
+    ) -> PyResult<Self> {
+        let path = path
+            .into_iter()
+            .map(|(x, y, z)| Point3::new(x, y, z))
+            .collect();
+        let mut railing = Railing::new(path, height, spacing).map_err(geometry_error)?;
+        railing.infill = parse_infill(infill)?;
+        Ok(Self { inner: railing })
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    #[getter]
+    fn height(&self) -> f64 {
+        self.inner.height
+    }
+
+    #[getter]
+    fn spacing(&self) -> f64 {
+        self.inner.baluster_spacing
+    }
+
+    #[getter]
+    fn infill(&self) -> String {
+        match self.inner.infill {
+            InfillType::Balusters => "balusters".to_string(),
+            InfillType::Glass => "glass".to_string(),
+            InfillType::Mesh => "mesh".to_string(),
+        }
+    }
+
+    #[setter]
+    fn set_infill(&mut self, infill: &str) -> PyResult<()> {
+        self.inner.infill = parse_infill(infill)?;
+        Ok(())
+    }
+
+    #[getter]
+    fn path(&self) -> Vec<(f64, f64, f64)> {
+        self.inner.path.iter().map(|p| (p.x, p.y, p.z)).collect()
+    }
+
+    /// Get rail length, baluster count, and glass area.
+    fn takeoff(&self) -> PyResult<Py<PyDict>> {
+        let takeoff = self.inner.takeoff();
+        Python::with_gil(|py| {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("rail_length", takeoff.rail_length)?;
+            dict.set_item("baluster_count", takeoff.baluster_count)?;
+            dict.set_item("glass_area", takeoff.glass_area)?;
+            Ok(dict.unbind())
+        })
+    }
+
+    /// Check height and baluster gap limits, returning a list of findings.
+    #[pyo3(signature = (min_height=1.1, max_baluster_gap=0.1))]
+    fn validate(&self, min_height: f64, max_baluster_gap: f64) -> PyResult<Vec<Py<PyDict>>> {
+        let rules = RailingRules {
+            min_height,
+            max_baluster_gap,
+        };
+        Python::with_gil(|py| {
+            self.inner
+                .validate(&rules)
+                .into_iter()
+                .map(|finding| {
+                    let dict = PyDict::new_bound(py);
+                    match finding {
+                        RailingFinding::HeightBelowMinimum { height, minimum } => {
+                            dict.set_item("kind", "height_below_minimum")?;
+                            dict.set_item("value", height)?;
+                            dict.set_item("limit", minimum)?;
+                        }
+                        RailingFinding::BalusterGapTooWide { gap, maximum } => {
+                            dict.set_item("kind", "baluster_gap_too_wide")?;
+                            dict.set_item("value", gap)?;
+                            dict.set_item("limit", maximum)?;
+                        }
+                    }
+                    Ok(dict.unbind())
+                })
+                .collect()
+        })
+    }
+
+    fn to_mesh(&self) -> PyResult<PyTriangleMesh> {
+        self.inner
+            .to_mesh()
+            .map(|m| PyTriangleMesh { inner: m })
+            .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Railing(id={}, length={:.2}, height={})",
+            self.inner.id,
+            self.inner.rail_length(),
+            self.inner.height
+        )
+    }
+}
+
+fn parse_infill(infill: &str) -> PyResult<InfillType> {
+    match infill.to_lowercase().as_str() {
+        "balusters" => Ok(InfillType::Balusters),
+        "glass" => Ok(InfillType::Glass),
+        "mesh" => Ok(InfillType::Mesh),
+        other => Err(PyValueError::new_err(format!(
+            "unknown infill type: {}",
+            other
+        ))),
+    }
+}
+
+/// Straight stair BIM element.
+#[pyclass(name = "Stair")]
+#[derive(Clone)]
+pub struct PyStair {
+    pub inner: Stair,
+}
+
+#[pymethods]
+impl PyStair {
+    /// Create a straight stair from the foot of its first riser.
+    #[new]
+    #[pyo3(signature = (start, direction, total_rise, tread_depth=0.28, riser_height=0.18, width=1.0))]
+    pub fn new(
+        start: (f64, f64),
+        direction: (f64, f64),
+        total_rise: f64,
+        tread_depth: f64,
+        riser_height: f64,
+        width: f64,
+    ) -> PyResult<Self> {
+        Stair::straight(
+            Point2::new(start.0, start.1),
+            Vector2::new(direction.0, direction.1),
+            total_rise,
+            tread_depth,
+            riser_height,
+            width,
+        )
+        .map(|s| Self { inner: s })
+        .map_err(geometry_error)
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    #[getter]
+    fn total_rise(&self) -> f64 {
+        self.inner.total_rise
+    }
+
+    #[getter]
+    fn riser_count(&self) -> usize {
+        self.inner.riser_count
+    }
+
+    #[getter]
+    fn riser_height(&self) -> f64 {
+        self.inner.riser_height
+    }
+
+    #[getter]
+    fn requested_riser_height(&self) -> f64 {
+        self.inner.requested_riser_height
+    }
+
+    #[getter]
+    fn tread_count(&self) -> usize {
+        self.inner.tread_count()
+    }
+
+    #[getter]
+    fn tread_depth(&self) -> f64 {
+        self.inner.tread_depth
+    }
+
+    #[getter]
+    fn width(&self) -> f64 {
+        self.inner.width
+    }
+
+    /// Whether the riser height was adjusted to divide the rise evenly.
+    fn riser_adjusted(&self) -> bool {
+        self.inner.riser_adjusted()
+    }
+
+    /// Horizontal length from the first riser to the last.
+    fn run_length(&self) -> f64 {
+        self.inner.run_length()
+    }
+
+    /// Replace the tread at a step (1-based) with a landing.
+    fn add_landing(&mut self, at_step: usize, depth: f64) -> PyResult<()> {
+        self.inner = self
+            .inner
+            .clone()
+            .with_landing(at_step, depth)
+            .map_err(geometry_error)?;
+        Ok(())
+    }
+
+    /// Add stringers along both sides of each flight.
+    #[pyo3(signature = (width=0.05, depth=0.25))]
+    fn set_stringers(&mut self, width: f64, depth: f64) {
+        self.inner.stringers = Some(RailProfile::new(width, depth));
+    }
+
+    /// Check riser and tread limits, returning a list of findings.
+    #[pyo3(signature = (max_riser=0.19, min_tread=0.25))]
+    fn validate(&self, max_riser: f64, min_tread: f64) -> PyResult<Vec<Py<PyDict>>> {
+        let rules = StairRules {
+            max_riser,
+            min_tread,
+        };
+        Python::with_gil(|py| {
+            self.inner
+                .validate(&rules)
+                .into_iter()
+                .map(|finding| {
+                    let dict = PyDict::new_bound(py);
+                    match finding {
+                        StairFinding::RiserTooHigh { riser, maximum } => {
+                            dict.set_item("kind", "riser_too_high")?;
+                            dict.set_item("value", riser)?;
+                            dict.set_item("limit", maximum)?;
+                        }
+                        StairFinding::TreadTooShallow { tread, minimum } => {
+                            dict.set_item("kind", "tread_too_shallow")?;
+                            dict.set_item("value", tread)?;
+                            dict.set_item("limit", minimum)?;
+                        }
+                    }
+                    Ok(dict.unbind())
+                })
+                .collect()
+        })
+    }
+
+    fn to_mesh(&self) -> PyResult<PyTriangleMesh> {
+        self.inner
+            .to_mesh()
+            .map(|m| PyTriangleMesh { inner: m })
+            .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Stair(id={}, risers={}, riser_height={:.4}, run={:.2})",
+            self.inner.id,
+            self.inner.riser_count,
+            self.inner.riser_height,
+            self.inner.run_length()
+        )
+    }
+}
+
+/// Read-only context mesh imported from an OBJ or PLY file.
+#[pyclass(name = "ContextMesh")]
+#[derive(Clone)]
+pub struct PyContextMesh {
+    pub inner: ContextMesh,
+}
+
+#[pymethods]
+impl PyContextMesh {
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    #[getter]
+    fn name(&self) -> String {
+        self.inner.name.clone()
+    }
+
+    #[getter]
+    fn path(&self) -> String {
+        self.inner.source.path.clone()
+    }
+
+    #[getter]
+    fn content_hash(&self) -> String {
+        self.inner.source.content_hash.clone()
+    }
+
+    #[getter]
+    fn triangle_count(&self) -> usize {
+        self.inner.triangle_count
+    }
+
+    #[getter]
+    fn source_triangle_count(&self) -> usize {
+        self.inner.source_triangle_count
+    }
+
+    #[getter]
+    fn chunk_count(&self) -> usize {
+        self.inner.chunks().len()
+    }
+
+    /// Read the geometry back from the source file.
+    fn reload(&mut self) -> PyResult<()> {
+        self.inner
+            .reload()
+            .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
+    }
+
+    fn bounding_box(&self) -> PyResult<PyBoundingBox3> {
+        self.inner
+            .bounding_box()
+            .map(|b| PyBoundingBox3 { inner: b })
+            .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
+    }
+
+    fn to_mesh(&self) -> PyResult<PyTriangleMesh> {
+        self.inner
+            .to_mesh()
+            .map(|m| PyTriangleMesh { inner: m })
+            .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ContextMesh(id={}, triangles={}, chunks={})",
+            self.inner.id,
+            self.inner.triangle_count,
+            self.inner.chunks().len()
+        )
+    }
+}
+
+// =============================================================================
+// Level and Building Wrappers
+// =============================================================================
+
+/// Building level (storey).
+#[pyclass(name = "Level")]
+#[derive(Clone)]
+pub struct PyLevel {
+    pub inner: Level,
+}
+
+#[pymethods]
+impl PyLevel {
+    #[new]
+    #[pyo3(signature = (name, elevation, height=3.0))]
+    pub fn new(name: &str, elevation: f64, height: f64) -> PyResult<Self> {
+        Level::new(name, elevation, height)
+            .map(|l| Self { inner: l })
+            .map_err(geometry_error)
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    #[getter]
+    fn name(&self) -> String {
+        self.inner.name.clone()
+    }
+
+    #[getter]
+    fn elevation(&self) -> f64 {
+        self.inner.elevation
+    }
+
+    #[getter]
+    fn height(&self) -> f64 {
+        self.inner.height
+    }
+
+    fn to_dict(&self) -> PyResult<Py<PyDict>> {
+        Python::with_gil(|py| {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("id", self.inner.id.to_string())?;
+            dict.set_item("name", self.inner.name.clone())?;
+            dict.set_item("elevation", self.inner.elevation)?;
+            dict.set_item("height", self.inner.height)?;
+            Ok(dict.unbind())
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Level(id={}, name=\"{}\", elevation={}, height={})",
+            self.inner.id, self.inner.name, self.inner.elevation, self.inner.height
+        )
+    }
+}
+
+/// Building container owning levels and elements.
+#[pyclass(name = "Building")]
+#[derive(Clone)]
+pub struct PyBuilding {
+    pub inner: Building,
+}
+
+#[pymethods]
+impl PyBuilding {
+    #[new]
+    #[pyo3(signature = (name="Building"))]
+    pub fn new(name: &str) -> Self {
+        Self {
+            inner: Building::new(name),
+        }
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    #[getter]
+    fn name(&self) -> String {
+        self.inner.name.clone()
+    }
+
+    #[getter]
+    fn levels(&self) -> Vec<PyLevel> {
+        self.inner
+            .levels
+            .iter()
+            .map(|l| PyLevel { inner: l.clone() })
+            .collect()
+    }
+
+    fn add_level(&mut self, level: &PyLevel) -> String {
+        self.inner.add_level(level.inner.clone()).to_string()
+    }
+
+    fn add_wall(&mut self, wall: &PyWall) -> String {
+        self.inner.add_wall(wall.inner.clone()).to_string()
+    }
+
+    fn add_floor(&mut self, floor: &PyFloor) -> String {
+        self.inner.add_floor(floor.inner.clone()).to_string()
+    }
+
+    fn add_roof(&mut self, roof: &PyRoof) -> String {
+        self.inner.add_roof(roof.inner.clone()).to_string()
+    }
+
+    fn add_room(&mut self, room: &PyRoom) -> String {
+        self.inner.add_room(room.inner.clone()).to_string()
+    }
+
+    fn add_door(&mut self, door: &PyDoor) -> String {
+        self.inner.add_door(door.inner.clone()).to_string()
+    }
+
+    fn add_window(&mut self, window: &PyWindow) -> String {
+        self.inner.add_window(window.inner.clone()).to_string()
+    }
+
+    fn add_railing(&mut self, railing: &PyRailing) -> String {
+        self.inner.add_railing(railing.inner.clone()).to_string()
+    }
+
+    fn add_stair(&mut self, stair: &PyStair) -> String {
+        self.inner.add_stair(stair.inner.clone()).to_string()
+    }
+
+    fn add_ceiling(&mut self, ceiling: &PyCeiling) -> String {
+        self.inner.add_ceiling(ceiling.inner.clone()).to_string()
+    }
+
+    pub fn assign_to_level(&mut self, element_id: &str, level_id: &str) -> PyResult<()> {
+        let element_id = Uuid::parse_str(element_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
+        let level_id = Uuid::parse_str(level_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
+        self.inner
+            .assign_to_level(element_id, level_id)
+            .map_err(geometry_error)
+    }
+
+    /// Get IDs of elements placed on a level.
+    fn elements_on_level(&self, level_id: &str) -> PyResult<Vec<String>> {
+        let level_id = Uuid::parse_str(level_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
+        Ok(self
+            .inner
+            .elements_on_level(level_id)
+            .iter()
+            .map(|e| e.id().to_string())
+            .collect())
+    }
+
+    /// Get an element's mesh offset by its level elevation.
+    fn element_mesh(&self, element_id: &str) -> PyResult<PyTriangleMesh> {
+        let element_id = Uuid::parse_str(element_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
+        self.inner
+            .element_mesh(element_id)
+            .map(|m| PyTriangleMesh { inner: m })
+            .map_err(|e| PyRuntimeError::new_err(format!("{}", e)))
+    }
+
+    /// Get per-level summaries as a list of dicts.
+    fn summary(&self) -> PyResult<Vec<Py<PyDict>>> {
+        Python::with_gil(|py| {
+            self.inner
+                .summary()
+                .into_iter()
+                .map(|s| {
+                    let dict = PyDict::new_bound(py);
+                    dict.set_item("level_id", s.level_id.to_string())?;
+                    dict.set_item("name", s.name)?;
+                    dict.set_item("elevation", s.elevation)?;
+                    dict.set_item("element_count", s.element_count)?;
+                    dict.set_item(
+                        "bounding_box",
+                        s.bounding_box
+                            .map(|b| ((b.min.x, b.min.y, b.min.z), (b.max.x, b.max.y, b.max.z))),
+                    )?;
+                    dict.set_item("floor_area", s.floor_area)?;
+                    dict.set_item("room_area", s.room_area)?;
+                    Ok(dict.unbind())
+                })
+                .collect()
+        })
+    }
+
+    /// Export a quantity schedule as CSV or JSON.
+    ///
+    /// Quantities are rounded to the given decimals and written as plain
+    /// decimals. Totals are the sum of the rounded rows unless
+    /// `round_then_sum` is False.
+    ///
+    /// Args:
+    ///     kind: "levels" or "railings"
+    ///     output: "csv" or "json" (default: "csv")
+    #[pyo3(signature = (
+        kind,
+        output="csv",
+        length_decimals=3,
+        area_decimals=3,
+        volume_decimals=3,
+        round_then_sum=true
+    ))]
+    fn schedule(
+        &self,
+        kind: &str,
+        output: &str,
+        length_decimals: u32,
+        area_decimals: u32,
+        volume_decimals: u32,
+        round_then_sum: bool,
+    ) -> PyResult<String> {
+        let schedule = match kind {
+            "levels" => self.inner.level_schedule(),
+            "railings" => self.inner.railing_schedule(),
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown schedule kind: {}",
+                    other
+                )))
+            }
+        };
+        let format = NumberFormat {
+            length_decimals,
+            area_decimals,
+            volume_decimals,
+            totals: if round_then_sum {
+                TotalsPolicy::RoundThenSum
+            } else {
+                TotalsPolicy::SumThenRound
+            },
+            ..Default::default()
+        };
+        match output {
+            "csv" => Ok(schedule.to_csv(&format)),
+            "json" => Ok(schedule.to_deterministic_json(&format)),
+            other => Err(PyValueError::new_err(format!(
+                "unknown schedule output: {}",
+                other
+            ))),
+        }
+    }
+
+    /// Quantity takeoff of the building as deterministic JSON.
+    ///
+    /// Lists gross/net areas, lengths, and volumes per wall, floor, roof,
+    /// room, door, and window, with totals per element type.
+    #[pyo3(signature = (length_decimals=3, area_decimals=3, volume_decimals=3))]
+    fn quantities(&self, length_decimals: u32, area_decimals: u32, volume_decimals: u32) -> String {
+        let format = NumberFormat {
+            length_decimals,
+            area_decimals,
+            volume_decimals,
+            ..Default::default()
+        };
+        quantities_for(&self.inner).to_deterministic_json(&format)
+    }
+
+    /// Lay out plan labels so they clear each other and the walls.
+    ///
+    /// Room names stay inside their room where they fit and otherwise move
+    /// outside with a leader; door and window marks slide away from their wall.
+    ///
+    /// Args:
+    ///     level_id: Level to label, or None for the whole building
+    ///     dimensions: Include wall length dimension texts (default: False)
+    ///     em_size: Text height in meters (default: 0.25)
+    ///
+    /// Returns:
+    ///     List of dicts with id, kind, text, position, bbox, leader
+    ///     (pair of points or None), and overlapping
+    #[pyo3(signature = (level_id=None, dimensions=false, em_size=0.25))]
+    fn layout_labels(
+        &self,
+        level_id: Option<&str>,
+        dimensions: bool,
+        em_size: f64,
+    ) -> PyResult<Vec<Py<PyDict>>> {
+        let level_id = level_id
+            .map(Uuid::parse_str)
+            .transpose()
+            .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
+        let options = LabelLayoutOptions {
+            em_size,
+            ..Default::default()
+        };
+        Python::with_gil(|py| {
+            self.inner
+                .layout_plan_labels(level_id, dimensions, &options)
+                .into_iter()
+                .map(|label| {
+                    let dict = PyDict::new_bound(py);
+                    dict.set_item("id", &label.id)?;
+                    dict.set_item(
+                        "kind",
+                        match label.kind {
+                            LabelKind::RoomName => "room_name",
+                            LabelKind::Tag => "tag",
+                            LabelKind::Dimension => "dimension",
+                            LabelKind::Junction => "junction",
+                        },
+                    )?;
+                    dict.set_item("text", &label.text)?;
+                    dict.set_item("position", (label.position.x, label.position.y))?;
+                    dict.set_item(
+                        "bbox",
+                        (
+                            (label.bbox.min.x, label.bbox.min.y),
+                            (label.bbox.max.x, label.bbox.max.y),
+                        ),
+                    )?;
+                    dict.set_item(
+                        "leader",
+                        label.leader.map(|[a, b]| ((a.x, a.y), (b.x, b.y))),
+                    )?;
+                    dict.set_item("overlapping", label.overlapping)?;
+                    Ok(dict.unbind())
+                })
+                .collect()
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Building(id={}, name=\"{}\", levels={})",
+            self.inner.id,
+            self.inner.name,
+            self.inner.levels.len()
+        )
+    }
+}
+
+// =============================================================================
+// Model and Template Wrappers
+// =============================================================================
+
+/// Healed wall network model (topology graph plus placed templates).
+#[pyclass(name = "Model")]
+pub struct PyModel {
+    pub inner: Context,
+}
+
+#[pymethods]
+impl PyModel {
+    /// Create an empty model.
+    ///
+    /// With `auto_rooms`, each heal creates Room elements for newly enclosed
+    /// spaces; rooms whose walls are removed are deleted or flagged unplaced,
+    /// per `removed_rooms` ("delete" or "unplace").
+    #[new]
+    #[pyo3(signature = (tolerance=0.0005, auto_rooms=false, removed_rooms="unplace"))]
+    pub fn new(tolerance: f64, auto_rooms: bool, removed_rooms: &str) -> PyResult<Self> {
+        let mut ctx = Context::new();
+        ctx.graph = TopologyGraph::with_tolerance(tolerance);
+        ctx.config.auto_rooms = auto_rooms;
+        ctx.config.removed_room_policy = match removed_rooms {
+            "delete" => RemovedRoomPolicy::Delete,
+            "unplace" => RemovedRoomPolicy::Unplace,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown removed room policy: {}",
+                    other
+                )))
+            }
+        };
+        Ok(Self { inner: ctx })
+    }
+
+    /// Add a wall between two points, returning its ID.
+    ///
+    /// Points, thickness, and height are in millimetres, like the rest of
+    /// the wall graph.
+    #[pyo3(signature = (start, end, thickness=200.0, height=2700.0))]
+    fn add_wall(
+        &mut self,
+        start: (f64, f64),
+        end: (f64, f64),
+        thickness: f64,
+        height: f64,
+    ) -> PyResult<String> {
+        self.inner
+            .graph
+            .add_edge(
+                [start.0, start.1],
+                [end.0, end.1],
+                EdgeData::wall(thickness, height),
+            )
+            .map(|id| id.0.to_string())
+            .ok_or_else(|| PyValueError::new_err("wall has zero length"))
+    }
+
+    /// Get IDs of all walls.
+    fn wall_ids(&self) -> Vec<String> {
+        self.inner
+            .graph
+            .edge_ids()
+            .iter()
+            .map(|id| id.0.to_string())
+            .collect()
+    }
+
+    /// Get a wall's endpoints as ((x1, y1), (x2, y2)).
+    fn wall_positions(&self, wall_id: &str) -> PyResult<((f64, f64), (f64, f64))> {
+        let uuid = Uuid::parse_str(wall_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
+        self.inner
+            .graph
+            .edge_positions(EdgeId::from_uuid(uuid))
+            .map(|(a, b)| ((a[0], a[1]), (b[0], b[1])))
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown wall: {}", wall_id)))
+    }
+
+    /// Add a door to a wall, returning the door ID.
+    ///
+    /// All arguments are in millimetres, like the wall graph: `offset` is
+    /// the distance from the wall start to the near side of the door. The
+    /// Door element itself is stored in metres.
+    #[pyo3(signature = (wall_id, offset, width=900.0, height=2100.0))]
+    fn add_door(
+        &mut self,
+        wall_id: &str,
+        offset: f64,
+        width: f64,
+        height: f64,
+    ) -> PyResult<String> {
+        let uuid = Uuid::parse_str(wall_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
+        let edge = self
+            .inner
+            .graph
+            .get_edge_mut(EdgeId::from_uuid(uuid))
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown wall: {}", wall_id)))?;
+        const MM_TO_M: f64 = 0.001;
+        let door = Door::new(
+            uuid,
+            width * MM_TO_M,
+            height * MM_TO_M,
+            (offset + width / 2.0) * MM_TO_M,
+        )
+        .map_err(geometry_error)?;
+        edge.data.openings.push(OpeningRef {
+            element_id: door.id,
+            offset,
+            width,
+            height,
+            sill_height: 0.0,
+        });
+        let id = door.id.to_string();
+        self.inner.doors.push(door);
+        Ok(id)
+    }
+
+    fn node_count(&self) -> usize {
+        self.inner.graph.node_count()
+    }
+
+    fn wall_count(&self) -> usize {
+        self.inner.graph.edge_count()
+    }
+
+    /// Rebuild rooms and return the number of interior rooms.
+    fn interior_room_count(&mut self) -> usize {
+        self.inner.graph.rebuild_rooms();
+        self.inner.graph.interior_rooms().len()
+    }
+
+    /// Re-run clash detection and keep the results on the model.
+    ///
+    /// Takes the same arguments as detect_clashes() and fires
+    /// "clash_results_changed" callbacks when the clashing pairs differ from
+    /// the previous run.
+    #[pyo3(signature = (elements, tolerance=0.001, clearance=0.0, ignore_same_type=false))]
+    fn detect_clashes(
+        &mut self,
+        py: Python<'_>,
+        elements: Vec<ClashBoxTuple>,
+        tolerance: f64,
+        clearance: f64,
+        ignore_same_type: bool,
+    ) -> PyResult<Vec<Py<PyDict>>> {
+        let detector = clash_detector(tolerance, clearance, ignore_same_type);
+        self.inner
+            .detect_clashes(&detector, &clash_elements(elements))
+            .iter()
+            .map(|clash| clash_to_dict(py, clash).map(Bound::unbind))
+            .collect()
+    }
+
+    /// Register a callback for a kernel event, returning a handle for off().
+    ///
+    /// The callback receives a dict with kind, ids, revision, and summary.
+    /// Exceptions are caught and reported by hook_failures().
+    #[pyo3(signature = (event, callback, priority=0))]
+    fn on(&mut self, event: &str, callback: PyObject, priority: i32) -> PyResult<u64> {
+        let kind = KernelEventKind::from_name(event)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown event: {}", event)))?;
+
+        let handle = self
+            .inner
+            .hooks
+            .on_with_priority(kind, priority, move |event, _| {
+                Python::with_gil(|py| {
+                    let payload = PyDict::new_bound(py);
+                    payload.set_item("kind", event.kind.name())?;
+                    payload.set_item("ids", event.ids.clone())?;
+                    payload.set_item("revision", event.revision)?;
+                    payload.set_item("summary", event.summary.clone())?;
+                    callback.call1(py, (payload,)).map(|_| ())
+                })
+                .map_err(|e| e.to_string())
+            });
+        Ok(handle.0)
+    }
+
+    /// Remove a callback registered with on().
+    fn off(&mut self, handle: u64) -> bool {
+        self.inner.hooks.off(HookHandle(handle))
+    }
+
+    /// Heal the model and notify callbacks, returning the room count.
+    fn heal(&mut self) -> usize {
+        self.inner.commit(&mut Delta::new())
+    }
+
+    /// Heal the model like heal(), returning what changed.
+    ///
+    /// Returns:
+    ///     dict with room_count, created, modified, deleted,
+    ///     auto_created_rooms, unplaced_rooms, and rooms_rebuilt
+    fn commit(&mut self) -> PyResult<Py<PyDict>> {
+        let mut delta = Delta::new();
+        let room_count = self.inner.commit(&mut delta);
+        Python::with_gil(|py| {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("room_count", room_count)?;
+            dict.set_item("created", delta.created)?;
+            dict.set_item("modified", delta.modified)?;
+            dict.set_item("deleted", delta.deleted)?;
+            dict.set_item("auto_created_rooms", delta.auto_created_rooms)?;
+            dict.set_item("unplaced_rooms", delta.unplaced_rooms)?;
+            dict.set_item("rooms_rebuilt", delta.rooms_rebuilt)?;
+            Ok(dict.unbind())
+        })
+    }
+
+    /// Get the model's Room elements, including unplaced ones.
+    fn rooms(&self) -> Vec<PyRoom> {
+        self.inner
+            .rooms
+            .iter()
+            .map(|room| PyRoom {
+                inner: room.clone(),
+            })
+            .collect()
+    }
+
+    /// Take callback failures recorded since the last call.
+    fn hook_failures(&mut self) -> PyResult<Vec<Py<PyDict>>> {
+        Python::with_gil(|py| {
+            self.inner
+                .hooks
+                .take_failures()
+                .into_iter()
+                .map(|f| {
+                    let dict = PyDict::new_bound(py);
+                    dict.set_item("handle", f.handle.0)?;
+                    dict.set_item("event", f.event.name())?;
+                    dict.set_item("message", f.message)?;
+                    Ok(dict.unbind())
+                })
+                .collect()
+        })
+    }
+
+    /// Get IDs of template instances placed from an older template version.
+    fn outdated_instances(&self, template: &PyTemplate) -> Vec<String> {
+        outdated_instances(&self.inner, &template.inner)
+            .iter()
+            .map(|i| i.id.to_string())
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Model(nodes={}, walls={}, template_instances={})",
+            self.inner.graph.node_count(),
+            self.inner.graph.edge_count(),
+            self.inner.template_instances.len()
+        )
+    }
+}
+
+/// Reusable wall group template.
+#[pyclass(name = "Template")]
+#[derive(Clone)]
+pub struct PyTemplate {
+    pub inner: WallTemplate,
+}
+
+#[pymethods]
+impl PyTemplate {
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id.to_string()
+    }
+
+    #[getter]
+    fn name(&self) -> String {
+        self.inner.name.clone()
+    }
+
+    #[getter]
+    fn version(&self) -> u32 {
+        self.inner.version
+    }
+
+    fn wall_count(&self) -> usize {
+        self.inner.walls.len()
+    }
+
+    /// Replace the template contents from elements in a model, bumping the
+    /// version.
+    fn recapture(
+        &mut self,
+        model: &PyModel,
+        ids: Vec<String>,
+        origin: (f64, f64),
+        axis: (f64, f64),
+    ) -> PyResult<()> {
+        let element_ids = parse_uuids(&ids)?;
+        self.inner
+            .recapture(
+                &model.inner,
+                &element_ids,
+                [origin.0, origin.1],
+                [axis.0, axis.1],
+            )
+            .map_err(geometry_error)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Template(id={}, name=\"{}\", version={}, walls={})",
+            self.inner.id,
+            self.inner.name,
+            self.inner.version,
+            self.inner.walls.len()
+        )
+    }
+}
+
+/// Parse wall ID strings into edge IDs.
+pub(crate) fn parse_edge_ids(ids: &[String]) -> PyResult<Vec<EdgeId>> {
+    Ok(parse_uuids(ids)?
+        .into_iter()
+        .map(EdgeId::from_uuid)
+        .collect())
+}
+
+/// Parse element ID strings.
+pub(crate) fn parse_uuids(ids: &[String]) -> PyResult<Vec<Uuid>> {
+    ids.iter()
+        .map(|id| {
+            Uuid::parse_str(id).map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))
+        })
+        .collect()
+}
+
+// =============================================================================
+// Topology Graph Wrapper
+// =============================================================================
+
+/// Persistent wall topology graph for incremental room detection.
+#[pyclass(name = "TopologyGraph")]
+pub struct PyTopologyGraph {
+    pub inner: TopologyGraph,
+}
+
+#[pymethods]
+impl PyTopologyGraph {
+    /// Create an empty graph; endpoints within `tolerance` share a node.
+    #[new]
+    #[pyo3(signature = (tolerance=0.0005))]
+    pub fn new(tolerance: f64) -> Self {
+        Self {
+            inner: TopologyGraph::with_tolerance(tolerance),
+        }
+    }
+
+    /// Add a wall between two points, returning its edge ID.
+    #[pyo3(signature = (start, end, thickness=0.2, height=3.0))]
+    pub fn add_wall(
+        &mut self,
+        start: (f64, f64),
+        end: (f64, f64),
+        thickness: f64,
+        height: f64,
+    ) -> PyResult<String> {
+        self.inner
+            .add_edge(
+                [start.0, start.1],
+                [end.0, end.1],
+                EdgeData::wall(thickness, height),
+            )
+            .map(|id| id.0.to_string())
+            .ok_or_else(|| PyValueError::new_err("wall has zero length"))
+    }
+
+    /// Remove an edge, returning whether it existed.
+    pub fn remove_edge(&mut self, edge_id: &str) -> PyResult<bool> {
+        let id = parse_edge_ids(&[edge_id.to_string()])?[0];
+        Ok(self.inner.remove_edge(id).is_some())
+    }
+
+    /// Detect rooms from scratch, returning the room count (including the
+    /// exterior region).
+    pub fn rebuild_rooms(&mut self) -> usize {
+        self.inner.rebuild_rooms()
+    }
+
+    /// IDs of the nodes within `radius` of a point.
+    pub fn nodes_within(&self, center: (f64, f64), radius: f64) -> Vec<String> {
+        self.inner
+            .nodes_within([center.0, center.1], radius)
+            .into_iter()
+            .map(|id| id.0.to_string())
+            .collect()
+    }
+
+    /// Run the fixup passes (snap, split crossings, merge colinear) and
+    /// rebuild the affected rooms.
+    ///
+    /// Returns:
+    ///     dict with room_count and the created, modified, and deleted edge IDs
+    fn heal(&mut self) -> PyResult<Py<PyDict>> {
+        let mut delta = Delta::new();
+        let room_count = heal_all_tracked(&mut self.inner, &mut delta);
+        Python::with_gil(|py| {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("room_count", room_count)?;
+            dict.set_item("created", delta.created)?;
+            dict.set_item("modified", delta.modified)?;
+            dict.set_item("deleted", delta.deleted)?;
+            Ok(dict.unbind())
+        })
+    }
+
+    /// Detected interior rooms as dicts with id, area, centroid, and
+    /// boundary_count.
+    fn rooms(&self) -> PyResult<Vec<Py<PyDict>>> {
+        Python::with_gil(|py| {
+            self.inner
+                .interior_rooms()
+                .iter()
+                .map(|room| {
+                    let dict = PyDict::new_bound(py);
+                    dict.set_item("id", room.id.0.to_string())?;
+                    dict.set_item("area", room.area())?;
+                    dict.set_item("centroid", (room.centroid[0], room.centroid[1]))?;
+                    dict.set_item("boundary_count", room.boundary_nodes.len())?;
+                    Ok(dict.unbind())
+                })
+                .collect()
+        })
+    }
+
+    /// Pairs of interior rooms sharing an edge, as (room_a, room_b,
+    /// edge_id) tuples; one per shared edge.
+    fn room_adjacency(&self) -> Vec<(String, String, String)> {
+        self.inner
+            .room_adjacency()
+            .into_iter()
+            .map(|(a, b, edge)| (a.0.to_string(), b.0.to_string(), edge.0.to_string()))
+            .collect()
+    }
+
+    /// IDs of the interior rooms sharing at least one edge with a room.
+    fn rooms_sharing_wall(&self, room_id: &str) -> PyResult<Vec<String>> {
+        let id = Uuid::parse_str(room_id)
+            .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))?;
+        Ok(self
+            .inner
+            .rooms_sharing_wall(RoomId(id))
+            .into_iter()
+            .map(|id| id.0.to_string())
+            .collect())
+    }
+
+    #[getter]
+    pub fn node_count(&self) -> usize {
+        self.inner.node_count()
+    }
+
+    #[getter]
+    pub fn edge_count(&self) -> usize {
+        self.inner.edge_count()
+    }
+
+    /// Number of enclosed interior rooms.
+    #[getter]
+    pub fn interior_room_count(&self) -> usize {
+        self.inner.interior_rooms().len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "TopologyGraph(nodes={}, edges={}, rooms={})",
+            self.inner.node_count(),
+            self.inner.edge_count(),
+            self.inner.interior_rooms().len()
+        )
+    }
+}
+
+// =============================================================================
+// Background Meshing (requires the `parallel` feature)
+// =============================================================================
+
+/// Background mesh generator fed from a building.
+#[cfg(feature = "parallel")]
+#[pyclass(name = "MeshWorker")]
+pub struct PyMeshWorker {
+    pub inner: Option<MeshWorker>,
+    pub building: Py<PyBuilding>,
+}
+
+#[cfg(feature = "parallel")]
+impl PyMeshWorker {
+    fn worker(&self) -> PyResult<&MeshWorker> {
+        self.inner
+            .as_ref()
+            .ok_or_else(|| PyRuntimeError::new_err("mesh worker has been shut down"))
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[pymethods]
+impl PyMeshWorker {
+    /// Queue elements by ID; lower priorities are meshed first.
+    ///
+    /// Returns the number of elements queued (unknown IDs are skipped).
+    fn enqueue(&self, py: Python<'_>, ids: Vec<String>, priorities: Vec<f64>) -> PyResult<usize> {
+        if ids.len() != priorities.len() {
+            return Err(PyValueError::new_err(
+                "ids and priorities must have the same length",
+            ));
+        }
+        let jobs = ids
+            .iter()
+            .zip(priorities)
+            .map(|(id, priority)| {
+                Uuid::parse_str(id)
+                    .map(|uuid| (uuid, priority))
+                    .map_err(|e| PyValueError::new_err(format!("Invalid UUID: {}", e)))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let building = self.building.borrow(py);
+        Ok(self.worker()?.enqueue_from(&building.inner, &jobs))
+    }
+
+    /// Re-mesh edited elements ahead of other queued work.
+    ///
+    /// Call after changing the model so stale meshes are discarded. IDs no
+    /// longer in the building are cancelled. Returns the number re-queued.
+    fn invalidate(&self, py: Python<'_>, ids: Vec<String>) -> PyResult<usize> {
+        let ids = parse_uuids(&ids)?;
+        let building = self.building.borrow(py);
+        Ok(self.worker()?.invalidate_from(&building.inner, &ids))
+    }
+
+    /// Cancel queued jobs for these element IDs.
+    fn cancel(&self, ids: Vec<String>) -> PyResult<()> {
+        let ids = parse_uuids(&ids)?;
+        self.worker()?.cancel(&ids);
+        Ok(())
+    }
+
+    /// Take finished meshes as a list of (id, TriangleMesh) tuples.
+    fn poll(&self) -> PyResult<Vec<(String, PyTriangleMesh)>> {
+        Ok(self
+            .worker()?
+            .poll_ready()
+            .into_iter()
+            .map(|(id, mesh)| {
+                (
+                    id.to_string(),
+                    PyTriangleMesh {
+                        inner: (*mesh).clone(),
+                    },
+                )
+            })
+            .collect())
+    }
+
+    /// Take meshing failures as a list of (id, PensaerGeometryError) tuples.
+    fn poll_errors(&self, py: Python<'_>) -> PyResult<Vec<(String, PyObject)>> {
+        Ok(self
+            .worker()?
+            .poll_failed()
+            .into_iter()
+            .map(|(id, e)| (id.to_string(), geometry_error(e).into_value(py).into_any()))
+            .collect())
+    }
+
+    /// Number of jobs queued or being meshed.
+    fn pending(&self) -> PyResult<usize> {
+        Ok(self.worker()?.pending())
+    }
+
+    /// Stop the worker threads.
+    fn shutdown(&mut self, py: Python<'_>) {
+        if let Some(worker) = self.inner.take() {
+            py.allow_threads(|| worker.shutdown());
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        match &self.inner {
+            Some(worker) => format!("MeshWorker(pending={})", worker.pending()),
+            None => "MeshWorker(shut down)".to_string(),
+        }
+    }
+}
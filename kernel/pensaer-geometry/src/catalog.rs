@@ -0,0 +1,454 @@
+//! Door and window type catalogs.
+//!
+//! A [`Catalog`] holds named door and window types, so an office's standard
+//! sizes are defined once and reused. Elements created with
+//! [`Door::from_type`] or [`Window::from_type`] remember their type name;
+//! after a type is edited, [`Catalog::apply_updates`] resizes every
+//! instance in a building together with its wall opening.
+//!
+//! Catalogs load from JSON:
+//!
+//! ```
+//! use pensaer_geometry::catalog::Catalog;
+//!
+//! let catalog = Catalog::from_json(r#"{
+//!     "doors": [
+//!         { "name": "Office", "width": 0.926, "height": 2.04, "swing": "Right" }
+//!     ],
+//!     "windows": [
+//!         { "name": "Strip", "width": 2.4, "height": 0.6, "sill_height": 1.5 }
+//!     ]
+//! }"#).unwrap();
+//!
+//! assert_eq!(catalog.door_type("Office").unwrap().width, 0.926);
+//! assert!(catalog.window_type("Bay").is_none());
+//! ```
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::elements::{
+    check_frame, default_frame_thickness, Building, Door, DoorSwing, DoorType, Wall, WallOpening,
+    Window, WindowType,
+};
+use crate::error::{GeometryError, GeometryResult};
+
+/// A named door type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoorTypeDef {
+    /// Type name, unique within a catalog.
+    pub name: String,
+    /// Door width (m).
+    pub width: f64,
+    /// Door height (m).
+    pub height: f64,
+    /// Door type.
+    #[serde(default)]
+    pub door_type: DoorType,
+    /// Door swing direction.
+    #[serde(default)]
+    pub swing: DoorSwing,
+    /// Width of the frame members lining the opening (m).
+    #[serde(default = "default_frame_thickness")]
+    pub frame_depth: f64,
+}
+
+impl DoorTypeDef {
+    /// Create a single left-swing door type with the default frame.
+    pub fn new(name: impl Into<String>, width: f64, height: f64) -> Self {
+        Self {
+            name: name.into(),
+            width,
+            height,
+            door_type: DoorType::default(),
+            swing: DoorSwing::default(),
+            frame_depth: default_frame_thickness(),
+        }
+    }
+
+    fn check(&self) -> GeometryResult<()> {
+        check_name(&self.name)?;
+        check_size(&self.name, self.width, self.height)?;
+        check_frame(self.width, self.height, self.frame_depth)
+    }
+
+    /// Check if a door already has this type's size, type, swing, and frame.
+    fn describes(&self, door: &Door) -> bool {
+        door.width == self.width
+            && door.height == self.height
+            && door.door_type == self.door_type
+            && door.swing == self.swing
+            && door.frame_thickness == self.frame_depth
+    }
+}
+
+/// A named window type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowTypeDef {
+    /// Type name, unique within a catalog.
+    pub name: String,
+    /// Window width (m).
+    pub width: f64,
+    /// Window height (m).
+    pub height: f64,
+    /// Height from the floor to the window bottom (m).
+    pub sill_height: f64,
+    /// Window type.
+    #[serde(default)]
+    pub window_type: WindowType,
+}
+
+impl WindowTypeDef {
+    /// Create a fixed window type.
+    pub fn new(name: impl Into<String>, width: f64, height: f64, sill_height: f64) -> Self {
+        Self {
+            name: name.into(),
+            width,
+            height,
+            sill_height,
+            window_type: WindowType::default(),
+        }
+    }
+
+    fn check(&self) -> GeometryResult<()> {
+        check_name(&self.name)?;
+        check_size(&self.name, self.width, self.height)?;
+        if self.sill_height < 0.0 {
+            return Err(GeometryError::InvalidCatalog(format!(
+                "type '{}' has a negative sill height",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check if a window already has this type's size, sill, and type.
+    fn describes(&self, window: &Window) -> bool {
+        window.width == self.width
+            && window.height == self.height
+            && window.sill_height == self.sill_height
+            && window.window_type == self.window_type
+    }
+}
+
+fn check_name(name: &str) -> GeometryResult<()> {
+    if name.trim().is_empty() {
+        return Err(GeometryError::InvalidCatalog(
+            "type name is empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn check_size(name: &str, width: f64, height: f64) -> GeometryResult<()> {
+    if width <= 0.0 || height <= 0.0 {
+        return Err(GeometryError::InvalidCatalog(format!(
+            "type '{}' must have a positive width and height",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Door and window types, each kept in the order they were added.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Catalog {
+    #[serde(default)]
+    doors: Vec<DoorTypeDef>,
+    #[serde(default)]
+    windows: Vec<WindowTypeDef>,
+}
+
+impl Catalog {
+    /// Create an empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A catalog of common sizes: 762, 838, and 926 mm single doors and
+    /// three casement windows.
+    pub fn standard() -> Self {
+        let mut catalog = Self::new();
+        for (name, width, height) in [
+            ("Door 762x1981", 0.762, 1.981),
+            ("Door 838x1981", 0.838, 1.981),
+            ("Door 926x2040", 0.926, 2.04),
+        ] {
+            catalog.doors.push(DoorTypeDef::new(name, width, height));
+        }
+        for (name, width, height) in [
+            ("Window 630x1050", 0.63, 1.05),
+            ("Window 1200x1050", 1.2, 1.05),
+            ("Window 1770x1200", 1.77, 1.2),
+        ] {
+            let mut def = WindowTypeDef::new(name, width, height, 0.9);
+            def.window_type = WindowType::Casement;
+            catalog.windows.push(def);
+        }
+        catalog
+    }
+
+    /// Load a catalog from JSON with optional `doors` and `windows` lists.
+    pub fn from_json(json: &str) -> GeometryResult<Self> {
+        let catalog: Self =
+            serde_json::from_str(json).map_err(|e| GeometryError::InvalidCatalog(e.to_string()))?;
+        let mut checked = Self::new();
+        for def in catalog.doors {
+            if checked.door_type(&def.name).is_some() {
+                return Err(duplicate(&def.name));
+            }
+            checked.add_door_type(def)?;
+        }
+        for def in catalog.windows {
+            if checked.window_type(&def.name).is_some() {
+                return Err(duplicate(&def.name));
+            }
+            checked.add_window_type(def)?;
+        }
+        Ok(checked)
+    }
+
+    /// Serialize the catalog to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Add a door type, replacing any type with the same name.
+    ///
+    /// Returns the type it replaced.
+    pub fn add_door_type(&mut self, def: DoorTypeDef) -> GeometryResult<Option<DoorTypeDef>> {
+        def.check()?;
+        match self.doors.iter_mut().find(|d| d.name == def.name) {
+            Some(existing) => Ok(Some(std::mem::replace(existing, def))),
+            None => {
+                self.doors.push(def);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Add a window type, replacing any type with the same name.
+    ///
+    /// Returns the type it replaced.
+    pub fn add_window_type(&mut self, def: WindowTypeDef) -> GeometryResult<Option<WindowTypeDef>> {
+        def.check()?;
+        match self.windows.iter_mut().find(|w| w.name == def.name) {
+            Some(existing) => Ok(Some(std::mem::replace(existing, def))),
+            None => {
+                self.windows.push(def);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Get a door type by name.
+    pub fn door_type(&self, name: &str) -> Option<&DoorTypeDef> {
+        self.doors.iter().find(|d| d.name == name)
+    }
+
+    /// Get a window type by name.
+    pub fn window_type(&self, name: &str) -> Option<&WindowTypeDef> {
+        self.windows.iter().find(|w| w.name == name)
+    }
+
+    /// All door types.
+    pub fn door_types(&self) -> &[DoorTypeDef] {
+        &self.doors
+    }
+
+    /// All window types.
+    pub fn window_types(&self) -> &[WindowTypeDef] {
+        &self.windows
+    }
+
+    /// Bring every door and window in a building in line with its type.
+    ///
+    /// Instances whose type is in the catalog take its current definition,
+    /// and the wall opening linked to each (through
+    /// [`hosted_element_id`](crate::elements::WallOpening::hosted_element_id))
+    /// is resized to match. Instances without a type, or with a type the
+    /// catalog doesn't have, are left alone. If any resized opening no
+    /// longer fits its wall, nothing is changed.
+    ///
+    /// Returns the number of instances that changed.
+    pub fn apply_updates(&self, building: &mut Building) -> GeometryResult<usize> {
+        let mut walls = building.walls.clone();
+        let mut doors = building.doors.clone();
+        let mut windows = building.windows.clone();
+        let mut changed = 0;
+
+        for door in &mut doors {
+            let Some(def) = door.type_name.as_deref().and_then(|n| self.door_type(n)) else {
+                continue;
+            };
+            if def.describes(door) {
+                continue;
+            }
+            door.apply_type(def)?;
+            resize_opening(&mut walls, door.host_wall_id, door.wall_opening())?;
+            changed += 1;
+        }
+
+        for window in &mut windows {
+            let Some(def) = window
+                .type_name
+                .as_deref()
+                .and_then(|n| self.window_type(n))
+            else {
+                continue;
+            };
+            if def.describes(window) {
+                continue;
+            }
+            window.apply_type(def)?;
+            resize_opening(&mut walls, window.host_wall_id, window.wall_opening())?;
+            changed += 1;
+        }
+
+        building.walls = walls;
+        building.doors = doors;
+        building.windows = windows;
+        Ok(changed)
+    }
+}
+
+fn duplicate(name: &str) -> GeometryError {
+    GeometryError::InvalidCatalog(format!("type '{}' is defined twice", name))
+}
+
+/// Resize the opening linked to the same element as `sized` in its host
+/// wall, if both exist. The opening keeps its ID and position.
+fn resize_opening(
+    walls: &mut [Wall],
+    host_wall_id: Uuid,
+    sized: WallOpening,
+) -> GeometryResult<()> {
+    let Some(wall) = walls.iter_mut().find(|w| w.id == host_wall_id) else {
+        return Ok(());
+    };
+    let Some(existing) = wall
+        .openings
+        .iter()
+        .find(|o| o.hosted_element_id == sized.hosted_element_id)
+    else {
+        return Ok(());
+    };
+    let opening = WallOpening {
+        id: existing.id,
+        offset_along_wall: existing.offset_along_wall,
+        ..sized
+    };
+    wall.replace_opening(opening)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pensaer_math::Point2;
+
+    fn building_with_door(catalog: &Catalog) -> (Building, Uuid) {
+        let mut building = Building::new("Test");
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        let door =
+            Door::from_type(catalog.door_type("Door 838x1981").unwrap(), wall.id, 1.0).unwrap();
+        wall.add_opening(door.wall_opening()).unwrap();
+        let window = Window::from_type(
+            catalog.window_type("Window 1200x1050").unwrap(),
+            wall.id,
+            3.5,
+        )
+        .unwrap();
+        wall.add_opening(window.wall_opening()).unwrap();
+        let door_id = building.add_door(door);
+        building.add_window(window);
+        building.add_wall(wall);
+        (building, door_id)
+    }
+
+    #[test]
+    fn from_type_takes_the_type_definition() {
+        let catalog = Catalog::standard();
+        let (building, _) = building_with_door(&catalog);
+        let door = &building.doors[0];
+
+        assert_eq!(door.width, 0.838);
+        assert_eq!(door.height, 1.981);
+        assert_eq!(door.type_name.as_deref(), Some("Door 838x1981"));
+        assert_eq!(building.windows[0].sill_height, 0.9);
+        assert_eq!(building.windows[0].window_type, WindowType::Casement);
+        assert_eq!(
+            building.walls[0].openings[0].hosted_element_id,
+            Some(door.id)
+        );
+    }
+
+    #[test]
+    fn updated_type_resizes_instances_and_openings() {
+        let mut catalog = Catalog::standard();
+        let (mut building, door_id) = building_with_door(&catalog);
+        assert_eq!(catalog.apply_updates(&mut building).unwrap(), 0);
+
+        let mut def = catalog.door_type("Door 838x1981").unwrap().clone();
+        def.width = 1.0;
+        def.height = 2.1;
+        def.swing = DoorSwing::Right;
+        assert!(catalog.add_door_type(def).unwrap().is_some());
+
+        assert_eq!(catalog.apply_updates(&mut building).unwrap(), 1);
+        let door = &building.doors[0];
+        assert_eq!(door.width, 1.0);
+        assert_eq!(door.swing, DoorSwing::Right);
+        let opening = building.walls[0]
+            .openings
+            .iter()
+            .find(|o| o.hosted_element_id == Some(door_id))
+            .unwrap();
+        assert_eq!(opening.width, 1.0);
+        assert_eq!(opening.height, 2.1);
+        assert_eq!(opening.offset_along_wall, 1.0);
+    }
+
+    #[test]
+    fn update_that_no_longer_fits_changes_nothing() {
+        let mut catalog = Catalog::standard();
+        let (mut building, _) = building_with_door(&catalog);
+
+        let mut def = catalog.window_type("Window 1200x1050").unwrap().clone();
+        def.width = 4.0;
+        catalog.add_window_type(def).unwrap();
+        let mut def = catalog.door_type("Door 838x1981").unwrap().clone();
+        def.width = 0.9;
+        catalog.add_door_type(def).unwrap();
+
+        assert!(matches!(
+            catalog.apply_updates(&mut building),
+            Err(GeometryError::OpeningOutOfBounds { .. } | GeometryError::OverlappingOpenings)
+        ));
+        assert_eq!(building.doors[0].width, 0.838);
+        assert_eq!(building.windows[0].width, 1.2);
+        assert_eq!(building.walls[0].openings[0].width, 0.838);
+    }
+
+    #[test]
+    fn json_round_trip_and_validation() {
+        let catalog = Catalog::standard();
+        let loaded = Catalog::from_json(&catalog.to_json()).unwrap();
+        assert_eq!(loaded, catalog);
+        assert_eq!(loaded.door_types().len(), 3);
+
+        let duplicate = r#"{ "doors": [
+            { "name": "A", "width": 0.9, "height": 2.1 },
+            { "name": "A", "width": 1.0, "height": 2.1 }
+        ] }"#;
+        assert!(matches!(
+            Catalog::from_json(duplicate),
+            Err(GeometryError::InvalidCatalog(_))
+        ));
+        let flat = r#"{ "windows": [ { "name": "W", "width": 1.0, "height": 0.0, "sill_height": 0.9 } ] }"#;
+        assert!(matches!(
+            Catalog::from_json(flat),
+            Err(GeometryError::InvalidCatalog(_))
+        ));
+    }
+}
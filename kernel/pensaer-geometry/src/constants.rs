@@ -49,7 +49,19 @@ pub const EPSILON: f64 = 1e-10;
 /// ```
 #[inline]
 pub fn quantize(x: f64) -> f64 {
-    (x / QUANTIZE_PRECISION).round() * QUANTIZE_PRECISION
+    quantize_to(x, QUANTIZE_PRECISION)
+}
+
+/// Quantize a value to an arbitrary precision.
+///
+/// # Example
+/// ```
+/// use pensaer_geometry::constants::quantize_to;
+/// assert_eq!(quantize_to(1.26, 0.5), 1.5);
+/// ```
+#[inline]
+pub fn quantize_to(x: f64, precision: f64) -> f64 {
+    (x / precision).round() * precision
 }
 
 /// Quantize a 2D point.
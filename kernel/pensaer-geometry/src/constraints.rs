@@ -0,0 +1,556 @@
+//! Lightweight geometric constraints on the topology graph.
+//!
+//! A [`ConstraintSet`] holds a few constraints on walls (edges) and nodes:
+//!
+//! - [`Constraint::LockLength`] keeps a wall at a fixed length
+//! - [`Constraint::LockAngle`] keeps the angle between two walls
+//! - [`Constraint::Coincident`] keeps two nodes at the same point
+//! - [`Constraint::Horizontal`] / [`Constraint::Vertical`] keep a wall
+//!   parallel to an axis
+//!
+//! [`ConstraintSet::validate`] measures how far each constraint is from
+//! holding. [`ConstraintSet::enforce`] is not a full solver: it does a few
+//! rounds of local projection, each constraint in turn moving the free end
+//! of its wall (stretching for length, rotating for angles), until every
+//! constraint holds or the iteration cap is reached. Pinned nodes never
+//! move, so pinning the corner being dragged keeps it under the cursor
+//! while the rest of the wall follows.
+//!
+//! Lengths and distances are in mm, angles in radians.
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use serde::{Deserialize, Serialize};
+
+use crate::topology::{EdgeId, NodeId, TopologyGraph};
+use crate::util::float::dist2;
+
+/// Default distance tolerance for a constraint to count as met, in mm.
+pub const DEFAULT_TOLERANCE: f64 = 1e-6;
+
+/// Default angle tolerance for a constraint to count as met, in radians.
+pub const DEFAULT_ANGLE_TOLERANCE: f64 = 1e-9;
+
+/// Default number of projection rounds before giving up.
+pub const DEFAULT_MAX_ITERATIONS: usize = 50;
+
+/// A geometric constraint on walls or nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Constraint {
+    /// The wall keeps this length (mm).
+    LockLength(EdgeId, f64),
+    /// The second wall's direction is the first's turned counter-clockwise
+    /// by this angle (radians). Directions run from start node to end node.
+    LockAngle(EdgeId, EdgeId, f64),
+    /// The two nodes share a position.
+    Coincident(NodeId, NodeId),
+    /// The wall runs along the x axis.
+    Horizontal(EdgeId),
+    /// The wall runs along the y axis.
+    Vertical(EdgeId),
+}
+
+impl Constraint {
+    /// Whether the constraint measures an angle rather than a distance.
+    fn is_angular(&self) -> bool {
+        matches!(self, Constraint::LockAngle(..))
+    }
+}
+
+/// A constraint that does not hold.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Violation {
+    /// Position of the constraint in its set.
+    pub index: usize,
+    /// The constraint itself.
+    pub constraint: Constraint,
+    /// How far it is from holding: mm for lengths, coincidence, and
+    /// horizontal/vertical offsets, radians for angles. Infinite when the
+    /// constraint refers to a wall or node that no longer exists.
+    pub magnitude: f64,
+}
+
+/// What [`ConstraintSet::enforce`] did.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EnforceReport {
+    /// Projection rounds run.
+    pub iterations: usize,
+    /// Nodes that moved, in the order the constraints first reach them.
+    pub moved: Vec<NodeId>,
+    /// Constraints still violated when enforcement stopped.
+    pub unresolved: Vec<Violation>,
+}
+
+impl EnforceReport {
+    /// Whether every constraint holds.
+    pub fn converged(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+}
+
+/// A set of constraints with the tolerances used to enforce them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintSet {
+    /// Constraints, enforced in this order each round.
+    pub constraints: Vec<Constraint>,
+    /// Distance below which a constraint counts as met (mm).
+    pub tolerance: f64,
+    /// Angle below which a constraint counts as met (radians).
+    pub angle_tolerance: f64,
+    /// Maximum projection rounds.
+    pub max_iterations: usize,
+}
+
+impl Default for ConstraintSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConstraintSet {
+    /// Create an empty set with the default tolerances.
+    pub fn new() -> Self {
+        Self {
+            constraints: Vec::new(),
+            tolerance: DEFAULT_TOLERANCE,
+            angle_tolerance: DEFAULT_ANGLE_TOLERANCE,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+        }
+    }
+
+    /// Set the distance tolerance (mm).
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the angle tolerance (radians).
+    pub fn with_angle_tolerance(mut self, angle_tolerance: f64) -> Self {
+        self.angle_tolerance = angle_tolerance;
+        self
+    }
+
+    /// Set the maximum number of projection rounds.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Add a constraint.
+    pub fn add(&mut self, constraint: Constraint) {
+        self.constraints.push(constraint);
+    }
+
+    /// Number of constraints.
+    pub fn len(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Whether the set has no constraints.
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+
+    /// Constraints that do not hold in `graph`, in set order.
+    pub fn validate(&self, graph: &TopologyGraph) -> Vec<Violation> {
+        let positions = Positions::new(graph);
+        self.violations(&positions)
+    }
+
+    /// Move nodes until the constraints hold, or the iteration cap is hit.
+    ///
+    /// Each round projects every constraint in turn onto the current
+    /// positions, moving only unpinned nodes: a wall with one pinned end
+    /// moves its other end, one with both ends free moves both equally,
+    /// and a fully pinned wall is left alone. Nodes are written back to the
+    /// graph once at the end; no healing is done here.
+    pub fn enforce(&self, graph: &mut TopologyGraph) -> EnforceReport {
+        let mut positions = Positions::new(graph);
+        let mut report = EnforceReport::default();
+
+        let mut unresolved = self.violations(&positions);
+        while !unresolved.is_empty() && report.iterations < self.max_iterations {
+            for constraint in &self.constraints {
+                positions.project(constraint);
+            }
+            report.iterations += 1;
+            unresolved = self.violations(&positions);
+        }
+
+        for (node, position) in positions.changed() {
+            graph.move_node(node, position);
+            report.moved.push(node);
+        }
+        report.unresolved = unresolved;
+        report
+    }
+
+    fn violations(&self, positions: &Positions) -> Vec<Violation> {
+        self.constraints
+            .iter()
+            .enumerate()
+            .filter_map(|(index, constraint)| {
+                let magnitude = positions.error(constraint).unwrap_or(f64::INFINITY);
+                let tolerance = if constraint.is_angular() {
+                    self.angle_tolerance
+                } else {
+                    self.tolerance
+                };
+                (magnitude > tolerance).then_some(Violation {
+                    index,
+                    constraint: *constraint,
+                    magnitude,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A wall's start and end node with their current positions.
+type WallEnds = ((NodeId, [f64; 2]), (NodeId, [f64; 2]));
+
+/// Working copy of the node positions the constraints touch.
+struct Positions<'a> {
+    graph: &'a TopologyGraph,
+    /// Nodes moved so far, in first-move order, with their new positions.
+    moved: Vec<(NodeId, [f64; 2])>,
+    slots: HashMap<NodeId, usize>,
+}
+
+impl<'a> Positions<'a> {
+    fn new(graph: &'a TopologyGraph) -> Self {
+        Self {
+            graph,
+            moved: Vec::new(),
+            slots: HashMap::new(),
+        }
+    }
+
+    fn get(&self, node: NodeId) -> Option<[f64; 2]> {
+        match self.slots.get(&node) {
+            Some(&slot) => Some(self.moved[slot].1),
+            None => self.graph.get_node(node).map(|n| n.position),
+        }
+    }
+
+    fn set(&mut self, node: NodeId, position: [f64; 2]) {
+        match self.slots.get(&node) {
+            Some(&slot) => self.moved[slot].1 = position,
+            None => {
+                self.slots.insert(node, self.moved.len());
+                self.moved.push((node, position));
+            }
+        }
+    }
+
+    fn pinned(&self, node: NodeId) -> bool {
+        // A missing node can't move either
+        match self.graph.get_node(node) {
+            Some(n) => n.pinned,
+            None => true,
+        }
+    }
+
+    /// Nodes whose position actually differs from the graph's.
+    fn changed(&self) -> Vec<(NodeId, [f64; 2])> {
+        self.moved
+            .iter()
+            .filter(|(node, position)| {
+                self.graph.get_node(*node).map(|n| n.position) != Some(*position)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Start and end node of a wall with their current positions.
+    fn wall(&self, edge: EdgeId) -> Option<WallEnds> {
+        let edge = self.graph.get_edge(edge)?;
+        let start = (edge.start_node, self.get(edge.start_node)?);
+        let end = (edge.end_node, self.get(edge.end_node)?);
+        Some((start, end))
+    }
+
+    /// How far a constraint is from holding, or `None` if it refers to a
+    /// missing wall or node.
+    fn error(&self, constraint: &Constraint) -> Option<f64> {
+        Some(match *constraint {
+            Constraint::LockLength(edge, length) => {
+                let ((_, s), (_, t)) = self.wall(edge)?;
+                (dist2(s, t) - length).abs()
+            }
+            Constraint::LockAngle(a, b, angle) => {
+                let ((_, sa), (_, ta)) = self.wall(a)?;
+                let ((_, sb), (_, tb)) = self.wall(b)?;
+                let turn = heading(sb, tb) - heading(sa, ta);
+                wrap_angle(turn - angle).abs()
+            }
+            Constraint::Coincident(a, b) => dist2(self.get(a)?, self.get(b)?),
+            Constraint::Horizontal(edge) => {
+                let ((_, s), (_, t)) = self.wall(edge)?;
+                (t[1] - s[1]).abs()
+            }
+            Constraint::Vertical(edge) => {
+                let ((_, s), (_, t)) = self.wall(edge)?;
+                (t[0] - s[0]).abs()
+            }
+        })
+    }
+
+    /// Move free nodes so one constraint holds, as far as pins allow.
+    fn project(&mut self, constraint: &Constraint) {
+        match *constraint {
+            Constraint::LockLength(edge, length) => {
+                let Some(((sn, s), (tn, t))) = self.wall(edge) else {
+                    return;
+                };
+                let current = dist2(s, t);
+                let dir = if current > 0.0 {
+                    [(t[0] - s[0]) / current, (t[1] - s[1]) / current]
+                } else {
+                    [1.0, 0.0]
+                };
+                let correction = length - current;
+                let (s_share, t_share) = match (self.pinned(sn), self.pinned(tn)) {
+                    (false, false) => (0.5, 0.5),
+                    (true, false) => (0.0, 1.0),
+                    (false, true) => (1.0, 0.0),
+                    (true, true) => return,
+                };
+                if s_share > 0.0 {
+                    self.set(sn, offset(s, dir, -correction * s_share));
+                }
+                if t_share > 0.0 {
+                    self.set(tn, offset(t, dir, correction * t_share));
+                }
+            }
+            Constraint::LockAngle(a, b, angle) => {
+                let (Some(wall_a), Some(wall_b)) = (self.wall(a), self.wall(b)) else {
+                    return;
+                };
+                let ((_, sa), (_, ta)) = wall_a;
+                let ((_, sb), (_, tb)) = wall_b;
+                // Turn the second wall if it can move, otherwise the first
+                if !self.rotate(wall_b, heading(sa, ta) + angle, Some(wall_a)) {
+                    self.rotate(wall_a, heading(sb, tb) - angle, Some(wall_b));
+                }
+            }
+            Constraint::Coincident(a, b) => {
+                let (Some(pa), Some(pb)) = (self.get(a), self.get(b)) else {
+                    return;
+                };
+                match (self.pinned(a), self.pinned(b)) {
+                    (false, false) => {
+                        let mid = [(pa[0] + pb[0]) / 2.0, (pa[1] + pb[1]) / 2.0];
+                        self.set(a, mid);
+                        self.set(b, mid);
+                    }
+                    (false, true) => self.set(a, pb),
+                    (true, false) => self.set(b, pa),
+                    (true, true) => {}
+                }
+            }
+            Constraint::Horizontal(edge) | Constraint::Vertical(edge) => {
+                let Some(wall) = self.wall(edge) else {
+                    return;
+                };
+                let ((_, s), (_, t)) = wall;
+                let horizontal = matches!(constraint, Constraint::Horizontal(_));
+                // Turn to whichever way along the axis is nearer
+                let target = match (horizontal, t[0] >= s[0], t[1] >= s[1]) {
+                    (true, true, _) => 0.0,
+                    (true, false, _) => PI,
+                    (false, _, true) => PI / 2.0,
+                    (false, _, false) => -PI / 2.0,
+                };
+                self.rotate(wall, target, None);
+            }
+        }
+    }
+
+    /// Turn a wall to `heading`, keeping its length, about a pinned end,
+    /// else the end it shares with `neighbour`, else its midpoint. Returns
+    /// false if both ends are pinned.
+    fn rotate(&mut self, wall: WallEnds, heading: f64, neighbour: Option<WallEnds>) -> bool {
+        let ((sn, s), (tn, t)) = wall;
+        let length = dist2(s, t);
+        let dir = [heading.cos(), heading.sin()];
+        let shared =
+            |node: NodeId| neighbour.is_some_and(|((na, _), (nb, _))| node == na || node == nb);
+
+        match (self.pinned(sn), self.pinned(tn)) {
+            (true, true) => return false,
+            (true, false) => self.set(tn, offset(s, dir, length)),
+            (false, true) => self.set(sn, offset(t, dir, -length)),
+            (false, false) if shared(sn) => self.set(tn, offset(s, dir, length)),
+            (false, false) if shared(tn) => self.set(sn, offset(t, dir, -length)),
+            (false, false) => {
+                let mid = [(s[0] + t[0]) / 2.0, (s[1] + t[1]) / 2.0];
+                self.set(sn, offset(mid, dir, -length / 2.0));
+                self.set(tn, offset(mid, dir, length / 2.0));
+            }
+        }
+        true
+    }
+}
+
+/// Direction of travel from `from` to `to`, in radians.
+fn heading(from: [f64; 2], to: [f64; 2]) -> f64 {
+    (to[1] - from[1]).atan2(to[0] - from[0])
+}
+
+/// Angle brought into (-π, π].
+fn wrap_angle(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(2.0 * PI);
+    if wrapped > PI {
+        wrapped - 2.0 * PI
+    } else {
+        wrapped
+    }
+}
+
+fn offset(point: [f64; 2], dir: [f64; 2], distance: f64) -> [f64; 2] {
+    [point[0] + dir[0] * distance, point[1] + dir[1] * distance]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::EdgeData;
+
+    fn wall(graph: &mut TopologyGraph, start: [f64; 2], end: [f64; 2]) -> EdgeId {
+        graph
+            .add_edge(start, end, EdgeData::wall(200.0, 2700.0))
+            .unwrap()
+    }
+
+    fn node_at(graph: &TopologyGraph, position: [f64; 2]) -> NodeId {
+        graph.nearest_node(position).unwrap().0
+    }
+
+    fn pin(graph: &mut TopologyGraph, position: [f64; 2]) {
+        let id = node_at(graph, position);
+        graph.get_node_mut(id).unwrap().pinned = true;
+    }
+
+    fn position(graph: &TopologyGraph, id: NodeId) -> [f64; 2] {
+        graph.get_node(id).unwrap().position
+    }
+
+    #[test]
+    fn dragged_rectangle_corner_snaps_back() {
+        let mut graph = TopologyGraph::new();
+        let corners = [[0.0, 0.0], [4000.0, 0.0], [4000.0, 3000.0], [0.0, 3000.0]];
+        let mut set = ConstraintSet::new();
+        for i in 0..4 {
+            let edge = wall(&mut graph, corners[i], corners[(i + 1) % 4]);
+            let length = dist2(corners[i], corners[(i + 1) % 4]);
+            set.add(Constraint::LockLength(edge, length));
+        }
+        for &corner in &[corners[0], corners[1], corners[3]] {
+            pin(&mut graph, corner);
+        }
+        assert!(set.validate(&graph).is_empty());
+
+        // Drag the free corner away and let go
+        let dragged = node_at(&graph, corners[2]);
+        graph.move_node(dragged, [4350.0, 3420.0]);
+        let violations = set.validate(&graph);
+        assert_eq!(violations.len(), 2);
+        assert!(violations.iter().all(|v| v.magnitude > 100.0));
+
+        let report = set.enforce(&mut graph);
+        assert!(report.converged(), "{:?}", report.unresolved);
+        assert_eq!(report.moved, vec![dragged]);
+        let snapped = position(&graph, dragged);
+        assert!(dist2(snapped, corners[2]) < 1e-3, "{:?}", snapped);
+        for &corner in &[corners[0], corners[1], corners[3]] {
+            assert_eq!(position(&graph, node_at(&graph, corner)), corner);
+        }
+    }
+
+    #[test]
+    fn length_follows_a_pinned_drag() {
+        let mut graph = TopologyGraph::new();
+        let edge = wall(&mut graph, [0.0, 0.0], [4000.0, 0.0]);
+        let mut set = ConstraintSet::new();
+        set.add(Constraint::LockLength(edge, 4000.0));
+
+        // The dragged end is pinned under the cursor; the other end follows
+        let dragged = node_at(&graph, [4000.0, 0.0]);
+        let free = node_at(&graph, [0.0, 0.0]);
+        graph.move_node(dragged, [5000.0, 0.0]);
+        graph.get_node_mut(dragged).unwrap().pinned = true;
+
+        let report = set.enforce(&mut graph);
+        assert!(report.converged());
+        assert_eq!(report.iterations, 1);
+        assert_eq!(position(&graph, dragged), [5000.0, 0.0]);
+        assert!(dist2(position(&graph, free), [1000.0, 0.0]) < 1e-9);
+    }
+
+    #[test]
+    fn angle_and_axis_constraints_rotate_walls() {
+        let mut graph = TopologyGraph::new();
+        let base = wall(&mut graph, [0.0, 0.0], [3000.0, 200.0]);
+        let side = wall(&mut graph, [3000.0, 200.0], [3500.0, 2500.0]);
+        pin(&mut graph, [0.0, 0.0]);
+
+        let mut set = ConstraintSet::new();
+        set.add(Constraint::Horizontal(base));
+        set.add(Constraint::LockAngle(base, side, PI / 2.0));
+        // Turning the base drags the shared corner, so hold the side's length
+        let side_length = dist2([3000.0, 200.0], [3500.0, 2500.0]);
+        set.add(Constraint::LockLength(side, side_length));
+
+        let report = set.enforce(&mut graph);
+        assert!(report.converged(), "{:?}", report.unresolved);
+
+        let (s, t) = graph.edge_positions(base).unwrap();
+        assert_eq!(s, [0.0, 0.0]);
+        assert!(t[1].abs() < 1e-6);
+        let (s2, t2) = graph.edge_positions(side).unwrap();
+        assert!((t2[0] - s2[0]).abs() < 1e-6 && t2[1] > s2[1]);
+        assert!((dist2(s2, t2) - side_length).abs() < 1e-6);
+    }
+
+    #[test]
+    fn coincident_nodes_meet() {
+        let mut graph = TopologyGraph::new();
+        wall(&mut graph, [0.0, 0.0], [1000.0, 0.0]);
+        wall(&mut graph, [1010.0, 0.0], [1010.0, 1000.0]);
+        let a = node_at(&graph, [1000.0, 0.0]);
+        let b = node_at(&graph, [1010.0, 0.0]);
+        graph.get_node_mut(b).unwrap().pinned = true;
+
+        let mut set = ConstraintSet::new();
+        set.add(Constraint::Coincident(a, b));
+        assert!((set.validate(&graph)[0].magnitude - 10.0).abs() < 1e-9);
+
+        assert!(set.enforce(&mut graph).converged());
+        assert_eq!(position(&graph, a), [1010.0, 0.0]);
+    }
+
+    #[test]
+    fn conflicting_constraints_report_unresolved() {
+        let mut graph = TopologyGraph::new();
+        let edge = wall(&mut graph, [0.0, 0.0], [1000.0, 1000.0]);
+        pin(&mut graph, [0.0, 0.0]);
+        pin(&mut graph, [1000.0, 1000.0]);
+        let missing = EdgeId::new();
+
+        let mut set = ConstraintSet::new().with_max_iterations(5);
+        set.add(Constraint::Vertical(edge));
+        set.add(Constraint::LockLength(missing, 1000.0));
+
+        let report = set.enforce(&mut graph);
+        assert!(!report.converged());
+        assert_eq!(report.iterations, 5);
+        assert!(report.moved.is_empty());
+        assert_eq!(report.unresolved.len(), 2);
+        assert!((report.unresolved[0].magnitude - 1000.0).abs() < 1e-9);
+        assert_eq!(report.unresolved[1].index, 1);
+        assert!(report.unresolved[1].magnitude.is_infinite());
+    }
+}
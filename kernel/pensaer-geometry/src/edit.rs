@@ -0,0 +1,356 @@
+//! Editing helpers for arranging walls.
+//!
+//! - [`align_walls`] moves parallel walls onto a common line.
+//! - [`distribute_walls_evenly`] spaces parallel walls at equal intervals.
+//! - [`make_perpendicular`] turns a wall about one end so it meets another
+//!   wall at 90°.
+//!
+//! Walls move as rigid bodies: lengths never change and openings keep their
+//! offsets, so they travel with their wall. Each helper returns an
+//! [`EditReport`] of the endpoints it moved, which the exec and CRDT layers
+//! can record as the mutation.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use pensaer_math::{Point2, Vector2};
+
+use crate::constants::EPSILON;
+use crate::elements::Wall;
+use crate::error::{GeometryError, GeometryResult};
+use crate::joins::WallEnd;
+
+/// Largest sine of the angle between two walls that still counts as parallel.
+const PARALLEL_TOLERANCE: f64 = 1e-6;
+
+/// Reference line used to arrange a set of parallel walls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlignAxis {
+    /// The walls' mean direction and position.
+    #[default]
+    Average,
+    /// The first wall's direction and position.
+    First,
+}
+
+/// One wall endpoint moved by an edit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EndpointMove {
+    /// ID of the moved wall.
+    pub wall_id: Uuid,
+    /// Which end moved.
+    pub end: WallEnd,
+    /// Endpoint before the edit.
+    pub from: Point2,
+    /// Endpoint after the edit.
+    pub to: Point2,
+}
+
+/// Endpoints moved by an edit.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EditReport {
+    /// Moves in wall order, each wall's start before its end. Endpoints
+    /// that stayed put are left out.
+    pub moves: Vec<EndpointMove>,
+}
+
+impl EditReport {
+    /// Whether the edit left every wall where it was.
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// IDs of the walls that moved, in report order.
+    pub fn moved_walls(&self) -> Vec<Uuid> {
+        let mut ids: Vec<Uuid> = Vec::new();
+        for mv in &self.moves {
+            if !ids.contains(&mv.wall_id) {
+                ids.push(mv.wall_id);
+            }
+        }
+        ids
+    }
+
+    /// Move a wall's baseline and record which endpoints changed.
+    fn move_wall(&mut self, wall: &mut Wall, start: Point2, end: Point2) {
+        let ends = [
+            (WallEnd::Start, wall.baseline.start, start),
+            (WallEnd::End, wall.baseline.end, end),
+        ];
+        for (which, from, to) in ends {
+            if from.distance_to(&to) > EPSILON {
+                self.moves.push(EndpointMove {
+                    wall_id: wall.id,
+                    end: which,
+                    from,
+                    to,
+                });
+            }
+        }
+        wall.baseline.start = start;
+        wall.baseline.end = end;
+    }
+
+    /// Translate a wall so its midpoint lies `target` along `normal`.
+    fn shift_wall(&mut self, wall: &mut Wall, normal: Vector2, target: f64) {
+        let shift = normal * (target - normal_offset(wall, normal));
+        let (start, end) = (wall.baseline.start + shift, wall.baseline.end + shift);
+        self.move_wall(wall, start, end);
+    }
+}
+
+/// Move parallel walls onto a common line.
+///
+/// Walls are translated across their direction only, so each keeps its
+/// length and its position along the line. With [`AlignAxis::First`] the
+/// line is the first wall's and that wall does not move; with
+/// [`AlignAxis::Average`] it runs at the walls' mean position. Walls that
+/// are not parallel (in either direction) are rejected before anything
+/// moves.
+pub fn align_walls(walls: &mut [Wall], axis: AlignAxis) -> GeometryResult<EditReport> {
+    let mut report = EditReport::default();
+    if walls.is_empty() {
+        return Ok(report);
+    }
+
+    let normal = common_direction(walls, axis)?.perp();
+    let offsets: Vec<f64> = walls.iter().map(|w| normal_offset(w, normal)).collect();
+    let target = match axis {
+        AlignAxis::Average => offsets.iter().sum::<f64>() / offsets.len() as f64,
+        AlignAxis::First => offsets[0],
+    };
+
+    for wall in walls.iter_mut() {
+        report.shift_wall(wall, normal, target);
+    }
+    Ok(report)
+}
+
+/// Space parallel walls at equal intervals between the outermost two.
+///
+/// Spacing is measured across the reference direction chosen by `axis`.
+/// The outermost walls stay put and the others are translated across their
+/// direction, keeping their order. Fewer than three walls are left
+/// unchanged. Walls that are not parallel are rejected before anything
+/// moves.
+pub fn distribute_walls_evenly(walls: &mut [Wall], axis: AlignAxis) -> GeometryResult<EditReport> {
+    let mut report = EditReport::default();
+    if walls.is_empty() {
+        return Ok(report);
+    }
+
+    let normal = common_direction(walls, axis)?.perp();
+    if walls.len() < 3 {
+        return Ok(report);
+    }
+
+    let offsets: Vec<f64> = walls.iter().map(|w| normal_offset(w, normal)).collect();
+    let mut order: Vec<usize> = (0..walls.len()).collect();
+    order.sort_by(|&a, &b| offsets[a].total_cmp(&offsets[b]));
+
+    let low = offsets[order[0]];
+    let high = offsets[order[order.len() - 1]];
+    let step = (high - low) / (order.len() - 1) as f64;
+    let mut targets = vec![0.0; walls.len()];
+    for (rank, &i) in order.iter().enumerate() {
+        targets[i] = low + step * rank as f64;
+    }
+    targets[order[order.len() - 1]] = high;
+
+    for (wall, target) in walls.iter_mut().zip(targets) {
+        report.shift_wall(wall, normal, target);
+    }
+    Ok(report)
+}
+
+/// Rotate a wall about one of its ends so it is at 90° to `reference`.
+///
+/// `end` stays fixed and the wall keeps its length, turning the shorter way
+/// round. Openings keep their offsets, so their distance from both ends is
+/// unchanged. A wall parallel to the reference could turn either way and is
+/// rejected.
+pub fn make_perpendicular(
+    wall: &mut Wall,
+    reference: &Wall,
+    end: WallEnd,
+) -> GeometryResult<EditReport> {
+    let normal = reference.direction()?.perp();
+    let along = wall.direction()?.dot(&normal);
+    if along.abs() < PARALLEL_TOLERANCE {
+        return Err(GeometryError::ParallelBaselines);
+    }
+
+    let span = normal * (along.signum() * wall.length());
+    let (start, finish) = match end {
+        WallEnd::Start => (wall.baseline.start, wall.baseline.start + span),
+        WallEnd::End => (wall.baseline.end - span, wall.baseline.end),
+    };
+
+    let mut report = EditReport::default();
+    report.move_wall(wall, start, finish);
+    Ok(report)
+}
+
+/// Unit direction shared by `walls`, flipped to agree with the first wall.
+fn common_direction(walls: &[Wall], axis: AlignAxis) -> GeometryResult<Vector2> {
+    let first = walls[0].direction()?;
+    let mut sum = Vector2::new(0.0, 0.0);
+    for wall in walls {
+        let dir = wall.direction()?;
+        if first.cross(&dir).abs() > PARALLEL_TOLERANCE {
+            return Err(GeometryError::NonParallelWalls);
+        }
+        sum += if first.dot(&dir) < 0.0 { -dir } else { dir };
+    }
+
+    match axis {
+        AlignAxis::First => Ok(first),
+        AlignAxis::Average => Ok(sum.normalize()?),
+    }
+}
+
+/// Position of a wall's midpoint along `normal`.
+fn normal_offset(wall: &Wall, normal: Vector2) -> f64 {
+    let mid = wall.baseline.start.midpoint(&wall.baseline.end);
+    normal.dot(&mid.to_vector())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{OpeningType, WallOpening};
+
+    fn wall(x0: f64, y0: f64, x1: f64, y1: f64) -> Wall {
+        Wall::new(Point2::new(x0, y0), Point2::new(x1, y1), 3.0, 0.2).unwrap()
+    }
+
+    fn opening_position(wall: &Wall) -> Point2 {
+        let offset = wall.openings[0].offset_along_wall;
+        wall.baseline.point_at(offset / wall.length())
+    }
+
+    fn close(a: Point2, b: Point2) -> bool {
+        a.distance_to(&b) < 1e-9
+    }
+
+    #[test]
+    fn align_to_first_translates_walls_and_openings() {
+        let mut walls = vec![
+            wall(0.0, 0.0, 4.0, 0.0),
+            wall(5.0, 0.3, 9.0, 0.3),
+            // Antiparallel walls still line up
+            wall(14.0, -0.2, 10.0, -0.2),
+        ];
+        walls[1]
+            .add_opening(WallOpening::new(1.0, 0.0, 0.9, 2.1, OpeningType::Door))
+            .unwrap();
+        let before = opening_position(&walls[1]);
+
+        let report = align_walls(&mut walls, AlignAxis::First).unwrap();
+
+        assert!(walls.iter().all(|w| w.baseline.start.y.abs() < 1e-12));
+        assert!(walls.iter().all(|w| w.baseline.end.y.abs() < 1e-12));
+        assert!(close(walls[2].baseline.start, Point2::new(14.0, 0.0)));
+        // The opening moved with its wall, straight across
+        assert!(close(
+            opening_position(&walls[1]),
+            Point2::new(before.x, 0.0)
+        ));
+
+        assert_eq!(report.moves.len(), 4);
+        assert_eq!(report.moved_walls(), vec![walls[1].id, walls[2].id]);
+        assert_eq!(report.moves[0].end, WallEnd::Start);
+        assert!(close(report.moves[0].from, Point2::new(5.0, 0.3)));
+        assert!(close(report.moves[0].to, Point2::new(5.0, 0.0)));
+    }
+
+    #[test]
+    fn align_to_average_uses_mean_position() {
+        let mut walls = vec![wall(0.0, 0.0, 0.0, 3.0), wall(0.6, 4.0, 0.6, 7.0)];
+
+        align_walls(&mut walls, AlignAxis::Average).unwrap();
+
+        for w in &walls {
+            assert!((w.baseline.start.x - 0.3).abs() < 1e-12);
+            assert!((w.baseline.end.x - 0.3).abs() < 1e-12);
+        }
+        assert!((walls[1].length() - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn align_rejects_non_parallel_walls() {
+        let mut walls = vec![wall(0.0, 0.0, 4.0, 0.0), wall(0.0, 1.0, 4.0, 1.1)];
+        let original = walls.clone();
+
+        let result = align_walls(&mut walls, AlignAxis::Average);
+
+        assert!(matches!(result, Err(GeometryError::NonParallelWalls)));
+        for (w, o) in walls.iter().zip(&original) {
+            assert_eq!(w.baseline.start, o.baseline.start);
+            assert_eq!(w.baseline.end, o.baseline.end);
+        }
+    }
+
+    #[test]
+    fn distribute_spaces_inner_walls_in_order() {
+        let mut walls = vec![
+            wall(0.0, 0.0, 0.0, 3.0),
+            wall(10.0, 0.0, 10.0, 3.0),
+            wall(2.0, 1.0, 2.0, 4.0),
+            wall(3.0, 0.0, 3.0, 3.0),
+        ];
+
+        let report = distribute_walls_evenly(&mut walls, AlignAxis::First).unwrap();
+
+        let xs: Vec<f64> = walls.iter().map(|w| w.baseline.start.x).collect();
+        for (x, want) in xs.iter().zip([0.0, 10.0, 10.0 / 3.0, 20.0 / 3.0]) {
+            assert!((x - want).abs() < 1e-9, "{:?}", xs);
+        }
+        // Positions along the walls are kept
+        assert!((walls[2].baseline.start.y - 1.0).abs() < 1e-12);
+        assert_eq!(report.moved_walls(), vec![walls[2].id, walls[3].id]);
+    }
+
+    #[test]
+    fn distribute_leaves_two_walls_alone() {
+        let mut walls = vec![wall(0.0, 0.0, 4.0, 0.0), wall(0.0, 2.0, 4.0, 2.0)];
+        assert!(distribute_walls_evenly(&mut walls, AlignAxis::Average)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn make_perpendicular_rotates_about_fixed_end() {
+        let reference = wall(0.0, 0.0, 10.0, 0.0);
+        let mut leaning = wall(4.0, 0.0, 4.5, 3.0);
+        leaning
+            .add_opening(WallOpening::new(1.0, 0.9, 0.6, 1.2, OpeningType::Window))
+            .unwrap();
+        let length = leaning.length();
+
+        let report = make_perpendicular(&mut leaning, &reference, WallEnd::Start).unwrap();
+
+        assert!(close(leaning.baseline.start, Point2::new(4.0, 0.0)));
+        assert!(close(leaning.baseline.end, Point2::new(4.0, length)));
+        assert!(close(opening_position(&leaning), Point2::new(4.0, 1.0)));
+        assert_eq!(report.moves.len(), 1);
+        assert_eq!(report.moves[0].end, WallEnd::End);
+
+        // Pivoting on the far end turns the other way round it
+        let mut leaning = wall(4.5, -3.0, 4.0, 0.0);
+        make_perpendicular(&mut leaning, &reference, WallEnd::End).unwrap();
+        assert!(close(leaning.baseline.start, Point2::new(4.0, -length)));
+        assert!(close(leaning.baseline.end, Point2::new(4.0, 0.0)));
+    }
+
+    #[test]
+    fn make_perpendicular_rejects_parallel_wall() {
+        let reference = wall(0.0, 0.0, 10.0, 0.0);
+        let mut other = wall(0.0, 2.0, 5.0, 2.0);
+        assert!(matches!(
+            make_perpendicular(&mut other, &reference, WallEnd::Start),
+            Err(GeometryError::ParallelBaselines)
+        ));
+    }
+}
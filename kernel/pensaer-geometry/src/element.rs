@@ -1,11 +1,17 @@
 //! Element trait and common types for BIM elements.
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use pensaer_math::BoundingBox3;
+use pensaer_math::{BoundingBox3, Line2, Point2, Polygon2, Vector2};
 
-use crate::error::GeometryResult;
+use crate::elements::Wall;
+use crate::error::{GeometryError, GeometryResult};
+use crate::fixup::Delta;
+use crate::ids::{IdGenerator, IdMode, IdPolicy, ELEMENT_ID_NAMESPACE};
 use crate::mesh::TriangleMesh;
 
 /// Type of BIM element.
@@ -24,6 +30,7 @@ pub enum ElementType {
     Stair,
     Railing,
     Furniture,
+    ContextMesh,
     Generic,
 }
 
@@ -44,11 +51,52 @@ impl ElementType {
             ElementType::Stair => "Stair",
             ElementType::Railing => "Railing",
             ElementType::Furniture => "Furniture",
+            ElementType::ContextMesh => "ContextMesh",
             ElementType::Generic => "Generic",
         }
     }
 }
 
+/// Derive an element ID from the element's defining parameters.
+///
+/// The type name, `seed`, and parameters are hashed into a UUIDv5 under
+/// [`ELEMENT_ID_NAMESPACE`], as a fresh [`IdMode::ContentHash`] policy
+/// would, so the same inputs always give the same ID and repeated exports
+/// of a model keep their IFC GlobalIds. Use the seed to tell apart elements
+/// whose parameters coincide.
+pub fn content_id(element_type: ElementType, params: &[f64], seed: u64) -> Uuid {
+    let kind = format!("{}:{}", element_type.name(), seed);
+    IdPolicy::new(IdMode::ContentHash {
+        namespace: ELEMENT_ID_NAMESPACE,
+    })
+    .next_id(&kind, &[], params)
+}
+
+thread_local! {
+    static ID_POLICY: RefCell<IdPolicy> = RefCell::new(IdPolicy::default());
+}
+
+/// Set how element constructors assign IDs to new elements on this thread.
+///
+/// Constructors that take an ID (`with_id`) always keep the one they are
+/// given. Also resets the sequence and duplicate counters, so a script that
+/// sets the mode and then builds its model gets the same IDs on every run.
+pub fn set_id_mode(mode: IdMode) {
+    ID_POLICY.with(|policy| *policy.borrow_mut() = IdPolicy::new(mode));
+}
+
+/// The ID mode of this thread.
+pub fn id_mode() -> IdMode {
+    ID_POLICY.with(|policy| policy.borrow().mode())
+}
+
+/// ID for a new element from this thread's [`IdPolicy`].
+///
+/// See [`IdGenerator::next_id`] for the arguments.
+pub fn next_element_id(kind: &str, refs: &[Uuid], params: &[f64]) -> Uuid {
+    ID_POLICY.with(|policy| policy.borrow_mut().next_id(kind, refs, params))
+}
+
 /// Common trait for all BIM elements.
 ///
 /// All geometry elements in Pensaer implement this trait, providing:
@@ -60,6 +108,9 @@ pub trait Element: Send + Sync {
     /// Get the unique identifier for this element.
     fn id(&self) -> Uuid;
 
+    /// Get the element's metadata.
+    fn metadata(&self) -> &ElementMetadata;
+
     /// Get the type of this element.
     fn element_type(&self) -> ElementType;
 
@@ -68,6 +119,158 @@ pub trait Element: Send + Sync {
 
     /// Generate a triangle mesh for visualization.
     fn to_mesh(&self) -> GeometryResult<TriangleMesh>;
+
+    /// Get the level this element is placed on, if any.
+    fn level_id(&self) -> Option<Uuid> {
+        None
+    }
+
+    /// Get the wall hosting this element, for doors and windows.
+    fn host_wall_id(&self) -> Option<Uuid> {
+        None
+    }
+
+    /// Generate the mesh placed in its host wall. Elements without a host
+    /// wall mesh as [`to_mesh`](Self::to_mesh).
+    fn to_mesh_in_wall(&self, _host: &Wall) -> GeometryResult<TriangleMesh> {
+        self.to_mesh()
+    }
+}
+
+/// A rigid plan-view transform applied to element geometry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlanTransform {
+    /// Move by an offset.
+    Translate(Vector2),
+    /// Rotate counter-clockwise by `angle` radians about `center`.
+    Rotate { center: Point2, angle: f64 },
+    /// Mirror across a line.
+    Mirror(Line2),
+}
+
+impl PlanTransform {
+    /// Transform a point.
+    pub fn apply_point(&self, p: Point2) -> Point2 {
+        match self {
+            PlanTransform::Translate(offset) => p + *offset,
+            PlanTransform::Rotate { center, angle } => *center + (p - *center).rotate(*angle),
+            PlanTransform::Mirror(axis) => axis.reflect_point(&p),
+        }
+    }
+
+    /// Transform a polygon, keeping its winding.
+    pub fn apply_polygon(&self, polygon: &Polygon2) -> Polygon2 {
+        match self {
+            PlanTransform::Translate(offset) => polygon.translate(*offset),
+            PlanTransform::Rotate { center, angle } => polygon.rotate(*center, *angle),
+            PlanTransform::Mirror(axis) => polygon.mirror(axis),
+        }
+    }
+
+    /// Whether the transform reverses handedness.
+    pub fn is_mirror(&self) -> bool {
+        matches!(self, PlanTransform::Mirror(_))
+    }
+
+    /// Whether the X axis ends up closer to the Y axis than to itself,
+    /// i.e. the transform is nearer an odd quarter turn.
+    pub fn swaps_axes(&self) -> bool {
+        let angle = match self {
+            PlanTransform::Translate(_) => return false,
+            PlanTransform::Rotate { angle, .. } => *angle,
+            PlanTransform::Mirror(axis) => 2.0 * axis.direction.angle(),
+        };
+        let (sin, cos) = angle.sin_cos();
+        sin.abs() > cos.abs()
+    }
+}
+
+/// Elements whose parametric definition can be moved, rotated, and mirrored.
+///
+/// Transforms act on the defining geometry (baselines, boundaries) rather
+/// than the mesh, so dimensions and hosted openings survive. Heights and
+/// elevations are unchanged.
+pub trait TransformableElement: Element {
+    /// Apply a plan transform in place.
+    fn apply_transform(&mut self, transform: &PlanTransform);
+
+    /// Move the element by an offset.
+    fn translate(&mut self, offset: Vector2) {
+        self.apply_transform(&PlanTransform::Translate(offset));
+    }
+
+    /// Rotate the element counter-clockwise about a point.
+    fn rotate_about(&mut self, center: Point2, angle: f64) {
+        self.apply_transform(&PlanTransform::Rotate { center, angle });
+    }
+
+    /// Mirror the element across a line.
+    fn mirror(&mut self, axis: &Line2) {
+        self.apply_transform(&PlanTransform::Mirror(*axis));
+    }
+}
+
+/// Apply the same transform to a selection of elements.
+///
+/// Every element is reported in [`Delta::modified`]. Doors and windows are
+/// positioned by their host wall; after a mirror, update them with
+/// [`Door::mirror_in_host`](crate::elements::Door::mirror_in_host) and
+/// [`Window::mirror_in_host`](crate::elements::Window::mirror_in_host).
+pub fn transform_elements(
+    elements: &mut [&mut dyn TransformableElement],
+    transform: &PlanTransform,
+) -> Delta {
+    let mut delta = Delta::new();
+    for element in elements.iter_mut() {
+        element.apply_transform(transform);
+        delta.modified.push(element.id().to_string());
+    }
+    delta
+}
+
+/// Elements whose tag `key` is set to `value`.
+pub fn filter_by_tag<'a, E: Element>(elements: &'a [E], key: &str, value: &str) -> Vec<&'a E> {
+    elements
+        .iter()
+        .filter(|e| e.metadata().get_tag(key) == Some(value))
+        .collect()
+}
+
+/// A building level (storey).
+///
+/// Elements reference a level by ID. Their geometry stays relative to the
+/// level, which supplies the absolute elevation when placed in a building.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Level {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Level name (e.g., "Ground Floor").
+    pub name: String,
+    /// Elevation of the level datum.
+    pub elevation: f64,
+    /// Floor-to-floor height.
+    pub height: f64,
+}
+
+impl Level {
+    /// Create a new level.
+    pub fn new(name: impl Into<String>, elevation: f64, height: f64) -> GeometryResult<Self> {
+        if height <= 0.0 {
+            return Err(GeometryError::NonPositiveHeight { value: height });
+        }
+
+        Ok(Self {
+            id: next_element_id("Level", &[], &[elevation, height]),
+            name: name.into(),
+            elevation,
+            height,
+        })
+    }
+
+    /// Elevation of the level above this one.
+    pub fn top_elevation(&self) -> f64 {
+        self.elevation + self.height
+    }
 }
 
 /// Metadata common to all elements.
@@ -84,6 +287,11 @@ pub struct ElementMetadata {
 
     /// Custom properties.
     pub properties: std::collections::HashMap<String, String>,
+
+    /// Free-form tags such as phase or discipline, for grouping and
+    /// filtering elements.
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
 }
 
 impl ElementMetadata {
@@ -114,4 +322,146 @@ impl ElementMetadata {
     pub fn get_property(&self, key: &str) -> Option<&String> {
         self.properties.get(key)
     }
+
+    /// Set a tag, replacing any previous value.
+    pub fn set_tag(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.tags.insert(key.into(), value.into());
+    }
+
+    /// Get a tag's value.
+    pub fn get_tag(&self, key: &str) -> Option<&str> {
+        self.tags.get(key).map(String::as_str)
+    }
+
+    /// Whether a tag is set, whatever its value.
+    pub fn has_tag(&self, key: &str) -> bool {
+        self.tags.contains_key(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_creation() {
+        let level = Level::new("Level 2", 3.0, 3.2).unwrap();
+        assert_eq!(level.name, "Level 2");
+        assert!((level.top_elevation() - 6.2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn level_rejects_non_positive_height() {
+        assert!(matches!(
+            Level::new("Bad", 0.0, 0.0),
+            Err(GeometryError::NonPositiveHeight { .. })
+        ));
+    }
+
+    #[test]
+    fn content_id_is_stable() {
+        let id = content_id(ElementType::Wall, &[0.0, 1.0, 2.5], 0);
+
+        assert_eq!(id, content_id(ElementType::Wall, &[-0.0, 1.0, 2.5], 0));
+        assert_eq!(id.get_version_num(), 5);
+        assert_ne!(id, content_id(ElementType::Wall, &[0.0, 1.0, 2.5], 1));
+        assert_ne!(id, content_id(ElementType::Floor, &[0.0, 1.0, 2.5], 0));
+        assert_ne!(id, content_id(ElementType::Wall, &[0.0, 1.0, 2.6], 0));
+    }
+
+    #[test]
+    fn id_modes_repeat_per_run() {
+        let namespace = Uuid::from_u128(7);
+        let run = |mode| {
+            set_id_mode(mode);
+            let ids: Vec<Uuid> = (0..3)
+                .map(|_| next_element_id("Wall", &[], &[0.0, 1.0]))
+                .collect();
+            set_id_mode(IdMode::Random);
+            ids
+        };
+
+        let hashed = run(IdMode::ContentHash { namespace });
+        assert_eq!(hashed, run(IdMode::ContentHash { namespace }));
+        // Repeats of the same parameters still get distinct IDs
+        assert_ne!(hashed[0], hashed[1]);
+        assert_ne!(hashed[1], hashed[2]);
+        assert_ne!(
+            hashed[0],
+            run(IdMode::ContentHash {
+                namespace: Uuid::from_u128(8)
+            })[0]
+        );
+
+        let sequential = run(IdMode::Sequential { seed: 1 });
+        assert_eq!(sequential, run(IdMode::Sequential { seed: 1 }));
+        assert_ne!(sequential, run(IdMode::Sequential { seed: 2 }));
+
+        assert_ne!(run(IdMode::Random), run(IdMode::Random));
+        assert_eq!(id_mode(), IdMode::Random);
+    }
+
+    #[test]
+    fn filter_walls_by_tag() {
+        use crate::elements::Wall;
+
+        let walls: Vec<Wall> = (0..4)
+            .map(|i| {
+                let y = i as f64;
+                let mut wall =
+                    Wall::new(Point2::new(0.0, y), Point2::new(4.0, y), 3.0, 0.2).unwrap();
+                if i % 2 == 0 {
+                    wall.metadata.set_tag("level", "L1");
+                } else if i == 1 {
+                    wall.metadata.set_tag("level", "L2");
+                }
+                wall
+            })
+            .collect();
+
+        let l1 = filter_by_tag(&walls, "level", "L1");
+        assert_eq!(l1.len(), 2);
+        assert_eq!(l1[0].id, walls[0].id);
+        assert_eq!(l1[1].id, walls[2].id);
+        assert!(walls[1].metadata.has_tag("level"));
+        assert!(!walls[3].metadata.has_tag("level"));
+        assert!(filter_by_tag(&walls, "phase", "L1").is_empty());
+
+        // Tags survive a JSON round trip
+        let json = serde_json::to_string(&walls[0]).unwrap();
+        let back: Wall = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.metadata.get_tag("level"), Some("L1"));
+    }
+
+    #[test]
+    fn transform_elements_moves_selection() {
+        use crate::elements::{Floor, Room, Wall};
+
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), 3.0, 0.2).unwrap();
+        let mut floor =
+            Floor::rectangle(Point2::new(0.0, 0.0), Point2::new(4.0, 3.0), 0.3).unwrap();
+        let mut room = Room::rectangle(
+            "Office",
+            "1",
+            Point2::new(0.0, 0.0),
+            Point2::new(4.0, 3.0),
+            3.0,
+        )
+        .unwrap();
+        let ids = [wall.id, floor.id, room.id];
+
+        let delta = transform_elements(
+            &mut [&mut wall, &mut floor, &mut room],
+            &PlanTransform::Translate(Vector2::new(10.0, 0.0)),
+        );
+
+        assert_eq!(
+            delta.modified,
+            ids.iter().map(|id| id.to_string()).collect::<Vec<_>>()
+        );
+        assert!((wall.baseline.start.x - 10.0).abs() < 1e-10);
+        assert!((floor.area() - 12.0).abs() < 1e-10);
+        assert!(room.contains_point_2d(&Point2::new(12.0, 1.5)));
+        assert!(!room.contains_point_2d(&Point2::new(2.0, 1.5)));
+    }
 }
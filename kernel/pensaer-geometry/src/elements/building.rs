@@ -0,0 +1,728 @@
+//! Building container for multi-storey models.
+//!
+//! A [`Building`] owns its [`Level`]s and elements. Element geometry is
+//! authored relative to its level; the building applies the level elevation
+//! when producing world-space meshes and bounding boxes.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use pensaer_math::{BoundingBox3, Polygon2, Transform3, Vector3};
+
+use crate::element::{next_element_id, Element, Level};
+use crate::error::{GeometryError, GeometryResult};
+use crate::labels::{layout_labels, LabelCandidate, LabelLayoutOptions, PlacedLabel};
+use crate::mesh::TriangleMesh;
+use crate::report::{QuantityKind, Schedule, ScheduleColumn, ScheduleRow};
+
+use super::{Ceiling, ContextMesh, Door, Floor, Railing, Roof, Room, Stair, Wall, Window};
+
+/// Distance from a wall face to its dimension line in plan labels.
+const DIMENSION_OFFSET: f64 = 0.5;
+
+/// Per-level summary of a building.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelSummary {
+    /// Level ID.
+    pub level_id: Uuid,
+    /// Level name.
+    pub name: String,
+    /// Elevation of the level datum.
+    pub elevation: f64,
+    /// Number of elements placed on the level.
+    pub element_count: usize,
+    /// World-space bounding box of the level's elements.
+    pub bounding_box: Option<BoundingBox3>,
+    /// Total floor slab area.
+    pub floor_area: f64,
+    /// Total room area.
+    pub room_area: f64,
+}
+
+/// An element detached from its building for meshing off the main thread.
+///
+/// Carries the host wall and level elevation, so [`to_mesh`](Self::to_mesh)
+/// gives the same world-space mesh as [`Building::element_mesh`].
+#[derive(Clone)]
+pub struct ElementSnapshot {
+    /// The element.
+    pub element: Arc<dyn Element>,
+    /// Host wall of a door or window.
+    pub host: Option<Wall>,
+    /// Elevation of the element's level.
+    pub elevation: f64,
+}
+
+impl ElementSnapshot {
+    /// Snapshot an element outside any building: no host, at elevation zero.
+    pub fn new(element: Arc<dyn Element>) -> Self {
+        Self {
+            element,
+            host: None,
+            elevation: 0.0,
+        }
+    }
+
+    /// ID of the element.
+    pub fn id(&self) -> Uuid {
+        self.element.id()
+    }
+
+    /// Generate the element's world-space mesh.
+    pub fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
+        world_mesh(&*self.element, self.host.as_ref(), self.elevation)
+    }
+}
+
+/// Mesh an element in its host wall, if any, raised to its level elevation.
+fn world_mesh(
+    element: &dyn Element,
+    host: Option<&Wall>,
+    elevation: f64,
+) -> GeometryResult<TriangleMesh> {
+    let mesh = match host {
+        Some(wall) => element.to_mesh_in_wall(wall)?,
+        None => element.to_mesh()?,
+    };
+    if elevation == 0.0 {
+        return Ok(mesh);
+    }
+
+    Ok(mesh.transformed(&Transform3::translation(0.0, 0.0, elevation)))
+}
+
+/// A building with levels and the elements placed on them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Building {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Building name.
+    pub name: String,
+    /// Levels, ordered by elevation.
+    pub levels: Vec<Level>,
+    /// Walls.
+    pub walls: Vec<Wall>,
+    /// Floor slabs.
+    pub floors: Vec<Floor>,
+    /// Roofs.
+    pub roofs: Vec<Roof>,
+    /// Rooms.
+    pub rooms: Vec<Room>,
+    /// Doors.
+    pub doors: Vec<Door>,
+    /// Windows.
+    pub windows: Vec<Window>,
+    /// Railings.
+    #[serde(default)]
+    pub railings: Vec<Railing>,
+    /// Stairs.
+    #[serde(default)]
+    pub stairs: Vec<Stair>,
+    /// Ceilings.
+    #[serde(default)]
+    pub ceilings: Vec<Ceiling>,
+    /// Imported reference geometry. Not part of [`elements`](Self::elements),
+    /// so it stays out of takeoff and schedules.
+    #[serde(default)]
+    pub context_meshes: Vec<ContextMesh>,
+}
+
+impl Building {
+    /// Create an empty building.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            id: next_element_id("Building", &[], &[]),
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a level, keeping levels ordered by elevation.
+    pub fn add_level(&mut self, level: Level) -> Uuid {
+        let id = level.id;
+        let index = self
+            .levels
+            .partition_point(|l| l.elevation <= level.elevation);
+        self.levels.insert(index, level);
+        id
+    }
+
+    /// Get a level by ID.
+    pub fn level(&self, id: Uuid) -> Option<&Level> {
+        self.levels.iter().find(|l| l.id == id)
+    }
+
+    /// Add a wall.
+    pub fn add_wall(&mut self, wall: Wall) -> Uuid {
+        let id = wall.id;
+        self.walls.push(wall);
+        id
+    }
+
+    /// Add a floor slab.
+    pub fn add_floor(&mut self, floor: Floor) -> Uuid {
+        let id = floor.id;
+        self.floors.push(floor);
+        id
+    }
+
+    /// Add a roof.
+    pub fn add_roof(&mut self, roof: Roof) -> Uuid {
+        let id = roof.id;
+        self.roofs.push(roof);
+        id
+    }
+
+    /// Add a room.
+    pub fn add_room(&mut self, room: Room) -> Uuid {
+        let id = room.id;
+        self.rooms.push(room);
+        id
+    }
+
+    /// Add a door.
+    pub fn add_door(&mut self, door: Door) -> Uuid {
+        let id = door.id;
+        self.doors.push(door);
+        id
+    }
+
+    /// Add a window.
+    pub fn add_window(&mut self, window: Window) -> Uuid {
+        let id = window.id;
+        self.windows.push(window);
+        id
+    }
+
+    /// Add a railing.
+    pub fn add_railing(&mut self, railing: Railing) -> Uuid {
+        let id = railing.id;
+        self.railings.push(railing);
+        id
+    }
+
+    /// Add a stair.
+    pub fn add_stair(&mut self, stair: Stair) -> Uuid {
+        let id = stair.id;
+        self.stairs.push(stair);
+        id
+    }
+
+    /// Add a ceiling.
+    pub fn add_ceiling(&mut self, ceiling: Ceiling) -> Uuid {
+        let id = ceiling.id;
+        self.ceilings.push(ceiling);
+        id
+    }
+
+    /// Add a context mesh.
+    pub fn add_context_mesh(&mut self, context: ContextMesh) -> Uuid {
+        let id = context.id;
+        self.context_meshes.push(context);
+        id
+    }
+
+    /// Get a context mesh by ID.
+    pub fn context_mesh(&self, id: Uuid) -> Option<&ContextMesh> {
+        self.context_meshes.iter().find(|c| c.id == id)
+    }
+
+    /// Iterate over all elements in the building.
+    pub fn elements(&self) -> impl Iterator<Item = &dyn Element> + '_ {
+        self.walls
+            .iter()
+            .map(|e| e as &dyn Element)
+            .chain(self.floors.iter().map(|e| e as &dyn Element))
+            .chain(self.roofs.iter().map(|e| e as &dyn Element))
+            .chain(self.rooms.iter().map(|e| e as &dyn Element))
+            .chain(self.doors.iter().map(|e| e as &dyn Element))
+            .chain(self.windows.iter().map(|e| e as &dyn Element))
+            .chain(self.railings.iter().map(|e| e as &dyn Element))
+            .chain(self.stairs.iter().map(|e| e as &dyn Element))
+            .chain(self.ceilings.iter().map(|e| e as &dyn Element))
+    }
+
+    /// Get an element by ID.
+    pub fn element(&self, id: Uuid) -> Option<&dyn Element> {
+        self.elements().find(|e| e.id() == id)
+    }
+
+    /// Clone an element, with its host wall and level elevation, so it can
+    /// be meshed off the main thread.
+    pub fn element_snapshot(&self, id: Uuid) -> Option<ElementSnapshot> {
+        fn snap<T: Element + Clone + 'static>(items: &[T], id: Uuid) -> Option<Arc<dyn Element>> {
+            items
+                .iter()
+                .find(|e| e.id() == id)
+                .map(|e| Arc::new(e.clone()) as Arc<dyn Element>)
+        }
+
+        let element = snap(&self.walls, id)
+            .or_else(|| snap(&self.floors, id))
+            .or_else(|| snap(&self.roofs, id))
+            .or_else(|| snap(&self.rooms, id))
+            .or_else(|| snap(&self.doors, id))
+            .or_else(|| snap(&self.windows, id))
+            .or_else(|| snap(&self.railings, id))
+            .or_else(|| snap(&self.stairs, id))
+            .or_else(|| snap(&self.ceilings, id))?;
+
+        Some(ElementSnapshot {
+            host: self.host_wall(&*element).cloned(),
+            elevation: self.elevation_of(&*element),
+            element,
+        })
+    }
+
+    /// Place an element on a level.
+    pub fn assign_to_level(&mut self, element_id: Uuid, level_id: Uuid) -> GeometryResult<()> {
+        if self.level(level_id).is_none() {
+            return Err(GeometryError::InvalidElementRef(format!(
+                "level {}",
+                level_id
+            )));
+        }
+
+        let slot = if let Some(w) = self.walls.iter_mut().find(|w| w.id == element_id) {
+            &mut w.level_id
+        } else if let Some(f) = self.floors.iter_mut().find(|f| f.id == element_id) {
+            &mut f.level_id
+        } else if let Some(r) = self.roofs.iter_mut().find(|r| r.id == element_id) {
+            &mut r.level_id
+        } else if let Some(r) = self.rooms.iter_mut().find(|r| r.id == element_id) {
+            &mut r.level_id
+        } else if let Some(d) = self.doors.iter_mut().find(|d| d.id == element_id) {
+            &mut d.level_id
+        } else if let Some(w) = self.windows.iter_mut().find(|w| w.id == element_id) {
+            &mut w.level_id
+        } else if let Some(r) = self.railings.iter_mut().find(|r| r.id == element_id) {
+            &mut r.level_id
+        } else if let Some(s) = self.stairs.iter_mut().find(|s| s.id == element_id) {
+            &mut s.level_id
+        } else if let Some(c) = self.ceilings.iter_mut().find(|c| c.id == element_id) {
+            &mut c.level_id
+        } else {
+            return Err(GeometryError::InvalidElementRef(element_id.to_string()));
+        };
+
+        *slot = Some(level_id);
+        Ok(())
+    }
+
+    /// Get all elements placed on a level.
+    pub fn elements_on_level(&self, level_id: Uuid) -> Vec<&dyn Element> {
+        self.elements()
+            .filter(|e| e.level_id() == Some(level_id))
+            .collect()
+    }
+
+    /// Elevation offset applied to an element (zero when unassigned).
+    pub fn elevation_of(&self, element: &dyn Element) -> f64 {
+        element
+            .level_id()
+            .and_then(|id| self.level(id))
+            .map_or(0.0, |l| l.elevation)
+    }
+
+    /// Generate an element's mesh in world space, offset by its level elevation.
+    ///
+    /// Doors and windows are placed in their host wall's opening when the
+    /// host is in the building.
+    pub fn element_mesh(&self, element_id: Uuid) -> GeometryResult<TriangleMesh> {
+        let element = self
+            .element(element_id)
+            .ok_or_else(|| GeometryError::InvalidElementRef(element_id.to_string()))?;
+        world_mesh(element, self.host_wall(element), self.elevation_of(element))
+    }
+
+    /// The wall hosting a door or window, if it is in the building.
+    fn host_wall(&self, element: &dyn Element) -> Option<&Wall> {
+        let host_id = element.host_wall_id()?;
+        self.walls.iter().find(|w| w.id == host_id)
+    }
+
+    /// World-space bounding box of the elements on a level.
+    ///
+    /// Doors and windows are skipped; they lie within their host walls.
+    pub fn level_bounding_box(&self, level_id: Uuid) -> Option<BoundingBox3> {
+        let elevation = self.level(level_id)?.elevation;
+        let offset = Vector3::new(0.0, 0.0, elevation);
+
+        self.walls
+            .iter()
+            .map(|e| e as &dyn Element)
+            .chain(self.floors.iter().map(|e| e as &dyn Element))
+            .chain(self.roofs.iter().map(|e| e as &dyn Element))
+            .chain(self.rooms.iter().map(|e| e as &dyn Element))
+            .chain(self.railings.iter().map(|e| e as &dyn Element))
+            .chain(self.stairs.iter().map(|e| e as &dyn Element))
+            .chain(self.ceilings.iter().map(|e| e as &dyn Element))
+            .filter(|e| e.level_id() == Some(level_id))
+            .filter_map(|e| e.bounding_box().ok())
+            .map(|b| BoundingBox3::new(b.min + offset, b.max + offset))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Total floor slab area on a level.
+    pub fn level_floor_area(&self, level_id: Uuid) -> f64 {
+        self.floors
+            .iter()
+            .filter(|f| f.level_id == Some(level_id))
+            .map(|f| f.area())
+            .sum()
+    }
+
+    /// Total room area on a level.
+    pub fn level_room_area(&self, level_id: Uuid) -> f64 {
+        self.rooms
+            .iter()
+            .filter(|r| r.level_id == Some(level_id))
+            .map(|r| r.area())
+            .sum()
+    }
+
+    /// Summarize each level, in elevation order.
+    pub fn summary(&self) -> Vec<LevelSummary> {
+        self.levels
+            .iter()
+            .map(|level| LevelSummary {
+                level_id: level.id,
+                name: level.name.clone(),
+                elevation: level.elevation,
+                element_count: self.elements_on_level(level.id).len(),
+                bounding_box: self.level_bounding_box(level.id),
+                floor_area: self.level_floor_area(level.id),
+                room_area: self.level_room_area(level.id),
+            })
+            .collect()
+    }
+
+    /// Per-level quantity schedule: element count, floor area, and room area.
+    pub fn level_schedule(&self) -> Schedule {
+        let mut schedule = Schedule::new(
+            format!("{} levels", self.name),
+            vec![
+                ScheduleColumn::new("elements", QuantityKind::Count),
+                ScheduleColumn::new("floor_area", QuantityKind::Area),
+                ScheduleColumn::new("room_area", QuantityKind::Area),
+            ],
+        );
+        for summary in self.summary() {
+            schedule.rows.push(ScheduleRow {
+                label: summary.name,
+                values: vec![
+                    summary.element_count as f64,
+                    summary.floor_area,
+                    summary.room_area,
+                ],
+            });
+        }
+        schedule
+    }
+
+    /// Railing takeoff schedule, one row per railing.
+    pub fn railing_schedule(&self) -> Schedule {
+        let mut schedule = Schedule::new(
+            format!("{} railings", self.name),
+            vec![
+                ScheduleColumn::new("rail_length", QuantityKind::Length),
+                ScheduleColumn::new("balusters", QuantityKind::Count),
+                ScheduleColumn::new("glass_area", QuantityKind::Area),
+            ],
+        );
+        for railing in &self.railings {
+            let takeoff = railing.takeoff();
+            schedule.rows.push(ScheduleRow {
+                label: railing.id.to_string(),
+                values: vec![
+                    takeoff.rail_length,
+                    takeoff.baluster_count as f64,
+                    takeoff.glass_area,
+                ],
+            });
+        }
+        schedule
+    }
+
+    /// Plan label candidates for a level (or the whole building for `None`):
+    /// room names, door and window marks, and optionally wall length
+    /// dimensions. Unnamed doors and windows are marked D1, D2, ... and
+    /// W1, W2, ... in insertion order.
+    pub fn plan_labels(&self, level_id: Option<Uuid>, dimensions: bool) -> Vec<LabelCandidate> {
+        let on_level =
+            |element_level: Option<Uuid>| level_id.is_none() || element_level == level_id;
+        let mut labels = Vec::new();
+
+        for room in self.rooms.iter().filter(|r| on_level(r.level_id)) {
+            let text = if room.name.is_empty() {
+                &room.number
+            } else {
+                &room.name
+            };
+            labels.push(LabelCandidate::room_name(
+                room.id.to_string(),
+                text.clone(),
+                &room.boundary,
+            ));
+        }
+
+        let doors = self.doors.iter().enumerate().map(|(i, d)| {
+            let mark = d.metadata.name.clone();
+            (
+                d.id,
+                d.host_wall_id,
+                d.offset_along_wall,
+                d.level_id,
+                mark.unwrap_or(format!("D{}", i + 1)),
+            )
+        });
+        let windows = self.windows.iter().enumerate().map(|(i, w)| {
+            let mark = w.metadata.name.clone();
+            (
+                w.id,
+                w.host_wall_id,
+                w.offset_along_wall,
+                w.level_id,
+                mark.unwrap_or(format!("W{}", i + 1)),
+            )
+        });
+        for (id, wall_id, offset, level, mark) in doors.chain(windows) {
+            if !on_level(level) {
+                continue;
+            }
+            let Some(wall) = self.walls.iter().find(|w| w.id == wall_id) else {
+                continue;
+            };
+            let (Ok(direction), Ok(normal)) = (wall.direction(), wall.normal()) else {
+                continue;
+            };
+            let anchor = wall.baseline.start + direction * offset;
+            labels.push(LabelCandidate::tag(id.to_string(), mark, anchor, normal));
+        }
+
+        if dimensions {
+            for wall in self.walls.iter().filter(|w| on_level(w.level_id)) {
+                let Ok(normal) = wall.normal() else {
+                    continue;
+                };
+                let offset = normal * (wall.thickness / 2.0 + DIMENSION_OFFSET);
+                labels.push(LabelCandidate::dimension(
+                    wall.id.to_string(),
+                    format!("{:.2}", wall.length()),
+                    wall.baseline.start + offset,
+                    wall.baseline.end + offset,
+                ));
+            }
+        }
+        labels
+    }
+
+    /// Wall poché outlines on a level, used as label obstacles.
+    pub fn plan_obstacles(&self, level_id: Option<Uuid>) -> Vec<Polygon2> {
+        self.walls
+            .iter()
+            .filter(|w| level_id.is_none() || w.level_id == level_id)
+            .filter_map(|w| w.base_corners().ok())
+            .map(|corners| Polygon2 {
+                vertices: corners.to_vec(),
+            })
+            .collect()
+    }
+
+    /// Lay out plan labels for a level so they clear each other and the walls.
+    pub fn layout_plan_labels(
+        &self,
+        level_id: Option<Uuid>,
+        dimensions: bool,
+        options: &LabelLayoutOptions,
+    ) -> Vec<PlacedLabel> {
+        layout_labels(
+            &self.plan_labels(level_id, dimensions),
+            &self.plan_obstacles(level_id),
+            options,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pensaer_math::{Point2, Polygon2};
+
+    fn square(size: f64) -> Polygon2 {
+        Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(size, 0.0),
+            Point2::new(size, size),
+            Point2::new(0.0, size),
+        ])
+        .unwrap()
+    }
+
+    fn two_storey() -> (Building, Uuid, Uuid) {
+        let mut building = Building::new("Test");
+        let upper = building.add_level(Level::new("Level 2", 3.0, 3.0).unwrap());
+        let ground = building.add_level(Level::new("Level 1", 0.0, 3.0).unwrap());
+        (building, ground, upper)
+    }
+
+    #[test]
+    fn levels_sorted_by_elevation() {
+        let (building, ground, upper) = two_storey();
+        assert_eq!(building.levels[0].id, ground);
+        assert_eq!(building.levels[1].id, upper);
+    }
+
+    #[test]
+    fn elements_on_level() {
+        let (mut building, ground, upper) = two_storey();
+        let w1 = building
+            .add_wall(Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap());
+        let w2 = building
+            .add_wall(Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap());
+        building.assign_to_level(w1, ground).unwrap();
+        building.assign_to_level(w2, upper).unwrap();
+
+        let on_upper = building.elements_on_level(upper);
+        assert_eq!(on_upper.len(), 1);
+        assert_eq!(on_upper[0].id(), w2);
+    }
+
+    #[test]
+    fn element_snapshot_is_detached() {
+        let (mut building, _, _) = two_storey();
+        let wall = building
+            .add_wall(Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap());
+
+        let snapshot = building.element_snapshot(wall).unwrap();
+        building.walls[0].height = 4.0;
+
+        let bbox = snapshot.element.bounding_box().unwrap();
+        assert!((bbox.max.z - 3.0).abs() < 1e-10);
+        assert!(building.element_snapshot(Uuid::new_v4()).is_none());
+    }
+
+    #[test]
+    fn assign_to_unknown_level_fails() {
+        let (mut building, _, _) = two_storey();
+        let wall = building
+            .add_wall(Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap());
+        assert!(building.assign_to_level(wall, Uuid::new_v4()).is_err());
+        assert!(building
+            .assign_to_level(Uuid::new_v4(), building.levels[0].id)
+            .is_err());
+    }
+
+    #[test]
+    fn wall_mesh_offset_by_level_elevation() {
+        let (mut building, _, upper) = two_storey();
+        let wall = building
+            .add_wall(Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap());
+        building.assign_to_level(wall, upper).unwrap();
+
+        let bbox = building.element_mesh(wall).unwrap().bounding_box().unwrap();
+        assert!((bbox.min.z - 3.0).abs() < 1e-10);
+        assert!((bbox.max.z - 6.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn door_mesh_placed_in_host_wall() {
+        let (mut building, _, upper) = two_storey();
+        let host = Wall::new(Point2::new(0.0, 0.0), Point2::new(0.0, 5.0), 3.0, 0.2).unwrap();
+        let door = Door::new(host.id, 0.9, 2.1, 1.5).unwrap();
+        let wall = building.add_wall(host);
+        let door = building.add_door(door);
+        building.assign_to_level(wall, upper).unwrap();
+        building.assign_to_level(door, upper).unwrap();
+
+        let bbox = building.element_mesh(door).unwrap().bounding_box().unwrap();
+        assert!((bbox.min.x + 0.1).abs() < 1e-10 && (bbox.max.x - 0.1).abs() < 1e-10);
+        assert!((bbox.min.y - 1.05).abs() < 1e-10 && (bbox.max.y - 1.95).abs() < 1e-10);
+        assert!((bbox.min.z - 3.0).abs() < 1e-10 && (bbox.max.z - 5.1).abs() < 1e-10);
+    }
+
+    #[test]
+    fn summary_reports_areas_and_bounds() {
+        let (mut building, ground, upper) = two_storey();
+        let floor = building.add_floor(Floor::new(square(10.0), 0.3).unwrap());
+        let room = building.add_room(Room::new("Office", "101", square(4.0), 3.0).unwrap());
+        building.assign_to_level(floor, upper).unwrap();
+        building.assign_to_level(room, upper).unwrap();
+
+        let summary = building.summary();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].level_id, ground);
+        assert_eq!(summary[0].element_count, 0);
+        assert!(summary[0].bounding_box.is_none());
+
+        assert_eq!(summary[1].element_count, 2);
+        assert!((summary[1].floor_area - 100.0).abs() < 1e-10);
+        assert!((summary[1].room_area - 16.0).abs() < 1e-10);
+        let bbox = summary[1].bounding_box.unwrap();
+        assert!(bbox.min.z >= 3.0 - 0.3 - 1e-10);
+        assert!((bbox.max.x - 10.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn level_schedule_exports_rounded_quantities() {
+        let (mut building, _, upper) = two_storey();
+        let floor = building.add_floor(Floor::new(square(10.0), 0.3).unwrap());
+        let room = building.add_room(Room::new("Office", "101", square(1.0 / 3.0), 3.0).unwrap());
+        building.assign_to_level(floor, upper).unwrap();
+        building.assign_to_level(room, upper).unwrap();
+
+        let csv = building
+            .level_schedule()
+            .to_csv(&crate::report::NumberFormat::default());
+        assert_eq!(
+            csv,
+            "label,elements,floor_area,room_area\n\
+             Level 1,0,0.000,0.000\n\
+             Level 2,2,100.000,0.111\n\
+             Total,2,100.000,0.111\n"
+        );
+    }
+
+    #[test]
+    fn plan_labels_clear_walls_and_each_other() {
+        let mut building = Building::new("Labels");
+        let wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(6.0, 0.0), 3.0, 0.2).unwrap();
+        let wall_id = building.add_wall(wall);
+        for (name, offset) in [("Door 101", 2.0), ("Door 102", 3.0)] {
+            let mut door = Door::new(wall_id, 0.9, 2.1, offset).unwrap();
+            door.metadata.name = Some(name.to_string());
+            building.add_door(door);
+        }
+        building.add_room(
+            Room::new(
+                "Mechanical Room",
+                "B01",
+                Polygon2::rectangle(Point2::new(0.0, 1.0), Point2::new(1.0, 2.0)),
+                3.0,
+            )
+            .unwrap(),
+        );
+
+        let options = LabelLayoutOptions::default();
+        let labels = building.layout_plan_labels(None, true, &options);
+        assert_eq!(labels.len(), 4);
+        assert!(labels.iter().all(|l| !l.overlapping));
+        for (i, a) in labels.iter().enumerate() {
+            for b in &labels[i + 1..] {
+                assert!(!a
+                    .bbox
+                    .intersection(&b.bbox)
+                    .is_some_and(|o| o.area() > 1e-12));
+            }
+        }
+
+        let room = &labels[0];
+        assert_eq!(room.text, "Mechanical Room");
+        assert!(room.leader.is_some());
+
+        // Repeated layout produces identical coordinates
+        assert_eq!(labels, building.layout_plan_labels(None, true, &options));
+    }
+}
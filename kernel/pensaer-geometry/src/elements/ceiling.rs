@@ -0,0 +1,320 @@
+//! Ceiling (soffit) element for BIM modeling.
+//!
+//! A ceiling is a horizontal slab whose underside sits at `elevation`. It can
+//! be drawn directly from a boundary or derived from a [`Room`] (hung a given
+//! distance below the room top) or a topological room. Openings cut holes
+//! for skylights and access hatches.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use pensaer_math::{BoundingBox3, Point2, Point3, Polygon2};
+
+use crate::element::{next_element_id, Element, ElementMetadata, ElementType};
+use crate::error::{GeometryError, GeometryResult};
+use crate::mesh::{extrude_polygon_with_holes, TriangleMesh};
+use crate::topology::{TopoRoom, TopologyGraph};
+
+use super::Room;
+
+/// Topology coordinates are in millimeters; elements use meters.
+const MM_TO_M: f64 = 0.001;
+
+/// A ceiling element in the BIM model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ceiling {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Ceiling boundary polygon.
+    pub boundary: Polygon2,
+    /// Slab thickness.
+    pub thickness: f64,
+    /// Elevation of the underside (soffit).
+    pub elevation: f64,
+    /// Holes for skylights, hatches, and similar.
+    pub openings: Vec<Polygon2>,
+    /// Room the ceiling was derived from, if any.
+    pub room_id: Option<Uuid>,
+    /// Level this element is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
+    /// Metadata.
+    pub metadata: ElementMetadata,
+}
+
+impl Ceiling {
+    /// Create a ceiling from a boundary polygon, with its underside at zero.
+    pub fn new(boundary: Polygon2, thickness: f64) -> GeometryResult<Self> {
+        if thickness <= 0.0 {
+            return Err(GeometryError::NonPositiveThickness { value: thickness });
+        }
+        boundary
+            .validate()
+            .map_err(|_| GeometryError::InsufficientVertices)?;
+
+        let mut params: Vec<f64> = boundary.vertices.iter().flat_map(|v| [v.x, v.y]).collect();
+        params.push(thickness);
+
+        Ok(Self {
+            id: next_element_id(ElementType::Ceiling.name(), &[], &params),
+            boundary,
+            thickness,
+            elevation: 0.0,
+            openings: Vec::new(),
+            room_id: None,
+            level_id: None,
+            metadata: ElementMetadata::new(),
+        })
+    }
+
+    /// Create a ceiling covering a room, with its underside `height_offset`
+    /// below the top of the room. The ceiling takes the room's level.
+    pub fn from_room(room: &Room, height_offset: f64, thickness: f64) -> GeometryResult<Self> {
+        if height_offset < 0.0 || height_offset >= room.height {
+            return Err(GeometryError::InvalidCeiling(format!(
+                "offset {} outside room height {}",
+                height_offset, room.height
+            )));
+        }
+
+        let mut ceiling = Self::new(room.boundary.clone(), thickness)?;
+        ceiling.elevation = room.top_elevation() - height_offset;
+        ceiling.room_id = Some(room.id);
+        ceiling.level_id = room.level_id;
+        Ok(ceiling)
+    }
+
+    /// Create a ceiling covering a room detected in a topology graph, with
+    /// its underside at `elevation`. Node positions are converted from
+    /// millimeters to meters.
+    pub fn from_topo_room(
+        graph: &TopologyGraph,
+        room: &TopoRoom,
+        elevation: f64,
+        thickness: f64,
+    ) -> GeometryResult<Self> {
+        if room.is_exterior {
+            return Err(GeometryError::InvalidCeiling(
+                "exterior region has no ceiling".to_string(),
+            ));
+        }
+
+        let vertices = room
+            .boundary_nodes
+            .iter()
+            .map(|id| {
+                graph
+                    .get_node(*id)
+                    .map(|n| Point2::new(n.position[0] * MM_TO_M, n.position[1] * MM_TO_M))
+                    .ok_or_else(|| GeometryError::InvalidElementRef(id.to_string()))
+            })
+            .collect::<GeometryResult<Vec<_>>>()?;
+        let boundary = Polygon2::new(vertices).map_err(|_| GeometryError::InsufficientVertices)?;
+
+        let mut ceiling = Self::new(boundary, thickness)?;
+        ceiling.elevation = elevation;
+        Ok(ceiling)
+    }
+
+    /// Set the elevation of the underside.
+    pub fn set_elevation(&mut self, elevation: f64) {
+        self.elevation = elevation;
+    }
+
+    /// Top elevation of the slab.
+    pub fn top_elevation(&self) -> f64 {
+        self.elevation + self.thickness
+    }
+
+    /// Area of the ceiling surface, excluding openings.
+    pub fn area(&self) -> f64 {
+        let opening_area: f64 = self.openings.iter().map(|o| o.area()).sum();
+        self.boundary.area() - opening_area
+    }
+
+    /// Cut an opening. It must lie inside the boundary and clear of other
+    /// openings.
+    pub fn add_opening(&mut self, opening: Polygon2) -> GeometryResult<()> {
+        opening
+            .validate()
+            .map_err(|_| GeometryError::InsufficientVertices)?;
+
+        let inside = opening
+            .vertices
+            .iter()
+            .all(|v| self.boundary.contains_point(v))
+            && !opening
+                .edges()
+                .any(|e| self.boundary.edges().any(|b| b.intersects(&e)));
+        if !inside {
+            return Err(GeometryError::InvalidCeiling(
+                "opening must lie inside the ceiling boundary".to_string(),
+            ));
+        }
+        if self.openings.iter().any(|o| o.intersects(&opening)) {
+            return Err(GeometryError::InvalidCeiling(
+                "opening overlaps an existing opening".to_string(),
+            ));
+        }
+
+        self.openings.push(opening);
+        Ok(())
+    }
+
+    /// Remove an opening by index.
+    pub fn remove_opening(&mut self, index: usize) -> bool {
+        if index < self.openings.len() {
+            self.openings.remove(index);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Element for Ceiling {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
+    fn element_type(&self) -> ElementType {
+        ElementType::Ceiling
+    }
+
+    fn bounding_box(&self) -> GeometryResult<BoundingBox3> {
+        let bbox2 = self
+            .boundary
+            .bounding_box()
+            .ok_or(GeometryError::InsufficientVertices)?;
+
+        Ok(BoundingBox3::new(
+            Point3::new(bbox2.min.x, bbox2.min.y, self.elevation),
+            Point3::new(bbox2.max.x, bbox2.max.y, self.top_elevation()),
+        ))
+    }
+
+    fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
+        // Outer boundary CCW and holes CW, as the extrusion expects
+        let mut outer = self.boundary.clone();
+        outer.ensure_ccw();
+        let holes: Vec<Vec<Point2>> = self
+            .openings
+            .iter()
+            .map(|o| {
+                let mut hole = o.clone();
+                hole.ensure_cw();
+                hole.vertices
+            })
+            .collect();
+
+        extrude_polygon_with_holes(&outer.vertices, &holes, self.thickness, self.elevation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::EdgeData;
+
+    fn office() -> Room {
+        Room::rectangle(
+            "Office",
+            "101",
+            Point2::new(0.0, 0.0),
+            Point2::new(5.0, 4.0),
+            3.0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn ceiling_from_room_matches_room() {
+        let mut room = office();
+        room.set_elevation(3.0);
+        let ceiling = Ceiling::from_room(&room, 0.4, 0.02).unwrap();
+
+        assert!((ceiling.area() - room.area()).abs() < 1e-10);
+        assert!((ceiling.elevation - 5.6).abs() < 1e-10);
+        assert_eq!(ceiling.room_id, Some(room.id));
+
+        let bbox = ceiling.bounding_box().unwrap();
+        assert!((bbox.max.z - 5.62).abs() < 1e-10);
+        assert!(ceiling.to_mesh().unwrap().is_valid());
+    }
+
+    #[test]
+    fn opening_subtracts_from_area() {
+        let mut ceiling = Ceiling::from_room(&office(), 0.3, 0.02).unwrap();
+        ceiling
+            .add_opening(Polygon2::rectangle(
+                Point2::new(1.0, 1.0),
+                Point2::new(2.0, 1.5),
+            ))
+            .unwrap();
+        ceiling
+            .add_opening(Polygon2::rectangle(
+                Point2::new(3.0, 2.0),
+                Point2::new(3.6, 2.6),
+            ))
+            .unwrap();
+
+        let expected = 20.0 - 0.5 - 0.36;
+        assert!((ceiling.area() - expected).abs() < 1e-10);
+
+        let mesh = ceiling.to_mesh().unwrap();
+        assert!(mesh.is_valid());
+        assert!((mesh.volume() - expected * 0.02).abs() < 1e-9);
+    }
+
+    #[test]
+    fn opening_must_fit_inside() {
+        let mut ceiling = Ceiling::from_room(&office(), 0.3, 0.02).unwrap();
+        let outside = Polygon2::rectangle(Point2::new(4.5, 1.0), Point2::new(5.5, 2.0));
+        assert!(matches!(
+            ceiling.add_opening(outside),
+            Err(GeometryError::InvalidCeiling(_))
+        ));
+
+        let hatch = Polygon2::rectangle(Point2::new(1.0, 1.0), Point2::new(2.0, 2.0));
+        ceiling.add_opening(hatch.clone()).unwrap();
+        assert!(matches!(
+            ceiling.add_opening(hatch),
+            Err(GeometryError::InvalidCeiling(_))
+        ));
+    }
+
+    #[test]
+    fn offset_must_be_within_room_height() {
+        assert!(matches!(
+            Ceiling::from_room(&office(), 3.0, 0.02),
+            Err(GeometryError::InvalidCeiling(_))
+        ));
+    }
+
+    #[test]
+    fn ceiling_from_topo_room_uses_meters() {
+        let mut graph = TopologyGraph::new();
+        let corners = [[0.0, 0.0], [4000.0, 0.0], [4000.0, 3000.0], [0.0, 3000.0]];
+        for i in 0..4 {
+            graph.add_edge(
+                corners[i],
+                corners[(i + 1) % 4],
+                EdgeData::wall(200.0, 2700.0),
+            );
+        }
+        graph.rebuild_rooms();
+        let room = graph.rooms().find(|r| !r.is_exterior).unwrap();
+
+        let ceiling = Ceiling::from_topo_room(&graph, room, 2.6, 0.02).unwrap();
+        assert!((ceiling.area() - 12.0).abs() < 1e-9);
+        assert!((ceiling.elevation - 2.6).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,530 @@
+//! Context mesh: imported reference geometry such as existing buildings or
+//! terrain scans.
+//!
+//! A context mesh is read-only and kept apart from the model's elements. It
+//! takes part in clash detection (as the B set) and region queries, but not
+//! in takeoff or schedules. The geometry is split into chunks with their own
+//! bounding boxes, so culling and clash queries only touch the chunks they
+//! need. Only the source reference (path and content hash) is serialized;
+//! call [`ContextMesh::reload`] after deserializing to read the geometry back.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::mem::size_of;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use pensaer_math::{BoundingBox3, Point3};
+
+use crate::element::{next_element_id, Element, ElementMetadata, ElementType};
+use crate::error::{GeometryError, GeometryResult};
+use crate::mesh::{import_mesh, ImportOptions, ImportStats, MeshFormat, TriangleMesh};
+use crate::spatial::ClashElement;
+
+/// Limits for importing a context mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextMeshOptions {
+    /// Triangle budget; larger meshes are decimated to fit.
+    pub max_triangles: usize,
+    /// Most triangles in one chunk.
+    pub chunk_triangles: usize,
+}
+
+impl Default for ContextMeshOptions {
+    fn default() -> Self {
+        Self {
+            max_triangles: 2_000_000,
+            chunk_triangles: 65_536,
+        }
+    }
+}
+
+/// Where a context mesh's geometry comes from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeshReference {
+    /// Source file path.
+    pub path: String,
+    /// Source file format.
+    pub format: MeshFormat,
+    /// Hash of the source file at import time.
+    pub content_hash: String,
+}
+
+/// A spatial tile of a context mesh.
+#[derive(Debug, Clone)]
+pub struct MeshChunk {
+    /// Bounds of the chunk's triangles.
+    pub bbox: BoundingBox3,
+    /// The chunk's triangles, with only the vertices they use.
+    pub mesh: TriangleMesh,
+}
+
+/// Read-only reference geometry imported from an OBJ or PLY file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextMesh {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Display name.
+    pub name: String,
+    /// Source file reference.
+    pub source: MeshReference,
+    /// Options the mesh was imported with, reused by [`reload`](Self::reload).
+    pub options: ContextMeshOptions,
+    /// Bounds of the whole mesh.
+    pub bbox: Option<BoundingBox3>,
+    /// Triangles kept after decimation.
+    pub triangle_count: usize,
+    /// Triangles in the source file.
+    pub source_triangle_count: usize,
+    /// Level this mesh is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
+    /// Metadata.
+    pub metadata: ElementMetadata,
+    /// Chunked geometry; empty until loaded.
+    #[serde(skip)]
+    chunks: Vec<MeshChunk>,
+}
+
+impl ContextMesh {
+    /// Import a mesh file, choosing the format from its extension.
+    pub fn import(path: impl AsRef<Path>, options: ContextMeshOptions) -> GeometryResult<Self> {
+        let path = path.as_ref();
+        let format = MeshFormat::from_path(path).ok_or_else(|| {
+            GeometryError::MeshImport(format!("unknown mesh format: {}", path.display()))
+        })?;
+        let file = File::open(path).map_err(|e| GeometryError::MeshImport(e.to_string()))?;
+
+        let (mesh, _) = Self::from_reader(path.display().to_string(), file, format, options)?;
+        Ok(mesh)
+    }
+
+    /// Import a mesh from a reader, recording `path` as its source.
+    ///
+    /// The returned stats include the memory used while chunking.
+    pub fn from_reader<R: Read>(
+        path: impl Into<String>,
+        reader: R,
+        format: MeshFormat,
+        options: ContextMeshOptions,
+    ) -> GeometryResult<(Self, ImportStats)> {
+        let path = path.into();
+        let (chunks, stats) = load_chunks(reader, format, options)?;
+
+        let name = Path::new(&path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        // The source's content hash stands in for its geometry
+        let source = Uuid::new_v5(&Uuid::NAMESPACE_OID, stats.content_hash.as_bytes());
+        let mut context = Self {
+            id: next_element_id(ElementType::ContextMesh.name(), &[source], &[]),
+            name,
+            source: MeshReference {
+                path,
+                format,
+                content_hash: stats.content_hash.clone(),
+            },
+            options,
+            bbox: None,
+            triangle_count: 0,
+            source_triangle_count: stats.source_triangles,
+            level_id: None,
+            metadata: ElementMetadata::new(),
+            chunks: Vec::new(),
+        };
+        context.set_chunks(chunks);
+        Ok((context, stats))
+    }
+
+    /// Read the geometry back from the source file.
+    ///
+    /// Fails if the file's content no longer matches the recorded hash.
+    pub fn reload(&mut self) -> GeometryResult<()> {
+        let file =
+            File::open(&self.source.path).map_err(|e| GeometryError::MeshImport(e.to_string()))?;
+        let (chunks, stats) = load_chunks(file, self.source.format, self.options)?;
+        if stats.content_hash != self.source.content_hash {
+            return Err(GeometryError::MeshImport(format!(
+                "{} changed since import",
+                self.source.path
+            )));
+        }
+
+        self.source_triangle_count = stats.source_triangles;
+        self.set_chunks(chunks);
+        Ok(())
+    }
+
+    /// Whether the chunked geometry is in memory.
+    pub fn is_loaded(&self) -> bool {
+        !self.chunks.is_empty() || self.triangle_count == 0
+    }
+
+    /// All chunks.
+    pub fn chunks(&self) -> &[MeshChunk] {
+        &self.chunks
+    }
+
+    /// Chunks whose bounds touch `region`, with their indices.
+    pub fn chunks_in<'a>(
+        &'a self,
+        region: &'a BoundingBox3,
+    ) -> impl Iterator<Item = (usize, &'a MeshChunk)> + 'a {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(move |(_, chunk)| chunk.bbox.intersects(region))
+    }
+
+    /// One clash candidate per chunk, all carrying this mesh's ID.
+    pub fn clash_elements(&self) -> Vec<ClashElement> {
+        self.chunks
+            .iter()
+            .map(|chunk| ClashElement::new(self.id, ElementType::ContextMesh.name(), chunk.bbox))
+            .collect()
+    }
+
+    fn set_chunks(&mut self, chunks: Vec<MeshChunk>) {
+        self.triangle_count = chunks.iter().map(|c| c.mesh.triangle_count()).sum();
+        self.bbox = chunks.iter().map(|c| c.bbox).reduce(|a, b| a.union(&b));
+        self.chunks = chunks;
+    }
+}
+
+impl Element for ContextMesh {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
+    fn element_type(&self) -> ElementType {
+        ElementType::ContextMesh
+    }
+
+    fn bounding_box(&self) -> GeometryResult<BoundingBox3> {
+        self.bbox
+            .ok_or_else(|| GeometryError::MeshImport("context mesh is empty".to_string()))
+    }
+
+    fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
+        let mut mesh = TriangleMesh::new();
+        for chunk in &self.chunks {
+            mesh.merge(&chunk.mesh);
+        }
+        Ok(mesh)
+    }
+}
+
+/// Import a mesh and split it into chunks.
+fn load_chunks<R: Read>(
+    reader: R,
+    format: MeshFormat,
+    options: ContextMeshOptions,
+) -> GeometryResult<(Vec<MeshChunk>, ImportStats)> {
+    let import_options = ImportOptions {
+        max_triangles: options.max_triangles,
+    };
+    let (mesh, mut stats) = import_mesh(reader, format, import_options)?;
+
+    let chunks = split_into_chunks(&mesh, options.chunk_triangles.max(1));
+
+    // The whole mesh and its chunks are held at once while splitting
+    let bytes = |m: &TriangleMesh| {
+        m.vertices.capacity() * size_of::<Point3>() + m.indices.capacity() * size_of::<[u32; 3]>()
+    };
+    let held = bytes(&mesh) + chunks.iter().map(|c| bytes(&c.mesh)).sum::<usize>();
+    stats.peak_bytes = stats.peak_bytes.max(held);
+    Ok((chunks, stats))
+}
+
+/// Split a mesh into `ceil(n / max_triangles)` chunks of near-equal size.
+///
+/// Triangles are divided recursively at the median centroid along the
+/// longest axis, so each chunk covers a compact region.
+fn split_into_chunks(mesh: &TriangleMesh, max_triangles: usize) -> Vec<MeshChunk> {
+    let n = mesh.triangle_count();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let centroids: Vec<Point3> = mesh
+        .indices
+        .iter()
+        .map(|tri| {
+            let [a, b, c] = tri.map(|i| mesh.vertices[i as usize]);
+            Point3::new(
+                (a.x + b.x + c.x) / 3.0,
+                (a.y + b.y + c.y) / 3.0,
+                (a.z + b.z + c.z) / 3.0,
+            )
+        })
+        .collect();
+
+    let parts = n.div_ceil(max_triangles);
+    let mut order: Vec<u32> = (0..n as u32).collect();
+    let mut chunks = Vec::with_capacity(parts);
+    split(mesh, &centroids, &mut order, parts, &mut chunks);
+    chunks
+}
+
+fn split(
+    mesh: &TriangleMesh,
+    centroids: &[Point3],
+    triangles: &mut [u32],
+    parts: usize,
+    chunks: &mut Vec<MeshChunk>,
+) {
+    if parts <= 1 {
+        chunks.push(build_chunk(mesh, triangles));
+        return;
+    }
+
+    let points: Vec<Point3> = triangles.iter().map(|&t| centroids[t as usize]).collect();
+    let Some(bounds) = BoundingBox3::from_points(&points) else {
+        return;
+    };
+    let size = bounds.max - bounds.min;
+    let axis = |p: &Point3| {
+        if size.x >= size.y && size.x >= size.z {
+            p.x
+        } else if size.y >= size.z {
+            p.y
+        } else {
+            p.z
+        }
+    };
+
+    let left_parts = parts / 2;
+    let mid = triangles.len() * left_parts / parts;
+    triangles.select_nth_unstable_by(mid, |&a, &b| {
+        axis(&centroids[a as usize]).total_cmp(&axis(&centroids[b as usize]))
+    });
+    let (left, right) = triangles.split_at_mut(mid);
+    split(mesh, centroids, left, left_parts, chunks);
+    split(mesh, centroids, right, parts - left_parts, chunks);
+}
+
+fn build_chunk(mesh: &TriangleMesh, triangles: &mut [u32]) -> MeshChunk {
+    triangles.sort_unstable();
+
+    let mut remap: HashMap<u32, u32> = HashMap::new();
+    let mut chunk = TriangleMesh::new();
+    for &t in triangles.iter() {
+        let tri = mesh.indices[t as usize].map(|i| {
+            *remap.entry(i).or_insert_with(|| {
+                chunk.vertices.push(mesh.vertices[i as usize]);
+                (chunk.vertices.len() - 1) as u32
+            })
+        });
+        chunk.indices.push(tri);
+    }
+
+    let bbox = chunk
+        .bounding_box()
+        .unwrap_or_else(|| BoundingBox3::new(Point3::ORIGIN, Point3::ORIGIN));
+    MeshChunk { bbox, mesh: chunk }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Wall;
+    use crate::spatial::ClashDetector;
+    use pensaer_math::Point2;
+    use std::io;
+
+    /// Binary PLY of an `n` x `n` vertex terrain grid, generated row by row
+    /// so the source never exists in memory as a whole.
+    struct TerrainPly {
+        n: usize,
+        spacing: f64,
+        stage: usize,
+        row: usize,
+        buf: Vec<u8>,
+        pos: usize,
+    }
+
+    impl TerrainPly {
+        fn new(n: usize, spacing: f64) -> Self {
+            Self {
+                n,
+                spacing,
+                stage: 0,
+                row: 0,
+                buf: Vec::new(),
+                pos: 0,
+            }
+        }
+
+        fn height(&self, x: f64, y: f64) -> f64 {
+            1.0 + 0.5 * (x * 0.05).sin() * (y * 0.05).cos()
+        }
+
+        /// Generate the next piece of the file; `false` at the end.
+        fn refill(&mut self) -> bool {
+            self.buf.clear();
+            self.pos = 0;
+            let n = self.n;
+            match self.stage {
+                0 => {
+                    let faces = 2 * (n - 1) * (n - 1);
+                    self.buf.extend_from_slice(
+                        format!(
+                            "ply\nformat binary_little_endian 1.0\nelement vertex {}\n\
+                             property float x\nproperty float y\nproperty float z\n\
+                             element face {}\nproperty list uchar uint vertex_indices\n\
+                             end_header\n",
+                            n * n,
+                            faces
+                        )
+                        .as_bytes(),
+                    );
+                    self.stage = 1;
+                }
+                1 if self.row < n => {
+                    let y = self.row as f64 * self.spacing;
+                    for col in 0..n {
+                        let x = col as f64 * self.spacing;
+                        for c in [x, y, self.height(x, y)] {
+                            self.buf.extend_from_slice(&(c as f32).to_le_bytes());
+                        }
+                    }
+                    self.row += 1;
+                }
+                1 => {
+                    self.stage = 2;
+                    self.row = 0;
+                    return self.refill();
+                }
+                2 if self.row < n - 1 => {
+                    for col in 0..n - 1 {
+                        let i = (self.row * n + col) as u32;
+                        let n = n as u32;
+                        for tri in [[i, i + 1, i + n + 1], [i, i + n + 1, i + n]] {
+                            self.buf.push(3);
+                            for v in tri {
+                                self.buf.extend_from_slice(&v.to_le_bytes());
+                            }
+                        }
+                    }
+                    self.row += 1;
+                }
+                _ => return false,
+            }
+            true
+        }
+    }
+
+    impl Read for TerrainPly {
+        fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+            while self.pos == self.buf.len() {
+                if !self.refill() {
+                    return Ok(0);
+                }
+            }
+            let n = out.len().min(self.buf.len() - self.pos);
+            out[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn huge_ply_is_decimated_chunked_and_clashed_by_chunk() {
+        // 1583 x 1583 vertices: 5,005,448 triangles
+        let options = ContextMeshOptions {
+            max_triangles: 1_000_000,
+            ..Default::default()
+        };
+        let (context, stats) = ContextMesh::from_reader(
+            "survey/terrain.ply",
+            TerrainPly::new(1583, 0.1),
+            MeshFormat::Ply,
+            options,
+        )
+        .unwrap();
+
+        assert_eq!(stats.source_triangles, 5_005_448);
+        assert!(context.triangle_count <= 1_000_000);
+        assert!(context.triangle_count > 250_000);
+        // Holding the source faces alone would take 60 MB
+        assert!(stats.peak_bytes < 128 << 20, "peak {}", stats.peak_bytes);
+
+        let expected_chunks = context.triangle_count.div_ceil(65_536);
+        assert_eq!(context.chunks().len(), expected_chunks);
+        assert!(context
+            .chunks()
+            .iter()
+            .all(|c| c.mesh.triangle_count() <= 65_536));
+        assert_eq!(context.name, "terrain");
+
+        // A short wall in the middle of one chunk, standing through the surface
+        let (index, chunk) = context
+            .chunks()
+            .iter()
+            .enumerate()
+            .find(|(i, chunk)| {
+                let c = chunk.bbox.center();
+                let probe = BoundingBox3::new(
+                    Point3::new(c.x - 0.5, c.y - 0.5, 0.0),
+                    Point3::new(c.x + 0.5, c.y + 0.5, 3.0),
+                );
+                let alone = context.chunks_in(&probe).all(|(j, _)| j == *i);
+                alone
+            })
+            .unwrap();
+        let c = chunk.bbox.center();
+        let wall = Wall::new(
+            Point2::new(c.x - 0.2, c.y),
+            Point2::new(c.x + 0.2, c.y),
+            3.0,
+            0.2,
+        )
+        .unwrap();
+        let wall_mesh = wall.to_mesh().unwrap();
+
+        let result = ClashDetector::new(0.001)
+            .detect_clashes_with_context(&[(wall.id, "Wall".to_string(), &wall_mesh)], &context);
+        assert_eq!(result.clashes.len(), 1);
+        assert_eq!(result.clashes[0].element_b_id, context.id);
+        assert_eq!(result.narrow_phase_chunks, vec![index]);
+    }
+
+    #[test]
+    fn serialized_by_reference_and_reloaded() {
+        let path = std::env::temp_dir().join(format!("context-{}.obj", Uuid::new_v4()));
+        let obj = "v 0 0 0\nv 4 0 0\nv 4 3 0\nv 0 3 0\nf 1 2 3 4\n";
+        std::fs::write(&path, obj).unwrap();
+
+        let context = ContextMesh::import(&path, ContextMeshOptions::default()).unwrap();
+        assert_eq!(context.triangle_count, 2);
+        assert_eq!(context.chunks().len(), 1);
+
+        let json = serde_json::to_string(&context).unwrap();
+        assert!(json.contains(&context.source.content_hash));
+        assert!(!json.contains("vertices"));
+
+        let mut restored: ContextMesh = serde_json::from_str(&json).unwrap();
+        assert!(!restored.is_loaded());
+        restored.reload().unwrap();
+        assert!(restored.is_loaded());
+        assert_eq!(restored.triangle_count, 2);
+
+        // A changed file no longer matches the recorded hash
+        std::fs::write(&path, format!("{}v 9 9 9\n", obj)).unwrap();
+        let result = restored.reload();
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(result, Err(GeometryError::MeshImport(_))));
+    }
+}
@@ -5,9 +5,18 @@ use uuid::Uuid;
 
 use pensaer_math::{BoundingBox3, Point2, Point3, Polygon2};
 
-use crate::element::{Element, ElementMetadata, ElementType};
+use crate::element::{
+    next_element_id, Element, ElementMetadata, ElementType, PlanTransform, TransformableElement,
+};
 use crate::error::{GeometryError, GeometryResult};
-use crate::mesh::TriangleMesh;
+use crate::mesh::{extrude_polygon_with_holes_using, TriangleMesh, TriangulationMethod};
+use crate::topology::{RoomId, TopologyGraph};
+
+use super::roof::without_spurs;
+use super::room::MIN_ROOM_AREA;
+
+/// Topology coordinates are in millimeters; elements use meters.
+const MM_TO_M: f64 = 0.001;
 
 /// Type of floor construction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -36,6 +45,9 @@ pub struct Floor {
     pub floor_type: FloorType,
     /// Holes/cutouts in the floor.
     pub holes: Vec<Polygon2>,
+    /// Level this element is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
     /// Metadata.
     pub metadata: ElementMetadata,
 }
@@ -43,38 +55,86 @@ pub struct Floor {
 impl Floor {
     /// Create a new floor from a boundary polygon.
     pub fn new(boundary: Polygon2, thickness: f64) -> GeometryResult<Self> {
+        let mut floor = Self::with_id(Uuid::nil(), boundary, thickness)?;
+        let mut params: Vec<f64> = floor
+            .boundary
+            .vertices
+            .iter()
+            .flat_map(|v| [v.x, v.y])
+            .collect();
+        params.push(thickness);
+        floor.id = next_element_id(ElementType::Floor.name(), &[], &params);
+        Ok(floor)
+    }
+
+    /// Create a rectangular floor.
+    pub fn rectangle(min: Point2, max: Point2, thickness: f64) -> GeometryResult<Self> {
+        if min.x >= max.x || min.y >= max.y {
+            return Err(GeometryError::InvalidFloorBounds { min, max });
+        }
+        let boundary = Polygon2::rectangle(min, max);
+        Self::new(boundary, thickness)
+    }
+
+    /// Create a floor with specific ID.
+    pub fn with_id(id: Uuid, boundary: Polygon2, thickness: f64) -> GeometryResult<Self> {
         if thickness <= 0.0 {
-            return Err(GeometryError::NonPositiveThickness);
+            return Err(GeometryError::NonPositiveThickness { value: thickness });
         }
         boundary
             .validate()
             .map_err(|_| GeometryError::InsufficientVertices)?;
 
         Ok(Self {
-            id: Uuid::new_v4(),
+            id,
             boundary,
             thickness,
             base_elevation: 0.0,
             floor_type: FloorType::default(),
             holes: Vec::new(),
+            level_id: None,
             metadata: ElementMetadata::new(),
         })
     }
 
-    /// Create a rectangular floor.
-    pub fn rectangle(min: Point2, max: Point2, thickness: f64) -> GeometryResult<Self> {
-        if min.x >= max.x || min.y >= max.y {
-            return Err(GeometryError::InvalidFloorBounds);
+    /// Create a floor slab covering a region detected in a topology graph.
+    /// Node positions are converted from millimeters to meters and the
+    /// slab sits at elevation 0.
+    pub fn from_topo_room(
+        graph: &TopologyGraph,
+        room_id: RoomId,
+        thickness: f64,
+    ) -> GeometryResult<Self> {
+        let room = graph
+            .get_room(room_id)
+            .ok_or_else(|| GeometryError::InvalidElementRef(room_id.to_string()))?;
+        if room.is_exterior {
+            return Err(GeometryError::InvalidFloor(
+                "exterior region cannot be floored".to_string(),
+            ));
         }
-        let boundary = Polygon2::rectangle(min, max);
-        Self::new(boundary, thickness)
-    }
 
-    /// Create a floor with specific ID.
-    pub fn with_id(id: Uuid, boundary: Polygon2, thickness: f64) -> GeometryResult<Self> {
-        let mut floor = Self::new(boundary, thickness)?;
-        floor.id = id;
-        Ok(floor)
+        // Walls dangling into the room do not bound the slab
+        let vertices = without_spurs(&room.boundary_nodes)
+            .into_iter()
+            .map(|id| {
+                graph
+                    .get_node(id)
+                    .map(|n| Point2::new(n.position[0] * MM_TO_M, n.position[1] * MM_TO_M))
+                    .ok_or_else(|| GeometryError::InvalidElementRef(id.to_string()))
+            })
+            .collect::<GeometryResult<Vec<_>>>()?;
+        let mut boundary =
+            Polygon2::new(vertices).map_err(|_| GeometryError::InsufficientVertices)?;
+        if boundary.area() < MIN_ROOM_AREA {
+            return Err(GeometryError::InvalidFloor(format!(
+                "area {:.4} m² is below the minimum {MIN_ROOM_AREA} m²",
+                boundary.area()
+            )));
+        }
+        boundary.ensure_ccw();
+
+        Self::new(boundary, thickness)
     }
 
     /// Set base elevation.
@@ -117,6 +177,33 @@ impl Floor {
         }
     }
 
+    /// Generate the mesh, triangulating the slab faces with `method`.
+    ///
+    /// [`Element::to_mesh`] uses ear clipping; constrained Delaunay gives
+    /// better-shaped triangles around many holes, for shading and analysis.
+    pub fn to_mesh_using(&self, method: TriangulationMethod) -> GeometryResult<TriangleMesh> {
+        // Outer boundary CCW and holes CW, as the extrusion expects
+        let mut outer = self.boundary.clone();
+        outer.ensure_ccw();
+        let holes: Vec<Vec<Point2>> = self
+            .holes
+            .iter()
+            .map(|h| {
+                let mut hole = h.clone();
+                hole.ensure_cw();
+                hole.vertices
+            })
+            .collect();
+
+        extrude_polygon_with_holes_using(
+            &outer.vertices,
+            &holes,
+            self.thickness,
+            self.base_elevation,
+            method,
+        )
+    }
+
     /// Generate mesh (simplified - no holes).
     pub fn to_mesh_simple(&self) -> GeometryResult<TriangleMesh> {
         // For now, use simple rectangular extrusion
@@ -170,52 +257,34 @@ impl Floor {
 
         Ok(TriangleMesh::from_vertices_indices(vertices, indices))
     }
+}
 
-    /// Generate mesh with boundary shape (uses simple triangulation).
-    fn to_mesh_from_boundary(&self) -> GeometryResult<TriangleMesh> {
-        let n = self.boundary.vertex_count();
-        if n < 3 {
-            return Err(GeometryError::InsufficientVertices);
-        }
-
-        let z0 = self.base_elevation;
-        let z1 = self.top_elevation();
-
-        // Create vertices: bottom ring + top ring
-        let mut vertices = Vec::with_capacity(n * 2);
-        for v in &self.boundary.vertices {
-            vertices.push(Point3::new(v.x, v.y, z0));
-        }
-        for v in &self.boundary.vertices {
-            vertices.push(Point3::new(v.x, v.y, z1));
-        }
-
-        let mut indices = Vec::new();
-
-        // Fan triangulation for top and bottom (works for convex polygons)
-        // For concave polygons, proper triangulation needed
-        for i in 1..n - 1 {
-            // Bottom face (CCW when viewed from below)
-            indices.push([0, (i + 1) as u32, i as u32]);
-            // Top face (CCW when viewed from above)
-            let offset = n as u32;
-            indices.push([offset, offset + i as u32, offset + (i + 1) as u32]);
-        }
+/// Floor slabs generated for the interior rooms of a topology graph.
+#[derive(Debug, Clone, Default)]
+pub struct RoomFloors {
+    /// One floor per interior room, with the room it covers.
+    pub floors: Vec<(RoomId, Floor)>,
+    /// Why each skipped room got no floor.
+    pub warnings: Vec<String>,
+}
 
-        // Side faces
-        for i in 0..n {
-            let next = (i + 1) % n;
-            let i0 = i as u32;
-            let i1 = next as u32;
-            let i2 = (n + next) as u32;
-            let i3 = (n + i) as u32;
+/// Create one floor slab per interior room of a topology graph.
+///
+/// Rooms whose boundary cannot be floored, such as slivers below the
+/// minimum area, are skipped and reported in [`RoomFloors::warnings`].
+pub fn floors_from_rooms(graph: &TopologyGraph, thickness: f64) -> GeometryResult<RoomFloors> {
+    if thickness <= 0.0 {
+        return Err(GeometryError::NonPositiveThickness { value: thickness });
+    }
 
-            indices.push([i0, i1, i2]);
-            indices.push([i0, i2, i3]);
+    let mut result = RoomFloors::default();
+    for room in graph.interior_rooms() {
+        match Floor::from_topo_room(graph, room.id, thickness) {
+            Ok(floor) => result.floors.push((room.id, floor)),
+            Err(e) => result.warnings.push(format!("{}: {e}", room.id)),
         }
-
-        Ok(TriangleMesh::from_vertices_indices(vertices, indices))
     }
+    Ok(result)
 }
 
 impl Element for Floor {
@@ -223,6 +292,14 @@ impl Element for Floor {
         self.id
     }
 
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
     fn element_type(&self) -> ElementType {
         ElementType::Floor
     }
@@ -240,12 +317,15 @@ impl Element for Floor {
     }
 
     fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
-        if self.boundary.is_convex() {
-            self.to_mesh_from_boundary()
-        } else {
-            // Fall back to bounding box for non-convex polygons
-            // until proper triangulation is implemented
-            self.to_mesh_simple()
+        self.to_mesh_using(TriangulationMethod::default())
+    }
+}
+
+impl TransformableElement for Floor {
+    fn apply_transform(&mut self, transform: &PlanTransform) {
+        self.boundary = transform.apply_polygon(&self.boundary);
+        for hole in &mut self.holes {
+            *hole = transform.apply_polygon(hole);
         }
     }
 }
@@ -265,13 +345,19 @@ mod tests {
     #[test]
     fn floor_invalid_bounds() {
         let result = Floor::rectangle(Point2::new(10.0, 0.0), Point2::new(0.0, 10.0), 0.3);
-        assert!(matches!(result, Err(GeometryError::InvalidFloorBounds)));
+        assert!(matches!(
+            result,
+            Err(GeometryError::InvalidFloorBounds { min, .. }) if min.x == 10.0
+        ));
     }
 
     #[test]
     fn floor_non_positive_thickness() {
         let result = Floor::rectangle(Point2::new(0.0, 0.0), Point2::new(10.0, 10.0), 0.0);
-        assert!(matches!(result, Err(GeometryError::NonPositiveThickness)));
+        assert!(matches!(
+            result,
+            Err(GeometryError::NonPositiveThickness { .. })
+        ));
     }
 
     #[test]
@@ -325,4 +411,81 @@ mod tests {
         assert_eq!(floor.holes.len(), 1);
         assert!((floor.area() - 96.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn floor_mesh_u_shape_with_holes() {
+        let boundary = Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(30.0, 0.0),
+            Point2::new(30.0, 20.0),
+            Point2::new(20.0, 20.0),
+            Point2::new(20.0, 8.0),
+            Point2::new(10.0, 8.0),
+            Point2::new(10.0, 20.0),
+            Point2::new(0.0, 20.0),
+        ])
+        .unwrap();
+        let mut floor = Floor::new(boundary, 0.5).unwrap();
+        for y in [2.0, 8.0, 14.0] {
+            let hole = Polygon2::rectangle(Point2::new(22.0, y), Point2::new(28.0, y + 4.0));
+            floor.add_hole(hole).unwrap();
+        }
+
+        let mesh = floor.to_mesh().unwrap();
+
+        assert!(mesh.triangle_count() > 0);
+        assert!(mesh.is_valid());
+        assert!(!mesh.has_degenerate_triangles());
+        assert!((floor.area() - 408.0).abs() < 1e-10);
+        assert!((mesh.volume() - floor.area() * 0.5).abs() < 1e-6);
+
+        // Constrained Delaunay fills the same slab
+        let mesh = floor
+            .to_mesh_using(TriangulationMethod::ConstrainedDelaunay)
+            .unwrap();
+        assert!(mesh.is_valid());
+        assert!(!mesh.has_degenerate_triangles());
+        assert!((mesh.volume() - floor.area() * 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn floors_from_adjacent_rooms() {
+        use crate::topology::EdgeData;
+
+        let mut graph = TopologyGraph::new();
+        let wall = || EdgeData::wall(200.0, 3000.0);
+        let corners = [[0.0, 0.0], [10000.0, 0.0], [10000.0, 8000.0], [0.0, 8000.0]];
+        for i in 0..4 {
+            graph.add_edge(corners[i], corners[(i + 1) % 4], wall());
+        }
+        graph.add_edge([4000.0, 0.0], [4000.0, 8000.0], wall());
+        // A stub wall dangling into the larger room
+        graph.add_edge([4000.0, 4000.0], [6000.0, 4000.0], wall());
+        // A sliver enclosure too small to floor
+        graph.add_edge([20000.0, 0.0], [20050.0, 0.0], wall());
+        graph.add_edge([20050.0, 0.0], [20000.0, 50.0], wall());
+        graph.add_edge([20000.0, 50.0], [20000.0, 0.0], wall());
+        crate::fixup::split_crossings(&mut graph);
+        graph.rebuild_rooms();
+
+        let result = floors_from_rooms(&graph, 0.25).unwrap();
+        assert_eq!(result.floors.len(), 2);
+        assert_eq!(result.warnings.len(), 1);
+
+        let total: f64 = result.floors.iter().map(|(_, f)| f.area()).sum();
+        assert!((total - 80.0).abs() < 1e-9);
+        for (room_id, floor) in &result.floors {
+            assert!(graph.get_room(*room_id).is_some_and(|r| !r.is_exterior));
+            assert!(floor.boundary.signed_area() > 0.0);
+            assert_eq!(floor.base_elevation, 0.0);
+            assert!(floor.to_mesh().unwrap().is_valid());
+        }
+
+        let exterior = graph.rooms().find(|r| r.is_exterior).unwrap();
+        assert!(matches!(
+            Floor::from_topo_room(&graph, exterior.id, 0.25),
+            Err(GeometryError::InvalidFloor(_))
+        ));
+        assert!(floors_from_rooms(&graph, 0.0).is_err());
+    }
 }
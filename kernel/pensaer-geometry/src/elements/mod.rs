@@ -3,23 +3,64 @@
 //! This module contains all the parametric BIM elements:
 //! - [`Wall`] - Walls with baselines, openings, and join support
 //! - [`Floor`] - Floor slabs with polygon boundaries and holes
+//! - [`Ceiling`] - Ceiling slabs, optionally derived from rooms, with openings
 //! - [`Roof`] - Roof elements with various slope types
 //! - [`Door`] - Doors hosted in walls
 //! - [`Window`] - Windows hosted in walls
+//! - [`Skylight`] - Skylights hosted in roof openings
 //! - [`Room`] - Room spaces bounded by walls
+//! - [`Railing`] - Balustrades along slab edges, stairs, and ramps
+//! - [`Stair`] - Straight stairs with landings
+//! - [`ContextMesh`] - Imported reference geometry for context and clashes
+//! - [`Building`] - Container grouping elements by level
 
+mod building;
+mod ceiling;
+mod context_mesh;
 mod floor;
 mod opening;
+mod railing;
 mod roof;
 mod room;
+mod skylight;
+mod stair;
 mod wall;
 
-pub use wall::{OpeningType, Wall, WallBaseline, WallOpening, WallType};
+#[cfg(any(test, feature = "python"))]
+pub(crate) use wall::wall_graph;
+pub use wall::{
+    offset_walls, OffsetSide, OpeningType, SplitPolicy, TopProfile, Wall, WallBaseline, WallEndCap,
+    WallOpening, WallType, SPLIT_PARENT_PROPERTY,
+};
 
-pub use floor::{Floor, FloorType};
+pub use ceiling::Ceiling;
 
-pub use roof::{RidgeDirection, Roof, RoofType};
+pub use floor::{floors_from_rooms, Floor, FloorType, RoomFloors};
 
-pub use opening::{Door, DoorSwing, DoorType, Window, WindowType};
+pub use roof::{
+    RidgeDirection, Roof, RoofOpening, RoofOpeningType, RoofType, DEFAULT_BREAK_INSET,
+    DEFAULT_LOWER_SLOPE_DEGREES,
+};
 
-pub use room::Room;
+pub use skylight::{Skylight, DEFAULT_UPSTAND_HEIGHT};
+
+pub(crate) use opening::{check_frame, default_frame_thickness};
+pub use opening::{
+    Door, DoorSwing, DoorSwingGeometry, DoorType, Window, WindowType, DEFAULT_FRAME_THICKNESS,
+    DEFAULT_SWING_SEGMENTS,
+};
+
+pub use room::{Room, MIN_ROOM_AREA};
+
+pub use railing::{
+    InfillType, RailProfile, Railing, RailingFinding, RailingHost, RailingRules, RailingTakeoff,
+    DEFAULT_MAX_BALUSTER_GAP, DEFAULT_MIN_RAILING_HEIGHT,
+};
+
+pub use stair::{
+    Stair, StairFinding, StairLanding, StairRules, DEFAULT_MAX_RISER, DEFAULT_MIN_TREAD,
+};
+
+pub use context_mesh::{ContextMesh, ContextMeshOptions, MeshChunk, MeshReference};
+
+pub use building::{Building, ElementSnapshot, LevelSummary};
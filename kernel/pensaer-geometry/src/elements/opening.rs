@@ -1,14 +1,198 @@
 //! Door and window elements for BIM modeling.
+//!
+//! Doors and windows are meshed as simple solids: a frame lining the
+//! opening through the full wall thickness, with a door panel or an inset
+//! glazing panel inside it. Their placement comes from the host wall, so
+//! [`Door::to_mesh_in_host`] and [`Window::to_mesh_in_host`] give the
+//! world-space mesh; the plain [`Element::to_mesh`] gives the same solid
+//! in a local frame centred on the opening.
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use pensaer_math::{BoundingBox3, Point3};
+use pensaer_math::{BoundingBox3, Point2, Point3, Vector2};
 
-use crate::element::{Element, ElementMetadata, ElementType};
+use crate::catalog::{DoorTypeDef, WindowTypeDef};
+use crate::element::{next_element_id, Element, ElementMetadata, ElementType};
 use crate::error::{GeometryError, GeometryResult};
 use crate::mesh::TriangleMesh;
 
+use super::wall::{OffsetSide, OpeningType, Wall, WallOpening};
+
+/// Default width of door and window frame members (m).
+pub const DEFAULT_FRAME_THICKNESS: f64 = 0.05;
+
+/// Thickness of a door panel (m), capped at the host wall thickness.
+const DOOR_PANEL_THICKNESS: f64 = 0.04;
+
+/// Thickness of a window glazing panel (m), capped at the host wall thickness.
+const GLAZING_THICKNESS: f64 = 0.024;
+
+/// Default number of segments in a plan swing arc.
+pub const DEFAULT_SWING_SEGMENTS: usize = 16;
+
+/// Frame depth used when a door or window is meshed without its host (m).
+const UNHOSTED_DEPTH: f64 = 0.1;
+
+pub(crate) fn default_frame_thickness() -> f64 {
+    DEFAULT_FRAME_THICKNESS
+}
+
+/// Check a frame member width leaves room for the panel inside it.
+pub(crate) fn check_frame(width: f64, height: f64, frame_thickness: f64) -> GeometryResult<()> {
+    if frame_thickness <= 0.0 {
+        return Err(GeometryError::NonPositiveThickness {
+            value: frame_thickness,
+        });
+    }
+    if 2.0 * frame_thickness >= width.min(height) {
+        return Err(GeometryError::InvalidFrame(format!(
+            "frame thickness {} leaves no room in a {} x {} opening",
+            frame_thickness, width, height
+        )));
+    }
+    Ok(())
+}
+
+/// An axis-aligned box as a closed, outward-wound mesh.
+fn cuboid(min: Point3, max: Point3) -> TriangleMesh {
+    let vertices = vec![
+        Point3::new(min.x, min.y, min.z),
+        Point3::new(max.x, min.y, min.z),
+        Point3::new(max.x, max.y, min.z),
+        Point3::new(min.x, max.y, min.z),
+        Point3::new(min.x, min.y, max.z),
+        Point3::new(max.x, min.y, max.z),
+        Point3::new(max.x, max.y, max.z),
+        Point3::new(min.x, max.y, max.z),
+    ];
+    let indices = vec![
+        // Bottom
+        [0, 2, 1],
+        [0, 3, 2],
+        // Top
+        [4, 5, 6],
+        [4, 6, 7],
+        // Front (-y)
+        [0, 1, 5],
+        [0, 5, 4],
+        // Back (+y)
+        [2, 3, 7],
+        [2, 7, 6],
+        // Left (-x)
+        [3, 0, 4],
+        [3, 4, 7],
+        // Right (+x)
+        [1, 2, 6],
+        [1, 6, 5],
+    ];
+    TriangleMesh::from_vertices_indices(vertices, indices)
+}
+
+/// Width of frame members in an opening, kept from crossing in a very
+/// small opening.
+fn member_width(width: f64, height: f64, frame: f64) -> f64 {
+    frame.min(width.min(height) / 4.0)
+}
+
+/// Frame members of an opening, in a local frame: x along the wall centred
+/// on the opening, y across the wall centred on the baseline, z up from
+/// the bottom of the opening.
+///
+/// The jambs run the full height; the head (and the sill, if `sill`) sit
+/// between them.
+fn frame_members(width: f64, height: f64, frame: f64, depth: f64, sill: bool) -> TriangleMesh {
+    let frame = member_width(width, height, frame);
+    let (x0, x1) = (-width / 2.0, width / 2.0);
+    let (y0, y1) = (-depth / 2.0, depth / 2.0);
+
+    let mut mesh = TriangleMesh::new();
+    for (min, max) in [
+        // Jambs
+        ((x0, y0, 0.0), (x0 + frame, y1, height)),
+        ((x1 - frame, y0, 0.0), (x1, y1, height)),
+        // Head
+        ((x0 + frame, y0, height - frame), (x1 - frame, y1, height)),
+    ] {
+        mesh.merge(&cuboid(
+            Point3::new(min.0, min.1, min.2),
+            Point3::new(max.0, max.1, max.2),
+        ));
+    }
+    if sill {
+        mesh.merge(&cuboid(
+            Point3::new(x0 + frame, y0, 0.0),
+            Point3::new(x1 - frame, y1, frame),
+        ));
+    }
+    mesh
+}
+
+/// [`frame_members`] with an infill filling the frame's inside,
+/// `infill_thickness` deep, standing on the floor when there is no sill.
+fn frame_mesh(
+    width: f64,
+    height: f64,
+    frame: f64,
+    depth: f64,
+    sill: bool,
+    infill_thickness: f64,
+) -> TriangleMesh {
+    let mut mesh = frame_members(width, height, frame, depth, sill);
+    let frame = member_width(width, height, frame);
+    let infill_x = width / 2.0 - frame;
+    let infill_y = infill_thickness.min(depth) / 2.0;
+    let bottom = if sill { frame } else { 0.0 };
+    mesh.merge(&cuboid(
+        Point3::new(-infill_x, -infill_y, bottom),
+        Point3::new(infill_x, infill_y, height - frame),
+    ));
+    mesh
+}
+
+/// Swing symbol for a leaf hinged at `hinge`, lying along `closed` when
+/// shut and along `open` when swung through 90 degrees.
+fn swing_leaf(
+    hinge: Point2,
+    closed: Vector2,
+    open: Vector2,
+    leaf: f64,
+    segments: usize,
+) -> DoorSwingGeometry {
+    let arc_points = (0..=segments)
+        .map(|i| {
+            let angle = std::f64::consts::FRAC_PI_2 * i as f64 / segments as f64;
+            hinge + (closed * angle.cos() + open * angle.sin()) * leaf
+        })
+        .collect();
+    DoorSwingGeometry {
+        hinge_point: hinge,
+        leaf_end: hinge + open * leaf,
+        arc_points,
+    }
+}
+
+/// Move a [`frame_mesh`] into its host wall's opening: x along the
+/// baseline from `offset_along_wall`, y along the wall normal, z up from
+/// the wall base plus `base_height`.
+fn place_in_host(
+    mut mesh: TriangleMesh,
+    host: &Wall,
+    offset_along_wall: f64,
+    base_height: f64,
+) -> GeometryResult<TriangleMesh> {
+    let direction = host.direction()?;
+    let normal = host.normal()?;
+    let origin = host.baseline.start + direction * offset_along_wall;
+    let z0 = host.base_offset + base_height;
+
+    for v in &mut mesh.vertices {
+        let plan = origin + direction * v.x + normal * v.y;
+        *v = Point3::new(plan.x, plan.y, z0 + v.z);
+    }
+    Ok(mesh)
+}
+
 /// Door swing direction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum DoorSwing {
@@ -23,6 +207,40 @@ pub enum DoorSwing {
     None,
 }
 
+impl DoorSwing {
+    /// The swing seen in a mirror image: left and right trade places.
+    pub fn mirrored(self) -> Self {
+        match self {
+            DoorSwing::Left => DoorSwing::Right,
+            DoorSwing::Right => DoorSwing::Left,
+            other => other,
+        }
+    }
+}
+
+/// Plan symbol of one door leaf: the leaf drawn fully open and the
+/// quarter-circle its free edge sweeps.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoorSwingGeometry {
+    /// Hinge position on the wall baseline.
+    pub hinge_point: Point2,
+    /// Free edge of the leaf opened to 90 degrees.
+    pub leaf_end: Point2,
+    /// Arc from the closed free edge (across the opening) to `leaf_end`.
+    pub arc_points: Vec<Point2>,
+}
+
+/// Check that `host` is the wall an opening element claims to sit in.
+fn check_host(host_wall_id: Uuid, host: &Wall) -> GeometryResult<()> {
+    if host.id != host_wall_id {
+        return Err(GeometryError::InvalidElementRef(format!(
+            "wall {} is not the host {}",
+            host.id, host_wall_id
+        )));
+    }
+    Ok(())
+}
+
 /// Type of door.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum DoorType {
@@ -58,6 +276,18 @@ pub struct Door {
     pub swing: DoorSwing,
     /// Offset along wall from wall start to door center.
     pub offset_along_wall: f64,
+    /// Side of the host wall the door opens to.
+    #[serde(default)]
+    pub opening_side: OffsetSide,
+    /// Width of the frame members lining the opening.
+    #[serde(default = "default_frame_thickness")]
+    pub frame_thickness: f64,
+    /// Name of the catalog type this door was created from, if any.
+    #[serde(default)]
+    pub type_name: Option<String>,
+    /// Level this element is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
     /// Metadata.
     pub metadata: ElementMetadata,
 }
@@ -71,24 +301,69 @@ impl Door {
         offset_along_wall: f64,
     ) -> GeometryResult<Self> {
         if width <= 0.0 {
-            return Err(GeometryError::NonPositiveThickness);
+            return Err(GeometryError::NonPositiveThickness { value: width });
         }
         if height <= 0.0 {
-            return Err(GeometryError::NonPositiveHeight);
+            return Err(GeometryError::NonPositiveHeight { value: height });
         }
 
         Ok(Self {
-            id: Uuid::new_v4(),
+            id: next_element_id(
+                ElementType::Door.name(),
+                &[host_wall_id],
+                &[width, height, offset_along_wall],
+            ),
             host_wall_id,
             width,
             height,
             door_type: DoorType::default(),
             swing: DoorSwing::default(),
             offset_along_wall,
+            opening_side: OffsetSide::default(),
+            frame_thickness: DEFAULT_FRAME_THICKNESS,
+            type_name: None,
+            level_id: None,
             metadata: ElementMetadata::new(),
         })
     }
 
+    /// Create a door of a catalog type.
+    pub fn from_type(
+        def: &DoorTypeDef,
+        host_wall_id: Uuid,
+        offset_along_wall: f64,
+    ) -> GeometryResult<Self> {
+        let mut door = Self::new(host_wall_id, def.width, def.height, offset_along_wall)?;
+        door.apply_type(def)?;
+        Ok(door)
+    }
+
+    /// Take the size, type, swing, and frame of a catalog type.
+    pub fn apply_type(&mut self, def: &DoorTypeDef) -> GeometryResult<()> {
+        check_frame(def.width, def.height, def.frame_depth)?;
+        self.width = def.width;
+        self.height = def.height;
+        self.door_type = def.door_type;
+        self.swing = def.swing;
+        self.frame_thickness = def.frame_depth;
+        self.type_name = Some(def.name.clone());
+        Ok(())
+    }
+
+    /// A wall opening sized for this door and linked to it through
+    /// [`WallOpening::hosted_element_id`].
+    pub fn wall_opening(&self) -> WallOpening {
+        let mut opening = WallOpening::new(
+            self.offset_along_wall,
+            0.0,
+            self.width,
+            self.height,
+            OpeningType::Door,
+        );
+        opening.hosted_element_id = Some(self.id);
+        opening
+    }
+
     /// Set door type.
     pub fn set_type(&mut self, door_type: DoorType) {
         self.door_type = door_type;
@@ -98,6 +373,121 @@ impl Door {
     pub fn set_swing(&mut self, swing: DoorSwing) {
         self.swing = swing;
     }
+
+    /// Plan swing symbols in the host wall, one per leaf, with arcs of
+    /// [`DEFAULT_SWING_SEGMENTS`] segments. See
+    /// [`Door::swing_geometry_with_segments`].
+    pub fn swing_geometry(&self, host: &Wall) -> GeometryResult<Vec<DoorSwingGeometry>> {
+        self.swing_geometry_with_segments(host, DEFAULT_SWING_SEGMENTS)
+    }
+
+    /// Plan swing symbols in the host wall, one per leaf.
+    ///
+    /// A left-swing leaf hinges at the jamb nearer the wall start, a right
+    /// one at the far jamb; a double door has a half-width leaf on each
+    /// jamb, and a door with no swing has none. Leaves open towards
+    /// [`Door::opening_side`]. Each arc has `segments` segments (at least
+    /// one).
+    pub fn swing_geometry_with_segments(
+        &self,
+        host: &Wall,
+        segments: usize,
+    ) -> GeometryResult<Vec<DoorSwingGeometry>> {
+        check_host(self.host_wall_id, host)?;
+        let direction = host.direction()?;
+        let outward = match self.opening_side {
+            OffsetSide::Left => host.normal()?,
+            OffsetSide::Right => -host.normal()?,
+        };
+        let jamb = |offset: f64| host.baseline.start + direction * offset;
+        let start_jamb = jamb(self.offset_along_wall - self.width / 2.0);
+        let end_jamb = jamb(self.offset_along_wall + self.width / 2.0);
+
+        // (hinge, direction from the hinge across the closed opening, leaf width)
+        let leaves = match self.swing {
+            DoorSwing::Left => vec![(start_jamb, direction, self.width)],
+            DoorSwing::Right => vec![(end_jamb, -direction, self.width)],
+            DoorSwing::Both => vec![
+                (start_jamb, direction, self.width / 2.0),
+                (end_jamb, -direction, self.width / 2.0),
+            ],
+            DoorSwing::None => Vec::new(),
+        };
+
+        let segments = segments.max(1);
+        Ok(leaves
+            .into_iter()
+            .map(|(hinge, closed, leaf)| swing_leaf(hinge, closed, outward, leaf, segments))
+            .collect())
+    }
+
+    /// The swing arc in the host wall as a single polyline of
+    /// [`DEFAULT_SWING_SEGMENTS`] segments per leaf, from the closed
+    /// position to the leaf opened to 90 degrees.
+    ///
+    /// A double door's two arcs meet where the leaves close, so its
+    /// polyline runs from one open leaf to the other through that point. A
+    /// door with no swing has an empty arc.
+    pub fn swing_arc(&self, host: &Wall) -> GeometryResult<Vec<Point2>> {
+        let mut leaves = self.swing_geometry(host)?.into_iter();
+        let Some(first) = leaves.next() else {
+            return Ok(Vec::new());
+        };
+        let mut arc = first.arc_points;
+        if let Some(second) = leaves.next() {
+            arc.reverse();
+            arc.extend(second.arc_points.into_iter().skip(1));
+        }
+        Ok(arc)
+    }
+
+    /// Set the frame member width; it must leave room for the panel.
+    pub fn set_frame_thickness(&mut self, frame_thickness: f64) -> GeometryResult<()> {
+        check_frame(self.width, self.height, frame_thickness)?;
+        self.frame_thickness = frame_thickness;
+        Ok(())
+    }
+
+    /// World-space mesh of the door in its host wall's opening: a frame
+    /// through the wall thickness around a centred panel.
+    pub fn to_mesh_in_host(&self, host: &Wall) -> GeometryResult<TriangleMesh> {
+        check_host(self.host_wall_id, host)?;
+        place_in_host(
+            self.local_mesh(host.thickness),
+            host,
+            self.offset_along_wall,
+            0.0,
+        )
+    }
+
+    /// World-space bounding box of the door in its host wall.
+    pub fn bounding_box_in_host(&self, host: &Wall) -> GeometryResult<BoundingBox3> {
+        self.to_mesh_in_host(host)?
+            .bounding_box()
+            .ok_or(GeometryError::InsufficientVertices)
+    }
+
+    fn local_mesh(&self, depth: f64) -> TriangleMesh {
+        frame_mesh(
+            self.width,
+            self.height,
+            self.frame_thickness,
+            depth,
+            false,
+            DOOR_PANEL_THICKNESS,
+        )
+    }
+
+    /// Follow a host wall that has just been mirrored.
+    ///
+    /// Mirroring reverses the wall's baseline, so the offset is measured
+    /// from the other end, and the swing changes hand.
+    pub fn mirror_in_host(&mut self, host: &Wall) -> GeometryResult<()> {
+        check_host(self.host_wall_id, host)?;
+        self.offset_along_wall = host.length() - self.offset_along_wall;
+        self.swing = self.swing.mirrored();
+        Ok(())
+    }
 }
 
 impl Element for Door {
@@ -105,57 +495,36 @@ impl Element for Door {
         self.id
     }
 
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
+    fn host_wall_id(&self) -> Option<Uuid> {
+        Some(self.host_wall_id)
+    }
+
+    fn to_mesh_in_wall(&self, host: &Wall) -> GeometryResult<TriangleMesh> {
+        self.to_mesh_in_host(host)
+    }
+
     fn element_type(&self) -> ElementType {
         ElementType::Door
     }
 
     fn bounding_box(&self) -> GeometryResult<BoundingBox3> {
-        // Door bounding box depends on host wall position
-        // This is a placeholder - actual position comes from wall
-        Ok(BoundingBox3::new(
-            Point3::new(0.0, 0.0, 0.0),
-            Point3::new(self.width, 0.1, self.height),
-        ))
+        // Local frame; see `bounding_box_in_host` for the placed box
+        self.to_mesh()?
+            .bounding_box()
+            .ok_or(GeometryError::InsufficientVertices)
     }
 
     fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
-        // Simple rectangular door panel mesh
-        let half_width = self.width / 2.0;
-        let thickness = 0.05; // Door panel thickness
-
-        let vertices = vec![
-            Point3::new(-half_width, 0.0, 0.0),
-            Point3::new(half_width, 0.0, 0.0),
-            Point3::new(half_width, 0.0, self.height),
-            Point3::new(-half_width, 0.0, self.height),
-            Point3::new(-half_width, thickness, 0.0),
-            Point3::new(half_width, thickness, 0.0),
-            Point3::new(half_width, thickness, self.height),
-            Point3::new(-half_width, thickness, self.height),
-        ];
-
-        let indices = vec![
-            // Front
-            [0, 1, 2],
-            [0, 2, 3],
-            // Back
-            [5, 4, 7],
-            [5, 7, 6],
-            // Top
-            [3, 2, 6],
-            [3, 6, 7],
-            // Bottom
-            [0, 5, 1],
-            [0, 4, 5],
-            // Left
-            [0, 3, 7],
-            [0, 7, 4],
-            // Right
-            [1, 5, 6],
-            [1, 6, 2],
-        ];
-
-        Ok(TriangleMesh::from_vertices_indices(vertices, indices))
+        // Without the host, centre the door on the origin
+        Ok(self.local_mesh(UNHOSTED_DEPTH))
     }
 }
 
@@ -196,6 +565,15 @@ pub struct Window {
     pub window_type: WindowType,
     /// Offset along wall from wall start to window center.
     pub offset_along_wall: f64,
+    /// Width of the frame members lining the opening.
+    #[serde(default = "default_frame_thickness")]
+    pub frame_thickness: f64,
+    /// Name of the catalog type this window was created from, if any.
+    #[serde(default)]
+    pub type_name: Option<String>,
+    /// Level this element is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
     /// Metadata.
     pub metadata: ElementMetadata,
 }
@@ -210,24 +588,73 @@ impl Window {
         offset_along_wall: f64,
     ) -> GeometryResult<Self> {
         if width <= 0.0 {
-            return Err(GeometryError::NonPositiveThickness);
+            return Err(GeometryError::NonPositiveThickness { value: width });
         }
         if height <= 0.0 {
-            return Err(GeometryError::NonPositiveHeight);
+            return Err(GeometryError::NonPositiveHeight { value: height });
         }
 
         Ok(Self {
-            id: Uuid::new_v4(),
+            id: next_element_id(
+                ElementType::Window.name(),
+                &[host_wall_id],
+                &[width, height, sill_height, offset_along_wall],
+            ),
             host_wall_id,
             width,
             height,
             sill_height,
             window_type: WindowType::default(),
             offset_along_wall,
+            frame_thickness: DEFAULT_FRAME_THICKNESS,
+            type_name: None,
+            level_id: None,
             metadata: ElementMetadata::new(),
         })
     }
 
+    /// Create a window of a catalog type.
+    pub fn from_type(
+        def: &WindowTypeDef,
+        host_wall_id: Uuid,
+        offset_along_wall: f64,
+    ) -> GeometryResult<Self> {
+        let mut window = Self::new(
+            host_wall_id,
+            def.width,
+            def.height,
+            def.sill_height,
+            offset_along_wall,
+        )?;
+        window.apply_type(def)?;
+        Ok(window)
+    }
+
+    /// Take the size, sill height, and type of a catalog type.
+    pub fn apply_type(&mut self, def: &WindowTypeDef) -> GeometryResult<()> {
+        check_frame(def.width, def.height, self.frame_thickness)?;
+        self.width = def.width;
+        self.height = def.height;
+        self.sill_height = def.sill_height;
+        self.window_type = def.window_type;
+        self.type_name = Some(def.name.clone());
+        Ok(())
+    }
+
+    /// A wall opening sized for this window and linked to it through
+    /// [`WallOpening::hosted_element_id`].
+    pub fn wall_opening(&self) -> WallOpening {
+        let mut opening = WallOpening::new(
+            self.offset_along_wall,
+            self.sill_height,
+            self.width,
+            self.height,
+            OpeningType::Window,
+        );
+        opening.hosted_element_id = Some(self.id);
+        opening
+    }
+
     /// Set window type.
     pub fn set_type(&mut self, window_type: WindowType) {
         self.window_type = window_type;
@@ -237,6 +664,91 @@ impl Window {
     pub fn head_height(&self) -> f64 {
         self.sill_height + self.height
     }
+
+    /// Set the frame member width; it must leave room for the glazing.
+    pub fn set_frame_thickness(&mut self, frame_thickness: f64) -> GeometryResult<()> {
+        check_frame(self.width, self.height, frame_thickness)?;
+        self.frame_thickness = frame_thickness;
+        Ok(())
+    }
+
+    /// World-space mesh of the window in its host wall's opening: a frame
+    /// through the wall thickness around a centred glazing panel.
+    pub fn to_mesh_in_host(&self, host: &Wall) -> GeometryResult<TriangleMesh> {
+        check_host(self.host_wall_id, host)?;
+        place_in_host(
+            self.local_mesh(host.thickness),
+            host,
+            self.offset_along_wall,
+            self.sill_height,
+        )
+    }
+
+    /// World-space bounding box of the window in its host wall.
+    pub fn bounding_box_in_host(&self, host: &Wall) -> GeometryResult<BoundingBox3> {
+        self.to_mesh_in_host(host)?
+            .bounding_box()
+            .ok_or(GeometryError::InsufficientVertices)
+    }
+
+    /// World-space mesh of the window's frame alone, without glazing: the
+    /// jambs, head, and sill lining the opening through the wall thickness,
+    /// plus a mullion dividing the sashes. Double-hung windows get a
+    /// horizontal meeting rail at mid-height; casement and sliding windows a
+    /// vertical mullion at the centre. Members are
+    /// [`Window::frame_thickness`] wide.
+    pub fn to_frame_mesh(&self, host: &Wall) -> GeometryResult<TriangleMesh> {
+        check_host(self.host_wall_id, host)?;
+        place_in_host(
+            self.local_frame(host.thickness),
+            host,
+            self.offset_along_wall,
+            self.sill_height,
+        )
+    }
+
+    fn local_mesh(&self, depth: f64) -> TriangleMesh {
+        frame_mesh(
+            self.width,
+            self.height,
+            self.frame_thickness,
+            depth,
+            true,
+            GLAZING_THICKNESS,
+        )
+    }
+
+    fn local_frame(&self, depth: f64) -> TriangleMesh {
+        let mut mesh = frame_members(self.width, self.height, self.frame_thickness, depth, true);
+        let frame = member_width(self.width, self.height, self.frame_thickness);
+        let (inner_x, y) = (self.width / 2.0 - frame, depth / 2.0);
+        let (mid_x, mid_z) = (frame / 2.0, self.height / 2.0);
+        let mullion = match self.window_type {
+            WindowType::DoubleHung => Some((
+                Point3::new(-inner_x, -y, mid_z - mid_x),
+                Point3::new(inner_x, y, mid_z + mid_x),
+            )),
+            WindowType::Casement | WindowType::Sliding => Some((
+                Point3::new(-mid_x, -y, frame),
+                Point3::new(mid_x, y, self.height - frame),
+            )),
+            _ => None,
+        };
+        if let Some((min, max)) = mullion {
+            mesh.merge(&cuboid(min, max));
+        }
+        mesh
+    }
+
+    /// Follow a host wall that has just been mirrored.
+    ///
+    /// Mirroring reverses the wall's baseline, so the offset is measured
+    /// from the other end.
+    pub fn mirror_in_host(&mut self, host: &Wall) -> GeometryResult<()> {
+        check_host(self.host_wall_id, host)?;
+        self.offset_along_wall = host.length() - self.offset_along_wall;
+        Ok(())
+    }
 }
 
 impl Element for Window {
@@ -244,62 +756,40 @@ impl Element for Window {
         self.id
     }
 
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
+    fn host_wall_id(&self) -> Option<Uuid> {
+        Some(self.host_wall_id)
+    }
+
+    fn to_mesh_in_wall(&self, host: &Wall) -> GeometryResult<TriangleMesh> {
+        self.to_mesh_in_host(host)
+    }
+
     fn element_type(&self) -> ElementType {
         ElementType::Window
     }
 
     fn bounding_box(&self) -> GeometryResult<BoundingBox3> {
-        // Window bounding box depends on host wall position
-        // This is a placeholder - actual position comes from wall
-        Ok(BoundingBox3::new(
-            Point3::new(0.0, 0.0, self.sill_height),
-            Point3::new(self.width, 0.1, self.head_height()),
-        ))
+        // Local frame; see `bounding_box_in_host` for the placed box
+        self.to_mesh()?
+            .bounding_box()
+            .ok_or(GeometryError::InsufficientVertices)
     }
 
     fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
-        // Simple window frame mesh (rectangular with glass)
-        let half_width = self.width / 2.0;
-        let frame_depth = 0.1;
-        let z0 = self.sill_height;
-        let z1 = self.head_height();
-
-        // Simple frame (4 vertices per face, front and back)
-        let vertices = vec![
-            // Front face
-            Point3::new(-half_width, 0.0, z0),
-            Point3::new(half_width, 0.0, z0),
-            Point3::new(half_width, 0.0, z1),
-            Point3::new(-half_width, 0.0, z1),
-            // Back face
-            Point3::new(-half_width, frame_depth, z0),
-            Point3::new(half_width, frame_depth, z0),
-            Point3::new(half_width, frame_depth, z1),
-            Point3::new(-half_width, frame_depth, z1),
-        ];
-
-        let indices = vec![
-            // Front
-            [0, 1, 2],
-            [0, 2, 3],
-            // Back
-            [5, 4, 7],
-            [5, 7, 6],
-            // Top
-            [3, 2, 6],
-            [3, 6, 7],
-            // Bottom
-            [0, 5, 1],
-            [0, 4, 5],
-            // Left
-            [0, 3, 7],
-            [0, 7, 4],
-            // Right
-            [1, 5, 6],
-            [1, 6, 2],
-        ];
-
-        Ok(TriangleMesh::from_vertices_indices(vertices, indices))
+        // Without the host, centre the window on the origin at sill height
+        let mut mesh = self.local_mesh(UNHOSTED_DEPTH);
+        for v in &mut mesh.vertices {
+            v.z += self.sill_height;
+        }
+        Ok(mesh)
     }
 }
 
@@ -363,4 +853,289 @@ mod tests {
 
         assert!(mesh.is_valid());
     }
+
+    /// Extent of a mesh in a wall's frame: (along, across, up from base).
+    fn extent_in_wall(wall: &Wall, mesh: &TriangleMesh) -> ([f64; 3], [f64; 3]) {
+        let direction = wall.direction().unwrap();
+        let normal = wall.normal().unwrap();
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+        for v in &mesh.vertices {
+            let d = Point2::new(v.x, v.y) - wall.baseline.start;
+            let local = [d.dot(&direction), d.dot(&normal), v.z - wall.base_offset];
+            for i in 0..3 {
+                min[i] = min[i].min(local[i]);
+                max[i] = max[i].max(local[i]);
+            }
+        }
+        (min, max)
+    }
+
+    fn assert_fills_opening(wall: &Wall, mesh: &TriangleMesh, opening: &crate::WallOpening) {
+        let (min, max) = extent_in_wall(wall, mesh);
+        let half = wall.thickness / 2.0;
+        let expected_min = [opening.start_offset(), -half, opening.base_height];
+        let expected_max = [opening.end_offset(), half, opening.top_height()];
+        for i in 0..3 {
+            assert!((min[i] - expected_min[i]).abs() < 1e-9, "{:?}", min);
+            assert!((max[i] - expected_max[i]).abs() < 1e-9, "{:?}", max);
+        }
+    }
+
+    fn sloped_wall() -> Wall {
+        let mut wall = Wall::new(Point2::new(1.0, 1.0), Point2::new(5.0, 4.0), 3.0, 0.25).unwrap();
+        wall.base_offset = 0.3;
+        wall
+    }
+
+    #[test]
+    fn door_mesh_sits_in_its_opening() {
+        use crate::elements::{OpeningType, WallOpening};
+
+        let mut wall = sloped_wall();
+        let opening = WallOpening::new(2.0, 0.0, 0.9, 2.1, OpeningType::Door);
+        wall.add_opening(opening.clone()).unwrap();
+        let door = Door::new(wall.id, 0.9, 2.1, 2.0).unwrap();
+
+        let mesh = door.to_mesh_in_host(&wall).unwrap();
+        assert!(mesh.is_valid());
+        assert_fills_opening(&wall, &mesh, &opening);
+
+        // Frame through the wall, panel only part way
+        let full = wall.thickness * opening.width * opening.height;
+        assert!(mesh.volume() < full);
+        assert!(mesh.volume() > 0.2 * full);
+
+        let bbox = door.bounding_box_in_host(&wall).unwrap();
+        assert!((bbox.min.z - 0.3).abs() < 1e-9);
+        assert!((bbox.max.z - 2.4).abs() < 1e-9);
+
+        let other = sloped_wall();
+        assert!(matches!(
+            door.to_mesh_in_host(&other),
+            Err(GeometryError::InvalidElementRef(_))
+        ));
+    }
+
+    #[test]
+    fn window_mesh_sits_in_its_opening() {
+        use crate::elements::{OpeningType, WallOpening};
+
+        let mut wall = sloped_wall();
+        let opening = WallOpening::new(3.0, 0.9, 1.2, 1.5, OpeningType::Window);
+        wall.add_opening(opening.clone()).unwrap();
+        let mut window = Window::new(wall.id, 1.2, 1.5, 0.9, 3.0).unwrap();
+        window.set_frame_thickness(0.07).unwrap();
+
+        let mesh = window.to_mesh_in_host(&wall).unwrap();
+        assert!(mesh.is_valid());
+        assert_fills_opening(&wall, &mesh, &opening);
+
+        // The glazing is inset: its faces lie inside the wall faces
+        let (direction, normal) = (wall.direction().unwrap(), wall.normal().unwrap());
+        let glass_face = mesh.vertices.iter().any(|v| {
+            let d = Point2::new(v.x, v.y) - wall.baseline.start;
+            let along = d.dot(&direction) - opening.start_offset();
+            (d.dot(&normal) - GLAZING_THICKNESS / 2.0).abs() < 1e-9 && (along - 0.07).abs() < 1e-9
+        });
+        assert!(glass_face);
+    }
+
+    #[test]
+    fn ray_through_door_opening_hits_only_the_door() {
+        use crate::elements::{OpeningType, WallOpening};
+        use crate::mesh::{pick_mesh, Ray};
+        use pensaer_math::Vector3;
+
+        let mut wall = sloped_wall();
+        wall.add_opening(WallOpening::new(2.0, 0.0, 0.9, 2.1, OpeningType::Door))
+            .unwrap();
+        let door = Door::new(wall.id, 0.9, 2.1, 2.0).unwrap();
+        let wall_mesh = wall.to_mesh_with_openings().unwrap();
+        let door_mesh = door.to_mesh_in_host(&wall).unwrap();
+
+        let (direction, normal) = (wall.direction().unwrap(), wall.normal().unwrap());
+        let centre = wall.baseline.start + direction * 2.0 - normal;
+        let origin = Point3::new(centre.x, centre.y, 0.3 + 1.0);
+        let across = Vector3::new(normal.x, normal.y, 0.0);
+
+        assert!(wall_mesh.raycast(origin, across).is_none());
+        let ray = Ray::new(origin, across).unwrap();
+        let hit = pick_mesh(&[(wall.id, &wall_mesh), (door.id, &door_mesh)], &ray).unwrap();
+        assert_eq!(hit.element_id, door.id);
+
+        // Above the head the wall is solid again
+        let above = Point3::new(centre.x, centre.y, 0.3 + 2.5);
+        assert!(wall_mesh.raycast(above, across).is_some());
+    }
+
+    #[test]
+    fn window_frame_has_type_mullion() {
+        let wall = sloped_wall();
+        let mut window = Window::new(wall.id, 1.2, 1.5, 0.9, 3.0).unwrap();
+        let fixed = window.to_frame_mesh(&wall).unwrap();
+        assert!(fixed.is_valid());
+        assert_eq!(fixed.triangle_count(), 4 * 12);
+
+        window.set_type(WindowType::DoubleHung);
+        let double_hung = window.to_frame_mesh(&wall).unwrap();
+        assert!(double_hung.triangle_count() > fixed.triangle_count());
+
+        // The meeting rail spans between the jambs at mid-height
+        let direction = wall.direction().unwrap();
+        let inner = (2.4 + window.frame_thickness, 3.6 - window.frame_thickness);
+        for v in &double_hung.vertices[4 * 8..] {
+            let along = (Point2::new(v.x, v.y) - wall.baseline.start).dot(&direction);
+            let up = v.z - wall.base_offset;
+            assert!(along > inner.0 - 1e-9 && along < inner.1 + 1e-9);
+            assert!((up - 1.65).abs() < window.frame_thickness / 2.0 + 1e-9);
+        }
+
+        // A casement's mullion stands between sill and head
+        window.set_type(WindowType::Casement);
+        let casement = window.to_frame_mesh(&wall).unwrap();
+        assert_eq!(casement.triangle_count(), double_hung.triangle_count());
+        let (_, top) = extent_in_wall(&wall, &casement);
+        assert!((top[2] - window.head_height()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn frame_must_fit_the_opening() {
+        let mut window = Window::new(Uuid::new_v4(), 0.6, 0.4, 0.9, 1.0).unwrap();
+        assert!(matches!(
+            window.set_frame_thickness(0.2),
+            Err(GeometryError::InvalidFrame(_))
+        ));
+        assert!(matches!(
+            window.set_frame_thickness(0.0),
+            Err(GeometryError::NonPositiveThickness { .. })
+        ));
+        assert_eq!(window.frame_thickness, DEFAULT_FRAME_THICKNESS);
+    }
+
+    fn assert_near(a: Point2, b: Point2) {
+        assert!(a.distance_to(&b) < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn swing_hinges_on_the_jamb_for_its_hand() {
+        let wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        let mut door = Door::new(wall.id, 0.9, 2.1, 2.0).unwrap();
+
+        let left = door.swing_geometry(&wall).unwrap();
+        assert_eq!(left.len(), 1);
+        assert_near(left[0].hinge_point, Point2::new(1.55, 0.0));
+        assert_near(left[0].leaf_end, Point2::new(1.55, 0.9));
+        let arc = &left[0].arc_points;
+        assert_eq!(arc.len(), DEFAULT_SWING_SEGMENTS + 1);
+        assert_near(arc[0], Point2::new(2.45, 0.0));
+        assert_near(arc[arc.len() - 1], left[0].leaf_end);
+        assert!(arc
+            .iter()
+            .all(|p| (p.distance_to(&left[0].hinge_point) - 0.9).abs() < 1e-9));
+
+        door.set_swing(DoorSwing::Right);
+        door.opening_side = OffsetSide::Right;
+        let right = door.swing_geometry_with_segments(&wall, 4).unwrap();
+        assert_near(right[0].hinge_point, Point2::new(2.45, 0.0));
+        assert_near(right[0].leaf_end, Point2::new(2.45, -0.9));
+        assert_eq!(right[0].arc_points.len(), 5);
+        assert_near(right[0].arc_points[0], Point2::new(1.55, 0.0));
+
+        door.set_swing(DoorSwing::Both);
+        let both = door.swing_geometry(&wall).unwrap();
+        assert_eq!(both.len(), 2);
+        assert_near(both[0].arc_points[0], Point2::new(2.0, 0.0));
+        assert_near(both[1].arc_points[0], Point2::new(2.0, 0.0));
+        assert_near(both[1].leaf_end, Point2::new(2.45, -0.45));
+
+        door.set_swing(DoorSwing::None);
+        assert!(door.swing_geometry(&wall).unwrap().is_empty());
+    }
+
+    #[test]
+    fn swing_arc_is_a_quarter_circle() {
+        let wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        let mut door = Door::new(wall.id, 0.9, 2.1, 2.0).unwrap();
+        door.set_swing(DoorSwing::Left);
+
+        let arc = door.swing_arc(&wall).unwrap();
+        assert_eq!(arc.len(), DEFAULT_SWING_SEGMENTS + 1);
+        let hinge = Point2::new(1.55, 0.0);
+        assert!(arc
+            .iter()
+            .all(|p| (p.distance_to(&hinge) - 0.9).abs() < 1e-9));
+        let (closed, open) = (arc[0] - hinge, arc[arc.len() - 1] - hinge);
+        assert!(closed.dot(&open).abs() < 1e-9);
+
+        // A double door's arcs join where the leaves meet
+        door.set_swing(DoorSwing::Both);
+        let both = door.swing_arc(&wall).unwrap();
+        assert_eq!(both.len(), 2 * DEFAULT_SWING_SEGMENTS + 1);
+        assert_near(both[DEFAULT_SWING_SEGMENTS], Point2::new(2.0, 0.0));
+        assert_near(both[0], Point2::new(1.55, 0.45));
+        assert_near(both[both.len() - 1], Point2::new(2.45, 0.45));
+
+        door.set_swing(DoorSwing::None);
+        assert!(door.swing_arc(&wall).unwrap().is_empty());
+    }
+
+    #[test]
+    fn mirrored_wall_mirrors_swing_arcs() {
+        use crate::element::TransformableElement;
+        use pensaer_math::Line2;
+
+        let mut wall = Wall::new(Point2::new(1.0, 1.0), Point2::new(6.0, 2.0), 3.0, 0.2).unwrap();
+        let mut door = Door::new(wall.id, 0.9, 2.1, 1.5).unwrap();
+        let before = door.swing_geometry(&wall).unwrap();
+
+        // Mirror across the x axis
+        let axis = Line2::from_points(Point2::new(0.0, 0.0), Point2::new(1.0, 0.0)).unwrap();
+        let reflect = |p: Point2| Point2::new(p.x, -p.y);
+        wall.mirror(&axis);
+        door.mirror_in_host(&wall).unwrap();
+        let after = door.swing_geometry(&wall).unwrap();
+
+        assert_eq!(door.swing, DoorSwing::Right);
+        assert_near(after[0].hinge_point, reflect(before[0].hinge_point));
+        assert_near(after[0].leaf_end, reflect(before[0].leaf_end));
+        for (a, b) in after[0].arc_points.iter().zip(&before[0].arc_points) {
+            assert_near(*a, reflect(*b));
+        }
+    }
+
+    #[test]
+    fn mirrored_left_door_becomes_right() {
+        use crate::element::TransformableElement;
+        use crate::elements::{OpeningType, WallOpening};
+        use pensaer_math::{Line2, Point2};
+
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        let mut door = Door::new(wall.id, 0.9, 2.1, 1.0).unwrap();
+        door.set_swing(DoorSwing::Left);
+        let mut opening = WallOpening::new(1.0, 0.0, 0.9, 2.1, OpeningType::Door);
+        opening.hosted_element_id = Some(door.id);
+        wall.add_opening(opening).unwrap();
+
+        // Mirror across y = x
+        let axis = Line2::from_points(Point2::new(0.0, 0.0), Point2::new(1.0, 1.0)).unwrap();
+        wall.mirror(&axis);
+        door.mirror_in_host(&wall).unwrap();
+
+        assert_eq!(door.swing, DoorSwing::Right);
+        assert!((door.offset_along_wall - 4.0).abs() < 1e-10);
+        assert!((door.offset_along_wall - wall.openings[0].offset_along_wall).abs() < 1e-10);
+        // The door centre lands on the mirror image of (1, 0)
+        let center = wall
+            .baseline
+            .point_at(door.offset_along_wall / wall.length());
+        assert!(center.x.abs() < 1e-10);
+        assert!((center.y - 1.0).abs() < 1e-10);
+
+        let other = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        assert!(matches!(
+            door.mirror_in_host(&other),
+            Err(GeometryError::InvalidElementRef(_))
+        ));
+    }
 }
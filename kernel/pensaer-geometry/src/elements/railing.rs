@@ -0,0 +1,616 @@
+//! Railing (balustrade) element for BIM modeling.
+//!
+//! A railing follows a 3D path: sloped segments follow the pitch of a stair
+//! flight or ramp, level segments run along landings and slab edges. The
+//! handrail and bottom rail are swept along the path, and vertical balusters
+//! (or a glass/mesh panel) fill the space between them.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use pensaer_math::{BoundingBox3, Point2, Point3, Vector2};
+
+use crate::element::{next_element_id, Element, ElementMetadata, ElementType};
+use crate::error::{GeometryError, GeometryResult};
+use crate::mesh::TriangleMesh;
+
+use super::Floor;
+
+/// Default minimum guard height (1100mm).
+pub const DEFAULT_MIN_RAILING_HEIGHT: f64 = 1.1;
+
+/// Default maximum clear gap between balusters (100mm).
+pub const DEFAULT_MAX_BALUSTER_GAP: f64 = 0.1;
+
+/// Type of infill between the handrail and bottom rail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum InfillType {
+    /// Vertical balusters.
+    #[default]
+    Balusters,
+    /// Glass panels.
+    Glass,
+    /// Wire mesh panels.
+    Mesh,
+}
+
+impl InfillType {
+    /// Thickness of a panel infill.
+    fn panel_thickness(&self) -> f64 {
+        match self {
+            InfillType::Balusters => 0.0,
+            InfillType::Glass => 0.012,
+            InfillType::Mesh => 0.005,
+        }
+    }
+}
+
+/// Rectangular cross-section of a rail or baluster.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RailProfile {
+    /// Horizontal width across the path.
+    pub width: f64,
+    /// Vertical depth.
+    pub depth: f64,
+}
+
+impl RailProfile {
+    /// Create a rectangular profile.
+    pub fn new(width: f64, depth: f64) -> Self {
+        Self { width, depth }
+    }
+
+    /// Create a square profile.
+    pub fn square(size: f64) -> Self {
+        Self::new(size, size)
+    }
+}
+
+/// What a railing is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RailingHost {
+    /// Free-standing along an explicit path.
+    #[default]
+    Path,
+    /// Along one boundary edge of a floor slab.
+    FloorEdge {
+        /// Host floor ID.
+        floor_id: Uuid,
+        /// Index of the edge's first vertex in the floor boundary.
+        edge_index: usize,
+    },
+}
+
+/// Quantities for schedules.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RailingTakeoff {
+    /// Handrail length along the (possibly sloped) path.
+    pub rail_length: f64,
+    /// Number of balusters.
+    pub baluster_count: usize,
+    /// Glass panel area.
+    pub glass_area: f64,
+}
+
+/// Limits checked by [`Railing::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RailingRules {
+    /// Minimum height of the handrail above the path.
+    pub min_height: f64,
+    /// Maximum clear gap between balusters.
+    pub max_baluster_gap: f64,
+}
+
+impl Default for RailingRules {
+    fn default() -> Self {
+        Self {
+            min_height: DEFAULT_MIN_RAILING_HEIGHT,
+            max_baluster_gap: DEFAULT_MAX_BALUSTER_GAP,
+        }
+    }
+}
+
+/// A railing that breaks a [`RailingRules`] limit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RailingFinding {
+    /// The railing is lower than the minimum height.
+    HeightBelowMinimum {
+        /// Railing height.
+        height: f64,
+        /// Required minimum.
+        minimum: f64,
+    },
+    /// Balusters are further apart than allowed.
+    BalusterGapTooWide {
+        /// Largest clear gap between balusters.
+        gap: f64,
+        /// Allowed maximum.
+        maximum: f64,
+    },
+}
+
+/// A railing element in the BIM model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Railing {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Path along the walking surface, as a polyline.
+    pub path: Vec<Point3>,
+    /// Host the path was taken from.
+    pub host: RailingHost,
+    /// Height of the top of the handrail above the path.
+    pub height: f64,
+    /// Maximum clear gap between balusters.
+    pub baluster_spacing: f64,
+    /// Baluster cross-section.
+    pub baluster_profile: RailProfile,
+    /// Handrail cross-section.
+    pub handrail_profile: RailProfile,
+    /// Bottom rail cross-section.
+    pub bottom_rail_profile: RailProfile,
+    /// Height of the bottom of the bottom rail above the path.
+    pub bottom_rail_clearance: f64,
+    /// Infill type.
+    pub infill: InfillType,
+    /// Level this element is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
+    /// Metadata.
+    pub metadata: ElementMetadata,
+}
+
+impl Railing {
+    /// Create a new railing along a path.
+    ///
+    /// Every path segment must have a horizontal extent; sloped segments
+    /// follow the path's change in elevation.
+    pub fn new(path: Vec<Point3>, height: f64, baluster_spacing: f64) -> GeometryResult<Self> {
+        if height <= 0.0 {
+            return Err(GeometryError::NonPositiveHeight { value: height });
+        }
+        if baluster_spacing <= 0.0 {
+            return Err(GeometryError::NonPositiveSpacing {
+                value: baluster_spacing,
+            });
+        }
+        if path.len() < 2 || path.windows(2).any(|w| plan_length(w[0], w[1]) < 1e-10) {
+            return Err(GeometryError::DegenerateRailingPath);
+        }
+
+        let mut params: Vec<f64> = path.iter().flat_map(|p| [p.x, p.y, p.z]).collect();
+        params.extend([height, baluster_spacing]);
+
+        Ok(Self {
+            id: next_element_id(ElementType::Railing.name(), &[], &params),
+            path,
+            host: RailingHost::Path,
+            height,
+            baluster_spacing,
+            baluster_profile: RailProfile::square(0.02),
+            handrail_profile: RailProfile::new(0.05, 0.04),
+            bottom_rail_profile: RailProfile::new(0.04, 0.03),
+            bottom_rail_clearance: 0.05,
+            infill: InfillType::default(),
+            level_id: None,
+            metadata: ElementMetadata::new(),
+        })
+    }
+
+    /// Create a railing along one boundary edge of a floor, on its top face.
+    ///
+    /// `edge_index` is the index of the edge's first vertex.
+    pub fn on_floor_edge(
+        floor: &Floor,
+        edge_index: usize,
+        height: f64,
+        baluster_spacing: f64,
+    ) -> GeometryResult<Self> {
+        let vertices = &floor.boundary.vertices;
+        if edge_index >= vertices.len() {
+            return Err(GeometryError::InvalidElementRef(format!(
+                "floor edge {}",
+                edge_index
+            )));
+        }
+        let a = vertices[edge_index];
+        let b = vertices[(edge_index + 1) % vertices.len()];
+        let z = floor.top_elevation();
+
+        let mut railing = Self::new(
+            vec![Point3::new(a.x, a.y, z), Point3::new(b.x, b.y, z)],
+            height,
+            baluster_spacing,
+        )?;
+        railing.host = RailingHost::FloorEdge {
+            floor_id: floor.id,
+            edge_index,
+        };
+        railing.level_id = floor.level_id;
+        Ok(railing)
+    }
+
+    /// Handrail length along the path.
+    pub fn rail_length(&self) -> f64 {
+        self.path.windows(2).map(|w| w[0].distance_to(&w[1])).sum()
+    }
+
+    /// Height of the infill zone between the bottom rail and the handrail.
+    pub fn infill_height(&self) -> f64 {
+        let bottom = self.bottom_rail_clearance + self.bottom_rail_profile.depth;
+        let top = self.height - self.handrail_profile.depth;
+        (top - bottom).max(0.0)
+    }
+
+    /// Plan positions of balusters along each segment, as parameters in [0, 1].
+    ///
+    /// Balusters stand at both ends of every segment, with evenly spaced
+    /// balusters in between so no clear gap exceeds the baluster spacing.
+    fn baluster_params(&self) -> Vec<Vec<f64>> {
+        let w = self.baluster_profile.width;
+        self.path
+            .windows(2)
+            .map(|seg| {
+                let run = plan_length(seg[0], seg[1]);
+                let intervals = ((run - w) / (self.baluster_spacing + w)).ceil().max(1.0) as usize;
+                (0..=intervals)
+                    .map(|i| i as f64 / intervals as f64)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Number of balusters (zero for panel infill).
+    pub fn baluster_count(&self) -> usize {
+        if self.infill != InfillType::Balusters {
+            return 0;
+        }
+        // Segments share the baluster at their common vertex
+        self.baluster_params()
+            .iter()
+            .map(|params| params.len() - 1)
+            .sum::<usize>()
+            + 1
+    }
+
+    /// Largest clear gap between adjacent balusters, measured horizontally.
+    pub fn max_baluster_gap(&self) -> f64 {
+        if self.infill != InfillType::Balusters {
+            return 0.0;
+        }
+        let w = self.baluster_profile.width;
+        self.path
+            .windows(2)
+            .zip(self.baluster_params())
+            .map(|(seg, params)| {
+                let intervals = (params.len() - 1) as f64;
+                (plan_length(seg[0], seg[1]) / intervals - w).max(0.0)
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Glass panel area (zero for other infill types).
+    pub fn glass_area(&self) -> f64 {
+        if self.infill != InfillType::Glass {
+            return 0.0;
+        }
+        // Panels have vertical edges, so each is a parallelogram whose area
+        // is its plan length times its vertical height
+        let run: f64 = self.path.windows(2).map(|w| plan_length(w[0], w[1])).sum();
+        run * self.infill_height()
+    }
+
+    /// Quantities for schedules.
+    pub fn takeoff(&self) -> RailingTakeoff {
+        RailingTakeoff {
+            rail_length: self.rail_length(),
+            baluster_count: self.baluster_count(),
+            glass_area: self.glass_area(),
+        }
+    }
+
+    /// Check the railing against height and baluster gap limits.
+    pub fn validate(&self, rules: &RailingRules) -> Vec<RailingFinding> {
+        let mut findings = Vec::new();
+        if self.height < rules.min_height {
+            findings.push(RailingFinding::HeightBelowMinimum {
+                height: self.height,
+                minimum: rules.min_height,
+            });
+        }
+        let gap = self.max_baluster_gap();
+        if gap > rules.max_baluster_gap + 1e-9 {
+            findings.push(RailingFinding::BalusterGapTooWide {
+                gap,
+                maximum: rules.max_baluster_gap,
+            });
+        }
+        findings
+    }
+}
+
+/// Horizontal distance between two path points.
+fn plan_length(a: Point3, b: Point3) -> f64 {
+    Point2::new(a.x, a.y).distance_to(&Point2::new(b.x, b.y))
+}
+
+/// Box with the given bottom face (counter-clockwise from above) raised by `depth`.
+pub(super) fn prism(bottom: [Point3; 4], depth: f64) -> TriangleMesh {
+    let mut vertices = bottom.to_vec();
+    vertices.extend(bottom.iter().map(|p| Point3::new(p.x, p.y, p.z + depth)));
+
+    let indices = vec![
+        // Bottom
+        [0, 2, 1],
+        [0, 3, 2],
+        // Top
+        [4, 5, 6],
+        [4, 6, 7],
+        // Sides
+        [0, 1, 5],
+        [0, 5, 4],
+        [1, 2, 6],
+        [1, 6, 5],
+        [2, 3, 7],
+        [2, 7, 6],
+        [3, 0, 4],
+        [3, 4, 7],
+    ];
+
+    TriangleMesh::from_vertices_indices(vertices, indices)
+}
+
+/// Plan direction and left normal of a path segment.
+fn segment_frame(a: Point3, b: Point3) -> GeometryResult<(Vector2, Vector2)> {
+    let dir = Vector2::new(b.x - a.x, b.y - a.y)
+        .normalize()
+        .map_err(|_| GeometryError::DegenerateRailingPath)?;
+    Ok((dir, dir.perp()))
+}
+
+/// Offset a path point horizontally and vertically.
+fn offset(p: Point3, plan: Vector2, dz: f64) -> Point3 {
+    Point3::new(p.x + plan.x, p.y + plan.y, p.z + dz)
+}
+
+/// Rail swept along a segment, `z` above the path and `width` across it.
+fn rail(a: Point3, b: Point3, profile: RailProfile, z: f64) -> GeometryResult<TriangleMesh> {
+    let (_, normal) = segment_frame(a, b)?;
+    let half = normal * (profile.width / 2.0);
+    Ok(prism(
+        [
+            offset(a, -half, z),
+            offset(b, -half, z),
+            offset(b, half, z),
+            offset(a, half, z),
+        ],
+        profile.depth,
+    ))
+}
+
+impl Element for Railing {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
+    fn element_type(&self) -> ElementType {
+        ElementType::Railing
+    }
+
+    fn bounding_box(&self) -> GeometryResult<BoundingBox3> {
+        let reach = self
+            .handrail_profile
+            .width
+            .max(self.bottom_rail_profile.width)
+            / 2.0;
+        let points: Vec<Point3> = self
+            .path
+            .iter()
+            .flat_map(|p| {
+                [
+                    Point3::new(p.x - reach, p.y - reach, p.z),
+                    Point3::new(p.x + reach, p.y + reach, p.z + self.height),
+                ]
+            })
+            .collect();
+
+        BoundingBox3::from_points(&points).ok_or(GeometryError::DegenerateRailingPath)
+    }
+
+    fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
+        let bottom_rail_top = self.bottom_rail_clearance + self.bottom_rail_profile.depth;
+        let handrail_bottom = self.height - self.handrail_profile.depth;
+        let infill_height = self.infill_height();
+
+        let mut mesh = TriangleMesh::new();
+        let segments = self.path.windows(2).zip(self.baluster_params());
+        for (index, (seg, params)) in segments.enumerate() {
+            let (a, b) = (seg[0], seg[1]);
+            mesh.merge(&rail(a, b, self.handrail_profile, handrail_bottom)?);
+            mesh.merge(&rail(
+                a,
+                b,
+                self.bottom_rail_profile,
+                self.bottom_rail_clearance,
+            )?);
+
+            match self.infill {
+                InfillType::Balusters => {
+                    let (dir, normal) = segment_frame(a, b)?;
+                    let along = dir * (self.baluster_profile.width / 2.0);
+                    let across = normal * (self.baluster_profile.width / 2.0);
+                    // The shared baluster at a vertex is emitted by the
+                    // segment that ends there
+                    for &t in params.iter().skip(usize::from(index > 0)) {
+                        let c = a.lerp(&b, t);
+                        mesh.merge(&prism(
+                            [
+                                offset(c, -along - across, bottom_rail_top),
+                                offset(c, along - across, bottom_rail_top),
+                                offset(c, along + across, bottom_rail_top),
+                                offset(c, -along + across, bottom_rail_top),
+                            ],
+                            infill_height,
+                        ));
+                    }
+                }
+                InfillType::Glass | InfillType::Mesh => {
+                    let (_, normal) = segment_frame(a, b)?;
+                    let half = normal * (self.infill.panel_thickness() / 2.0);
+                    mesh.merge(&prism(
+                        [
+                            offset(a, -half, bottom_rail_top),
+                            offset(b, -half, bottom_rail_top),
+                            offset(b, half, bottom_rail_top),
+                            offset(a, half, bottom_rail_top),
+                        ],
+                        infill_height,
+                    ));
+                }
+            }
+        }
+
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A straight flight rising 3m over a 4m run (5m along the pitch).
+    fn flight() -> Railing {
+        Railing::new(
+            vec![Point3::new(0.0, 0.0, 0.0), Point3::new(4.0, 0.0, 3.0)],
+            1.1,
+            0.1,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn flight_handrail_follows_pitch() {
+        let railing = flight();
+        let takeoff = railing.takeoff();
+
+        assert!((takeoff.rail_length - 5.0).abs() < 1e-10);
+        // 4m run, 20mm balusters, 100mm max gap: ceil(3.98 / 0.12) = 34 gaps
+        assert_eq!(takeoff.baluster_count, 35);
+        assert_eq!(takeoff.glass_area, 0.0);
+        assert!(railing.max_baluster_gap() <= 0.1);
+        assert!(railing.validate(&RailingRules::default()).is_empty());
+    }
+
+    #[test]
+    fn flight_mesh_follows_pitch() {
+        let railing = flight();
+        let mesh = railing.to_mesh().unwrap();
+
+        // Two rails plus one prism per baluster
+        assert_eq!(mesh.triangle_count(), 12 * (2 + 35));
+        assert!(mesh.is_valid());
+
+        let bbox = mesh.bounding_box().unwrap();
+        assert!((bbox.max.z - 4.1).abs() < 1e-10);
+        assert!((bbox.min.z - 0.05).abs() < 1e-10);
+    }
+
+    #[test]
+    fn flight_and_landing_share_baluster() {
+        let railing = Railing::new(
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(4.0, 0.0, 3.0),
+                Point3::new(4.0, 1.2, 3.0),
+            ],
+            1.1,
+            0.1,
+        )
+        .unwrap();
+
+        // Landing: ceil(1.18 / 0.12) = 10 gaps
+        assert_eq!(railing.baluster_count(), 35 + 10);
+        assert!((railing.rail_length() - 6.2).abs() < 1e-10);
+        assert_eq!(railing.to_mesh().unwrap().triangle_count(), 12 * (4 + 45));
+    }
+
+    #[test]
+    fn wide_spacing_produces_finding() {
+        let mut railing = flight();
+        railing.baluster_spacing = 0.12;
+
+        let findings = railing.validate(&RailingRules::default());
+        assert_eq!(findings.len(), 1);
+        assert!(matches!(
+            findings[0],
+            RailingFinding::BalusterGapTooWide { gap, maximum } if gap > 0.1 && maximum == 0.1
+        ));
+    }
+
+    #[test]
+    fn low_railing_produces_finding() {
+        let mut railing = flight();
+        railing.height = 0.9;
+
+        let findings = railing.validate(&RailingRules::default());
+        assert_eq!(
+            findings,
+            vec![RailingFinding::HeightBelowMinimum {
+                height: 0.9,
+                minimum: 1.1
+            }]
+        );
+    }
+
+    #[test]
+    fn glass_infill_reports_area() {
+        let mut railing = flight();
+        railing.infill = InfillType::Glass;
+
+        let takeoff = railing.takeoff();
+        assert_eq!(takeoff.baluster_count, 0);
+        // 4m run x (1.1 - 0.04 - 0.05 - 0.03) infill height
+        assert!((takeoff.glass_area - 4.0 * 0.98).abs() < 1e-10);
+        assert!(railing.validate(&RailingRules::default()).is_empty());
+    }
+
+    #[test]
+    fn railing_on_floor_edge() {
+        let mut floor =
+            Floor::rectangle(Point2::new(0.0, 0.0), Point2::new(6.0, 4.0), 0.2).unwrap();
+        floor.set_elevation(3.0);
+
+        let railing = Railing::on_floor_edge(&floor, 1, 1.1, 0.1).unwrap();
+        assert_eq!(
+            railing.host,
+            RailingHost::FloorEdge {
+                floor_id: floor.id,
+                edge_index: 1
+            }
+        );
+        assert!((railing.rail_length() - 4.0).abs() < 1e-10);
+        assert!(railing.path.iter().all(|p| (p.z - 3.2).abs() < 1e-10));
+
+        assert!(matches!(
+            Railing::on_floor_edge(&floor, 4, 1.1, 0.1),
+            Err(GeometryError::InvalidElementRef(_))
+        ));
+    }
+
+    #[test]
+    fn vertical_path_segment_fails() {
+        let result = Railing::new(
+            vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 3.0)],
+            1.1,
+            0.1,
+        );
+        assert!(matches!(result, Err(GeometryError::DegenerateRailingPath)));
+    }
+}
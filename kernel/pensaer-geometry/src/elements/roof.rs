@@ -2,15 +2,49 @@
 //!
 //! Supports various roof types including flat, gable, hip, shed, and mansard.
 //! Roofs can be attached to walls and support multiple slope configurations.
+//! Rectangular openings (for skylights, chimneys, and so on) are cut through
+//! the mesh of every roof type; see [`Roof::add_opening`].
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use pensaer_math::{BoundingBox3, Point2, Point3, Polygon2};
+use pensaer_math::{
+    BoundingBox2, BoundingBox3, LineSegment2, OffsetJoin, Point2, Point3, Polygon2, Vector3,
+};
 
-use crate::element::{Element, ElementMetadata, ElementType};
+use crate::constants::SNAP_MERGE_TOL;
+use crate::element::{
+    next_element_id, Element, ElementMetadata, ElementType, PlanTransform, TransformableElement,
+};
 use crate::error::{GeometryError, GeometryResult};
-use crate::mesh::TriangleMesh;
+use crate::mesh::{
+    extrude_polygon, extrude_polygon_with_holes, triangulate_polygon_with_holes, TriangleMesh,
+};
+use crate::topology::NodeId;
+
+use super::wall::{wall_graph, Wall};
+
+/// Distance within which pitched roof mesh vertices are shared.
+const WELD_TOLERANCE: f64 = 1e-9;
+
+/// Topology coordinates are in millimeters; elements use meters.
+const M_TO_MM: f64 = 1000.0;
+
+/// Default pitch of the steep lower slopes of a mansard roof (degrees).
+pub const DEFAULT_LOWER_SLOPE_DEGREES: f64 = 70.0;
+
+/// Default plan distance from the eaves to a mansard's break line (m).
+///
+/// At the default lower pitch this rises about 3.3m, roughly a storey.
+pub const DEFAULT_BREAK_INSET: f64 = 1.2;
+
+fn default_lower_slope_degrees() -> f64 {
+    DEFAULT_LOWER_SLOPE_DEGREES
+}
+
+fn default_break_inset() -> f64 {
+    DEFAULT_BREAK_INSET
+}
 
 /// Type of roof construction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -51,6 +85,72 @@ pub enum RidgeDirection {
     AlongY,
 }
 
+/// Type of roof opening.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RoofOpeningType {
+    /// Opening for a skylight.
+    Skylight,
+    /// Opening for a chimney or flue.
+    Chimney,
+    /// Generic opening.
+    #[default]
+    Generic,
+}
+
+/// A rectangular, axis-aligned opening through a roof.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoofOpening {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Plan centre of the opening.
+    pub center: Point2,
+    /// Extent along X.
+    pub width: f64,
+    /// Extent along Y.
+    pub depth: f64,
+    /// Type of opening.
+    pub opening_type: RoofOpeningType,
+    /// ID of hosted element (skylight), if any.
+    pub hosted_element_id: Option<Uuid>,
+}
+
+impl RoofOpening {
+    /// Create a new roof opening.
+    pub fn new(center: Point2, width: f64, depth: f64, opening_type: RoofOpeningType) -> Self {
+        Self {
+            id: next_element_id("RoofOpening", &[], &[center.x, center.y, width, depth]),
+            center,
+            width,
+            depth,
+            opening_type,
+            hosted_element_id: None,
+        }
+    }
+
+    /// Plan area of the opening.
+    pub fn area(&self) -> f64 {
+        self.width * self.depth
+    }
+
+    /// Plan footprint corners, counter-clockwise from the south-west.
+    pub fn footprint(&self) -> [Point2; 4] {
+        let (hw, hd) = (self.width / 2.0, self.depth / 2.0);
+        let c = self.center;
+        [
+            Point2::new(c.x - hw, c.y - hd),
+            Point2::new(c.x + hw, c.y - hd),
+            Point2::new(c.x + hw, c.y + hd),
+            Point2::new(c.x - hw, c.y + hd),
+        ]
+    }
+
+    /// Whether two openings' footprints overlap.
+    fn overlaps(&self, other: &RoofOpening) -> bool {
+        (self.center.x - other.center.x).abs() < (self.width + other.width) / 2.0
+            && (self.center.y - other.center.y).abs() < (self.depth + other.depth) / 2.0
+    }
+}
+
 /// A roof element in the BIM model.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Roof {
@@ -65,13 +165,26 @@ pub struct Roof {
     /// Roof type.
     pub roof_type: RoofType,
     /// Slope angle in degrees (0 for flat, typically 15-45 for pitched).
+    /// On a mansard roof this is the upper slope.
     pub slope_degrees: f64,
+    /// Pitch of a mansard roof's lower slopes in degrees.
+    #[serde(default = "default_lower_slope_degrees")]
+    pub lower_slope_degrees: f64,
+    /// Plan distance from the eaves to a mansard roof's break line.
+    #[serde(default = "default_break_inset")]
+    pub break_inset: f64,
     /// Eave overhang distance beyond walls.
     pub eave_overhang: f64,
     /// Ridge direction for gable/hip roofs.
     pub ridge_direction: RidgeDirection,
     /// IDs of walls this roof is attached to.
     pub attached_wall_ids: Vec<Uuid>,
+    /// Openings cut through the roof.
+    #[serde(default)]
+    pub openings: Vec<RoofOpening>,
+    /// Level this element is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
     /// Metadata.
     pub metadata: ElementMetadata,
 }
@@ -79,31 +192,22 @@ pub struct Roof {
 impl Roof {
     /// Create a new flat roof from a boundary polygon.
     pub fn new(boundary: Polygon2, thickness: f64) -> GeometryResult<Self> {
-        if thickness <= 0.0 {
-            return Err(GeometryError::NonPositiveThickness);
-        }
-        boundary
-            .validate()
-            .map_err(|_| GeometryError::InsufficientVertices)?;
-
-        Ok(Self {
-            id: Uuid::new_v4(),
-            boundary,
-            thickness,
-            base_elevation: 0.0,
-            roof_type: RoofType::Flat,
-            slope_degrees: 0.0,
-            eave_overhang: 0.0,
-            ridge_direction: RidgeDirection::default(),
-            attached_wall_ids: Vec::new(),
-            metadata: ElementMetadata::new(),
-        })
+        let mut roof = Self::with_id(Uuid::nil(), boundary, thickness)?;
+        let mut params: Vec<f64> = roof
+            .boundary
+            .vertices
+            .iter()
+            .flat_map(|v| [v.x, v.y])
+            .collect();
+        params.push(thickness);
+        roof.id = next_element_id(ElementType::Roof.name(), &[], &params);
+        Ok(roof)
     }
 
     /// Create a rectangular flat roof.
     pub fn rectangle(min: Point2, max: Point2, thickness: f64) -> GeometryResult<Self> {
         if min.x >= max.x || min.y >= max.y {
-            return Err(GeometryError::InvalidFloorBounds);
+            return Err(GeometryError::InvalidFloorBounds { min, max });
         }
         let boundary = Polygon2::rectangle(min, max);
         Self::new(boundary, thickness)
@@ -152,13 +256,102 @@ impl Roof {
         Ok(roof)
     }
 
-    /// Create a roof with specific ID.
-    pub fn with_id(id: Uuid, boundary: Polygon2, thickness: f64) -> GeometryResult<Self> {
+    /// Create a mansard roof.
+    ///
+    /// Each side rises at [`DEFAULT_LOWER_SLOPE_DEGREES`] to a break line
+    /// [`DEFAULT_BREAK_INSET`] in from the eaves, then at `slope_degrees` to
+    /// a ridge (or a flat top when `slope_degrees` is 0).
+    pub fn mansard(
+        min: Point2,
+        max: Point2,
+        thickness: f64,
+        slope_degrees: f64,
+    ) -> GeometryResult<Self> {
+        let mut roof = Self::rectangle(min, max, thickness)?;
+        roof.roof_type = RoofType::Mansard;
+        roof.slope_degrees = slope_degrees.clamp(0.0, 89.0);
+        Ok(roof)
+    }
+
+    /// Fit a roof to the outer loop of a set of walls.
+    ///
+    /// The boundary is the outline of the wall baselines: they are joined
+    /// in a topology graph, crossings are split, and the largest exterior
+    /// loop is taken, dropping dangling walls and interior partitions. The
+    /// roof sits on the top of the tallest wall and is attached to the
+    /// walls along the boundary. Gable and shed ridges run along the
+    /// longest boundary edge.
+    ///
+    /// Hip and mansard roofs over an outline that is not an axis-aligned
+    /// rectangle are meshed over its bounding rectangle; see
+    /// [`uses_bounding_box_mesh`](Self::uses_bounding_box_mesh).
+    pub fn from_walls(
+        walls: &[&Wall],
+        thickness: f64,
+        roof_type: RoofType,
+        slope_degrees: f64,
+    ) -> GeometryResult<Self> {
+        let boundary = wall_loop_outline(walls)?;
         let mut roof = Self::new(boundary, thickness)?;
-        roof.id = id;
+        let slope = match roof_type {
+            RoofType::Flat => 0.0,
+            _ => slope_degrees,
+        };
+        roof.set_type(roof_type, slope);
+        roof.base_elevation = walls
+            .iter()
+            .map(|w| w.base_offset + w.max_height())
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        let longest = roof
+            .boundary
+            .edges()
+            .max_by(|a, b| a.length().total_cmp(&b.length()))
+            .ok_or(GeometryError::InsufficientVertices)?;
+        let along = longest.end - longest.start;
+        roof.ridge_direction = if along.x.abs() >= along.y.abs() {
+            RidgeDirection::AlongX
+        } else {
+            RidgeDirection::AlongY
+        };
+
+        let tolerance = SNAP_MERGE_TOL / M_TO_MM;
+        for wall in walls {
+            let midpoint = wall.baseline.point_at(0.5);
+            if roof.boundary.point_on_boundary(&midpoint, tolerance) {
+                roof.attach_to_wall(wall.id);
+            }
+        }
         Ok(roof)
     }
 
+    /// Create a roof with specific ID.
+    pub fn with_id(id: Uuid, boundary: Polygon2, thickness: f64) -> GeometryResult<Self> {
+        if thickness <= 0.0 {
+            return Err(GeometryError::NonPositiveThickness { value: thickness });
+        }
+        boundary
+            .validate()
+            .map_err(|_| GeometryError::InsufficientVertices)?;
+
+        Ok(Self {
+            id,
+            boundary,
+            thickness,
+            base_elevation: 0.0,
+            roof_type: RoofType::Flat,
+            slope_degrees: 0.0,
+            lower_slope_degrees: DEFAULT_LOWER_SLOPE_DEGREES,
+            break_inset: DEFAULT_BREAK_INSET,
+            eave_overhang: 0.0,
+            ridge_direction: RidgeDirection::default(),
+            attached_wall_ids: Vec::new(),
+            openings: Vec::new(),
+            level_id: None,
+            metadata: ElementMetadata::new(),
+        })
+    }
+
     /// Set base elevation.
     pub fn set_elevation(&mut self, elevation: f64) {
         self.base_elevation = elevation;
@@ -170,6 +363,22 @@ impl Roof {
         self.slope_degrees = slope_degrees.clamp(0.0, 89.0);
     }
 
+    /// Set the pitch of a mansard roof's lower slopes.
+    pub fn set_lower_slope(&mut self, degrees: f64) {
+        self.lower_slope_degrees = degrees.clamp(0.0, 89.0);
+    }
+
+    /// Set the plan distance from the eaves to a mansard roof's break line.
+    pub fn set_break_inset(&mut self, inset: f64) -> GeometryResult<()> {
+        if inset <= 0.0 {
+            return Err(GeometryError::InvalidRoofProfile(
+                "break inset must be positive".to_string(),
+            ));
+        }
+        self.break_inset = inset;
+        Ok(())
+    }
+
     /// Set eave overhang distance.
     pub fn set_eave_overhang(&mut self, overhang: f64) {
         self.eave_overhang = overhang.max(0.0);
@@ -210,7 +419,16 @@ impl Roof {
     }
 
     /// Ridge height above base elevation.
+    ///
+    /// A mansard roof's faces keep their nominal pitches, so its ridge is
+    /// the rise of both slopes from the eaves.
     pub fn ridge_height(&self) -> f64 {
+        if self.roof_type == RoofType::Mansard {
+            return match self.eave_bounds() {
+                Ok(bbox) => self.mansard_rise(half_span(&bbox)),
+                Err(_) => self.thickness,
+            };
+        }
         if self.slope_degrees <= 0.0 {
             return self.thickness;
         }
@@ -240,9 +458,16 @@ impl Roof {
         self.boundary.area()
     }
 
-    /// Approximate surface area (accounting for slope).
+    /// Approximate surface area (accounting for slope and openings).
     pub fn surface_area(&self) -> f64 {
-        let footprint = self.footprint_area();
+        if self.roof_type == RoofType::Mansard {
+            if let Ok(bbox) = self.eave_bounds() {
+                return self.mansard_surface_area(&bbox);
+            }
+        }
+
+        let openings: f64 = self.openings.iter().map(RoofOpening::area).sum();
+        let footprint = self.footprint_area() - openings;
         if self.slope_degrees <= 0.0 {
             return footprint;
         }
@@ -251,304 +476,541 @@ impl Roof {
         footprint / slope_rad.cos()
     }
 
+    /// Whether the pitched mesh spans the boundary's bounding rectangle
+    /// rather than the boundary itself.
+    ///
+    /// Hip and mansard roofs are meshed over the rectangle around the
+    /// eaves, so this is true for them on any boundary that is not an
+    /// axis-aligned rectangle. Flat roofs follow the boundary, and gable
+    /// and shed roofs clip their slopes to it.
+    pub fn uses_bounding_box_mesh(&self) -> bool {
+        if !matches!(self.roof_type, RoofType::Hip | RoofType::Mansard) {
+            return false;
+        }
+        self.boundary
+            .bounding_box()
+            .is_some_and(|bbox| self.boundary.area() < bbox.area() * (1.0 - 1e-9))
+    }
+
     /// Perimeter of the roof boundary.
     pub fn perimeter(&self) -> f64 {
         self.boundary.perimeter()
     }
 
-    /// Generate mesh for a flat roof.
-    fn to_mesh_flat(&self) -> GeometryResult<TriangleMesh> {
-        let bbox = self
-            .boundary
-            .bounding_box()
-            .ok_or(GeometryError::InsufficientVertices)?;
+    /// Add an opening through the roof.
+    ///
+    /// The footprint must lie inside the boundary (not the eave overhang)
+    /// and clear the other openings. On a pitched roof it must also sit on
+    /// a single sloped face: openings across a ridge or hip are rejected,
+    /// as are openings across a mansard roof's break line.
+    pub fn add_opening(&mut self, opening: RoofOpening) -> GeometryResult<()> {
+        if opening.width <= 0.0 || opening.depth <= 0.0 {
+            return Err(GeometryError::InvalidRoofOpening(
+                "width and depth must be positive".to_string(),
+            ));
+        }
 
-        let z0 = self.base_elevation;
-        let z1 = self.base_elevation + self.thickness;
+        let corners = opening.footprint();
+        let rect = BoundingBox2::new(corners[0], corners[2]);
+        let boundary_inside_rect =
+            self.boundary.vertices.iter().any(|v| {
+                v.x > rect.min.x && v.x < rect.max.x && v.y > rect.min.y && v.y < rect.max.y
+            });
+        if boundary_inside_rect || !corners.iter().all(|c| self.boundary.contains_point(c)) {
+            return Err(GeometryError::InvalidRoofOpening(
+                "footprint must lie inside the roof boundary".to_string(),
+            ));
+        }
 
-        // Apply eave overhang
-        let overhang = self.eave_overhang;
-        let corners = [
-            Point2::new(bbox.min.x - overhang, bbox.min.y - overhang),
-            Point2::new(bbox.max.x + overhang, bbox.min.y - overhang),
-            Point2::new(bbox.max.x + overhang, bbox.max.y + overhang),
-            Point2::new(bbox.min.x - overhang, bbox.max.y + overhang),
-        ];
+        if self.openings.iter().any(|o| o.overlaps(&opening)) {
+            return Err(GeometryError::OverlappingOpenings);
+        }
 
-        let vertices = vec![
-            // Bottom face
-            Point3::new(corners[0].x, corners[0].y, z0),
-            Point3::new(corners[1].x, corners[1].y, z0),
-            Point3::new(corners[2].x, corners[2].y, z0),
-            Point3::new(corners[3].x, corners[3].y, z0),
-            // Top face
-            Point3::new(corners[0].x, corners[0].y, z1),
-            Point3::new(corners[1].x, corners[1].y, z1),
-            Point3::new(corners[2].x, corners[2].y, z1),
-            Point3::new(corners[3].x, corners[3].y, z1),
-        ];
+        let bbox = self.eave_bounds()?;
+        if self.face_of(&bbox, &opening).is_none() {
+            return Err(GeometryError::OpeningSpansRidge);
+        }
 
-        let indices = vec![
-            // Bottom (facing down)
-            [0, 2, 1],
-            [0, 3, 2],
-            // Top (facing up)
-            [4, 5, 6],
-            [4, 6, 7],
-            // Front
-            [0, 1, 5],
-            [0, 5, 4],
-            // Back
-            [2, 3, 7],
-            [2, 7, 6],
-            // Left
-            [0, 4, 7],
-            [0, 7, 3],
-            // Right
-            [1, 2, 6],
-            [1, 6, 5],
-        ];
+        self.openings.push(opening);
+        Ok(())
+    }
 
-        Ok(TriangleMesh::from_vertices_indices(vertices, indices))
+    /// Remove an opening by ID.
+    pub fn remove_opening(&mut self, opening_id: Uuid) -> bool {
+        if let Some(pos) = self.openings.iter().position(|o| o.id == opening_id) {
+            self.openings.remove(pos);
+            true
+        } else {
+            false
+        }
     }
 
-    /// Generate mesh for a gable roof.
-    fn to_mesh_gable(&self) -> GeometryResult<TriangleMesh> {
-        let bbox = self
-            .boundary
-            .bounding_box()
-            .ok_or(GeometryError::InsufficientVertices)?;
+    /// Elevation of the roof's top surface above a plan point.
+    pub fn surface_elevation(&self, point: Point2) -> GeometryResult<f64> {
+        let bbox = self.eave_bounds()?;
+        Ok(self.surface_z(&bbox, point))
+    }
 
-        let overhang = self.eave_overhang;
-        let z_base = self.base_elevation;
-        let ridge_z = self.top_elevation();
+    /// Elevation of the roof's underside below a plan point.
+    ///
+    /// This is the [`surface_elevation`](Self::surface_elevation) less the
+    /// [`slab_depth`](Self::slab_depth), except on a mansard roof, where
+    /// each slope is lowered by its own depth.
+    pub fn soffit_elevation(&self, point: Point2) -> GeometryResult<f64> {
+        let bbox = self.eave_bounds()?;
+        Ok(self.soffit_z(&bbox, point))
+    }
 
-        let (x_min, x_max, y_min, y_max) = (
-            bbox.min.x - overhang,
-            bbox.max.x + overhang,
-            bbox.min.y - overhang,
-            bbox.max.y + overhang,
-        );
+    /// Vertical depth of the roof slab below its top surface.
+    ///
+    /// Thickness is measured square to the slope, so a pitched slab is
+    /// `thickness / cos(pitch)` deep, taking the pitch of the meshed faces
+    /// (they rise from the eaves to [`top_elevation`](Self::top_elevation)).
+    /// A mansard roof gives the depth of its lower slopes.
+    pub fn slab_depth(&self) -> GeometryResult<f64> {
+        let bbox = self.eave_bounds()?;
+        Ok(self.slab_depth_in(&bbox))
+    }
 
-        let vertices = match self.ridge_direction {
-            RidgeDirection::AlongX => {
-                // Ridge runs along X (east-west), slopes face north and south
-                let y_mid = (y_min + y_max) / 2.0;
-                vec![
-                    // Eave corners (bottom of slope) - 4 corners
-                    Point3::new(x_min, y_min, z_base), // 0: front-left
-                    Point3::new(x_max, y_min, z_base), // 1: front-right
-                    Point3::new(x_max, y_max, z_base), // 2: back-right
-                    Point3::new(x_min, y_max, z_base), // 3: back-left
-                    // Ridge points (top) - 2 points
-                    Point3::new(x_min, y_mid, ridge_z), // 4: ridge-left
-                    Point3::new(x_max, y_mid, ridge_z), // 5: ridge-right
-                ]
-            }
-            RidgeDirection::AlongY => {
-                // Ridge runs along Y (north-south), slopes face east and west
-                let x_mid = (x_min + x_max) / 2.0;
-                vec![
-                    // Eave corners (bottom of slope) - 4 corners
-                    Point3::new(x_min, y_min, z_base), // 0: front-left
-                    Point3::new(x_max, y_min, z_base), // 1: front-right
-                    Point3::new(x_max, y_max, z_base), // 2: back-right
-                    Point3::new(x_min, y_max, z_base), // 3: back-left
-                    // Ridge points (top) - 2 points
-                    Point3::new(x_mid, y_min, ridge_z), // 4: ridge-front
-                    Point3::new(x_mid, y_max, ridge_z), // 5: ridge-back
-                ]
-            }
-        };
+    fn slab_depth_in(&self, bbox: &BoundingBox2) -> f64 {
+        let gradient = self.gradient(bbox);
+        self.thickness * (1.0 + gradient * gradient).sqrt()
+    }
 
-        let indices = match self.ridge_direction {
-            RidgeDirection::AlongX => vec![
-                // Front slope (south face)
-                [0, 1, 5],
-                [0, 5, 4],
-                // Back slope (north face)
-                [2, 3, 4],
-                [2, 4, 5],
-                // Left gable end
-                [3, 0, 4],
-                // Right gable end
-                [1, 2, 5],
-            ],
-            RidgeDirection::AlongY => vec![
-                // Left slope (west face)
-                [0, 3, 5],
-                [0, 5, 4],
-                // Right slope (east face)
-                [1, 4, 5],
-                [1, 5, 2],
-                // Front gable end
-                [0, 4, 1],
-                // Back gable end
-                [3, 2, 5],
-            ],
+    /// Rise over run of the meshed sloped faces at the eaves.
+    fn gradient(&self, bbox: &BoundingBox2) -> f64 {
+        let rise = self.top_elevation() - self.base_elevation;
+        let (width, depth) = (bbox.max.x - bbox.min.x, bbox.max.y - bbox.min.y);
+        match (self.roof_type, self.ridge_direction) {
+            (RoofType::Flat, _) => 0.0,
+            (RoofType::Mansard, _) => self.lower_slope_degrees.to_radians().tan(),
+            (RoofType::Shed, RidgeDirection::AlongX) => rise / depth,
+            (RoofType::Shed, RidgeDirection::AlongY) => rise / width,
+            (RoofType::Gable, RidgeDirection::AlongX) => rise / (depth / 2.0),
+            (RoofType::Gable, RidgeDirection::AlongY) => rise / (width / 2.0),
+            (RoofType::Hip, _) => rise / (width.min(depth) / 2.0),
+        }
+    }
+
+    /// Top surface elevation, matching the meshes below.
+    fn surface_z(&self, bbox: &BoundingBox2, p: Point2) -> f64 {
+        let z_base = self.base_elevation;
+        let run = match (self.roof_type, self.ridge_direction) {
+            (RoofType::Flat, _) => return z_base + self.thickness,
+            (RoofType::Shed, RidgeDirection::AlongX) => p.y - bbox.min.y,
+            (RoofType::Shed, RidgeDirection::AlongY) => p.x - bbox.min.x,
+            _ => self.eave_run(bbox, p),
         };
+        if self.roof_type == RoofType::Mansard {
+            return z_base + self.mansard_rise(run);
+        }
+        z_base + self.gradient(bbox) * run
+    }
 
-        Ok(TriangleMesh::from_vertices_indices(vertices, indices))
+    /// Underside elevation, matching the meshes below.
+    fn soffit_z(&self, bbox: &BoundingBox2, p: Point2) -> f64 {
+        if self.roof_type != RoofType::Mansard {
+            return self.surface_z(bbox, p) - self.slab_depth_in(bbox);
+        }
+        let (lower, upper) = self.mansard_pitches();
+        let run = self.eave_run(bbox, p);
+        if run < self.soffit_break_inset() {
+            self.base_elevation + lower.tan() * run - self.thickness / lower.cos()
+        } else {
+            self.base_elevation + self.mansard_rise(run) - self.thickness / upper.cos()
+        }
     }
 
-    /// Generate mesh for a hip roof.
-    fn to_mesh_hip(&self) -> GeometryResult<TriangleMesh> {
-        let bbox = self
-            .boundary
-            .bounding_box()
-            .ok_or(GeometryError::InsufficientVertices)?;
+    /// Plan distance from a point to the nearest eave it rises from.
+    fn eave_run(&self, bbox: &BoundingBox2, p: Point2) -> f64 {
+        self.eave_distances(bbox, p)
+            .into_iter()
+            .map(|(_, d)| d)
+            .fold(f64::INFINITY, f64::min)
+    }
 
-        let overhang = self.eave_overhang;
-        let z_base = self.base_elevation;
-        let ridge_z = self.top_elevation();
+    /// Lower and upper pitches of a mansard roof, in radians.
+    fn mansard_pitches(&self) -> (f64, f64) {
+        (
+            self.lower_slope_degrees.to_radians(),
+            self.slope_degrees.to_radians(),
+        )
+    }
 
-        let (x_min, x_max, y_min, y_max) = (
-            bbox.min.x - overhang,
-            bbox.max.x + overhang,
-            bbox.min.y - overhang,
-            bbox.max.y + overhang,
-        );
+    /// Height of a mansard roof's surface `run` in from the eaves.
+    fn mansard_rise(&self, run: f64) -> f64 {
+        let (lower, upper) = self.mansard_pitches();
+        let inset = self.break_inset;
+        lower.tan() * run.min(inset) + upper.tan() * (run - inset).max(0.0)
+    }
 
-        // Determine shorter dimension to calculate ridge endpoints
-        let width = x_max - x_min;
-        let depth = y_max - y_min;
-        let x_mid = (x_min + x_max) / 2.0;
-        let y_mid = (y_min + y_max) / 2.0;
-
-        let vertices = if width >= depth {
-            // Ridge along X (wider dimension)
-            let ridge_inset = depth / 2.0;
-            vec![
-                // 4 eave corners
-                Point3::new(x_min, y_min, z_base), // 0: SW
-                Point3::new(x_max, y_min, z_base), // 1: SE
-                Point3::new(x_max, y_max, z_base), // 2: NE
-                Point3::new(x_min, y_max, z_base), // 3: NW
-                // 2 ridge endpoints
-                Point3::new(x_min + ridge_inset, y_mid, ridge_z), // 4: ridge-west
-                Point3::new(x_max - ridge_inset, y_mid, ridge_z), // 5: ridge-east
-            ]
-        } else {
-            // Ridge along Y (deeper dimension)
-            let ridge_inset = width / 2.0;
-            vec![
-                // 4 eave corners
-                Point3::new(x_min, y_min, z_base), // 0: SW
-                Point3::new(x_max, y_min, z_base), // 1: SE
-                Point3::new(x_max, y_max, z_base), // 2: NE
-                Point3::new(x_min, y_max, z_base), // 3: NW
-                // 2 ridge endpoints
-                Point3::new(x_mid, y_min + ridge_inset, ridge_z), // 4: ridge-south
-                Point3::new(x_mid, y_max - ridge_inset, ridge_z), // 5: ridge-north
-            ]
-        };
+    /// Plan distance from the eaves to the break line on a mansard's soffit.
+    ///
+    /// Each slope's soffit is its surface lowered square to the slope by the
+    /// thickness, so the two soffit planes cross further in than the break
+    /// line above.
+    fn soffit_break_inset(&self) -> f64 {
+        let (lower, upper) = self.mansard_pitches();
+        let run = lower.tan() - upper.tan();
+        if run.abs() < 1e-12 {
+            return self.break_inset;
+        }
+        self.break_inset + self.thickness * (1.0 / lower.cos() - 1.0 / upper.cos()) / run
+    }
 
-        let indices = if width >= depth {
-            vec![
-                // South slope
-                [0, 1, 5],
-                [0, 5, 4],
-                // North slope
-                [2, 3, 4],
-                [2, 4, 5],
-                // West hip
-                [3, 0, 4],
-                // East hip
-                [1, 2, 5],
-            ]
-        } else {
-            vec![
-                // West slope
-                [0, 3, 5],
-                [0, 5, 4],
-                // East slope
-                [1, 4, 5],
-                [1, 5, 2],
-                // South hip
-                [0, 4, 1],
-                // North hip
-                [3, 2, 5],
-            ]
+    /// Sloped surface area of a mansard roof, less its openings.
+    fn mansard_surface_area(&self, bbox: &BoundingBox2) -> f64 {
+        let (lower, upper) = self.mansard_pitches();
+        let inset = self.break_inset.min(half_span(bbox));
+        let (width, depth) = (bbox.max.x - bbox.min.x, bbox.max.y - bbox.min.y);
+        let upper_plan = (width - 2.0 * inset) * (depth - 2.0 * inset);
+        let mut area = (width * depth - upper_plan) / lower.cos() + upper_plan / upper.cos();
+        for opening in &self.openings {
+            let pitch = match self.face_of(bbox, opening) {
+                Some(face) if face >= 4 => upper,
+                _ => lower,
+            };
+            area -= opening.area() / pitch.cos();
+        }
+        area
+    }
+
+    /// Distances from a plan point to the eaves a pitched roof rises from,
+    /// keyed by face: 0 south, 1 east, 2 north, 3 west.
+    fn eave_distances(&self, bbox: &BoundingBox2, p: Point2) -> Vec<(usize, f64)> {
+        let all = [
+            (0, p.y - bbox.min.y),
+            (1, bbox.max.x - p.x),
+            (2, bbox.max.y - p.y),
+            (3, p.x - bbox.min.x),
+        ];
+        match (self.roof_type, self.ridge_direction) {
+            (RoofType::Gable, RidgeDirection::AlongX) => vec![all[0], all[2]],
+            (RoofType::Gable, RidgeDirection::AlongY) => vec![all[1], all[3]],
+            (RoofType::Hip | RoofType::Mansard, _) => all.to_vec(),
+            _ => vec![(0, 0.0)],
+        }
+    }
+
+    /// The face an opening lies on, or `None` if its footprint reaches a
+    /// ridge, hip, or break line.
+    fn face_of(&self, bbox: &BoundingBox2, opening: &RoofOpening) -> Option<usize> {
+        let nearest = |p: Point2| {
+            self.eave_distances(bbox, p)
+                .into_iter()
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(face, _)| face)
         };
+        let face = nearest(opening.center)?;
+        let on_face = |p: &Point2| {
+            let distances = self.eave_distances(bbox, *p);
+            let own = distances.iter().find(|(f, _)| *f == face).map(|(_, d)| *d);
+            own.is_some_and(|own| distances.iter().all(|(f, d)| *f == face || own < d - 1e-9))
+        };
+        let corners = opening.footprint();
+        if !corners.iter().all(on_face) {
+            return None;
+        }
+        if self.roof_type != RoofType::Mansard {
+            return Some(face);
+        }
 
-        Ok(TriangleMesh::from_vertices_indices(vertices, indices))
+        // Mansard sides split at the break line, keyed `face` below and
+        // `face + 4` above; upper openings must clear the soffit's break too
+        let runs = corners.map(|p| self.eave_distances(bbox, p)[face].1);
+        if runs.iter().all(|r| *r < self.break_inset - 1e-9) {
+            Some(face)
+        } else if runs.iter().all(|r| *r > self.soffit_break_inset() + 1e-9) {
+            Some(face + 4)
+        } else {
+            None
+        }
     }
 
-    /// Generate mesh for a shed roof (single slope).
-    fn to_mesh_shed(&self) -> GeometryResult<TriangleMesh> {
-        let bbox = self
-            .boundary
-            .bounding_box()
-            .ok_or(GeometryError::InsufficientVertices)?;
+    /// Plan polygons of the sloped faces, keyed like
+    /// [`eave_distances`](Self::eave_distances) (a mansard's upper faces
+    /// four higher) and counter-clockwise.
+    fn sloped_faces(&self, bbox: &BoundingBox2) -> Vec<(usize, Vec<Point2>)> {
+        let (x_min, x_max, y_min, y_max) = (bbox.min.x, bbox.max.x, bbox.min.y, bbox.max.y);
+        let (x_mid, y_mid) = ((x_min + x_max) / 2.0, (y_min + y_max) / 2.0);
+        let sw = Point2::new(x_min, y_min);
+        let se = Point2::new(x_max, y_min);
+        let ne = Point2::new(x_max, y_max);
+        let nw = Point2::new(x_min, y_max);
+
+        match (self.roof_type, self.ridge_direction) {
+            (RoofType::Gable, RidgeDirection::AlongX) => {
+                let (w, e) = (Point2::new(x_min, y_mid), Point2::new(x_max, y_mid));
+                vec![(0, vec![sw, se, e, w]), (2, vec![ne, nw, w, e])]
+            }
+            (RoofType::Gable, RidgeDirection::AlongY) => {
+                let (s, n) = (Point2::new(x_mid, y_min), Point2::new(x_mid, y_max));
+                vec![(1, vec![se, ne, n, s]), (3, vec![nw, sw, s, n])]
+            }
+            (RoofType::Hip, _) => hip_faces(bbox),
+            (RoofType::Mansard, _) => mansard_faces(bbox, self.break_inset),
+            _ => vec![(0, vec![sw, se, ne, nw])],
+        }
+    }
 
-        let overhang = self.eave_overhang;
-        let z_low = self.base_elevation;
-        let z_high = self.top_elevation();
+    /// Sloped faces of a gable or shed roof clipped to the eave outline
+    /// `ring`, keyed and wound like [`sloped_faces`](Self::sloped_faces).
+    ///
+    /// The slopes still rise from the bounding rectangle's eaves to a ridge
+    /// across its middle; only the parts over the outline are kept. Each
+    /// face also gets the ring's vertices that lie on its edges, so it
+    /// meets the eave band and the other face without T-junctions.
+    fn outline_faces(
+        &self,
+        bbox: &BoundingBox2,
+        ring: &[Point2],
+    ) -> GeometryResult<Vec<(usize, Vec<Point2>)>> {
+        let outline = Polygon2::new(ring.to_vec())?;
+        let mut faces = Vec::new();
+        for (face, rect) in self.sloped_faces(bbox) {
+            for piece in Polygon2::new(rect)?.intersection(&outline) {
+                if piece.signed_area() > WELD_TOLERANCE {
+                    faces.push((face, with_points_on_edges(&piece.vertices, ring)));
+                }
+            }
+        }
+        Ok(faces)
+    }
 
-        let (x_min, x_max, y_min, y_max) = (
-            bbox.min.x - overhang,
-            bbox.max.x + overhang,
-            bbox.min.y - overhang,
-            bbox.max.y + overhang,
+    /// Plan outline of the sloped faces, counter-clockwise, with the ridge
+    /// ends where they meet a gable edge.
+    ///
+    /// Gable and shed roofs follow the eave outline, with a vertex wherever
+    /// it crosses the ridge; hip and mansard roofs take the bounding
+    /// rectangle.
+    fn eave_ring(&self, bbox: &BoundingBox2) -> GeometryResult<Vec<Point2>> {
+        let (x_mid, y_mid) = (
+            (bbox.min.x + bbox.max.x) / 2.0,
+            (bbox.min.y + bbox.max.y) / 2.0,
         );
+        Ok(match (self.roof_type, self.ridge_direction) {
+            (RoofType::Gable, RidgeDirection::AlongX) => split_at(
+                &self.eave_outline()?.vertices,
+                |p| p.y - y_mid,
+                |p| Point2::new(p.x, y_mid),
+            ),
+            (RoofType::Gable, RidgeDirection::AlongY) => split_at(
+                &self.eave_outline()?.vertices,
+                |p| p.x - x_mid,
+                |p| Point2::new(x_mid, p.y),
+            ),
+            (RoofType::Shed, _) => self.eave_outline()?.vertices,
+            _ => vec![
+                bbox.min,
+                Point2::new(bbox.max.x, bbox.min.y),
+                bbox.max,
+                Point2::new(bbox.min.x, bbox.max.y),
+            ],
+        })
+    }
+
+    /// Opening footprints on a face, clockwise as triangulation holes.
+    fn face_holes(&self, bbox: &BoundingBox2, face: usize) -> Vec<Vec<Point2>> {
+        self.openings
+            .iter()
+            .filter(|o| self.face_of(bbox, o) == Some(face))
+            .map(|o| o.footprint().iter().rev().copied().collect())
+            .collect()
+    }
 
-        let vertices = match self.ridge_direction {
-            RidgeDirection::AlongX => {
-                // Slope faces south (low at front, high at back)
-                vec![
-                    // Low edge (front/south)
-                    Point3::new(x_min, y_min, z_low), // 0
-                    Point3::new(x_max, y_min, z_low), // 1
-                    // High edge (back/north)
-                    Point3::new(x_max, y_max, z_high), // 2
-                    Point3::new(x_min, y_max, z_high), // 3
-                    // Bottom face
-                    Point3::new(x_min, y_min, z_low - self.thickness), // 4
-                    Point3::new(x_max, y_min, z_low - self.thickness), // 5
-                    Point3::new(x_max, y_max, z_high - self.thickness), // 6
-                    Point3::new(x_min, y_max, z_high - self.thickness), // 7
-                ]
+    /// Triangulate each sloped face around its openings and lift it onto
+    /// the roof's top surface, or onto its soffit facing down, with the
+    /// face's normal and slope-developed UVs (see [`developed_uv`]).
+    ///
+    /// `ring` is the eave ring from [`eave_ring`](Self::eave_ring).
+    fn lifted_faces(
+        &self,
+        bbox: &BoundingBox2,
+        ring: &[Point2],
+        soffit: bool,
+    ) -> GeometryResult<TriangleMesh> {
+        let faces = match self.roof_type {
+            RoofType::Mansard if soffit => mansard_faces(bbox, self.soffit_break_inset()),
+            RoofType::Gable | RoofType::Shed => self.outline_faces(bbox, ring)?,
+            _ => self.sloped_faces(bbox),
+        };
+        let mut mesh = TriangleMesh::new();
+        for (face, outline) in faces {
+            let holes = self.face_holes(bbox, face);
+            let (points, triangles) = triangulate_polygon_with_holes(&outline, &holes)?;
+            let base = mesh.vertices.len() as u32;
+            mesh.vertices.extend(points.iter().map(|p| {
+                let z = if soffit {
+                    self.soffit_z(bbox, *p)
+                } else {
+                    self.surface_z(bbox, *p)
+                };
+                Point3::new(p.x, p.y, z)
+            }));
+            let first = mesh.indices.len();
+            mesh.indices.extend(triangles.iter().map(|t| {
+                let [a, b, c] = t.map(|i| base + i as u32);
+                if soffit {
+                    [a, c, b]
+                } else {
+                    [a, b, c]
+                }
+            }));
+
+            // Faces are planar; take the normal of the largest triangle
+            let normal = mesh.indices[first..]
+                .iter()
+                .map(|tri| {
+                    let [a, b, c] = tri.map(|i| mesh.vertices[i as usize]);
+                    (b - a).cross(&(c - a))
+                })
+                .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+                .and_then(|n| n.try_normalize())
+                .unwrap_or(if soffit {
+                    -Vector3::UNIT_Z
+                } else {
+                    Vector3::UNIT_Z
+                });
+            for v in &mesh.vertices[base as usize..] {
+                mesh.normals.push(normal);
+                mesh.uvs.push(developed_uv(*v, normal));
             }
-            RidgeDirection::AlongY => {
-                // Slope faces west (low at left, high at right)
-                vec![
-                    // Low edge (left/west)
-                    Point3::new(x_min, y_min, z_low), // 0
-                    Point3::new(x_min, y_max, z_low), // 1
-                    // High edge (right/east)
-                    Point3::new(x_max, y_max, z_high), // 2
-                    Point3::new(x_max, y_min, z_high), // 3
-                    // Bottom face
-                    Point3::new(x_min, y_min, z_low - self.thickness), // 4
-                    Point3::new(x_min, y_max, z_low - self.thickness), // 5
-                    Point3::new(x_max, y_max, z_high - self.thickness), // 6
-                    Point3::new(x_max, y_min, z_high - self.thickness), // 7
-                ]
+        }
+        Ok(mesh)
+    }
+
+    /// Vertical band from the soffit to the surface along a closed ring,
+    /// facing the right of the direction of travel.
+    ///
+    /// UVs run along the ring against height above the roof base, as on a
+    /// wall face.
+    fn surface_band(&self, bbox: &BoundingBox2, ring: &[Point2], mesh: &mut TriangleMesh) {
+        let mut along = 0.0;
+        for (i, p0) in ring.iter().enumerate() {
+            let p1 = ring[(i + 1) % ring.len()];
+            let (z0, z1) = (self.surface_z(bbox, *p0), self.surface_z(bbox, p1));
+            let corners = [
+                Point3::new(p0.x, p0.y, self.soffit_z(bbox, *p0)),
+                Point3::new(p1.x, p1.y, self.soffit_z(bbox, p1)),
+                Point3::new(p1.x, p1.y, z1),
+                Point3::new(p0.x, p0.y, z0),
+            ];
+            let next = along + p0.distance_to(&p1);
+            let normal = Vector3::new(p1.y - p0.y, p0.x - p1.x, 0.0)
+                .try_normalize()
+                .unwrap_or(Vector3::UNIT_X);
+
+            let base = mesh.vertices.len() as u32;
+            for (corner, u) in corners.into_iter().zip([along, next, next, along]) {
+                mesh.vertices.push(corner);
+                mesh.normals.push(normal);
+                mesh.uvs.push((u, corner.z - self.base_elevation));
             }
+            mesh.indices.push([base, base + 1, base + 2]);
+            mesh.indices.push([base, base + 2, base + 3]);
+            along = next;
+        }
+    }
+
+    /// Roof outline at the eaves: the boundary grown by the eave overhang.
+    ///
+    /// The boundary itself is offset, so non-rectangular footprints keep
+    /// their shape. Returned counter-clockwise.
+    pub fn eave_outline(&self) -> GeometryResult<Polygon2> {
+        let mut outline = if self.eave_overhang > 0.0 {
+            self.boundary
+                .offset(self.eave_overhang, OffsetJoin::default())?
+                .into_iter()
+                .max_by(|a, b| a.area().total_cmp(&b.area()))
+                .ok_or(GeometryError::InsufficientVertices)?
+        } else {
+            self.boundary.clone()
         };
+        outline.ensure_ccw();
+        Ok(outline)
+    }
 
-        let indices = vec![
-            // Top surface
-            [0, 1, 2],
-            [0, 2, 3],
-            // Bottom surface
-            [4, 6, 5],
-            [4, 7, 6],
-            // Front edge
-            [0, 5, 1],
-            [0, 4, 5],
-            // Back edge
-            [2, 7, 3],
-            [2, 6, 7],
-            // Left edge
-            [0, 3, 7],
-            [0, 7, 4],
-            // Right edge
-            [1, 6, 2],
-            [1, 5, 6],
-        ];
+    /// Plan extents of the eave outline.
+    fn eave_bounds(&self) -> GeometryResult<BoundingBox2> {
+        self.eave_outline()?
+            .bounding_box()
+            .ok_or(GeometryError::InsufficientVertices)
+    }
 
-        Ok(TriangleMesh::from_vertices_indices(vertices, indices))
+    /// Generate mesh for a flat roof.
+    fn to_mesh_flat(&self) -> GeometryResult<TriangleMesh> {
+        let outline = self.eave_outline()?;
+        if self.openings.is_empty() {
+            return extrude_polygon(&outline.vertices, self.thickness, self.base_elevation);
+        }
+        let holes: Vec<Vec<Point2>> = self
+            .openings
+            .iter()
+            .map(|o| o.footprint().iter().rev().copied().collect())
+            .collect();
+        extrude_polygon_with_holes(
+            &outline.vertices,
+            &holes,
+            self.thickness,
+            self.base_elevation,
+        )
+    }
+
+    /// Generate mesh for a gable, hip, shed, or mansard roof.
+    ///
+    /// The slab is closed: the top surface, the soffit below it (see
+    /// [`soffit_elevation`](Self::soffit_elevation)), and vertical edges
+    /// round the eaves, gable ends, and any openings.
+    fn to_mesh_pitched(&self) -> GeometryResult<TriangleMesh> {
+        let mut mesh = self.pitched_faces()?;
+
+        // Faces and bands are built apart; share their common corners,
+        // which their separate normals and UVs would keep apart
+        mesh.normals.clear();
+        mesh.uvs.clear();
+        mesh.weld_vertices(WELD_TOLERANCE);
+        Ok(mesh)
+    }
+
+    /// The faces and bands of a pitched roof, each with its own vertices,
+    /// normals, and UVs.
+    fn pitched_faces(&self) -> GeometryResult<TriangleMesh> {
+        // Pitched roofs span the rectangle around the eave outline
+        let bbox = self.eave_bounds()?;
+        if self.roof_type == RoofType::Mansard
+            && self.soffit_break_inset() >= half_span(&bbox) - WELD_TOLERANCE
+        {
+            return Err(GeometryError::InvalidRoofProfile(format!(
+                "break inset {} leaves no upper slope on a {} wide roof",
+                self.break_inset,
+                2.0 * half_span(&bbox)
+            )));
+        }
+
+        let ring = self.eave_ring(&bbox)?;
+        let mut mesh = self.lifted_faces(&bbox, &ring, false)?;
+        mesh.merge(&self.lifted_faces(&bbox, &ring, true)?);
+        self.surface_band(&bbox, &ring, &mut mesh);
+        for opening in &self.openings {
+            let hole: Vec<Point2> = opening.footprint().iter().rev().copied().collect();
+            self.surface_band(&bbox, &hole, &mut mesh);
+        }
+        Ok(mesh)
+    }
+
+    /// Generate a mesh for rendering, with per-face normals and UVs.
+    ///
+    /// Flat roofs are meshed as by [`Element::to_mesh`], with plan UVs on
+    /// the slab faces. Pitched roofs keep each face's vertices apart rather
+    /// than welding them closed, so sloped faces carry slope-developed UVs
+    /// (true distances across and up the slope) and every face shades flat.
+    pub fn to_textured_mesh(&self) -> GeometryResult<TriangleMesh> {
+        match self.roof_type {
+            RoofType::Flat => self.to_mesh_flat(),
+            RoofType::Gable | RoofType::Hip | RoofType::Shed | RoofType::Mansard => {
+                self.pitched_faces()
+            }
+        }
     }
 }
 
@@ -557,45 +1019,248 @@ impl Element for Roof {
         self.id
     }
 
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
     fn element_type(&self) -> ElementType {
         ElementType::Roof
     }
 
     fn bounding_box(&self) -> GeometryResult<BoundingBox3> {
-        let bbox2 = self
-            .boundary
-            .bounding_box()
-            .ok_or(GeometryError::InsufficientVertices)?;
-
-        let overhang = self.eave_overhang;
+        let bbox2 = self.eave_bounds()?;
+        // Pitched slabs hang below the eave line by their depth
+        let bottom = match self.roof_type {
+            RoofType::Flat => self.base_elevation,
+            _ => self.base_elevation - self.slab_depth_in(&bbox2),
+        };
         Ok(BoundingBox3::new(
-            Point3::new(
-                bbox2.min.x - overhang,
-                bbox2.min.y - overhang,
-                self.base_elevation,
-            ),
-            Point3::new(
-                bbox2.max.x + overhang,
-                bbox2.max.y + overhang,
-                self.top_elevation(),
-            ),
+            Point3::new(bbox2.min.x, bbox2.min.y, bottom),
+            Point3::new(bbox2.max.x, bbox2.max.y, self.top_elevation()),
         ))
     }
 
     fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
         match self.roof_type {
             RoofType::Flat => self.to_mesh_flat(),
-            RoofType::Gable => self.to_mesh_gable(),
-            RoofType::Hip => self.to_mesh_hip(),
-            RoofType::Shed => self.to_mesh_shed(),
-            RoofType::Mansard => {
-                // Mansard is complex; fall back to flat for now
-                self.to_mesh_flat()
+            RoofType::Gable | RoofType::Hip | RoofType::Shed | RoofType::Mansard => {
+                self.to_mesh_pitched()
             }
         }
     }
 }
 
+/// Outer outline of a set of wall baselines, counter-clockwise.
+fn wall_loop_outline(walls: &[&Wall]) -> GeometryResult<Polygon2> {
+    let graph = wall_graph(walls);
+
+    // Each separate cluster of walls has its own exterior; take the largest
+    let exterior = graph
+        .rooms()
+        .filter(|r| r.is_exterior)
+        .max_by(|a, b| a.area().total_cmp(&b.area()))
+        .ok_or(GeometryError::WallsNotEnclosed)?;
+    let vertices: Vec<Point2> = without_spurs(&exterior.boundary_nodes)
+        .into_iter()
+        .filter_map(|id| graph.get_node(id))
+        .map(|n| Point2::new(n.position[0] / M_TO_MM, n.position[1] / M_TO_MM))
+        .collect();
+    if vertices.len() < 3 {
+        return Err(GeometryError::WallsNotEnclosed);
+    }
+
+    let mut outline = Polygon2::new(vertices)?.simplify(1e-9);
+    outline.ensure_ccw();
+    Ok(outline)
+}
+
+/// A closed node loop with dead ends (walked out and back) cut off.
+pub(super) fn without_spurs(nodes: &[NodeId]) -> Vec<NodeId> {
+    let mut kept: Vec<NodeId> = Vec::with_capacity(nodes.len());
+    for &node in nodes {
+        if kept.len() >= 2 && kept[kept.len() - 2] == node {
+            kept.pop();
+        } else {
+            kept.push(node);
+        }
+    }
+    // Spurs across the start of the loop
+    loop {
+        let n = kept.len();
+        if n >= 3 && kept[1] == kept[n - 1] {
+            kept.remove(0);
+            kept.pop();
+        } else if n >= 3 && kept[n - 2] == kept[0] {
+            kept.truncate(n - 2);
+        } else {
+            break;
+        }
+    }
+    kept
+}
+
+/// UVs in meters for a point on a plane with the given unit normal.
+///
+/// On a sloped plane u runs level across the slope and v up it, both as
+/// true distances along the plane, so a texture keeps its scale however
+/// steep the face. Level planes use plan coordinates.
+fn developed_uv(p: Point3, normal: Vector3) -> (f64, f64) {
+    let up = if normal.z < 0.0 { -normal } else { normal };
+    let tilt = up.x.hypot(up.y);
+    if tilt < 1e-12 || up.z < 1e-12 {
+        return (p.x, p.y);
+    }
+    // An upward normal leans downslope
+    let (sx, sy) = (-up.x / tilt, -up.y / tilt);
+    (sy * p.x - sx * p.y, (sx * p.x + sy * p.y) / up.z)
+}
+
+/// A ring with a vertex added wherever an edge crosses the zero level of
+/// `level`, such as the plan line of a ridge. `snap` puts each crossing
+/// exactly on that line.
+fn split_at(
+    ring: &[Point2],
+    level: impl Fn(Point2) -> f64,
+    snap: impl Fn(Point2) -> Point2,
+) -> Vec<Point2> {
+    let mut split = Vec::with_capacity(ring.len() + 2);
+    for (i, &p0) in ring.iter().enumerate() {
+        let p1 = ring[(i + 1) % ring.len()];
+        split.push(p0);
+        let (l0, l1) = (level(p0), level(p1));
+        if l0 * l1 < 0.0 {
+            split.push(snap(p0.lerp(&p1, l0 / (l0 - l1))));
+        }
+    }
+    split
+}
+
+/// A polygon with each of `points` that lies inside one of its edges added
+/// to that edge, in order along it.
+fn with_points_on_edges(polygon: &[Point2], points: &[Point2]) -> Vec<Point2> {
+    let mut result = Vec::with_capacity(polygon.len());
+    for (i, &start) in polygon.iter().enumerate() {
+        let end = polygon[(i + 1) % polygon.len()];
+        let edge = LineSegment2::new(start, end);
+        let mut on_edge: Vec<(f64, Point2)> = points
+            .iter()
+            .filter(|p| {
+                p.distance_to(&start) > WELD_TOLERANCE
+                    && p.distance_to(&end) > WELD_TOLERANCE
+                    && edge.distance_to_point(p) < WELD_TOLERANCE
+            })
+            .map(|p| (edge.project_point(p), *p))
+            .collect();
+        on_edge.sort_by(|a, b| a.0.total_cmp(&b.0));
+        result.push(start);
+        result.extend(on_edge.into_iter().map(|(_, p)| p));
+    }
+    result
+}
+
+/// Half the shorter side of a plan rectangle: the run from the eaves to
+/// the ridge of a hip roof over it.
+fn half_span(bbox: &BoundingBox2) -> f64 {
+    (bbox.max.x - bbox.min.x).min(bbox.max.y - bbox.min.y) / 2.0
+}
+
+/// Plan polygons of the faces of a hip roof over a rectangle, keyed like
+/// [`Roof::eave_distances`] and counter-clockwise.
+fn hip_faces(bbox: &BoundingBox2) -> Vec<(usize, Vec<Point2>)> {
+    let (x_min, x_max, y_min, y_max) = (bbox.min.x, bbox.max.x, bbox.min.y, bbox.max.y);
+    let (x_mid, y_mid) = ((x_min + x_max) / 2.0, (y_min + y_max) / 2.0);
+    let sw = Point2::new(x_min, y_min);
+    let se = Point2::new(x_max, y_min);
+    let ne = Point2::new(x_max, y_max);
+    let nw = Point2::new(x_min, y_max);
+
+    let (width, depth) = (x_max - x_min, y_max - y_min);
+    let faces = if width >= depth {
+        let lo = Point2::new(x_min + depth / 2.0, y_mid);
+        let hi = Point2::new(x_max - depth / 2.0, y_mid);
+        [
+            (0, vec![sw, se, hi, lo]),
+            (1, vec![se, ne, hi]),
+            (2, vec![ne, nw, lo, hi]),
+            (3, vec![nw, sw, lo]),
+        ]
+    } else {
+        let lo = Point2::new(x_mid, y_min + width / 2.0);
+        let hi = Point2::new(x_mid, y_max - width / 2.0);
+        [
+            (0, vec![sw, se, lo]),
+            (1, vec![se, ne, hi, lo]),
+            (2, vec![ne, nw, hi]),
+            (3, vec![nw, sw, lo, hi]),
+        ]
+    };
+    // A square hip meets at a point, collapsing the ridge
+    faces
+        .into_iter()
+        .map(|(face, mut points)| {
+            points.dedup_by(|a, b| a.distance_to(b) < 1e-12);
+            (face, points)
+        })
+        .collect()
+}
+
+/// Plan polygons of the faces of a mansard roof over a rectangle, with the
+/// break line `inset` in from the eaves. The lower faces are keyed like
+/// [`Roof::eave_distances`]; the upper faces, a hip over the break line,
+/// are keyed four higher.
+fn mansard_faces(bbox: &BoundingBox2, inset: f64) -> Vec<(usize, Vec<Point2>)> {
+    let inner = BoundingBox2::new(
+        Point2::new(bbox.min.x + inset, bbox.min.y + inset),
+        Point2::new(bbox.max.x - inset, bbox.max.y - inset),
+    );
+    let corners = |b: &BoundingBox2| {
+        [
+            b.min,
+            Point2::new(b.max.x, b.min.y),
+            b.max,
+            Point2::new(b.min.x, b.max.y),
+        ]
+    };
+    let (eaves, break_line) = (corners(bbox), corners(&inner));
+
+    let mut faces: Vec<(usize, Vec<Point2>)> = (0..4)
+        .map(|k| {
+            let j = (k + 1) % 4;
+            (k, vec![eaves[k], eaves[j], break_line[j], break_line[k]])
+        })
+        .collect();
+    faces.extend(
+        hip_faces(&inner)
+            .into_iter()
+            .map(|(face, points)| (face + 4, points)),
+    );
+    faces
+}
+
+impl TransformableElement for Roof {
+    /// The ridge stays axis-aligned: it switches between X and Y when the
+    /// transform is nearer a quarter turn.
+    fn apply_transform(&mut self, transform: &PlanTransform) {
+        self.boundary = transform.apply_polygon(&self.boundary);
+        for opening in &mut self.openings {
+            opening.center = transform.apply_point(opening.center);
+            if transform.swaps_axes() {
+                std::mem::swap(&mut opening.width, &mut opening.depth);
+            }
+        }
+        if transform.swaps_axes() {
+            self.ridge_direction = match self.ridge_direction {
+                RidgeDirection::AlongX => RidgeDirection::AlongY,
+                RidgeDirection::AlongY => RidgeDirection::AlongX,
+            };
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -650,13 +1315,19 @@ mod tests {
     #[test]
     fn roof_invalid_bounds() {
         let result = Roof::rectangle(Point2::new(10.0, 0.0), Point2::new(0.0, 10.0), 0.3);
-        assert!(matches!(result, Err(GeometryError::InvalidFloorBounds)));
+        assert!(matches!(
+            result,
+            Err(GeometryError::InvalidFloorBounds { .. })
+        ));
     }
 
     #[test]
     fn roof_non_positive_thickness() {
         let result = Roof::rectangle(Point2::new(0.0, 0.0), Point2::new(10.0, 10.0), 0.0);
-        assert!(matches!(result, Err(GeometryError::NonPositiveThickness)));
+        assert!(matches!(
+            result,
+            Err(GeometryError::NonPositiveThickness { .. })
+        ));
     }
 
     #[test]
@@ -739,6 +1410,38 @@ mod tests {
         assert!(mesh.is_valid());
     }
 
+    #[test]
+    fn roof_flat_overhang_follows_l_shaped_boundary() {
+        let boundary = Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 4.0),
+            Point2::new(4.0, 4.0),
+            Point2::new(4.0, 10.0),
+            Point2::new(0.0, 10.0),
+        ])
+        .unwrap();
+        let mut roof = Roof::new(boundary.clone(), 0.3).unwrap();
+        roof.set_eave_overhang(0.5);
+
+        // Every edge moves out by the overhang, mitred at the corners
+        let outline = roof.eave_outline().unwrap();
+        let expected_area = boundary.area() + boundary.perimeter() * 0.5 + 4.0 * 0.25;
+        assert!((outline.area() - expected_area).abs() < 1e-9);
+        assert!(outline.contains_point(&Point2::new(4.4, 4.4)));
+        assert!(!outline.contains_point(&Point2::new(4.6, 4.6)));
+
+        // The mesh keeps the notch instead of filling the bounding box
+        let mesh = roof.to_mesh().unwrap();
+        assert!(mesh.is_valid());
+        assert!((mesh.volume() - expected_area * 0.3).abs() < 1e-9);
+        let bbox = mesh.bounding_box().unwrap();
+        assert!((bbox.min.x + 0.5).abs() < 1e-9 && (bbox.max.y - 10.5).abs() < 1e-9);
+
+        let element_bbox = roof.bounding_box().unwrap();
+        assert!((element_bbox.max.x - 10.5).abs() < 1e-9);
+    }
+
     #[test]
     fn roof_mesh_gable_valid() {
         let roof = Roof::gable(
@@ -752,7 +1455,56 @@ mod tests {
 
         let mesh = roof.to_mesh().unwrap();
         assert!(mesh.is_valid());
-        assert!(mesh.vertex_count() == 6);
+        assert!(mesh.is_manifold());
+        assert!(mesh.vertex_count() == 12);
+
+        // Thickness is square to the slope, so the slab is deeper than it
+        let pitch = ((roof.top_elevation() - roof.base_elevation) / 4.0).atan();
+        let expected = roof.footprint_area() * 0.3 / pitch.cos();
+        assert!((mesh.volume() - expected).abs() < 1e-9);
+        let nominal = roof.footprint_area() * 0.3 / 30f64.to_radians().cos();
+        assert!((mesh.volume() - nominal).abs() / nominal < 0.05);
+    }
+
+    #[test]
+    fn roof_textured_mesh_develops_slopes() {
+        let roof = Roof::gable(
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 8.0),
+            0.3,
+            30.0,
+            RidgeDirection::AlongX,
+        )
+        .unwrap();
+
+        let mesh = roof.to_textured_mesh().unwrap();
+        assert_eq!(mesh.normals.len(), mesh.vertex_count());
+        assert_eq!(mesh.uvs.len(), mesh.vertex_count());
+        assert!((mesh.volume() - roof.to_mesh().unwrap().volume()).abs() < 1e-9);
+        for tri in &mesh.indices {
+            let [a, b, c] = tri.map(|i| mesh.vertices[i as usize]);
+            let winding = (b - a).cross(&(c - a)).normalize().unwrap();
+            assert!(mesh.normals[tri[0] as usize].approx_eq(&winding, 1e-9));
+        }
+
+        // Each top slope develops to the ridge length by the sloped run
+        let pitch = ((roof.top_elevation() - roof.base_elevation) / 4.0).atan();
+        for side in [-1.0, 1.0] {
+            let uvs: Vec<(f64, f64)> = mesh
+                .normals
+                .iter()
+                .zip(&mesh.uvs)
+                .filter(|(n, _)| n.z > 0.1 && n.y * side > 0.0)
+                .map(|(_, uv)| *uv)
+                .collect();
+            let span = |f: fn(&(f64, f64)) -> f64| {
+                let values = uvs.iter().map(f);
+                values.clone().fold(f64::NEG_INFINITY, f64::max)
+                    - values.fold(f64::INFINITY, f64::min)
+            };
+            assert!((span(|uv| uv.0) - 10.0).abs() < 1e-9);
+            assert!((span(|uv| uv.1) - 4.0 / pitch.cos()).abs() < 1e-9);
+        }
     }
 
     #[test]
@@ -761,6 +1513,15 @@ mod tests {
 
         let mesh = roof.to_mesh().unwrap();
         assert!(mesh.is_valid());
+        assert!(mesh.is_manifold());
+        let expected = roof.footprint_area() * roof.slab_depth().unwrap();
+        assert!((mesh.volume() - expected).abs() < 1e-9);
+
+        // A square hip meets at a single apex
+        let square = Roof::hip(Point2::new(0.0, 0.0), Point2::new(8.0, 8.0), 0.3, 25.0).unwrap();
+        let mesh = square.to_mesh().unwrap();
+        assert!(mesh.is_manifold());
+        assert_eq!(mesh.vertex_count(), 10);
     }
 
     #[test]
@@ -776,6 +1537,242 @@ mod tests {
 
         let mesh = roof.to_mesh().unwrap();
         assert!(mesh.is_valid());
+        assert!(mesh.is_manifold());
+        let expected = roof.footprint_area() * roof.slab_depth().unwrap();
+        assert!((mesh.volume() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn roof_mesh_mansard_valid() {
+        let (min, max) = (Point2::new(0.0, 0.0), Point2::new(10.0, 8.0));
+        let roof = Roof::mansard(min, max, 0.3, 30.0).unwrap();
+
+        let mesh = roof.to_mesh().unwrap();
+        assert!(mesh.is_valid());
+        assert!(mesh.is_manifold());
+        // Eaves, break line, and ridge ends, on top and underneath
+        assert_eq!(mesh.vertex_count(), 20);
+        // Four lower faces and a hip above, both sides, plus the eave band
+        assert_eq!(mesh.triangle_count(), 36);
+
+        // Taller than flat, lower than a single slope at the lower pitch
+        let height = |roof: &Roof| {
+            let bbox = roof.bounding_box().unwrap();
+            bbox.max.z - bbox.min.z
+        };
+        let flat = Roof::rectangle(min, max, 0.3).unwrap();
+        let gable = Roof::gable(min, max, 0.3, 70.0, RidgeDirection::AlongX).unwrap();
+        assert!(height(&roof) > height(&flat));
+        assert!(height(&roof) < height(&gable));
+
+        // The box is the meshed extent
+        let bbox = roof.bounding_box().unwrap();
+        let mesh_bbox = mesh.bounding_box().unwrap();
+        assert!((mesh_bbox.min.z - bbox.min.z).abs() < 1e-10);
+        assert!((mesh_bbox.max.z - bbox.max.z).abs() < 1e-10);
+    }
+
+    #[test]
+    fn roof_mansard_profile() {
+        let mut roof =
+            Roof::mansard(Point2::new(0.0, 0.0), Point2::new(10.0, 8.0), 0.3, 30.0).unwrap();
+        roof.set_lower_slope(60.0);
+        roof.set_break_inset(1.5).unwrap();
+        let (lower, upper) = (60f64.to_radians(), 30f64.to_radians());
+
+        let expected = 1.5 * lower.tan() + 2.5 * upper.tan();
+        assert!((roof.ridge_height() - expected).abs() < 1e-10);
+        let at_break = roof.surface_elevation(Point2::new(5.0, 1.5)).unwrap();
+        assert!((at_break - 1.5 * lower.tan()).abs() < 1e-10);
+
+        let upper_plan = 7.0 * 5.0;
+        let expected = (80.0 - upper_plan) / lower.cos() + upper_plan / upper.cos();
+        assert!((roof.surface_area() - expected).abs() < 1e-9);
+
+        // Each slope is the thickness deep, square to itself
+        let drop = |p| roof.surface_elevation(p).unwrap() - roof.soffit_elevation(p).unwrap();
+        assert!((drop(Point2::new(5.0, 0.5)) * lower.cos() - 0.3).abs() < 1e-10);
+        assert!((drop(Point2::new(5.0, 3.0)) * upper.cos() - 0.3).abs() < 1e-10);
+
+        // A flat-topped mansard
+        roof.set_type(RoofType::Mansard, 0.0);
+        let mesh = roof.to_mesh().unwrap();
+        assert!(mesh.is_manifold());
+        assert!((mesh.bounding_box().unwrap().max.z - 1.5 * lower.tan()).abs() < 1e-10);
+
+        // The break line must leave room for the upper slopes
+        assert!(matches!(
+            roof.set_break_inset(0.0),
+            Err(GeometryError::InvalidRoofProfile(_))
+        ));
+        roof.set_break_inset(4.0).unwrap();
+        assert!(matches!(
+            roof.to_mesh(),
+            Err(GeometryError::InvalidRoofProfile(_))
+        ));
+    }
+
+    #[test]
+    fn roof_mansard_openings() {
+        let mut roof =
+            Roof::mansard(Point2::new(0.0, 0.0), Point2::new(10.0, 8.0), 0.3, 30.0).unwrap();
+        let area = roof.surface_area();
+        let opening =
+            |x, y, size| RoofOpening::new(Point2::new(x, y), size, size, RoofOpeningType::Chimney);
+
+        // Across the break line
+        assert_eq!(
+            roof.add_opening(opening(5.0, 1.2, 0.6)),
+            Err(GeometryError::OpeningSpansRidge)
+        );
+        roof.add_opening(opening(5.0, 0.6, 0.6)).unwrap();
+        roof.add_opening(opening(5.0, 2.5, 1.0)).unwrap();
+
+        let mesh = roof.to_mesh().unwrap();
+        assert!(mesh.is_valid());
+        assert!(mesh.is_manifold());
+        let (lower, upper) = (70f64.to_radians(), 30f64.to_radians());
+        let lost = 0.36 / lower.cos() + 1.0 / upper.cos();
+        assert!((area - roof.surface_area() - lost).abs() < 1e-9);
+    }
+
+    fn walls_through(corners: &[(f64, f64)]) -> Vec<Wall> {
+        (0..corners.len())
+            .map(|i| {
+                let (a, b) = (corners[i], corners[(i + 1) % corners.len()]);
+                Wall::new(Point2::new(a.0, a.1), Point2::new(b.0, b.1), 3.0, 0.2).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn roof_from_rectangular_walls() {
+        let walls = walls_through(&[(0.0, 0.0), (10.0, 0.0), (10.0, 8.0), (0.0, 8.0)]);
+        let refs: Vec<&Wall> = walls.iter().collect();
+
+        let roof = Roof::from_walls(&refs, 0.25, RoofType::Gable, 30.0).unwrap();
+        let mut expected = Roof::gable(
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 8.0),
+            0.25,
+            30.0,
+            RidgeDirection::AlongX,
+        )
+        .unwrap();
+        expected.set_elevation(3.0);
+
+        assert_eq!(roof.boundary.vertex_count(), 4);
+        assert!((roof.footprint_area() - 80.0).abs() < 1e-9);
+        assert_eq!(roof.ridge_direction, RidgeDirection::AlongX);
+        assert_eq!(roof.attached_walls().len(), 4);
+        assert!(!roof.uses_bounding_box_mesh());
+
+        let (mesh, expected_mesh) = (roof.to_mesh().unwrap(), expected.to_mesh().unwrap());
+        assert_eq!(mesh.vertex_count(), expected_mesh.vertex_count());
+        assert!((mesh.volume() - expected_mesh.volume()).abs() < 1e-9);
+        let (bbox, expected_bbox) = (
+            roof.bounding_box().unwrap(),
+            expected.bounding_box().unwrap(),
+        );
+        assert!(bbox.min.distance_to(&expected_bbox.min) < 1e-9);
+        assert!(bbox.max.distance_to(&expected_bbox.max) < 1e-9);
+
+        // A plan longer in Y turns the ridge
+        let walls = walls_through(&[(0.0, 0.0), (6.0, 0.0), (6.0, 12.0), (0.0, 12.0)]);
+        let refs: Vec<&Wall> = walls.iter().collect();
+        let roof = Roof::from_walls(&refs, 0.25, RoofType::Shed, 15.0).unwrap();
+        assert_eq!(roof.ridge_direction, RidgeDirection::AlongY);
+    }
+
+    #[test]
+    fn roof_from_l_shaped_walls() {
+        let mut walls = walls_through(&[
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 4.0),
+            (4.0, 4.0),
+            (4.0, 8.0),
+            (0.0, 8.0),
+        ]);
+        // A partition and a garden wall are left out of the outline
+        walls.push(Wall::new(Point2::new(4.0, 0.0), Point2::new(4.0, 4.0), 3.0, 0.1).unwrap());
+        walls.push(Wall::new(Point2::new(10.0, 2.0), Point2::new(12.0, 2.0), 1.2, 0.2).unwrap());
+        walls[1].height = 3.5;
+        let refs: Vec<&Wall> = walls.iter().collect();
+
+        let roof = Roof::from_walls(&refs, 0.25, RoofType::Flat, 30.0).unwrap();
+
+        assert_eq!(roof.boundary.vertex_count(), 6);
+        assert!((roof.footprint_area() - 56.0).abs() < 1e-9);
+        assert!((roof.base_elevation - 3.5).abs() < 1e-12);
+        assert_eq!(roof.slope_degrees, 0.0);
+        assert_eq!(roof.attached_walls().len(), 6);
+        assert!(!roof.is_attached_to(walls[6].id));
+        assert!(!roof.is_attached_to(walls[7].id));
+
+        // Flat and gable roofs follow the L; hip roofs span its bounding box
+        let mesh = roof.to_mesh().unwrap();
+        assert!((mesh.volume() - 56.0 * 0.25).abs() < 1e-9);
+        assert!(!roof.uses_bounding_box_mesh());
+        let gable = Roof::from_walls(&refs, 0.25, RoofType::Gable, 30.0).unwrap();
+        assert!(!gable.uses_bounding_box_mesh());
+        assert!(gable.to_mesh().unwrap().is_manifold());
+        let hip = Roof::from_walls(&refs, 0.25, RoofType::Hip, 30.0).unwrap();
+        assert!(hip.uses_bounding_box_mesh());
+    }
+
+    #[test]
+    fn gable_follows_l_shaped_footprint() {
+        let boundary = Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 4.0),
+            Point2::new(4.0, 4.0),
+            Point2::new(4.0, 10.0),
+            Point2::new(0.0, 10.0),
+        ])
+        .unwrap();
+        let filled = Roof::gable(
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 10.0),
+            0.25,
+            30.0,
+            RidgeDirection::AlongX,
+        )
+        .unwrap()
+        .to_mesh()
+        .unwrap();
+
+        for ridge in [RidgeDirection::AlongX, RidgeDirection::AlongY] {
+            for roof_type in [RoofType::Gable, RoofType::Shed] {
+                let mut roof = Roof::new(boundary.clone(), 0.25).unwrap();
+                roof.set_type(roof_type, 30.0);
+                roof.ridge_direction = ridge;
+                let mesh = roof.to_mesh().unwrap();
+
+                // The mesh spans the L's extents without filling the notch
+                let bbox = mesh.bounding_box().unwrap();
+                assert!(bbox.min.x.abs() < 1e-9 && bbox.min.y.abs() < 1e-9);
+                assert!((bbox.max.x - 10.0).abs() < 1e-9 && (bbox.max.y - 10.0).abs() < 1e-9);
+                assert!(mesh
+                    .vertices
+                    .iter()
+                    .all(|v| v.x <= 4.0 + 1e-9 || v.y <= 4.0 + 1e-9));
+                assert!(mesh.volume() < filled.volume() * 0.75);
+                assert!(mesh.is_manifold(), "{:?} {:?}", roof_type, ridge);
+            }
+        }
+    }
+
+    #[test]
+    fn roof_from_open_walls() {
+        let walls = walls_through(&[(0.0, 0.0), (10.0, 0.0), (10.0, 8.0)]);
+        let refs: Vec<&Wall> = walls[..2].iter().collect();
+
+        assert!(matches!(
+            Roof::from_walls(&refs, 0.25, RoofType::Hip, 30.0),
+            Err(GeometryError::WallsNotEnclosed)
+        ));
     }
 
     #[test]
@@ -794,10 +1791,15 @@ mod tests {
         let bbox = roof.bounding_box().unwrap();
         assert!((bbox.min.x - (-0.5)).abs() < 1e-10);
         assert!((bbox.min.y - (-0.5)).abs() < 1e-10);
-        assert!((bbox.min.z - 5.0).abs() < 1e-10);
+        assert!((bbox.min.z - (5.0 - roof.slab_depth().unwrap())).abs() < 1e-10);
         assert!((bbox.max.x - 10.5).abs() < 1e-10);
         assert!((bbox.max.y - 8.5).abs() < 1e-10);
         assert!(bbox.max.z > 5.0); // Ridge is above base
+
+        // The slab hangs below the eaves, as meshed
+        let mesh_bbox = roof.to_mesh().unwrap().bounding_box().unwrap();
+        assert!((mesh_bbox.min.z - bbox.min.z).abs() < 1e-10);
+        assert!((mesh_bbox.max.z - bbox.max.z).abs() < 1e-10);
     }
 
     #[test]
@@ -823,4 +1825,157 @@ mod tests {
         .unwrap();
         assert!(pitched.surface_area() > 100.0); // Sloped surface is larger
     }
+
+    #[test]
+    fn roof_opening_cuts_flat_mesh() {
+        let mut roof =
+            Roof::rectangle(Point2::new(0.0, 0.0), Point2::new(10.0, 10.0), 0.3).unwrap();
+        roof.set_eave_overhang(0.5);
+        let before = roof.to_mesh().unwrap();
+
+        let opening = RoofOpening::new(Point2::new(5.0, 5.0), 2.0, 1.0, RoofOpeningType::Chimney);
+        roof.add_opening(opening).unwrap();
+
+        let mesh = roof.to_mesh().unwrap();
+        assert!(mesh.is_valid());
+        assert!((before.volume() - mesh.volume() - 2.0 * 0.3).abs() < 1e-9);
+        assert!(mesh.surface_area() < before.surface_area());
+        assert!((roof.surface_area() - 98.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn roof_opening_cuts_pitched_meshes() {
+        let (min, max) = (Point2::new(0.0, 0.0), Point2::new(10.0, 8.0));
+        let roofs = [
+            (
+                Roof::gable(min, max, 0.3, 30.0, RidgeDirection::AlongX).unwrap(),
+                (5.0, 2.0),
+            ),
+            (
+                Roof::gable(min, max, 0.3, 30.0, RidgeDirection::AlongY).unwrap(),
+                (2.0, 4.0),
+            ),
+            (Roof::hip(min, max, 0.3, 25.0).unwrap(), (5.0, 2.0)),
+            (
+                Roof::shed(min, max, 0.3, 15.0, RidgeDirection::AlongY).unwrap(),
+                (5.0, 2.0),
+            ),
+        ];
+
+        for (mut roof, (x, y)) in roofs {
+            let before = roof.to_mesh().unwrap();
+            let area = roof.surface_area();
+            let opening = RoofOpening::new(Point2::new(x, y), 1.0, 1.0, RoofOpeningType::Skylight);
+            roof.add_opening(opening).unwrap();
+
+            let mesh = roof.to_mesh().unwrap();
+            assert!(mesh.is_valid(), "{:?}", roof.roof_type);
+            assert!(mesh.is_manifold(), "{:?}", roof.roof_type);
+            assert!(mesh.volume() < before.volume());
+            assert!(
+                mesh.surface_area() < before.surface_area(),
+                "{:?}",
+                roof.roof_type
+            );
+            assert!(roof.surface_area() < area);
+
+            // Every vertex stays on (or below) the roof surface
+            for v in &mesh.vertices {
+                let z = roof.surface_elevation(Point2::new(v.x, v.y)).unwrap();
+                assert!(v.z <= z + 1e-9);
+            }
+        }
+
+        // On a gable the top and soffit lose the opening's area on the
+        // pitch, and the opening gains four vertical sides
+        let mut roof = Roof::gable(min, max, 0.3, 30.0, RidgeDirection::AlongX).unwrap();
+        let before = roof.to_mesh().unwrap().surface_area();
+        let opening = RoofOpening::new(Point2::new(5.0, 2.0), 1.0, 1.0, RoofOpeningType::Skylight);
+        roof.add_opening(opening).unwrap();
+        let pitch = (roof.top_elevation() - roof.base_elevation) / 4.0;
+        let depth = roof.slab_depth().unwrap();
+        let lost = before - roof.to_mesh().unwrap().surface_area();
+        assert!((lost - (2.0 * (1.0 + pitch * pitch).sqrt() - 4.0 * depth)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn roof_opening_validation() {
+        let (min, max) = (Point2::new(0.0, 0.0), Point2::new(10.0, 8.0));
+        let mut roof = Roof::gable(min, max, 0.3, 30.0, RidgeDirection::AlongX).unwrap();
+        roof.set_eave_overhang(0.5);
+        let opening =
+            |x, y| RoofOpening::new(Point2::new(x, y), 1.0, 1.0, RoofOpeningType::Generic);
+
+        // Across the ridge, into the overhang, or over another opening
+        assert_eq!(
+            roof.add_opening(opening(5.0, 4.0)),
+            Err(GeometryError::OpeningSpansRidge)
+        );
+        assert_eq!(
+            roof.add_opening(opening(9.8, 2.0)),
+            Err(GeometryError::InvalidRoofOpening(
+                "footprint must lie inside the roof boundary".to_string()
+            ))
+        );
+        roof.add_opening(opening(5.0, 2.0)).unwrap();
+        assert_eq!(
+            roof.add_opening(opening(5.5, 2.5)),
+            Err(GeometryError::OverlappingOpenings)
+        );
+        assert!(matches!(
+            roof.add_opening(RoofOpening::new(
+                Point2::new(2.0, 2.0),
+                0.0,
+                1.0,
+                RoofOpeningType::Generic
+            )),
+            Err(GeometryError::InvalidRoofOpening(_))
+        ));
+
+        // A hip opening must stay clear of the hip lines
+        let mut hip = Roof::hip(min, max, 0.3, 25.0).unwrap();
+        assert_eq!(
+            hip.add_opening(opening(2.0, 2.0)),
+            Err(GeometryError::OpeningSpansRidge)
+        );
+        hip.add_opening(opening(1.0, 3.0)).unwrap();
+        assert_eq!(hip.openings.len(), 1);
+
+        assert!(roof.remove_opening(roof.openings[0].id));
+        assert!(roof.openings.is_empty());
+    }
+
+    #[test]
+    fn roof_quarter_turn_swaps_ridge() {
+        let mut roof = Roof::gable(
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 6.0),
+            0.3,
+            30.0,
+            RidgeDirection::AlongX,
+        )
+        .unwrap();
+        let ridge = roof.ridge_height();
+
+        let opening = RoofOpening::new(Point2::new(5.0, 1.5), 2.0, 1.0, RoofOpeningType::Generic);
+        roof.add_opening(opening).unwrap();
+
+        roof.rotate_about(Point2::new(0.0, 0.0), std::f64::consts::FRAC_PI_2);
+        assert_eq!(roof.ridge_direction, RidgeDirection::AlongY);
+        let moved = &roof.openings[0];
+        assert!(moved.center.distance_to(&Point2::new(-1.5, 5.0)) < 1e-10);
+        assert!((moved.width - 1.0).abs() < 1e-10 && (moved.depth - 2.0).abs() < 1e-10);
+        assert!(roof.to_mesh().unwrap().is_valid());
+        assert!((roof.ridge_height() - ridge).abs() < 1e-10);
+        assert!((roof.footprint_area() - 60.0).abs() < 1e-10);
+
+        roof.rotate_about(Point2::new(0.0, 0.0), 0.1);
+        assert_eq!(roof.ridge_direction, RidgeDirection::AlongY);
+
+        // Mirroring across a diagonal also swaps the axes
+        let axis =
+            pensaer_math::Line2::from_points(Point2::new(0.0, 0.0), Point2::new(1.0, 1.0)).unwrap();
+        roof.mirror(&axis);
+        assert_eq!(roof.ridge_direction, RidgeDirection::AlongX);
+    }
 }
@@ -5,9 +5,18 @@ use uuid::Uuid;
 
 use pensaer_math::{BoundingBox3, Point2, Point3, Polygon2};
 
-use crate::element::{Element, ElementMetadata, ElementType};
+use crate::element::{
+    next_element_id, Element, ElementMetadata, ElementType, PlanTransform, TransformableElement,
+};
 use crate::error::{GeometryError, GeometryResult};
-use crate::mesh::TriangleMesh;
+use crate::mesh::{extrude_polygon, TriangleMesh};
+use crate::topology::{RoomId, TopologyGraph};
+
+/// Topology coordinates are in millimeters; elements use meters.
+const MM_TO_M: f64 = 0.001;
+
+/// Smallest floor area accepted for a room, in square meters.
+pub const MIN_ROOM_AREA: f64 = 0.01;
 
 /// A room element representing an enclosed space.
 ///
@@ -32,6 +41,13 @@ pub struct Room {
     pub height: f64,
     /// IDs of walls that form the boundary.
     pub bounding_walls: Vec<Uuid>,
+    /// Level this element is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
+    /// Whether the enclosing walls are gone. An unplaced room keeps its name,
+    /// number, and metadata until walls enclose it again.
+    #[serde(default)]
+    pub unplaced: bool,
     /// Metadata.
     pub metadata: ElementMetadata,
 }
@@ -43,22 +59,44 @@ impl Room {
         number: impl Into<String>,
         boundary: Polygon2,
         height: f64,
+    ) -> GeometryResult<Self> {
+        let mut room = Self::with_id(Uuid::nil(), name, number, boundary, height)?;
+        let mut params: Vec<f64> = room
+            .boundary
+            .vertices
+            .iter()
+            .flat_map(|v| [v.x, v.y])
+            .collect();
+        params.push(height);
+        room.id = next_element_id(ElementType::Room.name(), &[], &params);
+        Ok(room)
+    }
+
+    /// Create a room with a specific ID.
+    pub fn with_id(
+        id: Uuid,
+        name: impl Into<String>,
+        number: impl Into<String>,
+        boundary: Polygon2,
+        height: f64,
     ) -> GeometryResult<Self> {
         if height <= 0.0 {
-            return Err(GeometryError::NonPositiveHeight);
+            return Err(GeometryError::NonPositiveHeight { value: height });
         }
         boundary
             .validate()
             .map_err(|_| GeometryError::InsufficientVertices)?;
 
         Ok(Self {
-            id: Uuid::new_v4(),
+            id,
             name: name.into(),
             number: number.into(),
             boundary,
             base_elevation: 0.0,
             height,
             bounding_walls: Vec::new(),
+            level_id: None,
+            unplaced: false,
             metadata: ElementMetadata::new(),
         })
     }
@@ -72,12 +110,70 @@ impl Room {
         height: f64,
     ) -> GeometryResult<Self> {
         if min.x >= max.x || min.y >= max.y {
-            return Err(GeometryError::InvalidFloorBounds);
+            return Err(GeometryError::InvalidFloorBounds { min, max });
         }
         let boundary = Polygon2::rectangle(min, max);
         Self::new(name, number, boundary, height)
     }
 
+    /// Create a room from an arbitrary simple polygon.
+    ///
+    /// The boundary may be given in either winding and is stored
+    /// counter-clockwise. Self-intersecting boundaries and ones enclosing
+    /// less than [`MIN_ROOM_AREA`] are rejected.
+    pub fn from_polygon(
+        name: impl Into<String>,
+        number: impl Into<String>,
+        mut boundary: Polygon2,
+        height: f64,
+    ) -> GeometryResult<Self> {
+        boundary
+            .validate()
+            .map_err(|_| GeometryError::InvalidRoom("boundary is not a simple polygon".into()))?;
+        if boundary.area() < MIN_ROOM_AREA {
+            return Err(GeometryError::InvalidRoom(format!(
+                "area {} is below the minimum {}",
+                boundary.area(),
+                MIN_ROOM_AREA
+            )));
+        }
+        boundary.ensure_ccw();
+        Self::new(name, number, boundary, height)
+    }
+
+    /// Create a room from a region detected in a topology graph. Node
+    /// positions are converted from millimeters to meters.
+    pub fn from_topo_room(
+        graph: &TopologyGraph,
+        room_id: RoomId,
+        name: impl Into<String>,
+        number: impl Into<String>,
+        height: f64,
+    ) -> GeometryResult<Self> {
+        let room = graph
+            .get_room(room_id)
+            .ok_or_else(|| GeometryError::InvalidElementRef(room_id.to_string()))?;
+        if room.is_exterior {
+            return Err(GeometryError::InvalidRoom(
+                "exterior region cannot be a room".to_string(),
+            ));
+        }
+
+        let vertices = room
+            .boundary_nodes
+            .iter()
+            .map(|id| {
+                graph
+                    .get_node(*id)
+                    .map(|n| Point2::new(n.position[0] * MM_TO_M, n.position[1] * MM_TO_M))
+                    .ok_or_else(|| GeometryError::InvalidElementRef(id.to_string()))
+            })
+            .collect::<GeometryResult<Vec<_>>>()?;
+        let boundary = Polygon2::new(vertices).map_err(|_| GeometryError::InsufficientVertices)?;
+
+        Self::from_polygon(name, number, boundary, height)
+    }
+
     /// Set base elevation.
     pub fn set_elevation(&mut self, elevation: f64) {
         self.base_elevation = elevation;
@@ -103,6 +199,14 @@ impl Room {
         self.area() * self.height
     }
 
+    /// Floor polygon split into convex pieces, e.g. for navigation meshes.
+    ///
+    /// See [`Polygon2::decompose_convex`]; the pieces cover the floor
+    /// exactly.
+    pub fn convex_floor_pieces(&self) -> Vec<Polygon2> {
+        self.boundary.decompose_convex()
+    }
+
     /// Centroid of the room (useful for label placement).
     pub fn centroid(&self) -> Point3 {
         let c2 = self.boundary.centroid();
@@ -146,6 +250,14 @@ impl Element for Room {
         self.id
     }
 
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
     fn element_type(&self) -> ElementType {
         ElementType::Room
     }
@@ -163,55 +275,36 @@ impl Element for Room {
     }
 
     fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
-        // Room mesh is typically just the boundary extruded for visualization
-        // Similar to floor but represents the space, not the slab
-        let n = self.boundary.vertex_count();
-        if n < 3 {
-            return Err(GeometryError::InsufficientVertices);
-        }
-
-        let z0 = self.base_elevation;
-        let z1 = self.top_elevation();
-
-        // Create vertices: bottom ring + top ring
-        let mut vertices = Vec::with_capacity(n * 2);
-        for v in &self.boundary.vertices {
-            vertices.push(Point3::new(v.x, v.y, z0));
-        }
-        for v in &self.boundary.vertices {
-            vertices.push(Point3::new(v.x, v.y, z1));
-        }
-
-        let mut indices = Vec::new();
-
-        // Fan triangulation for top and bottom (works for convex polygons)
-        for i in 1..n - 1 {
-            // Bottom face
-            indices.push([0, (i + 1) as u32, i as u32]);
-            // Top face
-            let offset = n as u32;
-            indices.push([offset, offset + i as u32, offset + (i + 1) as u32]);
-        }
-
-        // Side faces
-        for i in 0..n {
-            let next = (i + 1) % n;
-            let i0 = i as u32;
-            let i1 = next as u32;
-            let i2 = (n + next) as u32;
-            let i3 = (n + i) as u32;
-
-            indices.push([i0, i1, i2]);
-            indices.push([i0, i2, i3]);
-        }
+        // Room mesh is the boundary extruded for visualization,
+        // like a floor but representing the space rather than the slab
+        let mut outline = self.boundary.clone();
+        outline.ensure_ccw();
+        extrude_polygon(&outline.vertices, self.height, self.base_elevation)
+    }
+}
 
-        Ok(TriangleMesh::from_vertices_indices(vertices, indices))
+impl TransformableElement for Room {
+    fn apply_transform(&mut self, transform: &PlanTransform) {
+        self.boundary = transform.apply_polygon(&self.boundary);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::topology::EdgeData;
+
+    fn l_shape() -> Polygon2 {
+        Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(6.0, 0.0),
+            Point2::new(6.0, 2.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(2.0, 5.0),
+            Point2::new(0.0, 5.0),
+        ])
+        .unwrap()
+    }
 
     #[test]
     fn room_creation() {
@@ -283,6 +376,26 @@ mod tests {
         assert!(!room.contains_point(&Point3::new(2.0, 2.0, 4.0)));
     }
 
+    #[test]
+    fn l_shaped_room_has_two_convex_pieces() {
+        let boundary = Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(6.0, 0.0),
+            Point2::new(6.0, 3.0),
+            Point2::new(2.5, 3.0),
+            Point2::new(2.5, 7.0),
+            Point2::new(0.0, 7.0),
+        ])
+        .unwrap();
+        let room = Room::from_polygon("Studio", "201", boundary, 2.7).unwrap();
+
+        let pieces = room.convex_floor_pieces();
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces.iter().all(Polygon2::is_convex));
+        let total: f64 = pieces.iter().map(Polygon2::area).sum();
+        assert!((total - room.area()).abs() < 1e-10);
+    }
+
     #[test]
     fn room_elevation() {
         let mut room = Room::rectangle(
@@ -375,4 +488,64 @@ mod tests {
         assert_eq!(bbox.max.y, 3.0);
         assert!((bbox.max.z - 2.8).abs() < 1e-10);
     }
+
+    #[test]
+    fn room_from_l_shaped_polygon() {
+        let room = Room::from_polygon("Lounge", "109", l_shape(), 2.5).unwrap();
+
+        assert!((room.area() - 18.0).abs() < 1e-10);
+        assert!((room.volume() - 45.0).abs() < 1e-10);
+        let centroid = room.centroid();
+        assert!((centroid.x - 42.0 / 18.0).abs() < 1e-10);
+        assert!((centroid.y - 33.0 / 18.0).abs() < 1e-10);
+
+        // The notch is outside, both arms inside
+        assert!(!room.contains_point_2d(&Point2::new(4.0, 4.0)));
+        assert!(room.contains_point_2d(&Point2::new(1.0, 4.0)));
+        assert!(room.contains_point_2d(&Point2::new(5.0, 1.0)));
+
+        let mesh = room.to_mesh().unwrap();
+        assert!(mesh.is_valid());
+        assert!(!mesh.has_degenerate_triangles());
+        assert!((mesh.volume() - 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn room_from_polygon_normalizes_winding() {
+        let room = Room::from_polygon("Lounge", "109", l_shape().reversed(), 2.5).unwrap();
+        assert!(!room.boundary.is_clockwise());
+        assert!((room.area() - 18.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn room_from_polygon_rejects_tiny_area() {
+        let sliver = Polygon2::rectangle(Point2::new(0.0, 0.0), Point2::new(1.0, 0.005));
+        assert!(matches!(
+            Room::from_polygon("Shaft", "110", sliver, 2.5),
+            Err(GeometryError::InvalidRoom(_))
+        ));
+    }
+
+    #[test]
+    fn room_from_topo_room_uses_meters() {
+        let mut graph = TopologyGraph::new();
+        let corners = [[0.0, 0.0], [4000.0, 0.0], [4000.0, 3000.0], [0.0, 3000.0]];
+        for i in 0..4 {
+            graph.add_edge(
+                corners[i],
+                corners[(i + 1) % 4],
+                EdgeData::wall(200.0, 2700.0),
+            );
+        }
+        graph.rebuild_rooms();
+        let interior = graph.rooms().find(|r| !r.is_exterior).unwrap().id;
+        let exterior = graph.rooms().find(|r| r.is_exterior).unwrap().id;
+
+        let room = Room::from_topo_room(&graph, interior, "Office", "111", 2.7).unwrap();
+        assert!((room.area() - 12.0).abs() < 1e-10);
+        assert!(matches!(
+            Room::from_topo_room(&graph, exterior, "Outside", "0", 2.7),
+            Err(GeometryError::InvalidRoom(_))
+        ));
+    }
 }
@@ -0,0 +1,230 @@
+//! Skylight element hosted in a roof opening.
+//!
+//! A skylight is meshed as a glazed curb: a solid lining its roof opening
+//! through the roof thickness and standing above the roof surface. On a
+//! pitched roof the curb follows the slope, so
+//! [`Skylight::to_mesh_in_host`] needs the host roof; the plain
+//! [`Element::to_mesh`] gives the same solid standing on the ground plane.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use pensaer_math::{BoundingBox3, Point2, Point3};
+
+use crate::element::{next_element_id, Element, ElementMetadata, ElementType};
+use crate::error::{GeometryError, GeometryResult};
+use crate::mesh::TriangleMesh;
+
+use super::roof::{Roof, RoofOpening, RoofOpeningType};
+
+/// Default height of a skylight curb above the roof surface (m).
+pub const DEFAULT_UPSTAND_HEIGHT: f64 = 0.15;
+
+/// Roof thickness used when a skylight is meshed without its host (m).
+const UNHOSTED_THICKNESS: f64 = 0.1;
+
+/// A skylight hosted in a roof.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Skylight {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// ID of the host roof.
+    pub host_roof_id: Uuid,
+    /// Plan centre of the skylight.
+    pub center: Point2,
+    /// Extent along X.
+    pub width: f64,
+    /// Extent along Y.
+    pub depth: f64,
+    /// Height of the curb above the roof surface.
+    pub upstand_height: f64,
+    /// Level this element is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
+    /// Metadata.
+    pub metadata: ElementMetadata,
+}
+
+impl Skylight {
+    /// Create a new skylight.
+    pub fn new(host_roof_id: Uuid, center: Point2, width: f64, depth: f64) -> GeometryResult<Self> {
+        if width <= 0.0 || depth <= 0.0 {
+            return Err(GeometryError::NonPositiveThickness {
+                value: width.min(depth),
+            });
+        }
+
+        Ok(Self {
+            id: next_element_id(
+                "Skylight",
+                &[host_roof_id],
+                &[center.x, center.y, width, depth],
+            ),
+            host_roof_id,
+            center,
+            width,
+            depth,
+            upstand_height: DEFAULT_UPSTAND_HEIGHT,
+            level_id: None,
+            metadata: ElementMetadata::new(),
+        })
+    }
+
+    /// Set the curb height above the roof surface.
+    pub fn set_upstand_height(&mut self, height: f64) -> GeometryResult<()> {
+        if height <= 0.0 {
+            return Err(GeometryError::NonPositiveHeight { value: height });
+        }
+        self.upstand_height = height;
+        Ok(())
+    }
+
+    /// A roof opening sized for this skylight and linked to it through
+    /// [`RoofOpening::hosted_element_id`].
+    pub fn roof_opening(&self) -> RoofOpening {
+        let mut opening = RoofOpening::new(
+            self.center,
+            self.width,
+            self.depth,
+            RoofOpeningType::Skylight,
+        );
+        opening.hosted_element_id = Some(self.id);
+        opening
+    }
+
+    /// World-space mesh of the skylight in its host roof's opening, lining
+    /// the roof thickness and standing proud of the slope.
+    pub fn to_mesh_in_host(&self, host: &Roof) -> GeometryResult<TriangleMesh> {
+        if host.id != self.host_roof_id {
+            return Err(GeometryError::InvalidElementRef(format!(
+                "roof {} is not the host {}",
+                host.id, self.host_roof_id
+            )));
+        }
+        let mut bottom = Vec::with_capacity(4);
+        let mut depth = 0.0;
+        for corner in self.roof_opening().footprint() {
+            let z = host.soffit_elevation(corner)?;
+            // Surface and soffit are parallel across the opening's face
+            depth = host.surface_elevation(corner)? - z;
+            bottom.push(Point3::new(corner.x, corner.y, z));
+        }
+        Ok(self.curb_mesh(&bottom, depth + self.upstand_height))
+    }
+
+    /// World-space bounding box of the skylight in its host roof.
+    pub fn bounding_box_in_host(&self, host: &Roof) -> GeometryResult<BoundingBox3> {
+        self.to_mesh_in_host(host)?
+            .bounding_box()
+            .ok_or(GeometryError::InsufficientVertices)
+    }
+
+    /// A closed prism rising `height` above a counter-clockwise quad.
+    fn curb_mesh(&self, bottom: &[Point3], height: f64) -> TriangleMesh {
+        let mut vertices = bottom.to_vec();
+        vertices.extend(bottom.iter().map(|p| Point3::new(p.x, p.y, p.z + height)));
+        let mut indices = vec![
+            // Bottom
+            [0, 2, 1],
+            [0, 3, 2],
+            // Top (glazing)
+            [4, 5, 6],
+            [4, 6, 7],
+        ];
+        for i in 0..4 {
+            let j = (i + 1) % 4;
+            indices.push([i, j, j + 4]);
+            indices.push([i, j + 4, i + 4]);
+        }
+        TriangleMesh::from_vertices_indices(vertices, indices)
+    }
+}
+
+impl Element for Skylight {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
+    fn element_type(&self) -> ElementType {
+        // IFC models skylights as windows with a SKYLIGHT predefined type
+        ElementType::Window
+    }
+
+    fn bounding_box(&self) -> GeometryResult<BoundingBox3> {
+        // Local frame; see `bounding_box_in_host` for the placed box
+        self.to_mesh()?
+            .bounding_box()
+            .ok_or(GeometryError::InsufficientVertices)
+    }
+
+    fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
+        // Without the host, stand the curb on a flat roof at the origin
+        let bottom: Vec<Point3> = self
+            .roof_opening()
+            .footprint()
+            .iter()
+            .map(|c| Point3::new(c.x, c.y, -UNHOSTED_THICKNESS))
+            .collect();
+        Ok(self.curb_mesh(&bottom, UNHOSTED_THICKNESS + self.upstand_height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::RidgeDirection;
+
+    #[test]
+    fn skylight_follows_the_slope() {
+        let mut roof = Roof::gable(
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 8.0),
+            0.3,
+            30.0,
+            RidgeDirection::AlongX,
+        )
+        .unwrap();
+        let skylight = Skylight::new(roof.id, Point2::new(5.0, 2.0), 1.0, 1.2).unwrap();
+        roof.add_opening(skylight.roof_opening()).unwrap();
+
+        assert_eq!(roof.openings[0].hosted_element_id, Some(skylight.id));
+        assert_eq!(roof.openings[0].opening_type, RoofOpeningType::Skylight);
+
+        let mesh = skylight.to_mesh_in_host(&roof).unwrap();
+        assert!(mesh.is_valid());
+        assert!(mesh.is_manifold());
+        assert_eq!(mesh.triangle_count(), 12);
+
+        // The curb's top edge is parallel to the roof, upstand above it
+        let top_south = roof.surface_elevation(Point2::new(4.5, 1.4)).unwrap();
+        let top_north = roof.surface_elevation(Point2::new(4.5, 2.6)).unwrap();
+        assert!(top_north > top_south);
+        assert!((mesh.vertices[4].z - (top_south + DEFAULT_UPSTAND_HEIGHT)).abs() < 1e-9);
+        assert!((mesh.vertices[7].z - (top_north + DEFAULT_UPSTAND_HEIGHT)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn skylight_needs_its_host() {
+        let roof = Roof::rectangle(Point2::new(0.0, 0.0), Point2::new(10.0, 10.0), 0.3).unwrap();
+        let skylight = Skylight::new(Uuid::new_v4(), Point2::new(5.0, 5.0), 1.0, 1.0).unwrap();
+
+        assert!(matches!(
+            skylight.to_mesh_in_host(&roof),
+            Err(GeometryError::InvalidElementRef(_))
+        ));
+        assert!(Skylight::new(roof.id, Point2::new(5.0, 5.0), 0.0, 1.0).is_err());
+
+        // Unhosted, it stands on the ground plane
+        let bbox = skylight.bounding_box().unwrap();
+        assert!((bbox.max.z - DEFAULT_UPSTAND_HEIGHT).abs() < 1e-9);
+        assert_eq!(skylight.element_type(), ElementType::Window);
+    }
+}
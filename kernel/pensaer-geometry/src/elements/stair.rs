@@ -0,0 +1,456 @@
+//! Stair element for BIM modeling.
+//!
+//! A straight stair climbs from its start point along a plan direction.
+//! The total rise is divided into equal risers; each tread (and any landing)
+//! is a solid block one riser high, so the flight reads as a stepped solid.
+//! Optional stringers run along both sides of each flight.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use pensaer_math::{BoundingBox3, Point2, Point3, Vector2};
+
+use crate::element::{next_element_id, Element, ElementMetadata, ElementType};
+use crate::error::{GeometryError, GeometryResult};
+use crate::mesh::TriangleMesh;
+
+use super::railing::{prism, RailProfile};
+
+/// Default maximum riser height (190mm).
+pub const DEFAULT_MAX_RISER: f64 = 0.19;
+
+/// Default minimum tread depth (250mm).
+pub const DEFAULT_MIN_TREAD: f64 = 0.25;
+
+/// Tolerance for treating a riser height as unchanged.
+const RISER_TOLERANCE: f64 = 1e-6;
+
+/// A landing replacing one tread of the flight.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StairLanding {
+    /// Step number (1-based) whose tread becomes the landing.
+    pub at_step: usize,
+    /// Landing depth along the walking direction.
+    pub depth: f64,
+}
+
+/// Limits checked by [`Stair::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StairRules {
+    /// Maximum riser height.
+    pub max_riser: f64,
+    /// Minimum tread depth.
+    pub min_tread: f64,
+}
+
+impl Default for StairRules {
+    fn default() -> Self {
+        Self {
+            max_riser: DEFAULT_MAX_RISER,
+            min_tread: DEFAULT_MIN_TREAD,
+        }
+    }
+}
+
+/// A stair that breaks a [`StairRules`] limit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StairFinding {
+    /// Risers are higher than allowed.
+    RiserTooHigh {
+        /// Riser height.
+        riser: f64,
+        /// Allowed maximum.
+        maximum: f64,
+    },
+    /// Treads are shallower than allowed.
+    TreadTooShallow {
+        /// Tread depth.
+        tread: f64,
+        /// Required minimum.
+        minimum: f64,
+    },
+}
+
+/// A straight stair element in the BIM model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stair {
+    /// Unique identifier.
+    pub id: Uuid,
+    /// Foot of the first riser, at the middle of the stair width.
+    pub start: Point2,
+    /// Walking direction (normalized).
+    pub direction: Vector2,
+    /// Total height climbed.
+    pub total_rise: f64,
+    /// Riser height, adjusted so the risers divide the total rise evenly.
+    pub riser_height: f64,
+    /// Riser height that was asked for.
+    pub requested_riser_height: f64,
+    /// Number of risers.
+    pub riser_count: usize,
+    /// Tread depth (going).
+    pub tread_depth: f64,
+    /// Clear width of the flight.
+    pub width: f64,
+    /// Landings, ordered by step.
+    pub landings: Vec<StairLanding>,
+    /// Stringer cross-section, or `None` for no stringers.
+    pub stringers: Option<RailProfile>,
+    /// Level this element is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
+    /// Metadata.
+    pub metadata: ElementMetadata,
+}
+
+impl Stair {
+    /// Create a straight stair.
+    ///
+    /// If `riser_height` does not divide `total_rise` into a whole number of
+    /// risers, the nearest riser count is used and the riser height adjusted
+    /// to match; the original value is kept in `requested_riser_height`.
+    pub fn straight(
+        start: Point2,
+        direction: Vector2,
+        total_rise: f64,
+        tread_depth: f64,
+        riser_height: f64,
+        width: f64,
+    ) -> GeometryResult<Self> {
+        if total_rise <= 0.0 || riser_height <= 0.0 {
+            return Err(GeometryError::NonPositiveHeight {
+                value: total_rise.min(riser_height),
+            });
+        }
+        if tread_depth <= 0.0 {
+            return Err(GeometryError::InvalidStair(
+                "tread depth must be positive".to_string(),
+            ));
+        }
+        if width <= 0.0 {
+            return Err(GeometryError::InvalidStair(
+                "width must be positive".to_string(),
+            ));
+        }
+        let direction = direction
+            .normalize()
+            .map_err(|_| GeometryError::InvalidStair("direction has zero length".to_string()))?;
+
+        let riser_count = (total_rise / riser_height).round() as usize;
+        if riser_count < 2 {
+            return Err(GeometryError::InvalidStair(
+                "stair needs at least two risers".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            id: next_element_id(
+                ElementType::Stair.name(),
+                &[],
+                &[
+                    start.x,
+                    start.y,
+                    direction.x,
+                    direction.y,
+                    total_rise,
+                    tread_depth,
+                    riser_height,
+                    width,
+                ],
+            ),
+            start,
+            direction,
+            total_rise,
+            riser_height: total_rise / riser_count as f64,
+            requested_riser_height: riser_height,
+            riser_count,
+            tread_depth,
+            width,
+            landings: Vec::new(),
+            stringers: None,
+            level_id: None,
+            metadata: ElementMetadata::new(),
+        })
+    }
+
+    /// Replace the tread at `at_step` (1-based) with a landing.
+    ///
+    /// The landing must be at least as deep as a tread and cannot be the
+    /// arrival at the top, which is the upper floor.
+    pub fn with_landing(mut self, at_step: usize, depth: f64) -> GeometryResult<Self> {
+        if at_step == 0 || at_step >= self.riser_count {
+            return Err(GeometryError::InvalidStair(format!(
+                "landing step {} outside 1..{}",
+                at_step,
+                self.riser_count - 1
+            )));
+        }
+        if depth < self.tread_depth {
+            return Err(GeometryError::InvalidStair(
+                "landing is shallower than a tread".to_string(),
+            ));
+        }
+
+        self.landings.retain(|l| l.at_step != at_step);
+        self.landings.push(StairLanding { at_step, depth });
+        self.landings.sort_by_key(|l| l.at_step);
+        Ok(self)
+    }
+
+    /// Add stringers with the given cross-section along both sides.
+    pub fn with_stringers(mut self, profile: RailProfile) -> Self {
+        self.stringers = Some(profile);
+        self
+    }
+
+    /// Whether the requested riser height had to be adjusted.
+    pub fn riser_adjusted(&self) -> bool {
+        (self.riser_height - self.requested_riser_height).abs() > RISER_TOLERANCE
+    }
+
+    /// Number of treads, counting landings. The last riser arrives at the
+    /// upper floor, so there is one tread fewer than risers.
+    pub fn tread_count(&self) -> usize {
+        self.riser_count - 1
+    }
+
+    /// Depth of the tread at a step (1-based), which may be a landing.
+    pub fn step_depth(&self, step: usize) -> f64 {
+        self.landings
+            .iter()
+            .find(|l| l.at_step == step)
+            .map_or(self.tread_depth, |l| l.depth)
+    }
+
+    /// Horizontal length from the first riser to the last.
+    pub fn run_length(&self) -> f64 {
+        (1..=self.tread_count()).map(|s| self.step_depth(s)).sum()
+    }
+
+    /// Check the stair against riser and tread limits.
+    pub fn validate(&self, rules: &StairRules) -> Vec<StairFinding> {
+        let mut findings = Vec::new();
+        if self.riser_height > rules.max_riser + 1e-9 {
+            findings.push(StairFinding::RiserTooHigh {
+                riser: self.riser_height,
+                maximum: rules.max_riser,
+            });
+        }
+        if self.tread_depth < rules.min_tread - 1e-9 {
+            findings.push(StairFinding::TreadTooShallow {
+                tread: self.tread_depth,
+                minimum: rules.min_tread,
+            });
+        }
+        findings
+    }
+
+    /// Plan point `along` the walking line and `across` to its left.
+    fn plan_point(&self, along: f64, across: f64, z: f64) -> Point3 {
+        let p = self.start + self.direction * along + self.direction.perp() * across;
+        Point3::new(p.x, p.y, z)
+    }
+
+    /// Block from `along` to `along + depth`, spanning `left..right` across.
+    fn block(&self, along: f64, depth: f64, right: f64, left: f64, z: f64) -> [Point3; 4] {
+        [
+            self.plan_point(along, right, z),
+            self.plan_point(along + depth, right, z),
+            self.plan_point(along + depth, left, z),
+            self.plan_point(along, left, z),
+        ]
+    }
+
+    /// Flights between landings as (first step, last step) pairs.
+    fn flights(&self) -> Vec<(usize, usize)> {
+        let mut flights = Vec::new();
+        let mut first = 1;
+        for landing in &self.landings {
+            if landing.at_step > first {
+                flights.push((first, landing.at_step - 1));
+            }
+            first = landing.at_step + 1;
+        }
+        if first <= self.tread_count() {
+            flights.push((first, self.tread_count()));
+        }
+        flights
+    }
+
+    /// Distance along the walking line to the front of a step's tread.
+    fn step_start(&self, step: usize) -> f64 {
+        (1..step).map(|s| self.step_depth(s)).sum()
+    }
+}
+
+impl Element for Stair {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
+    fn element_type(&self) -> ElementType {
+        ElementType::Stair
+    }
+
+    fn bounding_box(&self) -> GeometryResult<BoundingBox3> {
+        self.to_mesh()?
+            .bounding_box()
+            .ok_or_else(|| GeometryError::InvalidStair("stair has no treads".to_string()))
+    }
+
+    fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
+        let half = self.width / 2.0;
+        let h = self.riser_height;
+
+        let mut mesh = TriangleMesh::new();
+        let mut along = 0.0;
+        for step in 1..=self.tread_count() {
+            let depth = self.step_depth(step);
+            let base = (step - 1) as f64 * h;
+            mesh.merge(&prism(self.block(along, depth, -half, half, base), h));
+            along += depth;
+        }
+
+        if let Some(profile) = self.stringers {
+            // Each stringer sits under the line from the foot of the flight's
+            // first block to the top back corner of its last block
+            for (first, last) in self.flights() {
+                let foot = self.step_start(first);
+                let head = self.step_start(last) + self.step_depth(last);
+                let z0 = (first - 1) as f64 * h;
+                let z1 = last as f64 * h;
+                for (right, left) in [(-half - profile.width, -half), (half, half + profile.width)]
+                {
+                    mesh.merge(&prism(
+                        [
+                            self.plan_point(foot, right, z0),
+                            self.plan_point(head, right, z1),
+                            self.plan_point(head, left, z1),
+                            self.plan_point(foot, left, z0),
+                        ],
+                        profile.depth,
+                    ));
+                }
+            }
+        }
+
+        if mesh.triangle_count() == 0 {
+            return Err(GeometryError::InvalidStair(
+                "stair has no treads".to_string(),
+            ));
+        }
+        Ok(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn three_meter_stair(riser: f64) -> Stair {
+        Stair::straight(
+            Point2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            3.0,
+            0.28,
+            riser,
+            1.0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn exact_riser_count_is_kept() {
+        let stair = three_meter_stair(0.1875);
+        assert_eq!(stair.riser_count, 16);
+        assert_eq!(stair.tread_count(), 15);
+        assert!(!stair.riser_adjusted());
+        assert!((stair.run_length() - 15.0 * 0.28).abs() < 1e-10);
+    }
+
+    #[test]
+    fn inexact_riser_height_is_adjusted() {
+        let stair = three_meter_stair(0.18);
+        assert_eq!(stair.riser_count, 17);
+        assert!(stair.riser_adjusted());
+        assert!((stair.requested_riser_height - 0.18).abs() < 1e-12);
+        assert!((stair.riser_height * 17.0 - 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn three_meter_stair_meshes_as_solid() {
+        let stair = three_meter_stair(0.18);
+        let mesh = stair.to_mesh().unwrap();
+
+        assert!(mesh.is_valid());
+        assert!(!mesh.has_degenerate_triangles());
+        assert_eq!(mesh.triangle_count(), 12 * stair.tread_count());
+
+        // Blocks are closed, so the signed volume adds up
+        let expected = stair.tread_count() as f64 * 0.28 * 1.0 * stair.riser_height;
+        assert!((mesh.volume() - expected).abs() < 1e-9);
+
+        let bbox = stair.bounding_box().unwrap();
+        assert!((bbox.max.x - stair.run_length()).abs() < 1e-10);
+        assert!((bbox.max.z - 16.0 * stair.riser_height).abs() < 1e-10);
+        assert!((bbox.min.y + 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn landing_lengthens_run_and_splits_stringers() {
+        let stair = three_meter_stair(0.1875)
+            .with_landing(8, 1.0)
+            .unwrap()
+            .with_stringers(RailProfile::new(0.05, 0.25));
+
+        assert!((stair.run_length() - (14.0 * 0.28 + 1.0)).abs() < 1e-10);
+        assert_eq!(stair.flights(), vec![(1, 7), (9, 15)]);
+
+        let mesh = stair.to_mesh().unwrap();
+        assert!(mesh.is_valid());
+        // 15 blocks plus two stringers per flight
+        assert_eq!(mesh.triangle_count(), 12 * (15 + 4));
+    }
+
+    #[test]
+    fn landing_must_be_inside_flight() {
+        assert!(matches!(
+            three_meter_stair(0.1875).with_landing(16, 1.0),
+            Err(GeometryError::InvalidStair(_))
+        ));
+        assert!(matches!(
+            three_meter_stair(0.1875).with_landing(5, 0.1),
+            Err(GeometryError::InvalidStair(_))
+        ));
+    }
+
+    #[test]
+    fn validate_reports_steep_stairs() {
+        let steep = Stair::straight(
+            Point2::new(0.0, 0.0),
+            Vector2::new(0.0, 2.0),
+            3.0,
+            0.22,
+            0.2,
+            0.9,
+        )
+        .unwrap();
+
+        let findings = steep.validate(&StairRules::default());
+        assert_eq!(findings.len(), 2);
+        assert!(matches!(findings[0], StairFinding::RiserTooHigh { .. }));
+        assert!(matches!(findings[1], StairFinding::TreadTooShallow { .. }));
+
+        assert!(three_meter_stair(0.18)
+            .validate(&StairRules::default())
+            .is_empty());
+    }
+}
@@ -3,11 +3,19 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use pensaer_math::{BoundingBox3, Point2, Point3, Vector2};
+use pensaer_math::{BoundingBox3, Line2, LineSegment2, Point2, Point3, Transform3, Vector2};
 
-use crate::element::{Element, ElementMetadata, ElementType};
+use crate::constants::EPSILON;
+use crate::element::{
+    content_id, next_element_id, Element, ElementMetadata, ElementType, PlanTransform,
+    TransformableElement,
+};
 use crate::error::{GeometryError, GeometryResult};
-use crate::mesh::TriangleMesh;
+use crate::fixup::split_crossings;
+use crate::joins::WallEnd;
+use crate::mesh::extrude::extrude_elevation_with_openings;
+use crate::mesh::{extrude_polygon, triangulate_polygon, TriangleMesh};
+use crate::topology::{EdgeData, TopologyGraph};
 
 /// Wall baseline (centerline) definition.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -61,7 +69,7 @@ pub enum WallType {
 }
 
 /// An opening in a wall (for doors, windows, or generic openings).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WallOpening {
     /// Unique identifier.
     pub id: Uuid,
@@ -89,7 +97,11 @@ impl WallOpening {
         opening_type: OpeningType,
     ) -> Self {
         Self {
-            id: Uuid::new_v4(),
+            id: next_element_id(
+                ElementType::Opening.name(),
+                &[],
+                &[offset_along_wall, base_height, width, height],
+            ),
             offset_along_wall,
             base_height,
             width,
@@ -109,6 +121,15 @@ impl WallOpening {
         self.offset_along_wall + self.width / 2.0
     }
 
+    /// The error for this opening not fitting a wall of `wall_length`.
+    pub(crate) fn out_of_bounds(&self, wall_length: f64) -> GeometryError {
+        GeometryError::OpeningOutOfBounds {
+            offset: self.offset_along_wall,
+            width: self.width,
+            wall_length,
+        }
+    }
+
     /// Top height of opening.
     pub fn top_height(&self) -> f64 {
         self.base_height + self.height
@@ -126,6 +147,80 @@ pub enum OpeningType {
     Generic,
 }
 
+/// How [`Wall::split_at_with_policy`] treats an opening that straddles the split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SplitPolicy {
+    /// Refuse to split through an opening.
+    #[default]
+    Reject,
+    /// Slide the opening fully onto the half that contains its center.
+    ShiftToCenterSide,
+}
+
+/// Metadata property recording the wall a split half came from.
+pub const SPLIT_PARENT_PROPERTY: &str = "parent_id";
+
+/// Side of a wall's baseline, looking from start to end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OffsetSide {
+    /// Along the wall normal.
+    #[default]
+    Left,
+    /// Against the wall normal.
+    Right,
+}
+
+impl OffsetSide {
+    fn sign(self) -> f64 {
+        match self {
+            OffsetSide::Left => 1.0,
+            OffsetSide::Right => -1.0,
+        }
+    }
+}
+
+/// Shape of a wall's top edge along its baseline.
+///
+/// Heights are measured from the wall base, like [`Wall::height`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TopProfile {
+    /// Top sloping straight from one end to the other.
+    Sloped {
+        /// Height at the wall start.
+        start_height: f64,
+        /// Height at the wall end.
+        end_height: f64,
+    },
+    /// Top rising from [`Wall::height`] at both ends to a single peak.
+    Gable {
+        /// Distance from the wall start to the peak.
+        peak_offset: f64,
+        /// Height at the peak.
+        peak_height: f64,
+    },
+}
+
+/// Shape of a free wall end, one not joined to another wall.
+///
+/// Named like line caps: a flush end stops at the baseline end, while
+/// square and round ends reach half the thickness past it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WallEndCap {
+    /// Cut square at the baseline end.
+    #[default]
+    Flush,
+    /// Cut square half the thickness past the baseline end.
+    Square,
+    /// A half-disk as wide as the wall round the baseline end.
+    Round {
+        /// Straight segments approximating the half circle (at least 2).
+        segments: u32,
+    },
+}
+
+/// Endpoint tolerance when checking that walls form a chain.
+const CHAIN_TOLERANCE: f64 = 1e-3;
+
 /// A wall element in the BIM model.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Wall {
@@ -143,6 +238,18 @@ pub struct Wall {
     pub wall_type: WallType,
     /// Openings in this wall.
     pub openings: Vec<WallOpening>,
+    /// Non-level top edge; `None` keeps the top flat at `height`.
+    #[serde(default)]
+    pub top_profile: Option<TopProfile>,
+    /// Shape of the baseline start where it is not joined.
+    #[serde(default)]
+    pub start_cap: WallEndCap,
+    /// Shape of the baseline end where it is not joined.
+    #[serde(default)]
+    pub end_cap: WallEndCap,
+    /// Level this element is placed on.
+    #[serde(default)]
+    pub level_id: Option<Uuid>,
     /// Metadata.
     pub metadata: ElementMetadata,
 }
@@ -150,11 +257,44 @@ pub struct Wall {
 impl Wall {
     /// Create a new wall.
     pub fn new(start: Point2, end: Point2, height: f64, thickness: f64) -> GeometryResult<Self> {
+        let mut wall = Self::with_id(Uuid::nil(), start, end, height, thickness)?;
+        wall.id = next_element_id(
+            ElementType::Wall.name(),
+            &[],
+            &[start.x, start.y, end.x, end.y, height, thickness],
+        );
+        Ok(wall)
+    }
+
+    /// Create a wall whose ID is derived from its parameters.
+    ///
+    /// Walls with the same endpoints, height, thickness, and `seed` get the
+    /// same ID (see [`content_id`]).
+    pub fn new_with_seed(
+        start: Point2,
+        end: Point2,
+        height: f64,
+        thickness: f64,
+        seed: u64,
+    ) -> GeometryResult<Self> {
+        let params = [start.x, start.y, end.x, end.y, height, thickness];
+        let id = content_id(ElementType::Wall, &params, seed);
+        Self::with_id(id, start, end, height, thickness)
+    }
+
+    /// Create a wall with a specific ID.
+    pub fn with_id(
+        id: Uuid,
+        start: Point2,
+        end: Point2,
+        height: f64,
+        thickness: f64,
+    ) -> GeometryResult<Self> {
         if height <= 0.0 {
-            return Err(GeometryError::NonPositiveHeight);
+            return Err(GeometryError::NonPositiveHeight { value: height });
         }
         if thickness <= 0.0 {
-            return Err(GeometryError::NonPositiveThickness);
+            return Err(GeometryError::NonPositiveThickness { value: thickness });
         }
 
         let baseline = WallBaseline::new(start, end);
@@ -163,30 +303,21 @@ impl Wall {
         }
 
         Ok(Self {
-            id: Uuid::new_v4(),
+            id,
             baseline,
             height,
             thickness,
             base_offset: 0.0,
             wall_type: WallType::default(),
             openings: Vec::new(),
+            top_profile: None,
+            start_cap: WallEndCap::Flush,
+            end_cap: WallEndCap::Flush,
+            level_id: None,
             metadata: ElementMetadata::new(),
         })
     }
 
-    /// Create a wall with a specific ID.
-    pub fn with_id(
-        id: Uuid,
-        start: Point2,
-        end: Point2,
-        height: f64,
-        thickness: f64,
-    ) -> GeometryResult<Self> {
-        let mut wall = Self::new(start, end, height, thickness)?;
-        wall.id = id;
-        Ok(wall)
-    }
-
     /// Wall length.
     pub fn length(&self) -> f64 {
         self.baseline.length()
@@ -204,23 +335,37 @@ impl Wall {
 
     /// Add an opening to the wall.
     pub fn add_opening(&mut self, opening: WallOpening) -> GeometryResult<()> {
-        // Validate opening bounds
-        let wall_length = self.length();
-        if opening.start_offset() < 0.0 || opening.end_offset() > wall_length {
-            return Err(GeometryError::OpeningOutOfBounds);
-        }
-        if opening.base_height < 0.0 || opening.top_height() > self.height {
-            return Err(GeometryError::OpeningOutOfBounds);
+        self.check_opening(&opening, None)?;
+        self.openings.push(opening);
+        Ok(())
+    }
+
+    /// Replace the opening with the same ID, e.g. to resize it.
+    ///
+    /// The new opening is validated like [`add_opening`](Self::add_opening),
+    /// ignoring the opening it replaces. Returns false if the wall has no
+    /// opening with that ID.
+    pub fn replace_opening(&mut self, opening: WallOpening) -> GeometryResult<bool> {
+        let Some(pos) = self.openings.iter().position(|o| o.id == opening.id) else {
+            return Ok(false);
+        };
+        self.check_opening(&opening, Some(opening.id))?;
+        self.openings[pos] = opening;
+        Ok(true)
+    }
+
+    /// Check an opening fits in the wall and clears the other openings.
+    fn check_opening(&self, opening: &WallOpening, replacing: Option<Uuid>) -> GeometryResult<()> {
+        if !self.opening_in_bounds(opening) {
+            return Err(opening.out_of_bounds(self.length()));
         }
 
         // Check for overlaps with existing openings
         for existing in &self.openings {
-            if self.openings_overlap(&opening, existing) {
+            if Some(existing.id) != replacing && self.openings_overlap(opening, existing) {
                 return Err(GeometryError::OverlappingOpenings);
             }
         }
-
-        self.openings.push(opening);
         Ok(())
     }
 
@@ -234,8 +379,127 @@ impl Wall {
         }
     }
 
+    /// Set a sloped or gable top, or `None` for a flat top at `height`.
+    ///
+    /// Fails if a height is not positive, a gable peak is not strictly
+    /// inside the wall or below its ends, or an existing opening would
+    /// poke through the new top.
+    pub fn set_top_profile(&mut self, profile: Option<TopProfile>) -> GeometryResult<()> {
+        match profile {
+            Some(TopProfile::Sloped {
+                start_height,
+                end_height,
+            }) if start_height <= 0.0 || end_height <= 0.0 => {
+                return Err(GeometryError::NonPositiveHeight {
+                    value: start_height.min(end_height),
+                });
+            }
+            Some(TopProfile::Gable {
+                peak_offset,
+                peak_height,
+            }) => {
+                if peak_offset <= 0.0 || peak_offset >= self.length() {
+                    return Err(GeometryError::InvalidTopProfile(format!(
+                        "peak offset {} outside wall length {}",
+                        peak_offset,
+                        self.length()
+                    )));
+                }
+                if peak_height < self.height {
+                    return Err(GeometryError::InvalidTopProfile(format!(
+                        "peak height {} below end height {}",
+                        peak_height, self.height
+                    )));
+                }
+            }
+            _ => {}
+        }
+
+        let previous = std::mem::replace(&mut self.top_profile, profile);
+        let poking = self
+            .openings
+            .iter()
+            .find(|o| !self.opening_fits_below_top(o))
+            .map(|o| o.out_of_bounds(self.length()));
+        if let Some(err) = poking {
+            self.top_profile = previous;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Height of the top edge above the wall base at a distance along the
+    /// baseline (clamped to the wall).
+    pub fn top_height_at(&self, offset: f64) -> f64 {
+        let length = self.length();
+        let offset = offset.clamp(0.0, length);
+        match self.top_profile {
+            None => self.height,
+            Some(TopProfile::Sloped {
+                start_height,
+                end_height,
+            }) => start_height + (end_height - start_height) * offset / length,
+            Some(TopProfile::Gable {
+                peak_offset,
+                peak_height,
+            }) => {
+                let t = if offset <= peak_offset {
+                    offset / peak_offset
+                } else {
+                    (length - offset) / (length - peak_offset)
+                };
+                self.height + (peak_height - self.height) * t
+            }
+        }
+    }
+
+    /// Greatest height of the top edge above the wall base.
+    pub fn max_height(&self) -> f64 {
+        match self.top_profile {
+            None => self.height,
+            Some(TopProfile::Sloped {
+                start_height,
+                end_height,
+            }) => start_height.max(end_height),
+            Some(TopProfile::Gable { peak_height, .. }) => peak_height,
+        }
+    }
+
+    /// The openings' cut rectangles in elevation, in opening order, as
+    /// `(x_offset, y_offset, width, height)` for
+    /// [`extrude_wall_with_openings`](crate::mesh::extrude_wall_with_openings).
+    ///
+    /// `x_offset` is the start jamb's distance along the wall and
+    /// `y_offset` the opening's `base_height`, so a window or transom is
+    /// cut at its sill rather than from the floor.
+    pub fn opening_rects(&self) -> Vec<(f64, f64, f64, f64)> {
+        self.openings
+            .iter()
+            .map(|o| (o.start_offset(), o.base_height, o.width, o.height))
+            .collect()
+    }
+
+    /// Whether an opening lies within the wall's length and between its
+    /// base and top.
+    pub(crate) fn opening_in_bounds(&self, opening: &WallOpening) -> bool {
+        opening.start_offset() >= 0.0
+            && opening.end_offset() <= self.length()
+            && opening.base_height >= 0.0
+            && self.opening_fits_below_top(opening)
+    }
+
+    /// Whether an opening stays under the top edge across its width.
+    fn opening_fits_below_top(&self, opening: &WallOpening) -> bool {
+        // The top is piecewise linear with its only kink at a gable peak,
+        // which is above both ends, so the ends of the opening decide
+        let lowest = self
+            .top_height_at(opening.start_offset())
+            .min(self.top_height_at(opening.end_offset()));
+        opening.top_height() <= lowest
+    }
+
     /// Check if two openings overlap.
-    fn openings_overlap(&self, a: &WallOpening, b: &WallOpening) -> bool {
+    pub(crate) fn openings_overlap(&self, a: &WallOpening, b: &WallOpening) -> bool {
         // Check horizontal overlap
         let h_overlap = a.start_offset() < b.end_offset() && a.end_offset() > b.start_offset();
         // Check vertical overlap
@@ -257,8 +521,75 @@ impl Wall {
         ])
     }
 
+    /// Set the cap at both ends.
+    pub fn set_end_caps(&mut self, cap: WallEndCap) {
+        self.start_cap = cap;
+        self.end_cap = cap;
+    }
+
+    /// The cap at one end.
+    pub fn cap_at(&self, end: WallEnd) -> WallEndCap {
+        match end {
+            WallEnd::Start => self.start_cap,
+            WallEnd::End => self.end_cap,
+        }
+    }
+
+    /// Plan outline across one end, shaped by its cap, in the
+    /// counter-clockwise order of [`Wall::plan_outline`]: from one side
+    /// face round the end to the other. A flush end is just its two
+    /// corners.
+    pub fn end_outline(&self, end: WallEnd) -> GeometryResult<Vec<Point2>> {
+        let direction = self.direction()?;
+        let normal = direction.perp();
+        // Counter-clockwise, the outline arrives at the end along one side
+        // and leaves along the other
+        let (center, outward, side) = match end {
+            WallEnd::Start => (self.baseline.start, -direction, normal),
+            WallEnd::End => (self.baseline.end, direction, -normal),
+        };
+        let half = self.thickness / 2.0;
+
+        Ok(match self.cap_at(end) {
+            WallEndCap::Flush => vec![center + side * half, center - side * half],
+            WallEndCap::Square => vec![
+                center + side * half,
+                center + (side + outward) * half,
+                center + (outward - side) * half,
+                center - side * half,
+            ],
+            WallEndCap::Round { segments } => {
+                let segments = segments.max(2);
+                (0..=segments)
+                    .map(|i| {
+                        let angle = std::f64::consts::PI * f64::from(i) / f64::from(segments);
+                        center + (side * angle.cos() + outward * angle.sin()) * half
+                    })
+                    .collect()
+            }
+        })
+    }
+
+    /// Counter-clockwise plan outline of the wall, including its end caps.
+    pub fn plan_outline(&self) -> GeometryResult<Vec<Point2>> {
+        let mut outline = self.end_outline(WallEnd::End)?;
+        outline.extend(self.end_outline(WallEnd::Start)?);
+        Ok(outline)
+    }
+
     /// Generate mesh without openings.
+    ///
+    /// Square and round end caps extrude the [plan
+    /// outline](Wall::plan_outline), with normals and UVs. A wall with a
+    /// top profile is meshed with flush ends.
     pub fn to_mesh_simple(&self) -> GeometryResult<TriangleMesh> {
+        if self.top_profile.is_some() {
+            return self.to_mesh_profiled();
+        }
+        if self.start_cap != WallEndCap::Flush || self.end_cap != WallEndCap::Flush {
+            return extrude_polygon(&self.plan_outline()?, self.height, self.base_offset);
+        }
+
         let corners = self.base_corners()?;
         let z0 = self.base_offset;
         let z1 = self.base_offset + self.height;
@@ -300,43 +631,438 @@ impl Wall {
         Ok(TriangleMesh::from_vertices_indices(vertices, indices))
     }
 
-    /// Generate mesh with openings (simplified - creates holes but not reveals).
+    /// Generate mesh for a wall with a top profile.
+    ///
+    /// The elevation outline (distance along the wall against height) is
+    /// triangulated for the two faces and its edges swept across the
+    /// thickness, sharing vertices so the mesh stays closed.
+    fn to_mesh_profiled(&self) -> GeometryResult<TriangleMesh> {
+        let direction = self.direction()?;
+        let offset = self.normal()? * (self.thickness / 2.0);
+        let outline = self.elevation_outline();
+        let n = outline.len();
+
+        // Vertices 0..n on the positive normal face, n..2n on the negative
+        let mut vertices = Vec::with_capacity(n * 2);
+        for side in [offset, -offset] {
+            for p in &outline {
+                let plan = self.baseline.start + direction * p.x + side;
+                vertices.push(Point3::new(plan.x, plan.y, self.base_offset + p.y));
+            }
+        }
+
+        // A counter-clockwise outline faces against the normal, so the
+        // positive face takes its triangles reversed
+        let mut indices = Vec::new();
+        for tri in triangulate_polygon(&outline)? {
+            let [a, b, c] = tri.map(|i| i as u32);
+            indices.push([a, c, b]);
+            indices.push([n as u32 + a, n as u32 + b, n as u32 + c]);
+        }
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let (fi, fj) = (i as u32, j as u32);
+            let (bi, bj) = ((n + i) as u32, (n + j) as u32);
+            indices.push([fi, fj, bj]);
+            indices.push([fi, bj, bi]);
+        }
+
+        Ok(TriangleMesh::from_vertices_indices(vertices, indices))
+    }
+
+    /// Counter-clockwise elevation outline of the wall, in (distance along
+    /// the baseline, height above the base), starting along the bottom.
+    ///
+    /// The top follows the top profile. Square end caps lengthen the
+    /// outline by half the thickness, except on a wall with a top profile,
+    /// which is meshed with flush ends. Round caps are not part of the
+    /// elevation.
+    fn elevation_outline(&self) -> Vec<Point2> {
+        let length = self.length();
+        let reach = |end| match self.cap_at(end) {
+            WallEndCap::Square if self.top_profile.is_none() => self.thickness / 2.0,
+            _ => 0.0,
+        };
+        let (x0, x1) = (-reach(WallEnd::Start), length + reach(WallEnd::End));
+
+        let mut outline = vec![
+            Point2::new(x0, 0.0),
+            Point2::new(x1, 0.0),
+            Point2::new(x1, self.top_height_at(length)),
+        ];
+        if let Some(TopProfile::Gable {
+            peak_offset,
+            peak_height,
+        }) = self.top_profile
+        {
+            outline.push(Point2::new(peak_offset, peak_height));
+        }
+        outline.push(Point2::new(x0, self.top_height_at(0.0)));
+        outline
+    }
+
+    /// Generate mesh with the openings cut through and lined with reveals.
+    ///
+    /// The elevation outline is swept across the thickness as in
+    /// [`extrude_wall_with_openings`] and placed along the baseline:
+    /// openings resting on the base, such as doors, are notched out of the
+    /// bottom edge, and the rest are cut as holes. Round end caps are
+    /// extruded on their own and take the place of the flat end faces, so
+    /// the pieces weld into one closed mesh. A wall without openings is
+    /// meshed by [`Wall::to_mesh_simple`].
+    ///
+    /// [`extrude_wall_with_openings`]: crate::mesh::extrude_wall_with_openings
     pub fn to_mesh_with_openings(&self) -> GeometryResult<TriangleMesh> {
         if self.openings.is_empty() {
             return self.to_mesh_simple();
         }
 
-        // For now, return simple mesh.
-        // Full opening implementation requires polygon boolean operations
-        // and constrained triangulation, which will be added in Phase 4.
-        self.to_mesh_simple()
+        let mut mesh = extrude_elevation_with_openings(
+            &self.elevation_outline(),
+            self.thickness,
+            &self.opening_rects(),
+        )?;
+        if self.top_profile.is_none() {
+            for end in [WallEnd::Start, WallEnd::End] {
+                if matches!(self.cap_at(end), WallEndCap::Round { .. }) {
+                    self.join_round_cap(&mut mesh, end)?;
+                }
+            }
+        }
+        mesh.transform(&self.local_to_world()?);
+        Ok(mesh)
+    }
+
+    /// Replace the flat end face of a wall-local mesh with the end's round
+    /// cap, extruded to the wall's height.
+    fn join_round_cap(&self, mesh: &mut TriangleMesh, end: WallEnd) -> GeometryResult<()> {
+        let start = self.baseline.start;
+        let direction = self.direction()?;
+        let normal = direction.perp();
+        let cap_outline: Vec<Point2> = self
+            .end_outline(end)?
+            .into_iter()
+            .map(|p| {
+                let d = p - start;
+                Point2::new(d.dot(&direction), d.dot(&normal))
+            })
+            .collect();
+        let mut cap = extrude_polygon(&cap_outline, self.height, 0.0)?;
+
+        // Both the body's end face and the cap's flat side lie in the
+        // plane across the baseline end
+        let x = match end {
+            WallEnd::Start => 0.0,
+            WallEnd::End => self.length(),
+        };
+        for piece in [&mut *mesh, &mut cap] {
+            let TriangleMesh {
+                vertices, indices, ..
+            } = piece;
+            indices.retain(|tri| {
+                !tri.iter()
+                    .all(|&i| (vertices[i as usize].x - x).abs() < EPSILON)
+            });
+        }
+        mesh.merge(&cap);
+        mesh.remove_unused_vertices();
+        Ok(())
+    }
+
+    /// Transform from the wall-local coordinates of
+    /// [`extrude_wall_with_openings`] (X along the baseline, Y along the
+    /// normal, Z up from the base) to world space.
+    ///
+    /// [`extrude_wall_with_openings`]: crate::mesh::extrude_wall_with_openings
+    fn local_to_world(&self) -> GeometryResult<Transform3> {
+        let direction = self.direction()?;
+        let start = self.baseline.start;
+        Ok(Transform3::rotation_z(direction.y.atan2(direction.x))
+            .compose(&Transform3::translation(start.x, start.y, self.base_offset)))
+    }
+
+    /// Like [`Wall::to_mesh_with_openings`], but welded on position alone
+    /// (so UVs are dropped) and with any holes capped (see
+    /// [`TriangleMesh::close_holes`]), so the mesh is closed and its volume
+    /// can be trusted.
+    pub fn to_mesh_watertight(&self) -> GeometryResult<TriangleMesh> {
+        let mut mesh = self.to_mesh_with_openings()?;
+        mesh.uvs.clear();
+        mesh.weld_vertices(EPSILON);
+        mesh.close_holes();
+        Ok(mesh)
+    }
+
+    /// Split the wall at a distance along its baseline.
+    ///
+    /// Fails if the split would cut through an opening. See
+    /// [`Wall::split_at_with_policy`].
+    pub fn split_at(&self, offset: f64) -> GeometryResult<(Wall, Wall)> {
+        self.split_at_with_policy(offset, SplitPolicy::Reject)
+    }
+
+    /// Split the wall at the projection of a point onto its baseline.
+    pub fn split_at_point(&self, point: Point2) -> GeometryResult<(Wall, Wall)> {
+        self.split_at(self.offset_of_point(point))
+    }
+
+    /// Distance along the baseline of a point's projection (unclamped).
+    pub fn offset_of_point(&self, point: Point2) -> f64 {
+        let segment = LineSegment2::new(self.baseline.start, self.baseline.end);
+        segment.project_point(&point) * self.length()
+    }
+
+    /// Split the wall at a distance along its baseline.
+    ///
+    /// Both halves get fresh IDs and copy the wall's type, dimensions, level,
+    /// and metadata, with the original ID recorded under
+    /// [`SPLIT_PARENT_PROPERTY`]. Openings keep their IDs and move to the
+    /// half they lie on, with offsets measured from that half's start.
+    /// A sloped top is divided between the halves; a gable wall can only be
+    /// split at its peak, leaving two sloped halves. End caps stay at the
+    /// outer ends, and the ends at the split are flush.
+    pub fn split_at_with_policy(
+        &self,
+        offset: f64,
+        policy: SplitPolicy,
+    ) -> GeometryResult<(Wall, Wall)> {
+        const EPS: f64 = 1e-10;
+
+        let length = self.length();
+        if !(offset > EPS && offset < length - EPS) {
+            return Err(GeometryError::InvalidSplitOffset);
+        }
+
+        let split_height = self.top_height_at(offset);
+        let (first_profile, second_profile) = match self.top_profile {
+            None => (None, None),
+            Some(TopProfile::Sloped {
+                start_height,
+                end_height,
+            }) => (
+                Some(TopProfile::Sloped {
+                    start_height,
+                    end_height: split_height,
+                }),
+                Some(TopProfile::Sloped {
+                    start_height: split_height,
+                    end_height,
+                }),
+            ),
+            Some(TopProfile::Gable { peak_offset, .. }) if (offset - peak_offset).abs() <= EPS => (
+                Some(TopProfile::Sloped {
+                    start_height: self.height,
+                    end_height: split_height,
+                }),
+                Some(TopProfile::Sloped {
+                    start_height: split_height,
+                    end_height: self.height,
+                }),
+            ),
+            Some(TopProfile::Gable { .. }) => return Err(GeometryError::InvalidSplitOffset),
+        };
+
+        let mut first_openings = Vec::new();
+        let mut second_openings = Vec::new();
+        for opening in &self.openings {
+            let mut opening = opening.clone();
+            let on_first = opening.offset_along_wall < offset;
+            let straddles =
+                opening.start_offset() < offset - EPS && opening.end_offset() > offset + EPS;
+
+            if straddles {
+                if policy == SplitPolicy::Reject {
+                    return Err(opening.out_of_bounds(length));
+                }
+                opening.offset_along_wall = if on_first {
+                    offset - opening.width / 2.0
+                } else {
+                    offset + opening.width / 2.0
+                };
+            }
+
+            if on_first {
+                if opening.start_offset() < -EPS {
+                    return Err(opening.out_of_bounds(offset));
+                }
+                first_openings.push(opening);
+            } else {
+                opening.offset_along_wall -= offset;
+                if opening.end_offset() > length - offset + EPS {
+                    return Err(opening.out_of_bounds(length - offset));
+                }
+                second_openings.push(opening);
+            }
+        }
+
+        let split_point = self.baseline.point_at(offset / length);
+        let half = |start: Point2,
+                    end: Point2,
+                    openings: Vec<WallOpening>,
+                    top_profile: Option<TopProfile>| {
+            let mut wall = self.clone();
+            wall.id = next_element_id(
+                ElementType::Wall.name(),
+                &[self.id],
+                &[start.x, start.y, end.x, end.y],
+            );
+            wall.baseline = WallBaseline::new(start, end);
+            wall.openings = openings;
+            wall.top_profile = top_profile;
+            // The halves meet at the split point, which takes no cap
+            if start != self.baseline.start {
+                wall.start_cap = WallEndCap::Flush;
+            }
+            if end != self.baseline.end {
+                wall.end_cap = WallEndCap::Flush;
+            }
+            wall.metadata
+                .set_property(SPLIT_PARENT_PROPERTY, self.id.to_string());
+            wall
+        };
+
+        Ok((
+            half(
+                self.baseline.start,
+                split_point,
+                first_openings,
+                first_profile,
+            ),
+            half(
+                split_point,
+                self.baseline.end,
+                second_openings,
+                second_profile,
+            ),
+        ))
+    }
+
+    /// Create a parallel copy of the wall translated along its normal.
+    ///
+    /// `distance` is measured towards `side`; a negative distance goes the
+    /// other way. The copy gets a fresh ID and keeps the type, dimensions,
+    /// level, and metadata, but no openings.
+    pub fn offset(&self, distance: f64, side: OffsetSide) -> GeometryResult<Wall> {
+        let shift = self.normal()? * (distance * side.sign());
+
+        let mut wall = self.clone();
+        wall.id = next_element_id(ElementType::Wall.name(), &[self.id], &[shift.x, shift.y]);
+        wall.baseline = WallBaseline::new(self.baseline.start + shift, self.baseline.end + shift);
+        wall.openings = Vec::new();
+        Ok(wall)
+    }
+
+    /// Like [`Wall::offset`], but also copies the openings.
+    ///
+    /// The baseline is translated without changing direction or length, so
+    /// each copied opening keeps its offset and faces the original. Copies
+    /// get fresh IDs.
+    pub fn offset_with_openings(&self, distance: f64, side: OffsetSide) -> GeometryResult<Wall> {
+        let mut wall = self.offset(distance, side)?;
+        wall.openings = self
+            .openings
+            .iter()
+            .map(|opening| WallOpening {
+                id: next_element_id(ElementType::Opening.name(), &[wall.id, opening.id], &[]),
+                ..opening.clone()
+            })
+            .collect();
+        Ok(wall)
     }
 }
 
+/// Offset a chain of connected walls, keeping the corners joined.
+///
+/// Each wall's end must meet the next wall's start. Positive `distance`
+/// offsets to the left of each wall. Adjacent offset baselines are
+/// re-intersected so the offset chain stays connected; if the last wall
+/// ends where the first starts, the chain is treated as closed and that
+/// corner is solved too. Openings are not copied.
+pub fn offset_walls(walls: &[Wall], distance: f64) -> GeometryResult<Vec<Wall>> {
+    let n = walls.len();
+    for pair in walls.windows(2) {
+        if pair[0].baseline.end.distance_to(&pair[1].baseline.start) > CHAIN_TOLERANCE {
+            return Err(GeometryError::DisconnectedWallChain);
+        }
+    }
+    let closed = n > 2 && {
+        let (first, last) = (&walls[0].baseline, &walls[n - 1].baseline);
+        last.end.distance_to(&first.start) <= CHAIN_TOLERANCE
+    };
+
+    let mut offset = walls
+        .iter()
+        .map(|wall| wall.offset(distance, OffsetSide::Left))
+        .collect::<GeometryResult<Vec<_>>>()?;
+
+    let corners = if closed { n } else { n.saturating_sub(1) };
+    for i in 0..corners {
+        let j = (i + 1) % n;
+        let a = offset[i].baseline;
+        let b = offset[j].baseline;
+        let line_a = Line2::from_points(a.start, a.end)?;
+        let line_b = Line2::from_points(b.start, b.end)?;
+
+        // Collinear neighbours already share the offset endpoint.
+        let corner = if line_a.is_parallel_to(&line_b, 1e-9) {
+            a.end
+        } else {
+            line_a.intersect(&line_b)?
+        };
+        offset[i].baseline.end = corner;
+        offset[j].baseline.start = corner;
+    }
+
+    if offset.iter().any(|wall| wall.length() < 1e-10) {
+        return Err(GeometryError::ZeroLengthWall);
+    }
+    Ok(offset)
+}
+
+/// Topology graph of a set of wall baselines, in millimeters, with
+/// crossings split and rooms traced.
+pub(crate) fn wall_graph(walls: &[&Wall]) -> TopologyGraph {
+    const M_TO_MM: f64 = 1000.0;
+    let mut graph = TopologyGraph::new();
+    for wall in walls {
+        let (start, end) = (wall.baseline.start, wall.baseline.end);
+        graph.add_edge(
+            [start.x * M_TO_MM, start.y * M_TO_MM],
+            [end.x * M_TO_MM, end.y * M_TO_MM],
+            EdgeData::wall(wall.thickness * M_TO_MM, wall.height * M_TO_MM),
+        );
+    }
+    split_crossings(&mut graph);
+    graph.rebuild_rooms();
+    graph
+}
+
 impl Element for Wall {
     fn id(&self) -> Uuid {
         self.id
     }
 
+    fn metadata(&self) -> &ElementMetadata {
+        &self.metadata
+    }
+
+    fn level_id(&self) -> Option<Uuid> {
+        self.level_id
+    }
+
     fn element_type(&self) -> ElementType {
         ElementType::Wall
     }
 
     fn bounding_box(&self) -> GeometryResult<BoundingBox3> {
-        let corners = self.base_corners()?;
         let z0 = self.base_offset;
-        let z1 = self.base_offset + self.height;
+        let z1 = self.base_offset + self.max_height();
 
-        let points = vec![
-            Point3::new(corners[0].x, corners[0].y, z0),
-            Point3::new(corners[1].x, corners[1].y, z0),
-            Point3::new(corners[2].x, corners[2].y, z0),
-            Point3::new(corners[3].x, corners[3].y, z0),
-            Point3::new(corners[0].x, corners[0].y, z1),
-            Point3::new(corners[1].x, corners[1].y, z1),
-            Point3::new(corners[2].x, corners[2].y, z1),
-            Point3::new(corners[3].x, corners[3].y, z1),
-        ];
+        let points: Vec<Point3> = self
+            .plan_outline()?
+            .iter()
+            .flat_map(|c| [Point3::new(c.x, c.y, z0), Point3::new(c.x, c.y, z1)])
+            .collect();
 
         BoundingBox3::from_points(&points).ok_or(GeometryError::ZeroLengthWall)
     }
@@ -346,9 +1072,62 @@ impl Element for Wall {
     }
 }
 
+impl TransformableElement for Wall {
+    /// Thickness, height, and openings are kept. A mirrored baseline is
+    /// reversed so each face stays on the same side of the wall; opening
+    /// offsets and the top profile are re-measured from the new start, and
+    /// the end caps swap ends.
+    fn apply_transform(&mut self, transform: &PlanTransform) {
+        let start = transform.apply_point(self.baseline.start);
+        let end = transform.apply_point(self.baseline.end);
+        if !transform.is_mirror() {
+            self.baseline = WallBaseline::new(start, end);
+            return;
+        }
+
+        let length = self.length();
+        self.baseline = WallBaseline::new(end, start);
+        std::mem::swap(&mut self.start_cap, &mut self.end_cap);
+        for opening in &mut self.openings {
+            opening.offset_along_wall = length - opening.offset_along_wall;
+        }
+        self.top_profile = self.top_profile.map(|profile| match profile {
+            TopProfile::Sloped {
+                start_height,
+                end_height,
+            } => TopProfile::Sloped {
+                start_height: end_height,
+                end_height: start_height,
+            },
+            TopProfile::Gable {
+                peak_offset,
+                peak_height,
+            } => TopProfile::Gable {
+                peak_offset: length - peak_offset,
+                peak_height,
+            },
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pensaer_math::Vector3;
+
+    #[test]
+    fn seeded_walls_share_ids() {
+        let (a, b) = (Point2::new(0.0, 0.0), Point2::new(5.0, 0.0));
+        let first = Wall::new_with_seed(a, b, 3.0, 0.2, 7).unwrap();
+        let second = Wall::new_with_seed(a, b, 3.0, 0.2, 7).unwrap();
+
+        assert_eq!(first.id, second.id);
+        assert_ne!(first.id, Wall::new_with_seed(a, b, 3.0, 0.2, 8).unwrap().id);
+        assert_ne!(
+            Wall::new(a, b, 3.0, 0.2).unwrap().id,
+            Wall::new(a, b, 3.0, 0.2).unwrap().id
+        );
+    }
 
     #[test]
     fn wall_creation() {
@@ -368,7 +1147,10 @@ mod tests {
     #[test]
     fn wall_non_positive_height_fails() {
         let result = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 0.0, 0.2);
-        assert!(matches!(result, Err(GeometryError::NonPositiveHeight)));
+        assert!(matches!(
+            result,
+            Err(GeometryError::NonPositiveHeight { value }) if value == 0.0
+        ));
     }
 
     #[test]
@@ -405,10 +1187,41 @@ mod tests {
 
         // Opening extends past wall end
         let opening = WallOpening::new(5.0, 0.0, 1.0, 2.0, OpeningType::Window);
-        assert!(matches!(
+        assert_eq!(
             wall.add_opening(opening),
-            Err(GeometryError::OpeningOutOfBounds)
-        ));
+            Err(GeometryError::OpeningOutOfBounds {
+                offset: 5.0,
+                width: 1.0,
+                wall_length: 5.0,
+            })
+        );
+    }
+
+    #[test]
+    fn transom_is_cut_above_the_floor() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        let transom = WallOpening::new(2.5, 2.1, 1.0, 0.6, OpeningType::Generic);
+        wall.add_opening(transom).unwrap();
+
+        assert_eq!(wall.opening_rects(), [(2.0, 2.1, 1.0, 0.6)]);
+        let mesh = crate::mesh::extrude_wall_with_openings(
+            wall.length(),
+            wall.height,
+            wall.thickness,
+            &wall.opening_rects(),
+        )
+        .unwrap();
+        assert!(mesh.is_valid());
+
+        // Rays across the wall under the transom's centre: solid below the
+        // sill and above the head, open between
+        let across = Vector3::new(0.0, 1.0, 0.0);
+        for (z, solid) in [(0.5, true), (2.0, true), (2.4, false), (2.8, true)] {
+            let hit = mesh.raycast(Point3::new(2.5, -1.0, z), across);
+            assert_eq!(hit.is_some(), solid, "at z = {z}");
+        }
+        let sill = mesh.raycast(Point3::new(2.5, 0.0, 2.4), -Vector3::UNIT_Z);
+        assert!((sill.unwrap().distance - 0.3).abs() < 1e-9);
     }
 
     #[test]
@@ -421,6 +1234,57 @@ mod tests {
         assert_eq!(mesh.triangle_count(), 12);
     }
 
+    #[test]
+    fn round_end_cap_adds_fan_triangles() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        wall.end_cap = WallEndCap::Round { segments: 8 };
+
+        let outline = wall.end_outline(WallEnd::End).unwrap();
+        assert_eq!(outline.len(), 9);
+        for p in &outline {
+            assert!((p.distance_to(&wall.baseline.end) - 0.1).abs() < 1e-12);
+        }
+
+        // The half-disk adds 7 triangles to each of the top and bottom,
+        // and 7 side faces of two triangles each
+        let mesh = wall.to_mesh().unwrap();
+        assert!(mesh.is_valid());
+        let top = mesh
+            .indices
+            .iter()
+            .filter(|t| mesh.normals[t[0] as usize].z > 0.5)
+            .count();
+        assert_eq!(top, 2 + 7);
+        assert_eq!(mesh.triangle_count(), 2 * (2 + 7) + 2 * (4 + 7));
+
+        // Body plus an inscribed half 16-gon of radius 0.1
+        let half_disk = 8.0 * 0.5 * 0.01 * (std::f64::consts::PI / 8.0).sin();
+        assert!((mesh.volume() - (5.0 * 0.2 + half_disk) * 3.0).abs() < 1e-9);
+        let bbox = wall.bounding_box().unwrap();
+        assert!((bbox.max.x - 5.1).abs() < 1e-12);
+        assert!((bbox.min.x - 0.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn square_end_caps_reach_past_both_ends() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        wall.set_end_caps(WallEndCap::Square);
+
+        let mesh = wall.to_mesh().unwrap();
+        assert!((mesh.volume() - 5.2 * 0.2 * 3.0).abs() < 1e-9);
+
+        // Splitting leaves the split ends flush
+        let (first, second) = wall.split_at(2.0).unwrap();
+        assert_eq!(
+            (first.start_cap, first.end_cap),
+            (WallEndCap::Square, WallEndCap::Flush)
+        );
+        assert_eq!(
+            (second.start_cap, second.end_cap),
+            (WallEndCap::Flush, WallEndCap::Square)
+        );
+    }
+
     #[test]
     fn wall_bounding_box() {
         let wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), 3.0, 0.2).unwrap();
@@ -441,4 +1305,415 @@ mod tests {
         assert_eq!(wall.element_type(), ElementType::Wall);
         assert!(!wall.id().is_nil());
     }
+
+    fn wall_with_openings() -> Wall {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), 3.0, 0.2).unwrap();
+        wall.wall_type = WallType::Structural;
+        wall.metadata.set_property("fire_rating", "60");
+        wall.add_opening(WallOpening::new(2.0, 0.0, 1.0, 2.1, OpeningType::Door))
+            .unwrap();
+        wall.add_opening(WallOpening::new(7.0, 0.9, 1.2, 1.2, OpeningType::Window))
+            .unwrap();
+        wall
+    }
+
+    #[test]
+    fn split_distributes_openings() {
+        let wall = wall_with_openings();
+        let (first, second) = wall.split_at(4.0).unwrap();
+
+        assert!((first.length() - 4.0).abs() < 1e-10);
+        assert!((second.length() - 6.0).abs() < 1e-10);
+        assert_eq!(first.baseline.end, second.baseline.start);
+
+        assert_eq!(first.openings.len(), 1);
+        assert_eq!(first.openings[0].id, wall.openings[0].id);
+        assert!((first.openings[0].offset_along_wall - 2.0).abs() < 1e-10);
+
+        assert_eq!(second.openings.len(), 1);
+        assert_eq!(second.openings[0].id, wall.openings[1].id);
+        assert!((second.openings[0].offset_along_wall - 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn split_copies_properties_with_fresh_ids() {
+        let wall = wall_with_openings();
+        let (first, second) = wall.split_at(4.0).unwrap();
+
+        for half in [&first, &second] {
+            assert_ne!(half.id, wall.id);
+            assert_eq!(half.wall_type, WallType::Structural);
+            assert_eq!(half.height, wall.height);
+            assert_eq!(half.thickness, wall.thickness);
+            assert_eq!(half.metadata.get_property("fire_rating").unwrap(), "60");
+            assert_eq!(
+                half.metadata.get_property(SPLIT_PARENT_PROPERTY).unwrap(),
+                &wall.id.to_string()
+            );
+        }
+        assert_ne!(first.id, second.id);
+    }
+
+    #[test]
+    fn split_through_opening_rejected() {
+        let wall = wall_with_openings();
+        assert!(matches!(
+            wall.split_at(2.2),
+            Err(GeometryError::OpeningOutOfBounds { .. })
+        ));
+        // Touching an opening edge is not a bisection
+        assert!(wall.split_at(2.5).is_ok());
+    }
+
+    #[test]
+    fn split_shifts_opening_to_center_side() {
+        let wall = wall_with_openings();
+
+        let (first, second) = wall
+            .split_at_with_policy(2.2, SplitPolicy::ShiftToCenterSide)
+            .unwrap();
+        assert_eq!(first.openings.len(), 1);
+        assert!((first.openings[0].end_offset() - 2.2).abs() < 1e-10);
+        assert_eq!(second.openings.len(), 1);
+
+        let (first, second) = wall
+            .split_at_with_policy(1.8, SplitPolicy::ShiftToCenterSide)
+            .unwrap();
+        assert!(first.openings.is_empty());
+        assert_eq!(second.openings.len(), 2);
+        assert!(second.openings[0].start_offset().abs() < 1e-10);
+    }
+
+    #[test]
+    fn split_shift_fails_when_half_too_short() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        wall.add_opening(WallOpening::new(0.6, 0.0, 1.0, 2.1, OpeningType::Door))
+            .unwrap();
+        // The door (width 1.0) would have to fit in the first 0.7m
+        assert!(matches!(
+            wall.split_at_with_policy(0.7, SplitPolicy::ShiftToCenterSide),
+            Err(GeometryError::OpeningOutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn split_at_ends_fails() {
+        let wall = wall_with_openings();
+        for offset in [0.0, 10.0, -1.0, 12.0] {
+            assert!(matches!(
+                wall.split_at(offset),
+                Err(GeometryError::InvalidSplitOffset)
+            ));
+        }
+    }
+
+    #[test]
+    fn split_at_point_projects_onto_baseline() {
+        let wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(0.0, 8.0), 3.0, 0.2).unwrap();
+        let (first, second) = wall.split_at_point(Point2::new(0.4, 3.0)).unwrap();
+
+        assert!((first.baseline.end.x).abs() < 1e-10);
+        assert!((first.baseline.end.y - 3.0).abs() < 1e-10);
+        assert!((second.length() - 5.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn offset_translates_along_normal() {
+        let wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+
+        let left = wall.offset(0.5, OffsetSide::Left).unwrap();
+        assert_ne!(left.id, wall.id);
+        assert!((left.baseline.start.y - 0.5).abs() < 1e-10);
+        assert!((left.baseline.end.x - 5.0).abs() < 1e-10);
+        assert!((left.thickness - 0.2).abs() < 1e-10);
+
+        let right = wall.offset(0.5, OffsetSide::Right).unwrap();
+        assert!((right.baseline.start.y + 0.5).abs() < 1e-10);
+
+        // Negative distance flips the side
+        let flipped = wall.offset(-0.5, OffsetSide::Left).unwrap();
+        assert!((flipped.baseline.end.y + 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn offset_with_openings_copies_openings() {
+        let wall = wall_with_openings();
+
+        let plain = wall.offset(0.3, OffsetSide::Left).unwrap();
+        assert!(plain.openings.is_empty());
+
+        let copy = wall.offset_with_openings(0.3, OffsetSide::Left).unwrap();
+        assert_eq!(copy.openings.len(), wall.openings.len());
+        for (copied, original) in copy.openings.iter().zip(&wall.openings) {
+            assert_ne!(copied.id, original.id);
+            let a = copy
+                .baseline
+                .point_at(copied.offset_along_wall / copy.length());
+            let b = wall
+                .baseline
+                .point_at(original.offset_along_wall / wall.length());
+            // Copied opening sits directly across from the original
+            assert!((a - b).dot(&wall.direction().unwrap()).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn offset_walls_closed_rectangle_outward() {
+        let corners = [
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 8.0),
+            Point2::new(0.0, 8.0),
+        ];
+        let walls: Vec<Wall> = (0..4)
+            .map(|i| Wall::new(corners[i], corners[(i + 1) % 4], 3.0, 0.2).unwrap())
+            .collect();
+
+        // Counter-clockwise loop, so outward is to the right
+        let offset = offset_walls(&walls, -0.1).unwrap();
+        assert_eq!(offset.len(), 4);
+
+        for i in 0..4 {
+            let next = &offset[(i + 1) % 4];
+            assert!(offset[i].baseline.end.distance_to(&next.baseline.start) < 1e-10);
+        }
+        let start = offset[0].baseline.start;
+        assert!((start.x + 0.1).abs() < 1e-10);
+        assert!((start.y + 0.1).abs() < 1e-10);
+
+        let outline: Vec<Point2> = offset.iter().map(|w| w.baseline.start).collect();
+        let area = pensaer_math::Polygon2::new(outline).unwrap().area();
+        assert!((area - 10.2 * 8.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn offset_walls_open_chain_keeps_free_ends() {
+        let a = Wall::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), 3.0, 0.2).unwrap();
+        let b = Wall::new(Point2::new(4.0, 0.0), Point2::new(4.0, 3.0), 3.0, 0.2).unwrap();
+        let c = Wall::new(Point2::new(4.0, 3.0), Point2::new(4.0, 6.0), 3.0, 0.2).unwrap();
+
+        let offset = offset_walls(&[a, b, c], 0.5).unwrap();
+        assert!(offset[0].baseline.start.distance_to(&Point2::new(0.0, 0.5)) < 1e-10);
+        assert!(offset[0].baseline.end.distance_to(&Point2::new(3.5, 0.5)) < 1e-10);
+        assert!(offset[1].baseline.start.distance_to(&Point2::new(3.5, 0.5)) < 1e-10);
+        // Collinear neighbours meet at the shared offset endpoint
+        assert!(offset[1].baseline.end.distance_to(&Point2::new(3.5, 3.0)) < 1e-10);
+        assert!(offset[2].baseline.end.distance_to(&Point2::new(3.5, 6.0)) < 1e-10);
+    }
+
+    #[test]
+    fn offset_walls_rejects_disconnected_chain() {
+        let a = Wall::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), 3.0, 0.2).unwrap();
+        let b = Wall::new(Point2::new(5.0, 0.0), Point2::new(5.0, 3.0), 3.0, 0.2).unwrap();
+
+        assert!(matches!(
+            offset_walls(&[a, b], 0.1),
+            Err(GeometryError::DisconnectedWallChain)
+        ));
+    }
+
+    #[test]
+    fn sloped_top_wall_mesh() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(6.0, 0.0), 3.0, 0.2).unwrap();
+        wall.set_top_profile(Some(TopProfile::Sloped {
+            start_height: 2.4,
+            end_height: 4.2,
+        }))
+        .unwrap();
+
+        let mesh = wall.to_mesh().unwrap();
+        assert!(mesh.is_manifold());
+        assert!(!mesh.has_degenerate_triangles());
+        let bbox = mesh.bounding_box().unwrap();
+        assert!((bbox.max.z - 4.2).abs() < 1e-10);
+        assert!((wall.bounding_box().unwrap().max.z - 4.2).abs() < 1e-10);
+        // Trapezoidal elevation times thickness
+        assert!((mesh.volume() - 6.0 * 3.3 * 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn gable_top_wall_mesh() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(0.0, 8.0), 2.5, 0.3).unwrap();
+        wall.set_top_profile(Some(TopProfile::Gable {
+            peak_offset: 4.0,
+            peak_height: 5.0,
+        }))
+        .unwrap();
+
+        assert!((wall.top_height_at(2.0) - 3.75).abs() < 1e-10);
+        let mesh = wall.to_mesh().unwrap();
+        assert!(mesh.is_manifold());
+        assert!((mesh.bounding_box().unwrap().max.z - 5.0).abs() < 1e-10);
+        assert!((mesh.volume() - (8.0 * 2.5 + 8.0 * 2.5 / 2.0) * 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn watertight_mesh_is_closed() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        wall.add_opening(WallOpening::new(1.45, 0.0, 0.9, 2.1, OpeningType::Door))
+            .unwrap();
+        wall.set_top_profile(Some(TopProfile::Sloped {
+            start_height: 3.0,
+            end_height: 4.0,
+        }))
+        .unwrap();
+
+        let mesh = wall.to_mesh_watertight().unwrap();
+        assert!(mesh.is_valid());
+        assert!(mesh.is_manifold());
+        assert!(!mesh.has_degenerate_triangles());
+
+        // The door is still a void: the volume is the sloped slab less the
+        // door, and a ray through the doorway misses the wall
+        let solid = 5.0 * 3.5 * 0.2;
+        assert!((mesh.volume() - (solid - 0.9 * 2.1 * 0.2)).abs() < 1e-9);
+        assert!(mesh
+            .raycast(Point3::new(1.45, -1.0, 1.0), Vector3::UNIT_Y)
+            .is_none());
+        assert!(mesh
+            .raycast(Point3::new(1.45, -1.0, 2.5), Vector3::UNIT_Y)
+            .is_some());
+    }
+
+    #[test]
+    fn watertight_mesh_joins_round_caps() {
+        let mut wall = Wall::new(Point2::new(1.0, 1.0), Point2::new(1.0, 6.0), 3.0, 0.2).unwrap();
+        wall.set_end_caps(WallEndCap::Round { segments: 8 });
+        wall.add_opening(WallOpening::new(2.5, 0.9, 1.2, 1.2, OpeningType::Window))
+            .unwrap();
+
+        let mesh = wall.to_mesh_watertight().unwrap();
+        assert!(mesh.is_manifold());
+        assert!(!mesh.has_degenerate_triangles());
+
+        // Two caps of 8 segments each make a 16-gon of radius 0.1
+        let caps = 16.0 * 0.5 * 0.01 * (std::f64::consts::PI / 8.0).sin() * 3.0;
+        let body = (5.0 * 3.0 - 1.2 * 1.2) * 0.2;
+        assert!((mesh.volume() - (body + caps)).abs() < 1e-9);
+        let bbox = mesh.bounding_box().unwrap();
+        assert!((bbox.min.y - 0.9).abs() < 1e-9 && (bbox.max.y - 6.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn top_profile_limits_openings() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(6.0, 0.0), 3.0, 0.2).unwrap();
+        wall.set_top_profile(Some(TopProfile::Sloped {
+            start_height: 2.0,
+            end_height: 4.0,
+        }))
+        .unwrap();
+
+        // Fits near the tall end but not near the short one
+        let tall = WallOpening::new(5.0, 0.0, 1.0, 3.5, OpeningType::Door);
+        let short = WallOpening::new(1.0, 0.0, 1.0, 2.5, OpeningType::Door);
+        assert!(wall.add_opening(tall).is_ok());
+        assert!(matches!(
+            wall.add_opening(short),
+            Err(GeometryError::OpeningOutOfBounds { .. })
+        ));
+
+        // Lowering the top under the existing opening is refused
+        assert!(matches!(
+            wall.set_top_profile(Some(TopProfile::Sloped {
+                start_height: 2.0,
+                end_height: 3.0,
+            })),
+            Err(GeometryError::OpeningOutOfBounds { .. })
+        ));
+        assert!((wall.max_height() - 4.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn invalid_gable_rejected() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(6.0, 0.0), 3.0, 0.2).unwrap();
+        for peak_offset in [0.0, 6.0, 7.0] {
+            assert!(matches!(
+                wall.set_top_profile(Some(TopProfile::Gable {
+                    peak_offset,
+                    peak_height: 5.0,
+                })),
+                Err(GeometryError::InvalidTopProfile(_))
+            ));
+        }
+        assert!(matches!(
+            wall.set_top_profile(Some(TopProfile::Gable {
+                peak_offset: 3.0,
+                peak_height: 2.0,
+            })),
+            Err(GeometryError::InvalidTopProfile(_))
+        ));
+        assert!(wall.top_profile.is_none());
+    }
+
+    #[test]
+    fn split_divides_top_profile() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(6.0, 0.0), 3.0, 0.2).unwrap();
+        wall.set_top_profile(Some(TopProfile::Sloped {
+            start_height: 2.0,
+            end_height: 5.0,
+        }))
+        .unwrap();
+
+        let (first, second) = wall.split_at(2.0).unwrap();
+        assert!((first.top_height_at(2.0) - 3.0).abs() < 1e-10);
+        assert!((second.top_height_at(0.0) - 3.0).abs() < 1e-10);
+        assert!((second.top_height_at(4.0) - 5.0).abs() < 1e-10);
+
+        wall.set_top_profile(Some(TopProfile::Gable {
+            peak_offset: 3.0,
+            peak_height: 4.0,
+        }))
+        .unwrap();
+        assert!(matches!(
+            wall.split_at(2.0),
+            Err(GeometryError::InvalidSplitOffset)
+        ));
+        let (first, second) = wall.split_at(3.0).unwrap();
+        assert!((first.max_height() - 4.0).abs() < 1e-10);
+        assert!((second.top_height_at(3.0) - 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn rotate_keeps_wall_dimensions() {
+        let mut wall = Wall::new(Point2::new(1.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        wall.rotate_about(Point2::new(1.0, 0.0), std::f64::consts::FRAC_PI_2);
+
+        assert!((wall.baseline.end.x - 1.0).abs() < 1e-10);
+        assert!((wall.baseline.end.y - 4.0).abs() < 1e-10);
+        assert!((wall.length() - 4.0).abs() < 1e-10);
+        assert!((wall.thickness - 0.2).abs() < 1e-10);
+        assert!((wall.height - 3.0).abs() < 1e-10);
+
+        wall.translate(Vector2::new(-1.0, 2.0));
+        assert!(wall.baseline.start.x.abs() < 1e-10);
+        assert!((wall.baseline.start.y - 2.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn mirror_reverses_baseline_and_openings() {
+        let mut wall = Wall::new(Point2::new(1.0, 0.0), Point2::new(7.0, 0.0), 3.0, 0.2).unwrap();
+        wall.add_opening(WallOpening::new(1.5, 0.0, 0.9, 2.1, OpeningType::Door))
+            .unwrap();
+        wall.set_top_profile(Some(TopProfile::Sloped {
+            start_height: 3.0,
+            end_height: 4.0,
+        }))
+        .unwrap();
+        let opening_center = wall.baseline.point_at(1.5 / wall.length());
+
+        // Mirror across x = 0
+        let axis = Line2::from_points(Point2::new(0.0, 0.0), Point2::new(0.0, 1.0)).unwrap();
+        wall.mirror(&axis);
+
+        assert!((wall.baseline.start.x - (-7.0)).abs() < 1e-10);
+        assert!((wall.baseline.end.x - (-1.0)).abs() < 1e-10);
+        assert!((wall.openings[0].offset_along_wall - 4.5).abs() < 1e-10);
+        let mirrored_center = wall
+            .baseline
+            .point_at(wall.openings[0].offset_along_wall / wall.length());
+        assert!((mirrored_center.x + opening_center.x).abs() < 1e-10);
+        assert!((wall.top_height_at(0.0) - 4.0).abs() < 1e-10);
+        // The +Y face is still on the wall's left
+        assert!((wall.normal().unwrap().y - 1.0).abs() < 1e-10);
+    }
 }
@@ -1,38 +1,125 @@
 //! Error types for pensaer-geometry crate.
 
+use pensaer_math::Point2;
+use serde_json::{json, Value};
 use thiserror::Error;
 
 /// Errors that can occur in geometry operations.
-#[derive(Debug, Clone, PartialEq, Eq, Error)]
+///
+/// Variants carry the offending values where there are any, and each has a
+/// stable [`error_code`](GeometryError::error_code) for callers that need
+/// to tell errors apart without parsing the message.
+#[derive(Debug, Clone, PartialEq, Error)]
 pub enum GeometryError {
     /// Wall baseline has zero length.
     #[error("wall baseline has zero length")]
     ZeroLengthWall,
 
     /// Height must be positive.
-    #[error("height must be positive")]
-    NonPositiveHeight,
+    #[error("height must be positive, got {value}")]
+    NonPositiveHeight { value: f64 },
 
     /// Thickness must be positive.
-    #[error("thickness must be positive")]
-    NonPositiveThickness,
+    #[error("thickness must be positive, got {value}")]
+    NonPositiveThickness { value: f64 },
 
     /// Floor bounds are invalid (min >= max).
-    #[error("floor bounds are invalid")]
-    InvalidFloorBounds,
+    #[error(
+        "floor bounds are invalid: min ({}, {}) must be below max ({}, {})",
+        .min.x, .min.y, .max.x, .max.y
+    )]
+    InvalidFloorBounds { min: Point2, max: Point2 },
+
+    /// Floor boundary is too small or not an enclosed interior region.
+    #[error("invalid floor: {0}")]
+    InvalidFloor(String),
 
     /// Polygon has fewer than 3 vertices.
     #[error("polygon must have at least 3 vertices")]
     InsufficientVertices,
 
     /// Opening extends beyond wall bounds.
-    #[error("opening extends beyond wall bounds")]
-    OpeningOutOfBounds,
+    #[error(
+        "opening at offset {offset} with width {width} extends beyond wall of length {wall_length}"
+    )]
+    OpeningOutOfBounds {
+        offset: f64,
+        width: f64,
+        wall_length: f64,
+    },
 
     /// Opening overlaps with existing opening.
     #[error("opening overlaps with existing opening")]
     OverlappingOpenings,
 
+    /// Roof opening dimensions are invalid.
+    #[error("invalid roof opening: {0}")]
+    InvalidRoofOpening(String),
+
+    /// Roof opening crosses a ridge or hip line.
+    #[error("roof opening spans a ridge or hip; place it on a single slope")]
+    OpeningSpansRidge,
+
+    /// Roof slope profile does not fit the roof.
+    #[error("invalid roof profile: {0}")]
+    InvalidRoofProfile(String),
+
+    /// Spacing must be positive.
+    #[error("spacing must be positive, got {value}")]
+    NonPositiveSpacing { value: f64 },
+
+    /// Railing path has fewer than two points or a segment with no horizontal extent.
+    #[error("railing path must have at least two points, each segment with a horizontal run")]
+    DegenerateRailingPath,
+
+    /// Split point is not strictly inside the wall.
+    #[error("split point must lie strictly inside the wall")]
+    InvalidSplitOffset,
+
+    /// Walls passed as a chain do not connect end to start.
+    #[error("walls do not form a connected chain")]
+    DisconnectedWallChain,
+
+    /// Walls do not close around any area.
+    #[error("walls do not enclose an area")]
+    WallsNotEnclosed,
+
+    /// Wall top profile does not fit the wall.
+    #[error("invalid wall top profile: {0}")]
+    InvalidTopProfile(String),
+
+    /// Stair parameters are inconsistent.
+    #[error("invalid stair: {0}")]
+    InvalidStair(String),
+
+    /// Ceiling parameters or openings are invalid.
+    #[error("invalid ceiling: {0}")]
+    InvalidCeiling(String),
+
+    /// Door or window frame does not fit its opening.
+    #[error("invalid frame: {0}")]
+    InvalidFrame(String),
+
+    /// Room boundary is too small or not an enclosed interior region.
+    #[error("invalid room: {0}")]
+    InvalidRoom(String),
+
+    /// Schedule row does not match the schedule's columns.
+    #[error("invalid schedule row: {0}")]
+    InvalidScheduleRow(String),
+
+    /// Property schema could not be loaded.
+    #[error("invalid property schema: {0}")]
+    InvalidPropertySchema(String),
+
+    /// Door or window type catalog is invalid.
+    #[error("invalid type catalog: {0}")]
+    InvalidCatalog(String),
+
+    /// Property was rejected by its schema.
+    #[error("property rejected: {0}")]
+    PropertyRejected(String),
+
     /// Invalid element ID reference.
     #[error("invalid element reference: {0}")]
     InvalidElementRef(String),
@@ -41,10 +128,18 @@ pub enum GeometryError {
     #[error("mesh has invalid vertex indices")]
     InvalidMeshIndices,
 
+    /// Mesh file could not be read or parsed.
+    #[error("mesh import failed: {0}")]
+    MeshImport(String),
+
     /// Triangulation failed.
     #[error("triangulation failed: {0}")]
     TriangulationFailed(String),
 
+    /// Centerline extraction failed.
+    #[error("centerline extraction failed: {0}")]
+    CenterlineFailed(String),
+
     /// Invalid join configuration.
     #[error("invalid wall join configuration")]
     InvalidJoinConfiguration,
@@ -53,10 +148,181 @@ pub enum GeometryError {
     #[error("join computation failed: {0}")]
     JoinComputationFailed(String),
 
+    /// An opening reaches into the region a join trims off a wall end.
+    #[error("opening conflicts with wall join: {0}")]
+    OpeningConflictsWithJoin(String),
+
+    /// Wall baselines are parallel, so they never meet.
+    #[error("wall baselines are parallel")]
+    ParallelBaselines,
+
+    /// Walls that must share a direction are not parallel.
+    #[error("walls are not parallel")]
+    NonParallelWalls,
+
+    /// Extension would move an endpoint further than allowed.
+    #[error("extension too long: {0}")]
+    ExtensionTooLong(String),
+
+    /// Saved model uses a schema version this build cannot read.
+    #[error("unsupported model schema version: {0}")]
+    UnsupportedSchemaVersion(String),
+
+    /// Saved model is not a valid model document.
+    #[error("invalid model document: {0}")]
+    InvalidModelDocument(String),
+
+    /// Meshing an element panicked.
+    #[error("meshing panicked: {0}")]
+    MeshingPanicked(String),
+
+    /// A kernel operation failed or was rolled back.
+    #[error("operation failed: {0}")]
+    OperationFailed(String),
+
     /// Math error propagated from pensaer-math.
     #[error("math error: {0}")]
     MathError(#[from] pensaer_math::MathError),
 }
 
+impl GeometryError {
+    /// Stable machine-readable code for the variant, e.g.
+    /// `"non_positive_thickness"`.
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            GeometryError::ZeroLengthWall => "zero_length_wall",
+            GeometryError::NonPositiveHeight { .. } => "non_positive_height",
+            GeometryError::NonPositiveThickness { .. } => "non_positive_thickness",
+            GeometryError::InvalidFloorBounds { .. } => "invalid_floor_bounds",
+            GeometryError::InvalidFloor(_) => "invalid_floor",
+            GeometryError::InsufficientVertices => "insufficient_vertices",
+            GeometryError::OpeningOutOfBounds { .. } => "opening_out_of_bounds",
+            GeometryError::OverlappingOpenings => "overlapping_openings",
+            GeometryError::InvalidRoofOpening(_) => "invalid_roof_opening",
+            GeometryError::OpeningSpansRidge => "opening_spans_ridge",
+            GeometryError::InvalidRoofProfile(_) => "invalid_roof_profile",
+            GeometryError::NonPositiveSpacing { .. } => "non_positive_spacing",
+            GeometryError::DegenerateRailingPath => "degenerate_railing_path",
+            GeometryError::InvalidSplitOffset => "invalid_split_offset",
+            GeometryError::DisconnectedWallChain => "disconnected_wall_chain",
+            GeometryError::WallsNotEnclosed => "walls_not_enclosed",
+            GeometryError::InvalidTopProfile(_) => "invalid_top_profile",
+            GeometryError::InvalidStair(_) => "invalid_stair",
+            GeometryError::InvalidCeiling(_) => "invalid_ceiling",
+            GeometryError::InvalidFrame(_) => "invalid_frame",
+            GeometryError::InvalidRoom(_) => "invalid_room",
+            GeometryError::InvalidScheduleRow(_) => "invalid_schedule_row",
+            GeometryError::InvalidPropertySchema(_) => "invalid_property_schema",
+            GeometryError::InvalidCatalog(_) => "invalid_catalog",
+            GeometryError::PropertyRejected(_) => "property_rejected",
+            GeometryError::InvalidElementRef(_) => "invalid_element_ref",
+            GeometryError::InvalidMeshIndices => "invalid_mesh_indices",
+            GeometryError::MeshImport(_) => "mesh_import",
+            GeometryError::TriangulationFailed(_) => "triangulation_failed",
+            GeometryError::CenterlineFailed(_) => "centerline_failed",
+            GeometryError::InvalidJoinConfiguration => "invalid_join_configuration",
+            GeometryError::JoinComputationFailed(_) => "join_computation_failed",
+            GeometryError::OpeningConflictsWithJoin(_) => "opening_conflicts_with_join",
+            GeometryError::ParallelBaselines => "parallel_baselines",
+            GeometryError::NonParallelWalls => "non_parallel_walls",
+            GeometryError::ExtensionTooLong(_) => "extension_too_long",
+            GeometryError::UnsupportedSchemaVersion(_) => "unsupported_schema_version",
+            GeometryError::InvalidModelDocument(_) => "invalid_model_document",
+            GeometryError::MeshingPanicked(_) => "meshing_panicked",
+            GeometryError::OperationFailed(_) => "operation_failed",
+            GeometryError::MathError(_) => "math_error",
+        }
+    }
+
+    /// The offending values, as a JSON object. Variants without values
+    /// give an empty object; those with a message give it as `"reason"`.
+    pub fn details(&self) -> Value {
+        match self {
+            GeometryError::NonPositiveHeight { value }
+            | GeometryError::NonPositiveThickness { value }
+            | GeometryError::NonPositiveSpacing { value } => json!({ "value": value }),
+            GeometryError::InvalidFloorBounds { min, max } => {
+                json!({ "min": [min.x, min.y], "max": [max.x, max.y] })
+            }
+            GeometryError::OpeningOutOfBounds {
+                offset,
+                width,
+                wall_length,
+            } => json!({ "offset": offset, "width": width, "wall_length": wall_length }),
+            GeometryError::InvalidFloor(reason)
+            | GeometryError::InvalidRoofOpening(reason)
+            | GeometryError::InvalidRoofProfile(reason)
+            | GeometryError::InvalidTopProfile(reason)
+            | GeometryError::InvalidStair(reason)
+            | GeometryError::InvalidCeiling(reason)
+            | GeometryError::InvalidFrame(reason)
+            | GeometryError::InvalidRoom(reason)
+            | GeometryError::InvalidScheduleRow(reason)
+            | GeometryError::InvalidPropertySchema(reason)
+            | GeometryError::InvalidCatalog(reason)
+            | GeometryError::PropertyRejected(reason)
+            | GeometryError::InvalidElementRef(reason)
+            | GeometryError::MeshImport(reason)
+            | GeometryError::TriangulationFailed(reason)
+            | GeometryError::CenterlineFailed(reason)
+            | GeometryError::JoinComputationFailed(reason)
+            | GeometryError::OpeningConflictsWithJoin(reason)
+            | GeometryError::ExtensionTooLong(reason)
+            | GeometryError::UnsupportedSchemaVersion(reason)
+            | GeometryError::InvalidModelDocument(reason)
+            | GeometryError::MeshingPanicked(reason)
+            | GeometryError::OperationFailed(reason) => {
+                json!({ "reason": reason })
+            }
+            GeometryError::MathError(e) => json!({ "reason": e.to_string() }),
+            _ => json!({}),
+        }
+    }
+
+    /// The error as JSON: its code, its message, and its details.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "code": self.error_code(),
+            "message": self.to_string(),
+            "details": self.details(),
+        })
+    }
+}
+
 /// Result type for geometry operations.
 pub type GeometryResult<T> = Result<T, GeometryError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_carry_codes_and_details() {
+        let err = GeometryError::OpeningOutOfBounds {
+            offset: 3.5,
+            width: 1.0,
+            wall_length: 4.0,
+        };
+        let json = err.to_json();
+        assert_eq!(json["code"], "opening_out_of_bounds");
+        assert_eq!(json["details"]["wall_length"], 4.0);
+        assert_eq!(
+            json["message"],
+            "opening at offset 3.5 with width 1 extends beyond wall of length 4"
+        );
+
+        let err = GeometryError::InvalidFloorBounds {
+            min: Point2::new(1.0, 1.0),
+            max: Point2::new(1.0, 2.0),
+        };
+        assert_eq!(err.error_code(), "invalid_floor_bounds");
+        assert_eq!(
+            err.details(),
+            json!({ "min": [1.0, 1.0], "max": [1.0, 2.0] })
+        );
+
+        let err = GeometryError::InvalidStair("no risers".to_string());
+        assert_eq!(err.details(), json!({ "reason": "no risers" }));
+        assert_eq!(GeometryError::ZeroLengthWall.details(), json!({}));
+    }
+}
@@ -5,7 +5,26 @@
 //! 2. Runs all fixup passes (snap, split, merge, rooms)
 //! 3. Returns a delta describing what changed
 //!
-//! This ensures the model is always in a valid, healed state.
+//! This ensures the model is always in a valid, healed state. If healing
+//! can't repair what an operation did (a non-finite position, a degenerate
+//! edge, or a room with non-finite area), the model is restored to its state before
+//! the operation and the result is flagged as rolled back. Hooks
+//! registered on the context are notified after each committed operation
+//! (see [`crate::hooks`]), and each one is appended to the context's op log
+//! for replication.
+//!
+//! # Operations
+//!
+//! | Method | Parameters |
+//! |--------|------------|
+//! | `add_wall` | `start`, `end`, optional `height`, `thickness` |
+//! | `move_node` | `node_id`, `position` |
+//! | `move_wall` | `edge_id`, `offset` (translates both end nodes) |
+//! | `delete_edge` | `edge_id` |
+//! | `set_edge_properties` | `edge_id`, `thickness` and/or `height` |
+//!
+//! Points are `[x, y]` arrays in mm. IDs are UUID strings, optionally
+//! prefixed `node_` or `edge_`. Pinned nodes can't be moved.
 //!
 //! # Example
 //!
@@ -25,10 +44,36 @@
 //! // result contains the healed delta
 //! ```
 
-use crate::fixup::{self, Delta};
+use std::collections::HashSet;
+
+use crate::element::ElementType;
+use crate::elements::{Door, Room, Window};
+use crate::fixup::{self, Delta, RemovedRoomPolicy};
+use crate::hooks::{self, HookFailure, HookRegistry, KernelEvent, KernelEventKind};
+use crate::ids::{IdGenerator, IdMode, IdPolicy, ELEMENT_ID_NAMESPACE};
 use crate::io::{prepare_input, prepare_output};
-use crate::topology::TopologyGraph;
+use crate::spatial::{Clash, ClashDetector, ClashElement};
+use crate::templates::{self, TemplateInstance};
+use crate::topology::{EdgeData, EdgeId, NodeId, TopologyGraph};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use uuid::Uuid;
+
+/// Wall height used by `add_wall` when none is given (mm)
+const DEFAULT_WALL_HEIGHT: f64 = 2700.0;
+
+/// Wall thickness used by `add_wall` when none is given (mm)
+const DEFAULT_WALL_THICKNESS: f64 = 200.0;
+
+/// Kernel behavior switches for a session.
+#[derive(Debug, Clone, Default)]
+pub struct KernelConfig {
+    /// Create Room elements for newly enclosed spaces on each commit
+    /// (off by default)
+    pub auto_rooms: bool,
+    /// What auto-rooming does with rooms whose walls were removed
+    pub removed_room_policy: RemovedRoomPolicy,
+}
 
 /// Execution context containing the model and metadata.
 pub struct Context {
@@ -38,6 +83,27 @@ pub struct Context {
     pub session_id: Option<String>,
     /// User ID for audit logging
     pub user_id: Option<String>,
+    /// Placed wall group templates
+    pub template_instances: Vec<TemplateInstance>,
+    /// Event hooks for this session
+    pub hooks: HookRegistry,
+    /// Number of committed operations
+    pub revision: u64,
+    /// Kernel behavior switches
+    pub config: KernelConfig,
+    /// IDs for new walls, auto-created rooms, and placed templates (random
+    /// by default)
+    pub ids: Box<dyn IdGenerator>,
+    /// Room elements kept in step with detected rooms by auto-rooming
+    pub rooms: Vec<Room>,
+    /// Door elements, each sharing its ID with the wall opening it fills
+    pub doors: Vec<Door>,
+    /// Window elements, each sharing its ID with the wall opening it fills
+    pub windows: Vec<Window>,
+    /// Operations committed through [`exec_and_heal`], oldest first
+    pub op_log: Vec<OpLogEntry>,
+    /// Clashes found by the last [`Context::detect_clashes`] run
+    pub clashes: Vec<Clash>,
 }
 
 impl Context {
@@ -47,6 +113,16 @@ impl Context {
             graph: TopologyGraph::new(),
             session_id: None,
             user_id: None,
+            template_instances: Vec::new(),
+            hooks: HookRegistry::new(),
+            revision: 0,
+            config: KernelConfig::default(),
+            ids: Box::new(IdPolicy::default()),
+            rooms: Vec::new(),
+            doors: Vec::new(),
+            windows: Vec::new(),
+            op_log: Vec::new(),
+            clashes: Vec::new(),
         }
     }
 
@@ -56,8 +132,203 @@ impl Context {
             graph: TopologyGraph::new(),
             session_id: Some(session_id),
             user_id: Some(user_id),
+            template_instances: Vec::new(),
+            hooks: HookRegistry::new(),
+            revision: 0,
+            config: KernelConfig::default(),
+            ids: Box::new(IdPolicy::default()),
+            rooms: Vec::new(),
+            doors: Vec::new(),
+            windows: Vec::new(),
+            op_log: Vec::new(),
+            clashes: Vec::new(),
         }
     }
+
+    /// Switch new walls and auto-created rooms to content-hashed IDs.
+    ///
+    /// Replaying the same operations then yields the same IDs, so exports
+    /// of the same logical model match. Hashes are taken under
+    /// [`ELEMENT_ID_NAMESPACE`]; turning this off goes back to random IDs.
+    pub fn deterministic_ids(self, enabled: bool) -> Self {
+        let mode = if enabled {
+            IdMode::ContentHash {
+                namespace: ELEMENT_ID_NAMESPACE,
+            }
+        } else {
+            IdMode::Random
+        };
+        self.with_id_generator(IdPolicy::new(mode))
+    }
+
+    /// Make IDs for new walls and auto-created rooms with `ids`.
+    pub fn with_id_generator(mut self, ids: impl IdGenerator + 'static) -> Self {
+        self.ids = Box::new(ids);
+        self
+    }
+
+    /// Heal the model after a mutation and notify hooks.
+    ///
+    /// Edges that healing splits, merges, or removes are recorded in
+    /// `delta` alongside the mutation's own changes.
+    ///
+    /// With [`KernelConfig::auto_rooms`] set, Room elements are then synced
+    /// with the detected rooms and the changes added to `delta`. Bumps the
+    /// revision, dispatches element, room, and heal events, then runs any
+    /// follow-up operations the handlers queued.
+    ///
+    /// # Returns
+    /// Number of rooms after healing
+    pub fn commit(&mut self, delta: &mut Delta) -> usize {
+        let room_count = self.heal(delta);
+        self.publish(delta, room_count);
+        room_count
+    }
+
+    /// Capture what an operation can change, so a failed one can be undone.
+    ///
+    /// Operations only ever append doors, windows, and template instances,
+    /// so those are captured as lengths rather than copied, and Room
+    /// elements are only copied when auto-rooming can change them.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            graph: self.graph.clone(),
+            rooms: self.config.auto_rooms.then(|| self.rooms.clone()),
+            doors: self.doors.len(),
+            windows: self.windows.len(),
+            template_instances: self.template_instances.len(),
+        }
+    }
+
+    /// Put the model back as it was when `snapshot` was taken.
+    ///
+    /// The revision, op log, and hooks are left alone.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.graph = snapshot.graph;
+        if let Some(rooms) = snapshot.rooms {
+            self.rooms = rooms;
+        }
+        self.doors.truncate(snapshot.doors);
+        self.windows.truncate(snapshot.windows);
+        self.template_instances
+            .truncate(snapshot.template_instances);
+    }
+
+    /// Run the healing passes and, with auto-rooming on, sync Room elements.
+    fn heal(&mut self, delta: &mut Delta) -> usize {
+        let room_count = fixup::heal_all_tracked(&mut self.graph, delta);
+        if self.config.auto_rooms {
+            fixup::sync_room_elements(
+                &self.graph,
+                &mut self.rooms,
+                self.config.removed_room_policy,
+                self.ids.as_mut(),
+                delta,
+            );
+        }
+        room_count
+    }
+
+    /// Bump the revision and notify hooks of a healed change.
+    fn publish(&mut self, delta: &Delta, room_count: usize) {
+        self.revision += 1;
+
+        let element_events = [
+            (KernelEventKind::ElementCreated, &delta.created, "created"),
+            (
+                KernelEventKind::ElementModified,
+                &delta.modified,
+                "modified",
+            ),
+            (KernelEventKind::ElementDeleted, &delta.deleted, "deleted"),
+        ];
+        for (kind, ids, verb) in element_events {
+            if !ids.is_empty() {
+                self.dispatch(
+                    kind,
+                    ids.clone(),
+                    format!("{} elements {}", ids.len(), verb),
+                );
+            }
+        }
+
+        if delta.rooms_rebuilt {
+            let ids = self
+                .graph
+                .room_ids()
+                .iter()
+                .map(|id| id.0.to_string())
+                .collect();
+            self.dispatch(
+                KernelEventKind::RoomsRebuilt,
+                ids,
+                format!("{} rooms", room_count),
+            );
+        }
+
+        self.dispatch(
+            KernelEventKind::HealCompleted,
+            delta.affected_nodes.clone(),
+            format!("{} rooms", room_count),
+        );
+
+        hooks::run_follow_ups(self);
+    }
+
+    /// Notify hooks of an event raised outside [`Context::commit`], such as
+    /// new clash results, then run queued follow-ups.
+    pub fn emit(&mut self, kind: KernelEventKind, ids: Vec<String>, summary: impl Into<String>) {
+        self.dispatch(kind, ids, summary.into());
+        hooks::run_follow_ups(self);
+    }
+
+    /// Re-run clash detection over `elements` and keep the results.
+    ///
+    /// Raises [`KernelEventKind::ClashResultsChanged`] with the IDs of the
+    /// clashing elements when the set of clashing pairs differs from the
+    /// previous run.
+    pub fn detect_clashes(
+        &mut self,
+        detector: &ClashDetector,
+        elements: &[ClashElement],
+    ) -> &[Clash] {
+        let clashes = detector.detect_clashes_in_list(elements);
+
+        // Clash IDs are fresh on every run, so compare the pairs instead
+        let pair = |c: &Clash| {
+            let (a, b) = if c.element_a_id <= c.element_b_id {
+                (c.element_a_id, c.element_b_id)
+            } else {
+                (c.element_b_id, c.element_a_id)
+            };
+            (a, b, c.clash_type.name())
+        };
+        let before: HashSet<_> = self.clashes.iter().map(pair).collect();
+        let after: HashSet<_> = clashes.iter().map(pair).collect();
+        self.clashes = clashes;
+
+        if before != after {
+            let mut ids: Vec<Uuid> = after.iter().flat_map(|&(a, b, _)| [a, b]).collect();
+            ids.sort();
+            ids.dedup();
+            self.emit(
+                KernelEventKind::ClashResultsChanged,
+                ids.iter().map(|id| id.to_string()).collect(),
+                format!("{} clashes", self.clashes.len()),
+            );
+        }
+        &self.clashes
+    }
+
+    fn dispatch(&mut self, kind: KernelEventKind, ids: Vec<String>, summary: String) {
+        let event = KernelEvent {
+            kind,
+            ids,
+            revision: self.revision,
+            summary,
+        };
+        self.hooks.dispatch(&event);
+    }
 }
 
 impl Default for Context {
@@ -66,6 +337,36 @@ impl Default for Context {
     }
 }
 
+/// The parts of a context's model an operation can change, taken by
+/// [`Context::snapshot`].
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    graph: TopologyGraph,
+    /// Room elements, if auto-rooming was on
+    rooms: Option<Vec<Room>>,
+    doors: usize,
+    windows: usize,
+    template_instances: usize,
+}
+
+/// A committed operation, as recorded in [`Context::op_log`].
+///
+/// Holds the quantized parameters the operation ran with, so a replica can
+/// replay the log in order through [`exec_and_heal`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpLogEntry {
+    /// Revision the operation committed as
+    pub revision: u64,
+    /// The RPC method name
+    pub method: String,
+    /// The quantized method parameters
+    pub params: Value,
+    /// Session that ran the operation
+    pub session_id: Option<String>,
+    /// User that ran the operation
+    pub user_id: Option<String>,
+}
+
 /// Result of executing a command.
 #[derive(Debug)]
 pub struct ExecResult {
@@ -77,6 +378,13 @@ pub struct ExecResult {
     pub error: Option<String>,
     /// Additional data returned by the operation
     pub data: Option<Value>,
+    /// Hook handlers that failed while the operation was committed
+    pub hook_failures: Vec<HookFailure>,
+    /// Op log entry for the committed operation
+    pub op: Option<OpLogEntry>,
+    /// Whether the operation ran but was undone because healing couldn't
+    /// repair the model
+    pub rolled_back: bool,
 }
 
 impl ExecResult {
@@ -87,6 +395,9 @@ impl ExecResult {
             delta: Some(delta),
             error: None,
             data,
+            hook_failures: Vec::new(),
+            op: None,
+            rolled_back: false,
         }
     }
 
@@ -97,6 +408,17 @@ impl ExecResult {
             delta: None,
             error: Some(message.into()),
             data: None,
+            hook_failures: Vec::new(),
+            op: None,
+            rolled_back: false,
+        }
+    }
+
+    /// Create an error result for an operation that was undone.
+    pub fn rolled_back(message: impl Into<String>) -> Self {
+        Self {
+            rolled_back: true,
+            ..Self::err(message)
         }
     }
 
@@ -110,12 +432,33 @@ impl ExecResult {
             if let Some(data) = &self.data {
                 result["data"] = data.clone();
             }
+            if !self.hook_failures.is_empty() {
+                let failures: Vec<Value> = self
+                    .hook_failures
+                    .iter()
+                    .map(|f| {
+                        serde_json::json!({
+                            "handle": f.handle.0,
+                            "event": f.event.name(),
+                            "message": f.message
+                        })
+                    })
+                    .collect();
+                result["hook_failures"] = Value::Array(failures);
+            }
+            if let Some(op) = &self.op {
+                result["op"] = serde_json::to_value(op).unwrap_or(Value::Null);
+            }
             prepare_output(&result)
         } else {
-            serde_json::json!({
+            let mut result = serde_json::json!({
                 "success": false,
                 "error": self.error.as_deref().unwrap_or("Unknown error")
-            })
+            });
+            if self.rolled_back {
+                result["rolled_back"] = Value::Bool(true);
+            }
+            result
         }
     }
 }
@@ -131,24 +474,130 @@ impl ExecResult {
 /// * `ctx` - The execution context containing the model
 ///
 /// # Returns
-/// An `ExecResult` with the delta and any additional data
+/// An `ExecResult` with the delta and any additional data, or a rolled-back
+/// error if the healed model breaks an invariant
 pub fn exec_and_heal(method: &str, params: &Value, ctx: &mut Context) -> ExecResult {
+    match method {
+        // Doors and windows are hosted on the walls healing leaves
+        "place_template" => exec_staged(
+            method,
+            params,
+            ctx,
+            templates::stamp_template,
+            templates::finish_template,
+        ),
+        _ => exec_with(method, params, ctx, |params, ctx| {
+            dispatch(method, params, ctx)
+        }),
+    }
+}
+
+/// Run `handler` as `method` with healing, rollback, logging, and hooks.
+fn exec_with(
+    method: &str,
+    params: &Value,
+    ctx: &mut Context,
+    handler: impl FnOnce(&Value, &mut Context) -> Result<(Delta, Option<Value>), String>,
+) -> ExecResult {
+    exec_staged(method, params, ctx, handler, |data, _, _| Ok(data))
+}
+
+/// Like [`exec_with`], with `finish` completing the operation on the
+/// healed model before it is logged; it gets what `handler` passed on.
+fn exec_staged<S>(
+    method: &str,
+    params: &Value,
+    ctx: &mut Context,
+    handler: impl FnOnce(&Value, &mut Context) -> Result<(Delta, S), String>,
+    finish: impl FnOnce(S, &Delta, &mut Context) -> Result<Option<Value>, String>,
+) -> ExecResult {
     // 1. Quantize input parameters
     let params = prepare_input(params);
 
-    // 2. Dispatch to the appropriate handler
-    let result = dispatch(method, &params, ctx);
+    // 2. Run the handler
+    let snapshot = ctx.snapshot();
+    let result = handler(&params, ctx);
 
     match result {
-        Ok((delta, data)) => {
-            // 3. Run healing passes
-            fixup::heal_all(&mut ctx.graph, &delta);
+        Ok((mut delta, staged)) => {
+            // 3. Run healing passes, undoing the operation if they can't
+            //    repair the model. Healing can't reason about non-finite
+            //    positions, so those are caught before it runs.
+            let healed = check_positions(&ctx.graph).and_then(|()| {
+                let room_count = ctx.heal(&mut delta);
+                check_invariants(&ctx.graph).map(|()| room_count)
+            });
+            let healed = healed
+                .and_then(|room_count| finish(staged, &delta, ctx).map(|data| (room_count, data)));
+            let (room_count, data) = match healed {
+                Ok(healed) => healed,
+                Err(violation) => {
+                    ctx.restore(snapshot);
+                    return ExecResult::rolled_back(format!(
+                        "'{}' rolled back: {}",
+                        method, violation
+                    ));
+                }
+            };
+
+            // 4. Log the operation ahead of any follow-ups its hooks run
+            let op = OpLogEntry {
+                revision: ctx.revision + 1,
+                method: method.to_string(),
+                params,
+                session_id: ctx.session_id.clone(),
+                user_id: ctx.user_id.clone(),
+            };
+            ctx.op_log.push(op.clone());
+
+            // 5. Notify hooks
+            ctx.publish(&delta, room_count);
+
+            // 6. Return healed result
+            let mut result = ExecResult::ok(delta, data);
+            result.hook_failures = ctx.hooks.take_failures();
+            result.op = Some(op);
+            result
+        }
+        Err(e) => {
+            // The handler may have failed partway through its edits
+            ctx.restore(snapshot);
+            ExecResult::err(e)
+        }
+    }
+}
 
-            // 4. Return healed result
-            ExecResult::ok(delta, data)
+/// Fail if any node has a non-finite position.
+fn check_positions(graph: &TopologyGraph) -> Result<(), String> {
+    for node in graph.nodes() {
+        if !node.position.iter().all(|v| v.is_finite()) {
+            return Err(format!("node {} has a non-finite position", node.id.0));
         }
-        Err(e) => ExecResult::err(e),
     }
+    Ok(())
+}
+
+/// Check the invariants healing is expected to leave in place.
+///
+/// Every node has a finite position, every edge is at least the snap
+/// tolerance long, and every room has a finite area.
+fn check_invariants(graph: &TopologyGraph) -> Result<(), String> {
+    check_positions(graph)?;
+    for edge in graph.edges() {
+        let length = graph
+            .edge_positions(edge.id)
+            .map(|([x0, y0], [x1, y1])| (x1 - x0).hypot(y1 - y0))
+            .unwrap_or(0.0);
+        if length < graph.snap_tolerance() {
+            return Err(format!("edge {} is degenerate", edge.id.0));
+        }
+    }
+    for room in graph.rooms() {
+        if !room.signed_area.is_finite() {
+            return Err(format!("room {} has a non-finite area", room.id.0));
+        }
+    }
+    Ok(())
 }
 
 /// Dispatch to the appropriate method handler.
@@ -162,6 +611,9 @@ fn dispatch(
     match method {
         "add_wall" => handle_add_wall(params, ctx),
         "move_node" => handle_move_node(params, ctx),
+        "move_wall" => handle_move_wall(params, ctx),
+        "delete_edge" => handle_delete_edge(params, ctx),
+        "set_edge_properties" => handle_set_edge_properties(params, ctx),
         "delete_element" => handle_delete_element(params, ctx),
         "solve_joins" => handle_solve_joins(params, ctx),
         _ => Err(format!("Unknown method: {}", method)),
@@ -169,46 +621,116 @@ fn dispatch(
 }
 
 // ============================================================================
-// Method Handlers (stubs for M0, implemented in M2)
+// Method Handlers
 // ============================================================================
 
-fn handle_add_wall(params: &Value, _ctx: &mut Context) -> Result<(Delta, Option<Value>), String> {
-    // TODO: Implement in M2
-    // 1. Parse start, end, height, thickness from params
-    // 2. Create or find start/end nodes
-    // 3. Create wall edge between nodes
-    // 4. Return delta with created IDs
+fn handle_add_wall(params: &Value, ctx: &mut Context) -> Result<(Delta, Option<Value>), String> {
+    let start = parse_point(params, "start")?;
+    let end = parse_point(params, "end")?;
+    let height = parse_length(params, "height")?.unwrap_or(DEFAULT_WALL_HEIGHT);
+    let thickness = parse_length(params, "thickness")?.unwrap_or(DEFAULT_WALL_THICKNESS);
 
-    let _start = params.get("start").ok_or("Missing 'start' parameter")?;
-    let _end = params.get("end").ok_or("Missing 'end' parameter")?;
+    let params = [start[0], start[1], end[0], end[1], height, thickness];
+    let edge_id = EdgeId::from_uuid(ctx.ids.next_id(ElementType::Wall.name(), &[], &params));
+    if ctx.graph.get_edge(edge_id).is_some() {
+        return Err(format!("Wall ID already in use: {}", edge_id.0));
+    }
+    ctx.graph
+        .add_edge_with_id(edge_id, start, end, EdgeData::wall(thickness, height))
+        .ok_or("Wall start and end coincide")?;
+
+    let mut delta = Delta::new();
+    delta.created.push(edge_id.0.to_string());
+    add_end_nodes(&ctx.graph, edge_id, &mut delta);
+
+    let data = serde_json::json!({ "wall_id": edge_id.0.to_string() });
+    Ok((delta, Some(data)))
+}
+
+fn handle_move_node(params: &Value, ctx: &mut Context) -> Result<(Delta, Option<Value>), String> {
+    let node_id = parse_node_id(params, "node_id")?;
+    let position = parse_point(params, "position")?;
+    check_movable(&ctx.graph, node_id)?;
+
+    let mut delta = Delta::new();
+    add_edges_at_node(&ctx.graph, node_id, &mut delta);
+    delta.affected_nodes.push(node_id.0.to_string());
+    ctx.graph.move_node(node_id, position);
+
+    Ok((delta, None))
+}
+
+fn handle_move_wall(params: &Value, ctx: &mut Context) -> Result<(Delta, Option<Value>), String> {
+    let edge_id = parse_edge_id(params, "edge_id")?;
+    let [dx, dy] = parse_point(params, "offset")?;
+    let (start_node, end_node) = ctx
+        .graph
+        .get_edge(edge_id)
+        .map(|e| e.nodes())
+        .ok_or_else(|| format!("Edge not found: {}", edge_id.0))?;
+    check_movable(&ctx.graph, start_node)?;
+    check_movable(&ctx.graph, end_node)?;
+
+    // Walls joined at either end follow, so they are modified too
+    let mut delta = Delta::new();
+    for node_id in [start_node, end_node] {
+        add_edges_at_node(&ctx.graph, node_id, &mut delta);
+        delta.affected_nodes.push(node_id.0.to_string());
+    }
+    for node_id in [start_node, end_node] {
+        let [x, y] = ctx
+            .graph
+            .get_node(node_id)
+            .map(|n| n.position)
+            .unwrap_or_default();
+        ctx.graph.move_node(node_id, [x + dx, y + dy]);
+    }
+
+    Ok((delta, None))
+}
+
+fn handle_delete_edge(params: &Value, ctx: &mut Context) -> Result<(Delta, Option<Value>), String> {
+    let edge_id = parse_edge_id(params, "edge_id")?;
+    let edge = ctx
+        .graph
+        .remove_edge(edge_id)
+        .ok_or_else(|| format!("Edge not found: {}", edge_id.0))?;
 
     let delta = Delta {
-        created: vec!["wall_placeholder".to_string()],
-        modified: vec![],
-        deleted: vec![],
-        affected_nodes: vec![],
+        deleted: vec![edge_id.0.to_string()],
+        affected_nodes: vec![edge.start_node.0.to_string(), edge.end_node.0.to_string()],
+        ..Default::default()
     };
 
-    let data = serde_json::json!({
-        "wall_id": "wall_placeholder",
-        "message": "add_wall stub - implement in M2"
-    });
-
-    Ok((delta, Some(data)))
+    Ok((delta, None))
 }
 
-fn handle_move_node(params: &Value, _ctx: &mut Context) -> Result<(Delta, Option<Value>), String> {
-    // TODO: Implement in M2
-    let _node_id = params.get("node_id").ok_or("Missing 'node_id' parameter")?;
-    let _position = params
-        .get("position")
-        .ok_or("Missing 'position' parameter")?;
+fn handle_set_edge_properties(
+    params: &Value,
+    ctx: &mut Context,
+) -> Result<(Delta, Option<Value>), String> {
+    let edge_id = parse_edge_id(params, "edge_id")?;
+    let thickness = parse_length(params, "thickness")?;
+    let height = parse_length(params, "height")?;
+    if thickness.is_none() && height.is_none() {
+        return Err("Missing 'thickness' or 'height' parameter".to_string());
+    }
+
+    let edge = ctx
+        .graph
+        .get_edge_mut(edge_id)
+        .ok_or_else(|| format!("Edge not found: {}", edge_id.0))?;
+    if let Some(thickness) = thickness {
+        edge.data.thickness = thickness;
+    }
+    if let Some(height) = height {
+        edge.data.height = height;
+    }
 
+    // Properties don't change topology, so no nodes are affected
     let delta = Delta {
-        created: vec![],
-        modified: vec!["node_placeholder".to_string()],
-        deleted: vec![],
-        affected_nodes: vec!["node_placeholder".to_string()],
+        modified: vec![edge_id.0.to_string()],
+        ..Default::default()
     };
 
     Ok((delta, None))
@@ -228,6 +750,7 @@ fn handle_delete_element(
         modified: vec![],
         deleted: vec!["element_placeholder".to_string()],
         affected_nodes: vec![],
+        ..Default::default()
     };
 
     Ok((delta, None))
@@ -249,9 +772,87 @@ fn handle_solve_joins(
     Ok((delta, Some(data)))
 }
 
+// ============================================================================
+// Parameter Parsing and Delta Helpers
+// ============================================================================
+
+/// Parse an `[x, y]` point parameter.
+fn parse_point(params: &Value, key: &str) -> Result<[f64; 2], String> {
+    let value = params
+        .get(key)
+        .ok_or_else(|| format!("Missing '{}' parameter", key))?;
+    match value.as_array().map(|a| a.as_slice()) {
+        Some([x, y]) => match (x.as_f64(), y.as_f64()) {
+            (Some(x), Some(y)) => Ok([x, y]),
+            _ => Err(format!("'{}' must be an [x, y] array of numbers", key)),
+        },
+        _ => Err(format!("'{}' must be an [x, y] array of numbers", key)),
+    }
+}
+
+/// Parse an optional positive length parameter.
+fn parse_length(params: &Value, key: &str) -> Result<Option<f64>, String> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => match value.as_f64() {
+            Some(v) if v > 0.0 => Ok(Some(v)),
+            _ => Err(format!("'{}' must be a positive number", key)),
+        },
+    }
+}
+
+/// Parse a UUID parameter, with or without its type prefix.
+fn parse_uuid(params: &Value, key: &str, prefix: &str) -> Result<Uuid, String> {
+    let text = params
+        .get(key)
+        .ok_or_else(|| format!("Missing '{}' parameter", key))?
+        .as_str()
+        .ok_or_else(|| format!("'{}' must be a string", key))?;
+    Uuid::parse_str(text.trim_start_matches(prefix))
+        .map_err(|_| format!("Invalid '{}': {}", key, text))
+}
+
+fn parse_node_id(params: &Value, key: &str) -> Result<NodeId, String> {
+    parse_uuid(params, key, "node_").map(NodeId::from_uuid)
+}
+
+fn parse_edge_id(params: &Value, key: &str) -> Result<EdgeId, String> {
+    parse_uuid(params, key, "edge_").map(EdgeId::from_uuid)
+}
+
+/// Fail if the node doesn't exist or is pinned.
+fn check_movable(graph: &TopologyGraph, node_id: NodeId) -> Result<(), String> {
+    match graph.get_node(node_id) {
+        None => Err(format!("Node not found: {}", node_id.0)),
+        Some(node) if node.pinned => Err(format!("Node {} is pinned", node_id.0)),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Add the edges at a node to `delta.modified`, in ID order.
+fn add_edges_at_node(graph: &TopologyGraph, node_id: NodeId, delta: &mut Delta) {
+    let mut edge_ids = graph.edges_at_node(node_id);
+    edge_ids.sort_by_key(|id| id.0);
+    for edge_id in edge_ids {
+        let id = edge_id.0.to_string();
+        if !delta.modified.contains(&id) {
+            delta.modified.push(id);
+        }
+    }
+}
+
+/// Add an edge's end nodes to `delta.affected_nodes`.
+fn add_end_nodes(graph: &TopologyGraph, edge_id: EdgeId, delta: &mut Delta) {
+    if let Some(edge) = graph.get_edge(edge_id) {
+        delta.affected_nodes.push(edge.start_node.0.to_string());
+        delta.affected_nodes.push(edge.end_node.0.to_string());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::topology::{EdgeData, EdgeId};
     use serde_json::json;
 
     #[test]
@@ -264,7 +865,7 @@ mod tests {
     }
 
     #[test]
-    fn exec_and_heal_add_wall_stub() {
+    fn exec_and_heal_add_wall() {
         let mut ctx = Context::new();
         let params = json!({
             "start": [0, 0],
@@ -278,6 +879,13 @@ mod tests {
         assert!(result.success);
         assert!(result.delta.is_some());
         assert_eq!(result.delta.as_ref().unwrap().created.len(), 1);
+
+        let wall_id = result.data.as_ref().unwrap()["wall_id"].as_str().unwrap();
+        let edge = ctx
+            .graph
+            .get_edge(EdgeId::from_uuid(wall_id.parse().unwrap()));
+        assert_eq!(edge.unwrap().data.height, 2700.0);
+        assert_eq!(result.delta.unwrap().affected_nodes.len(), 2);
     }
 
     #[test]
@@ -298,6 +906,7 @@ mod tests {
             modified: vec![],
             deleted: vec![],
             affected_nodes: vec![],
+            ..Default::default()
         };
         let result = ExecResult::ok(delta, Some(json!({"wall_id": "w1"})));
         let json = result.to_json();
@@ -323,4 +932,457 @@ mod tests {
         assert_eq!(ctx.session_id, Some("sess123".to_string()));
         assert_eq!(ctx.user_id, Some("user456".to_string()));
     }
+
+    fn add_wall(ctx: &mut Context, start: [f64; 2], end: [f64; 2], height: f64) -> Delta {
+        let id = ctx
+            .graph
+            .add_edge(start, end, EdgeData::wall(200.0, height))
+            .unwrap();
+        let edge = ctx.graph.get_edge(id).unwrap();
+        Delta {
+            created: vec![id.0.to_string()],
+            affected_nodes: vec![edge.start_node.0.to_string(), edge.end_node.0.to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn auto_rooms_follow_enclosure() {
+        let mut ctx = Context::new();
+        let corners = [[0.0, 0.0], [6000.0, 0.0], [6000.0, 4000.0], [0.0, 4000.0]];
+
+        // Off by default
+        for i in 0..4 {
+            let mut delta = add_wall(&mut ctx, corners[i], corners[(i + 1) % 4], 2700.0);
+            ctx.commit(&mut delta);
+        }
+        assert!(ctx.rooms.is_empty());
+
+        let mut ctx = Context::new();
+        ctx.config.auto_rooms = true;
+        for i in 0..3 {
+            let mut delta = add_wall(&mut ctx, corners[i], corners[i + 1], 2700.0);
+            ctx.commit(&mut delta);
+            assert!(delta.auto_created_rooms.is_empty());
+        }
+
+        // The fourth wall closes the loop
+        let mut delta = add_wall(&mut ctx, corners[3], corners[0], 2400.0);
+        let closing = EdgeId::from_uuid(delta.created[0].parse().unwrap());
+        ctx.commit(&mut delta);
+        assert_eq!(delta.auto_created_rooms.len(), 1);
+        assert!(delta.created.contains(&delta.auto_created_rooms[0]));
+        assert_eq!(ctx.rooms.len(), 1);
+        let room_id = ctx.rooms[0].id;
+        assert!((ctx.rooms[0].area() - 24_000_000.0).abs() < 1e-6);
+        assert_eq!(ctx.rooms[0].height, 2400.0);
+        assert_eq!(ctx.rooms[0].number, "1");
+        assert_eq!(ctx.rooms[0].bounding_walls.len(), 4);
+        ctx.rooms[0].name = "Kitchen".to_string();
+
+        // A heal that changes nothing leaves the room alone
+        let mut delta = Delta::new();
+        ctx.commit(&mut delta);
+        assert!(delta.modified.is_empty() && delta.auto_created_rooms.is_empty());
+
+        // Deleting a wall flags the room but keeps its name
+        let edge = ctx.graph.remove_edge(closing).unwrap();
+        let mut delta = Delta {
+            deleted: vec![closing.0.to_string()],
+            affected_nodes: vec![edge.start_node.0.to_string(), edge.end_node.0.to_string()],
+            ..Default::default()
+        };
+        ctx.commit(&mut delta);
+        assert_eq!(delta.unplaced_rooms, vec![room_id.to_string()]);
+        assert_eq!(ctx.rooms.len(), 1);
+        assert!(ctx.rooms[0].unplaced);
+        assert_eq!(ctx.rooms[0].name, "Kitchen");
+
+        // Redrawing it places the same room again
+        let mut delta = add_wall(&mut ctx, corners[3], corners[0], 2700.0);
+        ctx.commit(&mut delta);
+        assert!(delta.auto_created_rooms.is_empty());
+        assert_eq!(delta.modified, vec![room_id.to_string()]);
+        assert!(!ctx.rooms[0].unplaced);
+
+        // Under the delete policy the room goes away
+        ctx.config.removed_room_policy = RemovedRoomPolicy::Delete;
+        let wall = ctx.graph.edge_ids()[0];
+        let edge = ctx.graph.remove_edge(wall).unwrap();
+        let mut delta = Delta {
+            affected_nodes: vec![edge.start_node.0.to_string(), edge.end_node.0.to_string()],
+            ..Default::default()
+        };
+        ctx.commit(&mut delta);
+        assert_eq!(delta.deleted, vec![room_id.to_string()]);
+        assert!(ctx.rooms.is_empty());
+    }
+
+    /// Add a wall through `exec_and_heal` and return its edge.
+    fn exec_wall(ctx: &mut Context, start: [f64; 2], end: [f64; 2]) -> EdgeId {
+        let params = json!({ "start": start, "end": end });
+        let result = exec_and_heal("add_wall", &params, ctx);
+        let id = result.data.unwrap()["wall_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        EdgeId::from_uuid(id.parse().unwrap())
+    }
+
+    fn ids(edges: &[EdgeId]) -> Vec<String> {
+        let mut ids: Vec<String> = edges.iter().map(|e| e.0.to_string()).collect();
+        ids.sort();
+        ids
+    }
+
+    fn sorted(mut ids: Vec<String>) -> Vec<String> {
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn move_node_modifies_connected_edges() {
+        let mut ctx = Context::new();
+        let a = exec_wall(&mut ctx, [0.0, 0.0], [4000.0, 0.0]);
+        let b = exec_wall(&mut ctx, [4000.0, 0.0], [4000.0, 3000.0]);
+        let corner = ctx.graph.shared_node(a, b).unwrap();
+
+        let params = json!({ "node_id": format!("node_{}", corner.0), "position": [4500, 500] });
+        let result = exec_and_heal("move_node", &params, &mut ctx);
+
+        assert!(result.success, "{:?}", result.error);
+        let delta = result.delta.unwrap();
+        assert_eq!(sorted(delta.modified), ids(&[a, b]));
+        assert!(delta.created.is_empty() && delta.deleted.is_empty());
+        assert_eq!(delta.affected_nodes, vec![corner.0.to_string()]);
+        assert_eq!(
+            ctx.graph.get_node(corner).unwrap().position,
+            [4500.0, 500.0]
+        );
+    }
+
+    #[test]
+    fn move_node_rejects_pinned_node() {
+        let mut ctx = Context::new();
+        let a = exec_wall(&mut ctx, [0.0, 0.0], [4000.0, 0.0]);
+        let node = ctx.graph.get_edge(a).unwrap().end_node;
+        ctx.graph.get_node_mut(node).unwrap().pinned = true;
+        let revision = ctx.revision;
+
+        let params = json!({ "node_id": node.0.to_string(), "position": [5000, 0] });
+        let result = exec_and_heal("move_node", &params, &mut ctx);
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("pinned"));
+        assert_eq!(ctx.graph.get_node(node).unwrap().position, [4000.0, 0.0]);
+        assert_eq!(ctx.revision, revision);
+        assert_eq!(ctx.op_log.len(), 1);
+
+        let params = json!({ "node_id": Uuid::new_v4().to_string(), "position": [0, 0] });
+        let result = exec_and_heal("move_node", &params, &mut ctx);
+        assert!(result.error.unwrap().contains("not found"));
+    }
+
+    #[test]
+    fn move_node_into_line_merges_edges() {
+        let mut ctx = Context::new();
+        let a = exec_wall(&mut ctx, [0.0, 0.0], [1000.0, 0.0]);
+        let b = exec_wall(&mut ctx, [1000.0, 0.0], [2000.0, 500.0]);
+        let corner = ctx.graph.shared_node(a, b).unwrap();
+        let (kept, removed) = if a.0 < b.0 { (a, b) } else { (b, a) };
+
+        // Halfway along (0, 0)-(2000, 500) the two walls line up
+        let params = json!({ "node_id": corner.0.to_string(), "position": [1000, 250] });
+        let delta = exec_and_heal("move_node", &params, &mut ctx).delta.unwrap();
+
+        assert_eq!(ctx.graph.edge_ids(), vec![kept]);
+        assert_eq!(delta.modified, ids(&[kept]));
+        assert_eq!(delta.deleted, ids(&[removed]));
+        assert!(delta.created.is_empty());
+        assert!(delta.affected_nodes.contains(&corner.0.to_string()));
+        assert_eq!(delta.affected_nodes.len(), 3);
+    }
+
+    #[test]
+    fn move_wall_translates_both_ends() {
+        let mut ctx = Context::new();
+        let left = exec_wall(&mut ctx, [0.0, 0.0], [0.0, 3000.0]);
+        let middle = exec_wall(&mut ctx, [0.0, 3000.0], [4000.0, 3000.0]);
+        let right = exec_wall(&mut ctx, [4000.0, 3000.0], [4000.0, 0.0]);
+
+        let params = json!({ "edge_id": format!("edge_{}", middle.0), "offset": [0, 1000] });
+        let result = exec_and_heal("move_wall", &params, &mut ctx);
+
+        assert!(result.success, "{:?}", result.error);
+        let delta = result.delta.unwrap();
+        assert_eq!(sorted(delta.modified), ids(&[left, middle, right]));
+        assert_eq!(delta.affected_nodes.len(), 2);
+        assert_eq!(
+            ctx.graph.edge_positions(middle),
+            Some(([0.0, 4000.0], [4000.0, 4000.0]))
+        );
+        assert_eq!(ctx.graph.edge_positions(left).unwrap().1, [0.0, 4000.0]);
+
+        // A pinned end holds the whole wall
+        let end = ctx.graph.get_edge(middle).unwrap().end_node;
+        ctx.graph.get_node_mut(end).unwrap().pinned = true;
+        let result = exec_and_heal("move_wall", &params, &mut ctx);
+        assert!(result.error.unwrap().contains("pinned"));
+        assert_eq!(
+            ctx.graph.edge_positions(middle),
+            Some(([0.0, 4000.0], [4000.0, 4000.0]))
+        );
+    }
+
+    #[test]
+    fn delete_edge_reports_deletion() {
+        let mut ctx = Context::new();
+        let a = exec_wall(&mut ctx, [0.0, 0.0], [4000.0, 0.0]);
+        let b = exec_wall(&mut ctx, [4000.0, 0.0], [4000.0, 3000.0]);
+        let (start, end) = ctx.graph.get_edge(b).unwrap().nodes();
+
+        let params = json!({ "edge_id": b.0.to_string() });
+        let delta = exec_and_heal("delete_edge", &params, &mut ctx)
+            .delta
+            .unwrap();
+
+        assert_eq!(delta.deleted, ids(&[b]));
+        assert!(delta.created.is_empty() && delta.modified.is_empty());
+        assert_eq!(
+            delta.affected_nodes,
+            vec![start.0.to_string(), end.0.to_string()]
+        );
+        assert_eq!(ctx.graph.edge_ids(), vec![a]);
+
+        let result = exec_and_heal("delete_edge", &params, &mut ctx);
+        assert!(result.error.unwrap().contains("not found"));
+        let result = exec_and_heal("delete_edge", &json!({ "edge_id": "wall" }), &mut ctx);
+        assert!(result.error.unwrap().contains("Invalid"));
+    }
+
+    #[test]
+    fn set_edge_properties_updates_data() {
+        let mut ctx = Context::new();
+        let a = exec_wall(&mut ctx, [0.0, 0.0], [4000.0, 0.0]);
+
+        let params = json!({ "edge_id": a.0.to_string(), "thickness": 300 });
+        let delta = exec_and_heal("set_edge_properties", &params, &mut ctx)
+            .delta
+            .unwrap();
+        assert_eq!(delta.modified, ids(&[a]));
+        assert!(delta.affected_nodes.is_empty());
+        let data = &ctx.graph.get_edge(a).unwrap().data;
+        assert_eq!((data.thickness, data.height), (300.0, 2700.0));
+
+        let params = json!({ "edge_id": a.0.to_string(), "height": 3000 });
+        exec_and_heal("set_edge_properties", &params, &mut ctx);
+        assert_eq!(ctx.graph.get_edge(a).unwrap().data.height, 3000.0);
+
+        for params in [
+            json!({ "edge_id": a.0.to_string() }),
+            json!({ "edge_id": a.0.to_string(), "thickness": -5 }),
+        ] {
+            let result = exec_and_heal("set_edge_properties", &params, &mut ctx);
+            assert!(!result.success);
+        }
+        assert_eq!(ctx.graph.get_edge(a).unwrap().data.thickness, 300.0);
+    }
+
+    #[test]
+    fn unhealable_operation_rolls_back() {
+        let mut ctx = Context::new();
+        ctx.config.auto_rooms = true;
+        let corners = [[0.0, 0.0], [4000.0, 0.0], [4000.0, 3000.0], [0.0, 3000.0]];
+        let walls: Vec<EdgeId> = (0..4)
+            .map(|i| exec_wall(&mut ctx, corners[i], corners[(i + 1) % 4]))
+            .collect();
+        let corner = ctx.graph.shared_node(walls[0], walls[1]).unwrap();
+        let positions: Vec<_> = walls.iter().map(|&w| ctx.graph.edge_positions(w)).collect();
+        let room_ids = sorted(
+            ctx.graph
+                .room_ids()
+                .iter()
+                .map(|r| r.0.to_string())
+                .collect(),
+        );
+        let (revision, log_len) = (ctx.revision, ctx.op_log.len());
+
+        // JSON can't carry NaN, so stand in for a handler that computes one
+        let params = json!({ "node_id": corner.0.to_string(), "position": [0, 0] });
+        let result = exec_with("move_node", &params, &mut ctx, |_, ctx| {
+            let mut delta = Delta::new();
+            add_edges_at_node(&ctx.graph, corner, &mut delta);
+            delta.affected_nodes.push(corner.0.to_string());
+            ctx.graph.move_node(corner, [f64::NAN, f64::NAN]);
+            Ok((delta, None))
+        });
+
+        let error = result.error.clone().unwrap();
+        assert!(!result.success && result.rolled_back);
+        assert!(error.contains("non-finite position"), "{}", error);
+        assert_eq!(result.to_json()["rolled_back"], true);
+        let after: Vec<_> = walls.iter().map(|&w| ctx.graph.edge_positions(w)).collect();
+        assert_eq!(after, positions);
+        assert_eq!(
+            sorted(
+                ctx.graph
+                    .room_ids()
+                    .iter()
+                    .map(|r| r.0.to_string())
+                    .collect()
+            ),
+            room_ids
+        );
+        assert_eq!(ctx.rooms.len(), 1);
+        assert_eq!((ctx.revision, ctx.op_log.len()), (revision, log_len));
+
+        // The restored model still takes edits
+        let params = json!({ "node_id": corner.0.to_string(), "position": [4500, 0] });
+        assert!(exec_and_heal("move_node", &params, &mut ctx).success);
+    }
+
+    #[test]
+    fn failing_handler_is_undone() {
+        let mut ctx = Context::new();
+        let wall = exec_wall(&mut ctx, [0.0, 0.0], [4000.0, 0.0]);
+        let (revision, log_len) = (ctx.revision, ctx.op_log.len());
+
+        // Fails after it has already added a wall, moved a node, and
+        // hosted a door
+        let result = exec_with("add_wall", &json!({}), &mut ctx, |_, ctx| {
+            ctx.graph
+                .add_edge(
+                    [0.0, 1000.0],
+                    [4000.0, 1000.0],
+                    EdgeData::wall(200.0, 2700.0),
+                )
+                .unwrap();
+            let start = ctx.graph.get_edge(wall).unwrap().start_node;
+            ctx.graph.move_node(start, [-500.0, 0.0]);
+            ctx.doors.push(Door::new(wall.0, 0.9, 2.1, 1.0).unwrap());
+            Err("ran out of walls".to_string())
+        });
+
+        assert!(!result.success && !result.rolled_back);
+        assert_eq!(result.error.as_deref(), Some("ran out of walls"));
+        assert_eq!(ctx.graph.edge_count(), 1);
+        assert_eq!(
+            ctx.graph.edge_positions(wall),
+            Some(([0.0, 0.0], [4000.0, 0.0]))
+        );
+        assert!(ctx.doors.is_empty());
+        assert_eq!((ctx.revision, ctx.op_log.len()), (revision, log_len));
+    }
+
+    #[test]
+    fn failing_finish_drops_appended_elements() {
+        let mut ctx = Context::new();
+        ctx.doors
+            .push(Door::new(Uuid::nil(), 0.9, 2.1, 0.5).unwrap());
+
+        let result = exec_staged(
+            "add_wall",
+            &json!({}),
+            &mut ctx,
+            |_, ctx| {
+                let id = ctx
+                    .graph
+                    .add_edge([0.0, 0.0], [4000.0, 0.0], EdgeData::wall(200.0, 2700.0))
+                    .unwrap();
+                Ok((Delta::new(), id))
+            },
+            |id, _, ctx| {
+                ctx.doors.push(Door::new(id.0, 0.9, 2.1, 1.0).unwrap());
+                ctx.windows
+                    .push(Window::new(id.0, 1.2, 1.5, 0.9, 2.5).unwrap());
+                Err("no template".to_string())
+            },
+        );
+
+        assert!(result.rolled_back);
+        assert_eq!(ctx.graph.edge_count(), 0);
+        assert_eq!(ctx.doors.len(), 1);
+        assert!(ctx.windows.is_empty());
+    }
+
+    #[test]
+    fn deterministic_ids_repeat_across_sessions() {
+        let corners = [[0.0, 0.0], [4000.0, 0.0], [4000.0, 3000.0], [0.0, 3000.0]];
+        let build = |deterministic: bool| {
+            let mut ctx = Context::new().deterministic_ids(deterministic);
+            ctx.config.auto_rooms = true;
+            let walls: Vec<EdgeId> = (0..4)
+                .map(|i| exec_wall(&mut ctx, corners[i], corners[(i + 1) % 4]))
+                .collect();
+            let room = ctx.rooms[0].id;
+            (walls, room)
+        };
+
+        let (walls, room) = build(true);
+        assert_eq!(build(true), (walls.clone(), room));
+        assert_eq!(walls[0].0.get_version_num(), 5);
+        assert_eq!(room.get_version_num(), 5);
+
+        let (random_walls, random_room) = build(false);
+        assert_ne!(build(false).0, random_walls);
+        assert_ne!(random_room, room);
+    }
+
+    #[test]
+    fn walls_and_rooms_share_the_context_generator() {
+        let build = || {
+            let mode = IdMode::Sequential { seed: 3 };
+            let mut ctx = Context::new().with_id_generator(IdPolicy::new(mode));
+            ctx.config.auto_rooms = true;
+            let mut expected = IdPolicy::new(mode);
+            let corners = [[0.0, 0.0], [4000.0, 0.0], [4000.0, 3000.0], [0.0, 3000.0]];
+            for i in 0..4 {
+                let wall = exec_wall(&mut ctx, corners[i], corners[(i + 1) % 4]);
+                assert_eq!(wall.0, expected.next_id("Wall", &[], &[]));
+            }
+            assert_eq!(ctx.rooms[0].id, expected.next_id("Room", &[], &[]));
+            ctx.rooms[0].id
+        };
+        assert_eq!(build(), build());
+    }
+
+    #[test]
+    fn auto_rooms_leave_the_thread_id_policy_alone() {
+        let mode = IdMode::Sequential { seed: 9 };
+        crate::element::set_id_mode(mode);
+        let mut ctx =
+            Context::new().with_id_generator(IdPolicy::new(IdMode::Sequential { seed: 3 }));
+        ctx.config.auto_rooms = true;
+        let corners = [[0.0, 0.0], [4000.0, 0.0], [4000.0, 3000.0], [0.0, 3000.0]];
+        for i in 0..4 {
+            exec_wall(&mut ctx, corners[i], corners[(i + 1) % 4]);
+        }
+        assert_eq!(ctx.rooms.len(), 1);
+
+        let next = crate::element::next_element_id("Wall", &[], &[]);
+        crate::element::set_id_mode(IdMode::Random);
+        assert_eq!(next, IdPolicy::new(mode).next_id("Wall", &[], &[]));
+    }
+
+    #[test]
+    fn op_log_records_each_committed_operation() {
+        let mut ctx = Context::with_audit("sess".to_string(), "user".to_string());
+        let a = exec_wall(&mut ctx, [0.0, 0.0], [4000.0, 0.0]);
+        let params = json!({ "edge_id": a.0.to_string(), "height": 3000.004 });
+        let result = exec_and_heal("set_edge_properties", &params, &mut ctx);
+        exec_and_heal("delete_edge", &json!({ "edge_id": "nope" }), &mut ctx);
+
+        assert_eq!(ctx.op_log.len(), 2);
+        let op = &ctx.op_log[1];
+        assert_eq!(result.op.as_ref(), Some(op));
+        assert_eq!(op.revision, 2);
+        assert_eq!(op.method, "set_edge_properties");
+        assert_eq!(op.params["height"], json!(3000.0));
+        assert_eq!(op.user_id.as_deref(), Some("user"));
+        assert_eq!(result.to_json()["op"]["method"], "set_edge_properties");
+
+        let text = serde_json::to_string(&ctx.op_log).unwrap();
+        let replayed: Vec<OpLogEntry> = serde_json::from_str(&text).unwrap();
+        assert_eq!(replayed, ctx.op_log);
+    }
 }
@@ -0,0 +1,202 @@
+//! Auto-rooming: keep Room elements in step with detected rooms.
+//!
+//! Detected rooms get fresh IDs whenever they are re-traced, so Room
+//! elements are matched to them geometrically: a pair matches when either
+//! one's centroid lies inside the other's boundary, and closer centroids
+//! match first. Rooms whose walls are gone are deleted or flagged unplaced;
+//! an unplaced room is placed again once walls enclose its old position.
+
+use pensaer_math::{Point2, Polygon2};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::element::ElementType;
+use crate::elements::Room;
+use crate::ids::IdGenerator;
+use crate::topology::{TopoRoom, TopologyGraph};
+
+use super::Delta;
+
+/// What happens to a Room element when its enclosing walls are removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemovedRoomPolicy {
+    /// Delete the Room element.
+    Delete,
+    /// Keep the Room element, flagged as unplaced.
+    #[default]
+    Unplace,
+}
+
+/// A detected interior room, ready to create or update a Room element.
+struct Detected {
+    boundary: Polygon2,
+    centroid: Point2,
+    bounding_walls: Vec<Uuid>,
+    height: f64,
+}
+
+/// Create, update, and retire Room elements to match the graph's rooms.
+///
+/// - Matched rooms take the detected boundary and bounding walls; unplaced
+///   rooms are placed again.
+/// - Detected rooms with no match get a new Room element, with height from
+///   the lowest bounding wall and the next free number.
+/// - Placed rooms with no match are handled per `policy`.
+///
+/// New Room elements take their IDs from `ids`, given the room boundary
+/// starting at its lowest vertex, so a content-hashing generator gives the
+/// same room the same ID wherever its loop was traced from.
+///
+/// Changes are recorded in `delta`. Returns the number of rooms created.
+pub fn sync_room_elements(
+    graph: &TopologyGraph,
+    rooms: &mut Vec<Room>,
+    policy: RemovedRoomPolicy,
+    ids: &mut dyn IdGenerator,
+    delta: &mut Delta,
+) -> usize {
+    let mut detected: Vec<Detected> = graph
+        .interior_rooms()
+        .into_iter()
+        .filter_map(|room| detect(graph, room))
+        .collect();
+    detected.sort_by(|a, b| {
+        a.centroid
+            .x
+            .total_cmp(&b.centroid.x)
+            .then(a.centroid.y.total_cmp(&b.centroid.y))
+    });
+
+    // Candidate pairs, placed rooms before unplaced ones, then closest first
+    let mut pairs: Vec<(bool, f64, usize, usize)> = Vec::new();
+    for (ri, room) in rooms.iter().enumerate() {
+        let centroid = room.boundary.centroid();
+        for (di, found) in detected.iter().enumerate() {
+            if found.boundary.contains_point(&centroid)
+                || room.boundary.contains_point(&found.centroid)
+            {
+                let distance = centroid.distance_to(&found.centroid);
+                pairs.push((room.unplaced, distance, di, ri));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then(a.1.total_cmp(&b.1))
+            .then(a.2.cmp(&b.2))
+            .then(a.3.cmp(&b.3))
+    });
+
+    let mut room_match: Vec<Option<usize>> = vec![None; rooms.len()];
+    let mut detected_used = vec![false; detected.len()];
+    for (_, _, di, ri) in pairs {
+        if room_match[ri].is_none() && !detected_used[di] {
+            room_match[ri] = Some(di);
+            detected_used[di] = true;
+        }
+    }
+
+    let mut retired = Vec::new();
+    for (ri, room) in rooms.iter_mut().enumerate() {
+        match room_match[ri] {
+            Some(di) => {
+                let found = &detected[di];
+                if room.unplaced
+                    || room.boundary != found.boundary
+                    || room.bounding_walls != found.bounding_walls
+                {
+                    room.boundary = found.boundary.clone();
+                    room.bounding_walls = found.bounding_walls.clone();
+                    room.unplaced = false;
+                    delta.modified.push(room.id.to_string());
+                }
+            }
+            None if room.unplaced => {}
+            None => match policy {
+                RemovedRoomPolicy::Delete => {
+                    delta.deleted.push(room.id.to_string());
+                    retired.push(ri);
+                }
+                RemovedRoomPolicy::Unplace => {
+                    room.unplaced = true;
+                    delta.modified.push(room.id.to_string());
+                    delta.unplaced_rooms.push(room.id.to_string());
+                }
+            },
+        }
+    }
+    for ri in retired.into_iter().rev() {
+        rooms.remove(ri);
+    }
+
+    let mut created = 0;
+    for (di, found) in detected.into_iter().enumerate() {
+        if detected_used[di] {
+            continue;
+        }
+        let number = next_room_number(rooms);
+        let Ok(mut room) = Room::with_id(Uuid::nil(), "Room", number, found.boundary, found.height)
+        else {
+            continue;
+        };
+        // Tracing can start anywhere on the loop, so start at the lowest
+        // vertex
+        let vertices = &room.boundary.vertices;
+        let first = (0..vertices.len())
+            .min_by(|&a, &b| {
+                let (a, b) = (vertices[a], vertices[b]);
+                a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y))
+            })
+            .unwrap_or(0);
+        let params: Vec<f64> = vertices[first..]
+            .iter()
+            .chain(&vertices[..first])
+            .flat_map(|v| [v.x, v.y])
+            .collect();
+        room.id = ids.next_id(ElementType::Room.name(), &[], &params);
+        room.bounding_walls = found.bounding_walls;
+        delta.created.push(room.id.to_string());
+        delta.auto_created_rooms.push(room.id.to_string());
+        rooms.push(room);
+        created += 1;
+    }
+    created
+}
+
+fn detect(graph: &TopologyGraph, room: &TopoRoom) -> Option<Detected> {
+    let vertices = room
+        .boundary_nodes
+        .iter()
+        .map(|id| {
+            let [x, y] = graph.get_node(*id)?.position;
+            Some(Point2::new(x, y))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut bounding_walls: Vec<Uuid> = room.boundary_edges.iter().map(|id| id.0).collect();
+    bounding_walls.sort();
+    bounding_walls.dedup();
+    let height = room
+        .boundary_edges
+        .iter()
+        .filter_map(|id| graph.get_edge(*id))
+        .map(|edge| edge.data.height)
+        .fold(f64::INFINITY, f64::min);
+
+    Some(Detected {
+        boundary: Polygon2::new(vertices).ok()?,
+        centroid: Point2::new(room.centroid[0], room.centroid[1]),
+        bounding_walls,
+        height,
+    })
+}
+
+/// Smallest positive number not used by any room.
+fn next_room_number(rooms: &[Room]) -> String {
+    let mut n = 1;
+    while rooms.iter().any(|r| r.number == n.to_string()) {
+        n += 1;
+    }
+    n.to_string()
+}
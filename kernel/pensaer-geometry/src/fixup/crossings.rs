@@ -0,0 +1,420 @@
+//! Bentley–Ottmann sweep for the X-crossings split by `split_crossings`.
+//!
+//! A vertical line sweeps the plan from left to right. Non-vertical edges
+//! enter the status line at their left end and leave it at their right end,
+//! kept in order of where they cut the sweep line, and each edge is only
+//! tested against its neighbours in that order. When two neighbours cross,
+//! a crossing event swaps them at the crossing's X, which brings each into
+//! contact with its new neighbour. Vertical edges never enter the status
+//! line: at their X they are tested against the status edges within their
+//! Y range.
+//!
+//! The number of intersection tests therefore grows with the number of
+//! edges plus the number of crossings, instead of with every pair of edges.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::spatial::segment_intersection;
+use crate::topology::{EdgeId, TopologyGraph};
+use crate::util::float::points2_within;
+
+/// An edge with its endpoints ordered left to right (then bottom to top).
+struct Segment {
+    id: EdgeId,
+    left: [f64; 2],
+    right: [f64; 2],
+}
+
+impl Segment {
+    fn is_vertical(&self) -> bool {
+        self.left[0] == self.right[0]
+    }
+
+    /// Y where the edge cuts the vertical line at `x`.
+    fn y_at(&self, x: f64) -> f64 {
+        let t = ((x - self.left[0]) / (self.right[0] - self.left[0])).clamp(0.0, 1.0);
+        self.left[1] + t * (self.right[1] - self.left[1])
+    }
+
+    fn slope(&self) -> f64 {
+        (self.right[1] - self.left[1]) / (self.right[0] - self.left[0])
+    }
+}
+
+/// Sweep events at the same X are handled in this order, so an edge ending
+/// where another starts is gone before the new one is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum EventKind {
+    End,
+    Cross,
+    Vertical,
+    Start,
+}
+
+/// Min-heap entry for the sweep's event queue.
+///
+/// `BinaryHeap` is a max-heap, so the ordering is reversed: the leftmost
+/// event pops first. For crossings, `a` is the lower edge on the status
+/// line and `b` the upper one; other events only use `a`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Event {
+    x: f64,
+    kind: EventKind,
+    a: usize,
+    b: usize,
+}
+
+impl Eq for Event {}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .x
+            .total_cmp(&self.x)
+            .then_with(|| other.kind.cmp(&self.kind))
+            .then_with(|| (other.a, other.b).cmp(&(self.a, self.b)))
+    }
+}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+struct Sweep<'a> {
+    graph: &'a TopologyGraph,
+    tolerance: f64,
+    segments: Vec<Segment>,
+    events: BinaryHeap<Event>,
+    /// Non-vertical edges cut by the sweep line, bottom to top
+    status: Vec<usize>,
+    x: f64,
+    tested: HashSet<(usize, usize)>,
+    crossings: Vec<(EdgeId, EdgeId, [f64; 2])>,
+    tests: usize,
+}
+
+/// Find all crossings between edges that don't share a node.
+///
+/// A crossing within `tolerance` of an endpoint of either edge is left to
+/// the T-junction pass. `tests` is incremented for every pair of edges that
+/// gets an intersection test.
+pub(super) fn find_crossings(
+    graph: &TopologyGraph,
+    tolerance: f64,
+    tests: &mut usize,
+) -> Vec<(EdgeId, EdgeId, [f64; 2])> {
+    let segments: Vec<Segment> = graph
+        .edge_ids()
+        .into_iter()
+        .filter_map(|id| {
+            let (a, b) = graph.edge_positions(id)?;
+            let (left, right) = if (a[0], a[1]) <= (b[0], b[1]) {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            Some(Segment { id, left, right })
+        })
+        .collect();
+
+    let mut events = BinaryHeap::with_capacity(segments.len() * 2);
+    for (i, segment) in segments.iter().enumerate() {
+        if segment.is_vertical() {
+            events.push(Event {
+                x: segment.left[0],
+                kind: EventKind::Vertical,
+                a: i,
+                b: i,
+            });
+        } else {
+            events.push(Event {
+                x: segment.left[0],
+                kind: EventKind::Start,
+                a: i,
+                b: i,
+            });
+            events.push(Event {
+                x: segment.right[0],
+                kind: EventKind::End,
+                a: i,
+                b: i,
+            });
+        }
+    }
+
+    let mut sweep = Sweep {
+        graph,
+        tolerance,
+        segments,
+        events,
+        status: Vec::new(),
+        x: f64::NEG_INFINITY,
+        tested: HashSet::new(),
+        crossings: Vec::new(),
+        tests: 0,
+    };
+    while let Some(event) = sweep.events.pop() {
+        sweep.x = event.x;
+        match event.kind {
+            EventKind::Start => sweep.start(event.a),
+            EventKind::End => sweep.end(event.a),
+            EventKind::Cross => sweep.cross(event.a, event.b),
+            EventKind::Vertical => sweep.vertical(event.a),
+        }
+    }
+
+    *tests += sweep.tests;
+    sweep.crossings
+}
+
+impl Sweep<'_> {
+    fn position(&self, segment: usize) -> Option<usize> {
+        self.status.iter().position(|&s| s == segment)
+    }
+
+    /// Place an edge on the status line and test it against its neighbours.
+    fn start(&mut self, segment: usize) {
+        let new = &self.segments[segment];
+        let (y, slope) = (new.y_at(self.x), new.slope());
+        let at = self.status.partition_point(|&s| {
+            let other = &self.segments[s];
+            other
+                .y_at(self.x)
+                .total_cmp(&y)
+                .then_with(|| other.slope().total_cmp(&slope))
+                .is_lt()
+        });
+        self.status.insert(at, segment);
+
+        if at > 0 {
+            self.check(self.status[at - 1], segment);
+        }
+        if at + 1 < self.status.len() {
+            self.check(segment, self.status[at + 1]);
+        }
+    }
+
+    /// Take an edge off the status line; its neighbours now meet.
+    fn end(&mut self, segment: usize) {
+        let Some(at) = self.position(segment) else {
+            return;
+        };
+        self.status.remove(at);
+        if at > 0 && at < self.status.len() {
+            self.check(self.status[at - 1], self.status[at]);
+        }
+    }
+
+    /// Swap two neighbours where they cross.
+    fn cross(&mut self, lower: usize, upper: usize) {
+        let (Some(lo), Some(hi)) = (self.position(lower), self.position(upper)) else {
+            return;
+        };
+        // Already swapped, or another edge came between them; that edge
+        // schedules its own swaps as it meets each of them
+        if hi != lo + 1 {
+            return;
+        }
+        self.status.swap(lo, hi);
+
+        if lo > 0 {
+            self.check(self.status[lo - 1], upper);
+        }
+        if hi + 1 < self.status.len() {
+            self.check(lower, self.status[hi + 1]);
+        }
+    }
+
+    /// Test a vertical edge against the status edges within its Y range.
+    fn vertical(&mut self, segment: usize) {
+        let (bottom, top) = (
+            self.segments[segment].left[1] - self.tolerance,
+            self.segments[segment].right[1] + self.tolerance,
+        );
+        let from = self
+            .status
+            .partition_point(|&s| self.segments[s].y_at(self.x) < bottom);
+        let hits: Vec<usize> = self.status[from..]
+            .iter()
+            .copied()
+            .take_while(|&s| self.segments[s].y_at(self.x) <= top)
+            .collect();
+        for other in hits {
+            if !self.share_node(segment, other) {
+                self.test(segment, other);
+            }
+        }
+    }
+
+    /// Test two status neighbours (`lower` below `upper`), and schedule a
+    /// swap if they end up the other way round.
+    fn check(&mut self, lower: usize, upper: usize) {
+        if self.share_node(lower, upper) {
+            return;
+        }
+        let hit = self.test(lower, upper);
+
+        let (a, b) = (&self.segments[lower], &self.segments[upper]);
+        let end = a.right[0].min(b.right[0]);
+        if a.y_at(end) > b.y_at(end) {
+            self.events.push(Event {
+                x: hit.map_or(self.x, |p| p[0]).max(self.x),
+                kind: EventKind::Cross,
+                a: lower,
+                b: upper,
+            });
+        }
+    }
+
+    /// Edges sharing a node meet at a vertex, not a crossing, and never
+    /// change places on the status line.
+    fn share_node(&self, i: usize, j: usize) -> bool {
+        self.graph
+            .edges_share_node(self.segments[i].id, self.segments[j].id)
+    }
+
+    /// Intersection test for a pair of edges, recording a crossing the first
+    /// time the pair is tested.
+    fn test(&mut self, i: usize, j: usize) -> Option<[f64; 2]> {
+        let (a, b) = (&self.segments[i], &self.segments[j]);
+        let hit = segment_intersection(a.left, a.right, b.left, b.right);
+        if !self.tested.insert((i.min(j), i.max(j))) {
+            return hit;
+        }
+        self.tests += 1;
+
+        if let Some(intersection) = hit {
+            // Make sure intersection is not at an endpoint of either edge
+            let at_endpoint = [a.left, a.right, b.left, b.right]
+                .iter()
+                .any(|&q| points2_within(intersection, q, self.tolerance));
+            if !at_endpoint {
+                self.crossings.push((a.id, b.id, intersection));
+            }
+        }
+        hit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::EdgeData;
+
+    /// Crossings found by testing every pair of edges once.
+    fn all_pairs_crossings(graph: &TopologyGraph, tolerance: f64) -> HashSet<(EdgeId, EdgeId)> {
+        let edge_ids = graph.edge_ids();
+        let mut found = HashSet::new();
+        for (i, &edge1_id) in edge_ids.iter().enumerate() {
+            let (a1, a2) = graph.edge_positions(edge1_id).unwrap();
+            for &edge2_id in edge_ids.iter().skip(i + 1) {
+                if graph.edges_share_node(edge1_id, edge2_id) {
+                    continue;
+                }
+                let (b1, b2) = graph.edge_positions(edge2_id).unwrap();
+                if let Some(p) = segment_intersection(a1, a2, b1, b2) {
+                    if [a1, a2, b1, b2]
+                        .iter()
+                        .all(|&q| !points2_within(p, q, tolerance))
+                    {
+                        found.insert(pair(edge1_id, edge2_id));
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    fn pair(a: EdgeId, b: EdgeId) -> (EdgeId, EdgeId) {
+        if a.0 <= b.0 {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    #[test]
+    fn sweep_matches_all_pairs_on_random_walls() {
+        // Small linear congruential generator, so the layout is repeatable
+        let mut seed: u64 = 0x5eed;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((seed >> 33) % 20_000) as f64
+        };
+
+        let mut graph = TopologyGraph::new();
+        for _ in 0..120 {
+            let start = [next(), next()];
+            let end = [next(), next()];
+            graph.add_edge(start, end, EdgeData::wall(200.0, 2700.0));
+        }
+        // Axis-aligned walls, including vertical ones that skip the status line
+        for i in 0..10 {
+            let c = 1000.0 + i as f64 * 1700.0;
+            graph.add_edge([c, 500.0], [c, 19_500.0], EdgeData::wall(200.0, 2700.0));
+            graph.add_edge([500.0, c], [19_500.0, c], EdgeData::wall(200.0, 2700.0));
+        }
+
+        let tolerance = graph.snap_tolerance();
+        let expected = all_pairs_crossings(&graph, tolerance);
+        assert!(expected.len() > 500);
+
+        let mut tests = 0;
+        let found: HashSet<_> = find_crossings(&graph, tolerance, &mut tests)
+            .into_iter()
+            .map(|(a, b, _)| pair(a, b))
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn sweep_crosses_walls_through_a_shared_point() {
+        // Three walls through (1000, 1000), none sharing a node
+        let mut graph = TopologyGraph::new();
+        graph.add_edge([0.0, 0.0], [2000.0, 2000.0], EdgeData::wall(200.0, 2700.0));
+        graph.add_edge([0.0, 2000.0], [2000.0, 0.0], EdgeData::wall(200.0, 2700.0));
+        graph.add_edge(
+            [0.0, 1000.0],
+            [2000.0, 1000.0],
+            EdgeData::wall(200.0, 2700.0),
+        );
+        graph.add_edge(
+            [1000.0, 0.0],
+            [1000.0, 2000.0],
+            EdgeData::wall(200.0, 2700.0),
+        );
+        // A wall above them all, met only once the others have swapped
+        graph.add_edge(
+            [0.0, 3000.0],
+            [2000.0, 1500.0],
+            EdgeData::wall(200.0, 2700.0),
+        );
+
+        let tolerance = graph.snap_tolerance();
+        let found = find_crossings(&graph, tolerance, &mut 0);
+        assert_eq!(found.len(), all_pairs_crossings(&graph, tolerance).len());
+        assert_eq!(found.len(), 6 + 1);
+    }
+
+    #[test]
+    fn sweep_only_tests_neighbouring_parallel_walls() {
+        // Long parallel walls are only ever tested against the walls beside
+        // them on the status line
+        let mut graph = TopologyGraph::new();
+        for i in 0..50 {
+            let y = i as f64 * 1000.0;
+            graph.add_edge([0.0, y], [20000.0, y], EdgeData::wall(200.0, 2700.0));
+        }
+
+        let mut tests = 0;
+        assert!(find_crossings(&graph, graph.snap_tolerance(), &mut tests).is_empty());
+        // At most two tests as each wall starts and one as each ends, out
+        // of 1225 pairs
+        assert!(tests <= 3 * 50, "sweep {}", tests);
+    }
+}
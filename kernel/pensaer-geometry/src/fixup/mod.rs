@@ -2,7 +2,8 @@
 //!
 //! These passes run after every mutation to maintain model invariants:
 //!
-//! 1. `snap_merge_nodes` - Merge nodes within SNAP_MERGE_TOL (0.5mm)
+//! 1. `snap_merge_nodes` - Merge nodes within the graph's snap tolerance
+//!    (SNAP_MERGE_TOL, 0.5mm, by default)
 //! 2. `split_crossings` - Insert T-nodes at edge intersections
 //! 3. `merge_colinear` - Collapse aligned edges into one
 //! 4. `rooms_rebuild_dirty` - Recompute affected room boundaries
@@ -13,12 +14,25 @@
 //! - Merge before split (avoid splitting then merging the split point)
 //! - Split before merge colinear (splitting may create new colinear segments)
 //! - Rooms last (depend on final topology)
+//!
+//! [`heal_all`] runs the passes; [`heal_all_tracked`] also records the edges
+//! they changed in the operation's [`Delta`].
+//!
+//! When auto-rooming is enabled, [`sync_room_elements`] runs after the
+//! passes to keep Room elements in step with the detected rooms.
 
-use crate::constants::SNAP_MERGE_TOL;
-use crate::spatial::segment_intersection;
-use crate::topology::{EdgeId, TopologyGraph};
-use crate::util::float::points2_within;
+use crate::topology::{EdgeId, NodeId, TopologyGraph};
+use crate::util::float::{dist2_squared, points2_within};
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+mod auto_room;
+mod crossings;
+
+pub use auto_room::{sync_room_elements, RemovedRoomPolicy};
+
+use crossings::find_crossings;
 
 /// Delta returned by operations, describing what changed.
 #[derive(Debug, Clone, Default)]
@@ -31,6 +45,12 @@ pub struct Delta {
     pub deleted: Vec<String>,
     /// IDs of affected nodes (for room rebuild)
     pub affected_nodes: Vec<String>,
+    /// IDs of Room elements created by auto-rooming (also in `created`)
+    pub auto_created_rooms: Vec<String>,
+    /// IDs of Room elements flagged unplaced (also in `modified`)
+    pub unplaced_rooms: Vec<String>,
+    /// Whether healing re-detected rooms, giving the re-traced ones new IDs
+    pub rooms_rebuilt: bool,
 }
 
 impl Delta {
@@ -44,7 +64,10 @@ impl Delta {
             "created": self.created,
             "modified": self.modified,
             "deleted": self.deleted,
-            "affected_nodes": self.affected_nodes
+            "affected_nodes": self.affected_nodes,
+            "auto_created_rooms": self.auto_created_rooms,
+            "unplaced_rooms": self.unplaced_rooms,
+            "rooms_rebuilt": self.rooms_rebuilt
         })
     }
 }
@@ -58,7 +81,10 @@ impl Delta {
 ///
 /// # Arguments
 /// * `graph` - The topology graph to modify
-/// * `tolerance` - Maximum distance for merge (typically SNAP_MERGE_TOL)
+/// * `tolerance` - Maximum distance for merge (typically SNAP_MERGE_TOL).
+///   This may differ from the graph's snap tolerance, e.g. to merge more
+///   loosely while cleaning up an import; new nodes still snap at the
+///   graph's tolerance.
 ///
 /// # Returns
 /// Number of nodes merged
@@ -66,11 +92,7 @@ pub fn snap_merge_nodes(graph: &mut TopologyGraph, tolerance: f64) -> usize {
     // The TopologyGraph already handles snap-merge on node creation,
     // but this pass catches any nodes that have drifted close together
     // after other operations.
-    //
-    // For now, we use the graph's built-in snap_merge_nodes which uses
-    // its own snap_tolerance. In the future, this could be parameterized.
-    let _ = tolerance;
-    graph.snap_merge_nodes()
+    graph.snap_merge_nodes_with(tolerance)
 }
 
 /// Split edges that cross each other or form T-junctions.
@@ -83,6 +105,9 @@ pub fn snap_merge_nodes(graph: &mut TopologyGraph, tolerance: f64) -> usize {
 /// - A new node is created at the intersection point
 /// - Each edge is split into two edges meeting at the new node
 ///
+/// X-crossings are found in a single Bentley–Ottmann sweep and split as a
+/// batch; the sweep only repeats if the splits introduced new crossings.
+///
 /// When a T-junction is found:
 /// - The edge is split at the existing node position
 /// - The node is connected to the split point
@@ -95,22 +120,16 @@ pub fn split_crossings(graph: &mut TopologyGraph) -> usize {
 
     // Phase 1: Handle X-crossings (two edges crossing in their interiors)
     loop {
-        let crossing = find_crossing(graph, tolerance);
-
-        match crossing {
-            Some((edge1_id, edge2_id, intersection)) => {
-                // Split both edges at the intersection point
-                if let Some((_node1, _e1a, _e1b)) = graph.split_edge(edge1_id, intersection) {
-                    // After splitting edge1, edge2 might still exist
-                    // (if they don't share the intersection point)
-                    if graph.get_edge(edge2_id).is_some() {
-                        let _ = graph.split_edge(edge2_id, intersection);
-                    }
-                    split_count += 1;
-                }
-            }
-            None => break,
+        let crossings = find_crossings(graph, tolerance, &mut 0);
+        if crossings.is_empty() {
+            break;
         }
+
+        let applied = apply_crossing_splits(graph, &crossings);
+        if applied == 0 {
+            break;
+        }
+        split_count += applied;
     }
 
     // Phase 2: Handle T-junctions (a node lies on an edge's interior)
@@ -131,44 +150,51 @@ pub fn split_crossings(graph: &mut TopologyGraph) -> usize {
     split_count
 }
 
-/// Find a crossing between two edges that don't share a node.
-fn find_crossing(graph: &TopologyGraph, tolerance: f64) -> Option<(EdgeId, EdgeId, [f64; 2])> {
-    let edge_ids = graph.edge_ids();
+/// Split every edge at all of its crossing points.
+///
+/// Each edge's points are applied in order from its start node, continuing
+/// on the remaining end piece after each split. Returns the number of
+/// crossings that produced a split.
+fn apply_crossing_splits(
+    graph: &mut TopologyGraph,
+    crossings: &[(EdgeId, EdgeId, [f64; 2])],
+) -> usize {
+    let mut order: Vec<EdgeId> = Vec::new();
+    let mut points: HashMap<EdgeId, Vec<(usize, [f64; 2])>> = HashMap::new();
+    for (k, &(edge1_id, edge2_id, intersection)) in crossings.iter().enumerate() {
+        for edge_id in [edge1_id, edge2_id] {
+            points
+                .entry(edge_id)
+                .or_insert_with(|| {
+                    order.push(edge_id);
+                    Vec::new()
+                })
+                .push((k, intersection));
+        }
+    }
 
-    for (i, &edge1_id) in edge_ids.iter().enumerate() {
-        let (a1, a2) = match graph.edge_positions(edge1_id) {
-            Some(p) => p,
+    let mut applied: HashSet<usize> = HashSet::new();
+    for edge_id in order {
+        let start = match graph.edge_positions(edge_id) {
+            Some((start, _)) => start,
             None => continue,
         };
 
-        for &edge2_id in edge_ids.iter().skip(i + 1) {
-            // Skip if edges share a node (they meet at a vertex, not a crossing)
-            if graph.edges_share_node(edge1_id, edge2_id) {
-                continue;
-            }
-
-            let (b1, b2) = match graph.edge_positions(edge2_id) {
-                Some(p) => p,
-                None => continue,
-            };
-
-            // Check for intersection
-            if let Some(intersection) = segment_intersection(a1, a2, b1, b2) {
-                // Make sure intersection is not at an endpoint of either edge
-                if points2_within(intersection, a1, tolerance)
-                    || points2_within(intersection, a2, tolerance)
-                    || points2_within(intersection, b1, tolerance)
-                    || points2_within(intersection, b2, tolerance)
-                {
-                    continue;
-                }
+        let mut edge_points = points.remove(&edge_id).unwrap_or_default();
+        edge_points.sort_by(|(_, p), (_, q)| {
+            dist2_squared(start, *p).total_cmp(&dist2_squared(start, *q))
+        });
 
-                return Some((edge1_id, edge2_id, intersection));
+        let mut current = edge_id;
+        for (k, point) in edge_points {
+            if let Some((_node, _first, rest)) = graph.split_edge(current, point) {
+                current = rest;
+                applied.insert(k);
             }
         }
     }
 
-    None
+    applied.len()
 }
 
 /// Find a T-junction: a node that lies on an edge's interior.
@@ -253,8 +279,8 @@ fn point_on_segment_interior(p: [f64; 2], a: [f64; 2], b: [f64; 2], tolerance: f
 /// Merge colinear edges that share a node.
 ///
 /// When two edges are colinear and share an endpoint:
-/// - They are merged into a single edge
-/// - The intermediate node is removed if it has no other edges
+/// - The first edge is extended over the second, keeping its ID and data
+/// - The second edge and the intermediate node are removed
 ///
 /// # Returns
 /// Number of edge pairs merged
@@ -268,37 +294,23 @@ pub fn merge_colinear(graph: &mut TopologyGraph) -> usize {
 
         match colinear_pair {
             Some((edge1_id, edge2_id, shared_node_id)) => {
-                // Get the edge data before merging
-                let edge1 = match graph.get_edge(edge1_id) {
-                    Some(e) => e.clone(),
-                    None => continue,
-                };
                 let edge2 = match graph.get_edge(edge2_id) {
                     Some(e) => e.clone(),
-                    None => continue,
+                    None => break,
                 };
 
-                // Determine the outer endpoints (not the shared node)
-                let outer1 = edge1.other_node(shared_node_id).unwrap();
-                let outer2 = edge2.other_node(shared_node_id).unwrap();
-
-                // Get positions
-                let pos1 = graph.get_node(outer1).map(|n| n.position);
-                let pos2 = graph.get_node(outer2).map(|n| n.position);
-
-                if let (Some(p1), Some(p2)) = (pos1, pos2) {
-                    // Use the data from the first edge (could also merge properties)
-                    let data = edge1.data.clone();
-
-                    // Remove both old edges
-                    graph.remove_edge(edge1_id);
-                    graph.remove_edge(edge2_id);
-
-                    // Create new merged edge
-                    graph.add_edge(p1, p2, data);
-
-                    merged_count += 1;
+                // Extend edge1 over edge2 so the merged wall keeps edge1's
+                // ID and data, then drop edge2 (and with it the shared node)
+                let outer2 = match edge2.other_node(shared_node_id) {
+                    Some(n) => n,
+                    None => break,
+                };
+                if !graph.reconnect_edge(edge1_id, shared_node_id, outer2) {
+                    break;
                 }
+                graph.remove_edge(edge2_id);
+
+                merged_count += 1;
             }
             None => break,
         }
@@ -323,7 +335,9 @@ fn find_colinear_pair(
             continue;
         }
 
-        let edges: Vec<EdgeId> = node.edges.iter().copied().collect();
+        // Sorted so the same edge survives the merge on every run
+        let mut edges: Vec<EdgeId> = node.edges.iter().copied().collect();
+        edges.sort_by_key(|id| id.0);
         let edge1_id = edges[0];
         let edge2_id = edges[1];
 
@@ -380,12 +394,21 @@ fn are_colinear(a1: [f64; 2], a2: [f64; 2], b1: [f64; 2], b2: [f64; 2], toleranc
     true
 }
 
+/// Fraction of graph nodes above which `rooms_rebuild_dirty` gives up on the
+/// incremental path and re-traces every room.
+pub const FULL_ROOM_REBUILD_RATIO: f64 = 0.3;
+
 /// Rebuild room boundaries affected by topology changes.
 ///
 /// This pass:
-/// - Identifies rooms that contain affected nodes
-/// - Invalidates those rooms
-/// - Rebuilds all rooms (full rebuild for simplicity)
+/// - Parses `delta.affected_nodes` into node IDs
+/// - Invalidates rooms touching those nodes, and rooms at their neighbours
+///   whose boundary no longer turns the same way
+/// - Re-traces only the invalidated region, leaving untouched rooms in place
+///
+/// Falls back to a full rebuild when no rooms exist yet, when an affected
+/// node ID can't be parsed, or when more than `FULL_ROOM_REBUILD_RATIO` of
+/// the nodes are affected.
 ///
 /// # Arguments
 /// * `graph` - The topology graph to modify
@@ -394,40 +417,150 @@ fn are_colinear(a1: [f64; 2], a2: [f64; 2], b1: [f64; 2], b2: [f64; 2], toleranc
 /// # Returns
 /// Number of rooms after rebuild
 pub fn rooms_rebuild_dirty(graph: &mut TopologyGraph, delta: &Delta) -> usize {
+    rebuild_dirty_rooms(graph, delta).0
+}
+
+/// [`rooms_rebuild_dirty`], also reporting whether any room was removed or
+/// re-traced.
+fn rebuild_dirty_rooms(graph: &mut TopologyGraph, delta: &Delta) -> (usize, bool) {
+    let before = graph.room_count();
+
     // If no affected nodes, check if we need initial room detection
     if delta.affected_nodes.is_empty() {
         // If rooms already exist, nothing to do
-        if graph.room_count() > 0 {
-            return graph.room_count();
+        if before > 0 {
+            return (before, false);
         }
     }
 
-    // For now, do a full rebuild.
-    // A more sophisticated implementation could:
-    // 1. Parse affected_nodes to NodeIds
-    // 2. Use invalidate_rooms_at_nodes to remove only affected rooms
-    // 3. Do incremental room tracing from affected edges
-    //
-    // Full rebuild is simpler and correct, though less efficient for large graphs.
-    graph.rebuild_rooms()
+    let node_ids = match parse_node_ids(&delta.affected_nodes) {
+        Some(ids) if before > 0 => ids,
+        _ => {
+            let count = graph.rebuild_rooms();
+            return (count, before > 0 || count > 0);
+        }
+    };
+
+    let threshold = graph.node_count() as f64 * FULL_ROOM_REBUILD_RATIO;
+    if node_ids.len() as f64 > threshold {
+        return (graph.rebuild_rooms(), true);
+    }
+
+    // Rooms are only dropped or re-traced, so an unchanged count with
+    // nothing re-traced means nothing changed
+    let retraced = graph.rebuild_rooms_at_nodes(&node_ids);
+    let count = graph.room_count();
+    (count, retraced > 0 || count != before)
+}
+
+/// Parse delta node ID strings (`"<uuid>"` or `"node_<uuid>"`).
+///
+/// Returns None if any ID is malformed.
+fn parse_node_ids(ids: &[String]) -> Option<Vec<NodeId>> {
+    ids.iter()
+        .map(|s| {
+            Uuid::parse_str(s.trim_start_matches("node_"))
+                .ok()
+                .map(NodeId::from_uuid)
+        })
+        .collect()
 }
 
 /// Run all fixup passes in the correct order.
 ///
-/// This is the main entry point for healing after any mutation.
+/// This is the main entry point for healing after any mutation. Edges the
+/// passes split, merge, or remove are tracked on a copy of `delta` (see
+/// [`heal_all_tracked`]) so the room rebuild covers them too.
 ///
 /// # Returns
 /// Number of rooms after rebuild
 pub fn heal_all(graph: &mut TopologyGraph, delta: &Delta) -> usize {
-    snap_merge_nodes(graph, SNAP_MERGE_TOL);
+    heal_all_tracked(graph, &mut delta.clone())
+}
+
+/// Run all fixup passes, recording what healing changed in `delta`.
+///
+/// Like [`heal_all`], but edges that the snap, split, and merge passes
+/// create, reshape, or remove are added to `delta`, and their nodes to
+/// `affected_nodes`, before rooms are rebuilt. An edge the operation itself
+/// created and healing then removed is dropped from `created` rather than
+/// reported as deleted. `rooms_rebuilt` is set when the rebuild removed or
+/// re-traced any room.
+///
+/// # Returns
+/// Number of rooms after rebuild
+pub fn heal_all_tracked(graph: &mut TopologyGraph, delta: &mut Delta) -> usize {
+    let before = edge_snapshot(graph);
+    snap_merge_nodes(graph, graph.snap_tolerance());
     split_crossings(graph);
     merge_colinear(graph);
-    rooms_rebuild_dirty(graph, delta)
+    record_healed_edges(&before, &edge_snapshot(graph), delta);
+    let (room_count, rebuilt) = rebuild_dirty_rooms(graph, delta);
+    delta.rooms_rebuilt |= rebuilt;
+    room_count
+}
+
+/// End nodes and positions of every edge, keyed by edge.
+type EdgeSnapshot = HashMap<EdgeId, (NodeId, NodeId, [f64; 2], [f64; 2])>;
+
+fn edge_snapshot(graph: &TopologyGraph) -> EdgeSnapshot {
+    graph
+        .edges()
+        .filter_map(|e| {
+            let (start, end) = graph.edge_positions(e.id)?;
+            Some((e.id, (e.start_node, e.end_node, start, end)))
+        })
+        .collect()
+}
+
+/// Add the differences between two edge snapshots to `delta`.
+fn record_healed_edges(before: &EdgeSnapshot, after: &EdgeSnapshot, delta: &mut Delta) {
+    let mut ids: Vec<EdgeId> = before.keys().chain(after.keys()).copied().collect();
+    ids.sort_by_key(|id| id.0);
+    ids.dedup();
+
+    let mut nodes = Vec::new();
+    for id in ids {
+        let key = id.0.to_string();
+        match (before.get(&id), after.get(&id)) {
+            (Some(old), None) => {
+                if let Some(i) = delta.created.iter().position(|c| *c == key) {
+                    delta.created.remove(i);
+                } else {
+                    delta.modified.retain(|m| *m != key);
+                    push_unique(&mut delta.deleted, key);
+                }
+                nodes.extend([old.0, old.1]);
+            }
+            (None, Some(new)) => {
+                push_unique(&mut delta.created, key);
+                nodes.extend([new.0, new.1]);
+            }
+            (Some(old), Some(new)) if old != new => {
+                if !delta.created.contains(&key) {
+                    push_unique(&mut delta.modified, key);
+                }
+                nodes.extend([old.0, old.1, new.0, new.1]);
+            }
+            _ => {}
+        }
+    }
+
+    for node in nodes {
+        push_unique(&mut delta.affected_nodes, node.0.to_string());
+    }
+}
+
+fn push_unique(ids: &mut Vec<String>, id: String) {
+    if !ids.contains(&id) {
+        ids.push(id);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::SNAP_MERGE_TOL;
     use crate::topology::EdgeData;
 
     #[test]
@@ -437,6 +570,7 @@ mod tests {
             modified: vec!["w2".to_string()],
             deleted: vec![],
             affected_nodes: vec!["n1".to_string(), "n2".to_string()],
+            ..Default::default()
         };
 
         let json = delta.to_json();
@@ -490,6 +624,31 @@ mod tests {
         assert_eq!(graph.node_count(), 4);
     }
 
+    #[test]
+    fn snap_merge_nodes_uses_passed_tolerance() {
+        // Two walls whose ends stop 3mm apart
+        let build = || {
+            let mut graph = TopologyGraph::new();
+            graph.add_edge([0.0, 0.0], [1000.0, 0.0], EdgeData::wall(200.0, 2700.0));
+            graph.add_edge(
+                [1003.0, 0.0],
+                [1003.0, 1000.0],
+                EdgeData::wall(200.0, 2700.0),
+            );
+            graph
+        };
+
+        let mut graph = build();
+        assert_eq!(snap_merge_nodes(&mut graph, SNAP_MERGE_TOL), 0);
+        assert_eq!(graph.node_count(), 4);
+
+        let mut graph = build();
+        assert_eq!(snap_merge_nodes(&mut graph, 5.0), 1);
+        assert_eq!(graph.node_count(), 3);
+        // New nodes still snap at the graph's own tolerance
+        assert_eq!(graph.snap_tolerance(), SNAP_MERGE_TOL);
+    }
+
     // =========================================================================
     // M3 Tests: split_crossings
     // =========================================================================
@@ -565,6 +724,74 @@ mod tests {
         assert_eq!(graph.edge_count(), 2);
     }
 
+    /// 10 horizontal and 10 vertical walls crossing in a 10x10 grid.
+    fn crossing_grid() -> TopologyGraph {
+        let mut graph = TopologyGraph::new();
+        for i in 1..=10 {
+            let c = i as f64 * 1000.0;
+            graph.add_edge([0.0, c], [11000.0, c], EdgeData::wall(200.0, 2700.0));
+            graph.add_edge([c, 0.0], [c, 11000.0], EdgeData::wall(200.0, 2700.0));
+        }
+        graph
+    }
+
+    /// Intersection tests made by a single all-pairs pass over the graph,
+    /// which skips edges sharing a node.
+    fn all_pairs_tests(graph: &TopologyGraph) -> usize {
+        let edge_ids = graph.edge_ids();
+        edge_ids
+            .iter()
+            .enumerate()
+            .map(|(i, &edge1_id)| {
+                edge_ids[i + 1..]
+                    .iter()
+                    .filter(|&&edge2_id| !graph.edges_share_node(edge1_id, edge2_id))
+                    .count()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn split_crossings_grid_in_one_batch() {
+        let mut graph = crossing_grid();
+        assert_eq!(graph.node_count(), 40);
+
+        let crossings = find_crossings(&graph, graph.snap_tolerance(), &mut 0);
+        assert_eq!(crossings.len(), 100);
+
+        let splits = split_crossings(&mut graph);
+        assert_eq!(splits, 100);
+        assert_eq!(graph.node_count(), 40 + 100);
+        assert_eq!(graph.edge_count(), 20 * 11);
+
+        // Nothing left to split
+        assert!(find_crossings(&graph, graph.snap_tolerance(), &mut 0).is_empty());
+    }
+
+    #[test]
+    fn split_crossings_sweep_tests_fewer_pairs_than_all_pairs() {
+        let mut graph = crossing_grid();
+        let tolerance = graph.snap_tolerance();
+
+        // Every wall crosses half the others, so the first sweep can't do
+        // much better than all pairs, but it still tests fewer
+        let mut first = 0;
+        let crossings = find_crossings(&graph, tolerance, &mut first);
+        assert!(first < all_pairs_tests(&graph), "sweep {}", first);
+
+        // Once split, the walls only meet their neighbours
+        apply_crossing_splits(&mut graph, &crossings);
+        let mut second = 0;
+        assert!(find_crossings(&graph, tolerance, &mut second).is_empty());
+        let all_pairs = all_pairs_tests(&graph);
+        assert!(
+            second * 20 < all_pairs,
+            "sweep {} vs all pairs {}",
+            second,
+            all_pairs
+        );
+    }
+
     // =========================================================================
     // M3 Tests: merge_colinear
     // =========================================================================
@@ -721,6 +948,52 @@ mod tests {
         assert_eq!(graph.node_count(), 5); // 4 corners + 1 center
     }
 
+    #[test]
+    fn heal_all_tracked_reports_healing() {
+        let mut graph = TopologyGraph::new();
+        let horizontal = graph
+            .add_edge([0.0, 500.0], [1000.0, 500.0], EdgeData::wall(200.0, 2700.0))
+            .unwrap();
+        let vertical = graph
+            .add_edge([500.0, 0.0], [500.0, 1000.0], EdgeData::wall(200.0, 2700.0))
+            .unwrap();
+
+        // The vertical wall is new in this operation, the horizontal one isn't
+        let mut delta = Delta {
+            created: vec![vertical.0.to_string()],
+            ..Default::default()
+        };
+        heal_all_tracked(&mut graph, &mut delta);
+
+        // Both were split: the new one silently, the old one as a delete
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(delta.deleted, vec![horizontal.0.to_string()]);
+        assert_eq!(delta.created.len(), 4);
+        assert!(!delta.created.contains(&vertical.0.to_string()));
+        for id in graph.edge_ids() {
+            assert!(delta.created.contains(&id.0.to_string()));
+        }
+        assert_eq!(delta.affected_nodes.len(), 5);
+
+        // Colinear halves merge back into one edge: a modify and a delete
+        let mut graph = TopologyGraph::new();
+        let a = graph
+            .add_edge([0.0, 0.0], [1000.0, 0.0], EdgeData::wall(200.0, 2700.0))
+            .unwrap();
+        let b = graph
+            .add_edge([1000.0, 0.0], [2000.0, 0.0], EdgeData::wall(200.0, 2700.0))
+            .unwrap();
+        let (kept, removed) = if a.0 < b.0 { (a, b) } else { (b, a) };
+
+        let mut delta = Delta::new();
+        heal_all_tracked(&mut graph, &mut delta);
+        assert_eq!(graph.edge_ids(), vec![kept]);
+        assert_eq!(delta.modified, vec![kept.0.to_string()]);
+        assert_eq!(delta.deleted, vec![removed.0.to_string()]);
+        assert!(delta.created.is_empty());
+        assert_eq!(delta.affected_nodes.len(), 3);
+    }
+
     // =========================================================================
     // M4 Tests: rooms_rebuild_dirty
     // =========================================================================
@@ -833,4 +1106,153 @@ mod tests {
         // Room count should be preserved (but we do full rebuild, so it's same)
         assert_eq!(graph.room_count(), initial_count);
     }
+
+    /// Build a `cols` x `rows` grid of 1000mm rooms from unit wall segments.
+    fn room_grid(cols: usize, rows: usize) -> TopologyGraph {
+        let mut graph = TopologyGraph::new();
+        for j in 0..=rows {
+            for i in 0..=cols {
+                let (x, y) = (i as f64 * 1000.0, j as f64 * 1000.0);
+                if i < cols {
+                    graph.add_edge([x, y], [x + 1000.0, y], EdgeData::wall(200.0, 2700.0));
+                }
+                if j < rows {
+                    graph.add_edge([x, y], [x, y + 1000.0], EdgeData::wall(200.0, 2700.0));
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn rooms_rebuild_dirty_only_retraces_affected_rooms() {
+        let mut graph = room_grid(5, 4);
+        assert_eq!(graph.rebuild_rooms(), 21); // 20 cells + exterior
+
+        // Move the interior wall between (2000,1000) and (2000,2000) by 200mm
+        let a = graph.nodes_within([2000.0, 1000.0], 1.0)[0];
+        let b = graph.nodes_within([2000.0, 2000.0], 1.0)[0];
+        let before: HashSet<_> = graph.room_ids().into_iter().collect();
+        graph.move_node(a, [2200.0, 1000.0]);
+        graph.move_node(b, [2200.0, 2000.0]);
+
+        // Two nodes touch 6 cells between them
+        let retraced = graph.rebuild_rooms_at_nodes(&[a, b]);
+        assert_eq!(retraced, 6);
+        assert_eq!(graph.room_count(), 21);
+
+        let after: HashSet<_> = graph.room_ids().into_iter().collect();
+        assert_eq!(before.intersection(&after).count(), 15);
+
+        // Same rooms as a full rebuild
+        let mut areas: Vec<i64> = graph
+            .interior_rooms()
+            .iter()
+            .map(|r| r.area() as i64)
+            .collect();
+        graph.rebuild_rooms();
+        let mut full: Vec<i64> = graph
+            .interior_rooms()
+            .iter()
+            .map(|r| r.area() as i64)
+            .collect();
+        areas.sort_unstable();
+        full.sort_unstable();
+        assert_eq!(areas, full);
+    }
+
+    #[test]
+    fn rooms_rebuild_dirty_uses_delta_nodes() {
+        let mut graph = room_grid(5, 4);
+        rooms_rebuild_dirty(&mut graph, &Delta::new());
+        let before: HashSet<_> = graph.room_ids().into_iter().collect();
+
+        let node = graph.nodes_within([1000.0, 1000.0], 1.0)[0];
+        graph.move_node(node, [1100.0, 1100.0]);
+        let delta = Delta {
+            affected_nodes: vec![node.0.to_string()],
+            ..Delta::default()
+        };
+
+        assert_eq!(rooms_rebuild_dirty(&mut graph, &delta), 21);
+        let after: HashSet<_> = graph.room_ids().into_iter().collect();
+        assert_eq!(before.intersection(&after).count(), 17); // 4 cells re-traced
+    }
+
+    #[test]
+    fn heal_all_retraces_rooms_split_by_a_crossing_wall() {
+        let mut graph = room_grid(1, 1);
+        heal_all(&mut graph, &Delta::new());
+        assert_eq!(graph.interior_rooms().len(), 1);
+
+        // A wall across the room, poking out both sides; the caller only
+        // knows about the new wall, not the splits healing makes
+        let wall = graph
+            .add_edge(
+                [-500.0, 500.0],
+                [1500.0, 500.0],
+                EdgeData::wall(200.0, 2700.0),
+            )
+            .unwrap();
+        let edge = graph.get_edge(wall).unwrap();
+        let delta = Delta {
+            created: vec![wall.0.to_string()],
+            affected_nodes: vec![edge.start_node.0.to_string(), edge.end_node.0.to_string()],
+            ..Delta::default()
+        };
+        heal_all(&mut graph, &delta);
+
+        let mut areas: Vec<i64> = graph
+            .interior_rooms()
+            .iter()
+            .map(|r| r.area().round() as i64)
+            .collect();
+        areas.sort_unstable();
+        assert_eq!(areas, vec![500_000, 500_000]);
+    }
+
+    #[test]
+    fn rooms_rebuild_dirty_detects_removed_wall() {
+        let mut graph = room_grid(2, 1);
+        rooms_rebuild_dirty(&mut graph, &Delta::new());
+        assert_eq!(graph.interior_rooms().len(), 2);
+
+        // Remove the dividing wall; its nodes stay (they have other edges)
+        let a = graph.nodes_within([1000.0, 0.0], 1.0)[0];
+        let b = graph.nodes_within([1000.0, 1000.0], 1.0)[0];
+        let divider = graph
+            .edges_at_node(a)
+            .into_iter()
+            .find(|e| graph.other_node(*e, a) == Some(b))
+            .unwrap();
+        graph.remove_edge(divider);
+
+        let delta = Delta {
+            affected_nodes: vec![a.0.to_string(), b.0.to_string()],
+            ..Delta::default()
+        };
+        rooms_rebuild_dirty(&mut graph, &delta);
+
+        let interior = graph.interior_rooms();
+        assert_eq!(interior.len(), 1);
+        assert!((interior[0].area() - 2_000_000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn rooms_rebuild_dirty_falls_back_on_unknown_ids() {
+        let mut graph = room_grid(2, 2);
+        rooms_rebuild_dirty(&mut graph, &Delta::new());
+        let before: HashSet<_> = graph.room_ids().into_iter().collect();
+
+        let delta = Delta {
+            affected_nodes: vec!["node_placeholder".to_string()],
+            ..Delta::default()
+        };
+        rooms_rebuild_dirty(&mut graph, &delta);
+
+        // Full rebuild assigns fresh room IDs everywhere
+        let after: HashSet<_> = graph.room_ids().into_iter().collect();
+        assert_eq!(before.intersection(&after).count(), 0);
+        assert_eq!(graph.interior_rooms().len(), 4);
+    }
 }
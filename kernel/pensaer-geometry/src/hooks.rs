@@ -0,0 +1,528 @@
+//! Per-session event hooks on kernel mutations.
+//!
+//! Downstream automations (auto-tagging, renumbering, re-running checks)
+//! register callbacks on a [`Context`]'s [`HookRegistry`] instead of polling
+//! for changes. Events are dispatched synchronously after an operation has
+//! been committed and healed, so handlers always see a consistent model.
+//!
+//! Handlers only get a read-only [`KernelEvent`]. To change the model they
+//! queue follow-up operations, which run after the current operation and all
+//! of its handlers have finished, in the order they were queued. A handler
+//! that panics or returns an error is recorded as a [`HookFailure`]; the
+//! operation that triggered it still completes.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pensaer_geometry::exec::Context;
+//! use pensaer_geometry::hooks::KernelEventKind;
+//!
+//! let mut ctx = Context::new();
+//! let handle = ctx.hooks.on(KernelEventKind::RoomsRebuilt, |event, follow_ups| {
+//!     let ids = event.ids.clone();
+//!     follow_ups.push(move |ctx| {
+//!         // Renumber rooms, re-run checks, ...
+//!         let _ = (ids, ctx);
+//!     });
+//!     Ok(())
+//! });
+//! ctx.hooks.off(handle);
+//! ```
+
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::exec::Context;
+
+/// Kind of kernel event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KernelEventKind {
+    /// Elements were created.
+    ElementCreated,
+    /// Elements were modified.
+    ElementModified,
+    /// Elements were deleted.
+    ElementDeleted,
+    /// Rooms were re-detected during healing.
+    RoomsRebuilt,
+    /// A heal pass finished.
+    HealCompleted,
+    /// Clash detection results changed.
+    ClashResultsChanged,
+}
+
+impl KernelEventKind {
+    /// All event kinds.
+    pub const ALL: [KernelEventKind; 6] = [
+        KernelEventKind::ElementCreated,
+        KernelEventKind::ElementModified,
+        KernelEventKind::ElementDeleted,
+        KernelEventKind::RoomsRebuilt,
+        KernelEventKind::HealCompleted,
+        KernelEventKind::ClashResultsChanged,
+    ];
+
+    /// Snake-case event name (e.g., "element_modified").
+    pub fn name(&self) -> &'static str {
+        match self {
+            KernelEventKind::ElementCreated => "element_created",
+            KernelEventKind::ElementModified => "element_modified",
+            KernelEventKind::ElementDeleted => "element_deleted",
+            KernelEventKind::RoomsRebuilt => "rooms_rebuilt",
+            KernelEventKind::HealCompleted => "heal_completed",
+            KernelEventKind::ClashResultsChanged => "clash_results_changed",
+        }
+    }
+
+    /// Parse a snake-case event name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.name() == name)
+    }
+}
+
+/// Read-only payload passed to event handlers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KernelEvent {
+    /// Event kind.
+    pub kind: KernelEventKind,
+    /// IDs of the elements, rooms, or nodes involved.
+    pub ids: Vec<String>,
+    /// Model revision after the operation was committed.
+    pub revision: u64,
+    /// Short human-readable summary.
+    pub summary: String,
+}
+
+/// Handle returned when registering a hook, used to remove it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HookHandle(pub u64);
+
+/// A handler that panicked or returned an error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookFailure {
+    /// Handler that failed.
+    pub handle: HookHandle,
+    /// Event being handled (or that queued the failed follow-up).
+    pub event: KernelEventKind,
+    /// Error or panic message.
+    pub message: String,
+}
+
+/// Follow-up operation queued by a handler.
+pub type FollowUp = Box<dyn FnOnce(&mut Context) + Send>;
+
+/// Event handler callback.
+pub type HookFn = Box<dyn FnMut(&KernelEvent, &mut FollowUps) -> Result<(), String> + Send>;
+
+/// Follow-up operations queued while handling one event.
+#[derive(Default)]
+pub struct FollowUps {
+    ops: Vec<FollowUp>,
+}
+
+impl FollowUps {
+    /// Queue an operation to run after the current one has finished.
+    pub fn push(&mut self, op: impl FnOnce(&mut Context) + Send + 'static) {
+        self.ops.push(Box::new(op));
+    }
+
+    /// Number of queued operations.
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Whether nothing has been queued.
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+}
+
+struct Hook {
+    handle: HookHandle,
+    kind: KernelEventKind,
+    priority: i32,
+    callback: HookFn,
+}
+
+/// Registered hooks and pending follow-up operations for one session.
+#[derive(Default)]
+pub struct HookRegistry {
+    hooks: Vec<Hook>,
+    next_handle: u64,
+    pending: VecDeque<(HookHandle, KernelEventKind, FollowUp)>,
+    draining: bool,
+    failures: Vec<HookFailure>,
+}
+
+impl HookRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler with default priority (0).
+    pub fn on(
+        &mut self,
+        kind: KernelEventKind,
+        callback: impl FnMut(&KernelEvent, &mut FollowUps) -> Result<(), String> + Send + 'static,
+    ) -> HookHandle {
+        self.on_with_priority(kind, 0, callback)
+    }
+
+    /// Register a handler. Lower priorities run first; equal priorities run
+    /// in registration order.
+    pub fn on_with_priority(
+        &mut self,
+        kind: KernelEventKind,
+        priority: i32,
+        callback: impl FnMut(&KernelEvent, &mut FollowUps) -> Result<(), String> + Send + 'static,
+    ) -> HookHandle {
+        let handle = HookHandle(self.next_handle);
+        self.next_handle += 1;
+
+        let index = self.hooks.partition_point(|h| h.priority <= priority);
+        self.hooks.insert(
+            index,
+            Hook {
+                handle,
+                kind,
+                priority,
+                callback: Box::new(callback),
+            },
+        );
+        handle
+    }
+
+    /// Remove a handler. Returns false if the handle is unknown.
+    pub fn off(&mut self, handle: HookHandle) -> bool {
+        let before = self.hooks.len();
+        self.hooks.retain(|h| h.handle != handle);
+        self.hooks.len() != before
+    }
+
+    /// Number of registered handlers.
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    /// Whether no handlers are registered.
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Take the failures recorded since the last call.
+    pub fn take_failures(&mut self) -> Vec<HookFailure> {
+        std::mem::take(&mut self.failures)
+    }
+
+    /// Call every handler registered for the event, queueing their follow-ups.
+    pub fn dispatch(&mut self, event: &KernelEvent) {
+        for hook in self.hooks.iter_mut().filter(|h| h.kind == event.kind) {
+            let mut follow_ups = FollowUps::default();
+            let result =
+                panic::catch_unwind(AssertUnwindSafe(|| (hook.callback)(event, &mut follow_ups)));
+
+            let error = match result {
+                Ok(Ok(())) => None,
+                Ok(Err(message)) => Some(message),
+                Err(payload) => Some(panic_message(payload.as_ref())),
+            };
+            if let Some(message) = error {
+                self.failures.push(HookFailure {
+                    handle: hook.handle,
+                    event: event.kind,
+                    message,
+                });
+            }
+
+            // Follow-ups queued before a failure still run
+            self.pending.extend(
+                follow_ups
+                    .ops
+                    .into_iter()
+                    .map(|op| (hook.handle, event.kind, op)),
+            );
+        }
+    }
+}
+
+/// Run queued follow-up operations in order.
+///
+/// Operations queued while draining are appended to the same queue rather
+/// than run re-entrantly, so nested commits never interleave.
+pub fn run_follow_ups(ctx: &mut Context) {
+    if ctx.hooks.draining {
+        return;
+    }
+    ctx.hooks.draining = true;
+
+    while let Some((handle, event, op)) = ctx.hooks.pending.pop_front() {
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| op(ctx))) {
+            ctx.hooks.failures.push(HookFailure {
+                handle,
+                event,
+                message: panic_message(payload.as_ref()),
+            });
+        }
+    }
+
+    ctx.hooks.draining = false;
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "handler panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::exec_and_heal;
+    use crate::fixup::Delta;
+    use crate::topology::EdgeData;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Add walls and build the delta an edit would produce.
+    fn add_walls(ctx: &mut Context, walls: &[([f64; 2], [f64; 2])]) -> Delta {
+        let mut delta = Delta::new();
+        for &(start, end) in walls {
+            let id = ctx
+                .graph
+                .add_edge(start, end, EdgeData::wall(200.0, 2700.0))
+                .unwrap();
+            let edge = ctx.graph.get_edge(id).unwrap();
+            delta.affected_nodes.push(edge.start_node.0.to_string());
+            delta.affected_nodes.push(edge.end_node.0.to_string());
+            delta.created.push(id.0.to_string());
+        }
+        delta
+    }
+
+    fn rectangle() -> Vec<([f64; 2], [f64; 2])> {
+        vec![
+            ([0.0, 0.0], [6000.0, 0.0]),
+            ([6000.0, 0.0], [6000.0, 4000.0]),
+            ([6000.0, 4000.0], [0.0, 4000.0]),
+            ([0.0, 4000.0], [0.0, 0.0]),
+        ]
+    }
+
+    #[test]
+    fn event_names_round_trip() {
+        for kind in KernelEventKind::ALL {
+            assert_eq!(KernelEventKind::from_name(kind.name()), Some(kind));
+        }
+        assert_eq!(KernelEventKind::from_name("unknown"), None);
+    }
+
+    #[test]
+    fn rooms_rebuilt_handler_renames_once_per_heal() {
+        let mut ctx = Context::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&calls);
+        ctx.hooks
+            .on(KernelEventKind::RoomsRebuilt, move |event, follow_ups| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let revision = event.revision;
+                follow_ups.push(move |ctx| {
+                    let ids: Vec<_> = ctx.graph.interior_rooms().iter().map(|r| r.id).collect();
+                    for (i, id) in ids.into_iter().enumerate() {
+                        let room = ctx.graph.get_room_mut(id).unwrap();
+                        room.name = Some(format!("R{}-{}", revision, i + 1));
+                    }
+                });
+                Ok(())
+            });
+
+        let mut delta = add_walls(&mut ctx, &rectangle());
+        ctx.commit(&mut delta);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let names: Vec<_> = ctx
+            .graph
+            .interior_rooms()
+            .iter()
+            .map(|r| r.name.clone())
+            .collect();
+        assert_eq!(names, vec![Some("R1-1".to_string())]);
+
+        // Splitting the room rebuilds rooms, and the new rooms get names too
+        let mut delta = add_walls(&mut ctx, &[([3000.0, 0.0], [3000.0, 4000.0])]);
+        ctx.commit(&mut delta);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let interior = ctx.graph.interior_rooms();
+        assert_eq!(interior.len(), 2);
+        assert!(interior
+            .iter()
+            .all(|r| r.name.as_deref().unwrap().starts_with("R2-")));
+
+        // A heal that changes nothing doesn't fire, nor does a wall clear
+        // of every room
+        ctx.commit(&mut Delta::new());
+        let mut delta = add_walls(&mut ctx, &[([9000.0, 0.0], [12000.0, 0.0])]);
+        ctx.commit(&mut delta);
+        assert!(!delta.rooms_rebuilt);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn failing_handlers_do_not_abort_edit() {
+        let mut ctx = Context::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        ctx.hooks.on(KernelEventKind::ElementCreated, |_, _| {
+            panic!("handler bug");
+        });
+        ctx.hooks.on(KernelEventKind::ElementCreated, |_, _| {
+            Err("refused".to_string())
+        });
+        let counter = Arc::clone(&calls);
+        ctx.hooks.on(KernelEventKind::ElementCreated, move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        });
+
+        let params = json!({"start": [0, 0], "end": [5000, 0]});
+        let result = exec_and_heal("add_wall", &params, &mut ctx);
+
+        assert!(result.success);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(ctx.revision, 1);
+
+        let messages: Vec<&str> = result
+            .hook_failures
+            .iter()
+            .map(|f| f.message.as_str())
+            .collect();
+        assert_eq!(messages, vec!["handler bug", "refused"]);
+        assert_eq!(
+            result.to_json()["hook_failures"].as_array().unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn follow_ups_run_in_order_after_handlers() {
+        let mut ctx = Context::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let l = Arc::clone(&log);
+        ctx.hooks
+            .on(KernelEventKind::HealCompleted, move |_, follow_ups| {
+                l.lock().unwrap().push("a");
+                for name in ["a1", "a2"] {
+                    let l = Arc::clone(&l);
+                    follow_ups.push(move |_| l.lock().unwrap().push(name));
+                }
+                Ok(())
+            });
+
+        // Runs before "a" despite registering later
+        let l = Arc::clone(&log);
+        ctx.hooks
+            .on_with_priority(KernelEventKind::HealCompleted, -1, move |_, follow_ups| {
+                l.lock().unwrap().push("b");
+                let l = Arc::clone(&l);
+                follow_ups.push(move |ctx| {
+                    l.lock().unwrap().push("b1");
+                    ctx.emit(
+                        KernelEventKind::ElementModified,
+                        vec![],
+                        "0 elements modified",
+                    );
+                });
+                Ok(())
+            });
+
+        // Queued from inside a follow-up: runs after the existing queue
+        let l = Arc::clone(&log);
+        ctx.hooks
+            .on(KernelEventKind::ElementModified, move |_, follow_ups| {
+                l.lock().unwrap().push("c");
+                let l = Arc::clone(&l);
+                follow_ups.push(move |_| l.lock().unwrap().push("c1"));
+                Ok(())
+            });
+
+        ctx.commit(&mut Delta::new());
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["b", "a", "b1", "c", "a1", "a2", "c1"]
+        );
+    }
+
+    #[test]
+    fn clash_results_changed_fires_when_clashing_pairs_change() {
+        use crate::spatial::{ClashDetector, ClashElement};
+        use pensaer_math::{BoundingBox3, Point3};
+        use uuid::Uuid;
+
+        let mut ctx = Context::new();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let e = Arc::clone(&events);
+        ctx.hooks
+            .on(KernelEventKind::ClashResultsChanged, move |event, _| {
+                e.lock().unwrap().push(event.clone());
+                Ok(())
+            });
+
+        let boxed = |id: u128, x: f64| {
+            ClashElement::new(
+                Uuid::from_u128(id),
+                "wall",
+                BoundingBox3::new(Point3::new(x, 0.0, 0.0), Point3::new(x + 1.0, 1.0, 1.0)),
+            )
+        };
+        let detector = ClashDetector::new(0.001);
+        let clashing = [boxed(1, 0.0), boxed(2, 0.5)];
+
+        assert_eq!(ctx.detect_clashes(&detector, &clashing).len(), 1);
+        // Same pairs again: results are recomputed but unchanged
+        ctx.detect_clashes(&detector, &clashing);
+        assert_eq!(events.lock().unwrap().len(), 1);
+        assert_eq!(
+            events.lock().unwrap()[0].ids,
+            vec![
+                Uuid::from_u128(1).to_string(),
+                Uuid::from_u128(2).to_string()
+            ]
+        );
+
+        // Moving one element clear resolves the clash
+        assert!(ctx
+            .detect_clashes(&detector, &[boxed(1, 0.0), boxed(2, 5.0)])
+            .is_empty());
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events[1].ids.is_empty());
+        assert_eq!(events[1].summary, "0 clashes");
+    }
+
+    #[test]
+    fn off_removes_handler() {
+        let mut ctx = Context::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&calls);
+        let handle = ctx.hooks.on(KernelEventKind::HealCompleted, move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        });
+
+        ctx.commit(&mut Delta::new());
+        assert!(ctx.hooks.off(handle));
+        assert!(!ctx.hooks.off(handle));
+        ctx.commit(&mut Delta::new());
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(ctx.hooks.is_empty());
+    }
+}
@@ -9,18 +9,27 @@
 //! - Coordinates: rounded to 0.01 mm (QUANTIZE_PRECISION)
 //! - IDs: sorted alphabetically for stable ordering
 //! - Arrays: sorted by a deterministic key
+//!
+//! Whole models are saved and loaded through [`model`].
+
+pub mod model;
 
 use crate::constants::{quantize, quantize_point2, quantize_point3};
+use serde_json::ser::Formatter;
 use serde_json::{json, Map, Value};
+use std::io;
 
 /// Quantize all numeric values in a JSON Value recursively.
 ///
 /// This ensures deterministic output regardless of floating point
-/// representation differences across platforms.
+/// representation differences across platforms. Integers (counts,
+/// versions) are left as they are.
 pub fn quantize_json(value: &Value) -> Value {
     match value {
         Value::Number(n) => {
-            if let Some(f) = n.as_f64() {
+            if n.is_i64() || n.is_u64() {
+                value.clone()
+            } else if let Some(f) = n.as_f64() {
                 // Quantize and re-encode
                 let q = quantize(f);
                 // Avoid -0.0
@@ -110,6 +119,38 @@ pub fn to_deterministic_json_compact(value: &Value) -> String {
     serde_json::to_string(&prepared).unwrap_or_else(|_| "{}".to_string())
 }
 
+/// JSON formatter that writes floats as plain decimals.
+///
+/// serde_json switches to exponent notation for very small and very large
+/// floats; `Display` for `f64` never does.
+struct PlainDecimalFormatter;
+
+impl Formatter for PlainDecimalFormatter {
+    fn write_f64<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f64) -> io::Result<()> {
+        let value = if value == 0.0 { 0.0 } else { value };
+        write!(writer, "{}", value)
+    }
+
+    fn write_f32<W: ?Sized + io::Write>(&mut self, writer: &mut W, value: f32) -> io::Result<()> {
+        self.write_f64(writer, f64::from(value))
+    }
+}
+
+/// Serialize to compact deterministic JSON with plain decimal numbers.
+///
+/// Unlike [`to_deterministic_json_compact`], numbers are not quantized:
+/// report values are already rounded to their own precision by
+/// [`crate::report::NumberFormat`].
+pub fn to_plain_decimal_json(value: &Value) -> String {
+    let sorted = sort_for_determinism(value);
+    let mut out = Vec::new();
+    let mut serializer = serde_json::Serializer::with_formatter(&mut out, PlainDecimalFormatter);
+    match serde::Serialize::serialize(&sorted, &mut serializer) {
+        Ok(()) => String::from_utf8(out).unwrap_or_else(|_| "{}".to_string()),
+        Err(_) => "{}".to_string(),
+    }
+}
+
 /// Helper: quantize a point from JSON params.
 pub fn extract_point2(value: &Value) -> Option<[f64; 2]> {
     if let Value::Array(arr) = value {
@@ -231,6 +272,25 @@ mod tests {
         assert_eq!(output1, output2);
     }
 
+    #[test]
+    fn plain_decimal_json_has_no_exponent() {
+        let input = json!({"big": 1.5e17, "small": 1.2e-7, "zero": -0.0});
+        let output = to_plain_decimal_json(&input);
+
+        assert_eq!(
+            output,
+            r#"{"big":150000000000000000,"small":0.00000012,"zero":0}"#
+        );
+    }
+
+    #[test]
+    fn quantize_json_keeps_integers() {
+        let input = json!({"count": 3, "offset": -2, "length": 3.0});
+        let output = to_deterministic_json_compact(&input);
+
+        assert_eq!(output, r#"{"count":3,"length":3.0,"offset":-2}"#);
+    }
+
     #[test]
     fn negative_zero_avoided() {
         let input = json!(-0.0);
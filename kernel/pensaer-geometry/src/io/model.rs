@@ -0,0 +1,206 @@
+//! Whole-model serialization.
+//!
+//! A [`ModelDocument`] holds every element type of a model in one canonical
+//! JSON document. Saving goes through the deterministic JSON path: keys are
+//! sorted, arrays of elements are ordered by id, and floats are quantized to
+//! [`QUANTIZE_PRECISION`](crate::constants::QUANTIZE_PRECISION), so saving a
+//! loaded document reproduces it byte for byte.
+//!
+//! # Compatibility
+//!
+//! - Documents carry a `schema_version`; [`load_model`] rejects any version
+//!   other than [`MODEL_SCHEMA_VERSION`].
+//! - Unknown fields, on elements or at the top level, are ignored, so
+//!   documents written by newer builds with extra fields still load.
+//! - Missing element lists load as empty.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::to_deterministic_json;
+use crate::elements::{Door, Floor, Roof, Room, Wall, Window};
+use crate::error::{GeometryError, GeometryResult};
+use crate::joins::WallJoin;
+
+/// Schema version written by [`save_model`] and accepted by [`load_model`].
+pub const MODEL_SCHEMA_VERSION: u32 = 1;
+
+/// A complete model: typed element lists plus a schema version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelDocument {
+    /// Schema version of the document.
+    pub schema_version: u32,
+    #[serde(default)]
+    pub walls: Vec<Wall>,
+    #[serde(default)]
+    pub floors: Vec<Floor>,
+    #[serde(default)]
+    pub roofs: Vec<Roof>,
+    #[serde(default)]
+    pub doors: Vec<Door>,
+    #[serde(default)]
+    pub windows: Vec<Window>,
+    #[serde(default)]
+    pub rooms: Vec<Room>,
+    #[serde(default)]
+    pub joins: Vec<WallJoin>,
+}
+
+impl ModelDocument {
+    /// Create an empty document at the current schema version.
+    pub fn new() -> Self {
+        Self {
+            schema_version: MODEL_SCHEMA_VERSION,
+            walls: Vec::new(),
+            floors: Vec::new(),
+            roofs: Vec::new(),
+            doors: Vec::new(),
+            windows: Vec::new(),
+            rooms: Vec::new(),
+            joins: Vec::new(),
+        }
+    }
+
+    /// Total number of elements, joins included.
+    pub fn element_count(&self) -> usize {
+        self.walls.len()
+            + self.floors.len()
+            + self.roofs.len()
+            + self.doors.len()
+            + self.windows.len()
+            + self.rooms.len()
+            + self.joins.len()
+    }
+}
+
+impl Default for ModelDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serialize a model to canonical JSON.
+///
+/// The document is written at [`MODEL_SCHEMA_VERSION`] whatever its
+/// `schema_version` field says.
+pub fn save_model(document: &ModelDocument) -> String {
+    let mut value = serde_json::to_value(document).unwrap_or(Value::Null);
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("schema_version".to_string(), MODEL_SCHEMA_VERSION.into());
+    }
+    to_deterministic_json(&value)
+}
+
+/// Load a model saved by [`save_model`].
+///
+/// # Errors
+///
+/// - [`GeometryError::UnsupportedSchemaVersion`] when the document was
+///   written with another schema version.
+/// - [`GeometryError::InvalidModelDocument`] when the text is not JSON, has
+///   no schema version, or an element does not match its type.
+pub fn load_model(json: &str) -> GeometryResult<ModelDocument> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|e| GeometryError::InvalidModelDocument(e.to_string()))?;
+
+    let version = value
+        .get("schema_version")
+        .ok_or_else(|| GeometryError::InvalidModelDocument("missing schema_version".to_string()))?;
+    if version.as_u64() != Some(u64::from(MODEL_SCHEMA_VERSION)) {
+        return Err(GeometryError::UnsupportedSchemaVersion(format!(
+            "{} (this build reads version {})",
+            version, MODEL_SCHEMA_VERSION
+        )));
+    }
+
+    serde_json::from_value(value).map_err(|e| GeometryError::InvalidModelDocument(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::joins::JoinDetector;
+    use pensaer_math::Point2;
+
+    /// The simple building: four walls around a 10 x 8 floor and room,
+    /// with a door, a window, a roof, and the corner joins.
+    fn simple_building() -> ModelDocument {
+        let corners = [
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 8.0),
+            Point2::new(0.0, 8.0),
+        ];
+        let walls: Vec<Wall> = (0..4)
+            .map(|i| Wall::new(corners[i], corners[(i + 1) % 4], 3.0, 0.2).unwrap())
+            .collect();
+        let refs: Vec<&Wall> = walls.iter().collect();
+        let joins = JoinDetector::new(0.001, 0.1).detect_all(&refs);
+
+        ModelDocument {
+            floors: vec![Floor::rectangle(corners[0], corners[2], 0.3).unwrap()],
+            roofs: vec![Roof::rectangle(corners[0], corners[2], 0.25).unwrap()],
+            rooms: vec![
+                Room::rectangle("Living Room", "101", corners[0], corners[2], 3.0).unwrap(),
+            ],
+            doors: vec![Door::new(walls[0].id, 0.9, 2.1, 2.0).unwrap()],
+            windows: vec![Window::new(walls[1].id, 1.2, 1.5, 0.9, 4.0).unwrap()],
+            walls,
+            joins,
+            ..ModelDocument::new()
+        }
+    }
+
+    #[test]
+    fn round_trip_is_byte_identical() {
+        let doc = simple_building();
+        assert_eq!(doc.joins.len(), 4);
+
+        let saved = save_model(&doc);
+        let loaded = load_model(&saved).unwrap();
+        assert_eq!(loaded.element_count(), doc.element_count());
+        assert_eq!(save_model(&loaded), saved);
+    }
+
+    #[test]
+    fn round_trip_keeps_elements() {
+        let doc = simple_building();
+        let loaded = load_model(&save_model(&doc)).unwrap();
+
+        let mut wall_ids: Vec<_> = doc.walls.iter().map(|w| w.id).collect();
+        wall_ids.sort();
+        let loaded_ids: Vec<_> = loaded.walls.iter().map(|w| w.id).collect();
+        assert_eq!(loaded_ids, wall_ids);
+
+        assert_eq!(loaded.doors[0].host_wall_id, doc.doors[0].host_wall_id);
+        assert_eq!(loaded.rooms[0].name, "Living Room");
+        assert!((loaded.windows[0].sill_height - 0.9).abs() < 1e-9);
+        assert!((loaded.floors[0].thickness - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unknown_schema_version_is_rejected() {
+        let saved = save_model(&simple_building());
+        let future = saved.replacen("\"schema_version\": 1", "\"schema_version\": 99", 1);
+        assert_ne!(future, saved);
+
+        let err = load_model(&future).unwrap_err();
+        assert!(matches!(err, GeometryError::UnsupportedSchemaVersion(_)));
+        assert!(err.to_string().contains("99"));
+
+        let missing = load_model(r#"{"walls": []}"#).unwrap_err();
+        assert!(matches!(missing, GeometryError::InvalidModelDocument(_)));
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored() {
+        let saved = save_model(&simple_building());
+        let mut value: Value = serde_json::from_str(&saved).unwrap();
+        value["walls"][0]["fire_rating"] = "EI 60".into();
+        value["stairs"] = serde_json::json!([]);
+
+        let loaded = load_model(&value.to_string()).unwrap();
+        assert_eq!(loaded.walls.len(), 4);
+        assert_eq!(save_model(&loaded), saved);
+    }
+}
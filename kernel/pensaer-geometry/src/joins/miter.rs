@@ -3,7 +3,7 @@
 //! A miter join creates a clean diagonal cut where two walls meet at an angle.
 //! The algorithm:
 //! 1. Computes wall directions pointing away from the join
-//! 2. Finds the bisector line between the walls
+//! 2. Finds the miter line through the inner and outer corners
 //! 3. Intersects wall edges with the miter line
 //! 4. Returns modified corner profiles for each wall
 //!
@@ -32,7 +32,7 @@ pub struct MiterJoinResult {
     pub profile_a: WallJoinProfile,
     /// Profile for the second wall.
     pub profile_b: WallJoinProfile,
-    /// The miter line direction (perpendicular to bisector).
+    /// The miter line direction (through the inner and outer corners).
     pub miter_direction: Vector2,
     /// The bisector direction (between the two walls).
     pub bisector: Vector2,
@@ -69,8 +69,12 @@ pub fn compute_miter_join(
     // Compute bisector (average of the two directions, normalized)
     let bisector = compute_bisector(&dir_a, &dir_b)?;
 
-    // Miter line is perpendicular to the bisector
-    let miter_direction = bisector.perp();
+    // The miter line runs through the join point from the inner to the outer
+    // corner; for collinear walls it is perpendicular to them
+    let miter_direction =
+        outer_corner_offset(dir_a, wall_a.thickness / 2.0, dir_b, wall_b.thickness / 2.0)
+            .and_then(|u| u.normalize().ok())
+            .unwrap_or(bisector);
 
     // Compute corner profiles for each wall
     let profile_a = compute_wall_miter_profile(
@@ -110,6 +114,38 @@ fn get_wall_direction_from_join(wall: &Wall, end: WallEnd) -> GeometryResult<Vec
     }
 }
 
+/// Offset from the join point to the corner where the outer faces of two
+/// walls meet, given unit directions pointing away from the join and half
+/// thicknesses. `None` for parallel walls.
+pub(super) fn outer_corner_offset(
+    dir_a: Vector2,
+    half_a: f64,
+    dir_b: Vector2,
+    half_b: f64,
+) -> Option<Vector2> {
+    // Outer faces: the side of each wall facing away from the other
+    let outward = |dir: Vector2, other: Vector2| {
+        let n = dir.perp();
+        if n.dot(&other) > 0.0 {
+            -n
+        } else {
+            n
+        }
+    };
+    let n_a = outward(dir_a, dir_b);
+    let n_b = outward(dir_b, dir_a);
+
+    // Solve u . n_a = half_a and u . n_b = half_b
+    let det = n_a.cross(&n_b);
+    if det.abs() < 1e-10 {
+        return None;
+    }
+    Some(Vector2::new(
+        (half_a * n_b.y - half_b * n_a.y) / det,
+        (n_a.x * half_b - n_b.x * half_a) / det,
+    ))
+}
+
 /// Compute the bisector direction between two vectors.
 ///
 /// The bisector splits the angle between the vectors equally.
@@ -404,4 +440,32 @@ mod tests {
             assert!(corner.distance_to(&join_point) < wall.thickness * 3.0);
         }
     }
+
+    #[test]
+    fn miter_cuts_from_inner_to_outer_corner() {
+        let wall_a = create_test_wall((0.0, 0.0), (5.0, 0.0));
+        let mut wall_b = create_test_wall((5.0, 0.0), (5.0, 4.0));
+        wall_b.thickness = 0.4;
+
+        let result = compute_miter_join(
+            &wall_a,
+            &wall_b,
+            Point2::new(5.0, 0.0),
+            WallEnd::End,
+            WallEnd::Start,
+            0.001,
+        )
+        .unwrap();
+
+        // Both end faces run from (4.8, 0.1) to the outer corner (5.2, -0.1)
+        for profile in [&result.profile_a, &result.profile_b] {
+            let near = &profile.corners[..2];
+            assert!(near
+                .iter()
+                .any(|p| p.distance_to(&Point2::new(4.8, 0.1)) < 1e-9));
+            assert!(near
+                .iter()
+                .any(|p| p.distance_to(&Point2::new(5.2, -0.1)) < 1e-9));
+        }
+    }
 }
@@ -36,12 +36,15 @@ mod miter;
 pub use detect::JoinDetector;
 pub use miter::{compute_miter_join, MiterJoinResult};
 
+use miter::outer_corner_offset;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use pensaer_math::{Point2, Vector2};
+use pensaer_math::{Line2, LineSegment2, Point2, Vector2};
 
-use crate::elements::Wall;
+use crate::element::next_element_id;
+use crate::elements::{Wall, WallEndCap, WallOpening};
 use crate::error::{GeometryError, GeometryResult};
 use crate::mesh::TriangleMesh;
 
@@ -111,7 +114,7 @@ impl WallJoin {
         angle: f64,
     ) -> Self {
         Self {
-            id: Uuid::new_v4(),
+            id: next_element_id("WallJoin", &wall_ids, &[join_point.x, join_point.y, angle]),
             join_type,
             wall_ids,
             wall_ends,
@@ -129,6 +132,99 @@ impl WallJoin {
     pub fn wall_count(&self) -> usize {
         self.wall_ids.len()
     }
+
+    /// Replace a split wall with whichever half lies at the join point.
+    ///
+    /// The halves from [`Wall::split_at`] keep the original's start and end,
+    /// so the recorded wall ends stay valid. Returns `true` if this join
+    /// involved the original wall.
+    pub fn reassign_split(&mut self, original_id: Uuid, first: &Wall, second: &Wall) -> bool {
+        let Some(pos) = self.wall_ids.iter().position(|&id| id == original_id) else {
+            return false;
+        };
+        let distance = |wall: &Wall| {
+            LineSegment2::new(wall.baseline.start, wall.baseline.end)
+                .distance_to_point(&self.join_point)
+        };
+        self.wall_ids[pos] = if distance(first) <= distance(second) {
+            first.id
+        } else {
+            second.id
+        };
+        true
+    }
+}
+
+/// Limits for [`JoinResolver::trim_or_extend`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrimPolicy {
+    /// Allow shortening the wall.
+    pub allow_trim: bool,
+    /// Allow lengthening the wall.
+    pub allow_extend: bool,
+    /// Maximum distance the endpoint may move when extending.
+    pub max_extension: f64,
+}
+
+impl Default for TrimPolicy {
+    fn default() -> Self {
+        Self {
+            allow_trim: true,
+            allow_extend: true,
+            max_extension: 10.0,
+        }
+    }
+}
+
+/// Whether a trim/extend shortened or lengthened the wall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrimKind {
+    /// The wall got shorter.
+    Trim,
+    /// The wall got longer.
+    Extend,
+    /// The endpoint already lay on the target.
+    Unchanged,
+}
+
+/// What [`JoinResolver::trim_or_extend`] changed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrimResult {
+    /// ID of the modified wall.
+    pub wall_id: Uuid,
+    /// Which end moved.
+    pub end: WallEnd,
+    /// Endpoint before the change.
+    pub old_point: Point2,
+    /// Endpoint after the change.
+    pub new_point: Point2,
+    /// Whether the wall was trimmed or extended.
+    pub kind: TrimKind,
+}
+
+/// One baseline end move made to keep a corner's footprint when its join
+/// type changes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EndAdjustment {
+    /// ID of the adjusted wall.
+    pub wall_id: Uuid,
+    /// Which end moves.
+    pub end: WallEnd,
+    /// Change in wall length; positive extends, negative trims.
+    pub delta: f64,
+}
+
+/// What [`JoinResolver::change_join_type`] changed, or would change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JoinChange {
+    /// ID of the join.
+    pub join_id: Uuid,
+    /// Join type before the change.
+    pub from: JoinType,
+    /// Join type after the change.
+    pub to: JoinType,
+    /// End moves, in the order they are applied.
+    pub adjustments: Vec<EndAdjustment>,
 }
 
 /// Profile of a wall at a join point.
@@ -148,6 +244,68 @@ pub struct WallJoinProfile {
     pub direction: Vector2,
 }
 
+impl WallJoinProfile {
+    /// How far the join cuts into the wall, measured along the wall from
+    /// its baseline end to the deeper of the two near corners.
+    pub fn trim_depth(&self, wall: &Wall) -> f64 {
+        let end = match self.wall_end {
+            WallEnd::Start => wall.baseline.start,
+            WallEnd::End => wall.baseline.end,
+        };
+        self.corners[..2]
+            .iter()
+            .map(|c| (*c - end).dot(&self.direction))
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Outline of a wall end that takes part in no join, shaped by its
+/// [`WallEndCap`]. See [`JoinResolver::free_end_profiles`].
+#[derive(Debug, Clone)]
+pub struct FreeEndProfile {
+    /// ID of the wall.
+    pub wall_id: Uuid,
+    /// Which end of the wall.
+    pub wall_end: WallEnd,
+    /// The cap shaping the end.
+    pub cap: WallEndCap,
+    /// Plan outline across the end, as from [`Wall::end_outline`].
+    pub outline: Vec<Point2>,
+}
+
+/// An opening shrunk away from a join so its jamb clears the trimmed
+/// wall end. See [`JoinResolver::with_opening_heal`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OpeningAdjustment {
+    /// ID of the host wall.
+    pub wall_id: Uuid,
+    /// ID of the opening.
+    pub opening_id: Uuid,
+    /// New distance from wall start to opening center.
+    pub offset_along_wall: f64,
+    /// New opening width.
+    pub width: f64,
+    /// How much narrower the opening got.
+    pub shrink: f64,
+}
+
+impl OpeningAdjustment {
+    /// Resize the opening in its host wall. Returns false if the wall has
+    /// no such opening.
+    pub fn apply(&self, wall: &mut Wall) -> GeometryResult<bool> {
+        if wall.id != self.wall_id {
+            return Ok(false);
+        }
+        let Some(opening) = wall.openings.iter().find(|o| o.id == self.opening_id) else {
+            return Ok(false);
+        };
+        let mut opening = opening.clone();
+        opening.offset_along_wall = self.offset_along_wall;
+        opening.width = self.width;
+        wall.replace_opening(opening)
+    }
+}
+
 /// Computed geometry for a join.
 #[derive(Debug, Clone)]
 pub struct JoinGeometry {
@@ -159,6 +317,8 @@ pub struct JoinGeometry {
     pub join_point: Point2,
     /// The type of join that was computed.
     pub join_type: JoinType,
+    /// Openings shrunk to clear the join, to be applied to their walls.
+    pub opening_adjustments: Vec<OpeningAdjustment>,
 }
 
 /// Resolves and computes wall joins.
@@ -167,6 +327,8 @@ pub struct JoinResolver {
     tolerance: f64,
     /// Angle tolerance for determining join types (in radians).
     angle_tolerance: f64,
+    /// Shrink openings that reach into a join instead of failing.
+    heal_openings: bool,
 }
 
 impl JoinResolver {
@@ -178,6 +340,7 @@ impl JoinResolver {
         Self {
             tolerance,
             angle_tolerance: 0.01, // ~0.5 degrees
+            heal_openings: false,
         }
     }
 
@@ -187,6 +350,13 @@ impl JoinResolver {
         self
     }
 
+    /// Shrink openings that reach into a join's trimmed wall end, rather
+    /// than failing with [`GeometryError::OpeningConflictsWithJoin`].
+    pub fn with_opening_heal(mut self, heal: bool) -> Self {
+        self.heal_openings = heal;
+        self
+    }
+
     /// Get the tolerance value.
     pub fn tolerance(&self) -> f64 {
         self.tolerance
@@ -200,14 +370,382 @@ impl JoinResolver {
         detector.detect_all(walls)
     }
 
+    /// Profiles of the wall ends that take part in none of `joins`, shaped
+    /// by each wall's end caps. Joined ends are shaped by their joins (see
+    /// [`JoinResolver::compute_join_geometry`]) and ignore their caps.
+    pub fn free_end_profiles(
+        &self,
+        walls: &[&Wall],
+        joins: &[WallJoin],
+    ) -> GeometryResult<Vec<FreeEndProfile>> {
+        let mut profiles = Vec::new();
+        for wall in walls {
+            for wall_end in [WallEnd::Start, WallEnd::End] {
+                let joined = joins.iter().any(|join| {
+                    join.wall_ids
+                        .iter()
+                        .zip(&join.wall_ends)
+                        .any(|(id, end)| *id == wall.id && *end == wall_end)
+                });
+                if joined {
+                    continue;
+                }
+                profiles.push(FreeEndProfile {
+                    wall_id: wall.id,
+                    wall_end,
+                    cap: wall.cap_at(wall_end),
+                    outline: wall.end_outline(wall_end)?,
+                });
+            }
+        }
+        Ok(profiles)
+    }
+
+    /// Trim or extend one end of a wall so it lands on another wall's baseline.
+    ///
+    /// The endpoint moves to the intersection of the two baselines (extended
+    /// as infinite lines). Openings keep their position in space, so offsets
+    /// are recomputed when the start moves.
+    pub fn trim_or_extend(
+        &self,
+        wall: &mut Wall,
+        end: WallEnd,
+        target: &Wall,
+        policy: TrimPolicy,
+    ) -> GeometryResult<TrimResult> {
+        let (fixed, moving) = match end {
+            WallEnd::Start => (wall.baseline.end, wall.baseline.start),
+            WallEnd::End => (wall.baseline.start, wall.baseline.end),
+        };
+
+        let line = Line2::from_points(fixed, moving).map_err(|_| GeometryError::ZeroLengthWall)?;
+        let target_line = Line2::from_points(target.baseline.start, target.baseline.end)
+            .map_err(|_| GeometryError::ZeroLengthWall)?;
+
+        let angle = line
+            .direction
+            .dot(&target_line.direction)
+            .abs()
+            .min(1.0)
+            .acos();
+        if angle < self.angle_tolerance {
+            return Err(GeometryError::ParallelBaselines);
+        }
+
+        // The line starts at the fixed end, so the parameter is the new length
+        let new_length = line
+            .intersect_parameter(&target_line)
+            .map_err(|_| GeometryError::ParallelBaselines)?;
+        if new_length < self.tolerance {
+            return Err(GeometryError::JoinComputationFailed(
+                "target lies behind the fixed end of the wall".to_string(),
+            ));
+        }
+
+        self.move_end(wall, end, &line, new_length, policy)
+    }
+
+    /// Lengthen (positive `delta`) or shorten one end of a wall along its
+    /// own baseline, under the same policy and opening checks as
+    /// [`trim_or_extend`](Self::trim_or_extend).
+    pub fn adjust_end(
+        &self,
+        wall: &mut Wall,
+        end: WallEnd,
+        delta: f64,
+        policy: TrimPolicy,
+    ) -> GeometryResult<TrimResult> {
+        let (fixed, moving) = match end {
+            WallEnd::Start => (wall.baseline.end, wall.baseline.start),
+            WallEnd::End => (wall.baseline.start, wall.baseline.end),
+        };
+        let line = Line2::from_points(fixed, moving).map_err(|_| GeometryError::ZeroLengthWall)?;
+
+        let new_length = wall.length() + delta;
+        if new_length < self.tolerance {
+            return Err(GeometryError::JoinComputationFailed(
+                "adjustment leaves no wall".to_string(),
+            ));
+        }
+        self.move_end(wall, end, &line, new_length, policy)
+    }
+
+    /// Move one end to `new_length` along `line`, which starts at the fixed end.
+    fn move_end(
+        &self,
+        wall: &mut Wall,
+        end: WallEnd,
+        line: &Line2,
+        new_length: f64,
+        policy: TrimPolicy,
+    ) -> GeometryResult<TrimResult> {
+        let moving = match end {
+            WallEnd::Start => wall.baseline.start,
+            WallEnd::End => wall.baseline.end,
+        };
+        let old_length = wall.length();
+        let delta = new_length - old_length;
+        let kind = if delta.abs() < self.tolerance {
+            TrimKind::Unchanged
+        } else if delta > 0.0 {
+            TrimKind::Extend
+        } else {
+            TrimKind::Trim
+        };
+
+        match kind {
+            TrimKind::Extend if !policy.allow_extend => {
+                return Err(GeometryError::JoinComputationFailed(
+                    "extending is not allowed".to_string(),
+                ))
+            }
+            TrimKind::Extend if delta > policy.max_extension => {
+                return Err(GeometryError::ExtensionTooLong(format!(
+                    "{:.3} exceeds limit of {:.3}",
+                    delta, policy.max_extension
+                )))
+            }
+            TrimKind::Trim if !policy.allow_trim => {
+                return Err(GeometryError::JoinComputationFailed(
+                    "trimming is not allowed".to_string(),
+                ))
+            }
+            _ => {}
+        }
+
+        // Offsets are measured from the start, which only moves for WallEnd::Start
+        let shift = match end {
+            WallEnd::Start => delta,
+            WallEnd::End => 0.0,
+        };
+        let misfit = wall.openings.iter().find(|o| {
+            o.start_offset() + shift < -self.tolerance
+                || o.end_offset() + shift > new_length + self.tolerance
+        });
+        if let Some(opening) = misfit {
+            return Err(GeometryError::OpeningOutOfBounds {
+                offset: opening.offset_along_wall + shift,
+                width: opening.width,
+                wall_length: new_length,
+            });
+        }
+
+        let new_point = line.point_at(new_length);
+        match end {
+            WallEnd::Start => wall.baseline.start = new_point,
+            WallEnd::End => wall.baseline.end = new_point,
+        }
+        for opening in &mut wall.openings {
+            opening.offset_along_wall += shift;
+        }
+
+        Ok(TrimResult {
+            wall_id: wall.id,
+            end,
+            old_point: moving,
+            new_point,
+            kind,
+        })
+    }
+
+    /// Change the type of a two-wall corner without changing the building's
+    /// overall dimensions.
+    ///
+    /// A miter corner has both baselines ending at the join point. At a butt
+    /// corner the first wall of the join runs through: it is extended to the
+    /// outer corner and the second wall is trimmed back to its face. Switching
+    /// between the two moves the ends accordingly, so the union footprint of
+    /// the two walls is kept (exactly for square corners). The ends move
+    /// through [`adjust_end`](Self::adjust_end), so `policy` and opening
+    /// checks apply; if either move fails, neither wall nor the join changes.
+    ///
+    /// `wall_a` and `wall_b` must be the join's walls in order.
+    pub fn change_join_type(
+        &self,
+        join: &mut WallJoin,
+        wall_a: &mut Wall,
+        wall_b: &mut Wall,
+        join_type: JoinType,
+        policy: TrimPolicy,
+    ) -> GeometryResult<JoinChange> {
+        let (change, a, b) = self.apply_join_change(join, wall_a, wall_b, join_type, policy)?;
+        *wall_a = a;
+        *wall_b = b;
+        join.join_type = join_type;
+        Ok(change)
+    }
+
+    /// Report the end moves [`change_join_type`](Self::change_join_type)
+    /// would make, without changing anything.
+    pub fn preview_join_change(
+        &self,
+        join: &WallJoin,
+        wall_a: &Wall,
+        wall_b: &Wall,
+        join_type: JoinType,
+        policy: TrimPolicy,
+    ) -> GeometryResult<JoinChange> {
+        self.apply_join_change(join, wall_a, wall_b, join_type, policy)
+            .map(|(change, _, _)| change)
+    }
+
+    /// Apply a join type change to copies of the walls.
+    fn apply_join_change(
+        &self,
+        join: &WallJoin,
+        wall_a: &Wall,
+        wall_b: &Wall,
+        join_type: JoinType,
+        policy: TrimPolicy,
+    ) -> GeometryResult<(JoinChange, Wall, Wall)> {
+        let is_corner =
+            |t: JoinType| matches!(t, JoinType::Butt | JoinType::Miter | JoinType::LJoin);
+        if join.wall_ids != [wall_a.id, wall_b.id]
+            || join.wall_ends.len() != 2
+            || !is_corner(join.join_type)
+            || !is_corner(join_type)
+        {
+            return Err(GeometryError::InvalidJoinConfiguration);
+        }
+
+        let mut adjustments = Vec::new();
+        let was_butt = join.join_type == JoinType::Butt;
+        if was_butt != (join_type == JoinType::Butt) {
+            if let Some((extend, trim)) =
+                self.butt_offsets(wall_a, join.wall_ends[0], wall_b, join.wall_ends[1])?
+            {
+                let sign = if was_butt { -1.0 } else { 1.0 };
+                adjustments.push(EndAdjustment {
+                    wall_id: wall_a.id,
+                    end: join.wall_ends[0],
+                    delta: sign * extend,
+                });
+                adjustments.push(EndAdjustment {
+                    wall_id: wall_b.id,
+                    end: join.wall_ends[1],
+                    delta: -sign * trim,
+                });
+            }
+        }
+
+        let mut a = wall_a.clone();
+        let mut b = wall_b.clone();
+        for (wall, adjustment) in [&mut a, &mut b].into_iter().zip(&adjustments) {
+            self.adjust_end(wall, adjustment.end, adjustment.delta, policy)?;
+        }
+
+        let change = JoinChange {
+            join_id: join.id,
+            from: join.join_type,
+            to: join_type,
+            adjustments,
+        };
+        Ok((change, a, b))
+    }
+
+    /// How far a butt corner extends the through wall `a` and trims the
+    /// butting wall `b`, relative to both ending at the join point. `None`
+    /// for collinear walls, where the two join types coincide.
+    fn butt_offsets(
+        &self,
+        wall_a: &Wall,
+        end_a: WallEnd,
+        wall_b: &Wall,
+        end_b: WallEnd,
+    ) -> GeometryResult<Option<(f64, f64)>> {
+        // Directions pointing away from the join
+        let away = |wall: &Wall, end: WallEnd| -> GeometryResult<Vector2> {
+            let dir = wall.direction()?;
+            Ok(match end {
+                WallEnd::Start => dir,
+                WallEnd::End => -dir,
+            })
+        };
+        let dir_a = away(wall_a, end_a)?;
+        let dir_b = away(wall_b, end_b)?;
+
+        let sin = dir_a.cross(&dir_b).abs();
+        if sin < self.angle_tolerance.sin() {
+            return Ok(None);
+        }
+
+        let (h_a, h_b) = (wall_a.thickness / 2.0, wall_b.thickness / 2.0);
+        let Some(u) = outer_corner_offset(dir_a, h_a, dir_b, h_b) else {
+            return Ok(None);
+        };
+
+        // The through wall reaches the outer corner; the butting wall stops
+        // at the through wall's inner face
+        let extend = -u.dot(&dir_a);
+        let trim = h_a / sin;
+        Ok(Some((extend, trim)))
+    }
+
     /// Compute the geometry for a specific join.
     ///
     /// This determines how wall endpoints should be modified to form a clean join.
+    /// An opening closer to a joined end than the join's trim depth would
+    /// leave a sliver between the cut and its jamb, so it is an error,
+    /// unless opening healing is on: then the opening is shrunk away from
+    /// the join and the change returned in
+    /// [`JoinGeometry::opening_adjustments`].
     pub fn compute_join_geometry(
         &self,
         walls: &[&Wall],
         join: &WallJoin,
     ) -> GeometryResult<JoinGeometry> {
+        let mut geometry = self.compute_profiles(walls, join)?;
+        for (wall, profile) in walls.iter().zip(&geometry.wall_profiles) {
+            for opening in &wall.openings {
+                if let Some(adjustment) = self.clear_opening(wall, profile, opening)? {
+                    geometry.opening_adjustments.push(adjustment);
+                }
+            }
+        }
+        Ok(geometry)
+    }
+
+    /// Check an opening clears a wall's trimmed end; when healing, the
+    /// adjustment that makes it clear.
+    fn clear_opening(
+        &self,
+        wall: &Wall,
+        profile: &WallJoinProfile,
+        opening: &WallOpening,
+    ) -> GeometryResult<Option<OpeningAdjustment>> {
+        let depth = profile.trim_depth(wall);
+        let length = wall.length();
+        let clearance = match profile.wall_end {
+            WallEnd::Start => opening.start_offset(),
+            WallEnd::End => length - opening.end_offset(),
+        };
+        let overlap = depth - clearance;
+        if overlap <= self.tolerance {
+            return Ok(None);
+        }
+        let width = opening.width - overlap;
+        if !self.heal_openings || width <= self.tolerance {
+            return Err(GeometryError::OpeningConflictsWithJoin(format!(
+                "opening {} reaches {:.3} m into the trimmed end of wall {}",
+                opening.id, overlap, wall.id
+            )));
+        }
+
+        let (start, end) = match profile.wall_end {
+            WallEnd::Start => (depth, opening.end_offset()),
+            WallEnd::End => (opening.start_offset(), length - depth),
+        };
+        Ok(Some(OpeningAdjustment {
+            wall_id: wall.id,
+            opening_id: opening.id,
+            offset_along_wall: (start + end) / 2.0,
+            width,
+            shrink: overlap,
+        }))
+    }
+
+    /// Wall end profiles for a join, before openings are considered.
+    fn compute_profiles(&self, walls: &[&Wall], join: &WallJoin) -> GeometryResult<JoinGeometry> {
         match join.join_type {
             JoinType::Miter | JoinType::LJoin => {
                 if walls.len() != 2 {
@@ -258,6 +796,7 @@ impl JoinResolver {
             fill_mesh: None, // Miter joins don't need fill
             join_point: join.join_point,
             join_type: JoinType::Miter,
+            opening_adjustments: Vec::new(),
         })
     }
 
@@ -278,6 +817,7 @@ impl JoinResolver {
             fill_mesh: None,
             join_point: join.join_point,
             join_type: JoinType::Butt,
+            opening_adjustments: Vec::new(),
         })
     }
 
@@ -300,6 +840,7 @@ impl JoinResolver {
             fill_mesh: None,
             join_point: join.join_point,
             join_type: JoinType::TJoin,
+            opening_adjustments: Vec::new(),
         })
     }
 
@@ -320,6 +861,7 @@ impl JoinResolver {
             fill_mesh: None, // Could add intersection fill mesh
             join_point: join.join_point,
             join_type: JoinType::CrossJoin,
+            opening_adjustments: Vec::new(),
         })
     }
 
@@ -363,6 +905,7 @@ impl Default for JoinResolver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::elements::{OpeningType, WallOpening};
     use pensaer_math::Point2;
     use std::f64::consts::PI;
 
@@ -465,4 +1008,432 @@ mod tests {
             .unwrap();
         assert_eq!(geometry.wall_profiles.len(), 2);
     }
+
+    #[test]
+    fn free_ends_take_their_caps() {
+        let mut wall1 = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        let wall2 = Wall::new(Point2::new(5.0, 0.0), Point2::new(5.0, 4.0), 3.0, 0.2).unwrap();
+        wall1.set_end_caps(WallEndCap::Round { segments: 6 });
+
+        let resolver = JoinResolver::default();
+        let joins = resolver.detect_joins(&[&wall1, &wall2]);
+        assert_eq!(joins.len(), 1);
+
+        // The corner ends are joined; only the far ends are free
+        let profiles = resolver
+            .free_end_profiles(&[&wall1, &wall2], &joins)
+            .unwrap();
+        assert_eq!(profiles.len(), 2);
+        let (round, flush) = (&profiles[0], &profiles[1]);
+        assert_eq!((round.wall_id, round.wall_end), (wall1.id, WallEnd::Start));
+        assert_eq!(round.outline.len(), 7);
+        assert_eq!((flush.wall_id, flush.wall_end), (wall2.id, WallEnd::End));
+        assert_eq!(flush.cap, WallEndCap::Flush);
+        assert_eq!(flush.outline.len(), 2);
+    }
+
+    #[test]
+    fn reassign_joins_after_split() {
+        let host = Wall::new(Point2::new(0.0, 0.0), Point2::new(10.0, 0.0), 3.0, 0.2).unwrap();
+        let corner = Wall::new(Point2::new(10.0, 0.0), Point2::new(10.0, 4.0), 3.0, 0.2).unwrap();
+        let branch = Wall::new(Point2::new(2.0, 5.0), Point2::new(2.0, 0.0), 3.0, 0.2).unwrap();
+
+        let resolver = JoinResolver::new(0.001);
+        let mut joins = resolver.detect_joins(&[&host, &corner, &branch]);
+        assert_eq!(joins.len(), 2);
+
+        let (first, second) = host.split_at(5.0).unwrap();
+        for join in &mut joins {
+            assert!(join.reassign_split(host.id, &first, &second));
+            assert!(!join.involves_wall(host.id));
+            if (join.join_point.x - 10.0).abs() < 1e-6 {
+                assert!(join.involves_wall(second.id));
+            } else {
+                assert!(join.involves_wall(first.id));
+            }
+        }
+
+        let walls = [&first, &second, &corner, &branch];
+        for join in &joins {
+            let members: Vec<&Wall> = join
+                .wall_ids
+                .iter()
+                .map(|id| *walls.iter().find(|w| w.id == *id).unwrap())
+                .collect();
+            assert!(resolver.compute_join_geometry(&members, join).is_ok());
+        }
+    }
+
+    fn wall(start: (f64, f64), end: (f64, f64)) -> Wall {
+        Wall::new(
+            Point2::new(start.0, start.1),
+            Point2::new(end.0, end.1),
+            3.0,
+            0.2,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn extend_end_to_target() {
+        let resolver = JoinResolver::default();
+        let mut w = wall((0.0, 0.0), (4.0, 0.0));
+        let target = wall((6.0, -2.0), (6.0, 2.0));
+
+        let result = resolver
+            .trim_or_extend(&mut w, WallEnd::End, &target, TrimPolicy::default())
+            .unwrap();
+
+        assert_eq!(result.kind, TrimKind::Extend);
+        assert_eq!(result.old_point, Point2::new(4.0, 0.0));
+        assert!((w.baseline.end.x - 6.0).abs() < 1e-10);
+        assert!((w.length() - 6.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn trim_start_keeps_openings_in_place() {
+        let resolver = JoinResolver::default();
+        let mut w = wall((0.0, 0.0), (10.0, 0.0));
+        w.add_opening(WallOpening::new(5.0, 0.0, 1.0, 2.1, OpeningType::Door))
+            .unwrap();
+        let target = wall((2.0, -1.0), (2.0, 1.0));
+
+        let result = resolver
+            .trim_or_extend(&mut w, WallEnd::Start, &target, TrimPolicy::default())
+            .unwrap();
+
+        assert_eq!(result.kind, TrimKind::Trim);
+        assert!((w.baseline.start.x - 2.0).abs() < 1e-10);
+        // The door stays at x = 5.0, now 3.0 from the new start
+        assert!((w.openings[0].offset_along_wall - 3.0).abs() < 1e-10);
+    }
+
+    /// An L corner at (5, 0) with a door on the first wall ending 50 mm
+    /// short of the corner.
+    fn door_at_corner() -> (Wall, Wall, WallJoin) {
+        let mut a = wall((0.0, 0.0), (5.0, 0.0));
+        a.add_opening(WallOpening::new(4.5, 0.0, 0.9, 2.1, OpeningType::Door))
+            .unwrap();
+        let b = wall((5.0, 0.0), (5.0, 4.0));
+        let join = JoinResolver::default().detect_joins(&[&a, &b]).remove(0);
+        (a, b, join)
+    }
+
+    #[test]
+    fn door_in_join_trim_fails() {
+        let (a, b, join) = door_at_corner();
+
+        // The inner faces meet 100 mm in from the corner
+        match JoinResolver::default().compute_join_geometry(&[&a, &b], &join) {
+            Err(GeometryError::OpeningConflictsWithJoin(msg)) => {
+                assert!(msg.contains("reaches 0.050 m"), "{msg}")
+            }
+            other => panic!("expected a conflict, got {:?}", other.map(|g| g.join_type)),
+        }
+    }
+
+    #[test]
+    fn door_in_join_trim_heals() {
+        let (mut a, b, join) = door_at_corner();
+        let resolver = JoinResolver::default().with_opening_heal(true);
+
+        let geometry = resolver.compute_join_geometry(&[&a, &b], &join).unwrap();
+        let [adjustment] = geometry.opening_adjustments[..] else {
+            panic!("expected one adjustment");
+        };
+        assert_eq!(adjustment.opening_id, a.openings[0].id);
+        assert!((adjustment.shrink - 0.05).abs() < 1e-9);
+        assert!((adjustment.width - 0.85).abs() < 1e-9);
+
+        // The shrunk door keeps its far jamb and clears the corner
+        assert!(adjustment.apply(&mut a).unwrap());
+        assert!((a.openings[0].start_offset() - 4.05).abs() < 1e-9);
+        assert!((a.openings[0].end_offset() - 4.9).abs() < 1e-9);
+        let geometry = JoinResolver::default()
+            .compute_join_geometry(&[&a, &b], &join)
+            .unwrap();
+        assert!(geometry.opening_adjustments.is_empty());
+
+        let mesh = crate::mesh::extrude_wall_with_openings(
+            a.length(),
+            a.height,
+            a.thickness,
+            &a.opening_rects(),
+        )
+        .unwrap();
+        assert!(mesh.is_valid());
+        assert!(!mesh.has_degenerate_triangles());
+    }
+
+    #[test]
+    fn trim_through_opening_fails() {
+        let resolver = JoinResolver::default();
+        let mut w = wall((0.0, 0.0), (10.0, 0.0));
+        w.add_opening(WallOpening::new(8.0, 0.0, 1.0, 2.1, OpeningType::Door))
+            .unwrap();
+        let target = wall((8.0, -1.0), (8.0, 1.0));
+
+        assert!(matches!(
+            resolver.trim_or_extend(&mut w, WallEnd::End, &target, TrimPolicy::default()),
+            Err(GeometryError::OpeningOutOfBounds { .. })
+        ));
+        assert!((w.length() - 10.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn parallel_target_fails() {
+        let resolver = JoinResolver::default();
+        let mut w = wall((0.0, 0.0), (4.0, 0.0));
+        // Within the ~0.5 degree angle tolerance
+        let target = wall((0.0, 1.0), (10.0, 1.05));
+
+        assert!(matches!(
+            resolver.trim_or_extend(&mut w, WallEnd::End, &target, TrimPolicy::default()),
+            Err(GeometryError::ParallelBaselines)
+        ));
+    }
+
+    #[test]
+    fn extension_limited() {
+        let resolver = JoinResolver::default();
+        let mut w = wall((0.0, 0.0), (4.0, 0.0));
+        // Shallow angle puts the intersection 50m away
+        let target = wall((0.0, 1.0), (10.0, 0.8));
+
+        let result = resolver.trim_or_extend(&mut w, WallEnd::End, &target, TrimPolicy::default());
+        assert!(matches!(result, Err(GeometryError::ExtensionTooLong(_))));
+
+        let policy = TrimPolicy {
+            max_extension: 100.0,
+            ..Default::default()
+        };
+        let result = resolver
+            .trim_or_extend(&mut w, WallEnd::End, &target, policy)
+            .unwrap();
+        assert!((result.new_point.x - 50.0).abs() < 1e-6);
+    }
+
+    /// Rectangle of four walls with a door near each end of the corner at
+    /// (10, 0), plus its detected joins.
+    fn rectangle_fixture() -> (Vec<Wall>, Vec<WallJoin>) {
+        let mut walls = vec![
+            wall((0.0, 0.0), (10.0, 0.0)),
+            wall((10.0, 0.0), (10.0, 8.0)),
+            wall((10.0, 8.0), (0.0, 8.0)),
+            wall((0.0, 8.0), (0.0, 0.0)),
+        ];
+        walls[0]
+            .add_opening(WallOpening::new(8.0, 0.0, 1.0, 2.1, OpeningType::Door))
+            .unwrap();
+        walls[1]
+            .add_opening(WallOpening::new(1.0, 0.0, 1.0, 2.1, OpeningType::Door))
+            .unwrap();
+        let refs: Vec<&Wall> = walls.iter().collect();
+        let joins = JoinResolver::default().detect_joins(&refs);
+        assert_eq!(joins.len(), 4);
+        (walls, joins)
+    }
+
+    /// Union of the wall outlines as resolved at their joins, quantized and
+    /// with each ring in a canonical order.
+    fn plan_footprint(walls: &[Wall], joins: &[WallJoin]) -> Vec<Vec<[f64; 2]>> {
+        use crate::constants::quantize_to;
+        use geo::{BooleanOps, LineString, MultiPolygon, Polygon};
+
+        let resolver = JoinResolver::default();
+        let find = |id: Uuid| walls.iter().find(|w| w.id == id).unwrap();
+
+        let mut union = MultiPolygon::<f64>::new(Vec::new());
+        for w in walls {
+            let normal = w.normal().unwrap();
+            let face = |end: WallEnd| {
+                let join = joins
+                    .iter()
+                    .find(|j| {
+                        j.wall_ids
+                            .iter()
+                            .zip(&j.wall_ends)
+                            .any(|(id, e)| *id == w.id && *e == end)
+                    })
+                    .unwrap();
+                let members: Vec<&Wall> = join.wall_ids.iter().map(|id| find(*id)).collect();
+                let geometry = resolver.compute_join_geometry(&members, join).unwrap();
+                let profile = geometry
+                    .wall_profiles
+                    .iter()
+                    .find(|p| p.wall_id == w.id)
+                    .unwrap();
+                // Order the end face corners right side first
+                let mut face = [profile.corners[0], profile.corners[1]];
+                face.sort_by(|p, q| {
+                    (*p - w.baseline.start)
+                        .dot(&normal)
+                        .total_cmp(&(*q - w.baseline.start).dot(&normal))
+                });
+                face
+            };
+            let (start, end) = (face(WallEnd::Start), face(WallEnd::End));
+            let ring: Vec<(f64, f64)> = [start[0], end[0], end[1], start[1]]
+                .iter()
+                .map(|p| (p.x, p.y))
+                .collect();
+            let outline = MultiPolygon::new(vec![Polygon::new(LineString::from(ring), vec![])]);
+            union = union.union(&outline);
+        }
+
+        let mut rings: Vec<Vec<[f64; 2]>> = union
+            .iter()
+            .flat_map(|poly| std::iter::once(poly.exterior()).chain(poly.interiors()))
+            .map(|ring| {
+                let mut pts: Vec<[f64; 2]> = ring
+                    .coords()
+                    .map(|c| [quantize_to(c.x, 1e-6) + 0.0, quantize_to(c.y, 1e-6) + 0.0])
+                    .collect();
+                pts.pop();
+                canonical_ring(pts)
+            })
+            .collect();
+        rings.sort_by(|a, b| {
+            a[0][0]
+                .total_cmp(&b[0][0])
+                .then(a[0][1].total_cmp(&b[0][1]))
+        });
+        rings
+    }
+
+    /// Drop collinear vertices, orient counter-clockwise, and start at the
+    /// lowest vertex.
+    fn canonical_ring(mut pts: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
+        let cross = |a: [f64; 2], b: [f64; 2], c: [f64; 2]| {
+            (b[0] - a[0]) * (c[1] - b[1]) - (b[1] - a[1]) * (c[0] - b[0])
+        };
+        let mut i = 0;
+        while i < pts.len() && pts.len() > 3 {
+            let n = pts.len();
+            if cross(pts[(i + n - 1) % n], pts[i], pts[(i + 1) % n]).abs() < 1e-12 {
+                pts.remove(i);
+                i = 0;
+            } else {
+                i += 1;
+            }
+        }
+        let area: f64 = (0..pts.len())
+            .map(|i| {
+                let (a, b) = (pts[i], pts[(i + 1) % pts.len()]);
+                a[0] * b[1] - b[0] * a[1]
+            })
+            .sum();
+        if area < 0.0 {
+            pts.reverse();
+        }
+        let first = (0..pts.len())
+            .min_by(|&i, &j| {
+                pts[i][0]
+                    .total_cmp(&pts[j][0])
+                    .then(pts[i][1].total_cmp(&pts[j][1]))
+            })
+            .unwrap();
+        pts.rotate_left(first);
+        pts
+    }
+
+    fn opening_positions(w: &Wall) -> Vec<Point2> {
+        let dir = w.direction().unwrap();
+        w.openings
+            .iter()
+            .map(|o| w.baseline.start + dir * o.offset_along_wall)
+            .collect()
+    }
+
+    #[test]
+    fn toggling_join_type_keeps_footprint() {
+        let resolver = JoinResolver::default();
+        let (mut walls, mut joins) = rectangle_fixture();
+        let corner = joins
+            .iter()
+            .position(|j| j.join_point.distance_to(&Point2::new(10.0, 0.0)) < 1e-9)
+            .unwrap();
+        let footprint = plan_footprint(&walls, &joins);
+        let doors: Vec<Vec<Point2>> = walls.iter().map(opening_positions).collect();
+
+        for join_type in [
+            JoinType::Butt,
+            JoinType::Miter,
+            JoinType::Butt,
+            JoinType::Miter,
+        ] {
+            let ia = walls
+                .iter()
+                .position(|w| w.id == joins[corner].wall_ids[0])
+                .unwrap();
+            let ib = walls
+                .iter()
+                .position(|w| w.id == joins[corner].wall_ids[1])
+                .unwrap();
+            let (lo, hi) = walls.split_at_mut(ia.max(ib));
+            let (a, b) = if ia < ib {
+                (&mut lo[ia], &mut hi[0])
+            } else {
+                (&mut hi[0], &mut lo[ib])
+            };
+
+            let change = resolver
+                .change_join_type(&mut joins[corner], a, b, join_type, TrimPolicy::default())
+                .unwrap();
+            assert_eq!(change.to, join_type);
+            assert_eq!(change.adjustments.len(), 2);
+            // Half of the other wall's thickness either way
+            let sign = if join_type == JoinType::Butt {
+                1.0
+            } else {
+                -1.0
+            };
+            assert!((change.adjustments[0].delta - sign * 0.1).abs() < 1e-9);
+            assert!((change.adjustments[1].delta + sign * 0.1).abs() < 1e-9);
+
+            assert_eq!(plan_footprint(&walls, &joins), footprint);
+            for (w, before) in walls.iter().zip(&doors) {
+                for (p, q) in opening_positions(w).iter().zip(before) {
+                    assert!(p.distance_to(q) < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn join_change_preview_and_policy_leave_walls_alone() {
+        let resolver = JoinResolver::default();
+        let (walls, joins) = rectangle_fixture();
+        let join = joins
+            .iter()
+            .find(|j| j.join_point.distance_to(&Point2::new(10.0, 0.0)) < 1e-9)
+            .unwrap();
+        let find = |id: Uuid| walls.iter().find(|w| w.id == id).unwrap().clone();
+        let (mut a, mut b) = (find(join.wall_ids[0]), find(join.wall_ids[1]));
+
+        let preview = resolver
+            .preview_join_change(join, &a, &b, JoinType::Butt, TrimPolicy::default())
+            .unwrap();
+        assert_eq!(preview.adjustments.len(), 2);
+        assert_eq!(a.baseline.start, find(a.id).baseline.start);
+        assert_eq!(a.baseline.end, find(a.id).baseline.end);
+
+        // Trimming the butting wall is not allowed, so nothing moves
+        let mut join = join.clone();
+        let policy = TrimPolicy {
+            allow_trim: false,
+            ..Default::default()
+        };
+        assert!(resolver
+            .change_join_type(&mut join, &mut a, &mut b, JoinType::Butt, policy)
+            .is_err());
+        assert_eq!(a.baseline.end, find(a.id).baseline.end);
+        assert_ne!(join.join_type, JoinType::Butt);
+
+        // Changing between the two miter-style corners moves nothing
+        let change = resolver
+            .change_join_type(&mut join, &mut a, &mut b, JoinType::Miter, policy)
+            .unwrap();
+        assert!(change.adjustments.is_empty());
+    }
 }
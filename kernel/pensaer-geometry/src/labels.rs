@@ -0,0 +1,430 @@
+//! Text label layout for plan exports.
+//!
+//! Plan exporters collect [`LabelCandidate`]s (room names, door and window
+//! tags, dimension texts, junction codes), each with a preferred anchor and
+//! a rule for how far it may move. [`layout_labels`] then places them
+//! greedily, most important kind first, so that no label box overlaps a
+//! placed label or drawn geometry (wall poché):
+//!
+//! - Room names try positions inside their room, nearest the anchor first.
+//!   A name that does not fit moves outside the room and gets a leader line.
+//! - Tags, dimension texts, and junction codes slide along a direction and
+//!   gain a leader once displaced beyond [`LabelLayoutOptions::leader_threshold`].
+//!
+//! Text boxes are estimated from the character count and an em size. The
+//! layout is deterministic: candidates are ordered by kind then id, and
+//! positions are tried in a fixed order.
+
+use serde::{Deserialize, Serialize};
+
+use pensaer_math::{BoundingBox2, Point2, Polygon2, Vector2};
+
+/// Kind of plan label, in placement priority order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelKind {
+    /// Room name, placed inside its room.
+    RoomName,
+    /// Door or window mark.
+    Tag,
+    /// Dimension text.
+    Dimension,
+    /// Wall junction code.
+    Junction,
+}
+
+/// How a label may move away from its anchor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LabelMotion {
+    /// Stays at the anchor.
+    Fixed,
+    /// Moves within a region; falls back to outside with a leader.
+    Within(Polygon2),
+    /// Slides along a direction (either way), preferring the positive side.
+    Along(Vector2),
+}
+
+/// A label to place, with its preferred position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelCandidate {
+    /// Identifier, used for stable ordering (e.g. the element id).
+    pub id: String,
+    /// Label kind.
+    pub kind: LabelKind,
+    /// Text shown.
+    pub text: String,
+    /// Preferred center of the label.
+    pub anchor: Point2,
+    /// Allowed displacement.
+    pub motion: LabelMotion,
+}
+
+impl LabelCandidate {
+    /// Room name at the centroid of the room boundary.
+    pub fn room_name(id: impl Into<String>, text: impl Into<String>, boundary: &Polygon2) -> Self {
+        Self {
+            id: id.into(),
+            kind: LabelKind::RoomName,
+            text: text.into(),
+            anchor: boundary.centroid(),
+            motion: LabelMotion::Within(boundary.clone()),
+        }
+    }
+
+    /// Tag at `anchor`, displaced along `direction` (e.g. away from its wall).
+    pub fn tag(
+        id: impl Into<String>,
+        text: impl Into<String>,
+        anchor: Point2,
+        direction: Vector2,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            kind: LabelKind::Tag,
+            text: text.into(),
+            anchor,
+            motion: LabelMotion::Along(direction.normalize().unwrap_or(Vector2::UNIT_Y)),
+        }
+    }
+
+    /// Dimension text at the midpoint of a dimension line, sliding along it.
+    pub fn dimension(
+        id: impl Into<String>,
+        text: impl Into<String>,
+        start: Point2,
+        end: Point2,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            kind: LabelKind::Dimension,
+            text: text.into(),
+            anchor: start.midpoint(&end),
+            motion: LabelMotion::Along((end - start).normalize().unwrap_or(Vector2::UNIT_X)),
+        }
+    }
+
+    /// Junction code at a wall junction, moving diagonally away from it.
+    pub fn junction(id: impl Into<String>, text: impl Into<String>, point: Point2) -> Self {
+        Self {
+            id: id.into(),
+            kind: LabelKind::Junction,
+            text: text.into(),
+            anchor: point,
+            motion: LabelMotion::Along(Vector2::new(1.0, 1.0) * std::f64::consts::FRAC_1_SQRT_2),
+        }
+    }
+}
+
+/// Text size and search settings for [`layout_labels`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LabelLayoutOptions {
+    /// Text height (em size) in plan units.
+    pub em_size: f64,
+    /// Average character width as a fraction of the em size.
+    pub char_width: f64,
+    /// Clear space kept around each label.
+    pub padding: f64,
+    /// Distance between tried positions.
+    pub step: f64,
+    /// Number of steps searched in each direction.
+    pub max_steps: usize,
+    /// Displacement beyond which a leader line is drawn.
+    pub leader_threshold: f64,
+}
+
+impl Default for LabelLayoutOptions {
+    fn default() -> Self {
+        Self {
+            em_size: 0.25,
+            char_width: 0.6,
+            padding: 0.05,
+            step: 0.1,
+            max_steps: 30,
+            leader_threshold: 0.5,
+        }
+    }
+}
+
+impl LabelLayoutOptions {
+    /// Estimated box size of a text, including padding.
+    pub fn text_size(&self, text: &str) -> Vector2 {
+        let chars = text.chars().count() as f64;
+        Vector2::new(
+            chars * self.em_size * self.char_width + 2.0 * self.padding,
+            self.em_size + 2.0 * self.padding,
+        )
+    }
+}
+
+/// A label at its final position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlacedLabel {
+    /// Identifier of the candidate.
+    pub id: String,
+    /// Label kind.
+    pub kind: LabelKind,
+    /// Text shown.
+    pub text: String,
+    /// Center of the label.
+    pub position: Point2,
+    /// Label box, including padding.
+    pub bbox: BoundingBox2,
+    /// Leader from the anchor to the label box, if displaced far enough.
+    pub leader: Option<[Point2; 2]>,
+    /// True if no free position was found and the label overlaps others.
+    pub overlapping: bool,
+}
+
+/// Place labels without overlapping each other or the obstacles.
+///
+/// Returns one placed label per candidate, in candidate order.
+pub fn layout_labels(
+    candidates: &[LabelCandidate],
+    obstacles: &[Polygon2],
+    options: &LabelLayoutOptions,
+) -> Vec<PlacedLabel> {
+    let obstacles: Vec<(BoundingBox2, &Polygon2)> = obstacles
+        .iter()
+        .filter_map(|p| p.bounding_box().map(|b| (b, p)))
+        .collect();
+
+    let mut order: Vec<usize> = (0..candidates.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (ca, cb) = (&candidates[a], &candidates[b]);
+        ca.kind.cmp(&cb.kind).then_with(|| ca.id.cmp(&cb.id))
+    });
+
+    let mut placed: Vec<Option<PlacedLabel>> = vec![None; candidates.len()];
+    let mut taken: Vec<BoundingBox2> = Vec::with_capacity(candidates.len());
+    for index in order {
+        let candidate = &candidates[index];
+        let size = options.text_size(&candidate.text);
+        let is_free = |bbox: &BoundingBox2| {
+            !taken.iter().any(|t| boxes_overlap(t, bbox))
+                && !obstacles
+                    .iter()
+                    .any(|(b, p)| boxes_overlap(b, bbox) && p.intersects(&rectangle(bbox)))
+        };
+
+        let found = positions(candidate, size, options)
+            .into_iter()
+            .find(|(center, _)| is_free(&box_at(*center, size)));
+        let (position, leader, overlapping) = match found {
+            Some((center, leader)) => (center, leader, false),
+            None => (candidate.anchor, false, true),
+        };
+
+        let bbox = box_at(position, size);
+        let leader = (leader && position.distance_to(&candidate.anchor) > options.leader_threshold)
+            .then(|| [candidate.anchor, nearest_on_box(&bbox, candidate.anchor)]);
+        taken.push(bbox);
+        placed[index] = Some(PlacedLabel {
+            id: candidate.id.clone(),
+            kind: candidate.kind,
+            text: candidate.text.clone(),
+            position,
+            bbox,
+            leader,
+            overlapping,
+        });
+    }
+
+    placed.into_iter().flatten().collect()
+}
+
+/// Positions to try, nearest first, with whether each may take a leader.
+fn positions(
+    candidate: &LabelCandidate,
+    size: Vector2,
+    options: &LabelLayoutOptions,
+) -> Vec<(Point2, bool)> {
+    let anchor = candidate.anchor;
+    let steps = options.max_steps as i64;
+    let at = |dir: Vector2, k: i64| anchor + dir * (k as f64 * options.step);
+
+    match &candidate.motion {
+        LabelMotion::Fixed => vec![(anchor, false)],
+        LabelMotion::Along(dir) => {
+            let mut out = vec![(anchor, true)];
+            for k in 1..=steps {
+                out.push((at(*dir, k), true));
+                out.push((at(*dir, -k), true));
+            }
+            out
+        }
+        LabelMotion::Within(region) => {
+            // Grid offsets inside the region, ordered by distance then (y, x)
+            let mut grid = Vec::new();
+            for j in -steps..=steps {
+                for i in -steps..=steps {
+                    grid.push((i * i + j * j, j, i));
+                }
+            }
+            grid.sort_unstable();
+            let mut out: Vec<(Point2, bool)> = grid
+                .into_iter()
+                .map(|(_, j, i)| anchor + Vector2::new(i as f64, j as f64) * options.step)
+                .filter(|p| box_inside(region, &box_at(*p, size)))
+                .map(|p| (p, false))
+                .collect();
+
+            // Outside the region, rings of eight directions
+            let diagonal = std::f64::consts::FRAC_1_SQRT_2;
+            let directions = [
+                Vector2::new(0.0, 1.0),
+                Vector2::new(1.0, 0.0),
+                Vector2::new(0.0, -1.0),
+                Vector2::new(-1.0, 0.0),
+                Vector2::new(diagonal, diagonal),
+                Vector2::new(diagonal, -diagonal),
+                Vector2::new(-diagonal, -diagonal),
+                Vector2::new(-diagonal, diagonal),
+            ];
+            for k in 1..=steps {
+                for dir in directions {
+                    let p = at(dir, k);
+                    if !region.intersects(&rectangle(&box_at(p, size))) {
+                        out.push((p, true));
+                    }
+                }
+            }
+            out
+        }
+    }
+}
+
+/// Label box centered at a point.
+fn box_at(center: Point2, size: Vector2) -> BoundingBox2 {
+    let half = size * 0.5;
+    BoundingBox2::new(center - half, center + half)
+}
+
+/// Strict overlap: boxes that only touch do not overlap.
+fn boxes_overlap(a: &BoundingBox2, b: &BoundingBox2) -> bool {
+    a.min.x < b.max.x && a.max.x > b.min.x && a.min.y < b.max.y && a.max.y > b.min.y
+}
+
+fn rectangle(bbox: &BoundingBox2) -> Polygon2 {
+    Polygon2::rectangle(bbox.min, bbox.max)
+}
+
+/// Whether a box lies inside a region: all corners inside and no region
+/// vertex poking into the box.
+fn box_inside(region: &Polygon2, bbox: &BoundingBox2) -> bool {
+    let corners = [
+        bbox.min,
+        Point2::new(bbox.max.x, bbox.min.y),
+        bbox.max,
+        Point2::new(bbox.min.x, bbox.max.y),
+    ];
+    corners.iter().all(|c| region.contains_point(c))
+        && !region
+            .vertices
+            .iter()
+            .any(|v| v.x > bbox.min.x && v.x < bbox.max.x && v.y > bbox.min.y && v.y < bbox.max.y)
+}
+
+fn nearest_on_box(bbox: &BoundingBox2, p: Point2) -> Point2 {
+    Point2::new(
+        p.x.clamp(bbox.min.x, bbox.max.x),
+        p.y.clamp(bbox.min.y, bbox.max.y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(min: (f64, f64), max: (f64, f64)) -> Polygon2 {
+        Polygon2::rectangle(Point2::new(min.0, min.1), Point2::new(max.0, max.1))
+    }
+
+    #[test]
+    fn room_name_stays_at_centroid_when_it_fits() {
+        let office = room((0.0, 0.0), (6.0, 4.0));
+        let labels = layout_labels(
+            &[LabelCandidate::room_name("r1", "Office", &office)],
+            &[],
+            &LabelLayoutOptions::default(),
+        );
+
+        assert_eq!(labels[0].position, Point2::new(3.0, 2.0));
+        assert!(labels[0].leader.is_none());
+        assert!(!labels[0].overlapping);
+    }
+
+    #[test]
+    fn small_room_name_moves_outside_with_leader() {
+        let closet = room((0.0, 0.0), (1.0, 1.0));
+        let labels = layout_labels(
+            &[LabelCandidate::room_name("r1", "Mechanical Room", &closet)],
+            &[],
+            &LabelLayoutOptions::default(),
+        );
+
+        let label = &labels[0];
+        assert!(!label.overlapping);
+        assert!(!closet.intersects(&rectangle(&label.bbox)));
+        let [from, to] = label.leader.unwrap();
+        assert_eq!(from, Point2::new(0.5, 0.5));
+        assert!(to.distance_to(&nearest_on_box(&label.bbox, from)) < 1e-12);
+    }
+
+    #[test]
+    fn adjacent_tags_do_not_overlap() {
+        let candidates = [
+            LabelCandidate::tag("d1", "Door 101", Point2::new(0.0, 0.0), Vector2::UNIT_Y),
+            LabelCandidate::tag("d2", "Door 102", Point2::new(1.0, 0.0), Vector2::UNIT_Y),
+        ];
+        let labels = layout_labels(&candidates, &[], &LabelLayoutOptions::default());
+
+        assert!(!boxes_overlap(&labels[0].bbox, &labels[1].bbox));
+        assert!(labels.iter().all(|l| !l.overlapping));
+        // The lower id keeps its preferred position
+        assert_eq!(labels[0].position, Point2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn labels_avoid_obstacles() {
+        let wall = room((-2.0, -0.1), (2.0, 0.1));
+        let labels = layout_labels(
+            &[LabelCandidate::tag(
+                "d1",
+                "D1",
+                Point2::new(0.0, 0.0),
+                Vector2::UNIT_Y,
+            )],
+            std::slice::from_ref(&wall),
+            &LabelLayoutOptions::default(),
+        );
+
+        assert!(!wall.intersects(&rectangle(&labels[0].bbox)));
+        assert!(labels[0].position.y > 0.0);
+    }
+
+    #[test]
+    fn layout_is_deterministic() {
+        let hall = room((0.0, 0.0), (3.0, 2.0));
+        let candidates: Vec<LabelCandidate> = (0..6)
+            .map(|i| {
+                LabelCandidate::tag(
+                    format!("t{}", i),
+                    "Tag",
+                    Point2::new(1.5, 1.0),
+                    Vector2::UNIT_X,
+                )
+            })
+            .chain([LabelCandidate::room_name("r1", "Hall", &hall)])
+            .collect();
+
+        let options = LabelLayoutOptions::default();
+        let a = layout_labels(&candidates, &[], &options);
+        let b = layout_labels(&candidates, &[], &options);
+        assert_eq!(a, b);
+        for (i, x) in a.iter().enumerate() {
+            for y in &a[i + 1..] {
+                assert!(!boxes_overlap(&x.bbox, &y.bbox));
+            }
+        }
+    }
+}
@@ -5,6 +5,10 @@
 //! - **Elements**: Parametric BIM elements (walls, floors, doors, windows, rooms)
 //! - **Meshing**: Triangle mesh generation for 3D visualization
 //! - **Element System**: Common traits and types for all BIM elements
+//! - **Editing**: Align, distribute, and square up walls
+//! - **Constraints**: Lock wall lengths, angles, and node coincidence
+//! - **Catalogs**: Named door and window types shared by their instances
+//! - **Validation**: Lint a whole model before export
 //! - **PyO3 Bindings**: Python integration for MCP tool servers (enable with `python` feature)
 //!
 //! # Example
@@ -63,7 +67,13 @@
 //! | Wall mesh (3 openings) | < 10ms |
 //! | Room detection (20 walls) | < 50ms |
 //! | Join detection (10 walls) | < 10ms |
+//! | Nearest edge query (10k edges) | < 1ms |
+//! | Snap query (5k edges) | < 1ms |
 
+pub mod analysis;
+pub mod catalog;
+pub mod constraints;
+pub mod edit;
 pub mod element;
 pub mod elements;
 pub mod error;
@@ -74,8 +84,15 @@ pub mod mesh;
 pub mod constants;
 pub mod exec;
 pub mod fixup;
+pub mod hooks;
+pub mod ids;
 pub mod io;
+pub mod labels;
+pub mod properties;
+pub mod report;
+pub mod templates;
 pub mod util;
+pub mod validation;
 
 // M1: Spatial indexing
 pub mod spatial;
@@ -88,37 +105,73 @@ pub mod topology;
 pub mod bindings;
 
 // Re-export main types at crate root for convenience
-pub use element::{Element, ElementMetadata, ElementType};
+pub use analysis::{quantities_for, ElementQuantities, QuantityReport, QuantityRollup};
+pub use catalog::{Catalog, DoorTypeDef, WindowTypeDef};
+pub use constraints::{Constraint, ConstraintSet, EnforceReport, Violation};
+pub use edit::{
+    align_walls, distribute_walls_evenly, make_perpendicular, AlignAxis, EditReport, EndpointMove,
+};
+pub use element::{
+    content_id, filter_by_tag, id_mode, next_element_id, set_id_mode, transform_elements, Element,
+    ElementMetadata, ElementType, Level, PlanTransform, TransformableElement,
+};
 pub use elements::{
-    Door, DoorSwing, DoorType, Floor, FloorType, OpeningType, RidgeDirection, Roof, RoofType, Room,
-    Wall, WallBaseline, WallOpening, WallType, Window, WindowType,
+    floors_from_rooms, offset_walls, Building, Ceiling, ContextMesh, ContextMeshOptions, Door,
+    DoorSwing, DoorSwingGeometry, DoorType, ElementSnapshot, Floor, FloorType, InfillType,
+    LevelSummary, MeshChunk, MeshReference, OffsetSide, OpeningType, RailProfile, Railing,
+    RailingFinding, RailingHost, RailingRules, RailingTakeoff, RidgeDirection, Roof, RoofOpening,
+    RoofOpeningType, RoofType, Room, RoomFloors, Skylight, SplitPolicy, Stair, StairFinding,
+    StairLanding, StairRules, TopProfile, Wall, WallBaseline, WallEndCap, WallOpening, WallType,
+    Window, WindowType,
 };
 pub use error::{GeometryError, GeometryResult};
 pub use joins::{
-    JoinDetector, JoinGeometry, JoinResolver, JoinType, WallEnd, WallJoin, WallJoinProfile,
+    EndAdjustment, FreeEndProfile, JoinChange, JoinDetector, JoinGeometry, JoinResolver, JoinType,
+    OpeningAdjustment, TrimKind, TrimPolicy, TrimResult, WallEnd, WallJoin, WallJoinProfile,
 };
 pub use mesh::{
-    extrude_polygon, extrude_polygon_with_hole, extrude_wall_with_openings, triangulate_polygon,
-    triangulate_polygon_with_holes, TriangleMesh,
+    extrude_polygon, extrude_polygon_with_hole, extrude_polygon_with_holes,
+    extrude_polygon_with_holes_using, extrude_wall_with_openings, import_mesh, pick_element,
+    pick_mesh, section_building, triangulate_constrained_delaunay, triangulate_polygon,
+    triangulate_polygon_with_holes, triangulate_polygon_with_holes_using, ImportOptions,
+    ImportStats, MeshFormat, PickHit, Ray, RayHit, SectionCurve, SectionPlane, TriangleMesh,
+    TriangulationMethod, WeldReport,
 };
 
 // M0 re-exports
 pub use constants::{
-    quantize, quantize_point2, quantize_point3, EPSILON, GEOM_TOL, QUANTIZE_PRECISION,
+    quantize, quantize_point2, quantize_point3, quantize_to, EPSILON, GEOM_TOL, QUANTIZE_PRECISION,
     SNAP_MERGE_TOL, UI_SNAP_DIST,
 };
-pub use exec::{exec_and_heal, Context, ExecResult};
-pub use io::{prepare_input, prepare_output, to_deterministic_json, to_deterministic_json_compact};
+pub use exec::{exec_and_heal, Context, ExecResult, KernelConfig};
+pub use ids::{IdGenerator, IdMode, IdPolicy, ELEMENT_ID_NAMESPACE};
+pub use io::model::{load_model, save_model, ModelDocument, MODEL_SCHEMA_VERSION};
+pub use io::{
+    prepare_input, prepare_output, to_deterministic_json, to_deterministic_json_compact,
+    to_plain_decimal_json,
+};
+pub use labels::{
+    layout_labels, LabelCandidate, LabelKind, LabelLayoutOptions, LabelMotion, PlacedLabel,
+};
+pub use properties::{
+    FindingSeverity, IfcPropertyTarget, KeyRewrite, PropertyDefinition, PropertyFinding,
+    PropertyMode, PropertySchemaRegistry, PropertyValueType,
+};
+pub use report::{NumberFormat, QuantityKind, Schedule, ScheduleColumn, ScheduleRow, TotalsPolicy};
 pub use spatial::{
-    orient2d, orient2d_robust, segment_intersection, segments_intersect, signed_area_2,
-    Clash, ClashDetector, ClashElement, ClashFilter, ClashType,
-    EdgeEntry, EdgeIndex, NodeIndex, Orientation,
+    orient2d, orient2d_robust, segment_intersection, segments_intersect, segments_intersect_at,
+    signed_area_2, Clash, ClashDetector, ClashElement, ClashFilter, ClashType, ContextClashes,
+    EdgeEntry, EdgeIndex, IntersectionKind, NodeIndex, Orientation, SegmentIntersection,
+    SnapEngine, SnapGrid, SnapKind, SnapResult,
 };
 
 // M2 re-exports
 pub use topology::{
     Baseline, EdgeData, EdgeId, NodeId, OpeningRef, TopoEdge, TopoNode, TopologyGraph,
 };
+pub use validation::{
+    validate_model, IssueSeverity, ValidationIssue, ValidationReport, ValidationRule,
+};
 
 #[cfg(test)]
 mod tests {
@@ -145,7 +198,10 @@ mod tests {
     fn floor_mesh_rejects_invalid_bounds() {
         let floor = Floor::rectangle(Point2::new(1.0, 1.0), Point2::new(1.0, 2.0), 0.3);
 
-        assert!(matches!(floor, Err(GeometryError::InvalidFloorBounds)));
+        assert!(matches!(
+            floor,
+            Err(GeometryError::InvalidFloorBounds { .. })
+        ));
     }
 
     #[test]
@@ -182,6 +238,76 @@ mod tests {
         assert!((room.volume() - 240.0).abs() < 1e-10);
     }
 
+    fn simple_building_json() -> String {
+        let corners = [
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 8.0),
+            Point2::new(0.0, 8.0),
+        ];
+        let mut document = ModelDocument::new();
+        for i in 0..4 {
+            let wall = Wall::new(corners[i], corners[(i + 1) % 4], 3.0, 0.2).unwrap();
+            document.walls.push(wall);
+        }
+        let door = Door::new(document.walls[0].id, 0.9, 2.1, 2.0).unwrap();
+        let floor = Floor::rectangle(corners[0], corners[2], 0.3).unwrap();
+        let room = Room::rectangle("Living Room", "101", corners[0], corners[2], 3.0).unwrap();
+        document.doors.push(door);
+        document.floors.push(floor);
+        document.rooms.push(room);
+        let walls: Vec<&Wall> = document.walls.iter().collect();
+        document.joins = JoinResolver::new(0.001).detect_joins(&walls);
+
+        let mut roof = Roof::rectangle(corners[0], corners[2], 0.25).unwrap();
+        roof.add_opening(RoofOpening::new(
+            Point2::new(5.0, 4.0),
+            1.0,
+            1.0,
+            RoofOpeningType::Skylight,
+        ))
+        .unwrap();
+        document.roofs.push(roof);
+        let level = Level::new("Ground", 0.0, 3.0).unwrap();
+
+        // Template walls take their IDs from the context, following the
+        // thread's mode
+        let context = || Context::new().with_id_generator(IdPolicy::new(id_mode()));
+        let mut source = context();
+        let params = serde_json::json!({ "start": [0, 0], "end": [2500, 0] });
+        let result = exec_and_heal("add_wall", &params, &mut source);
+        let wall_id = result.data.unwrap()["wall_id"]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let template =
+            templates::create_template(&source, &[wall_id], [0.0, 0.0], [1.0, 0.0], "Bay").unwrap();
+        let mut target = context();
+        let placement = templates::TemplatePlacement::at([4000.0, 4000.0]);
+        let instance = templates::place_template(&mut target, &template, &placement).unwrap();
+
+        to_deterministic_json(&serde_json::json!({
+            "document": document,
+            "level": level,
+            "template": template,
+            "instance": instance,
+        }))
+    }
+
+    #[test]
+    fn content_hash_ids_give_identical_json() {
+        let namespace = uuid::Uuid::from_u128(0x5eed);
+        set_id_mode(IdMode::ContentHash { namespace });
+        let first = simple_building_json();
+        set_id_mode(IdMode::ContentHash { namespace });
+        let second = simple_building_json();
+        set_id_mode(IdMode::Random);
+
+        assert_eq!(first, second);
+        assert_ne!(simple_building_json(), simple_building_json());
+    }
+
     #[test]
     fn wall_with_opening() {
         let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
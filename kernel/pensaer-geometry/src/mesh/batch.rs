@@ -0,0 +1,108 @@
+//! Parallel batch mesh generation.
+//!
+//! [`generate_meshes`] and [`generate_building_meshes`] mesh many elements
+//! at once on the rayon thread pool, for callers that need a whole model
+//! now rather than the viewport-first order of the background worker.
+//! [`merge_element_meshes`] then joins the results into one mesh with a
+//! group per element, so a picked triangle still maps back to its element.
+
+use rayon::prelude::*;
+use uuid::Uuid;
+
+use crate::element::Element;
+use crate::elements::Building;
+use crate::error::GeometryResult;
+
+use super::TriangleMesh;
+
+/// Mesh elements in parallel. Results are in the order of `elements`.
+pub fn generate_meshes(elements: &[&dyn Element]) -> Vec<GeometryResult<TriangleMesh>> {
+    elements.par_iter().map(|e| e.to_mesh()).collect()
+}
+
+/// Mesh every element of a building in parallel, in world space as
+/// [`Building::element_mesh`] does. Results are in the order of
+/// [`Building::elements`].
+pub fn generate_building_meshes(building: &Building) -> Vec<(Uuid, GeometryResult<TriangleMesh>)> {
+    let ids: Vec<Uuid> = building.elements().map(|e| e.id()).collect();
+    ids.into_par_iter()
+        .map(|id| (id, building.element_mesh(id)))
+        .collect()
+}
+
+/// Merge element meshes into one, each as a group named by its element ID.
+///
+/// The groups are the per-element triangle ranges, in input order.
+pub fn merge_element_meshes<'a>(
+    meshes: impl IntoIterator<Item = (Uuid, &'a TriangleMesh)>,
+) -> TriangleMesh {
+    let mut merged = TriangleMesh::new();
+    for (id, mesh) in meshes {
+        merged.merge_named(&id.to_string(), mesh);
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{Door, Floor, OpeningType, Wall, WallOpening};
+    use pensaer_math::Point2;
+
+    fn building() -> Building {
+        let mut building = Building::new("Batch");
+        for i in 0..20 {
+            let y = i as f64 * 2.0;
+            let mut wall = Wall::new(Point2::new(0.0, y), Point2::new(6.0, y), 3.0, 0.2).unwrap();
+            if i % 3 == 0 {
+                let door = Door::new(wall.id, 0.9, 2.1, 2.0).unwrap();
+                let mut opening = WallOpening::new(2.0, 0.0, 0.9, 2.1, OpeningType::Door);
+                opening.hosted_element_id = Some(door.id);
+                wall.add_opening(opening).unwrap();
+                building.add_door(door);
+            }
+            building.add_wall(wall);
+        }
+        building.add_floor(
+            Floor::rectangle(Point2::new(0.0, 0.0), Point2::new(6.0, 40.0), 0.3).unwrap(),
+        );
+        building
+    }
+
+    #[test]
+    fn parallel_meshes_match_sequential() {
+        let building = building();
+        let elements: Vec<&dyn Element> = building.elements().collect();
+
+        let parallel = generate_meshes(&elements);
+        assert_eq!(parallel.len(), elements.len());
+        for (element, mesh) in elements.iter().zip(&parallel) {
+            assert_eq!(mesh.as_ref().unwrap(), &element.to_mesh().unwrap());
+        }
+    }
+
+    #[test]
+    fn merged_building_mesh_matches_sequential_merge() {
+        let building = building();
+
+        let meshes = generate_building_meshes(&building);
+        let merged = merge_element_meshes(
+            meshes
+                .iter()
+                .map(|(id, mesh)| (*id, mesh.as_ref().unwrap())),
+        );
+
+        let mut sequential = TriangleMesh::new();
+        for element in building.elements() {
+            let mesh = building.element_mesh(element.id()).unwrap();
+            sequential.merge_named(&element.id().to_string(), &mesh);
+        }
+        assert_eq!(merged, sequential);
+
+        // One group per element, tiling the triangles in order
+        assert_eq!(merged.groups.len(), building.elements().count());
+        assert_eq!(merged.groups[0].1.start, 0);
+        assert!(merged.groups.windows(2).all(|g| g[0].1.end == g[1].1.start));
+        assert_eq!(merged.groups.last().unwrap().1.end, merged.triangle_count());
+    }
+}
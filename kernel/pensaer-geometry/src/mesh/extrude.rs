@@ -18,10 +18,17 @@
 //!
 //! let mesh = extrude_polygon(&profile, 3.0)?;
 //! ```
+//!
+//! # Texture coordinates
+//!
+//! Extruded meshes carry normals and UVs. UVs are in meters, so a texture
+//! one meter across tiles at true scale: horizontal faces use plan
+//! coordinates, and vertical faces use the distance along their profile (or
+//! wall) against the height above the base.
 
 use pensaer_math::{Point2, Point3, Vector3};
 
-use super::triangulate::triangulate_polygon;
+use super::triangulate::{triangulate_polygon, TriangulationMethod};
 use crate::error::{GeometryError, GeometryResult};
 use crate::mesh::TriangleMesh;
 
@@ -38,7 +45,7 @@ use crate::mesh::TriangleMesh;
 /// * `base_z` - Z-coordinate of the bottom cap (default: 0.0)
 ///
 /// # Returns
-/// A `TriangleMesh` with proper normals for rendering.
+/// A `TriangleMesh` with proper normals and UVs for rendering.
 ///
 /// # Errors
 /// - `InsufficientVertices` if profile has < 3 vertices
@@ -53,7 +60,7 @@ pub fn extrude_polygon(
     }
 
     if height <= 0.0 {
-        return Err(GeometryError::NonPositiveHeight);
+        return Err(GeometryError::NonPositiveHeight { value: height });
     }
 
     let top_z = base_z + height;
@@ -62,38 +69,35 @@ pub fn extrude_polygon(
     // Triangulate the profile for caps
     let cap_triangles = triangulate_polygon(profile)?;
 
-    // Calculate total vertices:
-    // - Bottom cap: n vertices
-    // - Top cap: n vertices
-    // - Sides: 4 vertices per edge (n edges) for proper normals
-    let side_vertex_count = n * 4;
-    let total_vertices = n * 2 + side_vertex_count;
-
-    let mut vertices = Vec::with_capacity(total_vertices);
-    let mut normals = Vec::with_capacity(total_vertices);
-    let mut indices = Vec::new();
+    // Bottom and top caps (n vertices each), then 4 vertices per side so
+    // each side face has its own normal
+    let mut mesh = TriangleMesh::new();
+    mesh.vertices.reserve(n * 6);
 
     // === Bottom cap (facing down) ===
     for point in profile {
-        vertices.push(Point3::new(point.x, point.y, base_z));
-        normals.push(Vector3::new(0.0, 0.0, -1.0));
+        mesh.vertices.push(Point3::new(point.x, point.y, base_z));
+        mesh.normals.push(Vector3::new(0.0, 0.0, -1.0));
+        mesh.uvs.push((point.x, point.y));
     }
 
     // Bottom cap triangles (reversed winding for downward normal)
     for tri in &cap_triangles {
-        indices.push([tri[0] as u32, tri[2] as u32, tri[1] as u32]);
+        mesh.indices
+            .push([tri[0] as u32, tri[2] as u32, tri[1] as u32]);
     }
 
     // === Top cap (facing up) ===
     let top_cap_start = n as u32;
     for point in profile {
-        vertices.push(Point3::new(point.x, point.y, top_z));
-        normals.push(Vector3::new(0.0, 0.0, 1.0));
+        mesh.vertices.push(Point3::new(point.x, point.y, top_z));
+        mesh.normals.push(Vector3::new(0.0, 0.0, 1.0));
+        mesh.uvs.push((point.x, point.y));
     }
 
     // Top cap triangles (normal winding for upward normal)
     for tri in &cap_triangles {
-        indices.push([
+        mesh.indices.push([
             top_cap_start + tri[0] as u32,
             top_cap_start + tri[1] as u32,
             top_cap_start + tri[2] as u32,
@@ -101,50 +105,9 @@ pub fn extrude_polygon(
     }
 
     // === Side walls ===
-    let sides_start = (n * 2) as u32;
+    add_side_walls(&mut mesh, profile, base_z, top_z);
 
-    for i in 0..n {
-        let j = (i + 1) % n;
-
-        let p0 = profile[i];
-        let p1 = profile[j];
-
-        // Bottom-left, bottom-right, top-right, top-left of this quad
-        let bl = Point3::new(p0.x, p0.y, base_z);
-        let br = Point3::new(p1.x, p1.y, base_z);
-        let tr = Point3::new(p1.x, p1.y, top_z);
-        let tl = Point3::new(p0.x, p0.y, top_z);
-
-        // Compute outward normal for this face
-        let edge = Vector3::new(p1.x - p0.x, p1.y - p0.y, 0.0);
-        let up = Vector3::new(0.0, 0.0, 1.0);
-        let normal = edge.cross(&up);
-        let normal = normal.normalize().unwrap_or(Vector3::new(1.0, 0.0, 0.0));
-
-        let base_idx = sides_start + (i as u32) * 4;
-
-        // Add 4 vertices for this quad
-        vertices.push(bl);
-        vertices.push(br);
-        vertices.push(tr);
-        vertices.push(tl);
-
-        normals.push(normal);
-        normals.push(normal);
-        normals.push(normal);
-        normals.push(normal);
-
-        // Two triangles for the quad
-        indices.push([base_idx, base_idx + 1, base_idx + 2]);
-        indices.push([base_idx, base_idx + 2, base_idx + 3]);
-    }
-
-    Ok(TriangleMesh {
-        vertices,
-        normals,
-        uvs: Vec::new(),
-        indices,
-    })
+    Ok(mesh)
 }
 
 /// Extrude a polygon with a hole.
@@ -165,30 +128,59 @@ pub fn extrude_polygon_with_hole(
     hole: &[Point2],
     height: f64,
     base_z: f64,
+) -> GeometryResult<TriangleMesh> {
+    let holes_vec = if hole.len() >= 3 {
+        vec![hole.to_vec()]
+    } else {
+        vec![]
+    };
+    extrude_polygon_with_holes(outer, &holes_vec, height, base_z)
+}
+
+/// Extrude a polygon with any number of holes.
+///
+/// Like [`extrude_polygon_with_hole`], with inner side walls around each
+/// hole. Holes with fewer than 3 vertices are ignored.
+pub fn extrude_polygon_with_holes(
+    outer: &[Point2],
+    holes: &[Vec<Point2>],
+    height: f64,
+    base_z: f64,
+) -> GeometryResult<TriangleMesh> {
+    extrude_polygon_with_holes_using(
+        outer,
+        holes,
+        height,
+        base_z,
+        TriangulationMethod::EarClipping,
+    )
+}
+
+/// [`extrude_polygon_with_holes`], triangulating the caps with `method`.
+pub fn extrude_polygon_with_holes_using(
+    outer: &[Point2],
+    holes: &[Vec<Point2>],
+    height: f64,
+    base_z: f64,
+    method: TriangulationMethod,
 ) -> GeometryResult<TriangleMesh> {
     if outer.len() < 3 {
         return Err(GeometryError::InsufficientVertices);
     }
 
     if height <= 0.0 {
-        return Err(GeometryError::NonPositiveHeight);
+        return Err(GeometryError::NonPositiveHeight { value: height });
     }
 
     let top_z = base_z + height;
 
-    // Triangulate with hole using bridged polygon
-    let holes_vec = if hole.len() >= 3 {
-        vec![hole.to_vec()]
-    } else {
-        vec![]
-    };
+    // Triangulate the caps around the holes
+    let holes: Vec<Vec<Point2>> = holes.iter().filter(|h| h.len() >= 3).cloned().collect();
 
     let (combined_vertices, cap_triangles) =
-        super::triangulate::triangulate_polygon_with_holes(outer, &holes_vec)?;
+        super::triangulate::triangulate_polygon_with_holes_using(outer, &holes, method)?;
 
     let combined_n = combined_vertices.len();
-    let _outer_n = outer.len();
-    let hole_n = hole.len();
 
     let mut mesh = TriangleMesh::new();
 
@@ -197,6 +189,7 @@ pub fn extrude_polygon_with_hole(
     for point in &combined_vertices {
         mesh.vertices.push(Point3::new(point.x, point.y, base_z));
         mesh.normals.push(Vector3::new(0.0, 0.0, -1.0));
+        mesh.uvs.push((point.x, point.y));
     }
 
     for tri in &cap_triangles {
@@ -212,6 +205,7 @@ pub fn extrude_polygon_with_hole(
     for point in &combined_vertices {
         mesh.vertices.push(Point3::new(point.x, point.y, top_z));
         mesh.normals.push(Vector3::new(0.0, 0.0, 1.0));
+        mesh.uvs.push((point.x, point.y));
     }
 
     for tri in &cap_triangles {
@@ -224,25 +218,25 @@ pub fn extrude_polygon_with_hole(
 
     // === Outer side walls ===
     let _outer_sides_start = mesh.vertices.len() as u32;
-    add_side_walls(&mut mesh, outer, base_z, top_z, true);
+    add_side_walls(&mut mesh, outer, base_z, top_z);
 
     // === Inner (hole) side walls ===
-    if hole_n >= 3 {
-        add_side_walls(&mut mesh, hole, base_z, top_z, false);
+    // Holes wind CW, so the same winding faces into the hole
+    for hole in &holes {
+        add_side_walls(&mut mesh, hole, base_z, top_z);
     }
 
     Ok(mesh)
 }
 
-/// Add side walls for a closed profile.
-fn add_side_walls(
-    mesh: &mut TriangleMesh,
-    profile: &[Point2],
-    base_z: f64,
-    top_z: f64,
-    outward: bool,
-) {
+/// Add side walls for a closed profile, facing right of the direction of
+/// travel (outward for a CCW boundary, into the hole for a CW one).
+///
+/// UVs run along the profile from its first vertex, against height.
+fn add_side_walls(mesh: &mut TriangleMesh, profile: &[Point2], base_z: f64, top_z: f64) {
     let n = profile.len();
+    let height = top_z - base_z;
+    let mut along = 0.0;
 
     for i in 0..n {
         let j = (i + 1) % n;
@@ -259,12 +253,10 @@ fn add_side_walls(
         // Compute normal
         let edge = Vector3::new(p1.x - p0.x, p1.y - p0.y, 0.0);
         let up = Vector3::new(0.0, 0.0, 1.0);
-        let normal = if outward {
-            edge.cross(&up)
-        } else {
-            up.cross(&edge) // Inward facing for hole
-        };
-        let normal = normal.normalize().unwrap_or(Vector3::new(1.0, 0.0, 0.0));
+        let normal = edge
+            .cross(&up)
+            .normalize()
+            .unwrap_or(Vector3::new(1.0, 0.0, 0.0));
 
         let base_idx = mesh.vertices.len() as u32;
 
@@ -278,26 +270,36 @@ fn add_side_walls(
         mesh.normals.push(normal);
         mesh.normals.push(normal);
 
-        // Triangle winding
-        if outward {
-            mesh.indices.push([base_idx, base_idx + 1, base_idx + 2]);
-            mesh.indices.push([base_idx, base_idx + 2, base_idx + 3]);
-        } else {
-            mesh.indices.push([base_idx, base_idx + 2, base_idx + 1]);
-            mesh.indices.push([base_idx, base_idx + 3, base_idx + 2]);
-        }
+        let next = along + p0.distance_to(&p1);
+        mesh.uvs
+            .extend([(along, 0.0), (next, 0.0), (next, height), (along, height)]);
+        along = next;
+
+        mesh.indices.push([base_idx, base_idx + 1, base_idx + 2]);
+        mesh.indices.push([base_idx, base_idx + 2, base_idx + 3]);
     }
 }
 
 /// Extrude a wall profile with multiple openings.
 ///
 /// This creates a wall mesh where openings (doors, windows) are cut through.
-/// Each opening creates a rectangular hole in the wall.
+/// Each opening creates a rectangular hole in the wall, or a notch in its
+/// bottom edge if it rests on the bottom.
+///
+/// The mesh is in wall-local coordinates: X along the wall, Y across it
+/// with the front face at -thickness/2, and Z up. Every face winds outward
+/// and its normals agree, with opening reveals facing into the opening.
+/// The front and back faces take UVs of (distance along the wall, height);
+/// the wall ends and opening jambs carry that on across the thickness, and
+/// the top, bottom, sill, and head faces use plan coordinates.
 ///
 /// # Arguments
 /// * `wall_profile` - The 2D outline of the wall (4 corners)
 /// * `openings` - List of rectangular openings, each as (x_offset, y_offset, width, height)
-///   where x_offset is along the wall, y_offset is from the bottom
+///   where x_offset is along the wall and y_offset is the opening's base height above the
+///   bottom, so the void spans `y_offset..y_offset + height`; see [`Wall::opening_rects`]
+///
+/// [`Wall::opening_rects`]: crate::elements::Wall::opening_rects
 /// * `wall_thickness` - Thickness of the wall (Z dimension)
 pub fn extrude_wall_with_openings(
     wall_length: f64,
@@ -305,190 +307,174 @@ pub fn extrude_wall_with_openings(
     wall_thickness: f64,
     openings: &[(f64, f64, f64, f64)], // (x_offset, y_offset, width, height)
 ) -> GeometryResult<TriangleMesh> {
-    if wall_length <= 0.0 || wall_height <= 0.0 || wall_thickness <= 0.0 {
-        return Err(GeometryError::NonPositiveThickness);
+    if wall_height <= 0.0 {
+        return Err(GeometryError::NonPositiveHeight { value: wall_height });
+    }
+    if wall_length <= 0.0 || wall_thickness <= 0.0 {
+        return Err(GeometryError::NonPositiveThickness {
+            value: wall_length.min(wall_thickness),
+        });
     }
 
-    // Create the wall front face with holes
-    let mut mesh = TriangleMesh::new();
-
-    // Front and back faces
-    let half_thick = wall_thickness / 2.0;
-
-    // Create outer rectangle
-    let outer = vec![
+    let outline = [
         Point2::new(0.0, 0.0),
         Point2::new(wall_length, 0.0),
         Point2::new(wall_length, wall_height),
         Point2::new(0.0, wall_height),
     ];
+    extrude_elevation_with_openings(&outline, wall_thickness, openings)
+}
+
+/// Sweep a wall elevation across its thickness, cutting openings through.
+///
+/// `outline` is the counter-clockwise elevation in (distance along the
+/// wall, height), starting with its bottom edge at height 0. Openings are
+/// as in [`extrude_wall_with_openings`], and any outside the outline's
+/// bounds are skipped. Openings resting on the bottom, such as doors, are
+/// notched out of the bottom edge so nothing closes them underneath; the
+/// rest are cut as holes. Every outline and opening edge is swept into a
+/// quad facing out of the wall, and the mesh is laid out as in
+/// [`extrude_wall_with_openings`].
+pub(crate) fn extrude_elevation_with_openings(
+    outline: &[Point2],
+    wall_thickness: f64,
+    openings: &[(f64, f64, f64, f64)],
+) -> GeometryResult<TriangleMesh> {
+    if outline.len() < 3 {
+        return Err(GeometryError::InsufficientVertices);
+    }
 
-    // Create holes for openings
-    // Use small epsilon to avoid coincident edges with outer boundary
+    // Keeps holes off the outline so they never share an edge with it
     const HOLE_EPSILON: f64 = 1e-6;
 
-    let holes: Vec<Vec<Point2>> = openings
+    let x_min = outline.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+    let x_max = outline
         .iter()
-        .filter_map(|&(x, y, w, h)| {
-            // Validate opening
-            if x < 0.0 || y < 0.0 || w <= 0.0 || h <= 0.0 {
-                return None;
-            }
-            if x + w > wall_length || y + h > wall_height {
-                return None;
-            }
-
-            // Adjust coordinates to avoid coincident edges with outer boundary
-            // This prevents degenerate triangulation when hole touches wall edge
-            let x0 = if x <= HOLE_EPSILON { HOLE_EPSILON } else { x };
-            let y0 = if y <= HOLE_EPSILON { HOLE_EPSILON } else { y };
-            let x1 = if x + w >= wall_length - HOLE_EPSILON {
-                wall_length - HOLE_EPSILON
-            } else {
-                x + w
-            };
-            let y1 = if y + h >= wall_height - HOLE_EPSILON {
-                wall_height - HOLE_EPSILON
-            } else {
-                y + h
-            };
+        .map(|p| p.x)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let top = outline
+        .iter()
+        .map(|p| p.y)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let mut openings: Vec<(f64, f64, f64, f64)> = openings
+        .iter()
+        .copied()
+        .filter(|&(x, y, w, h)| {
+            x >= x_min && y >= 0.0 && w > 0.0 && h > 0.0 && x + w <= x_max && y + h <= top
+        })
+        .collect();
+    openings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    // Notch openings on the bottom edge into the outline, between its
+    // first two points
+    let mut outer = vec![outline[0]];
+    for &(x, y, w, h) in openings.iter().filter(|o| o.1 <= HOLE_EPSILON) {
+        let x0 = if x - x_min <= HOLE_EPSILON { x_min } else { x };
+        let x1 = if x_max - (x + w) <= HOLE_EPSILON {
+            x_max
+        } else {
+            x + w
+        };
+        outer.extend([
+            Point2::new(x0, 0.0),
+            Point2::new(x0, y + h),
+            Point2::new(x1, y + h),
+            Point2::new(x1, 0.0),
+        ]);
+    }
+    outer.extend_from_slice(&outline[1..]);
+    drop_straight_points(&mut outer);
 
-            // CW winding for hole
-            Some(vec![
-                Point2::new(x0, y0),
+    // The rest become clockwise holes, nudged off the outline's edges
+    let holes: Vec<Vec<Point2>> = openings
+        .iter()
+        .filter(|o| o.1 > HOLE_EPSILON)
+        .map(|&(x, y, w, h)| {
+            let x0 = x.max(x_min + HOLE_EPSILON);
+            let x1 = (x + w).min(x_max - HOLE_EPSILON);
+            let y1 = (y + h).min(top - HOLE_EPSILON);
+            vec![
+                Point2::new(x0, y),
                 Point2::new(x0, y1),
                 Point2::new(x1, y1),
-                Point2::new(x1, y0),
-            ])
+                Point2::new(x1, y),
+            ]
         })
         .collect();
 
-    // Triangulate front face with holes
-    let hole_refs: Vec<Vec<Point2>> = holes.clone();
-    let (front_vertices, front_triangles) =
-        super::triangulate::triangulate_polygon_with_holes(&outer, &hole_refs)?;
+    let (face_vertices, face_triangles) =
+        super::triangulate::triangulate_polygon_with_holes(&outer, &holes)?;
+
+    let mut mesh = TriangleMesh::new();
+    let half_thick = wall_thickness / 2.0;
 
     // === Front face (at Y = -half_thick) ===
-    let front_start = 0u32;
-    for v in &front_vertices {
+    for v in &face_vertices {
         mesh.vertices.push(Point3::new(v.x, -half_thick, v.y)); // X along wall, Z is height
         mesh.normals.push(Vector3::new(0.0, -1.0, 0.0));
+        mesh.uvs.push((v.x, v.y));
     }
 
-    for tri in &front_triangles {
-        mesh.indices.push([
-            front_start + tri[0] as u32,
-            front_start + tri[2] as u32,
-            front_start + tri[1] as u32,
-        ]);
+    // The elevation is counter-clockwise seen from the front, facing -Y
+    for tri in &face_triangles {
+        mesh.indices.push(tri.map(|i| i as u32));
     }
 
     // === Back face (at Y = +half_thick) ===
     let back_start = mesh.vertices.len() as u32;
-    for v in &front_vertices {
+    for v in &face_vertices {
         mesh.vertices.push(Point3::new(v.x, half_thick, v.y));
         mesh.normals.push(Vector3::new(0.0, 1.0, 0.0));
+        mesh.uvs.push((v.x, v.y));
     }
 
-    for tri in &front_triangles {
+    for tri in &face_triangles {
         mesh.indices.push([
             back_start + tri[0] as u32,
-            back_start + tri[1] as u32,
             back_start + tri[2] as u32,
+            back_start + tri[1] as u32,
         ]);
     }
 
-    // === Outer edges (top, bottom, left, right) ===
-    add_wall_edge(
-        &mut mesh,
-        Point2::new(0.0, 0.0),
-        Point2::new(wall_length, 0.0),
-        -half_thick,
-        half_thick,
-        Vector3::new(0.0, 0.0, -1.0),
-    );
-
-    add_wall_edge(
-        &mut mesh,
-        Point2::new(wall_length, wall_height),
-        Point2::new(0.0, wall_height),
-        -half_thick,
-        half_thick,
-        Vector3::new(0.0, 0.0, 1.0),
-    );
-
-    add_wall_edge(
-        &mut mesh,
-        Point2::new(0.0, wall_height),
-        Point2::new(0.0, 0.0),
-        -half_thick,
-        half_thick,
-        Vector3::new(-1.0, 0.0, 0.0),
-    );
-
-    add_wall_edge(
-        &mut mesh,
-        Point2::new(wall_length, 0.0),
-        Point2::new(wall_length, wall_height),
-        -half_thick,
-        half_thick,
-        Vector3::new(1.0, 0.0, 0.0),
-    );
-
-    // === Opening reveals (inner edges of openings) ===
-    for &(x, y, w, h) in openings {
-        if x < 0.0 || y < 0.0 || w <= 0.0 || h <= 0.0 {
-            continue;
-        }
-        if x + w > wall_length || y + h > wall_height {
-            continue;
+    // === Outline edges and opening reveals ===
+    // Counter-clockwise outline edges face out of the wall, and clockwise
+    // hole edges into their openings
+    for ring in std::iter::once(&outer).chain(&holes) {
+        for (i, &p0) in ring.iter().enumerate() {
+            let p1 = ring[(i + 1) % ring.len()];
+            let Ok(normal) = Vector3::new(p1.y - p0.y, 0.0, p0.x - p1.x).normalize() else {
+                continue;
+            };
+            add_wall_edge(&mut mesh, p0, p1, -half_thick, half_thick, normal);
         }
-
-        // Bottom of opening
-        add_wall_edge(
-            &mut mesh,
-            Point2::new(x + w, y),
-            Point2::new(x, y),
-            -half_thick,
-            half_thick,
-            Vector3::new(0.0, 0.0, -1.0),
-        );
-
-        // Top of opening
-        add_wall_edge(
-            &mut mesh,
-            Point2::new(x, y + h),
-            Point2::new(x + w, y + h),
-            -half_thick,
-            half_thick,
-            Vector3::new(0.0, 0.0, 1.0),
-        );
-
-        // Left of opening
-        add_wall_edge(
-            &mut mesh,
-            Point2::new(x, y),
-            Point2::new(x, y + h),
-            -half_thick,
-            half_thick,
-            Vector3::new(-1.0, 0.0, 0.0),
-        );
-
-        // Right of opening
-        add_wall_edge(
-            &mut mesh,
-            Point2::new(x + w, y + h),
-            Point2::new(x + w, y),
-            -half_thick,
-            half_thick,
-            Vector3::new(1.0, 0.0, 0.0),
-        );
     }
 
     Ok(mesh)
 }
 
+/// Remove repeated points, and points where the ring runs straight on or
+/// doubles back, so every remaining edge turns a corner.
+fn drop_straight_points(ring: &mut Vec<Point2>) {
+    let mut i = 0;
+    while ring.len() > 3 && i < ring.len() {
+        let n = ring.len();
+        let prev = ring[(i + n - 1) % n];
+        let next = ring[(i + 1) % n];
+        let (a, b) = (ring[i] - prev, next - ring[i]);
+        if a.cross(&b).abs() <= 1e-12 * (1.0 + a.length() * b.length()) {
+            ring.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+}
+
 /// Add a wall edge (quad connecting front and back faces).
+///
+/// The quad winds to face along `normal`. Horizontal edges take plan UVs;
+/// vertical ones continue the front face's (distance, height) across the
+/// thickness.
 fn add_wall_edge(
     mesh: &mut TriangleMesh,
     p0: Point2,   // Start point in wall local coords (X along wall, Y is height)
@@ -510,8 +496,32 @@ fn add_wall_edge(
     mesh.normals.push(normal);
     mesh.normals.push(normal);
 
-    mesh.indices.push([base_idx, base_idx + 1, base_idx + 2]);
-    mesh.indices.push([base_idx, base_idx + 2, base_idx + 3]);
+    if normal.z.abs() > 0.5 {
+        mesh.uvs.extend([
+            (p0.x, y_front),
+            (p1.x, y_front),
+            (p1.x, y_back),
+            (p0.x, y_back),
+        ]);
+    } else {
+        let depth = y_back - y_front;
+        mesh.uvs.extend([
+            (p0.x, p0.y),
+            (p1.x, p1.y),
+            (p1.x + depth, p1.y),
+            (p0.x + depth, p0.y),
+        ]);
+    }
+
+    let corners = [0, 1, 2].map(|i| mesh.vertices[(base_idx + i) as usize]);
+    let winding = (corners[1] - corners[0]).cross(&(corners[2] - corners[0]));
+    if winding.dot(&normal) >= 0.0 {
+        mesh.indices.push([base_idx, base_idx + 1, base_idx + 2]);
+        mesh.indices.push([base_idx, base_idx + 2, base_idx + 3]);
+    } else {
+        mesh.indices.push([base_idx, base_idx + 2, base_idx + 1]);
+        mesh.indices.push([base_idx, base_idx + 3, base_idx + 2]);
+    }
 }
 
 #[cfg(test)]
@@ -580,6 +590,29 @@ mod tests {
         assert_eq!(mesh.normals.len(), mesh.vertices.len());
     }
 
+    #[test]
+    fn extrude_uvs_are_plan_and_perimeter() {
+        let profile = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(3.0, 0.0),
+            Point2::new(3.0, 4.0),
+        ];
+
+        let mesh = extrude_polygon(&profile, 2.5, 1.0).unwrap();
+        assert_eq!(mesh.uvs.len(), mesh.vertices.len());
+
+        for ((v, n), uv) in mesh.vertices.iter().zip(&mesh.normals).zip(&mesh.uvs) {
+            if n.z.abs() > 0.5 {
+                assert_eq!(*uv, (v.x, v.y));
+            } else {
+                assert!((uv.1 - (v.z - 1.0)).abs() < 1e-12);
+            }
+        }
+        // Sides run round the whole perimeter
+        let max_u = mesh.uvs.iter().map(|uv| uv.0).fold(0.0, f64::max);
+        assert!((max_u - 12.0).abs() < 1e-12);
+    }
+
     #[test]
     fn extrude_zero_height_fails() {
         let profile = vec![
@@ -601,7 +634,10 @@ mod tests {
         ];
 
         let result = extrude_polygon(&profile, -1.0, 0.0);
-        assert!(matches!(result, Err(GeometryError::NonPositiveHeight)));
+        assert!(matches!(
+            result,
+            Err(GeometryError::NonPositiveHeight { .. })
+        ));
     }
 
     #[test]
@@ -641,6 +677,107 @@ mod tests {
         // Should have more triangles than wall without openings
         let simple_mesh = extrude_wall_with_openings(5.0, 3.0, 0.2, &[]).unwrap();
         assert!(mesh.triangle_count() >= simple_mesh.triangle_count());
+
+        // Notched out of the bottom: open underneath up to the head
+        let up = mesh.raycast(Point3::new(2.45, 0.0, -1.0), Vector3::UNIT_Z);
+        assert!((up.unwrap().distance - 3.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn extrude_wall_faces_wind_with_their_normals() {
+        let mesh = extrude_wall_with_openings(5.0, 3.0, 0.2, &[(2.0, 0.9, 1.2, 1.2)]).unwrap();
+        assert_eq!(mesh.normals.len(), mesh.vertices.len());
+
+        let mut faces = Vec::new();
+        for tri in &mesh.indices {
+            let [a, b, c] = tri.map(|i| mesh.vertices[i as usize]);
+            let winding = (b - a).cross(&(c - a)).normalize().unwrap();
+            for &i in tri {
+                assert!(mesh.normals[i as usize].approx_eq(&winding, 1e-9));
+            }
+            let centroid = Point3::new(
+                (a.x + b.x + c.x) / 3.0,
+                (a.y + b.y + c.y) / 3.0,
+                (a.z + b.z + c.z) / 3.0,
+            );
+            faces.push((centroid, winding));
+        }
+
+        let has_face = |normal: Vector3, on: &dyn Fn(Point3) -> bool| {
+            faces
+                .iter()
+                .any(|(c, n)| n.approx_eq(&normal, 1e-9) && on(*c))
+        };
+        // Front, back, top, bottom, and ends face out of the wall
+        assert!(has_face(-Vector3::UNIT_Y, &|c| (c.y + 0.1).abs() < 1e-9));
+        assert!(has_face(Vector3::UNIT_Y, &|c| (c.y - 0.1).abs() < 1e-9));
+        assert!(has_face(Vector3::UNIT_Z, &|c| (c.z - 3.0).abs() < 1e-9));
+        assert!(has_face(-Vector3::UNIT_Z, &|c| c.z.abs() < 1e-9));
+        assert!(has_face(-Vector3::UNIT_X, &|c| c.x.abs() < 1e-9));
+        assert!(has_face(Vector3::UNIT_X, &|c| (c.x - 5.0).abs() < 1e-9));
+        // Sill, head, and jambs face into the window
+        assert!(has_face(Vector3::UNIT_Z, &|c| (c.z - 0.9).abs() < 1e-9));
+        assert!(has_face(-Vector3::UNIT_Z, &|c| (c.z - 2.1).abs() < 1e-9));
+        assert!(has_face(Vector3::UNIT_X, &|c| (c.x - 2.0).abs() < 1e-9));
+        assert!(has_face(-Vector3::UNIT_X, &|c| (c.x - 3.2).abs() < 1e-9));
+
+        // Consistently outward, so the signed volume is positive
+        let signed: f64 = mesh
+            .indices
+            .iter()
+            .map(|tri| {
+                let [a, b, c] = tri.map(|i| mesh.vertices[i as usize] - Point3::ORIGIN);
+                a.dot(&b.cross(&c)) / 6.0
+            })
+            .sum();
+        assert!((signed - (5.0 * 3.0 - 1.2 * 1.2) * 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn extrude_wall_uvs_span_length_and_height() {
+        let mesh = extrude_wall_with_openings(5.0, 3.0, 0.2, &[(2.0, 0.0, 0.9, 2.1)]).unwrap();
+        assert_eq!(mesh.uvs.len(), mesh.vertices.len());
+
+        let front: Vec<(f64, f64)> = mesh
+            .normals
+            .iter()
+            .zip(&mesh.uvs)
+            .filter(|(n, _)| n.approx_eq(&-Vector3::UNIT_Y, 1e-9))
+            .map(|(_, uv)| *uv)
+            .collect();
+        let min_u = front.iter().map(|uv| uv.0).fold(f64::INFINITY, f64::min);
+        let max_u = front
+            .iter()
+            .map(|uv| uv.0)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let min_v = front.iter().map(|uv| uv.1).fold(f64::INFINITY, f64::min);
+        let max_v = front
+            .iter()
+            .map(|uv| uv.1)
+            .fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!((min_u, max_u), (0.0, 5.0));
+        assert_eq!((min_v, max_v), (0.0, 3.0));
+
+        // A jamb carries on from the front face across the thickness
+        let jamb: Vec<(f64, f64)> = mesh
+            .vertices
+            .iter()
+            .zip(&mesh.normals)
+            .zip(&mesh.uvs)
+            .filter(|((v, n), _)| n.approx_eq(&Vector3::UNIT_X, 1e-9) && (v.x - 2.0).abs() < 1e-9)
+            .map(|(_, uv)| *uv)
+            .collect();
+        assert!(!jamb.is_empty());
+        assert!(jamb
+            .iter()
+            .all(|uv| (uv.0 - 2.0).abs() < 1e-9 || (uv.0 - 2.2).abs() < 1e-9));
+
+        // to_obj writes the UVs and normals
+        let obj = mesh.to_obj();
+        assert!(obj.contains("\nvt ") && obj.contains("\nvn "));
+        assert!(obj
+            .lines()
+            .any(|l| l.starts_with("f ") && l.matches('/').count() == 6));
     }
 
     #[test]
@@ -694,4 +831,28 @@ mod tests {
         assert!(mesh.is_valid());
         assert!(mesh.vertex_count() > 0);
     }
+
+    #[test]
+    fn extrude_polygon_with_two_holes() {
+        let outer = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(6.0, 0.0),
+            Point2::new(6.0, 3.0),
+            Point2::new(0.0, 3.0),
+        ];
+        let square = |x: f64| {
+            vec![
+                Point2::new(x, 1.0),
+                Point2::new(x, 2.0),
+                Point2::new(x + 1.0, 2.0),
+                Point2::new(x + 1.0, 1.0),
+            ]
+        };
+
+        let mesh =
+            extrude_polygon_with_holes(&outer, &[square(1.0), square(4.0)], 1.0, 0.0).unwrap();
+
+        assert!(mesh.is_valid());
+        assert!((mesh.volume() - (18.0 - 2.0)).abs() < 1e-9);
+    }
 }
@@ -0,0 +1,657 @@
+//! Streaming import of large OBJ and binary PLY meshes.
+//!
+//! Faces are never buffered. Vertex positions are kept as `f32` offsets from
+//! the first vertex, and each face goes straight into a vertex-clustering
+//! decimator that coarsens its grid whenever the output exceeds the triangle
+//! budget. Memory therefore scales with the source vertex count and the
+//! budget, not with the source triangle count.
+
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::SQRT_2;
+use std::io::{self, BufRead, BufReader, Read};
+use std::mem::size_of;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use pensaer_math::Point3;
+
+use super::TriangleMesh;
+use crate::error::{GeometryError, GeometryResult};
+
+/// 64-bit FNV-1a offset basis.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// 64-bit FNV-1a prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Source file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MeshFormat {
+    /// Wavefront OBJ (text).
+    Obj,
+    /// Stanford PLY, binary little- or big-endian.
+    Ply,
+}
+
+impl MeshFormat {
+    /// Guess the format from a file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "obj" => Some(Self::Obj),
+            "ply" => Some(Self::Ply),
+            _ => None,
+        }
+    }
+}
+
+/// Limits for [`import_mesh`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportOptions {
+    /// Triangle budget; larger meshes are decimated to fit.
+    pub max_triangles: usize,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            max_triangles: 2_000_000,
+        }
+    }
+}
+
+/// What [`import_mesh`] read and kept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportStats {
+    /// Triangles in the source, after splitting polygons into fans.
+    pub source_triangles: usize,
+    /// Triangles kept.
+    pub triangles: usize,
+    /// Final clustering cell size, or zero if no decimation was needed.
+    pub cell_size: f64,
+    /// Largest number of bytes held in import buffers at once.
+    pub peak_bytes: usize,
+    /// FNV-1a hash of the whole source, as `fnv1a64:<hex>`.
+    pub content_hash: String,
+}
+
+/// Read an OBJ or binary PLY mesh, decimating it to `options.max_triangles`.
+///
+/// Coincident vertices are welded and duplicate triangles dropped, so the
+/// result may have fewer triangles than the source even within budget.
+/// Normals and UVs in the source are ignored.
+pub fn import_mesh<R: Read>(
+    reader: R,
+    format: MeshFormat,
+    options: ImportOptions,
+) -> GeometryResult<(TriangleMesh, ImportStats)> {
+    if options.max_triangles == 0 {
+        return Err(GeometryError::MeshImport(
+            "triangle budget must be positive".to_string(),
+        ));
+    }
+
+    let mut reader = BufReader::new(HashingReader {
+        inner: reader,
+        hash: FNV_OFFSET,
+    });
+    let mut decimator = Decimator::new(options.max_triangles);
+    match format {
+        MeshFormat::Obj => read_obj(&mut reader, &mut decimator)?,
+        MeshFormat::Ply => read_ply(&mut reader, &mut decimator)?,
+    }
+
+    // Hash whatever follows the mesh data too
+    io::copy(&mut reader, &mut io::sink()).map_err(io_error)?;
+    let content_hash = format!("fnv1a64:{:016x}", reader.get_ref().hash);
+    Ok(decimator.finish(content_hash))
+}
+
+fn io_error(e: io::Error) -> GeometryError {
+    GeometryError::MeshImport(e.to_string())
+}
+
+/// Passes bytes through while hashing them with 64-bit FNV-1a.
+struct HashingReader<R> {
+    inner: R,
+    hash: u64,
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        for &b in &buf[..n] {
+            self.hash = (self.hash ^ b as u64).wrapping_mul(FNV_PRIME);
+        }
+        Ok(n)
+    }
+}
+
+/// Streaming vertex-clustering decimator.
+///
+/// With a zero cell size, vertices are only merged when their positions are
+/// identical. Once the output exceeds the budget, vertices are snapped to a
+/// grid whose cell grows by `sqrt(2)` (halving the triangle count of a
+/// surface) until the output fits again; later faces are clustered on the
+/// coarser grid directly.
+struct Decimator {
+    budget: usize,
+    /// First vertex; positions are stored relative to it.
+    origin: Option<[f64; 3]>,
+    positions: Vec<[f32; 3]>,
+    min: [f32; 3],
+    max: [f32; 3],
+    cell: f64,
+    clusters: HashMap<[i32; 3], u32>,
+    vertices: Vec<[f32; 3]>,
+    triangles: Vec<[u32; 3]>,
+    /// Kept triangles, rotated so the smallest index comes first.
+    seen: HashSet<[u32; 3]>,
+    source_triangles: usize,
+    peak_bytes: usize,
+}
+
+impl Decimator {
+    fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            origin: None,
+            positions: Vec::new(),
+            min: [f32::INFINITY; 3],
+            max: [f32::NEG_INFINITY; 3],
+            cell: 0.0,
+            clusters: HashMap::new(),
+            vertices: Vec::new(),
+            triangles: Vec::new(),
+            seen: HashSet::new(),
+            source_triangles: 0,
+            peak_bytes: 0,
+        }
+    }
+
+    fn add_vertex(&mut self, p: [f64; 3]) {
+        let origin = *self.origin.get_or_insert(p);
+        let rel = [
+            (p[0] - origin[0]) as f32,
+            (p[1] - origin[1]) as f32,
+            (p[2] - origin[2]) as f32,
+        ];
+        for (k, &r) in rel.iter().enumerate() {
+            self.min[k] = self.min[k].min(r);
+            self.max[k] = self.max[k].max(r);
+        }
+        self.positions.push(rel);
+    }
+
+    /// Add a polygon as a fan of triangles.
+    fn add_face(&mut self, face: &[usize]) -> GeometryResult<()> {
+        for k in 1..face.len().saturating_sub(1) {
+            self.add_triangle([face[0], face[k], face[k + 1]])?;
+        }
+        Ok(())
+    }
+
+    fn add_triangle(&mut self, tri: [usize; 3]) -> GeometryResult<()> {
+        self.source_triangles += 1;
+        let mut ids = [0u32; 3];
+        for (id, &i) in ids.iter_mut().zip(&tri) {
+            let p = *self.positions.get(i).ok_or_else(|| {
+                GeometryError::MeshImport(format!("face references missing vertex {}", i + 1))
+            })?;
+            *id = self.cluster(p);
+        }
+        self.push(ids);
+
+        if self.triangles.len() > self.budget {
+            self.track_peak(0);
+            self.coarsen();
+        }
+        Ok(())
+    }
+
+    fn cluster(&mut self, p: [f32; 3]) -> u32 {
+        let key = if self.cell > 0.0 {
+            p.map(|c| (c as f64 / self.cell).floor() as i32)
+        } else {
+            p.map(|c| (c + 0.0).to_bits() as i32)
+        };
+        let next = self.vertices.len() as u32;
+        let id = *self.clusters.entry(key).or_insert(next);
+        if id == next {
+            self.vertices.push(p);
+        }
+        id
+    }
+
+    fn push(&mut self, t: [u32; 3]) {
+        if t[0] == t[1] || t[1] == t[2] || t[2] == t[0] {
+            return;
+        }
+        let r = (0..3).min_by_key(|&i| t[i]).unwrap_or(0);
+        if self.seen.insert([t[r], t[(r + 1) % 3], t[(r + 2) % 3]]) {
+            self.triangles.push(t);
+        }
+    }
+
+    /// Grow the clustering cell until the kept triangles fit the budget.
+    fn coarsen(&mut self) {
+        while self.triangles.len() > self.budget {
+            self.cell = if self.cell > 0.0 {
+                self.cell * SQRT_2
+            } else {
+                // A surface spanning the bounds with about `budget` triangles
+                let extent = (0..3)
+                    .map(|k| (self.max[k] - self.min[k]) as f64)
+                    .map(|d| d * d)
+                    .sum::<f64>()
+                    .sqrt();
+                (extent / (self.budget as f64).sqrt()).max(1e-9)
+            };
+
+            let vertices = std::mem::take(&mut self.vertices);
+            let triangles = std::mem::take(&mut self.triangles);
+            self.clusters = HashMap::new();
+            self.seen = HashSet::new();
+
+            let remap: Vec<u32> = vertices.iter().map(|&p| self.cluster(p)).collect();
+            for t in &triangles {
+                self.push(t.map(|i| remap[i as usize]));
+            }
+            self.track_peak(
+                vertices.capacity() * size_of::<[f32; 3]>()
+                    + triangles.capacity() * size_of::<[u32; 3]>()
+                    + remap.capacity() * size_of::<u32>(),
+            );
+        }
+    }
+
+    /// Bytes currently held by the buffers.
+    fn bytes(&self) -> usize {
+        (self.positions.capacity() + self.vertices.capacity()) * size_of::<[f32; 3]>()
+            + self.triangles.capacity() * size_of::<[u32; 3]>()
+            + self.clusters.capacity() * size_of::<([i32; 3], u32)>()
+            + self.seen.capacity() * size_of::<[u32; 3]>()
+    }
+
+    fn track_peak(&mut self, extra: usize) {
+        self.peak_bytes = self.peak_bytes.max(self.bytes() + extra);
+    }
+
+    fn finish(mut self, content_hash: String) -> (TriangleMesh, ImportStats) {
+        self.track_peak(0);
+
+        // Only the kept vertices and triangles are needed from here on
+        self.positions = Vec::new();
+        self.clusters = HashMap::new();
+        self.seen = HashSet::new();
+
+        let origin = self.origin.unwrap_or([0.0; 3]);
+        let vertices: Vec<Point3> = self
+            .vertices
+            .iter()
+            .map(|p| {
+                Point3::new(
+                    origin[0] + p[0] as f64,
+                    origin[1] + p[1] as f64,
+                    origin[2] + p[2] as f64,
+                )
+            })
+            .collect();
+        self.track_peak(vertices.capacity() * size_of::<Point3>());
+
+        let mut mesh =
+            TriangleMesh::from_vertices_indices(vertices, std::mem::take(&mut self.triangles));
+        mesh.remove_unused_vertices();
+
+        let stats = ImportStats {
+            source_triangles: self.source_triangles,
+            triangles: mesh.triangle_count(),
+            cell_size: self.cell,
+            peak_bytes: self.peak_bytes,
+            content_hash,
+        };
+        (mesh, stats)
+    }
+}
+
+fn read_obj<R: BufRead>(reader: &mut R, decimator: &mut Decimator) -> GeometryResult<()> {
+    let mut line = String::new();
+    let mut face = Vec::new();
+    let mut number = 0;
+
+    loop {
+        line.clear();
+        number += 1;
+        if reader.read_line(&mut line).map_err(io_error)? == 0 {
+            return Ok(());
+        }
+        let bad_line = || GeometryError::MeshImport(format!("malformed OBJ line {}", number));
+
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let mut p = [0.0; 3];
+                for c in &mut p {
+                    *c = tokens
+                        .next()
+                        .and_then(|t| t.parse().ok())
+                        .ok_or_else(bad_line)?;
+                }
+                decimator.add_vertex(p);
+            }
+            Some("f") => {
+                face.clear();
+                let count = decimator.positions.len() as i64;
+                for token in tokens {
+                    // Only the position index of v/vt/vn is used
+                    let index: i64 = token
+                        .split('/')
+                        .next()
+                        .and_then(|t| t.parse().ok())
+                        .ok_or_else(bad_line)?;
+                    let resolved = if index < 0 { count + index } else { index - 1 };
+                    if resolved < 0 {
+                        return Err(bad_line());
+                    }
+                    face.push(resolved as usize);
+                }
+                decimator.add_face(&face)?;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// PLY scalar property type.
+#[derive(Debug, Clone, Copy)]
+enum Scalar {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" | "int8" => Self::I8,
+            "uchar" | "uint8" => Self::U8,
+            "short" | "int16" => Self::I16,
+            "ushort" | "uint16" => Self::U16,
+            "int" | "int32" => Self::I32,
+            "uint" | "uint32" => Self::U32,
+            "float" | "float32" => Self::F32,
+            "double" | "float64" => Self::F64,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+
+    fn read<R: Read>(self, reader: &mut R, big_endian: bool) -> io::Result<f64> {
+        let mut buf = [0u8; 8];
+        let bytes = &mut buf[..self.size()];
+        reader.read_exact(bytes)?;
+        if big_endian {
+            bytes.reverse();
+        }
+        let b = buf;
+        Ok(match self {
+            Self::I8 => b[0] as i8 as f64,
+            Self::U8 => b[0] as f64,
+            Self::I16 => i16::from_le_bytes([b[0], b[1]]) as f64,
+            Self::U16 => u16::from_le_bytes([b[0], b[1]]) as f64,
+            Self::I32 => i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            Self::U32 => u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            Self::F32 => f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+            Self::F64 => f64::from_le_bytes(b),
+        })
+    }
+}
+
+/// PLY element property.
+#[derive(Debug)]
+enum Property {
+    Scalar(String, Scalar),
+    List(String, Scalar, Scalar),
+}
+
+/// PLY element declaration.
+#[derive(Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+fn read_ply<R: BufRead>(reader: &mut R, decimator: &mut Decimator) -> GeometryResult<()> {
+    let malformed = |what: &str| GeometryError::MeshImport(format!("malformed PLY: {}", what));
+
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(io_error)?;
+    if line.trim_end() != "ply" {
+        return Err(malformed("missing magic number"));
+    }
+
+    let mut big_endian = None;
+    let mut elements: Vec<Element> = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(io_error)? == 0 {
+            return Err(malformed("unterminated header"));
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["format", "binary_little_endian", _] => big_endian = Some(false),
+            ["format", "binary_big_endian", _] => big_endian = Some(true),
+            ["format", other, ..] => {
+                return Err(GeometryError::MeshImport(format!(
+                    "unsupported PLY format: {}",
+                    other
+                )))
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count.parse().map_err(|_| malformed("element count"))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count_type, item_type, name] => {
+                let property = Property::List(
+                    name.to_string(),
+                    Scalar::parse(count_type).ok_or_else(|| malformed("property type"))?,
+                    Scalar::parse(item_type).ok_or_else(|| malformed("property type"))?,
+                );
+                elements
+                    .last_mut()
+                    .ok_or_else(|| malformed("property before element"))?
+                    .properties
+                    .push(property);
+            }
+            ["property", ty, name] => {
+                let property = Property::Scalar(
+                    name.to_string(),
+                    Scalar::parse(ty).ok_or_else(|| malformed("property type"))?,
+                );
+                elements
+                    .last_mut()
+                    .ok_or_else(|| malformed("property before element"))?
+                    .properties
+                    .push(property);
+            }
+            ["end_header"] => break,
+            _ => {}
+        }
+    }
+    let big_endian = big_endian.ok_or_else(|| malformed("missing format"))?;
+
+    let mut face = Vec::new();
+    for element in &elements {
+        if element.name == "vertex" {
+            decimator.positions.reserve_exact(element.count);
+        }
+        for _ in 0..element.count {
+            let mut p = [0.0; 3];
+            face.clear();
+            for property in &element.properties {
+                match property {
+                    Property::Scalar(name, ty) => {
+                        let value = ty.read(reader, big_endian).map_err(io_error)?;
+                        if element.name == "vertex" {
+                            match name.as_str() {
+                                "x" => p[0] = value,
+                                "y" => p[1] = value,
+                                "z" => p[2] = value,
+                                _ => {}
+                            }
+                        }
+                    }
+                    Property::List(name, count_type, item_type) => {
+                        let count = count_type.read(reader, big_endian).map_err(io_error)?;
+                        let keep = element.name == "face"
+                            && matches!(name.as_str(), "vertex_indices" | "vertex_index");
+                        for _ in 0..count as usize {
+                            let index = item_type.read(reader, big_endian).map_err(io_error)?;
+                            if keep {
+                                if index < 0.0 {
+                                    return Err(malformed("negative vertex index"));
+                                }
+                                face.push(index as usize);
+                            }
+                        }
+                    }
+                }
+            }
+            match element.name.as_str() {
+                "vertex" => decimator.add_vertex(p),
+                "face" => decimator.add_face(&face)?,
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CUBE_OBJ: &str = "\
+# unit cube with quad faces
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0 0 1
+v 1 0 1
+v 1 1 1
+v 0 1 1
+f 1 4 3 2
+f 5 6 7 8
+f 1 2 6 5
+f 3 4 8 7
+f 1 5 8 4
+f 2/1/1 3/2/1 7/3/1 6/4/1
+";
+
+    #[test]
+    fn obj_quads_become_triangles() {
+        let (mesh, stats) = import_mesh(
+            CUBE_OBJ.as_bytes(),
+            MeshFormat::Obj,
+            ImportOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(stats.source_triangles, 12);
+        assert_eq!(mesh.triangle_count(), 12);
+        assert_eq!(mesh.vertex_count(), 8);
+        assert!(mesh.is_manifold());
+        assert!((mesh.volume() - 1.0).abs() < 1e-9);
+        assert_eq!(stats.cell_size, 0.0);
+    }
+
+    #[test]
+    fn big_endian_ply_matches_obj() {
+        let mut ply = b"ply\nformat binary_big_endian 1.0\ncomment tetrahedron\n\
+element vertex 4\nproperty double x\nproperty double y\nproperty double z\n\
+element face 4\nproperty list uchar int vertex_indices\nend_header\n"
+            .to_vec();
+        for p in [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ] {
+            for c in p {
+                ply.extend_from_slice(&f64::to_be_bytes(c));
+            }
+        }
+        for f in [[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]] {
+            ply.push(3);
+            for i in f {
+                ply.extend_from_slice(&i32::to_be_bytes(i));
+            }
+        }
+
+        let (mesh, stats) =
+            import_mesh(ply.as_slice(), MeshFormat::Ply, ImportOptions::default()).unwrap();
+        assert_eq!(mesh.triangle_count(), 4);
+        assert!((mesh.volume() - 1.0 / 6.0).abs() < 1e-9);
+        assert!(stats.content_hash.starts_with("fnv1a64:"));
+    }
+
+    #[test]
+    fn budget_forces_decimation() {
+        // 100 x 100 grid of quads
+        let mut obj = String::new();
+        for y in 0..=100 {
+            for x in 0..=100 {
+                obj.push_str(&format!("v {} {} 0\n", x, y));
+            }
+        }
+        for y in 0..100 {
+            for x in 0..100 {
+                let i = y * 101 + x + 1;
+                obj.push_str(&format!("f {} {} {} {}\n", i, i + 1, i + 102, i + 101));
+            }
+        }
+
+        let options = ImportOptions {
+            max_triangles: 5_000,
+        };
+        let (mesh, stats) = import_mesh(obj.as_bytes(), MeshFormat::Obj, options).unwrap();
+        assert_eq!(stats.source_triangles, 20_000);
+        assert!(mesh.triangle_count() <= 5_000);
+        assert!(mesh.triangle_count() > 1_000);
+        assert!(stats.cell_size > 0.0);
+        // Clustering keeps the extent of the surface to within one cell
+        let bbox = mesh.bounding_box().unwrap();
+        assert!(bbox.max.x - bbox.min.x > 100.0 - 2.0 * stats.cell_size);
+    }
+
+    #[test]
+    fn malformed_input_is_rejected() {
+        let options = ImportOptions::default();
+        for (source, format) in [
+            ("v 0 0 0\nf 1 2 3\n", MeshFormat::Obj),
+            ("v 0 zero 0\n", MeshFormat::Obj),
+            ("ply\nformat ascii 1.0\nend_header\n", MeshFormat::Ply),
+            ("ply\nformat binary_little_endian 1.0\n", MeshFormat::Ply),
+        ] {
+            assert!(matches!(
+                import_mesh(source.as_bytes(), format, options),
+                Err(GeometryError::MeshImport(_))
+            ));
+        }
+    }
+}
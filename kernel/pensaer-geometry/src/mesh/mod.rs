@@ -2,21 +2,69 @@
 //!
 //! This module provides:
 //! - `TriangleMesh`: Core mesh data structure with vertices, normals, UVs, and indices
-//! - `triangulate`: Polygon triangulation algorithms (ear-clipping, holes)
+//! - `WeldReport`: Counts from vertex welding and mesh cleanup
+//! - `triangulate`: Polygon triangulation algorithms (ear-clipping, holes,
+//!   constrained Delaunay)
 //! - `extrude`: 2D to 3D extrusion for generating architectural elements
-
+//! - `import`: Streaming, memory-bounded OBJ/PLY import with decimation
+//! - `raycast`: Ray casting against meshes and picking the closest element
+//! - `section`: Cutting meshes with a plane for section drawings
+//! - `worker`: Background mesh generation (requires the `parallel` feature)
+//! - `batch`: Parallel mesh generation for many elements at once (requires
+//!   the `parallel` feature)
+
+#[cfg(feature = "parallel")]
+pub mod batch;
 pub mod extrude;
+pub mod import;
+pub mod raycast;
+pub mod section;
 pub mod triangulate;
-
-pub use extrude::{extrude_polygon, extrude_polygon_with_hole, extrude_wall_with_openings};
-pub use triangulate::{triangulate_polygon, triangulate_polygon_with_holes};
+#[cfg(feature = "parallel")]
+pub mod worker;
+
+#[cfg(feature = "parallel")]
+pub use batch::{generate_building_meshes, generate_meshes, merge_element_meshes};
+pub use extrude::{
+    extrude_polygon, extrude_polygon_with_hole, extrude_polygon_with_holes,
+    extrude_polygon_with_holes_using, extrude_wall_with_openings,
+};
+pub use import::{import_mesh, ImportOptions, ImportStats, MeshFormat};
+pub use raycast::{pick_element, pick_mesh, PickHit, Ray, RayHit};
+pub use section::{section_building, SectionCurve, SectionPlane};
+pub use triangulate::{
+    triangulate_constrained_delaunay, triangulate_polygon, triangulate_polygon_with_holes,
+    triangulate_polygon_with_holes_using, TriangulationMethod,
+};
+#[cfg(feature = "parallel")]
+pub use worker::MeshWorker;
+
+use std::ops::Range;
 
 use serde::{Deserialize, Serialize};
 
-use pensaer_math::{BoundingBox3, Point3, Transform3, Vector3};
+use pensaer_math::{BoundingBox3, Point2, Point3, Transform3, Vector3};
 
+use crate::constants::{quantize_to, EPSILON};
 use crate::error::{GeometryError, GeometryResult};
 
+/// Counts reported by [`TriangleMesh::weld_vertices`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WeldReport {
+    /// Vertex count before welding.
+    pub vertices_before: usize,
+    /// Vertex count after welding and cleanup.
+    pub vertices_after: usize,
+    /// Vertices merged into a coincident vertex.
+    pub vertices_welded: usize,
+    /// Triangles dropped because welding collapsed two of their corners.
+    pub degenerate_triangles: usize,
+    /// Triangles dropped as duplicates of another triangle.
+    pub duplicate_triangles: usize,
+    /// Vertices dropped because no triangle referenced them.
+    pub unused_vertices: usize,
+}
+
 /// A triangle mesh for 3D visualization.
 ///
 /// The mesh consists of:
@@ -24,6 +72,7 @@ use crate::error::{GeometryError, GeometryResult};
 /// - Normals: Normal vectors for lighting (optional, can be computed)
 /// - UVs: Texture coordinates (optional)
 /// - Indices: Triangles defined by vertex indices
+/// - Groups: Named triangle ranges, e.g. one per element (optional)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TriangleMesh {
     /// Vertex positions.
@@ -37,6 +86,11 @@ pub struct TriangleMesh {
 
     /// Triangle indices (each [u32; 3] is one triangle).
     pub indices: Vec<[u32; 3]>,
+
+    /// Named ranges over `indices`, exported as OBJ groups. Triangles
+    /// outside every range are ungrouped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<(String, Range<usize>)>,
 }
 
 impl TriangleMesh {
@@ -47,6 +101,7 @@ impl TriangleMesh {
             normals: Vec::new(),
             uvs: Vec::new(),
             indices: Vec::new(),
+            groups: Vec::new(),
         }
     }
 
@@ -57,6 +112,7 @@ impl TriangleMesh {
             normals: Vec::new(),
             uvs: Vec::new(),
             indices,
+            groups: Vec::new(),
         }
     }
 
@@ -179,9 +235,16 @@ impl TriangleMesh {
         (volume / 6.0).abs()
     }
 
-    /// Merge another mesh into this one.
+    /// Merge another mesh into this one, keeping its groups.
     pub fn merge(&mut self, other: &TriangleMesh) {
         let offset = self.vertices.len() as u32;
+        let first = self.indices.len();
+        self.groups.extend(
+            other
+                .groups
+                .iter()
+                .map(|(name, range)| (name.clone(), range.start + first..range.end + first)),
+        );
 
         self.vertices.extend(other.vertices.iter().cloned());
         self.normals.extend(other.normals.iter().cloned());
@@ -193,14 +256,38 @@ impl TriangleMesh {
         }
     }
 
+    /// Merge another mesh into this one as a single group called `name`.
+    ///
+    /// Any groups inside `other` are replaced by the one group.
+    pub fn merge_named(&mut self, name: &str, other: &TriangleMesh) {
+        let first = self.indices.len();
+        let groups = self.groups.len();
+        self.merge(other);
+        self.groups.truncate(groups);
+        self.groups
+            .push((name.to_string(), first..self.indices.len()));
+    }
+
     /// Apply a transform to all vertices.
+    ///
+    /// Normals go through the inverse-transpose of the upper 3x3, so they
+    /// stay perpendicular to their faces under non-uniform scale. A
+    /// singular transform falls back to transforming them as vectors.
     pub fn transform(&mut self, t: &Transform3) {
         for v in &mut self.vertices {
             *v = t.transform_point(*v);
         }
+        let inv = t.inverse().ok();
         for n in &mut self.normals {
-            *n = t.transform_vector(*n);
-            // Re-normalize in case of non-uniform scale
+            *n = match &inv {
+                // Transpose of inverse = use rows instead of columns
+                Some(inv) => Vector3::new(
+                    inv.m[0][0] * n.x + inv.m[0][1] * n.y + inv.m[0][2] * n.z,
+                    inv.m[1][0] * n.x + inv.m[1][1] * n.y + inv.m[1][2] * n.z,
+                    inv.m[2][0] * n.x + inv.m[2][1] * n.y + inv.m[2][2] * n.z,
+                ),
+                None => t.transform_vector(*n),
+            };
             if let Ok(normalized) = n.normalize() {
                 *n = normalized;
             }
@@ -266,6 +353,318 @@ impl TriangleMesh {
         }
     }
 
+    /// Snap every vertex coordinate to a multiple of `precision`.
+    ///
+    /// This removes sub-precision jitter so exports are deterministic and
+    /// survive conversion to `f32` without z-fighting. Negative zero is
+    /// written as zero. A non-positive precision leaves the mesh unchanged.
+    /// Vertices that land on the same point are not merged; see
+    /// [`TriangleMesh::quantize_and_weld`].
+    pub fn quantize(&mut self, precision: f64) {
+        if precision <= 0.0 {
+            return;
+        }
+        let snap = |x: f64| quantize_to(x, precision) + 0.0;
+        for v in &mut self.vertices {
+            *v = Point3::new(snap(v.x), snap(v.y), snap(v.z));
+        }
+    }
+
+    /// [`Quantize`](TriangleMesh::quantize) the mesh, then weld the
+    /// vertices that now coincide.
+    pub fn quantize_and_weld(&mut self, precision: f64) -> WeldReport {
+        self.quantize(precision);
+        self.weld_vertices(precision)
+    }
+
+    /// Merge vertices that coincide within `tolerance`, then drop the
+    /// triangles that collapse, duplicate triangles, and unused vertices.
+    ///
+    /// Positions are hashed on coordinates quantized to `tolerance` (a
+    /// non-positive tolerance falls back to [`EPSILON`]), so two points just
+    /// either side of a cell boundary are not merged. Vertices with
+    /// different UVs are never welded, which keeps texture seams intact.
+    /// Where welded vertices carried different normals, the result is their
+    /// average weighted by the area of the triangles around each one.
+    pub fn weld_vertices(&mut self, tolerance: f64) -> WeldReport {
+        use std::collections::HashMap;
+
+        let precision = if tolerance > 0.0 { tolerance } else { EPSILON };
+        let cell = |x: f64| (quantize_to(x, precision) + 0.0).to_bits();
+        let has_normals = self.has_normals();
+        let has_uvs = self.has_uvs();
+
+        let mut report = WeldReport {
+            vertices_before: self.vertices.len(),
+            ..WeldReport::default()
+        };
+
+        // Area of the triangles touching each original vertex
+        let mut incident_area = vec![0.0; self.vertices.len()];
+        if has_normals {
+            for tri in &self.indices {
+                let v0 = self.vertices[tri[0] as usize];
+                let area = (self.vertices[tri[1] as usize] - v0)
+                    .cross(&(self.vertices[tri[2] as usize] - v0))
+                    .length()
+                    * 0.5;
+                for &i in tri {
+                    incident_area[i as usize] += area;
+                }
+            }
+        }
+
+        let mut cells: HashMap<[u64; 5], u32> = HashMap::new();
+        let mut remap = Vec::with_capacity(self.vertices.len());
+        let mut vertices = Vec::new();
+        let mut normals = Vec::new();
+        let mut weighted = Vec::new();
+        let mut mixed = Vec::new();
+        let mut uvs = Vec::new();
+
+        for (i, v) in self.vertices.iter().enumerate() {
+            let (u, w) = if has_uvs { self.uvs[i] } else { (0.0, 0.0) };
+            let key = [
+                cell(v.x),
+                cell(v.y),
+                cell(v.z),
+                (u + 0.0).to_bits(),
+                (w + 0.0).to_bits(),
+            ];
+            let next = vertices.len() as u32;
+            let index = *cells.entry(key).or_insert(next);
+            remap.push(index);
+
+            if index == next {
+                vertices.push(*v);
+                if has_normals {
+                    normals.push(self.normals[i]);
+                    weighted.push(self.normals[i] * incident_area[i]);
+                    mixed.push(false);
+                }
+                if has_uvs {
+                    uvs.push((u, w));
+                }
+            } else if has_normals {
+                let k = index as usize;
+                weighted[k] += self.normals[i] * incident_area[i];
+                mixed[k] |= (self.normals[i] - normals[k]).length_squared() > EPSILON;
+            }
+        }
+
+        for (k, n) in normals.iter_mut().enumerate() {
+            if mixed[k] {
+                if let Ok(average) = weighted[k].normalize() {
+                    *n = average;
+                }
+            }
+        }
+
+        report.vertices_welded = self.vertices.len() - vertices.len();
+        self.vertices = vertices;
+        self.normals = normals;
+        self.uvs = uvs;
+
+        for tri in &mut self.indices {
+            *tri = tri.map(|i| remap[i as usize]);
+        }
+        report.degenerate_triangles =
+            self.retain_triangles(|tri| tri[0] != tri[1] && tri[1] != tri[2] && tri[2] != tri[0]);
+
+        report.duplicate_triangles = self.deduplicate_triangles();
+        report.unused_vertices = self.remove_unused_vertices();
+        report.vertices_after = self.vertices.len();
+        report
+    }
+
+    /// Remove vertices no triangle references, keeping the order of the
+    /// rest. Returns the number removed.
+    pub fn remove_unused_vertices(&mut self) -> usize {
+        let mut used = vec![false; self.vertices.len()];
+        for tri in &self.indices {
+            for &i in tri {
+                used[i as usize] = true;
+            }
+        }
+
+        let mut remap = vec![0u32; used.len()];
+        let mut next = 0;
+        for (i, &keep) in used.iter().enumerate() {
+            if keep {
+                remap[i] = next;
+                next += 1;
+            }
+        }
+
+        let removed = used.len() - next as usize;
+        if removed == 0 {
+            return 0;
+        }
+
+        retain_used(&mut self.vertices, &used);
+        if self.has_normals() {
+            retain_used(&mut self.normals, &used);
+        }
+        if self.has_uvs() {
+            retain_used(&mut self.uvs, &used);
+        }
+        for tri in &mut self.indices {
+            *tri = tri.map(|i| remap[i as usize]);
+        }
+        removed
+    }
+
+    /// Remove triangles that repeat another triangle with the same winding.
+    /// Returns the number removed.
+    pub fn deduplicate_triangles(&mut self) -> usize {
+        use std::collections::HashSet;
+
+        let mut seen = HashSet::new();
+        self.retain_triangles(|tri| {
+            // Rotate so the smallest index comes first, preserving winding
+            let r = (0..3).min_by_key(|&i| tri[i]).unwrap_or(0);
+            seen.insert([tri[r], tri[(r + 1) % 3], tri[(r + 2) % 3]])
+        })
+    }
+
+    /// Keep the triangles `keep` accepts, shrinking the groups to match.
+    /// Returns the number removed.
+    fn retain_triangles(&mut self, mut keep: impl FnMut(&[u32; 3]) -> bool) -> usize {
+        // kept[i] counts the triangles kept before triangle i
+        let mut kept = Vec::with_capacity(self.indices.len() + 1);
+        kept.push(0);
+        let before = self.indices.len();
+        self.indices.retain(|tri| {
+            let keep = keep(tri);
+            kept.push(kept[kept.len() - 1] + keep as usize);
+            keep
+        });
+
+        let last = kept.len() - 1;
+        for (_, range) in &mut self.groups {
+            *range = kept[range.start.min(last)]..kept[range.end.min(last)];
+        }
+        before - self.indices.len()
+    }
+
+    /// Cap every hole in the mesh and return the number filled.
+    ///
+    /// A hole is a loop of boundary edges, edges used by only one triangle.
+    /// Each loop is projected onto its best-fit plane and ear-clipped, and
+    /// the cap triangles are wound to face the same way as the triangles
+    /// around the hole. Boundary edges that do not close into a loop, and
+    /// loops that cannot be triangulated, are left open.
+    ///
+    /// Boundaries are found from shared vertex indices, so a mesh with split
+    /// vertices (one copy per face for flat shading) should be welded with
+    /// [`TriangleMesh::weld_vertices`] first. Caps reuse the boundary
+    /// vertices, so no normals or UVs are added.
+    pub fn close_holes(&mut self) -> usize {
+        use std::collections::{BTreeMap, HashMap};
+
+        let mut edge_count: HashMap<(u32, u32), u32> = HashMap::new();
+        for tri in &self.indices {
+            for i in 0..3 {
+                let (a, b) = (tri[i], tri[(i + 1) % 3]);
+                *edge_count.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+            }
+        }
+
+        // Walk each boundary edge against its triangle's winding, so loops
+        // come out wound the way their caps must face
+        let mut next: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for tri in &self.indices {
+            for i in 0..3 {
+                let (a, b) = (tri[i], tri[(i + 1) % 3]);
+                if edge_count[&(a.min(b), a.max(b))] == 1 {
+                    next.entry(b).or_default().push(a);
+                }
+            }
+        }
+
+        let mut caps = Vec::new();
+        let mut filled = 0;
+        while let Some(start) = next.keys().next().copied() {
+            let mut ring = vec![start];
+            let mut closed = false;
+            while let Some(to) = take_edge(&mut next, ring[ring.len() - 1]) {
+                if to == start {
+                    closed = true;
+                    break;
+                }
+                ring.push(to);
+            }
+
+            if let Some(triangles) = closed.then(|| self.cap_ring(&ring)).flatten() {
+                caps.extend(triangles);
+                filled += 1;
+            }
+        }
+
+        self.indices.extend(caps);
+        filled
+    }
+
+    /// Triangulate a closed ring of vertex indices, with the triangles
+    /// facing along the ring's winding.
+    fn cap_ring(&self, ring: &[u32]) -> Option<Vec<[u32; 3]>> {
+        if ring.len() < 3 {
+            return None;
+        }
+
+        // Where a T-junction leaves two boundary edges lying on top of each
+        // other, the ring doubles back on itself. Clip those zero-area
+        // spikes first; the rest has a proper area to triangulate.
+        let mut ring = ring.to_vec();
+        let mut caps = Vec::new();
+        while ring.len() > 3 {
+            let n = ring.len();
+            let Some(spike) = (0..n).find(|&i| {
+                let [prev, cur, next] = [ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]]
+                    .map(|k| self.vertices[k as usize]);
+                let (a, b) = (cur - prev, next - cur);
+                a.dot(&b) < 0.0 && a.cross(&b).length() <= EPSILON * a.length() * b.length()
+            }) else {
+                break;
+            };
+            let (prev, next) = (ring[(spike + n - 1) % n], ring[(spike + 1) % n]);
+            caps.push([prev, ring[spike], next]);
+            ring.remove(spike);
+        }
+
+        // Newell's method: the ring runs counter-clockwise about this normal
+        let mut normal = Vector3::ZERO;
+        for (i, &a) in ring.iter().enumerate() {
+            let p = self.vertices[a as usize];
+            let q = self.vertices[ring[(i + 1) % ring.len()] as usize];
+            normal.x += (p.y - q.y) * (p.z + q.z);
+            normal.y += (p.z - q.z) * (p.x + q.x);
+            normal.z += (p.x - q.x) * (p.y + q.y);
+        }
+        let normal = normal.normalize().ok()?;
+
+        let helper = if normal.x.abs() < 0.9 {
+            Vector3::new(1.0, 0.0, 0.0)
+        } else {
+            Vector3::new(0.0, 1.0, 0.0)
+        };
+        let u = helper.cross(&normal).normalize().ok()?;
+        let v = normal.cross(&u);
+
+        let origin = self.vertices[ring[0] as usize];
+        let points: Vec<Point2> = ring
+            .iter()
+            .map(|&i| {
+                let d = self.vertices[i as usize] - origin;
+                Point2::new(d.dot(&u), d.dot(&v))
+            })
+            .collect();
+
+        let triangles = triangulate_polygon(&points).ok()?;
+        caps.extend(triangles.into_iter().map(|t| t.map(|k| ring[k])));
+        Some(caps)
+    }
+
     /// Flip all normals and reverse triangle winding.
     pub fn flip_normals(&mut self) {
         for n in &mut self.normals {
@@ -277,6 +676,10 @@ impl TriangleMesh {
     }
 
     /// Export to OBJ format string.
+    ///
+    /// Each group starts with `g <name>` and `usemtl <name>` lines, with
+    /// whitespace in names replaced by underscores; ungrouped triangles
+    /// after a group go back to the `default` group.
     pub fn to_obj(&self) -> String {
         let mut obj = String::new();
 
@@ -299,7 +702,29 @@ impl TriangleMesh {
         let has_normals = self.has_normals();
         let has_uvs = self.has_uvs();
 
-        for tri in &self.indices {
+        // Group of each triangle; later groups win where ranges overlap
+        let mut group_of = vec![None; self.indices.len()];
+        for (g, (_, range)) in self.groups.iter().enumerate() {
+            let end = range.end.min(group_of.len());
+            for slot in group_of.iter_mut().take(end).skip(range.start) {
+                *slot = Some(g);
+            }
+        }
+
+        let mut current = None;
+        for (tri, &group) in self.indices.iter().zip(&group_of) {
+            if group != current {
+                let name = match group {
+                    Some(g) => {
+                        let words: Vec<_> = self.groups[g].0.split_whitespace().collect();
+                        words.join("_")
+                    }
+                    None => "default".to_string(),
+                };
+                obj.push_str(&format!("g {}\nusemtl {}\n", name, name));
+                current = group;
+            }
+
             if has_normals && has_uvs {
                 obj.push_str(&format!(
                     "f {}/{}/{} {}/{}/{} {}/{}/{}\n",
@@ -348,6 +773,22 @@ impl Default for TriangleMesh {
     }
 }
 
+/// Keep the items whose flag in `used` is set.
+fn retain_used<T>(items: &mut Vec<T>, used: &[bool]) {
+    let mut flags = used.iter();
+    items.retain(|_| flags.next().copied().unwrap_or(false));
+}
+
+/// Remove and return one boundary edge leaving `from`.
+fn take_edge(next: &mut std::collections::BTreeMap<u32, Vec<u32>>, from: u32) -> Option<u32> {
+    let targets = next.get_mut(&from)?;
+    let to = targets.pop();
+    if targets.is_empty() {
+        next.remove(&from);
+    }
+    to
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -402,6 +843,7 @@ mod tests {
             normals: Vec::new(),
             uvs: Vec::new(),
             indices: vec![[0, 1, 2]], // indices 1, 2 out of bounds
+            groups: Vec::new(),
         };
         assert!(!mesh.is_valid());
     }
@@ -468,6 +910,62 @@ mod tests {
         assert!((mesh.vertices[0].z - 3.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn mesh_rotation_keeps_normals_unit() {
+        let mut mesh = TriangleMesh::from_vertices_indices(
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+            ],
+            vec![[0, 1, 2]],
+        );
+        mesh.compute_flat_normals();
+
+        let t = Transform3::rotation_about(
+            Vector3::new(1.0, 1.0, 0.0),
+            std::f64::consts::FRAC_PI_2,
+            Point3::new(0.5, 0.5, 0.0),
+        )
+        .unwrap();
+        let rotated = mesh.transformed(&t);
+
+        for n in &rotated.normals {
+            assert!((n.length() - 1.0).abs() < 1e-10);
+        }
+        // Rotating about an in-plane axis tips the +Z normal into the plane
+        assert!(rotated.normals[0].z.abs() < 1e-10);
+        assert!((rotated.surface_area() - mesh.surface_area()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn mesh_non_uniform_scale_keeps_normals_perpendicular() {
+        // A face in the plane x + y = 1, normal at 45° in XY
+        let mut mesh = TriangleMesh::from_vertices_indices(
+            vec![
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+                Point3::new(1.0, 0.0, 1.0),
+            ],
+            vec![[0, 1, 2]],
+        );
+        mesh.compute_flat_normals();
+        let n = mesh.normals[0];
+        assert!((n.x - n.y).abs() < 1e-10 && n.x > 0.0);
+
+        let scaled = mesh.transformed(&Transform3::scale(2.0, 1.0, 1.0));
+
+        // The face becomes x/2 + y = 1, normal (1, 2, 0) normalized, not
+        // the rescaled (2, 1, 0)
+        let expected = Vector3::new(1.0, 2.0, 0.0).normalize().unwrap();
+        let mut recomputed = scaled.clone();
+        recomputed.compute_flat_normals();
+        for (n, r) in scaled.normals.iter().zip(&recomputed.normals) {
+            assert!(n.approx_eq(&expected, 1e-10));
+            assert!(n.approx_eq(r, 1e-10));
+        }
+    }
+
     #[test]
     fn mesh_to_obj() {
         let mesh = TriangleMesh::from_vertices_indices(
@@ -484,4 +982,221 @@ mod tests {
         assert!(obj.contains("v 1 0 0"));
         assert!(obj.contains("f 1 2 3"));
     }
+
+    #[test]
+    fn obj_export_names_groups() {
+        use crate::element::Element;
+        use crate::elements::{Floor, Wall};
+        use pensaer_math::Point2;
+
+        let wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(4.0, 0.0), 3.0, 0.2).unwrap();
+        let floor = Floor::rectangle(Point2::new(0.0, 0.0), Point2::new(4.0, 3.0), 0.3).unwrap();
+        let wall_mesh = wall.to_mesh().unwrap();
+        let floor_mesh = floor.to_mesh().unwrap();
+
+        let mut building = TriangleMesh::new();
+        building.merge_named("wall", &wall_mesh);
+        building.merge_named("floor", &floor_mesh);
+        let walls = wall_mesh.triangle_count();
+        assert_eq!(building.groups[0], ("wall".to_string(), 0..walls));
+        assert_eq!(
+            building.groups[1],
+            ("floor".to_string(), walls..building.triangle_count())
+        );
+
+        let obj = building.to_obj();
+        let wall_at = obj.find("g wall\nusemtl wall\n").unwrap();
+        let floor_at = obj.find("g floor\nusemtl floor\n").unwrap();
+        assert!(wall_at < obj.find("\nf ").unwrap() && wall_at < floor_at);
+        let faces = |s: &str| s.lines().filter(|l| l.starts_with("f ")).count();
+        assert_eq!(faces(&obj[wall_at..floor_at]), walls);
+        assert_eq!(faces(&obj[floor_at..]), floor_mesh.triangle_count());
+        assert!(!obj.contains("g default"));
+
+        // Ungrouped triangles after a group fall back to the default group
+        building.merge(&floor_mesh);
+        let obj = building.to_obj();
+        let default_at = obj.find("g default\nusemtl default\n").unwrap();
+        assert!(default_at > floor_at);
+        assert_eq!(faces(&obj[default_at..]), floor_mesh.triangle_count());
+    }
+
+    #[test]
+    fn weld_keeps_groups_aligned() {
+        let mut mesh = TriangleMesh::new();
+        mesh.merge_named("first", &unwelded_cube());
+        mesh.merge_named("copy", &unwelded_cube());
+
+        mesh.weld_vertices(1e-9);
+
+        // The copy's triangles duplicate the first cube's and are dropped
+        assert_eq!(mesh.triangle_count(), 12);
+        assert_eq!(mesh.groups[0].1, 0..12);
+        assert_eq!(mesh.groups[1].1, 12..12);
+    }
+
+    /// Cube with every triangle carrying its own corners and face normal,
+    /// as an element mesher emits it.
+    fn unwelded_cube() -> TriangleMesh {
+        let cube = cube_mesh();
+        let mut mesh = TriangleMesh::new();
+        for tri in &cube.indices {
+            let corners = tri.map(|i| cube.vertices[i as usize]);
+            let normal = (corners[1] - corners[0])
+                .cross(&(corners[2] - corners[0]))
+                .normalize()
+                .unwrap();
+            let base = mesh.vertices.len() as u32;
+            mesh.vertices.extend(corners);
+            mesh.normals.extend([normal; 3]);
+            mesh.indices.push([base, base + 1, base + 2]);
+        }
+        mesh
+    }
+
+    #[test]
+    fn weld_cube_to_eight_vertices() {
+        let mut mesh = unwelded_cube();
+        assert_eq!(mesh.vertex_count(), 36);
+        let volume = mesh.volume();
+        let area = mesh.surface_area();
+
+        let report = mesh.weld_vertices(1e-6);
+        assert_eq!(report.vertices_before, 36);
+        assert_eq!(report.vertices_after, 8);
+        assert_eq!(report.vertices_welded, 28);
+        assert_eq!(report.degenerate_triangles, 0);
+        assert_eq!(mesh.triangle_count(), 12);
+        assert!(mesh.is_manifold());
+        assert!((mesh.volume() - volume).abs() < EPSILON);
+        assert!((mesh.surface_area() - area).abs() < EPSILON);
+
+        // Corner normals blend the three faces and point away from the center
+        let center = Point3::new(0.5, 0.5, 0.5);
+        for (v, n) in mesh.vertices.iter().zip(&mesh.normals) {
+            assert!((n.length() - 1.0).abs() < 1e-9);
+            assert!(n.dot(&(*v - center)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn weld_drops_collapsed_and_duplicate_triangles() {
+        let mut mesh = TriangleMesh::from_vertices_indices(
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+                Point3::new(1.0, 0.0, 1e-9),
+                Point3::new(5.0, 5.0, 5.0),
+            ],
+            vec![[0, 1, 2], [1, 2, 0], [0, 1, 3], [0, 2, 1]],
+        );
+
+        let report = mesh.weld_vertices(1e-6);
+        assert_eq!(report.vertices_welded, 1);
+        assert_eq!(report.degenerate_triangles, 1);
+        // The rotated copy goes; the reversed one is a distinct face
+        assert_eq!(report.duplicate_triangles, 1);
+        assert_eq!(report.unused_vertices, 1);
+        assert_eq!(mesh.vertex_count(), 3);
+        assert_eq!(mesh.indices, vec![[0, 1, 2], [0, 2, 1]]);
+    }
+
+    #[test]
+    fn quantize_snaps_jitter_back_to_the_grid() {
+        use crate::constants::QUANTIZE_PRECISION;
+
+        let cube = cube_mesh();
+        let mut clean = cube.clone();
+        clean.quantize(QUANTIZE_PRECISION);
+        assert_eq!(clean.vertices, cube.vertices);
+
+        let mut jittered = cube.clone();
+        for (i, v) in jittered.vertices.iter_mut().enumerate() {
+            let jitter = if i % 2 == 0 { 3e-4 } else { -4e-4 };
+            *v = Point3::new(v.x + jitter, v.y - jitter, v.z + jitter);
+        }
+        assert_ne!(jittered.vertices, cube.vertices);
+        jittered.quantize(QUANTIZE_PRECISION);
+        assert_eq!(jittered.vertices, cube.vertices);
+        assert!(jittered.vertices.iter().all(|v| v.x.is_sign_positive()));
+
+        let mut unwelded = unwelded_cube();
+        for v in &mut unwelded.vertices {
+            v.z += 2e-4;
+        }
+        let report = unwelded.quantize_and_weld(QUANTIZE_PRECISION);
+        assert_eq!(report.vertices_after, 8);
+        assert!((unwelded.volume() - 1.0).abs() < EPSILON);
+    }
+
+    /// Whether every edge is walked once each way, i.e. the faces are
+    /// consistently wound.
+    fn consistently_wound(mesh: &TriangleMesh) -> bool {
+        use std::collections::HashSet;
+
+        let mut directed = HashSet::new();
+        for tri in &mesh.indices {
+            for i in 0..3 {
+                if !directed.insert((tri[i], tri[(i + 1) % 3])) {
+                    return false;
+                }
+            }
+        }
+        directed.iter().all(|&(a, b)| directed.contains(&(b, a)))
+    }
+
+    #[test]
+    fn close_holes_caps_quad_hole() {
+        let mut mesh = cube_mesh();
+        // Remove the top face, leaving a square hole
+        mesh.indices.drain(2..4);
+        assert!(!mesh.is_manifold());
+
+        assert_eq!(mesh.close_holes(), 1);
+        assert_eq!(mesh.triangle_count(), 12);
+        assert!(mesh.is_manifold());
+        assert!(consistently_wound(&mesh));
+        assert!((mesh.volume() - 1.0).abs() < EPSILON);
+
+        // Nothing left to fill
+        assert_eq!(mesh.close_holes(), 0);
+        assert_eq!(mesh.triangle_count(), 12);
+    }
+
+    #[test]
+    fn close_holes_fills_each_loop() {
+        let mut mesh = cube_mesh();
+        // Remove the bottom and top faces: two separate square holes
+        mesh.indices.drain(0..4);
+
+        assert_eq!(mesh.close_holes(), 2);
+        assert!(mesh.is_manifold());
+        assert!(consistently_wound(&mesh));
+        assert!((mesh.volume() - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn close_holes_seals_missing_door_head() {
+        let mut mesh = extrude_wall_with_openings(5.0, 3.0, 0.2, &[(1.0, 0.0, 0.9, 2.1)]).unwrap();
+        // Weld on position alone; the UV seams would keep the faces apart
+        mesh.uvs.clear();
+        mesh.weld_vertices(1e-9);
+        assert!(mesh.is_manifold());
+
+        // Knock out the head of the door, the only faces at its height
+        let head = |tri: &[u32; 3]| {
+            tri.iter()
+                .all(|&i| (mesh.vertices[i as usize].z - 2.1).abs() < 1e-9)
+        };
+        let kept: Vec<[u32; 3]> = mesh.indices.iter().copied().filter(|t| !head(t)).collect();
+        assert_eq!(mesh.indices.len() - kept.len(), 2);
+        mesh.indices = kept;
+        assert!(!mesh.is_manifold());
+
+        assert_eq!(mesh.close_holes(), 1);
+        assert!(mesh.is_manifold());
+        assert!(consistently_wound(&mesh));
+        assert!((mesh.volume() - (3.0 - 0.9 * 2.1 * 0.2)).abs() < 1e-9);
+    }
 }
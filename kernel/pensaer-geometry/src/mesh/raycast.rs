@@ -0,0 +1,320 @@
+//! Ray casting against triangle meshes, for picking elements in the viewer.
+//!
+//! Triangles are intersected with the Möller–Trumbore algorithm. Hits are
+//! resolved deterministically:
+//! - Rays parallel to a triangle's plane never hit it.
+//! - A ray through an edge or vertex shared by several triangles hits the
+//!   one with the lowest index.
+//! - Elements hit at the same distance resolve to the lowest element ID.
+
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use pensaer_math::{BoundingBox3, Point3, Vector3};
+
+use crate::element::Element;
+
+use super::TriangleMesh;
+
+/// Relative tolerance on barycentric coordinates, so rays through shared
+/// edges cannot slip between the triangles.
+const BARYCENTRIC_EPSILON: f64 = 1e-9;
+
+/// Hits closer than this are treated as being at the same distance.
+const DISTANCE_EPSILON: f64 = 1e-9;
+
+/// A half-line from an origin along a unit direction.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Ray {
+    /// Start of the ray.
+    pub origin: Point3,
+    /// Unit direction.
+    pub direction: Vector3,
+}
+
+impl Ray {
+    /// Create a ray, normalizing its direction.
+    ///
+    /// Returns `None` for a zero direction.
+    pub fn new(origin: Point3, direction: Vector3) -> Option<Self> {
+        Some(Self {
+            origin,
+            direction: direction.try_normalize()?,
+        })
+    }
+
+    /// Point at `distance` along the ray.
+    pub fn at(&self, distance: f64) -> Point3 {
+        self.origin + self.direction * distance
+    }
+
+    /// Distance at which the ray enters a box, or 0 if it starts inside.
+    ///
+    /// Returns `None` if the ray misses the box.
+    pub fn box_entry(&self, bbox: &BoundingBox3) -> Option<f64> {
+        let origin = [self.origin.x, self.origin.y, self.origin.z];
+        let direction = [self.direction.x, self.direction.y, self.direction.z];
+        let min = [bbox.min.x, bbox.min.y, bbox.min.z];
+        let max = [bbox.max.x, bbox.max.y, bbox.max.z];
+
+        let (mut near, mut far) = (0.0_f64, f64::INFINITY);
+        for k in 0..3 {
+            if direction[k].abs() < f64::EPSILON {
+                if origin[k] < min[k] || origin[k] > max[k] {
+                    return None;
+                }
+                continue;
+            }
+            let t0 = (min[k] - origin[k]) / direction[k];
+            let t1 = (max[k] - origin[k]) / direction[k];
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+        }
+        // Widen slightly so hits on a box face are not lost to rounding
+        (near <= far + DISTANCE_EPSILON).then_some(near)
+    }
+}
+
+/// Where a ray hits a mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RayHit {
+    /// Distance from the ray origin.
+    pub distance: f64,
+    /// Hit point.
+    pub point: Point3,
+    /// Index of the triangle hit.
+    pub triangle_index: usize,
+    /// Unit normal of the triangle hit, turned to face the ray origin.
+    pub normal: Vector3,
+}
+
+/// The closest element hit by a pick ray.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PickHit {
+    /// ID of the element hit.
+    pub element_id: Uuid,
+    /// Where its mesh was hit.
+    pub hit: RayHit,
+}
+
+impl TriangleMesh {
+    /// Find the closest triangle hit by a ray.
+    ///
+    /// `direction` need not be unit length; distances are in world units.
+    /// Returns `None` if the ray misses, the direction is zero, or every hit
+    /// is behind the origin.
+    pub fn raycast(&self, origin: Point3, direction: Vector3) -> Option<RayHit> {
+        let ray = Ray::new(origin, direction)?;
+        self.raycast_ray(&ray)
+    }
+
+    /// Find the closest triangle hit by a [`Ray`].
+    pub fn raycast_ray(&self, ray: &Ray) -> Option<RayHit> {
+        let mut best: Option<(usize, f64)> = None;
+        for (index, tri) in self.indices.iter().enumerate() {
+            let Some(distance) = self.intersect_triangle(ray, tri) else {
+                continue;
+            };
+            // Strictly closer only, so shared edges go to the lowest index
+            match best {
+                Some((_, d)) if distance >= d - DISTANCE_EPSILON => {}
+                _ => best = Some((index, distance)),
+            }
+        }
+
+        let (triangle_index, distance) = best?;
+        let [a, b, c] = self.indices[triangle_index].map(|i| self.vertices[i as usize]);
+        let mut normal = (b - a).cross(&(c - a)).try_normalize()?;
+        // Report the side that was hit, whatever the winding
+        if normal.dot(&ray.direction) > 0.0 {
+            normal = -normal;
+        }
+        Some(RayHit {
+            distance,
+            point: ray.at(distance),
+            triangle_index,
+            normal,
+        })
+    }
+
+    /// Möller–Trumbore intersection of a ray and one triangle.
+    fn intersect_triangle(&self, ray: &Ray, tri: &[u32; 3]) -> Option<f64> {
+        let [a, b, c] = tri.map(|i| self.vertices.get(i as usize).copied());
+        let (a, b, c) = (a?, b?, c?);
+        let e1 = b - a;
+        let e2 = c - a;
+
+        let p = ray.direction.cross(&e2);
+        let det = e1.dot(&p);
+        // Scale-aware parallel test: |det| = |e1 x e2| * |cos| for a unit ray
+        let area = e1.cross(&e2).length();
+        if area < f64::EPSILON || det.abs() <= area * 1e-12 {
+            return None;
+        }
+
+        let inv_det = 1.0 / det;
+        let s = ray.origin - a;
+        let u = s.dot(&p) * inv_det;
+        if !(-BARYCENTRIC_EPSILON..=1.0 + BARYCENTRIC_EPSILON).contains(&u) {
+            return None;
+        }
+        let q = s.cross(&e1);
+        let v = ray.direction.dot(&q) * inv_det;
+        if v < -BARYCENTRIC_EPSILON || u + v > 1.0 + BARYCENTRIC_EPSILON {
+            return None;
+        }
+
+        let t = e2.dot(&q) * inv_det;
+        (t > DISTANCE_EPSILON).then_some(t)
+    }
+}
+
+/// Pick the element closest along a ray.
+///
+/// Each element's [`bounding_box`](Element::bounding_box) is tested first,
+/// so meshes of elements the ray misses, or that lie behind a closer hit,
+/// are never intersected. Each mesh must be in the same space as its
+/// element's bounding box.
+pub fn pick_element(elements: &[(&dyn Element, &TriangleMesh)], ray: &Ray) -> Option<PickHit> {
+    let candidates = elements.iter().filter_map(|(element, mesh)| {
+        let bbox = element.bounding_box().ok()?;
+        Some((element.id(), bbox, *mesh))
+    });
+    closest_hit(candidates, ray)
+}
+
+/// Pick the closest of meshes tagged with element IDs.
+///
+/// Like [`pick_element`], but culls by each mesh's own bounding box.
+pub fn pick_mesh(meshes: &[(Uuid, &TriangleMesh)], ray: &Ray) -> Option<PickHit> {
+    let candidates = meshes
+        .iter()
+        .filter_map(|(id, mesh)| Some((*id, mesh.bounding_box()?, *mesh)));
+    closest_hit(candidates, ray)
+}
+
+fn closest_hit<'a>(
+    candidates: impl Iterator<Item = (Uuid, BoundingBox3, &'a TriangleMesh)>,
+    ray: &Ray,
+) -> Option<PickHit> {
+    // Nearest boxes first, so farther ones can be skipped once something is hit
+    let mut queue: Vec<(f64, Uuid, &TriangleMesh)> = candidates
+        .filter_map(|(id, bbox, mesh)| Some((ray.box_entry(&bbox)?, id, mesh)))
+        .collect();
+    queue.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+    let mut best: Option<PickHit> = None;
+    for (entry, element_id, mesh) in queue {
+        if let Some(best) = &best {
+            if entry > best.hit.distance + DISTANCE_EPSILON {
+                break;
+            }
+        }
+        let Some(hit) = mesh.raycast_ray(ray) else {
+            continue;
+        };
+        let closer = match &best {
+            None => true,
+            Some(best) => match compare_distance(hit.distance, best.hit.distance) {
+                Ordering::Less => true,
+                Ordering::Equal => element_id < best.element_id,
+                Ordering::Greater => false,
+            },
+        };
+        if closer {
+            best = Some(PickHit { element_id, hit });
+        }
+    }
+    best
+}
+
+fn compare_distance(a: f64, b: f64) -> Ordering {
+    if (a - b).abs() <= DISTANCE_EPSILON {
+        Ordering::Equal
+    } else {
+        a.total_cmp(&b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{OpeningType, Wall, WallOpening};
+    use crate::mesh::extrude_wall_with_openings;
+    use pensaer_math::Point2;
+
+    #[test]
+    fn ray_passes_through_door_opening() {
+        let mut wall = Wall::new(Point2::new(0.0, 0.0), Point2::new(5.0, 0.0), 3.0, 0.2).unwrap();
+        let door = WallOpening::new(2.5, 0.0, 1.0, 2.1, OpeningType::Door);
+        wall.add_opening(door).unwrap();
+        let wall_mesh = extrude_wall_with_openings(5.0, 3.0, 0.2, &wall.opening_rects()).unwrap();
+
+        let back = Wall::new(Point2::new(0.0, 3.0), Point2::new(5.0, 3.0), 3.0, 0.2).unwrap();
+        let back_mesh = back.to_mesh().unwrap();
+        let scene: [(&dyn Element, &TriangleMesh); 2] = [(&back, &back_mesh), (&wall, &wall_mesh)];
+
+        // Through the door: the wall is missed and the one behind is picked
+        let through = Ray::new(Point3::new(2.5, -5.0, 1.0), Vector3::UNIT_Y).unwrap();
+        assert!(wall_mesh.raycast_ray(&through).is_none());
+        let picked = pick_element(&scene, &through).unwrap();
+        assert_eq!(picked.element_id, back.id);
+        assert!((picked.hit.distance - 7.9).abs() < 1e-9);
+
+        // Beside the door the wall's front face is hit
+        let hit = wall_mesh
+            .raycast(Point3::new(1.0, -5.0, 1.0), Vector3::new(0.0, 2.0, 0.0))
+            .unwrap();
+        assert!((hit.distance - 4.9).abs() < 1e-9);
+        assert!(hit.point.distance_to(&Point3::new(1.0, -0.1, 1.0)) < 1e-9);
+        assert!(hit.normal.approx_eq(&Vector3::new(0.0, -1.0, 0.0), 1e-9));
+        let ray = Ray::new(Point3::new(1.0, -5.0, 1.0), Vector3::UNIT_Y).unwrap();
+        assert_eq!(pick_element(&scene, &ray).unwrap().element_id, wall.id);
+
+        // Aimed away from everything
+        let away = Ray::new(Point3::new(1.0, -5.0, 1.0), -Vector3::UNIT_Y).unwrap();
+        assert!(pick_element(&scene, &away).is_none());
+    }
+
+    #[test]
+    fn edge_hits_and_ties_are_deterministic() {
+        // Unit square in the z = 0 plane, split along its diagonal
+        let quad = TriangleMesh::from_vertices_indices(
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(1.0, 1.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+            ],
+            vec![[0, 1, 2], [0, 2, 3]],
+        );
+        let down = -Vector3::UNIT_Z;
+
+        // On the shared diagonal: the lower triangle index wins
+        let hit = quad.raycast(Point3::new(0.5, 0.5, 2.0), down).unwrap();
+        assert_eq!(hit.triangle_index, 0);
+        assert!((hit.distance - 2.0).abs() < 1e-12);
+
+        // Parallel to the plane, even when lying in it
+        assert!(quad
+            .raycast(Point3::new(-1.0, 0.5, 0.0), Vector3::UNIT_X)
+            .is_none());
+        assert!(quad
+            .raycast(Point3::new(-1.0, 0.5, 1.0), Vector3::UNIT_X)
+            .is_none());
+        assert!(quad
+            .raycast(Point3::new(0.5, 0.5, 2.0), Vector3::ZERO)
+            .is_none());
+
+        // Two coincident meshes: the lower element ID wins in either order
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let ray = Ray::new(Point3::new(0.25, 0.75, 1.0), down).unwrap();
+        for meshes in [[(a, &quad), (b, &quad)], [(b, &quad), (a, &quad)]] {
+            let picked = pick_mesh(&meshes, &ray).unwrap();
+            assert_eq!(picked.element_id, a);
+            assert_eq!(picked.hit.triangle_index, 1);
+        }
+    }
+}
@@ -0,0 +1,361 @@
+//! Section cuts: the curves where a plane slices through meshes, for
+//! section drawings.
+//!
+//! [`TriangleMesh::section`] intersects every triangle with the plane and
+//! chains the pieces into polylines. Endpoints within [`GEOM_TOL`] are
+//! merged, so meshes whose faces do not share vertices still chain. A cut
+//! through a closed solid gives closed loops, which end where they start;
+//! cuts through open meshes may give open polylines as well. Points in the
+//! middle of straight runs are dropped, so a box cuts to four corners.
+//!
+//! [`section_building`] cuts many element meshes at once and projects the
+//! curves into the plane's 2D coordinates, tagged with their element.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use pensaer_math::{LineSegment3, Point2, Point3, Vector3};
+
+use crate::constants::{EPSILON, GEOM_TOL};
+
+use super::TriangleMesh;
+
+/// Constants are in millimeters; meshes use meters.
+const MM_TO_M: f64 = 0.001;
+
+/// Points closer than this to the line through their neighbours are
+/// dropped from section polylines.
+const COLLINEAR_EPSILON: f64 = 1e-9;
+
+/// A cutting plane with 2D coordinates on it.
+///
+/// Seen from the side the normal points to, `u` runs right and `v` up: `v`
+/// is world Z projected onto the plane (world Y for a horizontal plane),
+/// so on a vertical plane `v` is height.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SectionPlane {
+    /// A point on the plane, the origin of its 2D coordinates.
+    pub origin: Point3,
+    /// Unit normal.
+    pub normal: Vector3,
+}
+
+impl SectionPlane {
+    /// Create a plane, normalizing its normal.
+    ///
+    /// Returns `None` for a zero normal.
+    pub fn new(origin: Point3, normal: Vector3) -> Option<Self> {
+        let normal = normal.normalize().ok()?;
+        Some(Self { origin, normal })
+    }
+
+    /// Signed distance of a point from the plane, positive on the normal's
+    /// side.
+    pub fn distance_to(&self, p: Point3) -> f64 {
+        (p - self.origin).dot(&self.normal)
+    }
+
+    /// The plane's `u` and `v` axes, unit length.
+    pub fn axes(&self) -> (Vector3, Vector3) {
+        let up = Vector3::UNIT_Z - self.normal * self.normal.z;
+        let v = up.normalize().unwrap_or(Vector3::UNIT_Y);
+        (v.cross(&self.normal), v)
+    }
+
+    /// A point in the plane's 2D coordinates, dropping its distance from
+    /// the plane.
+    pub fn project(&self, p: Point3) -> Point2 {
+        let (u, v) = self.axes();
+        let d = p - self.origin;
+        Point2::new(d.dot(&u), d.dot(&v))
+    }
+}
+
+/// A section polyline through one element, in plane coordinates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionCurve {
+    /// Element whose mesh was cut.
+    pub element_id: Uuid,
+    /// Points in the plane's `(u, v)` coordinates. A closed loop ends
+    /// where it starts.
+    pub points: Vec<Point2>,
+}
+
+impl SectionCurve {
+    /// Whether the curve is a closed loop.
+    pub fn is_closed(&self) -> bool {
+        self.points.len() > 2 && self.points.first() == self.points.last()
+    }
+}
+
+impl TriangleMesh {
+    /// The polylines where a plane cuts the mesh, in world coordinates.
+    ///
+    /// Closed loops end where they start. Faces lying in the plane are
+    /// skipped, as are triangles that only touch it at a vertex. A zero
+    /// normal gives no curves.
+    pub fn section(&self, origin: Point3, normal: Vector3) -> Vec<Vec<Point3>> {
+        match SectionPlane::new(origin, normal) {
+            Some(plane) => chain_segments(self.section_segments(&plane)),
+            None => Vec::new(),
+        }
+    }
+
+    /// The segment each triangle crossing the plane contributes.
+    fn section_segments(&self, plane: &SectionPlane) -> Vec<[Point3; 2]> {
+        let side = |d: f64| {
+            if d.abs() < EPSILON {
+                0.0
+            } else {
+                d.signum()
+            }
+        };
+
+        let mut segments = Vec::new();
+        for tri in &self.indices {
+            let points = tri.map(|i| self.vertices[i as usize]);
+            let distances = points.map(|p| plane.distance_to(p));
+            let sides = distances.map(side);
+            if sides.iter().all(|&s| s == 0.0) {
+                continue;
+            }
+
+            let mut crossing = Vec::with_capacity(3);
+            for i in 0..3 {
+                let j = (i + 1) % 3;
+                if sides[i] == 0.0 {
+                    crossing.push(points[i]);
+                } else if sides[i] * sides[j] < 0.0 {
+                    let t = distances[i] / (distances[i] - distances[j]);
+                    crossing.push(points[i].lerp(&points[j], t));
+                }
+            }
+            if let [a, b] = crossing[..] {
+                segments.push([a, b]);
+            }
+        }
+        segments
+    }
+}
+
+/// Cut element meshes with a plane and project the curves onto it.
+///
+/// Curves are in input order, and for each mesh in the order of
+/// [`TriangleMesh::section`].
+pub fn section_building<'a>(
+    elements_meshes: impl IntoIterator<Item = (Uuid, &'a TriangleMesh)>,
+    plane: &SectionPlane,
+) -> Vec<SectionCurve> {
+    let mut curves = Vec::new();
+    for (element_id, mesh) in elements_meshes {
+        for polyline in chain_segments(mesh.section_segments(plane)) {
+            curves.push(SectionCurve {
+                element_id,
+                points: polyline.into_iter().map(|p| plane.project(p)).collect(),
+            });
+        }
+    }
+    curves
+}
+
+/// Chain segments into polylines, merging endpoints within [`GEOM_TOL`].
+///
+/// Open polylines are traced from their ends first, then the remaining
+/// segments close into loops. Segments that collapse to a point, and
+/// repeats of a segment (such as an edge shared by two triangles lying on
+/// the plane), are dropped.
+fn chain_segments(segments: Vec<[Point3; 2]>) -> Vec<Vec<Point3>> {
+    let tolerance = GEOM_TOL * MM_TO_M;
+    let mut nodes: Vec<Point3> = Vec::new();
+    let mut grid: HashMap<[i64; 3], Vec<usize>> = HashMap::new();
+    let mut node_at = |p: Point3| -> usize {
+        let cell = [p.x, p.y, p.z].map(|c| (c / tolerance).floor() as i64);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let key = [cell[0] + dx, cell[1] + dy, cell[2] + dz];
+                    let near = grid.get(&key).into_iter().flatten();
+                    if let Some(&n) = near
+                        .into_iter()
+                        .find(|&&n| nodes[n].distance_to(&p) <= tolerance)
+                    {
+                        return n;
+                    }
+                }
+            }
+        }
+        nodes.push(p);
+        grid.entry(cell).or_default().push(nodes.len() - 1);
+        nodes.len() - 1
+    };
+
+    let mut edges: Vec<[usize; 2]> = Vec::new();
+    let mut seen = HashSet::new();
+    for [a, b] in segments {
+        let (a, b) = (node_at(a), node_at(b));
+        if a != b && seen.insert((a.min(b), a.max(b))) {
+            edges.push([a, b]);
+        }
+    }
+
+    let mut incident: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for (e, &[a, b]) in edges.iter().enumerate() {
+        incident[a].push(e);
+        incident[b].push(e);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let trace = |start: usize, used: &mut [bool]| -> Option<Vec<Point3>> {
+        let mut path = vec![start];
+        let mut at = start;
+        while let Some(&e) = incident[at].iter().find(|&&e| !used[e]) {
+            used[e] = true;
+            at = if edges[e][0] == at {
+                edges[e][1]
+            } else {
+                edges[e][0]
+            };
+            path.push(at);
+            if at == start {
+                break;
+            }
+        }
+        (path.len() > 1).then(|| drop_collinear(path.into_iter().map(|n| nodes[n]).collect()))
+    };
+
+    let mut polylines = Vec::new();
+    let ends = (0..nodes.len()).filter(|&n| incident[n].len() % 2 == 1);
+    let starts: Vec<usize> = ends.chain(edges.iter().map(|e| e[0])).collect();
+    for start in starts {
+        while let Some(polyline) = trace(start, &mut used) {
+            polylines.push(polyline);
+        }
+    }
+    polylines
+}
+
+/// Drop points lying on the line between their neighbours, such as where
+/// the plane crosses the diagonal of a quad. A closed loop may also lose
+/// its start point, and then starts at the next corner instead.
+fn drop_collinear(points: Vec<Point3>) -> Vec<Point3> {
+    let between = |p: &Point3, a: Point3, b: Point3| {
+        LineSegment3::new(a, b).distance_to_point(p) < COLLINEAR_EPSILON
+    };
+
+    let mut kept: Vec<Point3> = Vec::with_capacity(points.len());
+    for (i, p) in points.iter().enumerate() {
+        match (kept.last(), points.get(i + 1)) {
+            (Some(&prev), Some(&next)) if between(p, prev, next) => {}
+            _ => kept.push(*p),
+        }
+    }
+
+    let closed = kept.len() > 4 && kept.first() == kept.last();
+    if closed && between(&kept[0], kept[kept.len() - 2], kept[1]) {
+        kept.pop();
+        kept.remove(0);
+        kept.push(kept[0]);
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::extrude_wall_with_openings;
+    use pensaer_math::Polygon2;
+
+    fn extents(points: &[Point2]) -> [f64; 4] {
+        let xs = points.iter().map(|p| p.x);
+        let ys = points.iter().map(|p| p.y);
+        [
+            xs.clone().fold(f64::INFINITY, f64::min),
+            ys.clone().fold(f64::INFINITY, f64::min),
+            xs.fold(f64::NEG_INFINITY, f64::max),
+            ys.fold(f64::NEG_INFINITY, f64::max),
+        ]
+    }
+
+    #[test]
+    fn wall_with_window_gives_outline_and_window_loops() {
+        let mesh = extrude_wall_with_openings(5.0, 3.0, 0.2, &[(1.5, 0.9, 1.2, 1.2)]).unwrap();
+        let id = Uuid::from_u128(1);
+        // Through the middle of the wall, seen from the front
+        let plane = SectionPlane::new(Point3::ORIGIN, -Vector3::UNIT_Y).unwrap();
+
+        let curves = section_building([(id, &mesh)], &plane);
+        assert_eq!(curves.len(), 2);
+        assert!(curves.iter().all(|c| c.is_closed() && c.element_id == id));
+        // Four corners each, without the points on the quads' diagonals
+        assert!(curves.iter().all(|c| c.points.len() == 5));
+
+        let mut loops: Vec<([f64; 4], f64)> = curves
+            .iter()
+            .map(|c| {
+                let ring = c.points[1..].to_vec();
+                (extents(&c.points), Polygon2::new(ring).unwrap().area())
+            })
+            .collect();
+        loops.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let expected = [([0.0, 0.0, 5.0, 3.0], 15.0), ([1.5, 0.9, 2.7, 2.1], 1.44)];
+        for ((bounds, area), (want_bounds, want_area)) in loops.iter().zip(expected) {
+            for (got, want) in bounds.iter().zip(want_bounds) {
+                assert!((got - want).abs() < 1e-9, "{bounds:?}");
+            }
+            assert!((area - want_area).abs() < 1e-9);
+        }
+
+        // The same cut in world coordinates lies in the plane
+        let loops = mesh.section(Point3::ORIGIN, Vector3::UNIT_Y);
+        assert_eq!(loops.len(), 2);
+        assert!(loops.iter().flatten().all(|p| p.y.abs() < 1e-12));
+    }
+
+    #[test]
+    fn planes_missing_or_touching_the_mesh_give_nothing() {
+        let mesh = extrude_wall_with_openings(5.0, 3.0, 0.2, &[]).unwrap();
+
+        assert!(mesh
+            .section(Point3::new(0.0, 1.0, 0.0), Vector3::UNIT_Y)
+            .is_empty());
+        assert!(mesh.section(Point3::ORIGIN, Vector3::ZERO).is_empty());
+        // Only touching a corner edge, along the end face's plane
+        assert!(mesh
+            .section(Point3::new(-1e-3, 0.0, 0.0), Vector3::UNIT_X)
+            .is_empty());
+    }
+
+    #[test]
+    fn open_mesh_gives_open_polyline() {
+        // A single quad in the z = 0 plane, cut across its diagonal
+        let quad = TriangleMesh::from_vertices_indices(
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(2.0, 0.0, 0.0),
+                Point3::new(2.0, 1.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+            ],
+            vec![[0, 1, 2], [0, 2, 3]],
+        );
+
+        let polylines = quad.section(Point3::new(1.0, 0.0, 0.0), Vector3::UNIT_X);
+        assert_eq!(polylines.len(), 1);
+        let line = &polylines[0];
+        assert_eq!(line.len(), 2);
+        assert_ne!(line.first(), line.last());
+        assert!(line.iter().all(|p| (p.x - 1.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn plane_axes_follow_the_viewer() {
+        let front = SectionPlane::new(Point3::ORIGIN, -Vector3::UNIT_Y).unwrap();
+        let p = front.project(Point3::new(2.0, 5.0, 3.0));
+        assert!((p.x - 2.0).abs() < 1e-12 && (p.y - 3.0).abs() < 1e-12);
+
+        let plan = SectionPlane::new(Point3::ORIGIN, Vector3::UNIT_Z).unwrap();
+        let p = plan.project(Point3::new(2.0, 5.0, 3.0));
+        assert!((p.x - 2.0).abs() < 1e-12 && (p.y - 5.0).abs() < 1e-12);
+    }
+}
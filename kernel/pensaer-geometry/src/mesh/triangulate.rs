@@ -3,6 +3,8 @@
 //! This module provides algorithms for converting 2D polygons into triangles:
 //! - Ear-clipping for simple polygons (O(n²) but robust)
 //! - Ear-clipping with hole bridging for polygons with holes
+//! - Monotone decomposition as a fallback when ear clipping fails
+//! - Constrained Delaunay triangulation for well-shaped triangles
 //!
 //! # Algorithm Overview
 //!
@@ -16,13 +18,32 @@
 //! ## Holes
 //! Holes are handled by creating "bridges" - connecting the outer boundary
 //! to each hole to form a single simple polygon that can be triangulated.
+//!
+//! ## Monotone Decomposition
+//! Every ear-clipped result is validated: no degenerate triangles, none
+//! outside the polygon or inside a hole, and the triangle areas summing to
+//! the polygon area. When ear clipping fails or its result does not
+//! validate (slivers from bridges passing close to other holes), a sweep
+//! splits the polygon and its holes into y-monotone pieces with diagonals
+//! and each piece is triangulated in linear time.
+//!
+//! ## Constrained Delaunay
+//! Ear clipping tends to leave long slivers fanning out from bridge
+//! vertices. [`triangulate_constrained_delaunay`] starts from the monotone
+//! triangulation, whose edges include every boundary and hole edge, and
+//! flips interior edges until each is locally Delaunay. Boundary edges are
+//! never flipped, so they stay as constraints, and the result maximises
+//! the smallest angle among triangulations that keep them.
+
+use std::collections::{HashMap, HashSet};
 
 use pensaer_math::robust_predicates::{
-    is_convex_vertex, point_in_triangle as robust_point_in_triangle,
-    segments_properly_intersect as robust_segments_intersect,
+    incircle_2d, is_convex_vertex, orientation_2d, point_in_triangle as robust_point_in_triangle,
+    segments_properly_intersect as robust_segments_intersect, CirclePosition, Orientation,
 };
 use pensaer_math::Point2;
 
+use crate::constants::GEOM_TOL;
 use crate::error::{GeometryError, GeometryResult};
 
 /// Minimum number of vertices for a valid polygon.
@@ -66,6 +87,25 @@ pub fn triangulate_polygon(vertices: &[Point2]) -> GeometryResult<Vec<[usize; 3]
         return Ok(vec![[0, 1, 2]]);
     }
 
+    match ear_clip(vertices) {
+        Ok(triangles) if is_valid_triangulation(vertices, &triangles, vertices, &[]) => {
+            Ok(triangles)
+        }
+        _ => {
+            let ring: Vec<usize> = if compute_signed_area(vertices) > 0.0 {
+                (0..n).collect()
+            } else {
+                (0..n).rev().collect()
+            };
+            triangulate_monotone(vertices, &[ring])
+        }
+    }
+}
+
+/// Ear-clip a simple polygon given in either winding.
+fn ear_clip(vertices: &[Point2]) -> GeometryResult<Vec<[usize; 3]>> {
+    let n = vertices.len();
+
     // Ensure counter-clockwise winding
     let signed_area = compute_signed_area(vertices);
     let is_ccw = signed_area > 0.0;
@@ -174,16 +214,483 @@ pub fn triangulate_polygon_with_holes(
     });
 
     // Build combined polygon by bridging holes
-    let mut combined = outer_vertices;
+    let mut combined = outer_vertices.clone();
+    let mut hole_rings = Vec::with_capacity(processed_holes.len());
 
     for (_hole_idx, hole) in processed_holes {
         combined = bridge_hole_to_polygon(&combined, &hole)?;
+        hole_rings.push(hole);
+    }
+
+    // Triangulate the bridged polygon, checking the result against the
+    // original boundaries since bridges can still cut across other holes
+    if let Ok(triangles) = ear_clip(&combined) {
+        if is_valid_triangulation(&combined, &triangles, &outer_vertices, &hole_rings) {
+            return Ok((combined, triangles));
+        }
     }
 
-    // Now triangulate the simple polygon
-    let triangles = triangulate_polygon(&combined)?;
+    // Fall back on the rings themselves, outer first, then each hole
+    let mut points = outer_vertices;
+    let mut rings = vec![(0..points.len()).collect::<Vec<_>>()];
+    for hole in &hole_rings {
+        rings.push((points.len()..points.len() + hole.len()).collect());
+        points.extend_from_slice(hole);
+    }
+    let triangles = triangulate_monotone(&points, &rings)?;
+    Ok((points, triangles))
+}
 
-    Ok((combined, triangles))
+/// How to triangulate a polygon with holes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TriangulationMethod {
+    /// Ear clipping with hole bridges; fast, but can leave slivers.
+    #[default]
+    EarClipping,
+    /// Constrained Delaunay; better-shaped triangles for shading and
+    /// analysis meshes.
+    ConstrainedDelaunay,
+}
+
+/// Triangulate a polygon with holes using the given method.
+///
+/// Returns the vertex list the triangles index, like
+/// [`triangulate_polygon_with_holes`]. The constrained Delaunay vertex list
+/// is `outer` followed by each hole, with no bridge duplicates.
+pub fn triangulate_polygon_with_holes_using(
+    outer: &[Point2],
+    holes: &[Vec<Point2>],
+    method: TriangulationMethod,
+) -> GeometryResult<(Vec<Point2>, Vec<[usize; 3]>)> {
+    match method {
+        TriangulationMethod::EarClipping => triangulate_polygon_with_holes(outer, holes),
+        TriangulationMethod::ConstrainedDelaunay => {
+            let triangles = triangulate_constrained_delaunay(outer, holes)?;
+            let mut points = outer.to_vec();
+            for hole in holes {
+                points.extend_from_slice(hole);
+            }
+            let triangles = triangles.iter().map(|t| t.map(|i| i as usize)).collect();
+            Ok((points, triangles))
+        }
+    }
+}
+
+/// Constrained Delaunay triangulation of a polygon with holes.
+///
+/// Every outer and hole edge is kept; all other edges are locally
+/// Delaunay. Either winding is accepted for the outer boundary and the
+/// holes. Triangles are counter-clockwise and index `outer` followed by
+/// each hole in order.
+///
+/// # Errors
+/// - `InsufficientVertices` if the outer boundary has < 3 vertices
+/// - `TriangulationFailed` if the boundary cannot be triangulated
+pub fn triangulate_constrained_delaunay(
+    outer: &[Point2],
+    holes: &[Vec<Point2>],
+) -> GeometryResult<Vec<[u32; 3]>> {
+    if outer.len() < MIN_POLYGON_VERTICES {
+        return Err(GeometryError::InsufficientVertices);
+    }
+
+    // Rings wound as the sweep expects, indexing the points in input order
+    let ring = |start: usize, ring: &[Point2], ccw: bool| -> Vec<usize> {
+        let indices = start..start + ring.len();
+        if (compute_signed_area(ring) > 0.0) == ccw {
+            indices.collect()
+        } else {
+            indices.rev().collect()
+        }
+    };
+    let mut points = outer.to_vec();
+    let mut rings = vec![ring(0, outer, true)];
+    for hole in holes {
+        rings.push(ring(points.len(), hole, false));
+        points.extend_from_slice(hole);
+    }
+
+    let mut triangles = triangulate_monotone(&points, &rings)?;
+    for tri in &mut triangles {
+        if orientation_2d(points[tri[0]], points[tri[1]], points[tri[2]]) == Orientation::Clockwise
+        {
+            tri.swap(1, 2);
+        }
+    }
+    flip_to_delaunay(&points, &mut triangles);
+
+    Ok(triangles.iter().map(|t| t.map(|i| i as u32)).collect())
+}
+
+/// Flip interior edges until every one is locally Delaunay (Lawson's
+/// algorithm). Edges on only one triangle are boundary constraints.
+fn flip_to_delaunay(points: &[Point2], triangles: &mut [[usize; 3]]) {
+    let key = |a: usize, b: usize| (a.min(b), a.max(b));
+    let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (t, tri) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            edges
+                .entry(key(tri[k], tri[(k + 1) % 3]))
+                .or_default()
+                .push(t);
+        }
+    }
+
+    let mut stack: Vec<(usize, usize)> = edges
+        .iter()
+        .filter(|(_, tris)| tris.len() == 2)
+        .map(|(edge, _)| *edge)
+        .collect();
+    stack.sort_unstable();
+
+    while let Some(edge) = stack.pop() {
+        let Some(&[t0, t1]) = edges.get(&edge).map(Vec::as_slice) else {
+            continue;
+        };
+        // Orient the shared edge p -> q along t0, with c opposite in t0
+        // and d opposite in t1
+        let k = (0..3)
+            .find(|&k| key(triangles[t0][k], triangles[t0][(k + 1) % 3]) == edge)
+            .expect("edge belongs to its triangle");
+        let (p, q, c) = (
+            triangles[t0][k],
+            triangles[t0][(k + 1) % 3],
+            triangles[t0][(k + 2) % 3],
+        );
+        let d = triangles[t1]
+            .into_iter()
+            .find(|&v| v != p && v != q)
+            .expect("triangle has an apex");
+
+        let [pp, pq, pc, pd] = [p, q, c, d].map(|i| points[i]);
+        if incircle_2d(pp, pq, pc, pd) != CirclePosition::Inside {
+            continue;
+        }
+        // Only a convex quad can be flipped
+        if orientation_2d(pp, pd, pc) != Orientation::CounterClockwise
+            || orientation_2d(pq, pc, pd) != Orientation::CounterClockwise
+        {
+            continue;
+        }
+
+        triangles[t0] = [p, d, c];
+        triangles[t1] = [q, c, d];
+        edges.remove(&edge);
+        edges.insert(key(c, d), vec![t0, t1]);
+        for (e, from, to) in [(key(q, c), t0, t1), (key(p, d), t1, t0)] {
+            if let Some(tris) = edges.get_mut(&e) {
+                for t in tris.iter_mut().filter(|t| **t == from) {
+                    *t = to;
+                }
+            }
+        }
+        stack.extend([key(p, d), key(d, q), key(q, c), key(c, p)]);
+    }
+}
+
+/// Check a triangulation against the boundary it should fill.
+///
+/// Rejects degenerate triangles, triangles whose centroid falls outside
+/// `outer` or inside a hole, and results whose total area differs from
+/// the polygon's by more than `GEOM_TOL²`.
+fn is_valid_triangulation(
+    points: &[Point2],
+    triangles: &[[usize; 3]],
+    outer: &[Point2],
+    holes: &[Vec<Point2>],
+) -> bool {
+    let expected = compute_signed_area(outer).abs()
+        - holes
+            .iter()
+            .map(|h| compute_signed_area(h).abs())
+            .sum::<f64>();
+    let mut total = 0.0;
+
+    for tri in triangles {
+        let [a, b, c] = tri.map(|i| points[i]);
+        let area = compute_signed_area(&[a, b, c]);
+        if area.abs() < EPSILON {
+            return false;
+        }
+        total += area.abs();
+
+        let centroid = Point2::new((a.x + b.x + c.x) / 3.0, (a.y + b.y + c.y) / 3.0);
+        if !point_in_ring(&centroid, outer) || holes.iter().any(|h| point_in_ring(&centroid, h)) {
+            return false;
+        }
+    }
+
+    (total - expected).abs() <= GEOM_TOL * GEOM_TOL
+}
+
+/// Even-odd point-in-polygon test with robust crossing decisions.
+fn point_in_ring(p: &Point2, ring: &[Point2]) -> bool {
+    let n = ring.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (ring[i], ring[(i + 1) % n]);
+        if (a.y > p.y) != (b.y > p.y) {
+            // Crossing to the right of p when p is left of the upward edge
+            let (lower, upper) = if a.y < b.y { (a, b) } else { (b, a) };
+            if orientation_2d(lower, upper, *p) == Orientation::CounterClockwise {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Sweep order: higher first, then further left for equal heights.
+fn is_above(a: Point2, b: Point2) -> bool {
+    a.y > b.y || (a.y == b.y && a.x < b.x)
+}
+
+/// Triangulate a polygon with holes by monotone decomposition.
+///
+/// `rings[0]` indexes the outer boundary counter-clockwise and every
+/// further ring a hole clockwise, so the interior is always to the left of
+/// each ring edge. A top-to-bottom sweep adds diagonals at split and merge
+/// vertices, the resulting faces are y-monotone, and each is triangulated
+/// with the usual stack walk down its two chains.
+fn triangulate_monotone(
+    points: &[Point2],
+    rings: &[Vec<usize>],
+) -> GeometryResult<Vec<[usize; 3]>> {
+    let mut next = vec![usize::MAX; points.len()];
+    let mut prev = vec![usize::MAX; points.len()];
+    let mut order = Vec::new();
+    for ring in rings.iter().filter(|r| r.len() >= MIN_POLYGON_VERTICES) {
+        for k in 0..ring.len() {
+            next[ring[k]] = ring[(k + 1) % ring.len()];
+            prev[ring[(k + 1) % ring.len()]] = ring[k];
+        }
+        order.extend_from_slice(ring);
+    }
+    if order.is_empty() {
+        return Err(GeometryError::InsufficientVertices);
+    }
+    order.sort_by(|&a, &b| {
+        if is_above(points[a], points[b]) {
+            std::cmp::Ordering::Less
+        } else if is_above(points[b], points[a]) {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    });
+
+    let diagonals = monotone_diagonals(points, &order, &next, &prev)?;
+
+    // Walk the faces left of every ring edge and both sides of each diagonal
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); points.len()];
+    for &v in &order {
+        outgoing[v].push(next[v]);
+    }
+    for &(a, b) in &diagonals {
+        outgoing[a].push(b);
+        outgoing[b].push(a);
+    }
+    let mut used: HashSet<(usize, usize)> = HashSet::new();
+    let mut triangles = Vec::new();
+    for &start in &order {
+        for &first in &outgoing[start] {
+            if used.contains(&(start, first)) {
+                continue;
+            }
+            let mut face = vec![start];
+            let (mut from, mut at) = (start, first);
+            used.insert((from, at));
+            while at != start {
+                face.push(at);
+                let incoming = points[at] - points[from];
+                let turn = |to: usize| {
+                    let out = points[to] - points[at];
+                    let angle = incoming.cross(&out).atan2(incoming.dot(&out));
+                    if to == from {
+                        // Doubling back is the last resort
+                        -std::f64::consts::PI
+                    } else {
+                        angle
+                    }
+                };
+                let to = outgoing[at]
+                    .iter()
+                    .copied()
+                    .filter(|&to| !used.contains(&(at, to)))
+                    .max_by(|&x, &y| turn(x).total_cmp(&turn(y)))
+                    .ok_or_else(|| {
+                        GeometryError::TriangulationFailed(
+                            "monotone decomposition left an open face".to_string(),
+                        )
+                    })?;
+                used.insert((at, to));
+                from = at;
+                at = to;
+            }
+            triangulate_monotone_face(points, &face, &mut triangles);
+        }
+    }
+
+    Ok(triangles)
+}
+
+/// Diagonals splitting a polygon with holes into y-monotone pieces.
+///
+/// Each vertex is classified by its neighbours: start and split vertices
+/// have both below, end and merge vertices both above, split and merge
+/// ones being reflex. The sweep status holds the edges with the interior
+/// to their right, each with a helper vertex that a diagonal can reach.
+fn monotone_diagonals(
+    points: &[Point2],
+    order: &[usize],
+    next: &[usize],
+    prev: &[usize],
+) -> GeometryResult<Vec<(usize, usize)>> {
+    let mut status: Vec<usize> = Vec::new();
+    let mut helper = vec![usize::MAX; points.len()];
+    let mut is_merge = vec![false; points.len()];
+    let merge_helper = |is_merge: &[bool], h: usize| is_merge.get(h).copied().unwrap_or(false);
+    let mut diagonals = Vec::new();
+
+    // Status edge directly left of a vertex; edges are named by their
+    // upper endpoint and run downward
+    let left_of = |status: &[usize], v: usize| {
+        let p = points[v];
+        let x_at = |e: usize| {
+            let (a, b) = (points[e], points[next[e]]);
+            if a.y == b.y {
+                a.x.max(b.x)
+            } else {
+                a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y)
+            }
+        };
+        status
+            .iter()
+            .copied()
+            .filter(|&e| {
+                orientation_2d(points[e], points[next[e]], p) == Orientation::CounterClockwise
+            })
+            .max_by(|&a, &b| x_at(a).total_cmp(&x_at(b)))
+            .ok_or_else(|| {
+                GeometryError::TriangulationFailed("no edge left of sweep vertex".to_string())
+            })
+    };
+
+    for &v in order {
+        let (p, n) = (prev[v], next[v]);
+        let prev_below = is_above(points[v], points[p]);
+        let next_below = is_above(points[v], points[n]);
+        let reflex = orientation_2d(points[p], points[v], points[n]) == Orientation::Clockwise;
+
+        if prev_below && next_below {
+            if reflex {
+                // Split: connect up to the helper of the edge on the left
+                let e = left_of(&status, v)?;
+                diagonals.push((v, helper[e]));
+                helper[e] = v;
+            }
+            status.push(v);
+            helper[v] = v;
+        } else if !prev_below && !next_below {
+            if merge_helper(&is_merge, helper[p]) {
+                diagonals.push((v, helper[p]));
+            }
+            status.retain(|&e| e != p);
+            if reflex {
+                // Merge: becomes the helper of the edge on the left
+                is_merge[v] = true;
+                let e = left_of(&status, v)?;
+                if merge_helper(&is_merge, helper[e]) {
+                    diagonals.push((v, helper[e]));
+                }
+                helper[e] = v;
+            }
+        } else if !prev_below {
+            // Interior to the right: the upper edge hands over to the lower
+            if merge_helper(&is_merge, helper[p]) {
+                diagonals.push((v, helper[p]));
+            }
+            status.retain(|&e| e != p);
+            status.push(v);
+            helper[v] = v;
+        } else {
+            let e = left_of(&status, v)?;
+            if merge_helper(&is_merge, helper[e]) {
+                diagonals.push((v, helper[e]));
+            }
+            helper[e] = v;
+        }
+    }
+
+    Ok(diagonals)
+}
+
+/// Triangulate one counter-clockwise y-monotone face.
+fn triangulate_monotone_face(points: &[Point2], face: &[usize], triangles: &mut Vec<[usize; 3]>) {
+    let n = face.len();
+    if n < MIN_POLYGON_VERTICES {
+        return;
+    }
+
+    // Face positions in sweep order
+    let position = |k: usize| points[face[k]];
+    let mut sorted: Vec<usize> = (0..n).collect();
+    sorted.sort_by(|&a, &b| {
+        is_above(position(b), position(a)).cmp(&is_above(position(a), position(b)))
+    });
+    let (top, bottom) = (sorted[0], sorted[n - 1]);
+
+    // Counter-clockwise from the top runs down the left chain
+    let mut on_left = vec![false; n];
+    let mut k = (top + 1) % n;
+    while k != bottom && k != top {
+        on_left[k] = true;
+        k = (k + 1) % n;
+    }
+
+    let mut emit = |a: usize, b: usize, c: usize| {
+        let [a, b, c] = [face[a], face[b], face[c]];
+        match orientation_2d(points[a], points[b], points[c]) {
+            Orientation::CounterClockwise => triangles.push([a, b, c]),
+            Orientation::Clockwise => triangles.push([a, c, b]),
+            Orientation::Collinear => {}
+        }
+    };
+
+    let mut stack = vec![sorted[0], sorted[1]];
+    for &u in &sorted[2..n - 1] {
+        let top_of_stack = stack[stack.len() - 1];
+        if on_left[u] != on_left[top_of_stack] {
+            // Fan to every stacked vertex across the face
+            for w in stack.windows(2) {
+                emit(u, w[0], w[1]);
+            }
+            stack = vec![top_of_stack, u];
+        } else {
+            // Cut off stacked vertices while the diagonal stays inside
+            let mut last = stack.pop().unwrap_or(u);
+            while let Some(&below) = stack.last() {
+                let turn = orientation_2d(position(below), position(last), position(u));
+                let inside = if on_left[u] {
+                    turn == Orientation::CounterClockwise
+                } else {
+                    turn == Orientation::Clockwise
+                };
+                if !inside {
+                    break;
+                }
+                emit(below, last, u);
+                last = stack.pop().unwrap_or(last);
+            }
+            stack.push(last);
+            stack.push(u);
+        }
+    }
+
+    // The bottom vertex sees everything left on the stack
+    for w in stack.windows(2) {
+        emit(bottom, w[0], w[1]);
+    }
 }
 
 /// Compute signed area of a polygon (positive = CCW, negative = CW).
@@ -591,6 +1098,97 @@ mod tests {
         }
     }
 
+    fn u_shape() -> Vec<Point2> {
+        vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(30.0, 0.0),
+            Point2::new(30.0, 20.0),
+            Point2::new(20.0, 20.0),
+            Point2::new(20.0, 8.0),
+            Point2::new(10.0, 8.0),
+            Point2::new(10.0, 20.0),
+            Point2::new(0.0, 20.0),
+        ]
+    }
+
+    fn cw_rect(x0: f64, y0: f64, x1: f64, y1: f64) -> Vec<Point2> {
+        vec![
+            Point2::new(x0, y0),
+            Point2::new(x0, y1),
+            Point2::new(x1, y1),
+            Point2::new(x1, y0),
+        ]
+    }
+
+    fn triangulated_area(points: &[Point2], triangles: &[[usize; 3]]) -> f64 {
+        triangles
+            .iter()
+            .map(|t| compute_signed_area(&[points[t[0]], points[t[1]], points[t[2]]]))
+            .sum()
+    }
+
+    #[test]
+    fn monotone_triangulates_comb() {
+        // Three downward teeth: several merge and end vertices
+        let points = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(1.5, 3.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(3.0, 0.0),
+            Point2::new(3.5, 3.0),
+            Point2::new(4.0, 0.0),
+            Point2::new(5.0, 0.0),
+            Point2::new(5.0, 5.0),
+            Point2::new(0.0, 5.0),
+        ];
+        let ring: Vec<usize> = (0..points.len()).collect();
+
+        let triangles = triangulate_monotone(&points, &[ring]).unwrap();
+
+        assert_eq!(triangles.len(), points.len() - 2);
+        assert!(is_valid_triangulation(&points, &triangles, &points, &[]));
+    }
+
+    #[test]
+    fn monotone_triangulates_u_shape_with_holes() {
+        let outer = u_shape();
+        let holes = vec![
+            cw_rect(2.0, 10.0, 8.0, 18.0),
+            cw_rect(12.0, 2.0, 18.0, 6.0),
+            cw_rect(22.0, 10.0, 28.0, 18.0),
+        ];
+        let mut points = outer.clone();
+        let mut rings = vec![(0..outer.len()).collect::<Vec<_>>()];
+        for hole in &holes {
+            rings.push((points.len()..points.len() + hole.len()).collect());
+            points.extend_from_slice(hole);
+        }
+
+        let triangles = triangulate_monotone(&points, &rings).unwrap();
+
+        assert!(is_valid_triangulation(&points, &triangles, &outer, &holes));
+        assert!((triangulated_area(&points, &triangles) - 360.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn triangulate_with_holes_falls_back_when_ear_clipping_fails() {
+        // Holes stacked in one arm share their rightmost x, which defeats
+        // the bridged ear clipper
+        let outer = u_shape();
+        let holes = vec![
+            cw_rect(22.0, 8.0, 28.0, 12.0),
+            cw_rect(22.0, 2.0, 28.0, 6.0),
+            cw_rect(22.0, 14.0, 28.0, 18.0),
+        ];
+
+        let (points, triangles) = triangulate_polygon_with_holes(&outer, &holes).unwrap();
+
+        assert!(!triangles.is_empty());
+        assert!(is_valid_triangulation(&points, &triangles, &outer, &holes));
+        assert!((triangulated_area(&points, &triangles) - 408.0).abs() < 1e-9);
+    }
+
     #[test]
     fn triangulate_insufficient_vertices() {
         let vertices = vec![Point2::new(0.0, 0.0), Point2::new(1.0, 0.0)];
@@ -598,6 +1196,94 @@ mod tests {
         assert!(matches!(result, Err(GeometryError::InsufficientVertices)));
     }
 
+    /// Smallest interior angle over all triangles, in degrees.
+    fn min_angle(points: &[Point2], triangles: &[[usize; 3]]) -> f64 {
+        let mut min = f64::INFINITY;
+        for tri in triangles {
+            for k in 0..3 {
+                let [a, b, c] = [tri[k], tri[(k + 1) % 3], tri[(k + 2) % 3]].map(|i| points[i]);
+                let (u, v) = (b - a, c - a);
+                min = min.min(u.cross(&v).abs().atan2(u.dot(&v)).to_degrees());
+            }
+        }
+        min
+    }
+
+    #[test]
+    fn constrained_delaunay_beats_ear_clipping_on_holed_floor() {
+        // A 20 x 10 slab with a row of shafts through it
+        let outer = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(20.0, 0.0),
+            Point2::new(20.0, 10.0),
+            Point2::new(0.0, 10.0),
+        ];
+        let holes: Vec<Vec<Point2>> = (0..6)
+            .map(|i| {
+                let x = 2.0 + 3.0 * i as f64;
+                vec![
+                    Point2::new(x, 4.5),
+                    Point2::new(x, 5.5),
+                    Point2::new(x + 1.0, 5.5),
+                    Point2::new(x + 1.0, 4.5),
+                ]
+            })
+            .collect();
+
+        let (ear_points, ear) =
+            triangulate_polygon_with_holes_using(&outer, &holes, TriangulationMethod::EarClipping)
+                .unwrap();
+        let (points, cdt) = triangulate_polygon_with_holes_using(
+            &outer,
+            &holes,
+            TriangulationMethod::ConstrainedDelaunay,
+        )
+        .unwrap();
+
+        assert_eq!(points.len(), 4 + 6 * 4);
+        assert!(is_valid_triangulation(&points, &cdt, &outer, &holes));
+        assert!(min_angle(&points, &cdt) > min_angle(&ear_points, &ear));
+
+        // Every boundary and hole edge survives as a constraint
+        let edges: HashSet<(usize, usize)> = cdt
+            .iter()
+            .flat_map(|t| (0..3).map(move |k| (t[k].min(t[(k + 1) % 3]), t[k].max(t[(k + 1) % 3]))))
+            .collect();
+        let mut start = 0;
+        for ring in std::iter::once(&outer).chain(&holes) {
+            for k in 0..ring.len() {
+                let (a, b) = (start + k, start + (k + 1) % ring.len());
+                assert!(edges.contains(&(a.min(b), a.max(b))));
+            }
+            start += ring.len();
+        }
+    }
+
+    #[test]
+    fn constrained_delaunay_on_concave_polygon() {
+        // L-shape wound clockwise: the output is still counter-clockwise
+        let outer = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(0.0, 10.0),
+            Point2::new(4.0, 10.0),
+            Point2::new(4.0, 4.0),
+            Point2::new(10.0, 4.0),
+            Point2::new(10.0, 0.0),
+        ];
+        let triangles = triangulate_constrained_delaunay(&outer, &[]).unwrap();
+
+        assert_eq!(triangles.len(), 4);
+        let triangles: Vec<[usize; 3]> = triangles.iter().map(|t| t.map(|i| i as usize)).collect();
+        assert!(is_valid_triangulation(&outer, &triangles, &outer, &[]));
+        for t in &triangles {
+            assert!(compute_signed_area(&t.map(|i| outer[i])) > 0.0);
+        }
+        assert!(matches!(
+            triangulate_constrained_delaunay(&outer[..2], &[]),
+            Err(GeometryError::InsufficientVertices)
+        ));
+    }
+
     #[test]
     fn signed_area_ccw() {
         // CCW triangle
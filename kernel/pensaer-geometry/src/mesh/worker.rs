@@ -0,0 +1,500 @@
+//! Background mesh generation.
+//!
+//! [`MeshWorker`] owns a pool of threads that mesh element snapshots in
+//! priority order, so meshes for the area around the viewport are ready
+//! before the user pans to it. The worker never touches live model data:
+//! callers enqueue [`ElementSnapshot`]s, which carry the host wall and level
+//! elevation, so results match [`Building::element_mesh`]. The mutation path
+//! only takes the queue lock briefly.
+//!
+//! Each queued job gets a generation that is never reused. Cancelling or
+//! invalidating an element replaces its current generation, so a job
+//! already in flight for the old data is discarded instead of producing a
+//! stale mesh.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+
+use uuid::Uuid;
+
+use crate::elements::{Building, ElementSnapshot};
+use crate::error::{GeometryError, GeometryResult};
+
+use super::TriangleMesh;
+
+/// Priority given to invalidated elements, ahead of all caller priorities.
+pub const INVALIDATED_PRIORITY: f64 = f64::NEG_INFINITY;
+
+/// A queued meshing job. Its `seq` doubles as the job's generation.
+struct Job {
+    priority: f64,
+    seq: u64,
+    id: Uuid,
+    snapshot: ElementSnapshot,
+}
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Job {}
+
+impl PartialOrd for Job {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Job {
+    /// Lower priority values and earlier jobs come out of the heap first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .total_cmp(&self.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+#[derive(Default)]
+struct State {
+    queue: BinaryHeap<Job>,
+    /// Current generation of each element with a queued or in-flight job.
+    generations: HashMap<Uuid, u64>,
+    ready: Vec<(Uuid, Arc<TriangleMesh>)>,
+    failed: Vec<(Uuid, GeometryError)>,
+    in_flight: usize,
+    next_seq: u64,
+    shutdown: bool,
+}
+
+impl State {
+    /// Drop unpolled results for an element.
+    fn discard_results(&mut self, id: Uuid) {
+        self.ready.retain(|(ready_id, _)| *ready_id != id);
+        self.failed.retain(|(failed_id, _)| *failed_id != id);
+    }
+
+    /// Queue a job, superseding any earlier job and result for the element.
+    fn push(&mut self, snapshot: ElementSnapshot, priority: f64) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        let id = snapshot.id();
+        self.discard_results(id);
+        self.generations.insert(id, seq);
+        self.queue.push(Job {
+            priority,
+            seq,
+            id,
+            snapshot,
+        });
+    }
+
+    /// Forget an element: its queued and in-flight jobs become stale.
+    fn cancel(&mut self, id: Uuid) {
+        self.generations.remove(&id);
+        self.discard_results(id);
+    }
+
+    fn is_current(&self, job: &Job) -> bool {
+        self.generations.get(&job.id) == Some(&job.seq)
+    }
+
+    /// Record a finished job, if it is still current.
+    fn finish(&mut self, job: &Job, result: GeometryResult<TriangleMesh>) {
+        self.in_flight -= 1;
+        if !self.is_current(job) {
+            return;
+        }
+        self.generations.remove(&job.id);
+        match result {
+            Ok(mesh) => self.ready.push((job.id, Arc::new(mesh))),
+            Err(e) => self.failed.push((job.id, e)),
+        }
+    }
+}
+
+struct Shared {
+    state: Mutex<State>,
+    work_ready: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Background mesh generator with a priority queue.
+///
+/// Lower priority values are meshed first (e.g., distance from the
+/// viewport center). Dropping the worker shuts its threads down.
+pub struct MeshWorker {
+    shared: Arc<Shared>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl MeshWorker {
+    /// Start a worker with `threads` meshing threads (at least one).
+    pub fn new(threads: usize) -> Self {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State::default()),
+            work_ready: Condvar::new(),
+        });
+
+        let threads = (0..threads.max(1))
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                std::thread::spawn(move || run(&shared))
+            })
+            .collect();
+
+        Self { shared, threads }
+    }
+
+    /// Queue element snapshots with their priorities.
+    ///
+    /// Re-enqueuing an element supersedes any earlier job for it.
+    pub fn enqueue(&self, jobs: impl IntoIterator<Item = (ElementSnapshot, f64)>) {
+        let mut state = self.shared.lock();
+        for (snapshot, priority) in jobs {
+            state.push(snapshot, priority);
+        }
+        drop(state);
+        self.shared.work_ready.notify_all();
+    }
+
+    /// Snapshot elements from a building and queue them.
+    ///
+    /// Unknown IDs are skipped. Returns the number of jobs queued.
+    pub fn enqueue_from(&self, building: &Building, ids_with_priority: &[(Uuid, f64)]) -> usize {
+        let jobs: Vec<(ElementSnapshot, f64)> = ids_with_priority
+            .iter()
+            .filter_map(|&(id, priority)| Some((building.element_snapshot(id)?, priority)))
+            .collect();
+        let count = jobs.len();
+        self.enqueue(jobs);
+        count
+    }
+
+    /// Re-mesh a changed element ahead of all other queued work.
+    ///
+    /// Any queued, in-flight, or unpolled mesh for the old data is discarded.
+    pub fn invalidate(&self, snapshot: ElementSnapshot) {
+        self.enqueue([(snapshot, INVALIDATED_PRIORITY)]);
+    }
+
+    /// Re-snapshot changed elements from a building and re-mesh them ahead
+    /// of all other queued work.
+    ///
+    /// IDs no longer in the building are cancelled. Returns the number of
+    /// elements re-queued.
+    pub fn invalidate_from(&self, building: &Building, ids: &[Uuid]) -> usize {
+        let mut jobs = Vec::new();
+        let mut removed = Vec::new();
+        for &id in ids {
+            match building.element_snapshot(id) {
+                Some(snapshot) => jobs.push((snapshot, INVALIDATED_PRIORITY)),
+                None => removed.push(id),
+            }
+        }
+        self.cancel(&removed);
+
+        let count = jobs.len();
+        self.enqueue(jobs);
+        count
+    }
+
+    /// Cancel queued and in-flight jobs for these elements.
+    pub fn cancel(&self, ids: &[Uuid]) {
+        let mut state = self.shared.lock();
+        for &id in ids {
+            state.cancel(id);
+        }
+        let State {
+            queue, generations, ..
+        } = &mut *state;
+        queue.retain(|job| generations.get(&job.id) == Some(&job.seq));
+    }
+
+    /// Take all meshes finished since the last poll.
+    pub fn poll_ready(&self) -> Vec<(Uuid, Arc<TriangleMesh>)> {
+        std::mem::take(&mut self.shared.lock().ready)
+    }
+
+    /// Take all meshing errors since the last poll, including panics.
+    pub fn poll_failed(&self) -> Vec<(Uuid, GeometryError)> {
+        std::mem::take(&mut self.shared.lock().failed)
+    }
+
+    /// Number of jobs queued or being meshed.
+    pub fn pending(&self) -> usize {
+        let state = self.shared.lock();
+        state.queue.len() + state.in_flight
+    }
+
+    /// Whether all queued work has finished.
+    pub fn is_idle(&self) -> bool {
+        self.pending() == 0
+    }
+
+    /// Stop the threads, dropping queued work, and wait for them to exit.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.work_ready.notify_all();
+        for handle in self.threads.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MeshWorker {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Mesh a snapshot, turning a panic into an error.
+fn mesh_job(job: &Job) -> GeometryResult<TriangleMesh> {
+    panic::catch_unwind(AssertUnwindSafe(|| job.snapshot.to_mesh())).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(GeometryError::MeshingPanicked(message))
+    })
+}
+
+/// Worker thread loop.
+fn run(shared: &Shared) {
+    loop {
+        let job = {
+            let mut state = shared.lock();
+            loop {
+                if state.shutdown {
+                    return;
+                }
+                match state.queue.pop() {
+                    Some(job) if state.is_current(&job) => {
+                        state.in_flight += 1;
+                        break job;
+                    }
+                    Some(_) => continue,
+                    None => {
+                        state = shared
+                            .work_ready
+                            .wait(state)
+                            .unwrap_or_else(|e| e.into_inner());
+                    }
+                }
+            }
+        };
+
+        let result = mesh_job(&job);
+        shared.lock().finish(&job, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::{Element, ElementMetadata, ElementType, Level};
+    use crate::elements::{Door, OpeningType, Wall, WallOpening};
+    use pensaer_math::{BoundingBox3, Point2};
+    use std::time::{Duration, Instant};
+
+    fn wall(i: usize, height: f64) -> Wall {
+        let x = (i % 40) as f64 * 6.0;
+        let y = (i / 40) as f64 * 6.0;
+        Wall::new(Point2::new(x, y), Point2::new(x + 5.0, y), height, 0.2).unwrap()
+    }
+
+    fn snapshot(wall: &Wall) -> ElementSnapshot {
+        ElementSnapshot::new(Arc::new(wall.clone()))
+    }
+
+    /// Element whose meshing always panics.
+    struct Exploding(ElementMetadata);
+
+    impl Element for Exploding {
+        fn id(&self) -> Uuid {
+            Uuid::nil()
+        }
+
+        fn metadata(&self) -> &ElementMetadata {
+            &self.0
+        }
+
+        fn element_type(&self) -> ElementType {
+            ElementType::Wall
+        }
+
+        fn bounding_box(&self) -> GeometryResult<BoundingBox3> {
+            Err(GeometryError::InsufficientVertices)
+        }
+
+        fn to_mesh(&self) -> GeometryResult<TriangleMesh> {
+            panic!("boom")
+        }
+    }
+
+    /// Poll until idle, collecting results in completion order.
+    fn drain(worker: &MeshWorker) -> Vec<(Uuid, Arc<TriangleMesh>)> {
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let mut results = Vec::new();
+        while !worker.is_idle() {
+            assert!(Instant::now() < deadline, "worker did not finish");
+            results.extend(worker.poll_ready());
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        results.extend(worker.poll_ready());
+        results
+    }
+
+    #[test]
+    fn meshes_match_building_element_meshes() {
+        let mut building = Building::new("Test");
+        let upper = building.add_level(Level::new("Upper", 3.0, 3.0).unwrap());
+        let mut ids = Vec::new();
+        for i in 0..1000 {
+            let mut host = wall(i, 3.0);
+            host.level_id = Some(upper);
+            if i % 10 == 0 {
+                let mut door = Door::new(host.id, 0.9, 2.1, 2.0).unwrap();
+                door.level_id = Some(upper);
+                let mut opening = WallOpening::new(2.0, 0.0, 0.9, 2.1, OpeningType::Door);
+                opening.hosted_element_id = Some(door.id);
+                host.add_opening(opening).unwrap();
+                ids.push((building.add_door(door), i as f64));
+            }
+            ids.push((building.add_wall(host), i as f64));
+        }
+
+        let worker = MeshWorker::new(4);
+        assert_eq!(worker.enqueue_from(&building, &ids), ids.len());
+        let results = drain(&worker);
+
+        assert_eq!(results.len(), ids.len());
+        assert!(worker.poll_failed().is_empty());
+        for (id, mesh) in results {
+            // Doors land in their host opening on the upper level
+            assert_eq!(*mesh, building.element_mesh(id).unwrap());
+        }
+        assert!(worker.shared.lock().generations.is_empty());
+        worker.shutdown();
+    }
+
+    #[test]
+    fn lower_priorities_complete_first() {
+        let mut building = Building::new("Test");
+        let n = 1000;
+        // Scatter priorities so enqueue order doesn't match priority order
+        let ids: Vec<(Uuid, f64)> = (0..n)
+            .map(|i| (building.add_wall(wall(i, 3.0)), ((i * 389) % n) as f64))
+            .collect();
+        let priority: HashMap<Uuid, f64> = ids.iter().copied().collect();
+
+        let worker = MeshWorker::new(2);
+        worker.enqueue_from(&building, &ids);
+        let results = drain(&worker);
+        assert_eq!(results.len(), n);
+
+        let mean = |slice: &[(Uuid, Arc<TriangleMesh>)]| {
+            slice.iter().map(|(id, _)| priority[id]).sum::<f64>() / slice.len() as f64
+        };
+        let first = mean(&results[..n / 4]);
+        let last = mean(&results[n - n / 4..]);
+        assert!(first < last, "first {} vs last {}", first, last);
+        assert!(first < n as f64 / 2.0);
+    }
+
+    #[test]
+    fn invalidated_elements_never_yield_stale_meshes() {
+        let walls: Vec<Wall> = (0..300).map(|i| wall(i, 3.0)).collect();
+
+        let worker = MeshWorker::new(4);
+        worker.enqueue(walls.iter().map(|w| (snapshot(w), 0.0)));
+
+        // Change every wall while jobs are in flight
+        for w in &walls {
+            let mut changed = w.clone();
+            changed.height = 4.0;
+            worker.invalidate(snapshot(&changed));
+        }
+
+        let results = drain(&worker);
+        assert_eq!(results.len(), 300);
+        for (_, mesh) in results {
+            let bbox = mesh.bounding_box().unwrap();
+            assert!((bbox.max.z - 4.0).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn invalidate_from_remeshes_edits_and_cancels_deletions() {
+        let mut building = Building::new("Test");
+        let ids: Vec<(Uuid, f64)> = (0..50)
+            .map(|i| (building.add_wall(wall(i, 3.0)), 0.0))
+            .collect();
+
+        let worker = MeshWorker::new(2);
+        worker.enqueue_from(&building, &ids);
+        for w in &mut building.walls {
+            w.height = 4.0;
+        }
+        let removed = building.walls.pop().unwrap().id;
+        let changed: Vec<Uuid> = ids.iter().map(|&(id, _)| id).collect();
+        assert_eq!(worker.invalidate_from(&building, &changed), 49);
+
+        let results = drain(&worker);
+        assert_eq!(results.len(), 49);
+        assert!(results.iter().all(|(id, _)| *id != removed));
+        for (_, mesh) in results {
+            assert!((mesh.bounding_box().unwrap().max.z - 4.0).abs() < 1e-10);
+        }
+        assert!(worker.shared.lock().generations.is_empty());
+    }
+
+    #[test]
+    fn cancel_drops_jobs() {
+        let walls: Vec<Wall> = (0..200).map(|i| wall(i, 3.0)).collect();
+        let ids: Vec<Uuid> = walls.iter().map(|w| w.id).collect();
+
+        let worker = MeshWorker::new(1);
+        worker.enqueue(walls.iter().map(|w| (snapshot(w), 0.0)));
+        worker.cancel(&ids);
+
+        assert!(drain(&worker).is_empty());
+        assert!(worker.shared.lock().generations.is_empty());
+    }
+
+    #[test]
+    fn panicking_element_is_reported_and_worker_drains() {
+        let walls: Vec<Wall> = (0..20).map(|i| wall(i, 3.0)).collect();
+        let exploding = ElementSnapshot::new(Arc::new(Exploding(ElementMetadata::default())));
+
+        let worker = MeshWorker::new(1);
+        worker.enqueue(
+            std::iter::once((exploding, 0.0)).chain(walls.iter().map(|w| (snapshot(w), 1.0))),
+        );
+
+        assert_eq!(drain(&worker).len(), 20);
+        let failed = worker.poll_failed();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].0, Uuid::nil());
+        assert_eq!(
+            failed[0].1,
+            GeometryError::MeshingPanicked("boom".to_string())
+        );
+        assert!(worker.shared.lock().generations.is_empty());
+    }
+}
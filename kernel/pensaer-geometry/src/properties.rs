@@ -0,0 +1,760 @@
+//! Property schemas: a controlled vocabulary for element metadata.
+//!
+//! Element metadata is a free-form string map, so the same property drifts
+//! into variants like `FireRating`, `fire_rating` and `Fire Rating (min)`.
+//! A [`PropertySchemaRegistry`] declares, per [`ElementType`], the canonical
+//! property keys with their value types, limits, and IFC property set
+//! targets. It can then:
+//!
+//! - Match keys ignoring case, spaces and punctuation, plus declared
+//!   aliases, and rewrite them to the canonical key ([`KeyRewrite`])
+//! - Validate metadata, flagging unknown keys (warnings), and wrong-typed
+//!   values and missing required properties (errors)
+//! - Guard writes: [`PropertyMode::Strict`] rejects keys the schema does not
+//!   declare, [`PropertyMode::Lenient`] accepts them
+//! - List properties in schema order, for schedules and IFC property sets
+//!
+//! Schemas load from office-standard JSON:
+//!
+//! ```
+//! use pensaer_geometry::properties::PropertySchemaRegistry;
+//! use pensaer_geometry::{ElementMetadata, ElementType};
+//!
+//! let registry = PropertySchemaRegistry::from_json(r#"{
+//!     "mode": "strict",
+//!     "schemas": {
+//!         "Wall": [
+//!             { "key": "FireRating", "type": "integer", "min": 0, "max": 240,
+//!               "aliases": ["Fire Rating (min)"],
+//!               "ifc": { "pset": "Pset_WallCommon" } }
+//!         ]
+//!     }
+//! }"#).unwrap();
+//!
+//! let mut metadata = ElementMetadata::new();
+//! let rewrite = registry
+//!     .set_property(ElementType::Wall, &mut metadata, "fire_rating", "60")
+//!     .unwrap();
+//! assert_eq!(rewrite.unwrap().to, "FireRating");
+//! assert_eq!(metadata.get_property("FireRating").unwrap(), "60");
+//! assert!(registry.validate(ElementType::Wall, &metadata).is_empty());
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::element::{ElementMetadata, ElementType};
+use crate::error::{GeometryError, GeometryResult};
+
+/// Type of a property's value. Values are stored as strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyValueType {
+    /// Any string.
+    Text,
+    /// A whole number.
+    Integer,
+    /// A finite decimal number.
+    Number,
+    /// `true` or `false`, in any case.
+    Boolean,
+}
+
+impl PropertyValueType {
+    /// Get a human-readable name for the value type.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PropertyValueType::Text => "text",
+            PropertyValueType::Integer => "integer",
+            PropertyValueType::Number => "number",
+            PropertyValueType::Boolean => "boolean",
+        }
+    }
+
+    /// Numeric value of a string, if this type is numeric and it parses.
+    fn numeric(&self, value: &str) -> Option<f64> {
+        let value = value.trim();
+        match self {
+            PropertyValueType::Integer => value.parse::<i64>().ok().map(|v| v as f64),
+            PropertyValueType::Number => value.parse::<f64>().ok().filter(|v| v.is_finite()),
+            _ => None,
+        }
+    }
+
+    /// Check whether a string is a valid value of this type.
+    pub fn accepts(&self, value: &str) -> bool {
+        match self {
+            PropertyValueType::Text => true,
+            PropertyValueType::Integer | PropertyValueType::Number => self.numeric(value).is_some(),
+            PropertyValueType::Boolean => {
+                let value = value.trim();
+                value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false")
+            }
+        }
+    }
+}
+
+/// Where a property goes in IFC export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IfcPropertyTarget {
+    /// Property set name, e.g. `Pset_WallCommon`.
+    pub pset: String,
+    /// Property name in the set; the canonical key if not given.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// A declared property of an element type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropertyDefinition {
+    /// Canonical key.
+    pub key: String,
+    /// Value type.
+    #[serde(rename = "type")]
+    pub value_type: PropertyValueType,
+    /// Whether every element of the type must have the property.
+    #[serde(default)]
+    pub required: bool,
+    /// Allowed values; any value of the type if empty.
+    #[serde(default)]
+    pub allowed_values: Vec<String>,
+    /// Smallest allowed numeric value.
+    #[serde(default)]
+    pub min: Option<f64>,
+    /// Largest allowed numeric value.
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Other spellings rewritten to the canonical key.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    /// IFC property set mapping.
+    #[serde(default)]
+    pub ifc: Option<IfcPropertyTarget>,
+}
+
+impl PropertyDefinition {
+    /// Declare an optional property with no limits.
+    pub fn new(key: impl Into<String>, value_type: PropertyValueType) -> Self {
+        Self {
+            key: key.into(),
+            value_type,
+            required: false,
+            allowed_values: Vec::new(),
+            min: None,
+            max: None,
+            aliases: Vec::new(),
+            ifc: None,
+        }
+    }
+
+    /// Check a value, returning the finding it raises, if any.
+    fn check(&self, value: &str) -> Option<PropertyFinding> {
+        if !self.value_type.accepts(value) {
+            return Some(PropertyFinding::WrongType {
+                key: self.key.clone(),
+                expected: self.value_type,
+                value: value.to_string(),
+            });
+        }
+        if !self.allowed_values.is_empty() && !self.allowed_values.iter().any(|v| v == value) {
+            return Some(PropertyFinding::NotAllowed {
+                key: self.key.clone(),
+                value: value.to_string(),
+            });
+        }
+        if let Some(number) = self.value_type.numeric(value) {
+            let below = self.min.is_some_and(|min| number < min);
+            let above = self.max.is_some_and(|max| number > max);
+            if below || above {
+                return Some(PropertyFinding::OutOfRange {
+                    key: self.key.clone(),
+                    value: number,
+                    min: self.min,
+                    max: self.max,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// Whether writes may use keys the schema does not declare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyMode {
+    /// Accept undeclared keys; validation reports them as warnings.
+    #[default]
+    Lenient,
+    /// Reject undeclared keys and invalid values.
+    Strict,
+}
+
+/// A key rewritten to its canonical spelling.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRewrite {
+    /// Key as written.
+    pub from: String,
+    /// Canonical key.
+    pub to: String,
+}
+
+/// How serious a property finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSeverity {
+    /// Worth reviewing, but the data is usable.
+    Warning,
+    /// The data breaks the schema.
+    Error,
+}
+
+/// Metadata that does not conform to a property schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PropertyFinding {
+    /// The key is not declared for the element type.
+    UnknownKey {
+        /// Key as written.
+        key: String,
+    },
+    /// The value does not parse as the declared type.
+    WrongType {
+        /// Canonical key.
+        key: String,
+        /// Declared type.
+        expected: PropertyValueType,
+        /// Value found.
+        value: String,
+    },
+    /// The value is not one of the allowed values.
+    NotAllowed {
+        /// Canonical key.
+        key: String,
+        /// Value found.
+        value: String,
+    },
+    /// The numeric value is outside the declared range.
+    OutOfRange {
+        /// Canonical key.
+        key: String,
+        /// Value found.
+        value: f64,
+        /// Declared minimum.
+        min: Option<f64>,
+        /// Declared maximum.
+        max: Option<f64>,
+    },
+    /// A required property is missing.
+    MissingRequired {
+        /// Canonical key.
+        key: String,
+    },
+}
+
+impl PropertyFinding {
+    /// Key the finding is about.
+    pub fn key(&self) -> &str {
+        match self {
+            PropertyFinding::UnknownKey { key }
+            | PropertyFinding::WrongType { key, .. }
+            | PropertyFinding::NotAllowed { key, .. }
+            | PropertyFinding::OutOfRange { key, .. }
+            | PropertyFinding::MissingRequired { key } => key,
+        }
+    }
+
+    /// Unknown keys are warnings; everything else is an error.
+    pub fn severity(&self) -> FindingSeverity {
+        match self {
+            PropertyFinding::UnknownKey { .. } => FindingSeverity::Warning,
+            _ => FindingSeverity::Error,
+        }
+    }
+}
+
+/// Property schemas for each element type.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PropertySchemaRegistry {
+    /// How [`set_property`](Self::set_property) treats undeclared keys.
+    #[serde(default)]
+    pub mode: PropertyMode,
+    /// Declared properties per element type, in schedule and export order.
+    #[serde(default)]
+    schemas: HashMap<ElementType, Vec<PropertyDefinition>>,
+}
+
+impl PropertySchemaRegistry {
+    /// Create an empty, lenient registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a registry from JSON.
+    ///
+    /// The document has an optional `mode` (`"lenient"` or `"strict"`) and a
+    /// `schemas` object mapping element type names to property definitions.
+    pub fn from_json(json: &str) -> GeometryResult<Self> {
+        let value = serde_json::from_str(json)
+            .map_err(|e| GeometryError::InvalidPropertySchema(e.to_string()))?;
+        Self::from_value(value)
+    }
+
+    /// Load a registry from a parsed JSON value.
+    pub fn from_value(value: serde_json::Value) -> GeometryResult<Self> {
+        let registry: Self = serde_json::from_value(value)
+            .map_err(|e| GeometryError::InvalidPropertySchema(e.to_string()))?;
+        for (element_type, definitions) in &registry.schemas {
+            check_schema(*element_type, definitions)?;
+        }
+        Ok(registry)
+    }
+
+    /// Declare the properties of an element type, replacing any schema it had.
+    pub fn set_schema(
+        &mut self,
+        element_type: ElementType,
+        definitions: Vec<PropertyDefinition>,
+    ) -> GeometryResult<()> {
+        check_schema(element_type, &definitions)?;
+        self.schemas.insert(element_type, definitions);
+        Ok(())
+    }
+
+    /// Declared properties of an element type, in order.
+    pub fn schema(&self, element_type: ElementType) -> &[PropertyDefinition] {
+        self.schemas
+            .get(&element_type)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Find the definition a key refers to, by canonical key or alias.
+    ///
+    /// Case, spaces and punctuation are ignored.
+    pub fn definition(&self, element_type: ElementType, key: &str) -> Option<&PropertyDefinition> {
+        let wanted = normalize_key(key);
+        self.schema(element_type).iter().find(|definition| {
+            std::iter::once(&definition.key)
+                .chain(&definition.aliases)
+                .any(|k| normalize_key(k) == wanted)
+        })
+    }
+
+    /// Canonical spelling of a key, if the schema declares it.
+    pub fn canonical_key(&self, element_type: ElementType, key: &str) -> Option<&str> {
+        self.definition(element_type, key)
+            .map(|definition| definition.key.as_str())
+    }
+
+    /// Rewrite metadata keys to their canonical spelling.
+    ///
+    /// When several keys map to the same canonical key, a value already
+    /// under the canonical key wins, then the value of the first key in
+    /// sorted order. Returns the rewrites made, sorted by original key.
+    pub fn normalize(
+        &self,
+        element_type: ElementType,
+        metadata: &mut ElementMetadata,
+    ) -> Vec<KeyRewrite> {
+        let mut keys: Vec<String> = metadata.properties.keys().cloned().collect();
+        keys.sort();
+
+        let mut rewrites = Vec::new();
+        for key in keys {
+            let Some(canonical) = self.canonical_key(element_type, &key) else {
+                continue;
+            };
+            if key == canonical {
+                continue;
+            }
+            if let Some(value) = metadata.properties.remove(&key) {
+                metadata
+                    .properties
+                    .entry(canonical.to_string())
+                    .or_insert(value);
+            }
+            rewrites.push(KeyRewrite {
+                from: key,
+                to: canonical.to_string(),
+            });
+        }
+        rewrites
+    }
+
+    /// Set a property through the schema.
+    ///
+    /// A declared key is stored under its canonical spelling, and the
+    /// rewrite is returned. In strict mode undeclared keys and values that
+    /// fail their definition are rejected with
+    /// [`GeometryError::PropertyRejected`]; in lenient mode they are stored
+    /// as given.
+    pub fn set_property(
+        &self,
+        element_type: ElementType,
+        metadata: &mut ElementMetadata,
+        key: &str,
+        value: impl Into<String>,
+    ) -> GeometryResult<Option<KeyRewrite>> {
+        let value = value.into();
+        let Some(definition) = self.definition(element_type, key) else {
+            if self.mode == PropertyMode::Strict {
+                return Err(GeometryError::PropertyRejected(format!(
+                    "{} is not a declared {} property",
+                    key,
+                    element_type.name()
+                )));
+            }
+            metadata.set_property(key, value);
+            return Ok(None);
+        };
+
+        if self.mode == PropertyMode::Strict {
+            if let Some(finding) = definition.check(&value) {
+                return Err(GeometryError::PropertyRejected(describe(&finding)));
+            }
+        }
+
+        // Drop other spellings so only the canonical entry remains
+        let canonical = definition.key.as_str();
+        metadata
+            .properties
+            .retain(|k, _| self.canonical_key(element_type, k) != Some(canonical));
+        metadata.set_property(canonical, value);
+
+        Ok((key != canonical).then(|| KeyRewrite {
+            from: key.to_string(),
+            to: canonical.to_string(),
+        }))
+    }
+
+    /// Check metadata against the element type's schema.
+    ///
+    /// Declared properties are checked in schema order, followed by unknown
+    /// keys in sorted order. Keys are matched like
+    /// [`definition`](Self::definition), so unnormalized spellings are
+    /// checked rather than reported as unknown.
+    pub fn validate(
+        &self,
+        element_type: ElementType,
+        metadata: &ElementMetadata,
+    ) -> Vec<PropertyFinding> {
+        let mut values: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut unknown = Vec::new();
+        for (key, value) in &metadata.properties {
+            match self.canonical_key(element_type, key) {
+                Some(canonical) => values.entry(canonical).or_default().push(value),
+                None => unknown.push(key.clone()),
+            }
+        }
+
+        let mut findings = Vec::new();
+        for definition in self.schema(element_type) {
+            match values.get(definition.key.as_str()) {
+                None if definition.required => findings.push(PropertyFinding::MissingRequired {
+                    key: definition.key.clone(),
+                }),
+                None => {}
+                Some(found) => {
+                    let mut found = found.clone();
+                    found.sort_unstable();
+                    findings.extend(found.into_iter().filter_map(|v| definition.check(v)));
+                }
+            }
+        }
+
+        unknown.sort();
+        findings.extend(
+            unknown
+                .into_iter()
+                .map(|key| PropertyFinding::UnknownKey { key }),
+        );
+        findings
+    }
+
+    /// Metadata properties in schema order, then undeclared keys sorted.
+    ///
+    /// Schedules list property columns in this order.
+    pub fn ordered_properties<'a>(
+        &self,
+        element_type: ElementType,
+        metadata: &'a ElementMetadata,
+    ) -> Vec<(&'a str, &'a str)> {
+        let mut declared: BTreeMap<usize, Vec<(&'a str, &'a str)>> = BTreeMap::new();
+        let mut undeclared = Vec::new();
+        let schema = self.schema(element_type);
+        for (key, value) in &metadata.properties {
+            let position = self
+                .canonical_key(element_type, key)
+                .and_then(|canonical| schema.iter().position(|d| d.key == canonical));
+            match position {
+                Some(index) => declared
+                    .entry(index)
+                    .or_default()
+                    .push((key.as_str(), value.as_str())),
+                None => undeclared.push((key.as_str(), value.as_str())),
+            }
+        }
+
+        undeclared.sort_unstable();
+        declared
+            .into_values()
+            .flat_map(|mut entries| {
+                entries.sort_unstable();
+                entries
+            })
+            .chain(undeclared)
+            .collect()
+    }
+
+    /// Group declared properties into IFC property sets.
+    ///
+    /// Sets appear in the order their first property is declared, and
+    /// properties in schema order. Properties without an IFC target, and
+    /// keys the schema does not declare, are left out.
+    pub fn ifc_property_sets(
+        &self,
+        element_type: ElementType,
+        metadata: &ElementMetadata,
+    ) -> Vec<(String, Vec<(String, String)>)> {
+        let mut sets: Vec<(String, Vec<(String, String)>)> = Vec::new();
+        for (key, value) in self.ordered_properties(element_type, metadata) {
+            let Some(definition) = self.definition(element_type, key) else {
+                continue;
+            };
+            let Some(target) = &definition.ifc else {
+                continue;
+            };
+            let name = target
+                .name
+                .clone()
+                .unwrap_or_else(|| definition.key.clone());
+            let property = (name, value.to_string());
+            match sets.iter_mut().find(|(pset, _)| *pset == target.pset) {
+                Some((_, properties)) => properties.push(property),
+                None => sets.push((target.pset.clone(), vec![property])),
+            }
+        }
+        sets
+    }
+}
+
+/// Key with case, spaces and punctuation removed, for matching.
+pub fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Reject schemas whose keys collide after normalization or whose ranges
+/// are empty.
+fn check_schema(
+    element_type: ElementType,
+    definitions: &[PropertyDefinition],
+) -> GeometryResult<()> {
+    let mut seen = HashSet::new();
+    for definition in definitions {
+        for key in std::iter::once(&definition.key).chain(&definition.aliases) {
+            let normalized = normalize_key(key);
+            if normalized.is_empty() || !seen.insert(normalized) {
+                return Err(GeometryError::InvalidPropertySchema(format!(
+                    "{} key {:?} is empty or declared twice",
+                    element_type.name(),
+                    key
+                )));
+            }
+        }
+        if let (Some(min), Some(max)) = (definition.min, definition.max) {
+            if min > max {
+                return Err(GeometryError::InvalidPropertySchema(format!(
+                    "{}.{} has min {} above max {}",
+                    element_type.name(),
+                    definition.key,
+                    min,
+                    max
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn describe(finding: &PropertyFinding) -> String {
+    match finding {
+        PropertyFinding::UnknownKey { key } => format!("{} is not declared", key),
+        PropertyFinding::WrongType {
+            key,
+            expected,
+            value,
+        } => format!("{} must be {}, got {:?}", key, expected.name(), value),
+        PropertyFinding::NotAllowed { key, value } => {
+            format!("{:?} is not an allowed value of {}", value, key)
+        }
+        PropertyFinding::OutOfRange {
+            key,
+            value,
+            min,
+            max,
+        } => format!(
+            "{} = {} is outside {}..{}",
+            key,
+            value,
+            min.map(|v| v.to_string()).unwrap_or_default(),
+            max.map(|v| v.to_string()).unwrap_or_default()
+        ),
+        PropertyFinding::MissingRequired { key } => format!("{} is required", key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OFFICE_STANDARD: &str = r#"{
+        "schemas": {
+            "Wall": [
+                { "key": "FireRating", "type": "integer", "min": 0, "max": 240,
+                  "aliases": ["Fire Rating (min)"],
+                  "ifc": { "pset": "Pset_WallCommon" } },
+                { "key": "IsExternal", "type": "boolean", "required": true,
+                  "ifc": { "pset": "Pset_WallCommon" } },
+                { "key": "AcousticRating", "type": "number",
+                  "ifc": { "pset": "Pset_WallCommon", "name": "AcousticRating" } },
+                { "key": "Finish", "type": "text", "allowed_values": ["paint", "tile"] }
+            ]
+        }
+    }"#;
+
+    fn registry() -> PropertySchemaRegistry {
+        PropertySchemaRegistry::from_json(OFFICE_STANDARD).unwrap()
+    }
+
+    #[test]
+    fn mixed_case_keys_normalize_to_one_entry() {
+        let registry = registry();
+        let mut metadata = ElementMetadata::new();
+        metadata.set_property("fire_rating", "60");
+        metadata.set_property("Fire Rating (min)", "90");
+        metadata.set_property("FIRERATING", "30");
+        metadata.set_property("isexternal", "true");
+        metadata.set_property("Supplier", "ACME");
+
+        let rewrites = registry.normalize(ElementType::Wall, &mut metadata);
+        let from: Vec<&str> = rewrites.iter().map(|r| r.from.as_str()).collect();
+        assert_eq!(
+            from,
+            [
+                "FIRERATING",
+                "Fire Rating (min)",
+                "fire_rating",
+                "isexternal"
+            ]
+        );
+        assert_eq!(metadata.properties.len(), 3);
+        assert_eq!(metadata.get_property("FireRating").unwrap(), "30");
+        assert_eq!(metadata.get_property("IsExternal").unwrap(), "true");
+
+        // A write under another spelling replaces the canonical entry
+        let rewrite = registry
+            .set_property(ElementType::Wall, &mut metadata, "fire rating", "120")
+            .unwrap()
+            .unwrap();
+        assert_eq!(rewrite.to, "FireRating");
+        assert_eq!(metadata.properties.len(), 3);
+        assert_eq!(metadata.get_property("FireRating").unwrap(), "120");
+
+        // Schedules and IFC export follow the schema order
+        assert_eq!(
+            registry.ordered_properties(ElementType::Wall, &metadata),
+            [
+                ("FireRating", "120"),
+                ("IsExternal", "true"),
+                ("Supplier", "ACME")
+            ]
+        );
+        let psets = registry.ifc_property_sets(ElementType::Wall, &metadata);
+        assert_eq!(psets.len(), 1);
+        assert_eq!(psets[0].0, "Pset_WallCommon");
+        assert_eq!(psets[0].1[0], ("FireRating".to_string(), "120".to_string()));
+    }
+
+    #[test]
+    fn validation_reports_type_range_and_missing_findings() {
+        let registry = registry();
+        let mut metadata = ElementMetadata::new();
+        metadata.set_property("FireRating", "sixty");
+        metadata.set_property("AcousticRating", "52.5");
+        metadata.set_property("Finish", "wallpaper");
+        metadata.set_property("Supplier", "ACME");
+
+        let findings = registry.validate(ElementType::Wall, &metadata);
+        assert_eq!(
+            findings,
+            [
+                PropertyFinding::WrongType {
+                    key: "FireRating".to_string(),
+                    expected: PropertyValueType::Integer,
+                    value: "sixty".to_string(),
+                },
+                PropertyFinding::MissingRequired {
+                    key: "IsExternal".to_string()
+                },
+                PropertyFinding::NotAllowed {
+                    key: "Finish".to_string(),
+                    value: "wallpaper".to_string(),
+                },
+                PropertyFinding::UnknownKey {
+                    key: "Supplier".to_string()
+                },
+            ]
+        );
+        let severities: Vec<_> = findings.iter().map(PropertyFinding::severity).collect();
+        assert_eq!(severities[0], FindingSeverity::Error);
+        assert_eq!(severities[3], FindingSeverity::Warning);
+
+        metadata.set_property("FireRating", "300");
+        assert!(matches!(
+            registry.validate(ElementType::Wall, &metadata)[0],
+            PropertyFinding::OutOfRange { value, .. } if value == 300.0
+        ));
+
+        // Types without a schema accept anything
+        assert!(registry
+            .validate(ElementType::Floor, &metadata)
+            .iter()
+            .all(|f| f.severity() == FindingSeverity::Warning));
+    }
+
+    #[test]
+    fn strict_mode_rejects_unknown_keys() {
+        let mut registry = registry();
+        let mut metadata = ElementMetadata::new();
+
+        registry
+            .set_property(ElementType::Wall, &mut metadata, "Supplier", "ACME")
+            .unwrap();
+        assert_eq!(metadata.get_property("Supplier").unwrap(), "ACME");
+
+        registry.mode = PropertyMode::Strict;
+        let result = registry.set_property(ElementType::Wall, &mut metadata, "Colour", "red");
+        assert!(matches!(result, Err(GeometryError::PropertyRejected(_))));
+        assert!(metadata.get_property("Colour").is_none());
+
+        let result = registry.set_property(ElementType::Wall, &mut metadata, "FireRating", "1h");
+        assert!(matches!(result, Err(GeometryError::PropertyRejected(_))));
+        registry
+            .set_property(ElementType::Wall, &mut metadata, "firerating", "60")
+            .unwrap();
+        assert_eq!(metadata.get_property("FireRating").unwrap(), "60");
+
+        // Colliding declarations are rejected at load time
+        let duplicate = r#"{ "schemas": { "Wall": [
+            { "key": "FireRating", "type": "integer" },
+            { "key": "fire_rating", "type": "text" }
+        ] } }"#;
+        assert!(matches!(
+            PropertySchemaRegistry::from_json(duplicate),
+            Err(GeometryError::InvalidPropertySchema(_))
+        ));
+    }
+}
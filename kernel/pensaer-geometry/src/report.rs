@@ -0,0 +1,388 @@
+//! Tabular quantity reports (schedules) with a stable numeric output policy.
+//!
+//! Takeoff values come out of floating point arithmetic, so the same
+//! quantity can read `9.999999999999998e-5` or `1.2e6` depending on the
+//! computation path. Every schedule writer applies a [`NumberFormat`]:
+//!
+//! - Values are rounded to a per-kind number of decimals (lengths to 1 mm,
+//!   areas to 0.001 m², volumes to 0.001 m³, counts to integers by default)
+//! - Values are rendered as plain decimals, never in scientific notation
+//! - Negative zero is normalized to zero
+//! - Totals are computed from the rounded row values ("round then sum"), so
+//!   exported columns add up exactly. [`TotalsPolicy::SumThenRound`] rounds
+//!   the exact sum instead.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::error::{GeometryError, GeometryResult};
+use crate::io::to_plain_decimal_json;
+
+/// Kind of quantity in a schedule column, which selects its precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuantityKind {
+    /// Length in meters.
+    Length,
+    /// Area in square meters.
+    Area,
+    /// Volume in cubic meters.
+    Volume,
+    /// Number of items.
+    Count,
+}
+
+/// How schedule totals are computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TotalsPolicy {
+    /// Sum the rounded row values, so totals match the displayed rows.
+    #[default]
+    RoundThenSum,
+    /// Round the sum of the unrounded values.
+    SumThenRound,
+}
+
+/// Numeric output policy for schedules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NumberFormat {
+    /// Decimal places for lengths (3 = 1 mm).
+    pub length_decimals: u32,
+    /// Decimal places for areas (3 = 0.001 m²).
+    pub area_decimals: u32,
+    /// Decimal places for volumes (3 = 0.001 m³).
+    pub volume_decimals: u32,
+    /// Decimal places for counts.
+    pub count_decimals: u32,
+    /// How column totals are computed.
+    pub totals: TotalsPolicy,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            length_decimals: 3,
+            area_decimals: 3,
+            volume_decimals: 3,
+            count_decimals: 0,
+            totals: TotalsPolicy::default(),
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Decimal places used for a quantity kind.
+    pub fn decimals(&self, kind: QuantityKind) -> u32 {
+        match kind {
+            QuantityKind::Length => self.length_decimals,
+            QuantityKind::Area => self.area_decimals,
+            QuantityKind::Volume => self.volume_decimals,
+            QuantityKind::Count => self.count_decimals,
+        }
+    }
+
+    /// Round a value to the precision of its kind.
+    ///
+    /// Dividing by a power of ten yields the double nearest the decimal, so
+    /// the result prints without representation noise. Negative zero comes
+    /// back as zero.
+    pub fn round(&self, value: f64, kind: QuantityKind) -> f64 {
+        let scale = 10f64.powi(self.decimals(kind) as i32);
+        let rounded = (value * scale).round() / scale;
+        if rounded == 0.0 {
+            0.0
+        } else {
+            rounded
+        }
+    }
+
+    /// Render a value as a plain decimal with its kind's fixed precision.
+    pub fn format(&self, value: f64, kind: QuantityKind) -> String {
+        let decimals = self.decimals(kind) as usize;
+        format!("{:.*}", decimals, self.round(value, kind))
+    }
+
+    /// Total a column of values according to the totals policy.
+    pub fn total(&self, values: impl IntoIterator<Item = f64>, kind: QuantityKind) -> f64 {
+        let sum: f64 = match self.totals {
+            TotalsPolicy::RoundThenSum => values.into_iter().map(|v| self.round(v, kind)).sum(),
+            TotalsPolicy::SumThenRound => values.into_iter().sum(),
+        };
+        // Rounding again removes accumulation error from the summation itself
+        self.round(sum, kind)
+    }
+}
+
+/// A schedule column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleColumn {
+    /// Column heading.
+    pub name: String,
+    /// Quantity kind of the column's values.
+    pub kind: QuantityKind,
+}
+
+impl ScheduleColumn {
+    /// Create a column.
+    pub fn new(name: impl Into<String>, kind: QuantityKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+        }
+    }
+}
+
+/// A schedule row: a label and one unrounded value per column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleRow {
+    /// Row label, e.g. an element or level name.
+    pub label: String,
+    /// Raw values, rounded only on output.
+    pub values: Vec<f64>,
+}
+
+/// A quantity schedule exported as CSV or JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Schedule title.
+    pub title: String,
+    /// Columns after the label column.
+    pub columns: Vec<ScheduleColumn>,
+    /// Rows in output order.
+    pub rows: Vec<ScheduleRow>,
+}
+
+impl Schedule {
+    /// Create an empty schedule.
+    pub fn new(title: impl Into<String>, columns: Vec<ScheduleColumn>) -> Self {
+        Self {
+            title: title.into(),
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row. It must have one value per column.
+    pub fn add_row(&mut self, label: impl Into<String>, values: Vec<f64>) -> GeometryResult<()> {
+        if values.len() != self.columns.len() {
+            return Err(GeometryError::InvalidScheduleRow(format!(
+                "expected {} values, got {}",
+                self.columns.len(),
+                values.len()
+            )));
+        }
+        self.rows.push(ScheduleRow {
+            label: label.into(),
+            values,
+        });
+        Ok(())
+    }
+
+    /// Column totals under the format's totals policy.
+    pub fn totals(&self, format: &NumberFormat) -> Vec<f64> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| format.total(self.rows.iter().map(|r| r.values[i]), column.kind))
+            .collect()
+    }
+
+    /// Export as CSV with a header line and a trailing "Total" row.
+    pub fn to_csv(&self, format: &NumberFormat) -> String {
+        let mut lines = Vec::with_capacity(self.rows.len() + 2);
+
+        let header = std::iter::once("label")
+            .chain(self.columns.iter().map(|c| c.name.as_str()))
+            .map(csv_field)
+            .collect::<Vec<_>>();
+        lines.push(header.join(","));
+
+        let mut push_row = |label: &str, values: &[f64]| {
+            let mut fields = vec![csv_field(label)];
+            for (value, column) in values.iter().zip(&self.columns) {
+                fields.push(format.format(*value, column.kind));
+            }
+            lines.push(fields.join(","));
+        };
+        for row in &self.rows {
+            push_row(&row.label, &row.values);
+        }
+        push_row("Total", &self.totals(format));
+
+        let mut csv = lines.join("\n");
+        csv.push('\n');
+        csv
+    }
+
+    /// Export as a JSON value with rounded numbers.
+    pub fn to_json(&self, format: &NumberFormat) -> Value {
+        let round_all = |values: &[f64]| -> Vec<f64> {
+            values
+                .iter()
+                .zip(&self.columns)
+                .map(|(v, c)| format.round(*v, c.kind))
+                .collect()
+        };
+
+        json!({
+            "title": self.title,
+            "columns": self.columns,
+            "rows": self
+                .rows
+                .iter()
+                .map(|row| json!({"label": row.label, "values": round_all(&row.values)}))
+                .collect::<Vec<_>>(),
+            "totals": self.totals(format),
+            "totals_policy": format.totals,
+        })
+    }
+
+    /// Serialize to deterministic JSON with plain decimal numbers.
+    pub fn to_deterministic_json(&self, format: &NumberFormat) -> String {
+        to_plain_decimal_json(&self.to_json(format))
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote, or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sum displayed decimals exactly, in units of the last decimal place.
+    fn sum_displayed(cells: &[&str]) -> i64 {
+        cells
+            .iter()
+            .map(|c| c.replace('.', "").parse::<i64>().unwrap())
+            .sum()
+    }
+
+    fn accumulated_takeoff() -> Schedule {
+        let mut schedule = Schedule::new(
+            "Wall takeoff",
+            vec![
+                ScheduleColumn::new("length", QuantityKind::Length),
+                ScheduleColumn::new("area", QuantityKind::Area),
+                ScheduleColumn::new("count", QuantityKind::Count),
+            ],
+        );
+        // Values as they come out of summed segments
+        let length: f64 = [0.1, 0.2, 0.0001].iter().sum();
+        schedule
+            .add_row("W1", vec![length, 0.1 * 3.0 * 0.33335, 3.0])
+            .unwrap();
+        schedule
+            .add_row("W2", vec![1.0 / 3.0, 2.0 / 3.0, 2.0])
+            .unwrap();
+        schedule
+            .add_row("W3", vec![0.0004 + 0.0004, 9.999999999999998e-5, 1.0])
+            .unwrap();
+        schedule.add_row("W4", vec![-0.0001, 1.0006, 0.0]).unwrap();
+        schedule
+    }
+
+    #[test]
+    fn csv_totals_match_displayed_rows() {
+        let schedule = accumulated_takeoff();
+        let csv = schedule.to_csv(&NumberFormat::default());
+
+        let data = csv.split_once('\n').unwrap().1;
+        assert!(!data.contains('e'), "scientific notation in:\n{}", csv);
+        assert!(!csv.contains("-0.000"));
+
+        let lines: Vec<Vec<&str>> = csv
+            .lines()
+            .skip(1)
+            .map(|l| l.split(',').collect())
+            .collect();
+        let (total, rows) = lines.split_last().unwrap();
+        assert_eq!(total[0], "Total");
+        for col in 1..=3 {
+            let cells: Vec<&str> = rows.iter().map(|r| r[col]).collect();
+            assert_eq!(sum_displayed(&cells), sum_displayed(&[total[col]]));
+        }
+        assert_eq!(rows[0], ["W1", "0.300", "0.100", "3"]);
+        assert_eq!(rows[3], ["W4", "0.000", "1.001", "0"]);
+    }
+
+    #[test]
+    fn sum_then_round_uses_unrounded_values() {
+        let mut schedule = Schedule::new(
+            "Trim",
+            vec![ScheduleColumn::new("length", QuantityKind::Length)],
+        );
+        for _ in 0..3 {
+            schedule.add_row("piece", vec![0.0004]).unwrap();
+        }
+
+        assert_eq!(schedule.totals(&NumberFormat::default()), vec![0.0]);
+        let format = NumberFormat {
+            totals: TotalsPolicy::SumThenRound,
+            ..Default::default()
+        };
+        assert_eq!(schedule.totals(&format), vec![0.001]);
+    }
+
+    #[test]
+    fn large_area_has_no_exponent() {
+        let mut schedule = Schedule::new(
+            "Site",
+            vec![ScheduleColumn::new("area", QuantityKind::Area)],
+        );
+        schedule.add_row("Site area", vec![1.5e6]).unwrap();
+        schedule.add_row("Setback", vec![1.2e-7]).unwrap();
+
+        let format = NumberFormat::default();
+        let csv = schedule.to_csv(&format);
+        assert!(csv.contains("Site area,1500000.000\n"));
+        assert!(csv.contains("Total,1500000.000\n"));
+
+        let json = schedule.to_deterministic_json(&format);
+        assert!(json.contains("[1500000]"));
+        assert!(!json.contains("e6") && !json.contains("e-"));
+    }
+
+    #[test]
+    fn deterministic_json_is_sorted_and_stable() {
+        let schedule = accumulated_takeoff();
+        let format = NumberFormat::default();
+
+        let a = schedule.to_deterministic_json(&format);
+        let b = schedule.to_deterministic_json(&format);
+        assert_eq!(a, b);
+        assert!(a.find("\"columns\"").unwrap() < a.find("\"rows\"").unwrap());
+        assert!(a.contains("\"totals_policy\":\"round_then_sum\""));
+        assert!(a.contains("[0.3,0.1,3]"));
+    }
+
+    #[test]
+    fn add_row_rejects_wrong_width() {
+        let mut schedule = Schedule::new(
+            "Bad",
+            vec![ScheduleColumn::new("length", QuantityKind::Length)],
+        );
+        assert!(matches!(
+            schedule.add_row("x", vec![1.0, 2.0]),
+            Err(GeometryError::InvalidScheduleRow(_))
+        ));
+    }
+
+    #[test]
+    fn csv_quotes_labels() {
+        let mut schedule = Schedule::new(
+            "Rooms",
+            vec![ScheduleColumn::new("area", QuantityKind::Area)],
+        );
+        schedule.add_row("Office, \"north\"", vec![12.0]).unwrap();
+
+        let csv = schedule.to_csv(&NumberFormat::default());
+        assert!(csv.contains("\"Office, \"\"north\"\"\",12.000"));
+    }
+}
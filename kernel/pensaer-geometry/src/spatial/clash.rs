@@ -19,10 +19,14 @@
 //! }
 //! ```
 
-use pensaer_math::BoundingBox3;
+use pensaer_math::{BoundingBox3, Point3, Vector3};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::element::ElementType;
+use crate::elements::ContextMesh;
+use crate::mesh::TriangleMesh;
+
 /// Type of clash detected.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ClashType {
@@ -75,6 +79,9 @@ pub struct Clash {
     pub distance: f64,
     /// Volume of overlap region (for hard clashes).
     pub overlap_volume: f64,
+    /// Intersection of the two elements' bounding boxes (for hard clashes).
+    #[serde(default)]
+    pub overlap_bbox: Option<BoundingBox3>,
 }
 
 impl Clash {
@@ -98,6 +105,7 @@ impl Clash {
             clash_point,
             distance,
             overlap_volume: 0.0,
+            overlap_bbox: None,
         }
     }
 
@@ -106,6 +114,13 @@ impl Clash {
         self.overlap_volume = volume;
         self
     }
+
+    /// Set the overlap region, and the overlap volume to match it.
+    pub fn with_overlap_bbox(mut self, bbox: BoundingBox3) -> Self {
+        self.overlap_volume = bbox.volume();
+        self.overlap_bbox = Some(bbox);
+        self
+    }
 }
 
 /// Element info for clash detection (lightweight representation).
@@ -187,6 +202,15 @@ impl ClashFilter {
     }
 }
 
+/// Result of clashing elements against a context mesh.
+#[derive(Debug, Clone, Default)]
+pub struct ContextClashes {
+    /// At most one clash per element, against the context mesh's ID.
+    pub clashes: Vec<Clash>,
+    /// Indices of the chunks whose triangles were tested.
+    pub narrow_phase_chunks: Vec<usize>,
+}
+
 /// Clash detector with configurable tolerance.
 pub struct ClashDetector {
     /// Tolerance for considering overlapping bounding boxes (meters).
@@ -268,6 +292,108 @@ impl ClashDetector {
         clashes
     }
 
+    /// Detect clashes within a list of meshed elements.
+    ///
+    /// Pairs are pruned by bounding box exactly as in
+    /// [`ClashDetector::detect_clashes_in_list`], but a hard clash is only
+    /// reported if the meshes' triangles penetrate each other or one mesh
+    /// lies inside the other. The clash point is then the mean of the
+    /// triangle intersection segments. Duplicate and clearance checks still
+    /// use the bounding boxes. Empty meshes are skipped.
+    pub fn detect_clashes_meshes(&self, elements: &[(Uuid, String, &TriangleMesh)]) -> Vec<Clash> {
+        let entries: Vec<(ClashElement, &TriangleMesh)> = elements
+            .iter()
+            .filter_map(|(id, element_type, mesh)| {
+                let bbox = mesh.bounding_box()?;
+                Some((ClashElement::new(*id, element_type.as_str(), bbox), *mesh))
+            })
+            .collect();
+
+        let mut clashes = Vec::new();
+
+        for i in 0..entries.len() {
+            for j in (i + 1)..entries.len() {
+                let (a, mesh_a) = &entries[i];
+                let (b, mesh_b) = &entries[j];
+
+                // Apply filter
+                if !self.filter.should_test(a, b) {
+                    continue;
+                }
+
+                if let Some(clash) = self.check_mesh_pair(a, mesh_a, b, mesh_b) {
+                    clashes.push(clash);
+                }
+            }
+        }
+
+        clashes
+    }
+
+    /// Detect clashes between meshed elements and a context mesh.
+    ///
+    /// Each element is broad-phased against the context mesh's chunks, and
+    /// only chunks whose bounding boxes overlap it are tested triangle by
+    /// triangle, so most of a large context mesh is never touched. Each
+    /// element gets at most one clash: the first confirmed hard clash, or
+    /// else the closest clearance violation.
+    pub fn detect_clashes_with_context(
+        &self,
+        elements: &[(Uuid, String, &TriangleMesh)],
+        context: &ContextMesh,
+    ) -> ContextClashes {
+        let mut result = ContextClashes::default();
+        let context_type = ElementType::ContextMesh.name();
+        let clearance = self.filter.clearance_distance;
+
+        for (id, element_type, mesh) in elements {
+            let Some(bbox) = mesh.bounding_box() else {
+                continue;
+            };
+            let a = ClashElement::new(*id, element_type.as_str(), bbox);
+            let search = bbox.expand(clearance.max(0.0) + self.tolerance);
+
+            let mut hard = None;
+            let mut soft: Option<Clash> = None;
+            for (index, chunk) in context.chunks_in(&search) {
+                let b = ClashElement::new(context.id, context_type, chunk.bbox);
+                if !self.filter.should_test(&a, &b) {
+                    continue;
+                }
+                let Some(clash) = self.check_pair(&a, &b) else {
+                    continue;
+                };
+                if clash.clash_type != ClashType::Hard {
+                    match &soft {
+                        Some(s) if s.distance <= clash.distance => {}
+                        _ => soft = Some(clash),
+                    }
+                    continue;
+                }
+
+                result.narrow_phase_chunks.push(index);
+                let region = a
+                    .bbox
+                    .intersection(&b.bbox)
+                    .map(|r| r.expand(self.tolerance));
+                let point = region.and_then(|r| self.mesh_penetration_point(mesh, &chunk.mesh, &r));
+                if let Some(clash_point) = point {
+                    hard = Some(Clash {
+                        clash_point,
+                        ..clash
+                    });
+                    break;
+                }
+            }
+
+            if let Some(clash) = hard.or(soft) {
+                result.clashes.push(clash);
+            }
+        }
+
+        result
+    }
+
     /// Check a single pair of elements for clash.
     fn check_pair(&self, a: &ClashElement, b: &ClashElement) -> Option<Clash> {
         // Get bounding boxes
@@ -289,7 +415,8 @@ impl ClashDetector {
         }
 
         // Check for hard clash (bounding box intersection)
-        if let Some((overlap_point, overlap_volume)) = self.bbox_intersection(bbox_a, bbox_b) {
+        if let Some(overlap) = self.bbox_intersection(bbox_a, bbox_b) {
+            let center = overlap.center();
             return Some(
                 Clash::new(
                     a.id,
@@ -297,10 +424,10 @@ impl ClashDetector {
                     &a.element_type,
                     &b.element_type,
                     ClashType::Hard,
-                    overlap_point,
+                    [center.x, center.y, center.z],
                     0.0, // penetration depth would require mesh analysis
                 )
-                .with_overlap_volume(overlap_volume),
+                .with_overlap_bbox(overlap),
             );
         }
 
@@ -324,6 +451,77 @@ impl ClashDetector {
         None
     }
 
+    /// Check a pair of meshed elements, confirming hard clashes on the meshes.
+    fn check_mesh_pair(
+        &self,
+        a: &ClashElement,
+        mesh_a: &TriangleMesh,
+        b: &ClashElement,
+        mesh_b: &TriangleMesh,
+    ) -> Option<Clash> {
+        let clash = self.check_pair(a, b)?;
+        if clash.clash_type != ClashType::Hard {
+            return Some(clash);
+        }
+
+        let region = a.bbox.intersection(&b.bbox)?.expand(self.tolerance);
+        let clash_point = self.mesh_penetration_point(mesh_a, mesh_b, &region)?;
+        Some(Clash {
+            clash_point,
+            ..clash
+        })
+    }
+
+    /// Find where two meshes occupy the same space, if they do.
+    ///
+    /// Only triangles touching `region` (the bounding box overlap) are
+    /// tested. If no triangles cross, one mesh may still sit entirely inside
+    /// the other, which is checked with the centroid of its vertices.
+    fn mesh_penetration_point(
+        &self,
+        a: &TriangleMesh,
+        b: &TriangleMesh,
+        region: &BoundingBox3,
+    ) -> Option<[f64; 3]> {
+        let tris_a = triangles_in(a, region);
+        let tris_b = triangles_in(b, region);
+
+        let mut sum = Vector3::ZERO;
+        let mut count = 0usize;
+        for (ta, box_a) in &tris_a {
+            for (tb, box_b) in &tris_b {
+                if !box_a.intersects(box_b) {
+                    continue;
+                }
+                if let Some(p) = triangle_intersection_midpoint(ta, tb, self.tolerance) {
+                    sum += p.to_vector();
+                    count += 1;
+                }
+            }
+        }
+
+        if count > 0 {
+            let mean = sum / count as f64;
+            return Some([mean.x, mean.y, mean.z]);
+        }
+
+        // No surface crossings: check whether one mesh is nested in the other
+        let margin = self.tolerance;
+        let (bbox_a, bbox_b) = (a.bounding_box()?, b.bounding_box()?);
+        let nestings = [(a, bbox_a, b, bbox_b), (b, bbox_b, a, bbox_a)];
+        for (inner, inner_box, outer, outer_box) in nestings {
+            if !outer_box.expand(margin).contains(&inner_box) {
+                continue;
+            }
+            let centroid = vertex_centroid(inner)?;
+            if point_in_mesh(&centroid, outer) {
+                return Some([centroid.x, centroid.y, centroid.z]);
+            }
+        }
+
+        None
+    }
+
     /// Check if two bounding boxes are nearly identical (potential duplicates).
     fn are_duplicates(&self, a: &BoundingBox3, b: &BoundingBox3) -> bool {
         let tol = self.tolerance;
@@ -336,34 +534,19 @@ impl ClashDetector {
             && (a.max.z - b.max.z).abs() < tol
     }
 
-    /// Check if two bounding boxes intersect and return overlap info.
-    fn bbox_intersection(
-        &self,
-        a: &BoundingBox3,
-        b: &BoundingBox3,
-    ) -> Option<([f64; 3], f64)> {
-        // Check for overlap in each axis
-        let overlap_x = (a.max.x.min(b.max.x) - a.min.x.max(b.min.x)).max(0.0);
-        let overlap_y = (a.max.y.min(b.max.y) - a.min.y.max(b.min.y)).max(0.0);
-        let overlap_z = (a.max.z.min(b.max.z) - a.min.z.max(b.min.z)).max(0.0);
-
-        // If any dimension has no overlap, boxes don't intersect
-        if overlap_x <= self.tolerance
-            || overlap_y <= self.tolerance
-            || overlap_z <= self.tolerance
+    /// Check if two bounding boxes intersect and return the overlap region.
+    fn bbox_intersection(&self, a: &BoundingBox3, b: &BoundingBox3) -> Option<BoundingBox3> {
+        let overlap = a.intersection(b)?;
+
+        // Boxes that only touch (within tolerance) don't clash
+        if overlap.width() <= self.tolerance
+            || overlap.depth() <= self.tolerance
+            || overlap.height() <= self.tolerance
         {
             return None;
         }
 
-        // Calculate overlap volume
-        let volume = overlap_x * overlap_y * overlap_z;
-
-        // Calculate center of overlap region
-        let center_x = (a.min.x.max(b.min.x) + a.max.x.min(b.max.x)) / 2.0;
-        let center_y = (a.min.y.max(b.min.y) + a.max.y.min(b.max.y)) / 2.0;
-        let center_z = (a.min.z.max(b.min.z) + a.max.z.min(b.max.z)) / 2.0;
-
-        Some(([center_x, center_y, center_z], volume))
+        Some(overlap)
     }
 
     /// Check for clearance violation between non-intersecting bounding boxes.
@@ -438,6 +621,145 @@ impl Default for ClashDetector {
     }
 }
 
+/// Triangles of a mesh whose bounding boxes intersect `region`.
+fn triangles_in(mesh: &TriangleMesh, region: &BoundingBox3) -> Vec<([Point3; 3], BoundingBox3)> {
+    mesh.indices
+        .iter()
+        .filter_map(|tri| {
+            let points = tri.map(|i| mesh.vertices[i as usize]);
+            let bbox = BoundingBox3::from_points(&points)?;
+            bbox.intersects(region).then_some((points, bbox))
+        })
+        .collect()
+}
+
+/// Midpoint of the segment where two triangles cross, if they penetrate.
+///
+/// Interval-overlap test after Möller (1997): each triangle must straddle
+/// the other's plane, and the intervals the two triangles cut on the line
+/// where the planes meet must overlap. Vertices within `tol` of a plane
+/// count as lying on it, so triangles that only touch, including coplanar
+/// faces, do not intersect.
+fn triangle_intersection_midpoint(a: &[Point3; 3], b: &[Point3; 3], tol: f64) -> Option<Point3> {
+    let normal_a = triangle_normal(a)?;
+    let normal_b = triangle_normal(b)?;
+
+    let dist_a = a.map(|p| snap_to_zero((p - b[0]).dot(&normal_b), tol));
+    if !straddles(&dist_a) {
+        return None;
+    }
+    let dist_b = b.map(|p| snap_to_zero((p - a[0]).dot(&normal_a), tol));
+    if !straddles(&dist_b) {
+        return None;
+    }
+
+    let direction = normal_a.cross(&normal_b).try_normalize()?;
+    let (a0, a1) = plane_crossing(a, &dist_a);
+    let (b0, b1) = plane_crossing(b, &dist_b);
+
+    // Project both segments onto the common line and overlap the intervals
+    let project = |p: Point3| p.to_vector().dot(&direction);
+    let (ta0, ta1) = (project(a0), project(a1));
+    let (lo_a, hi_a) = (ta0.min(ta1), ta0.max(ta1));
+    let (tb0, tb1) = (project(b0), project(b1));
+    let lo = lo_a.max(tb0.min(tb1));
+    let hi = hi_a.min(tb0.max(tb1));
+    if hi - lo <= tol {
+        return None;
+    }
+
+    let mid = (lo + hi) / 2.0;
+    Some(a0 + (a1 - a0) * ((mid - ta0) / (ta1 - ta0)))
+}
+
+/// Unit normal of a triangle, or `None` if it is degenerate.
+fn triangle_normal(t: &[Point3; 3]) -> Option<Vector3> {
+    (t[1] - t[0]).cross(&(t[2] - t[0])).try_normalize()
+}
+
+fn snap_to_zero(d: f64, tol: f64) -> f64 {
+    if d.abs() <= tol {
+        0.0
+    } else {
+        d
+    }
+}
+
+/// Whether signed distances put vertices strictly on both sides of a plane.
+fn straddles(d: &[f64; 3]) -> bool {
+    d.iter().any(|&x| x > 0.0) && d.iter().any(|&x| x < 0.0)
+}
+
+/// The two points where a straddling triangle meets the plane.
+fn plane_crossing(t: &[Point3; 3], d: &[f64; 3]) -> (Point3, Point3) {
+    let mut points = [t[0]; 2];
+    let mut n = 0;
+    for i in 0..3 {
+        let j = (i + 1) % 3;
+        if d[i] == 0.0 {
+            points[n] = t[i];
+            n += 1;
+        } else if d[i] * d[j] < 0.0 {
+            points[n] = t[i] + (t[j] - t[i]) * (d[i] / (d[i] - d[j]));
+            n += 1;
+        }
+        if n == 2 {
+            break;
+        }
+    }
+    (points[0], points[1])
+}
+
+fn vertex_centroid(mesh: &TriangleMesh) -> Option<Point3> {
+    if mesh.vertices.is_empty() {
+        return None;
+    }
+    let sum = mesh
+        .vertices
+        .iter()
+        .fold(Vector3::ZERO, |acc, v| acc + v.to_vector());
+    let c = sum / mesh.vertices.len() as f64;
+    Some(Point3::new(c.x, c.y, c.z))
+}
+
+/// Point-in-mesh test by ray parity, assuming a closed mesh.
+///
+/// The ray direction is skewed off the axes so it does not graze the edges
+/// of axis-aligned geometry.
+fn point_in_mesh(p: &Point3, mesh: &TriangleMesh) -> bool {
+    const EPS: f64 = 1e-12;
+    let dir = Vector3::new(0.8723, 0.3512, 0.3401);
+
+    let mut crossings = 0;
+    for tri in &mesh.indices {
+        let [v0, v1, v2] = tri.map(|i| mesh.vertices[i as usize]);
+
+        // Möller–Trumbore ray/triangle intersection
+        let e1 = v1 - v0;
+        let e2 = v2 - v0;
+        let h = dir.cross(&e2);
+        let det = e1.dot(&h);
+        if det.abs() < EPS {
+            continue;
+        }
+        let s = *p - v0;
+        let u = s.dot(&h) / det;
+        if !(0.0..=1.0).contains(&u) {
+            continue;
+        }
+        let q = s.cross(&e1);
+        let v = dir.dot(&q) / det;
+        if v < 0.0 || u + v > 1.0 {
+            continue;
+        }
+        if e2.dot(&q) / det > EPS {
+            crossings += 1;
+        }
+    }
+
+    crossings % 2 == 1
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -556,6 +878,39 @@ mod tests {
         assert!(clashes[0].distance < 0.5);
     }
 
+    #[test]
+    fn hard_clash_reports_overlap_bbox() {
+        let detector = ClashDetector::new(0.001);
+
+        // A wall running into the end of another, overlapping by 0.5m
+        let elements = vec![
+            make_element(
+                "00000000-0000-0000-0000-000000000001",
+                "wall",
+                [0.0, 0.0, 0.0],
+                [4.0, 0.2, 3.0],
+            ),
+            make_element(
+                "00000000-0000-0000-0000-000000000002",
+                "wall",
+                [3.5, -1.0, 0.0],
+                [3.7, 2.0, 2.5],
+            ),
+        ];
+
+        let clashes = detector.detect_clashes_in_list(&elements);
+        assert_eq!(clashes.len(), 1);
+        let clash = &clashes[0];
+        let overlap = clash.overlap_bbox.expect("hard clash has an overlap box");
+
+        assert!((overlap.width() - 0.2).abs() < 1e-9);
+        assert!((overlap.depth() - 0.2).abs() < 1e-9);
+        assert!((overlap.height() - 2.5).abs() < 1e-9);
+        assert!((clash.overlap_volume - overlap.volume()).abs() < 1e-12);
+        let center = overlap.center();
+        assert_eq!(clash.clash_point, [center.x, center.y, center.z]);
+    }
+
     #[test]
     fn filter_same_type() {
         let filter = ClashFilter::new().ignore_same_type();
@@ -603,4 +958,115 @@ mod tests {
         assert_eq!(clashes[0].element_a_type, "wall");
         assert_eq!(clashes[0].element_b_type, "door");
     }
+
+    fn wall_mesh(start: (f64, f64), end: (f64, f64)) -> TriangleMesh {
+        use crate::element::Element;
+        use crate::elements::Wall;
+        use pensaer_math::Point2;
+
+        Wall::new(
+            Point2::new(start.0, start.1),
+            Point2::new(end.0, end.1),
+            3.0,
+            0.2,
+        )
+        .unwrap()
+        .to_mesh()
+        .unwrap()
+    }
+
+    fn box_mesh(min: [f64; 3], max: [f64; 3]) -> TriangleMesh {
+        use crate::mesh::extrude_polygon;
+        use pensaer_math::Point2;
+
+        let footprint = [
+            Point2::new(min[0], min[1]),
+            Point2::new(max[0], min[1]),
+            Point2::new(max[0], max[1]),
+            Point2::new(min[0], max[1]),
+        ];
+        extrude_polygon(&footprint, max[2] - min[2], min[2]).unwrap()
+    }
+
+    #[test]
+    fn mesh_clash_ignores_diagonal_walls_with_overlapping_boxes() {
+        let detector = ClashDetector::new(0.001);
+        let a = wall_mesh((0.0, 0.0), (4.0, 4.0));
+        let b = wall_mesh((0.0, 1.5), (4.0, 5.5));
+
+        let elements = vec![
+            (Uuid::new_v4(), "wall".to_string(), &a),
+            (Uuid::new_v4(), "wall".to_string(), &b),
+        ];
+
+        // The broad phase alone reports a clash...
+        let boxes: Vec<ClashElement> = elements
+            .iter()
+            .map(|(id, t, m)| ClashElement::new(*id, t.as_str(), m.bounding_box().unwrap()))
+            .collect();
+        assert_eq!(detector.detect_clashes_in_list(&boxes).len(), 1);
+
+        // ...but the meshes never touch
+        assert!(detector.detect_clashes_meshes(&elements).is_empty());
+    }
+
+    #[test]
+    fn mesh_clash_crossing_walls() {
+        let detector = ClashDetector::new(0.001);
+        let a = wall_mesh((0.0, 2.0), (6.0, 2.0));
+        let b = wall_mesh((3.0, 0.0), (3.0, 5.0));
+
+        let elements = vec![
+            (Uuid::new_v4(), "wall".to_string(), &a),
+            (Uuid::new_v4(), "wall".to_string(), &b),
+        ];
+
+        let clashes = detector.detect_clashes_meshes(&elements);
+        assert_eq!(clashes.len(), 1);
+        assert_eq!(clashes[0].clash_type, ClashType::Hard);
+
+        // Penetration point lies in the crossing region
+        let [x, y, z] = clashes[0].clash_point;
+        assert!((x - 3.0).abs() <= 0.1 + 1e-9);
+        assert!((y - 2.0).abs() <= 0.1 + 1e-9);
+        assert!((0.0..=3.0).contains(&z));
+    }
+
+    #[test]
+    fn mesh_clash_nested_mesh() {
+        let detector = ClashDetector::new(0.001);
+        let outer = box_mesh([0.0, 0.0, 0.0], [2.0, 2.0, 2.0]);
+        let inner = box_mesh([0.5, 0.5, 0.5], [1.0, 1.0, 1.0]);
+
+        let elements = vec![
+            (Uuid::new_v4(), "slab".to_string(), &outer),
+            (Uuid::new_v4(), "duct".to_string(), &inner),
+        ];
+
+        let clashes = detector.detect_clashes_meshes(&elements);
+        assert_eq!(clashes.len(), 1);
+        assert_eq!(clashes[0].clash_type, ClashType::Hard);
+        let [x, y, z] = clashes[0].clash_point;
+        assert!((x - 0.75).abs() < 1e-9 && (y - 0.75).abs() < 1e-9 && (z - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn mesh_clash_touching_faces_is_not_hard() {
+        use crate::element::Element;
+        use crate::elements::{OffsetSide, Wall};
+        use pensaer_math::Point2;
+
+        let detector = ClashDetector::new(0.001);
+        // Two leaves of a diagonal cavity-less double wall, face to face
+        let a = Wall::new(Point2::new(0.0, 0.0), Point2::new(4.0, 4.0), 3.0, 0.2).unwrap();
+        let b = a.offset(0.2, OffsetSide::Left).unwrap();
+        let (mesh_a, mesh_b) = (a.to_mesh().unwrap(), b.to_mesh().unwrap());
+
+        let elements = vec![
+            (a.id, "wall".to_string(), &mesh_a),
+            (b.id, "wall".to_string(), &mesh_b),
+        ];
+
+        assert!(detector.detect_clashes_meshes(&elements).is_empty());
+    }
 }
@@ -1,8 +1,12 @@
 //! R*-tree index for geometry edges/segments.
 //!
-//! Provides O(log n) spatial queries for edge lookup and intersection detection.
+//! Provides O(log n) spatial queries for edge lookup and intersection detection,
+//! nearest-edge queries by true point-to-segment distance, and ray casts.
 
-use rstar::{RTree, RTreeObject, AABB};
+use rstar::{PointDistance, RTree, RTreeObject, SelectionFunction, AABB};
+
+use super::take_nearest;
+use crate::util::float::dist2_squared;
 
 /// An edge entry in the spatial index.
 #[derive(Debug, Clone)]
@@ -32,6 +36,58 @@ impl EdgeEntry {
         let dy = self.end[1] - self.start[1];
         (dx * dx + dy * dy).sqrt()
     }
+
+    /// Get the point on the edge closest to `point`.
+    pub fn closest_point(&self, point: [f64; 2]) -> [f64; 2] {
+        let dx = self.end[0] - self.start[0];
+        let dy = self.end[1] - self.start[1];
+        let len_sq = dx * dx + dy * dy;
+        if len_sq == 0.0 {
+            return self.start;
+        }
+        let t = ((point[0] - self.start[0]) * dx + (point[1] - self.start[1]) * dy) / len_sq;
+        let t = t.clamp(0.0, 1.0);
+        [self.start[0] + t * dx, self.start[1] + t * dy]
+    }
+
+    /// Get the distance from `point` to the nearest point on the edge.
+    pub fn distance_to(&self, point: [f64; 2]) -> f64 {
+        self.distance_2(&point).sqrt()
+    }
+
+    /// Distance along a ray to where it first meets this edge, if within
+    /// `max_t`. `dir` must be unit length. A ray running along the edge
+    /// hits its nearer end, or its origin if that lies on the edge.
+    fn ray_hit(&self, origin: [f64; 2], dir: [f64; 2], max_t: f64) -> Option<f64> {
+        const EPS: f64 = 1e-12;
+        let cross = |a: [f64; 2], b: [f64; 2]| a[0] * b[1] - a[1] * b[0];
+        let e = [self.end[0] - self.start[0], self.end[1] - self.start[1]];
+        let w = [self.start[0] - origin[0], self.start[1] - origin[1]];
+        let scale = self.length().max(1.0);
+
+        let denom = cross(dir, e);
+        let t = if denom.abs() <= EPS * scale {
+            // Parallel: only a collinear edge can be hit
+            if cross(w, dir).abs() > EPS * scale * scale {
+                return None;
+            }
+            let t_start = w[0] * dir[0] + w[1] * dir[1];
+            let t_end = t_start + e[0] * dir[0] + e[1] * dir[1];
+            let (near, far) = (t_start.min(t_end), t_start.max(t_end));
+            if far < 0.0 {
+                return None;
+            }
+            near.max(0.0)
+        } else {
+            let t = cross(w, e) / denom;
+            let s = cross(w, dir) / denom;
+            if t < 0.0 || !(-EPS..=1.0 + EPS).contains(&s) {
+                return None;
+            }
+            t
+        };
+        (t <= max_t).then_some(t)
+    }
 }
 
 impl RTreeObject for EdgeEntry {
@@ -46,6 +102,13 @@ impl RTreeObject for EdgeEntry {
     }
 }
 
+impl PointDistance for EdgeEntry {
+    /// Squared distance to the segment itself, not its envelope.
+    fn distance_2(&self, point: &[f64; 2]) -> f64 {
+        dist2_squared(self.closest_point(*point), *point)
+    }
+}
+
 impl PartialEq for EdgeEntry {
     fn eq(&self, other: &Self) -> bool {
         self.id == other.id
@@ -53,7 +116,7 @@ impl PartialEq for EdgeEntry {
 }
 
 /// Spatial index for geometry edges using R*-tree.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EdgeIndex {
     tree: RTree<EdgeEntry>,
 }
@@ -113,6 +176,63 @@ impl EdgeIndex {
         self.in_envelope([min_x, min_y], [max_x, max_y])
     }
 
+    /// Find the k edges closest to a point, by distance to the segment.
+    ///
+    /// Results are sorted by increasing distance, with equal distances
+    /// ordered by edge ID.
+    pub fn nearest(&self, point: [f64; 2], k: usize) -> Vec<(&EdgeEntry, f64)> {
+        take_nearest(
+            self.tree.nearest_neighbor_iter_with_distance_2(&point),
+            k,
+            |e| e.id.as_str(),
+        )
+        .into_iter()
+        .map(|(entry, d2)| (entry, d2.sqrt()))
+        .collect()
+    }
+
+    /// Find all edges within `radius` of a point, by distance to the
+    /// segment, sorted nearest first with equal distances ordered by ID.
+    pub fn within_distance(&self, point: [f64; 2], radius: f64) -> Vec<(&EdgeEntry, f64)> {
+        let mut found: Vec<(&EdgeEntry, f64)> = self
+            .tree
+            .locate_within_distance(point, radius * radius)
+            .map(|entry| (entry, entry.distance_to(point)))
+            .collect();
+        found.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.id.cmp(&b.0.id)));
+        found
+    }
+
+    /// Find the first edge hit by a ray.
+    ///
+    /// Returns the edge, the distance along the ray, and the hit point. The
+    /// direction need not be unit length; a zero direction hits nothing.
+    /// Edges hit at the same distance are ordered by ID.
+    pub fn raycast(
+        &self,
+        origin: [f64; 2],
+        direction: [f64; 2],
+        max_distance: f64,
+    ) -> Option<(&EdgeEntry, f64, [f64; 2])> {
+        let len = (direction[0] * direction[0] + direction[1] * direction[1]).sqrt();
+        if len == 0.0 || !len.is_finite() {
+            return None;
+        }
+        let dir = [direction[0] / len, direction[1] / len];
+        let selection = RaySelection {
+            origin,
+            dir,
+            max_t: max_distance,
+        };
+
+        let (entry, t) = self
+            .tree
+            .locate_with_selection_function(selection)
+            .filter_map(|entry| Some((entry, entry.ray_hit(origin, dir, max_distance)?)))
+            .min_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.id.cmp(&b.0.id)))?;
+        Some((entry, t, [origin[0] + t * dir[0], origin[1] + t * dir[1]]))
+    }
+
     /// Get the number of edges in the index.
     pub fn len(&self) -> usize {
         self.tree.size()
@@ -129,6 +249,38 @@ impl EdgeIndex {
     }
 }
 
+/// Selects edges whose envelopes a ray segment passes through.
+struct RaySelection {
+    origin: [f64; 2],
+    dir: [f64; 2],
+    max_t: f64,
+}
+
+impl SelectionFunction<EdgeEntry> for RaySelection {
+    fn should_unpack_parent(&self, envelope: &AABB<[f64; 2]>) -> bool {
+        // Slab test against the box
+        let (lower, upper) = (envelope.lower(), envelope.upper());
+        let (mut t_min, mut t_max) = (0.0_f64, self.max_t);
+        for axis in 0..2 {
+            if self.dir[axis] == 0.0 {
+                if self.origin[axis] < lower[axis] || self.origin[axis] > upper[axis] {
+                    return false;
+                }
+                continue;
+            }
+            let inv = 1.0 / self.dir[axis];
+            let t0 = (lower[axis] - self.origin[axis]) * inv;
+            let t1 = (upper[axis] - self.origin[axis]) * inv;
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+            if t_min > t_max {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 impl Default for EdgeIndex {
     fn default() -> Self {
         Self::new()
@@ -186,6 +338,89 @@ mod tests {
         assert!((mid[1] - 50.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn nearest_uses_segment_not_envelope_distance() {
+        let mut index = EdgeIndex::new();
+        // A long diagonal whose envelope contains the query point...
+        index.insert("diag".to_string(), [0.0, 0.0], [100.0, 100.0]);
+        // ...and a short edge whose envelope is further away
+        index.insert("short".to_string(), [90.0, 5.0], [90.0, 15.0]);
+
+        let query = [95.0, 10.0];
+        let hits = index.nearest(query, 2);
+        assert_eq!(hits[0].0.id, "short");
+        assert!((hits[0].1 - 5.0).abs() < 1e-10);
+        assert_eq!(hits[1].0.id, "diag");
+        assert!((hits[1].1 - 85.0 / 2f64.sqrt()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn nearest_breaks_ties_by_id() {
+        let edges = vec![
+            ("c".to_string(), [0.0, 10.0], [10.0, 10.0]),
+            ("a".to_string(), [0.0, -10.0], [10.0, -10.0]),
+            ("b".to_string(), [-10.0, 0.0], [-10.0, 10.0]),
+            ("far".to_string(), [50.0, 0.0], [60.0, 0.0]),
+        ];
+        let index = EdgeIndex::bulk_load(edges.clone());
+        let ids: Vec<_> = index
+            .nearest([0.0, 0.0], 2)
+            .iter()
+            .map(|(e, _)| e.id.as_str())
+            .collect();
+        assert_eq!(ids, ["a", "b"]);
+
+        // Same answer whatever the insertion order
+        let mut index = EdgeIndex::new();
+        for (id, start, end) in edges.into_iter().rev() {
+            index.insert(id, start, end);
+        }
+        assert_eq!(index.nearest([0.0, 0.0], 1)[0].0.id, "a");
+        assert!(index.nearest([0.0, 0.0], 0).is_empty());
+    }
+
+    #[test]
+    fn within_distance_measures_to_segment() {
+        let mut index = EdgeIndex::new();
+        // A long diagonal whose envelope covers the query point
+        index.insert("diag".to_string(), [0.0, 0.0], [1000.0, 1000.0]);
+        index.insert("near".to_string(), [0.0, 495.0], [1000.0, 495.0]);
+
+        let found = index.within_distance([600.0, 500.0], 10.0);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0.id, "near");
+        assert!((found[0].1 - 5.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn raycast_finds_first_hit() {
+        let mut index = EdgeIndex::new();
+        index.insert("near".to_string(), [10.0, -5.0], [10.0, 5.0]);
+        index.insert("far".to_string(), [20.0, -5.0], [20.0, 5.0]);
+        index.insert("behind".to_string(), [-10.0, -5.0], [-10.0, 5.0]);
+
+        let (edge, t, point) = index.raycast([0.0, 0.0], [2.0, 0.0], 100.0).unwrap();
+        assert_eq!(edge.id, "near");
+        assert!((t - 10.0).abs() < 1e-10);
+        assert!((point[0] - 10.0).abs() < 1e-10 && point[1].abs() < 1e-10);
+
+        // Too short to reach, or pointing away from everything
+        assert!(index.raycast([0.0, 0.0], [1.0, 0.0], 5.0).is_none());
+        assert!(index.raycast([0.0, 0.0], [0.0, 1.0], 100.0).is_none());
+        assert!(index.raycast([0.0, 0.0], [0.0, 0.0], 100.0).is_none());
+    }
+
+    #[test]
+    fn raycast_along_collinear_edge_hits_near_end() {
+        let mut index = EdgeIndex::new();
+        index.insert("e1".to_string(), [5.0, 0.0], [15.0, 0.0]);
+
+        let (_, t, _) = index.raycast([0.0, 0.0], [1.0, 0.0], 100.0).unwrap();
+        assert!((t - 5.0).abs() < 1e-10);
+        let (_, t, _) = index.raycast([8.0, 0.0], [-1.0, 0.0], 100.0).unwrap();
+        assert!(t.abs() < 1e-10);
+    }
+
     #[test]
     fn edge_entry_length() {
         let edge = EdgeEntry::new("e1".to_string(), [0.0, 0.0], [3.0, 4.0]);
@@ -5,6 +5,7 @@
 //! - Find edges intersecting a bounding box
 //! - Nearest neighbor queries
 //! - Clash detection between elements
+//! - Cursor snapping to nodes, walls, guides, and a grid
 //!
 //! # Example
 //!
@@ -23,13 +24,40 @@ mod clash;
 mod edge_index;
 mod node_index;
 mod predicates;
+mod snapping;
 
-pub use clash::{Clash, ClashDetector, ClashElement, ClashFilter, ClashType};
+pub use clash::{Clash, ClashDetector, ClashElement, ClashFilter, ClashType, ContextClashes};
 pub use edge_index::{EdgeEntry, EdgeIndex};
 pub use node_index::NodeIndex;
 pub use predicates::{
-    orient2d, orient2d_robust, segment_intersection, segments_intersect, signed_area_2, Orientation,
+    orient2d, orient2d_robust, segment_intersection, segments_intersect, segments_intersect_at,
+    signed_area_2, IntersectionKind, Orientation, SegmentIntersection,
 };
+pub use snapping::{SnapEngine, SnapGrid, SnapKind, SnapResult};
+
+/// Take the `k` nearest items from a nearest-first `(item, distance²)`
+/// iterator, ordering equal distances by ID so results do not depend on
+/// the tree's layout.
+fn take_nearest<'a, T>(
+    iter: impl Iterator<Item = (&'a T, f64)>,
+    k: usize,
+    id: impl Fn(&T) -> &str,
+) -> Vec<(&'a T, f64)> {
+    let mut found: Vec<(&T, f64)> = Vec::new();
+    if k == 0 {
+        return found;
+    }
+    for (item, d2) in iter {
+        // Keep reading past k while there are ties with the k-th item
+        if found.len() >= k && d2 > found[k - 1].1 {
+            break;
+        }
+        found.push((item, d2));
+    }
+    found.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| id(a.0).cmp(id(b.0))));
+    found.truncate(k);
+    found
+}
 
 #[cfg(test)]
 mod tests {
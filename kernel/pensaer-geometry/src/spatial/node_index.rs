@@ -4,6 +4,8 @@
 
 use rstar::{PointDistance, RTree, RTreeObject, AABB};
 
+use super::take_nearest;
+
 /// A node entry in the spatial index.
 #[derive(Debug, Clone)]
 pub struct NodeEntry {
@@ -28,7 +30,7 @@ impl PointDistance for NodeEntry {
 }
 
 /// Spatial index for geometry nodes using R*-tree.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NodeIndex {
     tree: RTree<NodeEntry>,
 }
@@ -75,19 +77,24 @@ impl NodeIndex {
     }
 
     /// Find the nearest node to a point.
-    pub fn nearest(&self, point: [f64; 2]) -> Option<(&str, [f64; 2])> {
-        self.tree
-            .nearest_neighbor(&point)
-            .map(|entry| (entry.id.as_str(), entry.position))
+    ///
+    /// Of several nodes at the same distance, the one with the smallest ID
+    /// is returned.
+    pub fn nearest(&self, point: [f64; 2]) -> Option<(String, [f64; 2])> {
+        self.nearest_k(point, 1).pop()
     }
 
-    /// Find the k nearest nodes to a point.
-    pub fn k_nearest(&self, point: [f64; 2], k: usize) -> Vec<(&str, [f64; 2])> {
-        self.tree
-            .nearest_neighbor_iter(&point)
-            .take(k)
-            .map(|entry| (entry.id.as_str(), entry.position))
-            .collect()
+    /// Find the k nearest nodes to a point, sorted by increasing distance,
+    /// with equal distances ordered by ID.
+    pub fn nearest_k(&self, point: [f64; 2], k: usize) -> Vec<(String, [f64; 2])> {
+        take_nearest(
+            self.tree.nearest_neighbor_iter_with_distance_2(&point),
+            k,
+            |e| e.id.as_str(),
+        )
+        .into_iter()
+        .map(|(entry, _)| (entry.id.clone(), entry.position))
+        .collect()
     }
 
     /// Find all nodes within a bounding box.
@@ -172,6 +179,61 @@ mod tests {
         assert_eq!(nearest.unwrap().0, "n3");
     }
 
+    #[test]
+    fn nearest_on_empty_index_is_none() {
+        let index = NodeIndex::new();
+        assert!(index.nearest([0.0, 0.0]).is_none());
+        assert!(index.nearest_k([0.0, 0.0], 3).is_empty());
+    }
+
+    #[test]
+    fn nearest_k_orders_by_true_distance() {
+        let nodes = vec![
+            ("n1".to_string(), [0.0, 0.0]),
+            ("n2".to_string(), [30.0, 40.0]),
+            ("n3".to_string(), [-12.0, 5.0]),
+            ("n4".to_string(), [100.0, -100.0]),
+            ("n5".to_string(), [7.0, 24.0]),
+            ("n6".to_string(), [-60.0, 80.0]),
+        ];
+        let index = NodeIndex::bulk_load(nodes.clone());
+        let query = [1.0, 2.0];
+
+        let dist = |p: [f64; 2]| ((p[0] - query[0]).powi(2) + (p[1] - query[1]).powi(2)).sqrt();
+        let mut expected = nodes;
+        expected.sort_by(|a, b| dist(a.1).total_cmp(&dist(b.1)));
+
+        let result = index.nearest_k(query, 4);
+        assert_eq!(result.len(), 4);
+        for (got, want) in result.iter().zip(&expected) {
+            assert_eq!(got.0, want.0);
+        }
+        for pair in result.windows(2) {
+            assert!(dist(pair[0].1) <= dist(pair[1].1));
+        }
+
+        // Asking for more than available returns everything
+        assert_eq!(index.nearest_k(query, 10).len(), 6);
+        assert_eq!(index.nearest(query).unwrap().0, expected[0].0);
+    }
+
+    #[test]
+    fn nearest_breaks_ties_by_id() {
+        let mut index = NodeIndex::new();
+        index.insert("n3".to_string(), [10.0, 0.0]);
+        index.insert("n2".to_string(), [0.0, 10.0]);
+        index.insert("n1".to_string(), [-10.0, 0.0]);
+        index.insert("n0".to_string(), [0.0, 11.0]);
+
+        assert_eq!(index.nearest([0.0, 0.0]).unwrap().0, "n1");
+        let ids: Vec<_> = index
+            .nearest_k([0.0, 0.0], 2)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, ["n1", "n2"]);
+    }
+
     #[test]
     fn bulk_load_works() {
         let nodes = vec![
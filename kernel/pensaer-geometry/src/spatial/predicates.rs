@@ -164,6 +164,163 @@ pub fn segment_intersection(
     None
 }
 
+/// How two segments meet in [`segments_intersect_at`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntersectionKind {
+    /// The segments cross at a point interior to both.
+    Proper,
+    /// An endpoint of one segment lies on the other.
+    EndpointTouch,
+    /// The segments are collinear and share a stretch. The intersection
+    /// point is the start of the shared stretch along segment A; this is
+    /// its other end.
+    CollinearOverlap {
+        end: [f64; 2],
+        t_end: f64,
+        s_end: f64,
+    },
+}
+
+/// Where two segments meet, with parameters along each.
+///
+/// `t` is the parameter of `point` along segment A (`a1` at 0, `a2` at 1)
+/// and `s` the parameter along segment B; both lie in [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentIntersection {
+    pub point: [f64; 2],
+    pub t: f64,
+    pub s: f64,
+    pub kind: IntersectionKind,
+}
+
+/// Intersect two segments, returning the meeting point and its parameters
+/// along each segment.
+///
+/// Classification uses robust orientation tests, so an endpoint lying on
+/// the other segment is reported as [`IntersectionKind::EndpointTouch`]
+/// with that endpoint's exact coordinates and a parameter of exactly 0 or
+/// 1. Returns None if the segments do not meet.
+pub fn segments_intersect_at(
+    a1: [f64; 2],
+    a2: [f64; 2],
+    b1: [f64; 2],
+    b2: [f64; 2],
+) -> Option<SegmentIntersection> {
+    let o1 = orient2d_robust(a1, a2, b1);
+    let o2 = orient2d_robust(a1, a2, b2);
+    let o3 = orient2d_robust(b1, b2, a1);
+    let o4 = orient2d_robust(b1, b2, a2);
+
+    if [o1, o2, o3, o4]
+        .iter()
+        .all(|&o| o == Orientation::Collinear)
+    {
+        return collinear_overlap(a1, a2, b1, b2);
+    }
+    if o1 == o2 || o3 == o4 {
+        return None;
+    }
+
+    // The segments straddle each other; snap touching endpoints exactly
+    let d1 = signed_area_2(b1, b2, a1);
+    let d2 = signed_area_2(b1, b2, a2);
+    let d3 = signed_area_2(a1, a2, b1);
+    let d4 = signed_area_2(a1, a2, b2);
+    let touch = [(o3, a1), (o4, a2), (o1, b1), (o2, b2)]
+        .into_iter()
+        .find(|(o, _)| *o == Orientation::Collinear);
+    let t = match (o3, o4) {
+        (Orientation::Collinear, _) => 0.0,
+        (_, Orientation::Collinear) => 1.0,
+        _ => d1 / (d1 - d2),
+    };
+    let s = match (o1, o2) {
+        (Orientation::Collinear, _) => 0.0,
+        (_, Orientation::Collinear) => 1.0,
+        _ => d3 / (d3 - d4),
+    };
+
+    Some(match touch {
+        Some((_, point)) => SegmentIntersection {
+            point,
+            t,
+            s,
+            kind: IntersectionKind::EndpointTouch,
+        },
+        None => SegmentIntersection {
+            point: [a1[0] + t * (a2[0] - a1[0]), a1[1] + t * (a2[1] - a1[1])],
+            t,
+            s,
+            kind: IntersectionKind::Proper,
+        },
+    })
+}
+
+/// Shared stretch of two collinear segments, if any.
+fn collinear_overlap(
+    a1: [f64; 2],
+    a2: [f64; 2],
+    b1: [f64; 2],
+    b2: [f64; 2],
+) -> Option<SegmentIntersection> {
+    let (tb1, tb2) = (segment_param(a1, a2, b1), segment_param(a1, a2, b2));
+    let lo = tb1.min(tb2).max(0.0);
+    let hi = tb1.max(tb2).min(1.0);
+    if lo > hi {
+        return None;
+    }
+
+    // Use exact endpoints where the stretch ends at one
+    let point_at = |t: f64| {
+        if t == tb1 {
+            b1
+        } else if t == tb2 {
+            b2
+        } else if t == 0.0 {
+            a1
+        } else if t == 1.0 {
+            a2
+        } else {
+            [a1[0] + t * (a2[0] - a1[0]), a1[1] + t * (a2[1] - a1[1])]
+        }
+    };
+    let (start, end) = (point_at(lo), point_at(hi));
+    // A zero-length A projects everything to 0, so check it is on B
+    if !on_segment(a1, start, a2) || !on_segment(b1, start, b2) {
+        return None;
+    }
+    let s = segment_param(b1, b2, start);
+
+    if lo == hi || start == end {
+        return Some(SegmentIntersection {
+            point: start,
+            t: lo,
+            s,
+            kind: IntersectionKind::EndpointTouch,
+        });
+    }
+    Some(SegmentIntersection {
+        point: start,
+        t: lo,
+        s,
+        kind: IntersectionKind::CollinearOverlap {
+            end,
+            t_end: hi,
+            s_end: segment_param(b1, b2, end),
+        },
+    })
+}
+
+/// Parameter of the projection of p onto segment (a, b), 0 for a point.
+fn segment_param(a: [f64; 2], b: [f64; 2], p: [f64; 2]) -> f64 {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let len_sq = dx * dx + dy * dy;
+    if len_sq == 0.0 {
+        return 0.0;
+    }
+    ((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / len_sq
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -272,4 +429,70 @@ mod tests {
         let c = [1.0, 0.0];
         assert!(signed_area_2(a, b, c) < 0.0);
     }
+
+    #[test]
+    fn segments_intersect_at_crossing() {
+        // X pattern - both segments cross at their midpoints
+        let hit =
+            segments_intersect_at([0.0, 0.0], [10.0, 10.0], [0.0, 10.0], [10.0, 0.0]).unwrap();
+        assert_eq!(hit.kind, IntersectionKind::Proper);
+        assert!((hit.t - 0.5).abs() < 1e-10);
+        assert!((hit.s - 0.5).abs() < 1e-10);
+        assert!((hit.point[0] - 5.0).abs() < 1e-10);
+        assert!((hit.point[1] - 5.0).abs() < 1e-10);
+
+        // Off-centre crossing reports different parameters per segment
+        let hit = segments_intersect_at([0.0, 0.0], [10.0, 0.0], [2.0, -1.0], [2.0, 3.0]).unwrap();
+        assert!((hit.t - 0.2).abs() < 1e-10);
+        assert!((hit.s - 0.25).abs() < 1e-10);
+    }
+
+    #[test]
+    fn segments_intersect_at_endpoint_touch() {
+        // T junction: B starts on A
+        let hit = segments_intersect_at([0.0, 0.0], [10.0, 0.0], [5.0, 0.0], [5.0, 10.0]).unwrap();
+        assert_eq!(hit.kind, IntersectionKind::EndpointTouch);
+        assert_eq!(hit.point, [5.0, 0.0]);
+        assert!((hit.t - 0.5).abs() < 1e-10);
+        assert_eq!(hit.s, 0.0);
+
+        // Corner: segments share an endpoint
+        let hit = segments_intersect_at([0.0, 0.0], [4.0, 0.0], [4.0, 0.0], [4.0, 3.0]).unwrap();
+        assert_eq!(hit.kind, IntersectionKind::EndpointTouch);
+        assert_eq!((hit.t, hit.s), (1.0, 0.0));
+
+        // Collinear segments meeting end to end
+        let hit = segments_intersect_at([0.0, 0.0], [4.0, 0.0], [4.0, 0.0], [8.0, 0.0]).unwrap();
+        assert_eq!(hit.kind, IntersectionKind::EndpointTouch);
+        assert_eq!(hit.point, [4.0, 0.0]);
+    }
+
+    #[test]
+    fn segments_intersect_at_collinear_overlap() {
+        // B runs backwards over the second half of A and beyond
+        let hit = segments_intersect_at([0.0, 0.0], [10.0, 0.0], [15.0, 0.0], [5.0, 0.0]).unwrap();
+        assert_eq!(hit.point, [5.0, 0.0]);
+        assert!((hit.t - 0.5).abs() < 1e-10);
+        assert!((hit.s - 1.0).abs() < 1e-10);
+        match hit.kind {
+            IntersectionKind::CollinearOverlap { end, t_end, s_end } => {
+                assert_eq!(end, [10.0, 0.0]);
+                assert!((t_end - 1.0).abs() < 1e-10);
+                assert!((s_end - 0.5).abs() < 1e-10);
+            }
+            other => panic!("expected overlap, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn segments_intersect_at_none() {
+        // Parallel
+        assert!(segments_intersect_at([0.0, 0.0], [10.0, 0.0], [0.0, 1.0], [10.0, 1.0]).is_none());
+        // Collinear but apart
+        assert!(segments_intersect_at([0.0, 0.0], [4.0, 0.0], [5.0, 0.0], [8.0, 0.0]).is_none());
+        // Lines cross beyond the end of B
+        assert!(segments_intersect_at([0.0, 0.0], [10.0, 0.0], [5.0, 1.0], [5.0, 3.0]).is_none());
+        // Zero-length A away from B
+        assert!(segments_intersect_at([20.0, 0.0], [20.0, 0.0], [0.0, 0.0], [8.0, 0.0]).is_none());
+    }
 }
@@ -0,0 +1,501 @@
+//! Cursor snapping against the wall graph.
+//!
+//! [`SnapEngine`] collects candidate snaps near a cursor from the graph's
+//! spatial indexes and picks the best: lowest [`SnapKind::priority`] first,
+//! then nearest. Every candidate lies within the engine's tolerance of the
+//! cursor ([`UI_SNAP_DIST`] by default), and each query is a handful of
+//! index lookups, so it is cheap enough to run on every mouse move.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use pensaer_geometry::spatial::{SnapEngine, SnapGrid};
+//!
+//! let engine = SnapEngine::new().with_grid(SnapGrid::new(500.0));
+//! if let Some(snap) = engine.snap(&graph, cursor) {
+//!     cursor = snap.point;
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::EdgeEntry;
+use crate::constants::UI_SNAP_DIST;
+use crate::topology::{NodeId, TopologyGraph};
+
+/// Number of edges nearest the cursor whose extensions are intersected.
+const EXTENSION_EDGES: usize = 8;
+
+/// Default distance along a guide within which a node aligns (mm).
+const ALIGNMENT_RANGE: f64 = 10_000.0;
+
+/// What a snap locked on to, in priority order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapKind {
+    /// A node where walls meet.
+    Node,
+    /// The free end of a wall.
+    Endpoint,
+    /// The midpoint of a wall.
+    Midpoint,
+    /// Where the lines of two walls cross, beyond the end of at least one.
+    Extension,
+    /// Level with a nearby node horizontally, vertically, or both.
+    Alignment,
+    /// The foot of the perpendicular from the cursor onto a wall.
+    Perpendicular,
+    /// A grid intersection.
+    Grid,
+}
+
+impl SnapKind {
+    /// Rank among snap kinds; lower wins.
+    pub fn priority(&self) -> u8 {
+        match self {
+            SnapKind::Node => 0,
+            SnapKind::Endpoint => 1,
+            SnapKind::Midpoint => 2,
+            SnapKind::Extension => 3,
+            SnapKind::Alignment => 4,
+            SnapKind::Perpendicular => 5,
+            SnapKind::Grid => 6,
+        }
+    }
+
+    /// Snake-case name, as used in JSON.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SnapKind::Node => "node",
+            SnapKind::Endpoint => "endpoint",
+            SnapKind::Midpoint => "midpoint",
+            SnapKind::Extension => "extension",
+            SnapKind::Alignment => "alignment",
+            SnapKind::Perpendicular => "perpendicular",
+            SnapKind::Grid => "grid",
+        }
+    }
+}
+
+/// A square snap grid.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnapGrid {
+    /// A point where grid lines cross.
+    pub origin: [f64; 2],
+    /// Distance between grid lines.
+    pub spacing: f64,
+}
+
+impl SnapGrid {
+    /// Create a grid with lines through the origin.
+    pub fn new(spacing: f64) -> Self {
+        Self {
+            origin: [0.0, 0.0],
+            spacing,
+        }
+    }
+
+    /// Shift the grid so its lines cross at `origin`.
+    pub fn with_origin(mut self, origin: [f64; 2]) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// The grid intersection nearest a point, or `None` if the spacing
+    /// isn't positive.
+    pub fn nearest(&self, point: [f64; 2]) -> Option<[f64; 2]> {
+        if self.spacing <= 0.0 || !self.spacing.is_finite() {
+            return None;
+        }
+        let snap = |v: f64, o: f64| o + ((v - o) / self.spacing).round() * self.spacing;
+        Some([
+            snap(point[0], self.origin[0]),
+            snap(point[1], self.origin[1]),
+        ])
+    }
+}
+
+/// A snapped cursor position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapResult {
+    /// What the cursor snapped to.
+    pub kind: SnapKind,
+    /// The snapped position.
+    pub point: [f64; 2],
+    /// IDs of the nodes and edges the snap was taken from.
+    pub sources: Vec<String>,
+    /// Priority of the kind; lower wins.
+    pub priority: u8,
+    /// Distance from the cursor to `point`.
+    pub distance: f64,
+}
+
+impl SnapResult {
+    fn new(kind: SnapKind, point: [f64; 2], cursor: [f64; 2], sources: Vec<String>) -> Self {
+        Self {
+            kind,
+            point,
+            sources,
+            priority: kind.priority(),
+            distance: (point[0] - cursor[0]).hypot(point[1] - cursor[1]),
+        }
+    }
+}
+
+/// Finds where a cursor should snap to in a wall graph.
+#[derive(Debug, Clone)]
+pub struct SnapEngine {
+    /// How close a snap must be to the cursor.
+    pub tolerance: f64,
+    /// How far along a guide line a node can be and still align with the
+    /// cursor.
+    pub alignment_range: f64,
+    /// Grid to snap to, if any.
+    pub grid: Option<SnapGrid>,
+}
+
+impl Default for SnapEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnapEngine {
+    /// Create an engine with the UI snap distance and no grid.
+    pub fn new() -> Self {
+        Self {
+            tolerance: UI_SNAP_DIST,
+            alignment_range: ALIGNMENT_RANGE,
+            grid: None,
+        }
+    }
+
+    /// Set how close a snap must be to the cursor.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set how far away a node can be and still align with the cursor.
+    pub fn with_alignment_range(mut self, range: f64) -> Self {
+        self.alignment_range = range;
+        self
+    }
+
+    /// Also snap to a grid.
+    pub fn with_grid(mut self, grid: SnapGrid) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+
+    /// The best snap for a cursor position, or `None` if nothing is in range.
+    pub fn snap(&self, graph: &TopologyGraph, cursor: [f64; 2]) -> Option<SnapResult> {
+        self.candidates(graph, cursor).into_iter().next()
+    }
+
+    /// Every snap in range of a cursor position, best first: by priority,
+    /// then distance, then source IDs.
+    pub fn candidates(&self, graph: &TopologyGraph, cursor: [f64; 2]) -> Vec<SnapResult> {
+        let mut found = Vec::new();
+        self.node_snaps(graph, cursor, &mut found);
+        self.edge_snaps(graph, cursor, &mut found);
+        self.extension_snaps(graph, cursor, &mut found);
+        self.alignment_snaps(graph, cursor, &mut found);
+        if let Some(point) = self.grid.and_then(|grid| grid.nearest(cursor)) {
+            found.push(SnapResult::new(SnapKind::Grid, point, cursor, Vec::new()));
+        }
+
+        found.retain(|snap| snap.distance <= self.tolerance);
+        found.sort_by(|a, b| {
+            a.priority
+                .cmp(&b.priority)
+                .then(a.distance.total_cmp(&b.distance))
+                .then_with(|| a.sources.cmp(&b.sources))
+        });
+        found
+    }
+
+    /// Nodes near the cursor: free wall ends, or junctions.
+    fn node_snaps(&self, graph: &TopologyGraph, cursor: [f64; 2], found: &mut Vec<SnapResult>) {
+        for (id, position) in graph.node_index().within_radius(cursor, self.tolerance) {
+            let degree = Uuid::parse_str(id)
+                .ok()
+                .and_then(|uuid| graph.get_node(NodeId::from_uuid(uuid)))
+                .map_or(0, |node| node.degree());
+            let kind = if degree == 1 {
+                SnapKind::Endpoint
+            } else {
+                SnapKind::Node
+            };
+            found.push(SnapResult::new(
+                kind,
+                position,
+                cursor,
+                vec![id.to_string()],
+            ));
+        }
+    }
+
+    /// Midpoints of, and perpendicular feet on, walls near the cursor.
+    fn edge_snaps(&self, graph: &TopologyGraph, cursor: [f64; 2], found: &mut Vec<SnapResult>) {
+        for (edge, _) in graph.edge_index().within_distance(cursor, self.tolerance) {
+            let sources = vec![edge.id.clone()];
+            found.push(SnapResult::new(
+                SnapKind::Midpoint,
+                edge.midpoint(),
+                cursor,
+                sources.clone(),
+            ));
+            // Clamped to an end, the nearest point isn't a perpendicular foot
+            let foot = edge.closest_point(cursor);
+            if foot != edge.start && foot != edge.end {
+                found.push(SnapResult::new(
+                    SnapKind::Perpendicular,
+                    foot,
+                    cursor,
+                    sources,
+                ));
+            }
+        }
+    }
+
+    /// Apparent intersections of the walls nearest the cursor.
+    fn extension_snaps(
+        &self,
+        graph: &TopologyGraph,
+        cursor: [f64; 2],
+        found: &mut Vec<SnapResult>,
+    ) {
+        let edges = graph.edge_index().nearest(cursor, EXTENSION_EDGES);
+        for (i, (a, _)) in edges.iter().enumerate() {
+            for (b, _) in &edges[i + 1..] {
+                // A crossing within both walls is (or will heal into) a node
+                let Some((point, on_a, on_b)) = line_intersection(a, b) else {
+                    continue;
+                };
+                if on_a && on_b {
+                    continue;
+                }
+                let mut sources = vec![a.id.clone(), b.id.clone()];
+                sources.sort();
+                found.push(SnapResult::new(SnapKind::Extension, point, cursor, sources));
+            }
+        }
+    }
+
+    /// Guides through the nodes best aligned with the cursor on each axis,
+    /// combined into one snap where both line up.
+    fn alignment_snaps(
+        &self,
+        graph: &TopologyGraph,
+        cursor: [f64; 2],
+        found: &mut Vec<SnapResult>,
+    ) {
+        let vertical = self.aligned_node(graph, cursor, 0);
+        let horizontal = self.aligned_node(graph, cursor, 1);
+
+        if let (Some((v_id, v_pos)), Some((h_id, h_pos))) = (&vertical, &horizontal) {
+            let both = SnapResult::new(
+                SnapKind::Alignment,
+                [v_pos[0], h_pos[1]],
+                cursor,
+                vec![v_id.clone(), h_id.clone()],
+            );
+            if both.distance <= self.tolerance {
+                found.push(both);
+                return;
+            }
+        }
+        if let Some((id, pos)) = vertical {
+            found.push(SnapResult::new(
+                SnapKind::Alignment,
+                [pos[0], cursor[1]],
+                cursor,
+                vec![id],
+            ));
+        }
+        if let Some((id, pos)) = horizontal {
+            found.push(SnapResult::new(
+                SnapKind::Alignment,
+                [cursor[0], pos[1]],
+                cursor,
+                vec![id],
+            ));
+        }
+    }
+
+    /// The node that best matches the cursor's coordinate on `axis`,
+    /// within the alignment range along the other axis. Nodes close enough
+    /// to snap to directly are skipped.
+    fn aligned_node(
+        &self,
+        graph: &TopologyGraph,
+        cursor: [f64; 2],
+        axis: usize,
+    ) -> Option<(String, [f64; 2])> {
+        let other = 1 - axis;
+        let mut min = cursor;
+        let mut max = cursor;
+        min[axis] -= self.tolerance;
+        max[axis] += self.tolerance;
+        min[other] -= self.alignment_range;
+        max[other] += self.alignment_range;
+
+        graph
+            .node_index()
+            .in_envelope(min, max)
+            .into_iter()
+            .filter(|(_, pos)| (pos[other] - cursor[other]).abs() > self.tolerance)
+            .map(|(id, pos)| {
+                let offset = (pos[axis] - cursor[axis]).abs();
+                let along = (pos[other] - cursor[other]).abs();
+                (offset, along, id, pos)
+            })
+            .min_by(|a, b| {
+                a.0.total_cmp(&b.0)
+                    .then(a.1.total_cmp(&b.1))
+                    .then_with(|| a.2.cmp(b.2))
+            })
+            .map(|(_, _, id, pos)| (id.to_string(), pos))
+    }
+}
+
+/// Where the lines through two edges cross, and whether the crossing lies
+/// within each edge. `None` for parallel edges.
+fn line_intersection(a: &EdgeEntry, b: &EdgeEntry) -> Option<([f64; 2], bool, bool)> {
+    const EPS: f64 = 1e-9;
+    let cross = |u: [f64; 2], v: [f64; 2]| u[0] * v[1] - u[1] * v[0];
+    let da = [a.end[0] - a.start[0], a.end[1] - a.start[1]];
+    let db = [b.end[0] - b.start[0], b.end[1] - b.start[1]];
+    let denom = cross(da, db);
+    if denom.abs() <= EPS * a.length() * b.length() {
+        return None;
+    }
+    let w = [b.start[0] - a.start[0], b.start[1] - a.start[1]];
+    let t = cross(w, db) / denom;
+    let s = cross(w, da) / denom;
+    let within = |u: f64| (-EPS..=1.0 + EPS).contains(&u);
+    let point = [a.start[0] + t * da[0], a.start[1] + t * da[1]];
+    Some((point, within(t), within(s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::EdgeData;
+
+    fn graph(walls: &[([f64; 2], [f64; 2])]) -> TopologyGraph {
+        let mut graph = TopologyGraph::new();
+        for &(start, end) in walls {
+            graph.add_edge(start, end, EdgeData::wall(200.0, 2700.0));
+        }
+        graph
+    }
+
+    fn kinds(snaps: &[SnapResult]) -> Vec<SnapKind> {
+        snaps.iter().map(|s| s.kind).collect()
+    }
+
+    #[test]
+    fn node_beats_nearer_midpoint_and_foot() {
+        let graph = graph(&[([0.0, 0.0], [100.0, 0.0]), ([100.0, 0.0], [100.0, -100.0])]);
+        let engine = SnapEngine::new().with_tolerance(50.0);
+
+        let snaps = engine.candidates(&graph, [60.0, 5.0]);
+
+        assert_eq!(
+            kinds(&snaps),
+            vec![
+                SnapKind::Node,
+                SnapKind::Midpoint,
+                SnapKind::Alignment,
+                SnapKind::Perpendicular
+            ]
+        );
+        assert_eq!(snaps[0].point, [100.0, 0.0]);
+        assert!(snaps[0].distance > snaps[1].distance);
+        assert_eq!(snaps[3].point, [60.0, 0.0]);
+        assert!(snaps[3].distance < snaps[1].distance);
+        assert_eq!(engine.snap(&graph, [60.0, 5.0]), Some(snaps[0].clone()));
+    }
+
+    #[test]
+    fn free_wall_end_is_an_endpoint() {
+        let graph = graph(&[
+            ([0.0, 0.0], [1000.0, 0.0]),
+            ([1000.0, 0.0], [1000.0, 800.0]),
+        ]);
+        let engine = SnapEngine::new();
+
+        let end = engine.snap(&graph, [3.0, 4.0]).unwrap();
+        assert_eq!(
+            (end.kind, end.point, end.distance),
+            (SnapKind::Endpoint, [0.0, 0.0], 5.0)
+        );
+
+        let corner = engine.snap(&graph, [997.0, 2.0]).unwrap();
+        assert_eq!((corner.kind, corner.point), (SnapKind::Node, [1000.0, 0.0]));
+        assert_ne!(corner.sources, end.sources);
+    }
+
+    #[test]
+    fn extension_beats_alignment_at_the_same_point() {
+        // The walls' lines meet at (2000, 0), beyond both of them
+        let graph = graph(&[
+            ([0.0, 0.0], [1000.0, 0.0]),
+            ([2000.0, 500.0], [2000.0, 1000.0]),
+        ]);
+        let engine = SnapEngine::new();
+
+        let snaps = engine.candidates(&graph, [2003.0, 4.0]);
+
+        assert_eq!(
+            kinds(&snaps),
+            vec![SnapKind::Extension, SnapKind::Alignment]
+        );
+        assert_eq!(snaps[0].point, [2000.0, 0.0]);
+        assert_eq!(snaps[1].point, [2000.0, 0.0]);
+        assert_eq!(snaps[0].sources.len(), 2);
+        assert_eq!(snaps[1].sources.len(), 2);
+    }
+
+    #[test]
+    fn alignment_follows_the_nearer_guide() {
+        let graph = graph(&[([0.0, 0.0], [0.0, 3000.0])]);
+        let engine = SnapEngine::new();
+
+        // Level with the top node only
+        let snap = engine.snap(&graph, [2500.0, 3006.0]).unwrap();
+        assert_eq!(snap.kind, SnapKind::Alignment);
+        assert_eq!(snap.point, [2500.0, 3000.0]);
+
+        // Beyond the alignment range nothing lines up
+        let engine = engine.with_alignment_range(2000.0);
+        assert_eq!(engine.snap(&graph, [2500.0, 3006.0]), None);
+    }
+
+    #[test]
+    fn grid_is_the_last_resort() {
+        let graph = graph(&[([0.0, 0.0], [1000.0, 0.0])]);
+        let engine = SnapEngine::new().with_grid(SnapGrid::new(500.0).with_origin([0.0, 250.0]));
+
+        // Out of range of the grid, which would otherwise come last
+        let snaps = engine.candidates(&graph, [496.0, 4.0]);
+        assert_eq!(
+            kinds(&snaps),
+            vec![
+                SnapKind::Midpoint,
+                SnapKind::Alignment,
+                SnapKind::Perpendicular
+            ]
+        );
+
+        let snap = engine.snap(&graph, [1504.0, 748.0]).unwrap();
+        assert_eq!((snap.kind, snap.point), (SnapKind::Grid, [1500.0, 750.0]));
+        assert!(snap.sources.is_empty());
+
+        assert_eq!(engine.snap(&graph, [1520.0, 700.0]), None);
+        assert_eq!(SnapGrid::new(0.0).nearest([1.0, 1.0]), None);
+    }
+}
@@ -0,0 +1,886 @@
+//! Wall group templates.
+//!
+//! A template captures a set of walls relative to a local origin and axis,
+//! along with their openings, the doors and windows filling them, and the
+//! metadata (finishes included) of all of these, so a configured assembly
+//! such as a bathroom can be stamped elsewhere in the model.
+//!
+//! - [`create_template`] - Capture walls into a [`WallTemplate`]
+//! - [`place_template`] - Stamp a template with fresh IDs and heal the model
+//! - [`outdated_instances`] - Find placements made from an older version
+//!
+//! Placement is an `exec_and_heal` operation, so template walls split and
+//! join into the surrounding wall network and the placement is logged for
+//! replay like any other edit.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let template = create_template(&ctx, &element_ids, [0.0, 0.0], [1.0, 0.0], "Bathroom")?;
+//! let instance = place_template(&mut ctx, &template, &TemplatePlacement::at([12000.0, 4000.0]))?;
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::element::{next_element_id, ElementType};
+use crate::elements::{Door, OffsetSide, Window};
+use crate::error::{GeometryError, GeometryResult};
+use crate::exec::{exec_and_heal, Context};
+use crate::fixup::Delta;
+use crate::topology::{EdgeData, EdgeId, NodeId, TopologyGraph};
+
+/// A wall in a template, referencing template nodes by index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateWall {
+    /// Template-local ID (the ID of the wall the template was captured from)
+    pub local_id: Uuid,
+    /// Index of the start node in [`WallTemplate::nodes`]
+    pub start: usize,
+    /// Index of the end node in [`WallTemplate::nodes`]
+    pub end: usize,
+    /// Wall data, including metadata and openings with template-local IDs
+    pub data: EdgeData,
+}
+
+/// A reusable group of walls in template-local coordinates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WallTemplate {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Template name (e.g., "Bathroom A")
+    pub name: String,
+    /// Version, incremented each time the template is edited
+    pub version: u32,
+    /// Node positions relative to the template origin, with the template
+    /// axis along +X
+    pub nodes: Vec<[f64; 2]>,
+    /// Walls connecting template nodes
+    pub walls: Vec<TemplateWall>,
+    /// Doors in the template walls' openings, hosted by template-local ID
+    #[serde(default)]
+    pub doors: Vec<Door>,
+    /// Windows in the template walls' openings, hosted by template-local ID
+    #[serde(default)]
+    pub windows: Vec<Window>,
+}
+
+impl WallTemplate {
+    /// Replace the template contents with a new capture and bump the version.
+    ///
+    /// Instances placed from earlier versions become outdated.
+    pub fn recapture(
+        &mut self,
+        document: &Context,
+        element_ids: &[Uuid],
+        origin: [f64; 2],
+        axis: [f64; 2],
+    ) -> GeometryResult<()> {
+        let captured = create_template(document, element_ids, origin, axis, self.name.clone())?;
+        self.nodes = captured.nodes;
+        self.walls = captured.walls;
+        self.doors = captured.doors;
+        self.windows = captured.windows;
+        self.version += 1;
+        Ok(())
+    }
+
+    /// All template-local IDs (walls and openings), in template order.
+    pub fn local_ids(&self) -> Vec<Uuid> {
+        let mut ids = Vec::new();
+        for wall in &self.walls {
+            ids.push(wall.local_id);
+            for opening in &wall.data.openings {
+                if !ids.contains(&opening.element_id) {
+                    ids.push(opening.element_id);
+                }
+            }
+        }
+        ids
+    }
+}
+
+/// Where and how to stamp a template.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TemplatePlacement {
+    /// Model position of the template origin
+    pub at: [f64; 2],
+    /// Counter-clockwise rotation of the template axis, in degrees
+    pub rotation_deg: f64,
+    /// Mirror across the template axis before rotating
+    pub mirror: bool,
+}
+
+impl TemplatePlacement {
+    /// Place at a position without rotation or mirroring.
+    pub fn at(at: [f64; 2]) -> Self {
+        Self {
+            at,
+            rotation_deg: 0.0,
+            mirror: false,
+        }
+    }
+
+    /// Map a template-local point into model coordinates.
+    pub fn apply(&self, p: [f64; 2]) -> [f64; 2] {
+        let y = if self.mirror { -p[1] } else { p[1] };
+        let (sin, cos) = self.rotation_deg.to_radians().sin_cos();
+        [
+            self.at[0] + p[0] * cos - y * sin,
+            self.at[1] + p[0] * sin + y * cos,
+        ]
+    }
+}
+
+/// A placed copy of a template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateInstance {
+    /// Unique identifier of this placement
+    pub id: Uuid,
+    /// Template the instance was stamped from
+    pub template_id: Uuid,
+    /// Template version at the time of placement
+    pub template_version: u32,
+    /// Template-local ID → model IDs for every wall and opening
+    ///
+    /// An opening (and the door or window in it) maps to one ID. A wall
+    /// maps to the walls covering it once the placement has healed, in
+    /// order from its start: usually just its own copy, but healing may
+    /// have split it or merged it into a neighbour.
+    pub id_map: HashMap<Uuid, Vec<Uuid>>,
+}
+
+impl TemplateInstance {
+    /// Whether the instance was placed from an older version of `template`.
+    pub fn is_outdated(&self, template: &WallTemplate) -> bool {
+        self.template_id == template.id && self.template_version < template.version
+    }
+}
+
+/// Capture elements of a model into a template.
+///
+/// `element_ids` are walls, or doors and windows, which select the wall
+/// hosting them. Every selected wall is captured with its metadata and
+/// openings, and with the doors and windows filling those openings.
+/// Positions are stored relative to `origin`, rotated so that `axis` lies
+/// along +X. Elements keep their IDs as template-local IDs.
+///
+/// # Errors
+/// - `InvalidElementRef` if an ID is neither a wall nor an opening in one,
+///   or the selection is empty
+/// - `MathError` if `axis` has zero length
+pub fn create_template(
+    document: &Context,
+    element_ids: &[Uuid],
+    origin: [f64; 2],
+    axis: [f64; 2],
+    name: impl Into<String>,
+) -> GeometryResult<WallTemplate> {
+    if element_ids.is_empty() {
+        return Err(GeometryError::InvalidElementRef(
+            "empty template selection".to_string(),
+        ));
+    }
+
+    let len = (axis[0] * axis[0] + axis[1] * axis[1]).sqrt();
+    if len < 1e-15 {
+        return Err(pensaer_math::MathError::ZeroLengthVector.into());
+    }
+    let (cos, sin) = (axis[0] / len, axis[1] / len);
+
+    let graph = &document.graph;
+    let mut edge_ids: Vec<EdgeId> = Vec::with_capacity(element_ids.len());
+    for &id in element_ids {
+        let edge_id = if graph.get_edge(EdgeId::from_uuid(id)).is_some() {
+            EdgeId::from_uuid(id)
+        } else {
+            graph
+                .edges()
+                .find(|e| e.data.openings.iter().any(|o| o.element_id == id))
+                .map(|e| e.id)
+                .ok_or_else(|| GeometryError::InvalidElementRef(id.to_string()))?
+        };
+        if !edge_ids.contains(&edge_id) {
+            edge_ids.push(edge_id);
+        }
+    }
+
+    let to_local = |p: [f64; 2]| {
+        let (dx, dy) = (p[0] - origin[0], p[1] - origin[1]);
+        [dx * cos + dy * sin, -dx * sin + dy * cos]
+    };
+
+    let mut nodes = Vec::new();
+    let mut node_slots: HashMap<NodeId, usize> = HashMap::new();
+    let mut walls = Vec::with_capacity(edge_ids.len());
+
+    for &edge_id in &edge_ids {
+        let edge = graph
+            .get_edge(edge_id)
+            .ok_or_else(|| GeometryError::InvalidElementRef(edge_id.0.to_string()))?;
+
+        let mut slot = |node_id: NodeId| -> GeometryResult<usize> {
+            if let Some(&index) = node_slots.get(&node_id) {
+                return Ok(index);
+            }
+            let node = graph
+                .get_node(node_id)
+                .ok_or_else(|| GeometryError::InvalidElementRef(node_id.0.to_string()))?;
+            nodes.push(to_local(node.position));
+            node_slots.insert(node_id, nodes.len() - 1);
+            Ok(nodes.len() - 1)
+        };
+
+        let start = slot(edge.start_node)?;
+        let end = slot(edge.end_node)?;
+        walls.push(TemplateWall {
+            local_id: edge_id.0,
+            start,
+            end,
+            data: edge.data.clone(),
+        });
+    }
+
+    // Hosted elements follow the opening they fill into the template
+    let host = |id: Uuid| {
+        walls
+            .iter()
+            .find(|w| w.data.openings.iter().any(|o| o.element_id == id))
+            .map(|w| w.local_id)
+    };
+    let doors = document
+        .doors
+        .iter()
+        .filter_map(|door| {
+            let host_wall_id = host(door.id)?;
+            Some(Door {
+                host_wall_id,
+                ..door.clone()
+            })
+        })
+        .collect();
+    let windows = document
+        .windows
+        .iter()
+        .filter_map(|window| {
+            let host_wall_id = host(window.id)?;
+            Some(Window {
+                host_wall_id,
+                ..window.clone()
+            })
+        })
+        .collect();
+
+    let local_ids: Vec<Uuid> = walls.iter().map(|w| w.local_id).collect();
+    let params: Vec<f64> = nodes.iter().flatten().copied().collect();
+    Ok(WallTemplate {
+        id: next_element_id("WallTemplate", &local_ids, &params),
+        name: name.into(),
+        version: 1,
+        nodes,
+        walls,
+        doors,
+        windows,
+    })
+}
+
+/// Stamp a template into the model and heal.
+///
+/// Placement runs through [`exec_and_heal`] as a `place_template`
+/// operation, so it is logged in [`Context::op_log`] for replicas to replay,
+/// checked against the model invariants, and rolled back if healing can't
+/// repair the result.
+///
+/// Every wall, opening, door, and window, and the instance itself, gets a
+/// new ID from the context's ID generator; references between them inside
+/// the template are remapped consistently, and metadata is copied as is.
+/// Mirroring changes the hand of door swings.
+/// The instance is recorded on the context so it can later be checked
+/// with [`outdated_instances`].
+///
+/// # Errors
+/// - `OperationFailed` if a template wall collapses at placement or the
+///   healed model breaks an invariant
+pub fn place_template(
+    ctx: &mut Context,
+    template: &WallTemplate,
+    placement: &TemplatePlacement,
+) -> GeometryResult<TemplateInstance> {
+    let params = place_template_params(template, placement)?;
+    let result = exec_and_heal("place_template", &params, ctx);
+    if !result.success {
+        let reason = result.error.unwrap_or_default();
+        return Err(GeometryError::OperationFailed(reason));
+    }
+    let instance = result
+        .data
+        .and_then(|data| serde_json::from_value(data["instance"].clone()).ok())
+        .ok_or_else(|| GeometryError::OperationFailed("no instance placed".to_string()))?;
+    Ok(instance)
+}
+
+/// Parameters of a `place_template` operation.
+///
+/// The template travels as JSON text: operation parameters are quantized
+/// to 0.01, which would round its metre-valued door and window fields.
+pub fn place_template_params(
+    template: &WallTemplate,
+    placement: &TemplatePlacement,
+) -> GeometryResult<Value> {
+    let template = serde_json::to_string(template)
+        .map_err(|e| GeometryError::OperationFailed(e.to_string()))?;
+    Ok(serde_json::json!({ "template": template, "placement": placement }))
+}
+
+/// A template stamped into the graph, waiting for healing before its doors
+/// and windows are hosted.
+pub(crate) struct StampedTemplate {
+    template: WallTemplate,
+    placement: TemplatePlacement,
+    placement_params: [f64; 4],
+    /// Template opening ID → model opening ID
+    openings: HashMap<Uuid, Uuid>,
+    /// Model start and end of each template wall
+    placed: Vec<([f64; 2], [f64; 2])>,
+}
+
+/// First half of `place_template`: add the template's walls.
+pub(crate) fn stamp_template(
+    params: &Value,
+    ctx: &mut Context,
+) -> Result<(Delta, StampedTemplate), String> {
+    let template: WallTemplate = params
+        .get("template")
+        .and_then(Value::as_str)
+        .ok_or("Missing 'template' parameter")
+        .and_then(|text| serde_json::from_str(text).map_err(|_| "Invalid 'template' parameter"))?;
+    let placement: TemplatePlacement = params
+        .get("placement")
+        .cloned()
+        .ok_or("Missing 'placement' parameter")
+        .and_then(|value| {
+            serde_json::from_value(value).map_err(|_| "Invalid 'placement' parameter")
+        })?;
+
+    let placement_params = [
+        placement.at[0],
+        placement.at[1],
+        placement.rotation_deg,
+        f64::from(u8::from(placement.mirror)),
+    ];
+    let openings: HashMap<Uuid, Uuid> = template
+        .walls
+        .iter()
+        .flat_map(|w| &w.data.openings)
+        .map(|o| {
+            let id = ctx
+                .ids
+                .next_id("Opening", &[template.id, o.element_id], &placement_params);
+            (o.element_id, id)
+        })
+        .collect();
+    let mut delta = Delta::new();
+    let mut placed = Vec::with_capacity(template.walls.len());
+
+    for wall in &template.walls {
+        let start = placement.apply(template.nodes[wall.start]);
+        let end = placement.apply(template.nodes[wall.end]);
+
+        let mut data = wall.data.clone();
+        for opening in &mut data.openings {
+            opening.element_id = openings[&opening.element_id];
+        }
+
+        let params = [
+            start[0],
+            start[1],
+            end[0],
+            end[1],
+            data.height,
+            data.thickness,
+        ];
+        let edge_id = EdgeId::from_uuid(ctx.ids.next_id(ElementType::Wall.name(), &[], &params));
+        let edge_id = ctx
+            .graph
+            .add_edge_with_id(edge_id, start, end, data)
+            .ok_or("Template wall collapses at placement")?;
+        delta.created.push(edge_id.0.to_string());
+
+        if let Some(edge) = ctx.graph.get_edge(edge_id) {
+            delta.affected_nodes.push(edge.start_node.0.to_string());
+            delta.affected_nodes.push(edge.end_node.0.to_string());
+        }
+        placed.push((start, end));
+    }
+
+    let stamped = StampedTemplate {
+        template,
+        placement,
+        placement_params,
+        openings,
+        placed,
+    };
+    Ok((delta, stamped))
+}
+
+/// Second half of `place_template`, once healing has run: host doors and
+/// windows and record the instance.
+pub(crate) fn finish_template(
+    stamped: StampedTemplate,
+    delta: &Delta,
+    ctx: &mut Context,
+) -> Result<Option<Value>, String> {
+    let StampedTemplate {
+        template,
+        placement,
+        placement_params,
+        openings,
+        placed,
+    } = stamped;
+
+    // Map walls to what healing left in their place, and host each door
+    // and window on the piece its opening ended up in
+    let healed: Vec<EdgeId> = delta
+        .created
+        .iter()
+        .chain(&delta.modified)
+        .filter_map(|id| id.parse().ok().map(EdgeId::from_uuid))
+        .collect();
+    let mut id_map: HashMap<Uuid, Vec<Uuid>> = openings
+        .iter()
+        .map(|(&local, &id)| (local, vec![id]))
+        .collect();
+    let mut hosts: HashMap<Uuid, Uuid> = HashMap::new();
+    for (wall, &(start, end)) in template.walls.iter().zip(&placed) {
+        let pieces = covering_edges(&ctx.graph, &healed, start, end);
+        for opening in &wall.data.openings {
+            let centre = opening.offset + opening.width / 2.0;
+            let piece = pieces
+                .iter()
+                .find(|(_, from, to)| (*from..=*to).contains(&centre))
+                .or(pieces.first());
+            if let Some((id, _, _)) = piece {
+                hosts.insert(opening.element_id, id.0);
+            }
+        }
+        id_map.insert(
+            wall.local_id,
+            pieces.iter().map(|(id, _, _)| id.0).collect(),
+        );
+    }
+
+    for door in &template.doors {
+        let mut door = door.clone();
+        if placement.mirror {
+            door.swing = door.swing.mirrored();
+            door.opening_side = match door.opening_side {
+                OffsetSide::Left => OffsetSide::Right,
+                OffsetSide::Right => OffsetSide::Left,
+            };
+        }
+        door.host_wall_id = hosts.get(&door.id).copied().unwrap_or(door.host_wall_id);
+        door.id = openings.get(&door.id).copied().unwrap_or_else(|| {
+            ctx.ids.next_id(
+                ElementType::Door.name(),
+                &[template.id, door.id],
+                &placement_params,
+            )
+        });
+        ctx.doors.push(door);
+    }
+    for window in &template.windows {
+        let mut window = window.clone();
+        window.host_wall_id = hosts
+            .get(&window.id)
+            .copied()
+            .unwrap_or(window.host_wall_id);
+        window.id = openings.get(&window.id).copied().unwrap_or_else(|| {
+            ctx.ids.next_id(
+                ElementType::Window.name(),
+                &[template.id, window.id],
+                &placement_params,
+            )
+        });
+        ctx.windows.push(window);
+    }
+
+    let instance = TemplateInstance {
+        id: ctx
+            .ids
+            .next_id("TemplateInstance", &[template.id], &placement_params),
+        template_id: template.id,
+        template_version: template.version,
+        id_map,
+    };
+    ctx.template_instances.push(instance.clone());
+
+    let instance = serde_json::to_value(&instance).map_err(|e| e.to_string())?;
+    Ok(Some(serde_json::json!({ "instance": instance })))
+}
+
+/// Edges among `candidates` lying along the segment from `a` to `b`, with
+/// the stretch of it each covers as distances from `a`, in order.
+fn covering_edges(
+    graph: &TopologyGraph,
+    candidates: &[EdgeId],
+    a: [f64; 2],
+    b: [f64; 2],
+) -> Vec<(EdgeId, f64, f64)> {
+    let length = (b[0] - a[0]).hypot(b[1] - a[1]);
+    let (ux, uy) = ((b[0] - a[0]) / length, (b[1] - a[1]) / length);
+    let tolerance = graph.snap_tolerance();
+    // Distance along the segment and off its line
+    let project = |p: [f64; 2]| {
+        let (dx, dy) = (p[0] - a[0], p[1] - a[1]);
+        (dx * ux + dy * uy, (dy * ux - dx * uy).abs())
+    };
+
+    let mut covering: Vec<(EdgeId, f64, f64)> = candidates
+        .iter()
+        .filter_map(|&id| {
+            let (p, q) = graph.edge_positions(id)?;
+            let ((tp, off_p), (tq, off_q)) = (project(p), project(q));
+            let (from, to) = (tp.min(tq).max(0.0), tp.max(tq).min(length));
+            (off_p <= tolerance && off_q <= tolerance && to - from > tolerance)
+                .then_some((id, from, to))
+        })
+        .collect();
+    covering.sort_by(|x, y| x.1.total_cmp(&y.1));
+    covering
+}
+
+/// Instances in the model placed from an older version of `template`.
+pub fn outdated_instances<'a>(
+    ctx: &'a Context,
+    template: &WallTemplate,
+) -> Vec<&'a TemplateInstance> {
+    ctx.template_instances
+        .iter()
+        .filter(|i| i.is_outdated(template))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::DoorSwing;
+    use crate::ids::{IdMode, IdPolicy};
+    use crate::topology::OpeningRef;
+    use std::collections::HashSet;
+
+    /// Add a wall to `ctx` with a door filling an opening `offset` along it.
+    fn wall_with_door(ctx: &mut Context, start: [f64; 2], end: [f64; 2], offset: f64) -> EdgeId {
+        let mut data = EdgeData::wall(100.0, 2700.0);
+        data.metadata.set_property("Finish", "tile");
+        let edge = ctx.graph.add_edge(start, end, data).unwrap();
+        let mut door = Door::new(edge.0, 900.0, 2100.0, offset + 450.0).unwrap();
+        door.metadata.name = Some("Bathroom door".to_string());
+        ctx.graph
+            .get_edge_mut(edge)
+            .unwrap()
+            .data
+            .openings
+            .push(OpeningRef {
+                element_id: door.id,
+                offset,
+                width: 900.0,
+                height: 2100.0,
+                sill_height: 0.0,
+            });
+        ctx.doors.push(door);
+        edge
+    }
+
+    /// A U-shaped bathroom open towards -Y, with a door in its tiled back
+    /// wall.
+    fn bathroom_template() -> WallTemplate {
+        let mut source = Context::new();
+        let left = source
+            .graph
+            .add_edge([0.0, 0.0], [0.0, 2000.0], EdgeData::wall(100.0, 2700.0))
+            .unwrap();
+        let back = wall_with_door(&mut source, [0.0, 2000.0], [2500.0, 2000.0], 800.0);
+        let right = source
+            .graph
+            .add_edge(
+                [2500.0, 2000.0],
+                [2500.0, 0.0],
+                EdgeData::wall(100.0, 2700.0),
+            )
+            .unwrap();
+
+        create_template(
+            &source,
+            &[left.0, back.0, right.0],
+            [0.0, 0.0],
+            [1.0, 0.0],
+            "Bathroom",
+        )
+        .unwrap()
+    }
+
+    /// A 20m x 10m rectangle of host walls.
+    fn host_context() -> Context {
+        let mut ctx = Context::new();
+        let corners = [
+            [0.0, 0.0],
+            [20000.0, 0.0],
+            [20000.0, 10000.0],
+            [0.0, 10000.0],
+        ];
+        for i in 0..4 {
+            ctx.graph.add_edge(
+                corners[i],
+                corners[(i + 1) % 4],
+                EdgeData::wall(200.0, 2700.0),
+            );
+        }
+        ctx.graph.rebuild_rooms();
+        ctx
+    }
+
+    fn edge_length(graph: &TopologyGraph, id: Uuid) -> f64 {
+        let (a, b) = graph.edge_positions(EdgeId::from_uuid(id)).unwrap();
+        ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn create_template_uses_local_coordinates() {
+        let mut ctx = Context::new();
+        let edge = ctx
+            .graph
+            .add_edge(
+                [1000.0, 1000.0],
+                [1000.0, 4000.0],
+                EdgeData::wall(200.0, 2700.0),
+            )
+            .unwrap();
+
+        // Axis along +Y: the wall ends up along local +X
+        let template = create_template(&ctx, &[edge.0], [1000.0, 1000.0], [0.0, 2.0], "T").unwrap();
+
+        assert_eq!(template.version, 1);
+        let end = template.nodes[template.walls[0].end];
+        assert!((end[0] - 3000.0).abs() < 1e-9);
+        assert!(end[1].abs() < 1e-9);
+    }
+
+    #[test]
+    fn create_template_rejects_bad_input() {
+        let mut ctx = Context::new();
+        let edge = ctx
+            .graph
+            .add_edge([0.0, 0.0], [1000.0, 0.0], EdgeData::wall(200.0, 2700.0))
+            .unwrap();
+
+        assert!(create_template(&ctx, &[], [0.0, 0.0], [1.0, 0.0], "T").is_err());
+        assert!(create_template(&ctx, &[edge.0], [0.0, 0.0], [0.0, 0.0], "T").is_err());
+        assert!(create_template(&ctx, &[Uuid::new_v4()], [0.0, 0.0], [1.0, 0.0], "T").is_err());
+    }
+
+    #[test]
+    fn create_template_captures_hosted_elements() {
+        let mut ctx = Context::new();
+        ctx.graph
+            .add_edge([0.0, 0.0], [0.0, 3000.0], EdgeData::wall(100.0, 2700.0))
+            .unwrap();
+        let wall = wall_with_door(&mut ctx, [0.0, 3000.0], [3000.0, 3000.0], 1000.0);
+        let door_id = ctx.doors[0].id;
+
+        // Selecting the door captures its wall, with metadata
+        let template = create_template(&ctx, &[door_id], [0.0, 0.0], [1.0, 0.0], "T").unwrap();
+
+        assert_eq!(template.walls.len(), 1);
+        assert_eq!(template.walls[0].local_id, wall.0);
+        assert_eq!(
+            template.walls[0]
+                .data
+                .metadata
+                .get_property("Finish")
+                .map(String::as_str),
+            Some("tile")
+        );
+        assert_eq!(template.doors.len(), 1);
+        assert_eq!(template.doors[0].id, door_id);
+        assert_eq!(template.doors[0].host_wall_id, wall.0);
+        assert_eq!(
+            template.doors[0].metadata.name.as_deref(),
+            Some("Bathroom door")
+        );
+        assert!(template.windows.is_empty());
+    }
+
+    #[test]
+    fn placement_rotates_and_mirrors() {
+        let placement = TemplatePlacement {
+            at: [10.0, 20.0],
+            rotation_deg: 90.0,
+            mirror: true,
+        };
+
+        // (1, 2) -> mirror (1, -2) -> rotate 90 (2, 1) -> translate
+        let p = placement.apply([1.0, 2.0]);
+        assert!((p[0] - 12.0).abs() < 1e-9);
+        assert!((p[1] - 21.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn place_template_twice_heals_into_host_walls() {
+        let template = bathroom_template();
+        let mut ctx = host_context();
+        assert_eq!(ctx.graph.interior_rooms().len(), 1);
+
+        // Bathrooms hang off the top host wall, open side down onto it
+        let place = |x: f64| TemplatePlacement {
+            at: [x, 10000.0],
+            rotation_deg: 180.0,
+            mirror: false,
+        };
+        let first = place_template(&mut ctx, &template, &place(4000.0)).unwrap();
+        let second = place_template(&mut ctx, &template, &place(12000.0)).unwrap();
+
+        // Fresh, disjoint IDs for walls and openings; no wall was split
+        assert!(first.id_map.values().all(|ids| ids.len() == 1));
+        let first_ids: HashSet<Uuid> = first.id_map.values().flatten().copied().collect();
+        let second_ids: HashSet<Uuid> = second.id_map.values().flatten().copied().collect();
+        assert_eq!(first_ids.len(), 4);
+        assert!(first_ids.is_disjoint(&second_ids));
+        for local_id in template.local_ids() {
+            assert!(!first_ids.contains(&local_id));
+        }
+
+        // Identical internal dimensions
+        for wall in &template.walls {
+            let a = edge_length(&ctx.graph, first.id_map[&wall.local_id][0]);
+            let b = edge_length(&ctx.graph, second.id_map[&wall.local_id][0]);
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        // Door reference and element follow their remapped wall, which
+        // keeps its finish
+        let back = &template.walls[1];
+        let door_local = back.data.openings[0].element_id;
+        let back_id = first.id_map[&back.local_id][0];
+        let back_edge = ctx.graph.get_edge(EdgeId::from_uuid(back_id)).unwrap();
+        let door_id = first.id_map[&door_local][0];
+        assert_eq!(back_edge.data.openings[0].element_id, door_id);
+        assert_eq!(
+            back_edge
+                .data
+                .metadata
+                .get_property("Finish")
+                .map(String::as_str),
+            Some("tile")
+        );
+        assert_eq!(ctx.doors.len(), 2);
+        let door = ctx.doors.iter().find(|d| d.id == door_id).unwrap();
+        assert_eq!(door.host_wall_id, back_id);
+        assert_eq!(door.metadata.name.as_deref(), Some("Bathroom door"));
+        assert_eq!(door.swing, DoorSwing::Left);
+
+        // Host top wall split at both bathroom legs; each bathroom is a room
+        assert_eq!(ctx.graph.node_count(), 4 + 2 * 4);
+        assert_eq!(ctx.graph.edge_count(), 4 + 4 + 2 * 3);
+        assert_eq!(ctx.graph.interior_rooms().len(), 3);
+        assert_eq!(ctx.template_instances.len(), 2);
+    }
+
+    #[test]
+    fn placed_walls_map_to_their_healed_pieces() {
+        let mut source = Context::new();
+        let wall = wall_with_door(&mut source, [0.0, 0.0], [4000.0, 0.0], 3000.0);
+        let template = create_template(&source, &[wall.0], [0.0, 0.0], [1.0, 0.0], "T").unwrap();
+        let door_local = template.doors[0].id;
+
+        // Mirrored across the right host wall, which splits it
+        let mut ctx = host_context();
+        let placement = TemplatePlacement {
+            at: [18000.0, 5000.0],
+            rotation_deg: 0.0,
+            mirror: true,
+        };
+        let instance = place_template(&mut ctx, &template, &placement).unwrap();
+
+        let pieces = &instance.id_map[&wall.0];
+        assert_eq!(pieces.len(), 2);
+        let lengths: Vec<f64> = pieces
+            .iter()
+            .map(|&id| edge_length(&ctx.graph, id))
+            .collect();
+        assert!((lengths[0] - 2000.0).abs() < 1e-6);
+        assert!((lengths[1] - 2000.0).abs() < 1e-6);
+
+        // The door is hosted on the far piece, where its opening is, and
+        // swings the other way
+        let door = &ctx.doors[0];
+        assert_eq!(door.id, instance.id_map[&door_local][0]);
+        assert_eq!(door.host_wall_id, pieces[1]);
+        assert_eq!(door.swing, DoorSwing::Right);
+        assert_eq!(door.opening_side, OffsetSide::Right);
+    }
+
+    #[test]
+    fn recapture_marks_instances_outdated() {
+        let mut template = bathroom_template();
+        let mut ctx = host_context();
+        place_template(
+            &mut ctx,
+            &template,
+            &TemplatePlacement::at([3000.0, 3000.0]),
+        )
+        .unwrap();
+        assert!(outdated_instances(&ctx, &template).is_empty());
+
+        let mut source = Context::new();
+        let wall = source
+            .graph
+            .add_edge([0.0, 0.0], [3000.0, 0.0], EdgeData::wall(100.0, 2700.0))
+            .unwrap();
+        template
+            .recapture(&source, &[wall.0], [0.0, 0.0], [1.0, 0.0])
+            .unwrap();
+
+        assert_eq!(template.version, 2);
+        assert!(template.doors.is_empty());
+        assert_eq!(outdated_instances(&ctx, &template).len(), 1);
+    }
+
+    #[test]
+    fn placement_is_logged_and_replays() {
+        let ids = IdPolicy::new(IdMode::ContentHash {
+            namespace: Uuid::from_u128(0x7e57),
+        });
+        let template = bathroom_template();
+        let placement = TemplatePlacement {
+            at: [4000.0, 10000.0],
+            rotation_deg: 180.0,
+            mirror: true,
+        };
+        let mut ctx = host_context().with_id_generator(ids.clone());
+        let instance = place_template(&mut ctx, &template, &placement).unwrap();
+
+        assert_eq!(ctx.op_log.len(), 1);
+        assert_eq!(ctx.op_log[0].method, "place_template");
+        assert_eq!(ctx.revision, 1);
+
+        // A replica replaying the log places the same elements
+        let mut replica = host_context().with_id_generator(ids);
+        for op in &ctx.op_log {
+            assert!(exec_and_heal(&op.method, &op.params, &mut replica).success);
+        }
+        assert_eq!(replica.template_instances.len(), 1);
+        assert_eq!(replica.template_instances[0].id, instance.id);
+        assert_eq!(replica.doors.len(), 1);
+        assert_eq!(replica.doors[0].id, ctx.doors[0].id);
+        assert_eq!(replica.doors[0].host_wall_id, ctx.doors[0].host_wall_id);
+        assert_eq!(replica.doors[0].swing, ctx.doors[0].swing);
+        assert_eq!(replica.doors[0].width, ctx.doors[0].width);
+        assert_eq!(
+            replica.graph.interior_rooms().len(),
+            ctx.graph.interior_rooms().len()
+        );
+    }
+}
@@ -0,0 +1,1169 @@
+//! Corridor detection and circulation networks.
+//!
+//! Corridors are long, narrow interior rooms that connect several other
+//! rooms through doors. Their centerlines approximate the medial axis of the
+//! room polygon: the room is rasterized, each cell gets its clearance
+//! (distance to the nearest wall), and the region is thinned to a one-cell
+//! skeleton that is traced into polylines. Short spurs, such as the branches
+//! the medial axis sends into convex corners, are pruned. Door connection
+//! points are then attached and branches that lead to no door are trimmed.
+//!
+//! [`circulation_network`] joins corridor centerlines with straight paths
+//! from every other room's interior point to its doors, giving a walkable
+//! graph for travel-distance queries.
+//!
+//! Doors are the floor-level openings (`sill_height <= 0`) on room boundary
+//! edges. An opening's `offset` is taken to its near side, so the door point
+//! is `offset + width / 2` along the edge. Lengths are in graph units
+//! (millimeters by convention).
+
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use pensaer_math::{LineSegment2, Point2, Polygon2};
+use uuid::Uuid;
+
+use super::visit::Visit;
+use super::{RoomId, TopoRoom, TopologyGraph};
+use crate::error::{GeometryError, GeometryResult};
+
+/// Target raster resolution along the longer side of a room.
+const RASTER_CELLS: f64 = 200.0;
+
+/// Minimum number of cells across the estimated room width.
+const CELLS_ACROSS: f64 = 16.0;
+
+/// Upper bound on the raster size, in cells.
+const MAX_RASTER_CELLS: f64 = 1_000_000.0;
+
+/// Leaf branches shorter than this multiple of the clearance at their
+/// junction are spurs.
+const SPUR_FACTOR: f64 = 2.0;
+
+/// Criteria a room must meet to be classified as a corridor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorridorCriteria {
+    /// Maximum estimated width.
+    pub max_width: f64,
+    /// Minimum ratio of end-to-end length to width.
+    pub min_aspect_ratio: f64,
+    /// Minimum number of other rooms reached through doors.
+    pub connects_n_rooms: usize,
+}
+
+impl Default for CorridorCriteria {
+    fn default() -> Self {
+        Self {
+            max_width: 2400.0,
+            min_aspect_ratio: 3.0,
+            connects_n_rooms: 2,
+        }
+    }
+}
+
+/// Kind of a circulation node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CirculationNodeKind {
+    /// A door, identified by its opening element.
+    Door(Uuid),
+    /// A point where three or more centerline branches meet.
+    Junction,
+    /// A dead end of a centerline.
+    End,
+    /// The interior point of a room that is not a corridor.
+    Room(RoomId),
+}
+
+/// A node of a centerline or circulation network.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CirculationNode {
+    /// Position in graph coordinates.
+    pub position: [f64; 2],
+    /// What the node stands for.
+    pub kind: CirculationNodeKind,
+}
+
+/// A walkable path between two circulation nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CirculationEdge {
+    /// Index of the start node.
+    pub from: usize,
+    /// Index of the end node.
+    pub to: usize,
+    /// Polyline from `from` to `to`, including both end positions.
+    pub path: Vec<[f64; 2]>,
+    /// Length of the path.
+    pub length: f64,
+    /// Room the path runs through.
+    pub room_id: RoomId,
+}
+
+/// Approximate medial axis of a room, trimmed to its doors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Centerline {
+    /// Room the centerline belongs to.
+    pub room_id: RoomId,
+    /// Estimated clear width (twice the median clearance along the axis).
+    pub width: f64,
+    /// Doors, junctions, and dead ends.
+    pub nodes: Vec<CirculationNode>,
+    /// Centerline branches between nodes.
+    pub edges: Vec<CirculationEdge>,
+}
+
+impl Centerline {
+    /// Total length of all branches.
+    pub fn length(&self) -> f64 {
+        self.edges.iter().map(|e| e.length).sum()
+    }
+}
+
+/// A room classified as a corridor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Corridor {
+    /// The corridor room.
+    pub room_id: RoomId,
+    /// Estimated clear width.
+    pub width: f64,
+    /// End-to-end length of the untrimmed medial axis.
+    pub length: f64,
+    /// Other interior rooms reached through the corridor's doors.
+    pub connected_rooms: Vec<RoomId>,
+    /// Centerline trimmed to the doors.
+    pub centerline: Centerline,
+}
+
+/// Walkable graph of corridor centerlines and room-to-door paths.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CirculationNetwork {
+    /// Doors, junctions, dead ends, and room points.
+    pub nodes: Vec<CirculationNode>,
+    /// Paths between nodes.
+    pub edges: Vec<CirculationEdge>,
+}
+
+impl CirculationNetwork {
+    /// Total length of all paths.
+    pub fn total_length(&self) -> f64 {
+        self.edges.iter().map(|e| e.length).sum()
+    }
+
+    /// Index of the node for a door.
+    pub fn door_node(&self, door_id: Uuid) -> Option<usize> {
+        self.nodes
+            .iter()
+            .position(|n| n.kind == CirculationNodeKind::Door(door_id))
+    }
+
+    /// Index of the interior-point node for a room that is not a corridor.
+    pub fn room_node(&self, room_id: RoomId) -> Option<usize> {
+        self.nodes
+            .iter()
+            .position(|n| n.kind == CirculationNodeKind::Room(room_id))
+    }
+
+    /// Check if every node can be reached from every other.
+    pub fn is_connected(&self) -> bool {
+        if self.nodes.is_empty() {
+            return true;
+        }
+        let adjacency = self.adjacency();
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack = vec![0];
+        seen[0] = true;
+        while let Some(n) = stack.pop() {
+            for &(m, _) in &adjacency[n] {
+                if !seen[m] {
+                    seen[m] = true;
+                    stack.push(m);
+                }
+            }
+        }
+        seen.into_iter().all(|s| s)
+    }
+
+    /// Shortest walking distance between two nodes, or `None` if they are
+    /// not connected.
+    pub fn shortest_distance(&self, from: usize, to: usize) -> Option<f64> {
+        if from >= self.nodes.len() || to >= self.nodes.len() {
+            return None;
+        }
+        let adjacency = self.adjacency();
+        let mut dist = vec![f64::INFINITY; self.nodes.len()];
+        let mut heap = BinaryHeap::new();
+        dist[from] = 0.0;
+        heap.push(Visit {
+            distance: 0.0,
+            node: from,
+        });
+
+        while let Some(Visit { distance, node }) = heap.pop() {
+            if node == to {
+                return Some(distance);
+            }
+            if distance > dist[node] {
+                continue;
+            }
+            for &(next, length) in &adjacency[node] {
+                let candidate = distance + length;
+                if candidate < dist[next] {
+                    dist[next] = candidate;
+                    heap.push(Visit {
+                        distance: candidate,
+                        node: next,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn adjacency(&self) -> Vec<Vec<(usize, f64)>> {
+        let mut adjacency = vec![Vec::new(); self.nodes.len()];
+        for edge in &self.edges {
+            adjacency[edge.from].push((edge.to, edge.length));
+            adjacency[edge.to].push((edge.from, edge.length));
+        }
+        adjacency
+    }
+
+    /// Add a node, reusing the existing node for the same door.
+    fn add_node(&mut self, node: CirculationNode) -> usize {
+        if let CirculationNodeKind::Door(id) = node.kind {
+            if let Some(index) = self.door_node(id) {
+                return index;
+            }
+        }
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+}
+
+/// Extract the approximate medial axis of a room.
+///
+/// With two or more doors, branches that lead to no door are trimmed so the
+/// centerline runs from door to door; otherwise the spur-free medial axis is
+/// kept, with any door connected to it.
+pub fn extract_centerline(graph: &TopologyGraph, room: &TopoRoom) -> GeometryResult<Centerline> {
+    analyze_room(graph, room).map(|(centerline, _)| centerline)
+}
+
+/// Find the rooms that meet the corridor criteria.
+///
+/// Rooms must already be detected (see [`TopologyGraph::rebuild_rooms`]).
+/// Results are ordered by room centroid.
+pub fn classify_corridors(graph: &TopologyGraph, criteria: &CorridorCriteria) -> Vec<Corridor> {
+    let mut corridors = Vec::new();
+
+    for room in sorted_interior_rooms(graph) {
+        let connected_rooms = connected_rooms(graph, room);
+        if connected_rooms.len() < criteria.connects_n_rooms {
+            continue;
+        }
+        let Ok((centerline, length)) = analyze_room(graph, room) else {
+            continue;
+        };
+        let width = centerline.width;
+        if width <= 0.0 || width > criteria.max_width || length / width < criteria.min_aspect_ratio
+        {
+            continue;
+        }
+
+        corridors.push(Corridor {
+            room_id: room.id,
+            width,
+            length,
+            connected_rooms,
+            centerline,
+        });
+    }
+
+    corridors
+}
+
+/// Build the circulation network of a floor plan.
+///
+/// Corridors contribute their door-trimmed centerlines. Every other interior
+/// room contributes a node at an interior point with a straight path to each
+/// of its doors. Door nodes are shared between the rooms on either side.
+pub fn circulation_network(
+    graph: &TopologyGraph,
+    criteria: &CorridorCriteria,
+) -> CirculationNetwork {
+    let mut network = CirculationNetwork::default();
+    let corridors = classify_corridors(graph, criteria);
+    let corridor_ids: HashSet<RoomId> = corridors.iter().map(|c| c.room_id).collect();
+
+    for corridor in &corridors {
+        let centerline = &corridor.centerline;
+        let indices: Vec<usize> = centerline
+            .nodes
+            .iter()
+            .map(|n| network.add_node(n.clone()))
+            .collect();
+        for edge in &centerline.edges {
+            network.edges.push(CirculationEdge {
+                from: indices[edge.from],
+                to: indices[edge.to],
+                ..edge.clone()
+            });
+        }
+    }
+
+    for room in sorted_interior_rooms(graph) {
+        if corridor_ids.contains(&room.id) {
+            continue;
+        }
+        let Ok(polygon) = room_polygon(graph, room) else {
+            continue;
+        };
+        let point = interior_point(&polygon);
+        let room_node = network.add_node(CirculationNode {
+            position: point,
+            kind: CirculationNodeKind::Room(room.id),
+        });
+
+        for (door_id, position) in door_points(graph, room) {
+            let door_node = network.add_node(CirculationNode {
+                position,
+                kind: CirculationNodeKind::Door(door_id),
+            });
+            network.edges.push(CirculationEdge {
+                from: room_node,
+                to: door_node,
+                path: vec![point, position],
+                length: distance(point, position),
+                room_id: room.id,
+            });
+        }
+    }
+
+    network
+}
+
+/// Interior rooms in a deterministic order.
+fn sorted_interior_rooms(graph: &TopologyGraph) -> Vec<&TopoRoom> {
+    let mut rooms = graph.interior_rooms();
+    rooms.sort_by(|a, b| {
+        a.centroid[0]
+            .total_cmp(&b.centroid[0])
+            .then_with(|| a.centroid[1].total_cmp(&b.centroid[1]))
+    });
+    rooms
+}
+
+/// Compute a room's centerline and the end-to-end length of its untrimmed
+/// medial axis.
+fn analyze_room(graph: &TopologyGraph, room: &TopoRoom) -> GeometryResult<(Centerline, f64)> {
+    if room.is_exterior {
+        return Err(GeometryError::CenterlineFailed(
+            "exterior region has no centerline".to_string(),
+        ));
+    }
+
+    let polygon = room_polygon(graph, room)?;
+    let raster = Raster::new(&polygon)?;
+    let mut skeleton = Skeleton::trace(&raster);
+    skeleton.prune_spurs(&raster);
+    if skeleton.edges.iter().all(|e| !e.alive) && skeleton.nodes.is_empty() {
+        return Err(GeometryError::CenterlineFailed(
+            "room is too small to rasterize".to_string(),
+        ));
+    }
+
+    let width = 2.0 * skeleton.median_clearance(&raster);
+    // The medial axis stops about half a width short of each end
+    let length = skeleton.length() + width;
+
+    let doors = door_points(graph, room);
+    for (door_id, position) in &doors {
+        skeleton.attach_door(*door_id, *position, raster.cell);
+    }
+    if doors.len() >= 2 {
+        skeleton.trim_to_doors();
+    }
+
+    Ok((
+        skeleton.into_centerline(room.id, width, raster.cell),
+        length,
+    ))
+}
+
+/// The room boundary as a polygon.
+fn room_polygon(graph: &TopologyGraph, room: &TopoRoom) -> GeometryResult<Polygon2> {
+    let vertices = room
+        .boundary_nodes
+        .iter()
+        .map(|id| {
+            graph
+                .get_node(*id)
+                .map(|n| Point2::new(n.position[0], n.position[1]))
+                .ok_or_else(|| GeometryError::InvalidElementRef(id.to_string()))
+        })
+        .collect::<GeometryResult<Vec<_>>>()?;
+    Polygon2::new(vertices).map_err(|_| GeometryError::InsufficientVertices)
+}
+
+/// Doors on a room's boundary, as (opening ID, door point).
+fn door_points(graph: &TopologyGraph, room: &TopoRoom) -> Vec<(Uuid, [f64; 2])> {
+    let mut seen = HashSet::new();
+    let mut doors = Vec::new();
+
+    for edge_id in &room.boundary_edges {
+        if !seen.insert(*edge_id) {
+            continue;
+        }
+        let (Some(edge), Some((start, end))) =
+            (graph.get_edge(*edge_id), graph.edge_positions(*edge_id))
+        else {
+            continue;
+        };
+        let length = distance(start, end);
+        if length <= 0.0 {
+            continue;
+        }
+        for opening in edge.data.openings.iter().filter(|o| o.sill_height <= 0.0) {
+            let t = ((opening.offset + opening.width / 2.0) / length).clamp(0.0, 1.0);
+            doors.push((
+                opening.element_id,
+                [
+                    start[0] + t * (end[0] - start[0]),
+                    start[1] + t * (end[1] - start[1]),
+                ],
+            ));
+        }
+    }
+
+    doors
+}
+
+/// Other interior rooms sharing a door with this room.
+fn connected_rooms(graph: &TopologyGraph, room: &TopoRoom) -> Vec<RoomId> {
+    let mut connected = Vec::new();
+
+    for edge_id in &room.boundary_edges {
+        let has_door = graph
+            .get_edge(*edge_id)
+            .is_some_and(|e| e.data.openings.iter().any(|o| o.sill_height <= 0.0));
+        if !has_door {
+            continue;
+        }
+        for other in graph.rooms_at_edge(*edge_id) {
+            let interior = graph.get_room(other).is_some_and(|r| !r.is_exterior);
+            if other != room.id && interior && !connected.contains(&other) {
+                connected.push(other);
+            }
+        }
+    }
+
+    connected
+}
+
+/// A point inside the polygon: the centroid if it is inside, otherwise the
+/// point farthest from the boundary.
+fn interior_point(polygon: &Polygon2) -> [f64; 2] {
+    let centroid = polygon.centroid();
+    if polygon.contains_point(&centroid) {
+        return [centroid.x, centroid.y];
+    }
+    match Raster::new(polygon) {
+        Ok(raster) => raster.deepest_point(),
+        Err(_) => [centroid.x, centroid.y],
+    }
+}
+
+fn distance(a: [f64; 2], b: [f64; 2]) -> f64 {
+    (b[0] - a[0]).hypot(b[1] - a[1])
+}
+
+fn path_length(path: &[[f64; 2]]) -> f64 {
+    path.windows(2).map(|w| distance(w[0], w[1])).sum()
+}
+
+/// Douglas-Peucker simplification.
+fn simplify(path: &[[f64; 2]], tolerance: f64) -> Vec<[f64; 2]> {
+    if path.len() < 3 {
+        return path.to_vec();
+    }
+    let last = path.len() - 1;
+    let chord = LineSegment2::new(
+        Point2::new(path[0][0], path[0][1]),
+        Point2::new(path[last][0], path[last][1]),
+    );
+    let (index, deviation) = path[1..last]
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (i + 1, chord.distance_to_point(&Point2::new(p[0], p[1]))))
+        .fold((0, 0.0), |best, d| if d.1 > best.1 { d } else { best });
+
+    if deviation <= tolerance {
+        return vec![path[0], path[last]];
+    }
+    let mut simplified = simplify(&path[..=index], tolerance);
+    simplified.pop();
+    simplified.extend(simplify(&path[index..], tolerance));
+    simplified
+}
+
+/// A room polygon sampled on a grid, with the clearance of each inside cell.
+struct Raster {
+    /// Center of cell (0, 0).
+    origin: [f64; 2],
+    cell: f64,
+    cols: usize,
+    rows: usize,
+    /// Distance to the nearest boundary edge, or `None` outside the room.
+    clearance: Vec<Option<f64>>,
+}
+
+impl Raster {
+    fn new(polygon: &Polygon2) -> GeometryResult<Self> {
+        let bbox = polygon
+            .bounding_box()
+            .ok_or(GeometryError::InsufficientVertices)?;
+        let width = bbox.max.x - bbox.min.x;
+        let height = bbox.max.y - bbox.min.y;
+        let perimeter = polygon.perimeter();
+        if width <= 0.0 || height <= 0.0 || perimeter <= 0.0 {
+            return Err(GeometryError::CenterlineFailed(
+                "room has no area".to_string(),
+            ));
+        }
+
+        // Fine enough to resolve the narrowest part (2A/P estimates the
+        // width of a thin shape), but bounded in total size
+        let estimated_width = 2.0 * polygon.area() / perimeter;
+        let cell = (width.max(height) / RASTER_CELLS)
+            .min(estimated_width / CELLS_ACROSS)
+            .max((width * height / MAX_RASTER_CELLS).sqrt());
+
+        // One empty cell of padding on every side
+        let cols = (width / cell).ceil() as usize + 2;
+        let rows = (height / cell).ceil() as usize + 2;
+        let origin = [bbox.min.x - 0.5 * cell, bbox.min.y - 0.5 * cell];
+
+        let edges: Vec<LineSegment2> = polygon.edges().collect();
+        let mut clearance = vec![None; cols * rows];
+        for row in 1..rows - 1 {
+            for col in 1..cols - 1 {
+                let center =
+                    Point2::new(origin[0] + col as f64 * cell, origin[1] + row as f64 * cell);
+                if polygon.contains_point(&center) {
+                    let d = edges
+                        .iter()
+                        .map(|e| e.distance_to_point(&center))
+                        .fold(f64::INFINITY, f64::min);
+                    clearance[row * cols + col] = Some(d);
+                }
+            }
+        }
+
+        Ok(Self {
+            origin,
+            cell,
+            cols,
+            rows,
+            clearance,
+        })
+    }
+
+    fn center(&self, index: usize) -> [f64; 2] {
+        let col = index % self.cols;
+        let row = index / self.cols;
+        [
+            self.origin[0] + col as f64 * self.cell,
+            self.origin[1] + row as f64 * self.cell,
+        ]
+    }
+
+    /// Clearance of the cell containing a point, zero outside the room.
+    fn clearance_at(&self, p: [f64; 2]) -> f64 {
+        let col = ((p[0] - self.origin[0]) / self.cell).round();
+        let row = ((p[1] - self.origin[1]) / self.cell).round();
+        if col < 0.0 || row < 0.0 || col >= self.cols as f64 || row >= self.rows as f64 {
+            return 0.0;
+        }
+        self.clearance[row as usize * self.cols + col as usize].unwrap_or(0.0)
+    }
+
+    /// Center of the cell farthest from the boundary.
+    fn deepest_point(&self) -> [f64; 2] {
+        let index = self
+            .clearance
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| c.map(|c| (i, c)))
+            .fold(
+                (0, f64::NEG_INFINITY),
+                |best, c| {
+                    if c.1 > best.1 {
+                        c
+                    } else {
+                        best
+                    }
+                },
+            )
+            .0;
+        self.center(index)
+    }
+
+    /// Thin the inside cells to a one-cell skeleton (Zhang-Suen).
+    fn thin(&self) -> Vec<bool> {
+        let cols = self.cols;
+        let mut image: Vec<bool> = self.clearance.iter().map(Option::is_some).collect();
+
+        loop {
+            let mut changed = false;
+            for pass in 0..2 {
+                let mut removed = Vec::new();
+                for row in 1..self.rows - 1 {
+                    for col in 1..cols - 1 {
+                        let i = row * cols + col;
+                        if !image[i] {
+                            continue;
+                        }
+                        // N, NE, E, SE, S, SW, W, NW
+                        let p = [
+                            image[i + cols],
+                            image[i + cols + 1],
+                            image[i + 1],
+                            image[i - cols + 1],
+                            image[i - cols],
+                            image[i - cols - 1],
+                            image[i - 1],
+                            image[i + cols - 1],
+                        ];
+                        let count = p.iter().filter(|&&b| b).count();
+                        let transitions = (0..8).filter(|&k| !p[k] && p[(k + 1) % 8]).count();
+                        if !(2..=6).contains(&count) || transitions != 1 {
+                            continue;
+                        }
+                        let (n, e, s, w) = (p[0], p[2], p[4], p[6]);
+                        let keep = if pass == 0 {
+                            e && s && (n || w)
+                        } else {
+                            n && w && (e || s)
+                        };
+                        if !keep {
+                            removed.push(i);
+                        }
+                    }
+                }
+                changed |= !removed.is_empty();
+                for i in removed {
+                    image[i] = false;
+                }
+            }
+            if !changed {
+                return image;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SkeletonNode {
+    position: [f64; 2],
+    door: Option<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+struct SkeletonEdge {
+    a: usize,
+    b: usize,
+    path: Vec<[f64; 2]>,
+    alive: bool,
+}
+
+impl SkeletonEdge {
+    fn other(&self, node: usize) -> usize {
+        if self.a == node {
+            self.b
+        } else {
+            self.a
+        }
+    }
+
+    /// Path oriented to start at `node`.
+    fn path_from(&self, node: usize) -> Vec<[f64; 2]> {
+        let mut path = self.path.clone();
+        if self.a != node {
+            path.reverse();
+        }
+        path
+    }
+}
+
+/// Graph of skeleton branches, edited in place by pruning and trimming.
+#[derive(Debug, Default)]
+struct Skeleton {
+    nodes: Vec<SkeletonNode>,
+    edges: Vec<SkeletonEdge>,
+}
+
+impl Skeleton {
+    /// Trace the thinned raster into branches between nodes, where nodes
+    /// are the cells with other than two skeleton neighbours.
+    fn trace(raster: &Raster) -> Self {
+        let image = raster.thin();
+        let cols = raster.cols;
+
+        // 8-neighbours, dropping diagonals that a shared 4-neighbour already
+        // connects, so staircase steps do not look like junctions
+        let neighbours = |i: usize| -> Vec<usize> {
+            let (n, s, e, w) = (i + cols, i - cols, i + 1, i - 1);
+            let mut out: Vec<usize> = [n, e, s, w].into_iter().filter(|&j| image[j]).collect();
+            for (d, a, b) in [(n + 1, n, e), (s + 1, s, e), (s - 1, s, w), (n - 1, n, w)] {
+                if image[d] && !image[a] && !image[b] {
+                    out.push(d);
+                }
+            }
+            out
+        };
+
+        let pixels: Vec<usize> = (0..image.len()).filter(|&i| image[i]).collect();
+        let adjacency: HashMap<usize, Vec<usize>> =
+            pixels.iter().map(|&i| (i, neighbours(i))).collect();
+
+        let mut skeleton = Self::default();
+        let mut node_of: HashMap<usize, usize> = HashMap::new();
+        for &i in &pixels {
+            if adjacency[&i].len() != 2 {
+                node_of.insert(i, skeleton.push_node(raster.center(i), None));
+            }
+        }
+
+        let mut visited: HashSet<(usize, usize)> = HashSet::new();
+        let link = |a: usize, b: usize| (a.min(b), a.max(b));
+        let mut starts: Vec<usize> = node_of.keys().copied().collect();
+        starts.sort_unstable();
+
+        // Branches from nodes, then node-free loops
+        for start in starts.into_iter().chain(pixels.iter().copied()) {
+            if let Entry::Vacant(slot) = node_of.entry(start) {
+                if adjacency[&start]
+                    .iter()
+                    .any(|&j| visited.contains(&link(start, j)))
+                {
+                    continue;
+                }
+                slot.insert(skeleton.push_node(raster.center(start), None));
+            }
+            for &first in &adjacency[&start] {
+                if !visited.insert(link(start, first)) {
+                    continue;
+                }
+                let mut path = vec![raster.center(start), raster.center(first)];
+                let (mut prev, mut current) = (start, first);
+                while !node_of.contains_key(&current) {
+                    let Some(&next) = adjacency[&current].iter().find(|&&j| j != prev) else {
+                        break;
+                    };
+                    visited.insert(link(current, next));
+                    path.push(raster.center(next));
+                    prev = current;
+                    current = next;
+                }
+                if let Some(&end) = node_of.get(&current) {
+                    skeleton.push_edge(node_of[&start], end, path);
+                }
+            }
+        }
+
+        skeleton
+    }
+
+    fn push_node(&mut self, position: [f64; 2], door: Option<Uuid>) -> usize {
+        self.nodes.push(SkeletonNode { position, door });
+        self.nodes.len() - 1
+    }
+
+    fn push_edge(&mut self, a: usize, b: usize, path: Vec<[f64; 2]>) {
+        self.edges.push(SkeletonEdge {
+            a,
+            b,
+            path,
+            alive: true,
+        });
+    }
+
+    fn edges_at(&self, node: usize) -> Vec<usize> {
+        (0..self.edges.len())
+            .filter(|&e| {
+                self.edges[e].alive && (self.edges[e].a == node || self.edges[e].b == node)
+            })
+            .collect()
+    }
+
+    /// Number of branch ends at a node (a loop counts twice).
+    fn degree(&self, node: usize) -> usize {
+        self.edges
+            .iter()
+            .filter(|e| e.alive)
+            .map(|e| usize::from(e.a == node) + usize::from(e.b == node))
+            .sum()
+    }
+
+    fn length(&self) -> f64 {
+        self.edges
+            .iter()
+            .filter(|e| e.alive)
+            .map(|e| path_length(&e.path))
+            .sum()
+    }
+
+    fn median_clearance(&self, raster: &Raster) -> f64 {
+        let mut samples: Vec<f64> = self
+            .edges
+            .iter()
+            .filter(|e| e.alive)
+            .flat_map(|e| e.path.iter().map(|p| raster.clearance_at(*p)))
+            .collect();
+        if samples.is_empty() {
+            return self
+                .nodes
+                .iter()
+                .map(|n| raster.clearance_at(n.position))
+                .fold(0.0, f64::max);
+        }
+        samples.sort_by(f64::total_cmp);
+        samples[samples.len() / 2]
+    }
+
+    /// Join the two branches at every plain node of degree two.
+    fn merge_chains(&mut self) {
+        loop {
+            let mut merged = false;
+            for node in 0..self.nodes.len() {
+                if self.nodes[node].door.is_some() {
+                    continue;
+                }
+                let at = self.edges_at(node);
+                if at.len() != 2 || self.degree(node) != 2 {
+                    continue;
+                }
+                let (e1, e2) = (at[0], at[1]);
+                let mut path = self.edges[e1].path_from(node);
+                path.reverse();
+                path.extend(self.edges[e2].path_from(node).into_iter().skip(1));
+                let (a, b) = (self.edges[e1].other(node), self.edges[e2].other(node));
+                self.edges[e1].alive = false;
+                self.edges[e2].alive = false;
+                self.push_edge(a, b, path);
+                merged = true;
+            }
+            if !merged {
+                return;
+            }
+        }
+    }
+
+    /// Remove short leaf branches, keeping at least one branch per junction.
+    fn prune_spurs(&mut self, raster: &Raster) {
+        loop {
+            self.merge_chains();
+            let mut pruned = false;
+            for node in 0..self.nodes.len() {
+                let at = self.edges_at(node);
+                if self.degree(node) < 3 {
+                    continue;
+                }
+                let limit = SPUR_FACTOR * raster.clearance_at(self.nodes[node].position);
+                let mut spurs: Vec<usize> = at
+                    .iter()
+                    .copied()
+                    .filter(|&e| {
+                        let leaf = self.edges[e].other(node);
+                        leaf != node
+                            && self.degree(leaf) == 1
+                            && path_length(&self.edges[e].path) < limit
+                    })
+                    .collect();
+                if spurs.len() == at.len() {
+                    // Keep the longest so the junction's branch survives
+                    spurs.sort_by(|&x, &y| {
+                        path_length(&self.edges[x].path)
+                            .total_cmp(&path_length(&self.edges[y].path))
+                    });
+                    spurs.pop();
+                }
+                for e in spurs {
+                    self.edges[e].alive = false;
+                    pruned = true;
+                }
+            }
+            if !pruned {
+                return;
+            }
+        }
+    }
+
+    /// Connect a door to the nearest point of the skeleton.
+    fn attach_door(&mut self, door_id: Uuid, position: [f64; 2], cell: f64) {
+        let door = self.push_node(position, Some(door_id));
+        let target = Point2::new(position[0], position[1]);
+
+        // Nearest point over all branch segments: (edge, segment, point, distance)
+        let mut nearest: Option<(usize, usize, [f64; 2], f64)> = None;
+        for (e, edge) in self.edges.iter().enumerate().filter(|(_, e)| e.alive) {
+            for k in 0..edge.path.len().saturating_sub(1) {
+                let segment = LineSegment2::new(
+                    Point2::new(edge.path[k][0], edge.path[k][1]),
+                    Point2::new(edge.path[k + 1][0], edge.path[k + 1][1]),
+                );
+                let q = segment.closest_point(&target);
+                let d = q.distance_to(&target);
+                let closer = match nearest {
+                    Some(n) => d < n.3,
+                    None => true,
+                };
+                if closer {
+                    nearest = Some((e, k, [q.x, q.y], d));
+                }
+            }
+        }
+
+        let junction = match nearest {
+            Some((e, k, q, _)) => {
+                let edge = self.edges[e].clone();
+                if distance(q, edge.path[0]) <= cell {
+                    edge.a
+                } else if distance(q, edge.path[edge.path.len() - 1]) <= cell {
+                    edge.b
+                } else {
+                    let split = self.push_node(q, None);
+                    let mut first = edge.path[..=k].to_vec();
+                    first.push(q);
+                    let mut second = vec![q];
+                    second.extend_from_slice(&edge.path[k + 1..]);
+                    self.edges[e].alive = false;
+                    self.push_edge(edge.a, split, first);
+                    self.push_edge(split, edge.b, second);
+                    split
+                }
+            }
+            // No branches: connect to the nearest remaining node, if any
+            None => match (0..door)
+                .filter(|&n| self.nodes[n].door.is_none())
+                .min_by(|&x, &y| {
+                    distance(self.nodes[x].position, position)
+                        .total_cmp(&distance(self.nodes[y].position, position))
+                }) {
+                Some(node) => node,
+                None => return,
+            },
+        };
+
+        let end = self.nodes[junction].position;
+        self.push_edge(door, junction, vec![position, end]);
+    }
+
+    /// Remove branches that end without reaching a door.
+    fn trim_to_doors(&mut self) {
+        loop {
+            self.merge_chains();
+            let mut trimmed = false;
+            for e in 0..self.edges.len() {
+                let edge = &self.edges[e];
+                if !edge.alive || edge.a == edge.b {
+                    continue;
+                }
+                let dead_end = |n: usize| self.nodes[n].door.is_none() && self.degree(n) == 1;
+                let door_leaf = |n: usize| self.nodes[n].door.is_some() && self.degree(n) == 1;
+                let (a, b) = (edge.a, edge.b);
+                if (dead_end(a) && !door_leaf(b)) || (dead_end(b) && !door_leaf(a)) {
+                    self.edges[e].alive = false;
+                    trimmed = true;
+                }
+            }
+            if !trimmed {
+                return;
+            }
+        }
+    }
+
+    fn into_centerline(mut self, room_id: RoomId, width: f64, cell: f64) -> Centerline {
+        self.merge_chains();
+
+        let mut index: HashMap<usize, usize> = HashMap::new();
+        let mut nodes = Vec::new();
+        let mut node_index = |n: usize, skeleton: &Self| -> usize {
+            *index.entry(n).or_insert_with(|| {
+                let node = &skeleton.nodes[n];
+                let kind = match node.door {
+                    Some(id) => CirculationNodeKind::Door(id),
+                    None if skeleton.degree(n) >= 3 => CirculationNodeKind::Junction,
+                    None => CirculationNodeKind::End,
+                };
+                nodes.push(CirculationNode {
+                    position: node.position,
+                    kind,
+                });
+                nodes.len() - 1
+            })
+        };
+
+        let mut edges = Vec::new();
+        for edge in self.edges.iter().filter(|e| e.alive) {
+            let from = node_index(edge.a, &self);
+            let to = node_index(edge.b, &self);
+            let path = simplify(&edge.path, cell);
+            edges.push(CirculationEdge {
+                from,
+                to,
+                length: path_length(&path),
+                path,
+                room_id,
+            });
+        }
+        // Doors that could not be connected still appear
+        for n in 0..self.nodes.len() {
+            if self.nodes[n].door.is_some() {
+                node_index(n, &self);
+            }
+        }
+
+        Centerline {
+            room_id,
+            width,
+            nodes,
+            edges,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::topology::{EdgeData, OpeningRef};
+
+    /// Wall with a 900 mm door centered `at` mm from its start.
+    fn door_wall(at: f64) -> (EdgeData, Uuid) {
+        let mut data = EdgeData::wall(100.0, 2700.0);
+        let id = Uuid::new_v4();
+        data.openings.push(OpeningRef {
+            element_id: id,
+            offset: at - 450.0,
+            width: 900.0,
+            height: 2100.0,
+            sill_height: 0.0,
+        });
+        (data, id)
+    }
+
+    /// An H-shaped corridor (legs at x 4-6 m and 10-12 m, crossbar at
+    /// y 4-6 m) with six rooms, each with one door onto the corridor.
+    fn h_plan() -> TopologyGraph {
+        let mut graph = TopologyGraph::new();
+        let mut wall = |a: [f64; 2], b: [f64; 2]| {
+            graph.add_edge(a, b, EdgeData::wall(100.0, 2700.0));
+        };
+
+        // Outer walls, split where inner walls meet them
+        let xs = [0.0, 4000.0, 6000.0, 10000.0, 12000.0, 16000.0];
+        for pair in xs.windows(2) {
+            wall([pair[0], 0.0], [pair[1], 0.0]);
+            wall([pair[0], 10000.0], [pair[1], 10000.0]);
+        }
+        for y in [0.0, 5000.0] {
+            wall([0.0, y], [0.0, y + 5000.0]);
+            wall([16000.0, y], [16000.0, y + 5000.0]);
+        }
+
+        // Partitions without doors
+        wall([0.0, 5000.0], [4000.0, 5000.0]);
+        wall([12000.0, 5000.0], [16000.0, 5000.0]);
+        wall([6000.0, 0.0], [6000.0, 4000.0]);
+        wall([6000.0, 6000.0], [6000.0, 10000.0]);
+        wall([10000.0, 0.0], [10000.0, 4000.0]);
+        wall([10000.0, 6000.0], [10000.0, 10000.0]);
+
+        // Corridor walls with one door per room
+        let doors = [
+            ([4000.0, 0.0], [4000.0, 5000.0], 2500.0),
+            ([4000.0, 5000.0], [4000.0, 10000.0], 2500.0),
+            ([12000.0, 0.0], [12000.0, 5000.0], 2500.0),
+            ([12000.0, 5000.0], [12000.0, 10000.0], 2500.0),
+            ([6000.0, 4000.0], [10000.0, 4000.0], 2000.0),
+            ([6000.0, 6000.0], [10000.0, 6000.0], 2000.0),
+        ];
+        for (a, b, at) in doors {
+            graph.add_edge(a, b, door_wall(at).0);
+        }
+
+        graph.rebuild_rooms();
+        graph
+    }
+
+    #[test]
+    fn h_corridor_centerline_and_network() {
+        let graph = h_plan();
+        assert_eq!(graph.interior_rooms().len(), 7);
+
+        let corridors = classify_corridors(&graph, &CorridorCriteria::default());
+        assert_eq!(corridors.len(), 1);
+        let corridor = &corridors[0];
+        assert_eq!(corridor.connected_rooms.len(), 6);
+        assert!((corridor.width - 2000.0).abs() < 100.0);
+
+        // By hand: legs from door to door (2 x 5 m), the crossbar between
+        // leg centerlines (6 m), and 1 m from each door to the centerline
+        let centerline = &corridor.centerline;
+        let expected = 2.0 * 5000.0 + 6000.0 + 6.0 * 1000.0;
+        assert!(
+            (centerline.length() - expected).abs() < 0.03 * expected,
+            "centerline length {}",
+            centerline.length()
+        );
+        let doors = centerline
+            .nodes
+            .iter()
+            .filter(|n| matches!(n.kind, CirculationNodeKind::Door(_)))
+            .count();
+        assert_eq!(doors, 6);
+
+        let network = circulation_network(&graph, &CorridorCriteria::default());
+        assert!(network.is_connected());
+        let rooms: Vec<usize> = network
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| matches!(n.kind, CirculationNodeKind::Room(_)))
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(rooms.len(), 6);
+
+        // Walking between rooms is never shorter than the straight line
+        for &a in &rooms {
+            for &b in &rooms {
+                let walk = network.shortest_distance(a, b).unwrap();
+                let straight = distance(network.nodes[a].position, network.nodes[b].position);
+                assert!(walk >= straight - 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn rectangular_room_centerline() {
+        let mut graph = TopologyGraph::new();
+        let corners = [[0.0, 0.0], [10000.0, 0.0], [10000.0, 2000.0], [0.0, 2000.0]];
+        for i in 0..4 {
+            graph.add_edge(
+                corners[i],
+                corners[(i + 1) % 4],
+                EdgeData::wall(100.0, 2700.0),
+            );
+        }
+        graph.rebuild_rooms();
+        let room = graph.interior_rooms()[0].clone();
+
+        // Without doors the spur-free medial axis runs the long way
+        let centerline = extract_centerline(&graph, &room).unwrap();
+        assert_eq!(centerline.edges.len(), 1);
+        assert!((centerline.length() - 8000.0).abs() < 200.0);
+        assert!((centerline.width - 2000.0).abs() < 100.0);
+        for node in &centerline.nodes {
+            assert!((node.position[1] - 1000.0).abs() < 100.0);
+        }
+    }
+}
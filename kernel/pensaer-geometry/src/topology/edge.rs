@@ -1,6 +1,7 @@
 //! Topology edge representing a wall segment between two nodes.
 
 use super::NodeId;
+use crate::element::ElementMetadata;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -49,6 +50,10 @@ pub struct EdgeData {
 
     /// Optional openings on this edge
     pub openings: Vec<OpeningRef>,
+
+    /// Wall metadata, such as finishes in its custom properties
+    #[serde(default)]
+    pub metadata: ElementMetadata,
 }
 
 impl EdgeData {
@@ -60,6 +65,7 @@ impl EdgeData {
             baseline: Baseline::Center,
             wall_type_id: None,
             openings: Vec::new(),
+            metadata: ElementMetadata::new(),
         }
     }
 
@@ -71,6 +77,7 @@ impl EdgeData {
             baseline,
             wall_type_id: None,
             openings: Vec::new(),
+            metadata: ElementMetadata::new(),
         }
     }
 }
@@ -93,7 +100,8 @@ pub struct OpeningRef {
     /// ID of the opening element
     pub element_id: Uuid,
 
-    /// Offset along the edge from start node (mm)
+    /// Distance along the edge from the start node to the near side of the
+    /// opening (mm), not to its centre
     pub offset: f64,
 
     /// Width of the opening (mm)
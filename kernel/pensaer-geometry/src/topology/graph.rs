@@ -1,12 +1,15 @@
 //! The main topology graph structure.
 
-use super::edge::{EdgeData, EdgeId, TopoEdge};
+use super::edge::{Baseline, EdgeData, EdgeId, TopoEdge};
 use super::node::{NodeId, TopoNode};
 use super::room::{HalfEdge, RoomId, TopoRoom};
+use super::visit::Visit;
 use crate::constants::SNAP_MERGE_TOL;
 use crate::spatial::{EdgeIndex, NodeIndex};
-use crate::util::float::points2_within;
-use std::collections::{HashMap, HashSet};
+use crate::util::float::{dist2, points2_within};
+use pensaer_math::{Point2, Polygon2};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use uuid::Uuid;
 
 /// The topology graph storing the wall network.
 ///
@@ -17,7 +20,7 @@ use std::collections::{HashMap, HashSet};
 /// - R*-tree spatial indexes for efficient range queries
 /// - Automatic node merging within SNAP_MERGE_TOL
 /// - Room detection via boundary tracing
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TopologyGraph {
     /// All nodes in the graph
     nodes: HashMap<NodeId, TopoNode>,
@@ -28,6 +31,9 @@ pub struct TopologyGraph {
     /// All detected rooms (closed regions)
     rooms: HashMap<RoomId, TopoRoom>,
 
+    /// Room corners by node: where each room's boundary turns at that node
+    room_turns: HashMap<NodeId, Vec<RoomTurn>>,
+
     /// Spatial index for nodes
     node_index: NodeIndex,
 
@@ -45,6 +51,7 @@ impl TopologyGraph {
             nodes: HashMap::new(),
             edges: HashMap::new(),
             rooms: HashMap::new(),
+            room_turns: HashMap::new(),
             node_index: NodeIndex::new(),
             edge_index: EdgeIndex::new(),
             snap_tolerance: SNAP_MERGE_TOL,
@@ -57,6 +64,7 @@ impl TopologyGraph {
             nodes: HashMap::new(),
             edges: HashMap::new(),
             rooms: HashMap::new(),
+            room_turns: HashMap::new(),
             node_index: NodeIndex::new(),
             edge_index: EdgeIndex::new(),
             snap_tolerance,
@@ -114,6 +122,48 @@ impl TopologyGraph {
         id
     }
 
+    /// Find the node closest to a point.
+    ///
+    /// Returns the node ID and its position, or `None` if the graph is empty.
+    pub fn nearest_node(&self, point: [f64; 2]) -> Option<(NodeId, [f64; 2])> {
+        let (id_str, pos) = self.node_index.nearest(point)?;
+        let id = NodeId::from_uuid(Uuid::parse_str(&id_str).ok()?);
+        self.nodes.contains_key(&id).then_some((id, pos))
+    }
+
+    /// Find the edge closest to a point, measured to the segment.
+    ///
+    /// Returns the edge ID and the distance, or `None` if there are no edges.
+    pub fn nearest_edge(&self, point: [f64; 2]) -> Option<(EdgeId, f64)> {
+        let (entry, distance) = self.edge_index.nearest(point, 1).pop()?;
+        let id = EdgeId::from_uuid(Uuid::parse_str(&entry.id).ok()?);
+        self.edges.contains_key(&id).then_some((id, distance))
+    }
+
+    /// Find the first edge a ray hits within `max_distance`.
+    ///
+    /// Returns the edge ID, the distance along the ray, and the hit point.
+    pub fn first_edge_hit(
+        &self,
+        origin: [f64; 2],
+        direction: [f64; 2],
+        max_distance: f64,
+    ) -> Option<(EdgeId, f64, [f64; 2])> {
+        let (entry, t, point) = self.edge_index.raycast(origin, direction, max_distance)?;
+        let id = EdgeId::from_uuid(Uuid::parse_str(&entry.id).ok()?);
+        self.edges.contains_key(&id).then_some((id, t, point))
+    }
+
+    /// The spatial index of node positions, keyed by node UUID string.
+    pub fn node_index(&self) -> &NodeIndex {
+        &self.node_index
+    }
+
+    /// The spatial index of edge segments, keyed by edge UUID string.
+    pub fn edge_index(&self) -> &EdgeIndex {
+        &self.edge_index
+    }
+
     /// Get all nodes within a radius of a point.
     pub fn nodes_within(&self, center: [f64; 2], radius: f64) -> Vec<NodeId> {
         self.nodes
@@ -139,6 +189,38 @@ impl TopologyGraph {
         self.nodes.values()
     }
 
+    /// Move a node to a new position, updating the spatial indexes.
+    ///
+    /// Connected edges follow the node. No merging or splitting is done here;
+    /// that is left to the fixup passes. Returns false if the node doesn't exist.
+    pub fn move_node(&mut self, node_id: NodeId, position: [f64; 2]) -> bool {
+        let (old_pos, edge_ids) = match self.nodes.get(&node_id) {
+            Some(n) => (n.position, n.edges.iter().copied().collect::<Vec<_>>()),
+            None => return false,
+        };
+
+        // Remove connected edges from the index while they still have old positions
+        for &edge_id in &edge_ids {
+            if let Some((start, end)) = self.edge_positions(edge_id) {
+                self.edge_index.remove(&edge_id.0.to_string(), start, end);
+            }
+        }
+
+        self.node_index.remove(&node_id.0.to_string(), old_pos);
+        self.node_index.insert(node_id.0.to_string(), position);
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            node.position = position;
+        }
+
+        for &edge_id in &edge_ids {
+            if let Some((start, end)) = self.edge_positions(edge_id) {
+                self.edge_index.insert(edge_id.0.to_string(), start, end);
+            }
+        }
+
+        true
+    }
+
     // =========================================================================
     // Edge Operations
     // =========================================================================
@@ -168,6 +250,24 @@ impl TopologyGraph {
         end_pos: [f64; 2],
         data: EdgeData,
     ) -> Option<EdgeId> {
+        self.add_edge_with_id(EdgeId::new(), start_pos, end_pos, data)
+    }
+
+    /// Add an edge with a given ID between two positions.
+    ///
+    /// As [`add_edge`](Self::add_edge); also returns None if the ID is
+    /// already in use.
+    pub fn add_edge_with_id(
+        &mut self,
+        edge_id: EdgeId,
+        start_pos: [f64; 2],
+        end_pos: [f64; 2],
+        data: EdgeData,
+    ) -> Option<EdgeId> {
+        if self.edges.contains_key(&edge_id) {
+            return None;
+        }
+
         // Don't create zero-length edges
         if points2_within(start_pos, end_pos, self.snap_tolerance) {
             return None;
@@ -183,8 +283,8 @@ impl TopologyGraph {
         }
 
         // Create edge
-        let edge = TopoEdge::new(start_node, end_node, data);
-        let edge_id = edge.id;
+        let mut edge = TopoEdge::new(start_node, end_node, data);
+        edge.id = edge_id;
 
         // Get node positions for edge index
         let start = self.nodes.get(&start_node).unwrap().position;
@@ -255,61 +355,82 @@ impl TopologyGraph {
     /// - All edges referencing either node now reference the merged node
     /// - Duplicate edges are removed
     ///
+    /// Candidates are found through the node spatial index, so the pass
+    /// scales with the number of nearby pairs rather than all node pairs.
+    ///
     /// Returns the number of nodes merged.
     pub fn snap_merge_nodes(&mut self) -> usize {
-        let mut merged_count = 0;
-        let mut merge_map: HashMap<NodeId, NodeId> = HashMap::new();
+        self.snap_merge_nodes_with(self.snap_tolerance)
+    }
 
-        // Find all node pairs within tolerance using union-find approach
+    /// Merge nodes within `tolerance` of each other, rather than the
+    /// graph's snap tolerance; see [`snap_merge_nodes`](Self::snap_merge_nodes).
+    ///
+    /// Lets a single pass merge more loosely (say, cleaning up an import)
+    /// without changing how new nodes snap.
+    pub fn snap_merge_nodes_with(&mut self, tolerance: f64) -> usize {
         let node_ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+        let order: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (*id, i))
+            .collect();
 
-        for i in 0..node_ids.len() {
-            let id_a = node_ids[i];
+        // Union-find over node order; each root is the surviving node of its cluster
+        let mut parent: Vec<usize> = (0..node_ids.len()).collect();
+        let mut merged_count = 0;
 
+        for i in 0..node_ids.len() {
             // Skip if already merged
-            if merge_map.contains_key(&id_a) {
+            if parent[i] != i {
                 continue;
             }
 
-            let pos_a = match self.nodes.get(&id_a) {
-                Some(n) => n.position,
+            let id_a = node_ids[i];
+            let (pos_a, pinned_a) = match self.nodes.get(&id_a) {
+                Some(n) => (n.position, n.pinned),
                 None => continue,
             };
 
             // Skip pinned nodes
-            if self.nodes.get(&id_a).map(|n| n.pinned).unwrap_or(false) {
+            if pinned_a {
                 continue;
             }
 
-            for &id_b in node_ids.iter().skip(i + 1) {
-                // Skip if already merged
-                if merge_map.contains_key(&id_b) {
+            // Query the spatial index for merge candidates later in node order
+            let mut candidates: Vec<(usize, [f64; 2])> = self
+                .node_index
+                .within_radius(pos_a, tolerance)
+                .into_iter()
+                .filter_map(|(id_str, pos)| {
+                    let id = NodeId::from_uuid(Uuid::parse_str(id_str).ok()?);
+                    let j = *order.get(&id)?;
+                    (j > i && points2_within(pos_a, pos, tolerance)).then_some((j, pos))
+                })
+                .collect();
+            candidates.sort_unstable_by_key(|(j, _)| *j);
+
+            for (j, pos_b) in candidates {
+                // Skip if already merged, or pinned
+                if parent[j] != j || self.nodes.get(&node_ids[j]).is_some_and(|n| n.pinned) {
                     continue;
                 }
 
-                let pos_b = match self.nodes.get(&id_b) {
-                    Some(n) => n.position,
-                    None => continue,
-                };
-
-                // Skip pinned nodes
-                if self.nodes.get(&id_b).map(|n| n.pinned).unwrap_or(false) {
-                    continue;
+                // Merge b into a; position is the midpoint with the last merged node
+                parent[j] = i;
+                if let Some(node_a) = self.nodes.get_mut(&id_a) {
+                    node_a.position = [(pos_a[0] + pos_b[0]) / 2.0, (pos_a[1] + pos_b[1]) / 2.0];
                 }
 
-                // Check if within tolerance
-                if points2_within(pos_a, pos_b, self.snap_tolerance) {
-                    // Merge b into a
-                    merge_map.insert(id_b, id_a);
-
-                    // Update position to midpoint
-                    if let Some(node_a) = self.nodes.get_mut(&id_a) {
-                        node_a.position =
-                            [(pos_a[0] + pos_b[0]) / 2.0, (pos_a[1] + pos_b[1]) / 2.0];
-                    }
+                merged_count += 1;
+            }
+        }
 
-                    merged_count += 1;
-                }
+        let mut merge_map: HashMap<NodeId, NodeId> = HashMap::new();
+        for j in 0..node_ids.len() {
+            let root = find_root(&mut parent, j);
+            if root != j {
+                merge_map.insert(node_ids[j], node_ids[root]);
             }
         }
 
@@ -406,7 +527,7 @@ impl TopologyGraph {
     pub fn clear(&mut self) {
         self.nodes.clear();
         self.edges.clear();
-        self.rooms.clear();
+        self.clear_rooms();
         self.node_index = NodeIndex::new();
         self.edge_index = EdgeIndex::new();
     }
@@ -519,6 +640,52 @@ impl TopologyGraph {
         Some((split_node, edge1_id, edge2_id))
     }
 
+    /// Move one end of an edge from `from` to the existing node `to`.
+    ///
+    /// The edge keeps its ID and data. `from` is removed if this leaves it
+    /// orphaned. Returns false if the edge doesn't end at `from`, `to`
+    /// doesn't exist, or the edge would collapse onto a single node.
+    pub fn reconnect_edge(&mut self, edge_id: EdgeId, from: NodeId, to: NodeId) -> bool {
+        let (start_node, end_node) = match self.edges.get(&edge_id) {
+            Some(e) => (e.start_node, e.end_node),
+            None => return false,
+        };
+        let other = if start_node == from {
+            end_node
+        } else if end_node == from {
+            start_node
+        } else {
+            return false;
+        };
+        if other == to || !self.nodes.contains_key(&to) {
+            return false;
+        }
+
+        if let Some((start, end)) = self.edge_positions(edge_id) {
+            self.edge_index.remove(&edge_id.0.to_string(), start, end);
+        }
+
+        if let Some(edge) = self.edges.get_mut(&edge_id) {
+            if edge.start_node == from {
+                edge.start_node = to;
+            } else {
+                edge.end_node = to;
+            }
+        }
+        if let Some(node) = self.nodes.get_mut(&from) {
+            node.remove_edge(edge_id);
+        }
+        if let Some(node) = self.nodes.get_mut(&to) {
+            node.add_edge(edge_id);
+        }
+
+        if let Some((start, end)) = self.edge_positions(edge_id) {
+            self.edge_index.insert(edge_id.0.to_string(), start, end);
+        }
+        self.remove_if_orphaned(from);
+        true
+    }
+
     /// Get all edge IDs as a vector.
     pub fn edge_ids(&self) -> Vec<EdgeId> {
         self.edges.keys().copied().collect()
@@ -565,6 +732,116 @@ impl TopologyGraph {
         self.edges.get(&edge_id)?.other_node(node_id)
     }
 
+    /// Group nodes into connected components by walking their edges.
+    ///
+    /// Components are returned in a stable order: nodes are sorted by ID
+    /// and each component starts from its lowest remaining node.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut ids = self.node_ids();
+        ids.sort_by_key(|id| id.0);
+
+        let mut seen = HashSet::new();
+        let mut components = Vec::new();
+        for id in ids {
+            if !seen.insert(id) {
+                continue;
+            }
+            let mut component = vec![id];
+            let mut queue = VecDeque::from([id]);
+            while let Some(node_id) = queue.pop_front() {
+                for edge_id in self.edges_at_node(node_id) {
+                    if let Some(next) = self.other_node(edge_id, node_id) {
+                        if seen.insert(next) {
+                            component.push(next);
+                            queue.push_back(next);
+                        }
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Check if the graph forms a single connected wall network.
+    ///
+    /// An empty graph is not considered connected.
+    pub fn is_connected(&self) -> bool {
+        self.connected_components().len() == 1
+    }
+
+    /// Shortest route along walls between two nodes, as the edges walked.
+    ///
+    /// Edges are weighted by their baseline length. Returns an empty path
+    /// when `from == to`, and `None` if either node is missing or the two
+    /// are not connected.
+    pub fn shortest_path(&self, from: NodeId, to: NodeId) -> Option<Vec<EdgeId>> {
+        if !self.nodes.contains_key(&from) || !self.nodes.contains_key(&to) {
+            return None;
+        }
+        let mut ids = self.node_ids();
+        ids.sort_by_key(|id| id.0);
+        let index: HashMap<NodeId, usize> =
+            ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let (start, target) = (index[&from], index[&to]);
+
+        let mut dist = vec![f64::INFINITY; ids.len()];
+        let mut via: Vec<Option<EdgeId>> = vec![None; ids.len()];
+        let mut heap = BinaryHeap::new();
+        dist[start] = 0.0;
+        heap.push(Visit {
+            distance: 0.0,
+            node: start,
+        });
+
+        while let Some(Visit { distance, node }) = heap.pop() {
+            if node == target {
+                break;
+            }
+            if distance > dist[node] {
+                continue;
+            }
+            let mut edge_ids = self.edges_at_node(ids[node]);
+            edge_ids.sort_by_key(|id| id.0);
+            for edge_id in edge_ids {
+                let Some(next) = self.other_node(edge_id, ids[node]) else {
+                    continue;
+                };
+                let next = index[&next];
+                let candidate = distance + self.path_length(&[edge_id]);
+                if candidate < dist[next] {
+                    dist[next] = candidate;
+                    via[next] = Some(edge_id);
+                    heap.push(Visit {
+                        distance: candidate,
+                        node: next,
+                    });
+                }
+            }
+        }
+
+        if dist[target].is_infinite() {
+            return None;
+        }
+        let mut path = Vec::new();
+        let mut node = target;
+        while node != start {
+            let edge_id = via[node]?;
+            path.push(edge_id);
+            node = index[&self.other_node(edge_id, ids[node])?];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Total baseline length of a path of edges. Missing edges count as zero.
+    pub fn path_length(&self, path: &[EdgeId]) -> f64 {
+        path.iter()
+            .filter_map(|id| self.edge_positions(*id))
+            .map(|(start, end)| dist2(start, end))
+            .sum()
+    }
+
     /// Get the snap tolerance.
     pub fn snap_tolerance(&self) -> f64 {
         self.snap_tolerance
@@ -584,6 +861,14 @@ impl TopologyGraph {
         self.rooms.get(&id)
     }
 
+    /// Get a mutable reference to a room.
+    ///
+    /// Only metadata such as the name should be changed; the boundary is
+    /// indexed by node and must stay as traced.
+    pub fn get_room_mut(&mut self, id: RoomId) -> Option<&mut TopoRoom> {
+        self.rooms.get_mut(&id)
+    }
+
     /// Iterate over all rooms.
     pub fn rooms(&self) -> impl Iterator<Item = &TopoRoom> {
         self.rooms.values()
@@ -601,11 +886,13 @@ impl TopologyGraph {
 
     /// Find rooms containing a specific node.
     pub fn rooms_at_node(&self, node_id: NodeId) -> Vec<RoomId> {
-        self.rooms
-            .iter()
-            .filter(|(_, room)| room.contains_node(node_id))
-            .map(|(id, _)| *id)
-            .collect()
+        let mut ids: Vec<RoomId> = Vec::new();
+        for turn in self.room_turns.get(&node_id).into_iter().flatten() {
+            if !ids.contains(&turn.room) {
+                ids.push(turn.room);
+            }
+        }
+        ids
     }
 
     /// Find rooms containing a specific edge.
@@ -617,6 +904,129 @@ impl TopologyGraph {
             .collect()
     }
 
+    /// Pairs of interior rooms on either side of an edge, with that edge.
+    ///
+    /// Rooms sharing a wall split into several edges are listed once per
+    /// edge. Each pair is ordered by room ID, and the list is sorted, so
+    /// the result does not depend on hash order. Call
+    /// [`rebuild_rooms`](Self::rebuild_rooms) first.
+    pub fn room_adjacency(&self) -> Vec<(RoomId, RoomId, EdgeId)> {
+        let mut sides: HashMap<EdgeId, Vec<RoomId>> = HashMap::new();
+        for room in self.rooms.values().filter(|r| !r.is_exterior) {
+            for edge_id in &room.boundary_edges {
+                let rooms = sides.entry(*edge_id).or_default();
+                // A spur inside a room appears twice in its boundary
+                if !rooms.contains(&room.id) {
+                    rooms.push(room.id);
+                }
+            }
+        }
+
+        let mut adjacency: Vec<(RoomId, RoomId, EdgeId)> = sides
+            .into_iter()
+            .filter_map(|(edge_id, rooms)| match rooms[..] {
+                [a, b] if a.0 < b.0 => Some((a, b, edge_id)),
+                [a, b] => Some((b, a, edge_id)),
+                _ => None,
+            })
+            .collect();
+        adjacency.sort_by_key(|(a, b, e)| (a.0, b.0, e.0));
+        adjacency
+    }
+
+    /// Interior rooms sharing at least one edge with `room`, sorted by ID.
+    pub fn rooms_sharing_wall(&self, room: RoomId) -> Vec<RoomId> {
+        let mut neighbors: Vec<RoomId> = self
+            .room_adjacency()
+            .into_iter()
+            .filter_map(|(a, b, _)| {
+                if a == room {
+                    Some(b)
+                } else if b == room {
+                    Some(a)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        neighbors.dedup();
+        neighbors
+    }
+
+    /// The room's interior wall-face polygon.
+    ///
+    /// Each boundary edge is offset into the room by as much of its wall
+    /// as lies on the room side (half the thickness for a centred wall,
+    /// all or none of it for a left or right baseline), and adjacent offset
+    /// edges are intersected. Where consecutive edges run straight on but
+    /// their faces do not line up, as at a change of thickness, the face
+    /// steps across at the shared node.
+    ///
+    /// Returns None for the exterior region, an unknown room, or a face
+    /// that collapses to fewer than three points.
+    pub fn room_face_polygon(&self, room: RoomId) -> Option<Polygon2> {
+        let room = self.rooms.get(&room)?;
+        if room.is_exterior {
+            return None;
+        }
+        // Interior side of each half-edge: left when the loop runs CCW
+        let side = if room.signed_area >= 0.0 { 1.0 } else { -1.0 };
+
+        // Offset line (point on the face, unit direction) per half-edge
+        let mut lines = Vec::with_capacity(room.half_edges.len());
+        for he in &room.half_edges {
+            let edge = self.edges.get(&he.edge_id)?;
+            let from = self.nodes.get(&he.from_node)?.position;
+            let to = self.nodes.get(&he.to_node)?.position;
+            let length = dist2(from, to);
+            if length == 0.0 {
+                continue;
+            }
+            let dir = [(to[0] - from[0]) / length, (to[1] - from[1]) / length];
+            let inward = [-dir[1] * side, dir[0] * side];
+
+            // Does the room lie on the edge's own left?
+            let room_on_left = (he.from_node == edge.start_node) == (side > 0.0);
+            let t = edge.data.thickness;
+            let depth = match (edge.data.baseline, room_on_left) {
+                (Baseline::Center, _) => t / 2.0,
+                (Baseline::Left, true) | (Baseline::Right, false) => t,
+                (Baseline::Left, false) | (Baseline::Right, true) => 0.0,
+            };
+            let point = [from[0] + inward[0] * depth, from[1] + inward[1] * depth];
+            lines.push((point, dir, to, inward, depth));
+        }
+
+        let n = lines.len();
+        let mut vertices = Vec::with_capacity(n);
+        for i in 0..n {
+            let (p, d, corner, inward, depth) = lines[i];
+            let (q, e, _, next_inward, next_depth) = lines[(i + 1) % n];
+            let cross = d[0] * e[1] - d[1] * e[0];
+            if cross.abs() > 1e-9 {
+                let t = ((q[0] - p[0]) * e[1] - (q[1] - p[1]) * e[0]) / cross;
+                vertices.push(Point2::new(p[0] + d[0] * t, p[1] + d[1] * t));
+                continue;
+            }
+            // Straight on (or doubling back): end this face and start the next
+            // at the shared node
+            let end = Point2::new(corner[0] + inward[0] * depth, corner[1] + inward[1] * depth);
+            let start = Point2::new(
+                corner[0] + next_inward[0] * next_depth,
+                corner[1] + next_inward[1] * next_depth,
+            );
+            if end.distance_to(&start) > self.snap_tolerance {
+                vertices.push(end);
+                vertices.push(start);
+            } else if d[0] * e[0] + d[1] * e[1] < 0.0 {
+                // Tip of a zero-thickness spur
+                vertices.push(end);
+            }
+        }
+
+        Polygon2::new(vertices).ok()
+    }
+
     /// Rebuild all rooms by tracing boundaries.
     ///
     /// This uses the "turn-right" (clockwise traversal) algorithm:
@@ -626,7 +1036,7 @@ impl TopologyGraph {
     ///
     /// Returns the number of rooms detected.
     pub fn rebuild_rooms(&mut self) -> usize {
-        self.rooms.clear();
+        self.clear_rooms();
 
         if self.edges.is_empty() {
             return 0;
@@ -642,8 +1052,8 @@ impl TopologyGraph {
         // Track which half-edges have been used
         let mut used: HashSet<(EdgeId, NodeId, NodeId)> = HashSet::new();
 
-        // For each node, precompute sorted outgoing half-edges by angle
-        let outgoing_map = self.build_outgoing_half_edge_map(&all_half_edges);
+        // Each node's outgoing half-edges, sorted by angle as they are reached
+        let mut outgoing_map = HashMap::new();
 
         // Trace boundaries
         for he in &all_half_edges {
@@ -653,32 +1063,37 @@ impl TopologyGraph {
             }
 
             // Trace a boundary starting from this half-edge
-            if let Some(room) = self.trace_boundary(he, &outgoing_map, &mut used) {
-                self.rooms.insert(room.id, room);
+            if let Some(room) = self.trace_boundary(he, &mut outgoing_map, &mut used) {
+                self.insert_room(room);
             }
         }
 
         self.rooms.len()
     }
 
-    /// Build a map of node -> outgoing half-edges sorted by angle (counter-clockwise).
-    fn build_outgoing_half_edge_map(
+    /// Outgoing half-edges of a node sorted by angle (counter-clockwise),
+    /// computed on first use and cached in `outgoing_map`.
+    fn outgoing_half_edges<'m>(
         &self,
-        half_edges: &[HalfEdge],
-    ) -> HashMap<NodeId, Vec<HalfEdge>> {
-        let mut map: HashMap<NodeId, Vec<HalfEdge>> = HashMap::new();
-
-        for he in half_edges {
-            map.entry(he.from_node).or_default().push(*he);
-        }
-
-        // Sort each node's outgoing half-edges by angle (counter-clockwise from +X axis)
-        for (node_id, edges) in map.iter_mut() {
-            let node_pos = match self.nodes.get(node_id) {
+        node_id: NodeId,
+        outgoing_map: &'m mut HashMap<NodeId, Vec<HalfEdge>>,
+    ) -> &'m [HalfEdge] {
+        outgoing_map.entry(node_id).or_insert_with(|| {
+            let node_pos = match self.nodes.get(&node_id) {
                 Some(n) => n.position,
-                None => continue,
+                None => return Vec::new(),
             };
 
+            let mut edges: Vec<HalfEdge> = self
+                .edges_at_node(node_id)
+                .into_iter()
+                .filter_map(|edge_id| {
+                    let to = self.other_node(edge_id, node_id)?;
+                    Some(HalfEdge::new(edge_id, node_id, to))
+                })
+                .collect();
+
+            // Sort by angle (counter-clockwise from +X axis)
             edges.sort_by(|a, b| {
                 let angle_a = self.half_edge_angle(node_pos, a);
                 let angle_b = self.half_edge_angle(node_pos, b);
@@ -686,9 +1101,8 @@ impl TopologyGraph {
                     .partial_cmp(&angle_b)
                     .unwrap_or(std::cmp::Ordering::Equal)
             });
-        }
-
-        map
+            edges
+        })
     }
 
     /// Calculate the angle of a half-edge from its start node.
@@ -712,7 +1126,7 @@ impl TopologyGraph {
     fn trace_boundary(
         &self,
         start: &HalfEdge,
-        outgoing_map: &HashMap<NodeId, Vec<HalfEdge>>,
+        outgoing_map: &mut HashMap<NodeId, Vec<HalfEdge>>,
         used: &mut HashSet<(EdgeId, NodeId, NodeId)>,
     ) -> Option<TopoRoom> {
         let mut boundary_nodes: Vec<NodeId> = Vec::new();
@@ -787,10 +1201,10 @@ impl TopologyGraph {
     fn next_half_edge_cw(
         &self,
         incoming_reversed: &HalfEdge,
-        outgoing_map: &HashMap<NodeId, Vec<HalfEdge>>,
+        outgoing_map: &mut HashMap<NodeId, Vec<HalfEdge>>,
     ) -> Option<HalfEdge> {
         let node = incoming_reversed.from_node;
-        let outgoing = outgoing_map.get(&node)?;
+        let outgoing = self.outgoing_half_edges(node, outgoing_map);
 
         if outgoing.is_empty() {
             return None;
@@ -871,35 +1285,196 @@ impl TopologyGraph {
     /// Clear all rooms (used before rebuild).
     pub fn clear_rooms(&mut self) {
         self.rooms.clear();
+        self.room_turns.clear();
+    }
+
+    /// Store a traced room and index its corners by node.
+    fn insert_room(&mut self, room: TopoRoom) {
+        let n = room.half_edges.len();
+        for (i, incoming) in room.half_edges.iter().enumerate() {
+            let outgoing = room.half_edges[(i + 1) % n];
+            self.room_turns
+                .entry(incoming.to_node)
+                .or_default()
+                .push(RoomTurn {
+                    incoming: *incoming,
+                    outgoing,
+                    room: room.id,
+                });
+        }
+        self.rooms.insert(room.id, room);
+    }
+
+    /// Remove a room and its corners from the node index.
+    fn remove_room(&mut self, id: RoomId) -> Option<TopoRoom> {
+        let room = self.rooms.remove(&id)?;
+        for he in &room.half_edges {
+            if let Some(turns) = self.room_turns.get_mut(&he.to_node) {
+                turns.retain(|turn| turn.room != id);
+                if turns.is_empty() {
+                    self.room_turns.remove(&he.to_node);
+                }
+            }
+        }
+        Some(room)
     }
 
     /// Remove rooms that contain any of the specified nodes.
     ///
     /// Returns the IDs of removed rooms.
     pub fn invalidate_rooms_at_nodes(&mut self, node_ids: &[NodeId]) -> Vec<RoomId> {
+        let mut to_remove: Vec<RoomId> = Vec::new();
+        for node_id in node_ids {
+            for id in self.rooms_at_node(*node_id) {
+                if !to_remove.contains(&id) {
+                    to_remove.push(id);
+                }
+            }
+        }
+
+        for id in &to_remove {
+            self.remove_room(*id);
+        }
+
+        to_remove
+    }
+
+    /// Incrementally rebuild rooms around a set of changed nodes.
+    ///
+    /// `node_ids` must cover every node whose edges were added, removed,
+    /// split or moved; [`heal_all_tracked`](crate::fixup::heal_all_tracked)
+    /// records both endpoints of each changed edge. Rooms are dropped when
+    /// they:
+    /// - turn at one of the changed nodes, or
+    /// - turn at a neighbour of a changed node and the turn no longer follows
+    ///   the "turn-right" rule (a moved node swept an edge past another).
+    ///
+    /// Boundaries are then re-traced only from the half-edges of the dropped
+    /// rooms and the half-edges incident to the changed nodes. Rooms elsewhere
+    /// in the graph are never visited, so the cost follows the size of the
+    /// change rather than the size of the plan.
+    ///
+    /// Returns the number of rooms re-traced.
+    pub fn rebuild_rooms_at_nodes(&mut self, node_ids: &[NodeId]) -> usize {
+        self.rebuild_rooms_at_nodes_visiting(node_ids, &mut 0)
+    }
+
+    /// [`rebuild_rooms_at_nodes`](Self::rebuild_rooms_at_nodes), adding the
+    /// number of existing rooms examined to `visited`.
+    fn rebuild_rooms_at_nodes_visiting(
+        &mut self,
+        node_ids: &[NodeId],
+        visited: &mut usize,
+    ) -> usize {
+        if self.edges.is_empty() {
+            self.clear_rooms();
+            return 0;
+        }
+
+        let mut outgoing_map: HashMap<NodeId, Vec<HalfEdge>> = HashMap::new();
         let node_set: HashSet<NodeId> = node_ids.iter().copied().collect();
 
-        let to_remove: Vec<RoomId> = self
-            .rooms
+        // 1. Drop rooms turning at changed nodes
+        let mut stale: HashSet<RoomId> = HashSet::new();
+        for node_id in node_ids {
+            for turn in self.room_turns.get(node_id).into_iter().flatten() {
+                if stale.insert(turn.room) {
+                    *visited += 1;
+                }
+            }
+        }
+
+        // 2. Drop rooms whose turn at a neighbour of a changed node broke
+        let neighbours: HashSet<NodeId> = node_ids
             .iter()
-            .filter(|(_, room)| room.boundary_nodes.iter().any(|n| node_set.contains(n)))
-            .map(|(id, _)| *id)
+            .flat_map(|node_id| {
+                self.edges_at_node(*node_id)
+                    .into_iter()
+                    .filter_map(|edge_id| self.other_node(edge_id, *node_id))
+            })
+            .filter(|n| !node_set.contains(n))
             .collect();
+        let mut checked: HashSet<RoomId> = HashSet::new();
+        for node_id in &neighbours {
+            let turns = self.room_turns.get(node_id).cloned().unwrap_or_default();
+            for turn in turns {
+                if stale.contains(&turn.room) {
+                    continue;
+                }
+                if checked.insert(turn.room) {
+                    *visited += 1;
+                }
+                let next = self.next_half_edge_cw(&turn.incoming.reversed(), &mut outgoing_map);
+                if next != Some(turn.outgoing) {
+                    stale.insert(turn.room);
+                }
+            }
+        }
 
-        for id in &to_remove {
-            self.rooms.remove(id);
+        let mut seeds: Vec<HalfEdge> = Vec::new();
+        for id in &stale {
+            if let Some(room) = self.remove_room(*id) {
+                seeds.extend(room.half_edges);
+            }
         }
 
-        to_remove
+        // 3. Seed tracing from the invalidated region
+        for node_id in node_ids {
+            for edge_id in self.edges_at_node(*node_id) {
+                if let Some(edge) = self.edges.get(&edge_id) {
+                    seeds.push(HalfEdge::new(edge.id, edge.start_node, edge.end_node));
+                    seeds.push(HalfEdge::new(edge.id, edge.end_node, edge.start_node));
+                }
+            }
+        }
+
+        // Surviving rooms still follow the turn-right rule, and each
+        // half-edge has exactly one successor, so no trace from a seed can
+        // run into them: only this pass's traces need tracking.
+        let mut used: HashSet<(EdgeId, NodeId, NodeId)> = HashSet::new();
+        let mut retraced = 0;
+        for he in &seeds {
+            if !self.edges.contains_key(&he.edge_id) {
+                continue;
+            }
+            let key = (he.edge_id, he.from_node, he.to_node);
+            if used.contains(&key) {
+                continue;
+            }
+
+            if let Some(room) = self.trace_boundary(he, &mut outgoing_map, &mut used) {
+                self.insert_room(room);
+                retraced += 1;
+            }
+        }
+
+        retraced
     }
 }
 
+/// Where a room boundary arrives at a node and where it leaves.
+#[derive(Debug, Clone, Copy)]
+struct RoomTurn {
+    incoming: HalfEdge,
+    outgoing: HalfEdge,
+    room: RoomId,
+}
+
 impl Default for TopologyGraph {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Find the union-find root of `i`, compressing the path.
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -925,6 +1500,112 @@ mod tests {
         assert_eq!(graph.node_count(), 4);
     }
 
+    #[test]
+    fn separate_rectangles_are_two_components() {
+        let mut graph = TopologyGraph::new();
+        let wall = EdgeData::wall(200.0, 2700.0);
+        for x in [0.0, 10000.0] {
+            graph.add_edge([x, 0.0], [x + 4000.0, 0.0], wall.clone());
+            graph.add_edge([x + 4000.0, 0.0], [x + 4000.0, 3000.0], wall.clone());
+            graph.add_edge([x + 4000.0, 3000.0], [x, 3000.0], wall.clone());
+            graph.add_edge([x, 3000.0], [x, 0.0], wall.clone());
+        }
+
+        // 8 edges >= 8 nodes - 1, but the two loops never touch.
+        let components = graph.connected_components();
+        assert_eq!(components.len(), 2);
+        assert!(components.iter().all(|c| c.len() == 4));
+        assert!(!graph.is_connected());
+
+        graph.add_edge([4000.0, 0.0], [10000.0, 0.0], wall);
+        assert!(graph.is_connected());
+        assert!(!TopologyGraph::new().is_connected());
+    }
+
+    #[test]
+    fn shortest_path_takes_the_shorter_route() {
+        let mut graph = TopologyGraph::new();
+        let wall = EdgeData::wall(200.0, 2700.0);
+        let bottom = graph
+            .add_edge([0.0, 0.0], [4000.0, 0.0], wall.clone())
+            .unwrap();
+        let right = graph
+            .add_edge([4000.0, 0.0], [4000.0, 3000.0], wall.clone())
+            .unwrap();
+        graph.add_edge([4000.0, 3000.0], [0.0, 3000.0], wall.clone());
+        graph.add_edge([0.0, 3000.0], [0.0, 0.0], wall.clone());
+        let node_at = |graph: &TopologyGraph, p| graph.nearest_node(p).unwrap().0;
+        let origin = node_at(&graph, [0.0, 0.0]);
+        let corner = node_at(&graph, [4000.0, 0.0]);
+        let opposite = node_at(&graph, [4000.0, 3000.0]);
+
+        assert_eq!(graph.shortest_path(origin, corner), Some(vec![bottom]));
+        assert_eq!(graph.shortest_path(corner, origin), Some(vec![bottom]));
+        assert_eq!(graph.shortest_path(origin, origin), Some(vec![]));
+
+        // Raise one corner so the route over the top is longer (7531 vs 7000)
+        graph.move_node(node_at(&graph, [0.0, 3000.0]), [0.0, 3500.0]);
+        let path = graph.shortest_path(origin, opposite).unwrap();
+        assert_eq!(path, vec![bottom, right]);
+        assert!((graph.path_length(&path) - 7000.0).abs() < 1e-9);
+
+        let island = graph.add_edge([9000.0, 0.0], [9500.0, 0.0], wall).unwrap();
+        let far = graph.get_edge(island).unwrap().start_node;
+        assert_eq!(graph.shortest_path(origin, far), None);
+    }
+
+    #[test]
+    fn nearest_node_finds_closest() {
+        let mut graph = TopologyGraph::new();
+        assert!(graph.nearest_node([0.0, 0.0]).is_none());
+
+        graph.add_edge([0.0, 0.0], [4000.0, 0.0], EdgeData::wall(200.0, 2700.0));
+        graph.add_edge(
+            [4000.0, 0.0],
+            [4000.0, 3000.0],
+            EdgeData::wall(200.0, 2700.0),
+        );
+
+        let (id, pos) = graph.nearest_node([3800.0, 2500.0]).unwrap();
+        assert_eq!(pos, [4000.0, 3000.0]);
+        assert_eq!(graph.get_node(id).unwrap().position, pos);
+
+        let (_, pos) = graph.nearest_node([100.0, -50.0]).unwrap();
+        assert_eq!(pos, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn nearest_edge_and_first_hit() {
+        let mut graph = TopologyGraph::new();
+        assert!(graph.nearest_edge([0.0, 0.0]).is_none());
+
+        let bottom = graph
+            .add_edge([0.0, 0.0], [4000.0, 0.0], EdgeData::wall(200.0, 2700.0))
+            .unwrap();
+        let right = graph
+            .add_edge(
+                [4000.0, 0.0],
+                [4000.0, 3000.0],
+                EdgeData::wall(200.0, 2700.0),
+            )
+            .unwrap();
+
+        let (id, distance) = graph.nearest_edge([3500.0, 1000.0]).unwrap();
+        assert_eq!(id, right);
+        assert!((distance - 500.0).abs() < 1e-9);
+
+        let (id, t, point) = graph
+            .first_edge_hit([1000.0, 1000.0], [0.0, -1.0], 5000.0)
+            .unwrap();
+        assert_eq!(id, bottom);
+        assert!((t - 1000.0).abs() < 1e-9);
+        assert_eq!(point, [1000.0, 0.0]);
+
+        assert!(graph
+            .first_edge_hit([1000.0, 1000.0], [0.0, -1.0], 500.0)
+            .is_none());
+    }
+
     #[test]
     fn edge_positions() {
         let mut graph = TopologyGraph::new();
@@ -1008,6 +1689,31 @@ mod tests {
         assert!(graph.get_edge(edge2).is_some());
     }
 
+    #[test]
+    fn reconnect_edge_keeps_id() {
+        let mut graph = TopologyGraph::new();
+        let a = graph
+            .add_edge([0.0, 0.0], [1000.0, 0.0], EdgeData::wall(200.0, 2700.0))
+            .unwrap();
+        let b = graph
+            .add_edge([1000.0, 0.0], [2000.0, 0.0], EdgeData::wall(200.0, 2700.0))
+            .unwrap();
+        let mid = graph.shared_node(a, b).unwrap();
+        let far = graph.other_node(b, mid).unwrap();
+
+        assert!(graph.reconnect_edge(a, mid, far));
+        graph.remove_edge(b);
+
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(graph.node_count(), 2);
+        assert!(graph.get_node(mid).is_none());
+        assert_eq!(graph.edge_positions(a), Some(([0.0, 0.0], [2000.0, 0.0])));
+
+        // An edge can't collapse onto one node
+        let start = graph.get_edge(a).unwrap().start_node;
+        assert!(!graph.reconnect_edge(a, start, far));
+    }
+
     #[test]
     fn split_edge_at_endpoint_returns_none() {
         let mut graph = TopologyGraph::new();
@@ -1190,11 +1896,13 @@ mod tests {
         graph.add_edge([0.0, 1000.0], [0.0, 0.0], EdgeData::wall(200.0, 2700.0)); // left
 
         // Middle dividing wall (connects to existing T-junction nodes)
-        graph.add_edge(
-            [1000.0, 0.0],
-            [1000.0, 1000.0],
-            EdgeData::wall(200.0, 2700.0),
-        );
+        let middle = graph
+            .add_edge(
+                [1000.0, 0.0],
+                [1000.0, 1000.0],
+                EdgeData::wall(200.0, 2700.0),
+            )
+            .unwrap();
 
         assert_eq!(graph.node_count(), 6); // 4 corners + 2 T-junction points
         assert_eq!(graph.edge_count(), 7); // 6 perimeter + 1 middle
@@ -1209,10 +1917,24 @@ mod tests {
         assert_eq!(interior.len(), 2);
 
         // Each interior room should have area 1000 * 1000 = 1,000,000
-        for room in interior {
+        for room in &interior {
             assert!(room.signed_area > 0.0);
             assert!((room.area() - 1_000_000.0).abs() < 1.0);
         }
+
+        // The rooms meet only across the middle wall
+        let adjacency = graph.room_adjacency();
+        assert_eq!(adjacency.len(), 1);
+        let (a, b, edge) = adjacency[0];
+        assert_eq!(edge, middle);
+        let mut ids = [interior[0].id, interior[1].id];
+        ids.sort_by_key(|id| id.0);
+        assert_eq!([a, b], ids);
+
+        assert_eq!(graph.rooms_sharing_wall(a), vec![b]);
+        assert_eq!(graph.rooms_sharing_wall(b), vec![a]);
+        let exterior = graph.rooms().find(|r| r.is_exterior).unwrap().id;
+        assert!(graph.rooms_sharing_wall(exterior).is_empty());
     }
 
     #[test]
@@ -1312,4 +2034,249 @@ mod tests {
         graph.clear_rooms();
         assert_eq!(graph.room_count(), 0);
     }
+
+    /// Rectangle of walls, counter-clockwise from the origin.
+    fn walled_rectangle(width: f64, depth: f64, walls: [EdgeData; 4]) -> TopologyGraph {
+        let corners = [[0.0, 0.0], [width, 0.0], [width, depth], [0.0, depth]];
+        let mut graph = TopologyGraph::new();
+        for (i, data) in walls.into_iter().enumerate() {
+            graph.add_edge(corners[i], corners[(i + 1) % 4], data);
+        }
+        graph.rebuild_rooms();
+        graph
+    }
+
+    fn assert_face(graph: &TopologyGraph, expected: &[[f64; 2]]) {
+        let room = graph.interior_rooms()[0].id;
+        let face = graph.room_face_polygon(room).unwrap();
+        assert_eq!(face.vertices.len(), expected.len(), "{:?}", face.vertices);
+        for p in expected {
+            assert!(
+                face.vertices
+                    .iter()
+                    .any(|v| (v.x - p[0]).abs() < 1e-9 && (v.y - p[1]).abs() < 1e-9),
+                "{:?} not in {:?}",
+                p,
+                face.vertices
+            );
+        }
+    }
+
+    #[test]
+    fn room_face_shrinks_by_half_thickness() {
+        let wall = || EdgeData::wall(200.0, 2700.0);
+        let graph = walled_rectangle(6000.0, 4000.0, [wall(), wall(), wall(), wall()]);
+
+        assert_face(
+            &graph,
+            &[
+                [100.0, 100.0],
+                [5900.0, 100.0],
+                [5900.0, 3900.0],
+                [100.0, 3900.0],
+            ],
+        );
+        let room = graph.interior_rooms()[0].id;
+        let face = graph.room_face_polygon(room).unwrap();
+        assert!((face.area() - 5800.0 * 3800.0).abs() < 1e-6);
+
+        let exterior = graph.rooms().find(|r| r.is_exterior).unwrap().id;
+        assert!(graph.room_face_polygon(exterior).is_none());
+        assert!(graph.room_face_polygon(RoomId::new()).is_none());
+    }
+
+    #[test]
+    fn room_face_follows_baselines() {
+        // Bottom wall built into the room, left wall built outwards
+        let graph = walled_rectangle(
+            6000.0,
+            4000.0,
+            [
+                EdgeData::new(300.0, 2700.0, Baseline::Left),
+                EdgeData::wall(200.0, 2700.0),
+                EdgeData::wall(200.0, 2700.0),
+                EdgeData::new(100.0, 2700.0, Baseline::Right),
+            ],
+        );
+
+        assert_face(
+            &graph,
+            &[
+                [0.0, 300.0],
+                [5900.0, 300.0],
+                [5900.0, 3900.0],
+                [0.0, 3900.0],
+            ],
+        );
+    }
+
+    #[test]
+    fn room_face_steps_where_thickness_changes() {
+        let mut graph = TopologyGraph::new();
+        graph.add_edge([0.0, 0.0], [3000.0, 0.0], EdgeData::wall(200.0, 2700.0));
+        graph.add_edge([3000.0, 0.0], [6000.0, 0.0], EdgeData::wall(400.0, 2700.0));
+        graph.add_edge(
+            [6000.0, 0.0],
+            [6000.0, 4000.0],
+            EdgeData::wall(200.0, 2700.0),
+        );
+        // Drawn clockwise, so the room is on these walls' right
+        graph.add_edge(
+            [0.0, 4000.0],
+            [6000.0, 4000.0],
+            EdgeData::wall(200.0, 2700.0),
+        );
+        graph.add_edge([0.0, 0.0], [0.0, 4000.0], EdgeData::wall(200.0, 2700.0));
+        graph.rebuild_rooms();
+
+        assert_face(
+            &graph,
+            &[
+                [100.0, 100.0],
+                [3000.0, 100.0],
+                [3000.0, 200.0],
+                [5900.0, 200.0],
+                [5900.0, 3900.0],
+                [100.0, 3900.0],
+            ],
+        );
+    }
+
+    /// 32x32 grid of nodes joined by row edges, plus short edges whose
+    /// start nodes sit 1mm from every 100th grid node.
+    fn snap_grid() -> TopologyGraph {
+        let mut graph = TopologyGraph::new();
+        for row in 0..32 {
+            let y = row as f64 * 1000.0;
+            for col in 0..31 {
+                let x = col as f64 * 1000.0;
+                graph.add_edge([x, y], [x + 1000.0, y], EdgeData::wall(200.0, 2700.0));
+            }
+        }
+        for k in 0..10 {
+            let (col, row) = ((k * 100) % 32, (k * 100) / 32);
+            let (x, y) = (col as f64 * 1000.0, row as f64 * 1000.0);
+            graph.add_edge(
+                [x + 1.0, y],
+                [x + 1.0, y + 500.0],
+                EdgeData::wall(200.0, 2700.0),
+            );
+        }
+        graph.snap_tolerance = 2.0;
+        graph
+    }
+
+    /// The previous all-pairs search, returning the merge count and the
+    /// positions of surviving nodes.
+    fn snap_merge_all_pairs(graph: &TopologyGraph) -> (usize, Vec<[f64; 2]>) {
+        let mut positions: HashMap<NodeId, [f64; 2]> = graph
+            .nodes
+            .iter()
+            .map(|(id, n)| (*id, n.position))
+            .collect();
+        let mut merged: HashSet<NodeId> = HashSet::new();
+        let node_ids: Vec<NodeId> = graph.nodes.keys().copied().collect();
+
+        for i in 0..node_ids.len() {
+            let id_a = node_ids[i];
+            if merged.contains(&id_a) || graph.nodes[&id_a].pinned {
+                continue;
+            }
+            let pos_a = positions[&id_a];
+
+            for &id_b in node_ids.iter().skip(i + 1) {
+                if merged.contains(&id_b) || graph.nodes[&id_b].pinned {
+                    continue;
+                }
+                let pos_b = positions[&id_b];
+                if points2_within(pos_a, pos_b, graph.snap_tolerance) {
+                    merged.insert(id_b);
+                    positions.insert(
+                        id_a,
+                        [(pos_a[0] + pos_b[0]) / 2.0, (pos_a[1] + pos_b[1]) / 2.0],
+                    );
+                }
+            }
+        }
+
+        let mut survivors: Vec<[f64; 2]> = positions
+            .into_iter()
+            .filter(|(id, _)| !merged.contains(id))
+            .map(|(_, p)| p)
+            .collect();
+        survivors.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        (merged.len(), survivors)
+    }
+
+    #[test]
+    fn snap_merge_indexed_matches_all_pairs() {
+        let reference_graph = snap_grid();
+        assert_eq!(reference_graph.node_count(), 1024 + 20);
+
+        let (expected_count, expected_positions) = snap_merge_all_pairs(&reference_graph);
+
+        let mut graph = snap_grid();
+        let merged = graph.snap_merge_nodes();
+
+        assert_eq!(merged, 10);
+        assert_eq!(merged, expected_count);
+
+        let mut positions: Vec<[f64; 2]> = graph.nodes.values().map(|n| n.position).collect();
+        positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(positions, expected_positions);
+        assert!(positions.contains(&[0.5, 0.0]));
+
+        // Short edges now start at the merged grid nodes
+        assert_eq!(graph.edge_count(), 32 * 31 + 10);
+        assert!(graph.edges.values().all(|e| e.start_node != e.end_node));
+    }
+
+    #[test]
+    fn snap_merge_skips_pinned_nodes() {
+        let mut graph = TopologyGraph::new();
+        graph.add_edge([0.0, 0.0], [1000.0, 0.0], EdgeData::wall(200.0, 2700.0));
+        graph.add_edge([1.0, 0.0], [1.0, 1000.0], EdgeData::wall(200.0, 2700.0));
+        graph.snap_tolerance = 2.0;
+
+        let pinned = graph.find_or_create_node([1.0, 0.0]);
+        graph.get_node_mut(pinned).unwrap().pinned = true;
+
+        assert_eq!(graph.snap_merge_nodes(), 0);
+        assert_eq!(graph.node_count(), 4);
+    }
+
+    #[test]
+    fn rebuild_rooms_at_nodes_visits_only_nearby_rooms() {
+        // 40 x 40 grid of 1000mm cells
+        let mut graph = TopologyGraph::new();
+        for j in 0..=40 {
+            for i in 0..=40 {
+                let (x, y) = (i as f64 * 1000.0, j as f64 * 1000.0);
+                if i < 40 {
+                    graph.add_edge([x, y], [x + 1000.0, y], EdgeData::wall(200.0, 2700.0));
+                }
+                if j < 40 {
+                    graph.add_edge([x, y], [x, y + 1000.0], EdgeData::wall(200.0, 2700.0));
+                }
+            }
+        }
+        assert_eq!(graph.rebuild_rooms(), 1601);
+
+        let node = graph.nodes_within([20_000.0, 20_000.0], 1.0)[0];
+        graph.move_node(node, [20_150.0, 20_100.0]);
+
+        let mut visited = 0;
+        let retraced = graph.rebuild_rooms_at_nodes_visiting(&[node], &mut visited);
+        assert_eq!(retraced, 4);
+        // The 4 cells at the node and the 8 more at its neighbours
+        assert_eq!(visited, 12);
+        assert_eq!(graph.room_count(), 1601);
+
+        let mut areas: Vec<i64> = graph.rooms().map(|r| r.signed_area as i64).collect();
+        graph.rebuild_rooms();
+        let mut full: Vec<i64> = graph.rooms().map(|r| r.signed_area as i64).collect();
+        areas.sort_unstable();
+        full.sort_unstable();
+        assert_eq!(areas, full);
+    }
 }
@@ -6,6 +6,7 @@
 //! - Automatic node merging within SNAP_MERGE_TOL (0.5mm)
 //! - T-junction and crossing detection
 //! - Room boundary tracing
+//! - Corridor detection and circulation networks ([`corridor`])
 //!
 //! # Architecture
 //!
@@ -35,11 +36,17 @@
 //! graph.rebuild_rooms();
 //! ```
 
+pub mod corridor;
 mod edge;
 mod graph;
 mod node;
 mod room;
+mod visit;
 
+pub use corridor::{
+    circulation_network, classify_corridors, extract_centerline, Centerline, CirculationEdge,
+    CirculationNetwork, CirculationNode, CirculationNodeKind, Corridor, CorridorCriteria,
+};
 pub use edge::{Baseline, EdgeData, EdgeId, OpeningRef, TopoEdge};
 pub use graph::TopologyGraph;
 pub use node::{NodeId, TopoNode};
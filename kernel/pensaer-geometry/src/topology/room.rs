@@ -85,6 +85,9 @@ pub struct TopoRoom {
 
     /// Whether this is the exterior (unbounded) region
     pub is_exterior: bool,
+
+    /// User-assigned name (not preserved when rooms are rebuilt)
+    pub name: Option<String>,
 }
 
 impl TopoRoom {
@@ -104,6 +107,7 @@ impl TopoRoom {
             signed_area,
             centroid,
             is_exterior: signed_area < 0.0, // CW traversal = exterior
+            name: None,
         }
     }
 
@@ -0,0 +1,30 @@
+//! Priority-queue entry shared by the shortest-path searches.
+
+use std::cmp::Ordering;
+
+/// Min-heap entry for Dijkstra's algorithm.
+///
+/// `BinaryHeap` is a max-heap, so the ordering is reversed: the smallest
+/// distance pops first, with ties going to the lower node index.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct Visit {
+    pub(super) distance: f64,
+    pub(super) node: usize,
+}
+
+impl Eq for Visit {}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -0,0 +1,637 @@
+//! Model validation: a lint pass to run before export.
+//!
+//! [`validate_model`] checks a whole model and collects every problem it
+//! finds in a [`ValidationReport`] instead of stopping at the first one.
+//! Each [`ValidationIssue`] names the [`ValidationRule`] it breaks (with a
+//! stable id for tools to filter on), how serious it is, and the elements
+//! involved:
+//!
+//! | Rule | Severity | Finds |
+//! |------|----------|-------|
+//! | `missing_host_wall` | error | Doors and windows whose host wall is not in the model |
+//! | `opening_out_of_bounds` | error | Wall openings past the wall's ends, base, or top |
+//! | `overlapping_openings` | error | Wall openings that overlap each other |
+//! | `zero_area_room` | warning | Placed rooms under [`MIN_ROOM_AREA`] |
+//! | `short_wall` | warning | Walls shorter than [`SNAP_MERGE_TOL`] |
+//! | `duplicate_wall` | warning | Walls on the same baseline as another wall |
+//! | `missing_roof_wall` | warning | Roofs attached to walls not in the model |
+//! | `invalid_mesh` | error | Elements that fail to mesh, or mesh with bad indices |
+//! | `degenerate_mesh` | warning | Meshes with zero-area triangles |
+//! | `unclosed_walls` | warning | Wall ends that meet nothing, when the model has rooms |
+//!
+//! The report serializes through the deterministic JSON path, so the same
+//! model always gives the same text.
+
+use pensaer_math::LineSegment2;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::constants::SNAP_MERGE_TOL;
+use crate::element::Element;
+use crate::elements::{Door, Floor, Roof, Room, Wall, Window, MIN_ROOM_AREA};
+use crate::io::to_deterministic_json;
+use crate::topology::TopologyGraph;
+
+/// Topology coordinates are in millimeters; elements use meters.
+const MM_TO_M: f64 = 0.001;
+
+/// How serious a validation issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    /// Worth knowing, but nothing is wrong.
+    Info,
+    /// Worth reviewing, but the model exports.
+    Warning,
+    /// The model will not export correctly.
+    Error,
+}
+
+/// A check run by [`validate_model`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationRule {
+    /// A door or window's host wall is not in the model.
+    MissingHostWall,
+    /// A wall opening extends past the wall's ends, base, or top.
+    OpeningOutOfBounds,
+    /// Two openings in the same wall overlap.
+    OverlappingOpenings,
+    /// A placed room encloses less than [`MIN_ROOM_AREA`].
+    ZeroAreaRoom,
+    /// A wall is shorter than [`SNAP_MERGE_TOL`].
+    ShortWall,
+    /// Two walls share a baseline, in either direction.
+    DuplicateWall,
+    /// A roof is attached to a wall that is not in the model.
+    MissingRoofWall,
+    /// An element fails to mesh, or its mesh has out-of-range indices.
+    InvalidMesh,
+    /// An element's mesh has zero-area triangles.
+    DegenerateMesh,
+    /// A wall end meets no other wall although the model has rooms.
+    UnclosedWalls,
+}
+
+impl ValidationRule {
+    /// Stable identifier, as serialized.
+    pub fn id(&self) -> &'static str {
+        match self {
+            ValidationRule::MissingHostWall => "missing_host_wall",
+            ValidationRule::OpeningOutOfBounds => "opening_out_of_bounds",
+            ValidationRule::OverlappingOpenings => "overlapping_openings",
+            ValidationRule::ZeroAreaRoom => "zero_area_room",
+            ValidationRule::ShortWall => "short_wall",
+            ValidationRule::DuplicateWall => "duplicate_wall",
+            ValidationRule::MissingRoofWall => "missing_roof_wall",
+            ValidationRule::InvalidMesh => "invalid_mesh",
+            ValidationRule::DegenerateMesh => "degenerate_mesh",
+            ValidationRule::UnclosedWalls => "unclosed_walls",
+        }
+    }
+
+    /// Severity of the issues this rule raises.
+    pub fn severity(&self) -> IssueSeverity {
+        match self {
+            ValidationRule::MissingHostWall
+            | ValidationRule::OpeningOutOfBounds
+            | ValidationRule::OverlappingOpenings
+            | ValidationRule::InvalidMesh => IssueSeverity::Error,
+            _ => IssueSeverity::Warning,
+        }
+    }
+}
+
+/// One problem found by [`validate_model`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// How serious the issue is.
+    pub severity: IssueSeverity,
+    /// The rule that raised it.
+    pub rule: ValidationRule,
+    /// Elements involved, the offending element first.
+    pub element_ids: Vec<Uuid>,
+    /// Human-readable description.
+    pub message: String,
+}
+
+/// Every issue found in a model, in the order the rules ran.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// Issues found.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Whether no rule found anything.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Whether any issue is an [`IssueSeverity::Error`].
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|i| i.severity == IssueSeverity::Error)
+    }
+
+    /// Number of issues with the given severity.
+    pub fn count(&self, severity: IssueSeverity) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == severity)
+            .count()
+    }
+
+    /// Issues raised by one rule.
+    pub fn issues_for(&self, rule: ValidationRule) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(move |i| i.rule == rule)
+    }
+
+    /// Serialize to deterministic JSON.
+    pub fn to_json(&self) -> String {
+        to_deterministic_json(&serde_json::to_value(self).unwrap_or_default())
+    }
+
+    fn push(&mut self, rule: ValidationRule, element_ids: Vec<Uuid>, message: String) {
+        self.issues.push(ValidationIssue {
+            severity: rule.severity(),
+            rule,
+            element_ids,
+            message,
+        });
+    }
+}
+
+/// Check a whole model for problems that would spoil an export.
+///
+/// `graph` is the model's wall topology. When the model has rooms, its
+/// nodes with a single edge are reported as
+/// [`ValidationRule::UnclosedWalls`]; without a graph, wall ends that touch
+/// no other wall are reported instead.
+pub fn validate_model(
+    walls: &[Wall],
+    floors: &[Floor],
+    roofs: &[Roof],
+    doors: &[Door],
+    windows: &[Window],
+    rooms: &[Room],
+    graph: Option<&TopologyGraph>,
+) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    check_hosts(walls, doors, windows, &mut report);
+    check_openings(walls, &mut report);
+    check_rooms(rooms, &mut report);
+    check_walls(walls, &mut report);
+    check_roofs(walls, roofs, &mut report);
+
+    let elements = walls
+        .iter()
+        .map(|e| e as &dyn Element)
+        .chain(floors.iter().map(|e| e as &dyn Element))
+        .chain(roofs.iter().map(|e| e as &dyn Element))
+        .chain(doors.iter().map(|e| e as &dyn Element))
+        .chain(windows.iter().map(|e| e as &dyn Element))
+        .chain(rooms.iter().map(|e| e as &dyn Element));
+    for element in elements {
+        check_mesh(element, &mut report);
+    }
+
+    if !rooms.is_empty() {
+        match graph {
+            Some(graph) => check_graph_loops(graph, &mut report),
+            None => check_wall_loops(walls, &mut report),
+        }
+    }
+    report
+}
+
+fn check_hosts(walls: &[Wall], doors: &[Door], windows: &[Window], report: &mut ValidationReport) {
+    let hosts = doors
+        .iter()
+        .map(|d| ("door", d.id, d.host_wall_id))
+        .chain(windows.iter().map(|w| ("window", w.id, w.host_wall_id)));
+    for (kind, id, host) in hosts {
+        if !walls.iter().any(|w| w.id == host) {
+            report.push(
+                ValidationRule::MissingHostWall,
+                vec![id, host],
+                format!("{kind} {id} is hosted by wall {host}, which is not in the model"),
+            );
+        }
+    }
+}
+
+fn check_openings(walls: &[Wall], report: &mut ValidationReport) {
+    for wall in walls {
+        for (i, opening) in wall.openings.iter().enumerate() {
+            if !wall.opening_in_bounds(opening) {
+                report.push(
+                    ValidationRule::OpeningOutOfBounds,
+                    vec![wall.id, opening.id],
+                    format!(
+                        "opening {} ({:.3} to {:.3}) does not fit wall {} ({:.3} long)",
+                        opening.id,
+                        opening.start_offset(),
+                        opening.end_offset(),
+                        wall.id,
+                        wall.length()
+                    ),
+                );
+            }
+            for other in &wall.openings[i + 1..] {
+                if wall.openings_overlap(opening, other) {
+                    report.push(
+                        ValidationRule::OverlappingOpenings,
+                        vec![wall.id, opening.id, other.id],
+                        format!(
+                            "openings {} and {} overlap in wall {}",
+                            opening.id, other.id, wall.id
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn check_rooms(rooms: &[Room], report: &mut ValidationReport) {
+    for room in rooms.iter().filter(|r| !r.unplaced) {
+        let area = room.area();
+        if area < MIN_ROOM_AREA {
+            report.push(
+                ValidationRule::ZeroAreaRoom,
+                vec![room.id],
+                format!(
+                    "room {} ({}) has area {:.4}, below the minimum {}",
+                    room.number, room.name, area, MIN_ROOM_AREA
+                ),
+            );
+        }
+    }
+}
+
+fn check_walls(walls: &[Wall], report: &mut ValidationReport) {
+    let tolerance = SNAP_MERGE_TOL * MM_TO_M;
+    for (i, wall) in walls.iter().enumerate() {
+        let length = wall.length();
+        if length < tolerance {
+            report.push(
+                ValidationRule::ShortWall,
+                vec![wall.id],
+                format!(
+                    "wall {} is {:.6} long, under the merge tolerance {}",
+                    wall.id, length, tolerance
+                ),
+            );
+        }
+        let (start, end) = (wall.baseline.start, wall.baseline.end);
+        for other in &walls[i + 1..] {
+            let (a, b) = (other.baseline.start, other.baseline.end);
+            let same = start.distance_to(&a) < tolerance && end.distance_to(&b) < tolerance;
+            let reversed = start.distance_to(&b) < tolerance && end.distance_to(&a) < tolerance;
+            if same || reversed {
+                report.push(
+                    ValidationRule::DuplicateWall,
+                    vec![other.id, wall.id],
+                    format!("wall {} duplicates wall {}", other.id, wall.id),
+                );
+            }
+        }
+    }
+}
+
+fn check_roofs(walls: &[Wall], roofs: &[Roof], report: &mut ValidationReport) {
+    for roof in roofs {
+        for &wall_id in roof.attached_walls() {
+            if !walls.iter().any(|w| w.id == wall_id) {
+                report.push(
+                    ValidationRule::MissingRoofWall,
+                    vec![roof.id, wall_id],
+                    format!(
+                        "roof {} is attached to wall {wall_id}, which is not in the model",
+                        roof.id
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn check_mesh(element: &dyn Element, report: &mut ValidationReport) {
+    let kind = element.element_type().name();
+    let id = element.id();
+    match element.to_mesh() {
+        Err(e) => report.push(
+            ValidationRule::InvalidMesh,
+            vec![id],
+            format!("{kind} {id} failed to mesh: {e}"),
+        ),
+        Ok(mesh) if !mesh.is_valid() => report.push(
+            ValidationRule::InvalidMesh,
+            vec![id],
+            format!("{kind} {id} has a mesh with out-of-range indices"),
+        ),
+        Ok(mesh) if mesh.has_degenerate_triangles() => report.push(
+            ValidationRule::DegenerateMesh,
+            vec![id],
+            format!("{kind} {id} has a mesh with zero-area triangles"),
+        ),
+        Ok(_) => {}
+    }
+}
+
+fn check_graph_loops(graph: &TopologyGraph, report: &mut ValidationReport) {
+    for node in graph.nodes() {
+        if let [edge] = graph.edges_at_node(node.id)[..] {
+            let [x, y] = node.position;
+            report.push(
+                ValidationRule::UnclosedWalls,
+                vec![edge.0],
+                format!(
+                    "wall {} ends at ({x:.3}, {y:.3}) without meeting another wall",
+                    edge.0
+                ),
+            );
+        }
+    }
+}
+
+fn check_wall_loops(walls: &[Wall], report: &mut ValidationReport) {
+    let tolerance = SNAP_MERGE_TOL * MM_TO_M;
+    for (i, wall) in walls.iter().enumerate() {
+        for end in [wall.baseline.start, wall.baseline.end] {
+            let meets = walls.iter().enumerate().any(|(j, other)| {
+                let baseline = LineSegment2::new(other.baseline.start, other.baseline.end);
+                j != i && baseline.distance_to_point(&end) < tolerance
+            });
+            if !meets {
+                report.push(
+                    ValidationRule::UnclosedWalls,
+                    vec![wall.id],
+                    format!(
+                        "wall {} ends at ({:.3}, {:.3}) without meeting another wall",
+                        wall.id, end.x, end.y
+                    ),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::{wall_graph, OpeningType, WallOpening};
+    use pensaer_math::{Point2, Polygon2};
+
+    struct Model {
+        walls: Vec<Wall>,
+        floors: Vec<Floor>,
+        roofs: Vec<Roof>,
+        doors: Vec<Door>,
+        windows: Vec<Window>,
+        rooms: Vec<Room>,
+    }
+
+    impl Model {
+        fn validate(&self, graph: Option<&TopologyGraph>) -> ValidationReport {
+            validate_model(
+                &self.walls,
+                &self.floors,
+                &self.roofs,
+                &self.doors,
+                &self.windows,
+                &self.rooms,
+                graph,
+            )
+        }
+    }
+
+    /// A 10 × 8 box with a door, a window, a floor, a hip roof, and a room.
+    fn model() -> Model {
+        let corners = [
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 8.0),
+            Point2::new(0.0, 8.0),
+        ];
+        let mut walls: Vec<Wall> = (0..4)
+            .map(|i| Wall::new(corners[i], corners[(i + 1) % 4], 3.0, 0.2).unwrap())
+            .collect();
+
+        let door = Door::new(walls[0].id, 0.9, 2.1, 2.0).unwrap();
+        let mut opening = WallOpening::new(2.0, 0.0, 0.9, 2.1, OpeningType::Door);
+        opening.hosted_element_id = Some(door.id);
+        walls[0].add_opening(opening).unwrap();
+        let window = Window::new(walls[1].id, 1.2, 1.2, 0.9, 4.0).unwrap();
+        let mut opening = WallOpening::new(4.0, 0.9, 1.2, 1.2, OpeningType::Window);
+        opening.hosted_element_id = Some(window.id);
+        walls[1].add_opening(opening).unwrap();
+
+        let mut roof = Roof::hip(corners[0], corners[2], 0.25, 30.0).unwrap();
+        roof.set_elevation(3.0);
+        roof.attach_to_walls(&walls.iter().map(|w| w.id).collect::<Vec<_>>());
+
+        Model {
+            floors: vec![Floor::rectangle(corners[0], corners[2], 0.3).unwrap()],
+            roofs: vec![roof],
+            doors: vec![door],
+            windows: vec![window],
+            rooms: vec![Room::rectangle("Hall", "001", corners[0], corners[2], 3.0).unwrap()],
+            walls,
+        }
+    }
+
+    fn rules(report: &ValidationReport) -> Vec<ValidationRule> {
+        report.issues.iter().map(|i| i.rule).collect()
+    }
+
+    #[test]
+    fn clean_model_passes_every_rule() {
+        let model = model();
+        let refs: Vec<&Wall> = model.walls.iter().collect();
+        let graph = wall_graph(&refs);
+
+        for report in [model.validate(None), model.validate(Some(&graph))] {
+            assert!(report.is_clean(), "{:?}", report.issues);
+            assert!(!report.has_errors());
+        }
+        assert_eq!(model.validate(None).to_json(), "{\n  \"issues\": []\n}");
+    }
+
+    #[test]
+    fn missing_host_wall() {
+        let mut model = model();
+        let host = Uuid::new_v4();
+        model.doors.push(Door::new(host, 0.9, 2.1, 5.0).unwrap());
+
+        let report = model.validate(None);
+        assert_eq!(rules(&report), [ValidationRule::MissingHostWall]);
+        assert_eq!(report.issues[0].element_ids, [model.doors[1].id, host]);
+        assert_eq!(report.issues[0].severity, IssueSeverity::Error);
+        assert!(report.has_errors());
+
+        // Hosts found anywhere in the model count, not just the first wall
+        model.doors.pop();
+        model.windows[0].host_wall_id = model.walls[3].id;
+        assert!(model.validate(None).is_clean());
+    }
+
+    #[test]
+    fn openings_out_of_bounds_or_overlapping() {
+        let mut model = model();
+        let wall = &mut model.walls[2];
+        // Past the end, then overlapping the first one: pushed directly, as
+        // a loaded model would have them
+        wall.openings
+            .push(WallOpening::new(9.8, 0.0, 0.9, 2.1, OpeningType::Generic));
+        wall.openings
+            .push(WallOpening::new(9.0, 1.0, 1.0, 1.0, OpeningType::Generic));
+        let ids: Vec<Uuid> = wall.openings.iter().map(|o| o.id).collect();
+
+        let report = model.validate(None);
+        assert_eq!(
+            rules(&report),
+            [
+                ValidationRule::OpeningOutOfBounds,
+                ValidationRule::OverlappingOpenings
+            ]
+        );
+        assert_eq!(report.issues[0].element_ids, [model.walls[2].id, ids[0]]);
+        assert_eq!(
+            report.issues[1].element_ids,
+            [model.walls[2].id, ids[0], ids[1]]
+        );
+
+        // Side by side is fine
+        model.walls[2].openings[0].offset_along_wall = 7.0;
+        assert!(model.validate(None).is_clean());
+    }
+
+    #[test]
+    fn zero_area_rooms() {
+        let mut model = model();
+        let sliver = Polygon2::new(vec![
+            Point2::new(1.0, 1.0),
+            Point2::new(2.0, 1.0),
+            Point2::new(3.0, 1.0),
+        ])
+        .unwrap();
+        let mut room = Room::new("Sliver", "002", sliver, 3.0).unwrap();
+        model.rooms.push(room.clone());
+
+        let report = model.validate(None);
+        let issues: Vec<_> = report.issues_for(ValidationRule::ZeroAreaRoom).collect();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].element_ids, [room.id]);
+
+        // Unplaced rooms have no area to check
+        room.unplaced = true;
+        model.rooms[1] = room;
+        let report = model.validate(None);
+        assert_eq!(report.issues_for(ValidationRule::ZeroAreaRoom).count(), 0);
+    }
+
+    #[test]
+    fn short_and_duplicate_walls() {
+        let mut model = model();
+        let (start, end) = (model.walls[0].baseline.start, model.walls[0].baseline.end);
+        model.walls.push(Wall::new(end, start, 3.0, 0.2).unwrap());
+        let stub = Wall::new(start, Point2::new(0.0, 0.0001), 3.0, 0.2).unwrap();
+        model.walls.push(stub);
+
+        let report = model.validate(None);
+        let short: Vec<_> = report.issues_for(ValidationRule::ShortWall).collect();
+        assert_eq!(short.len(), 1);
+        assert_eq!(short[0].element_ids, [model.walls[5].id]);
+        let duplicate: Vec<_> = report.issues_for(ValidationRule::DuplicateWall).collect();
+        assert_eq!(duplicate.len(), 1);
+        assert_eq!(
+            duplicate[0].element_ids,
+            [model.walls[4].id, model.walls[0].id]
+        );
+
+        // A wall along part of another is not a duplicate
+        model.walls.truncate(4);
+        model
+            .walls
+            .push(Wall::new(start, Point2::new(5.0, 0.0), 3.0, 0.2).unwrap());
+        assert!(model.validate(None).is_clean());
+    }
+
+    #[test]
+    fn roofs_attached_to_missing_walls() {
+        let mut model = model();
+        let gone = model.walls[0].id;
+        model.walls[0].id = Uuid::new_v4();
+        model.doors.clear();
+
+        let report = model.validate(None);
+        assert_eq!(rules(&report), [ValidationRule::MissingRoofWall]);
+        assert_eq!(report.issues[0].element_ids, [model.roofs[0].id, gone]);
+
+        model.roofs[0].detach_from_wall(gone);
+        assert!(model.validate(None).is_clean());
+    }
+
+    #[test]
+    fn invalid_and_degenerate_meshes() {
+        let mut model = model();
+        model.floors[0].thickness = -0.3;
+        // Thin enough that its edge faces have no area
+        let mut film = Floor::rectangle(Point2::new(0.0, 0.0), Point2::new(1.0, 1.0), 0.3).unwrap();
+        film.thickness = 1e-12;
+        model.floors.push(film);
+
+        let report = model.validate(None);
+        assert_eq!(
+            rules(&report),
+            [ValidationRule::InvalidMesh, ValidationRule::DegenerateMesh]
+        );
+        assert_eq!(report.issues[0].element_ids, [model.floors[0].id]);
+        assert_eq!(report.issues[1].element_ids, [model.floors[1].id]);
+        assert_eq!(report.count(IssueSeverity::Error), 1);
+        assert_eq!(report.count(IssueSeverity::Warning), 1);
+
+        model.floors[0].thickness = 0.3;
+        model.floors.pop();
+        assert!(model.validate(None).is_clean());
+    }
+
+    #[test]
+    fn unclosed_walls_when_rooms_expected() {
+        let mut model = model();
+        model.walls.remove(2);
+        model.windows.clear();
+        model.roofs.clear();
+        let refs: Vec<&Wall> = model.walls.iter().collect();
+        let graph = wall_graph(&refs);
+
+        for report in [model.validate(None), model.validate(Some(&graph))] {
+            assert_eq!(
+                rules(&report),
+                [ValidationRule::UnclosedWalls, ValidationRule::UnclosedWalls]
+            );
+        }
+        let report = model.validate(None);
+        assert_eq!(report.issues[0].element_ids, [model.walls[1].id]);
+        assert_eq!(report.issues[1].element_ids, [model.walls[2].id]);
+
+        // Without rooms, open walls are expected
+        model.rooms.clear();
+        assert!(model.validate(Some(&graph)).is_clean());
+    }
+
+    #[test]
+    fn report_json_is_deterministic() {
+        let mut model = model();
+        model.doors[0].host_wall_id = Uuid::from_u128(7);
+
+        let json = model.validate(None).to_json();
+        assert_eq!(json, model.validate(None).to_json());
+        assert!(json.contains("\"rule\": \"missing_host_wall\""));
+        assert!(json.contains("\"severity\": \"error\""));
+        assert_eq!(ValidationRule::MissingHostWall.id(), "missing_host_wall");
+    }
+}
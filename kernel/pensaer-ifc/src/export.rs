@@ -3,9 +3,11 @@
 //! Exports Pensaer BIM elements to IFC format.
 
 use crate::error::Result;
+use crate::guid;
 use crate::IfcVersion;
-use pensaer_math::Point2;
+use pensaer_math::{Point2, Point3};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Wall data for IFC export.
@@ -67,6 +69,17 @@ pub struct FloorExportData {
     pub boundary_points: Vec<Point2>,
 }
 
+/// Ceiling data for IFC export, mapped to IfcCovering (CEILING).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CeilingExportData {
+    pub id: Uuid,
+    pub name: String,
+    pub thickness: f64,
+    /// Elevation of the underside.
+    pub elevation: f64,
+    pub boundary_points: Vec<Point2>,
+}
+
 /// Roof data for IFC export.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoofExportData {
@@ -78,6 +91,24 @@ pub struct RoofExportData {
     pub boundary_points: Vec<Point2>,
 }
 
+/// Railing data for IFC export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RailingExportData {
+    pub id: Uuid,
+    pub name: String,
+    pub height: f64,
+    pub path: Vec<Point3>,
+    pub railing_type: String,
+}
+
+/// Building level data for IFC export, mapped to IfcBuildingStorey.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelExportData {
+    pub id: Uuid,
+    pub name: String,
+    pub elevation: f64,
+}
+
 /// Building project metadata.
 #[derive(Debug, Clone)]
 pub struct ProjectMetadata {
@@ -98,6 +129,41 @@ impl Default for ProjectMetadata {
     }
 }
 
+/// A single property value, written as the matching IFC measure type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum IfcValue {
+    /// Written as IfcLabel.
+    String(String),
+    /// Written as IfcReal.
+    Real(f64),
+    /// Written as IfcInteger.
+    Integer(i64),
+    /// Written as IfcBoolean.
+    Boolean(bool),
+}
+
+impl IfcValue {
+    /// STEP encoding of the value, e.g. `IFCBOOLEAN(.T.)`.
+    fn to_step(&self) -> String {
+        match self {
+            Self::String(s) => format!("IFCLABEL('{}')", escape_step_string(s)),
+            Self::Real(v) if v.is_finite() => format!("IFCREAL({:.6})", v),
+            Self::Real(_) => "$".to_string(),
+            Self::Integer(v) => format!("IFCINTEGER({})", v),
+            Self::Boolean(true) => "IFCBOOLEAN(.T.)".to_string(),
+            Self::Boolean(false) => "IFCBOOLEAN(.F.)".to_string(),
+        }
+    }
+}
+
+/// A named property set attached to an element.
+#[derive(Debug, Clone)]
+struct PropertySet {
+    element_id: Uuid,
+    name: String,
+    properties: HashMap<String, IfcValue>,
+}
+
 /// IFC exporter for Pensaer elements.
 pub struct IfcExporter {
     version: IfcVersion,
@@ -107,7 +173,12 @@ pub struct IfcExporter {
     windows: Vec<WindowExportData>,
     rooms: Vec<RoomExportData>,
     floors: Vec<FloorExportData>,
+    ceilings: Vec<CeilingExportData>,
     roofs: Vec<RoofExportData>,
+    railings: Vec<RailingExportData>,
+    levels: Vec<LevelExportData>,
+    element_levels: HashMap<Uuid, Uuid>,
+    property_sets: Vec<PropertySet>,
 }
 
 impl IfcExporter {
@@ -125,7 +196,12 @@ impl IfcExporter {
             windows: Vec::new(),
             rooms: Vec::new(),
             floors: Vec::new(),
+            ceilings: Vec::new(),
             roofs: Vec::new(),
+            railings: Vec::new(),
+            levels: Vec::new(),
+            element_levels: HashMap::new(),
+            property_sets: Vec::new(),
         }
     }
 
@@ -166,11 +242,51 @@ impl IfcExporter {
         self.floors.push(floor);
     }
 
+    /// Add a ceiling to export.
+    pub fn add_ceiling(&mut self, ceiling: CeilingExportData) {
+        self.ceilings.push(ceiling);
+    }
+
     /// Add a roof to export.
     pub fn add_roof(&mut self, roof: RoofExportData) {
         self.roofs.push(roof);
     }
 
+    /// Add a railing to export.
+    pub fn add_railing(&mut self, railing: RailingExportData) {
+        self.railings.push(railing);
+    }
+
+    /// Add a building level to export as an IfcBuildingStorey.
+    pub fn add_level(&mut self, level: LevelExportData) {
+        self.levels.push(level);
+    }
+
+    /// Place an element on a level.
+    ///
+    /// Elements without a level are contained in the lowest storey.
+    pub fn assign_to_level(&mut self, element_id: Uuid, level_id: Uuid) {
+        self.element_levels.insert(element_id, level_id);
+    }
+
+    /// Attach a property set (e.g. `Pset_WallCommon`) to an element.
+    ///
+    /// Exported as an IfcPropertySet of single values, related to the
+    /// element by IfcRelDefinesByProperties. Sets for elements that are
+    /// not part of the export are left out.
+    pub fn add_property_set(
+        &mut self,
+        element_id: Uuid,
+        pset_name: &str,
+        props: HashMap<String, IfcValue>,
+    ) {
+        self.property_sets.push(PropertySet {
+            element_id,
+            name: pset_name.to_string(),
+            properties: props,
+        });
+    }
+
     /// Get the total element count.
     pub fn element_count(&self) -> usize {
         self.walls.len()
@@ -178,7 +294,9 @@ impl IfcExporter {
             + self.windows.len()
             + self.rooms.len()
             + self.floors.len()
+            + self.ceilings.len()
             + self.roofs.len()
+            + self.railings.len()
     }
 
     /// Export to IFC STEP format string.
@@ -203,19 +321,10 @@ impl IfcExporter {
         output.push_str("ENDSEC;\n\n");
         output.push_str("DATA;\n");
 
-        // Project entity
+        // Project entity, written once its context and units exist
         let project_id = entity_id;
         entity_id += 1;
-        output.push_str(&format!(
-            "#{}=IFCPROJECT('{}',#{},'{}','{}',*,*,*,(#{}),#{});\n",
-            project_id,
-            generate_global_id(),
-            entity_id, // owner history
-            self.metadata.name,
-            self.metadata.description,
-            entity_id + 2, // context
-            entity_id + 3, // units
-        ));
+        let project_pos = output.len();
 
         // Owner history (simplified)
         let owner_history_id = entity_id;
@@ -283,7 +392,7 @@ impl IfcExporter {
             entity_id + 1,
         ));
 
-        // Length unit (meters)
+        // Length unit: always metres, without prefix, matching the model
         let length_unit_id = entity_id;
         entity_id += 1;
         output.push_str(&format!(
@@ -299,6 +408,20 @@ impl IfcExporter {
             area_unit_id
         ));
 
+        output.insert_str(
+            project_pos,
+            &format!(
+                "#{}=IFCPROJECT('{}',#{},'{}','{}',*,*,*,(#{}),#{});\n",
+                project_id,
+                generate_global_id(),
+                owner_history_id,
+                self.metadata.name,
+                self.metadata.description,
+                context_id,
+                units_id,
+            ),
+        );
+
         // Site
         let site_id = entity_id;
         entity_id += 1;
@@ -319,91 +442,144 @@ impl IfcExporter {
             owner_history_id,
         ));
 
-        // Building storey
-        let storey_id = entity_id;
-        entity_id += 1;
-        output.push_str(&format!(
-            "#{}=IFCBUILDINGSTOREY('{}',#{},'Level 1',$,$,$,$,$,.ELEMENT.,0.);\n",
-            storey_id,
-            generate_global_id(),
-            owner_history_id,
-        ));
+        // Building storeys, lowest first; a default storey when no levels are defined
+        let mut levels: Vec<LevelExportData> = self.levels.clone();
+        levels.sort_by(|a, b| a.elevation.total_cmp(&b.elevation));
+        if levels.is_empty() {
+            levels.push(LevelExportData {
+                id: Uuid::nil(),
+                name: "Level 1".to_string(),
+                elevation: 0.0,
+            });
+        }
+
+        let mut storey_ids = Vec::with_capacity(levels.len());
+        for level in &levels {
+            let storey_id = entity_id;
+            entity_id += 1;
+            storey_ids.push(storey_id);
+            output.push_str(&format!(
+                "#{}=IFCBUILDINGSTOREY('{}',#{},'{}',$,$,$,$,$,.ELEMENT.,{:.6});\n",
+                storey_id,
+                generate_global_id(),
+                owner_history_id,
+                level.name,
+                level.elevation,
+            ));
+        }
 
-        // Rel aggregates: Project -> Site -> Building -> Storey
+        // Rel aggregates: Project -> Site -> Building -> Storeys
+        let rel_id = entity_id;
         entity_id += 1;
         output.push_str(&format!(
             "#{}=IFCRELAGGREGATES('{}',#{},$,$,#{},(#{}));\n",
-            entity_id,
+            rel_id,
             generate_global_id(),
             owner_history_id,
             project_id,
             site_id,
         ));
 
+        let rel_id = entity_id;
         entity_id += 1;
         output.push_str(&format!(
             "#{}=IFCRELAGGREGATES('{}',#{},$,$,#{},(#{}));\n",
-            entity_id,
+            rel_id,
             generate_global_id(),
             owner_history_id,
             site_id,
             building_id,
         ));
 
+        let storey_refs: Vec<String> = storey_ids.iter().map(|id| format!("#{}", id)).collect();
+        let rel_id = entity_id;
         entity_id += 1;
         output.push_str(&format!(
-            "#{}=IFCRELAGGREGATES('{}',#{},$,$,#{},(#{}));\n",
-            entity_id,
+            "#{}=IFCRELAGGREGATES('{}',#{},$,$,#{},({}));\n",
+            rel_id,
             generate_global_id(),
             owner_history_id,
             building_id,
-            storey_id,
+            storey_refs.join(","),
         ));
 
+        // Storey index for each element
+        let storey_of = |element_id: Uuid| -> usize {
+            self.element_levels
+                .get(&element_id)
+                .and_then(|level_id| levels.iter().position(|l| l.id == *level_id))
+                .unwrap_or(0)
+        };
+        let mut contained: Vec<Vec<u64>> = vec![Vec::new(); levels.len()];
+        let mut element_entities: HashMap<Uuid, u64> = HashMap::new();
+
         // Export walls
-        let mut wall_ids = Vec::new();
         for wall in &self.walls {
-            let wall_id = entity_id;
-            wall_ids.push(wall_id);
+            contained[storey_of(wall.id)].push(entity_id);
+            element_entities.insert(wall.id, entity_id);
             output.push_str(&self.export_wall(wall, &mut entity_id, owner_history_id, context_id));
         }
 
         // Export rooms
-        let mut room_ids = Vec::new();
         for room in &self.rooms {
-            let room_id = entity_id;
-            room_ids.push(room_id);
+            contained[storey_of(room.id)].push(entity_id);
+            element_entities.insert(room.id, entity_id);
             output.push_str(&self.export_room(room, &mut entity_id, owner_history_id, context_id));
         }
 
         // Export floors
-        let mut floor_ids = Vec::new();
         for floor in &self.floors {
-            let floor_id = entity_id;
-            floor_ids.push(floor_id);
+            contained[storey_of(floor.id)].push(entity_id);
+            element_entities.insert(floor.id, entity_id);
             output.push_str(&self.export_floor(floor, &mut entity_id, owner_history_id, context_id));
         }
 
-        // Relate elements to storey
-        if !wall_ids.is_empty() || !room_ids.is_empty() || !floor_ids.is_empty() {
-            let all_elements: Vec<String> = wall_ids
-                .iter()
-                .chain(room_ids.iter())
-                .chain(floor_ids.iter())
-                .map(|id| format!("#{}", id))
-                .collect();
+        // Export ceilings
+        for ceiling in &self.ceilings {
+            contained[storey_of(ceiling.id)].push(entity_id);
+            element_entities.insert(ceiling.id, entity_id);
+            output.push_str(&self.export_ceiling(ceiling, &mut entity_id, owner_history_id));
+        }
+
+        // Export railings
+        for railing in &self.railings {
+            contained[storey_of(railing.id)].push(entity_id);
+            element_entities.insert(railing.id, entity_id);
+            output.push_str(&self.export_railing(railing, &mut entity_id, owner_history_id));
+        }
+
+        // Relate elements to their storeys
+        for (storey_id, element_ids) in storey_ids.iter().zip(&contained) {
+            if element_ids.is_empty() {
+                continue;
+            }
 
+            let refs: Vec<String> = element_ids.iter().map(|id| format!("#{}", id)).collect();
+            let rel_id = entity_id;
             entity_id += 1;
             output.push_str(&format!(
                 "#{}=IFCRELCONTAINEDINSPATIALSTRUCTURE('{}',#{},$,$,({}),#{});\n",
-                entity_id,
+                rel_id,
                 generate_global_id(),
                 owner_history_id,
-                all_elements.join(","),
+                refs.join(","),
                 storey_id,
             ));
         }
 
+        // Property sets
+        for pset in &self.property_sets {
+            let Some(&element_entity) = element_entities.get(&pset.element_id) else {
+                continue;
+            };
+            output.push_str(&self.export_property_set(
+                pset,
+                element_entity,
+                &mut entity_id,
+                owner_history_id,
+            ));
+        }
+
         output.push_str("ENDSEC;\n");
         output.push_str("END-ISO-10303-21;\n");
 
@@ -415,7 +591,7 @@ impl IfcExporter {
         wall: &WallExportData,
         entity_id: &mut u64,
         owner_history_id: u64,
-        _context_id: u64,
+        context_id: u64,
     ) -> String {
         let mut output = String::new();
         let wall_id = *entity_id;
@@ -471,26 +647,227 @@ impl IfcExporter {
             x_dir_id, dir_x, dir_y
         ));
 
+        // Axis and body, in the wall's local frame
+        let (shape, shape_id) =
+            self.export_wall_shape(length, wall.thickness, wall.height, entity_id, context_id);
+        output.push_str(&shape);
+
         // Wall entity
         output.push_str(&format!(
-            "#{}=IFCWALLSTANDARDCASE('{}',#{},'{}','{}',$,#{},$,$,.NOTDEFINED.);\n",
+            "#{}=IFCWALLSTANDARDCASE('{}',#{},'{}','{}',$,#{},#{},$,.NOTDEFINED.);\n",
             wall_id,
-            format!("{:032X}", wall.id.as_u128()),
+            guid::compress(wall.id),
             owner_history_id,
             wall.name,
             wall.wall_type,
             placement_id,
+            shape_id,
         ));
 
         output
     }
 
+    fn export_property_set(
+        &self,
+        pset: &PropertySet,
+        element_entity: u64,
+        entity_id: &mut u64,
+        owner_history_id: u64,
+    ) -> String {
+        let mut output = String::new();
+
+        // Sorted so the output does not depend on map order
+        let mut properties: Vec<_> = pset.properties.iter().collect();
+        properties.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut property_refs = Vec::with_capacity(properties.len());
+        for (name, value) in properties {
+            let property_id = *entity_id;
+            *entity_id += 1;
+            property_refs.push(format!("#{}", property_id));
+            output.push_str(&format!(
+                "#{}=IFCPROPERTYSINGLEVALUE('{}',$,{},$);\n",
+                property_id,
+                escape_step_string(name),
+                value.to_step(),
+            ));
+        }
+
+        let pset_id = *entity_id;
+        *entity_id += 1;
+        output.push_str(&format!(
+            "#{}=IFCPROPERTYSET('{}',#{},'{}',$,({}));\n",
+            pset_id,
+            generate_global_id(),
+            owner_history_id,
+            escape_step_string(&pset.name),
+            property_refs.join(","),
+        ));
+
+        let rel_id = *entity_id;
+        *entity_id += 1;
+        output.push_str(&format!(
+            "#{}=IFCRELDEFINESBYPROPERTIES('{}',#{},$,$,(#{}),#{});\n",
+            rel_id,
+            generate_global_id(),
+            owner_history_id,
+            element_entity,
+            pset_id,
+        ));
+
+        output
+    }
+
+    /// Write a wall's `Axis` polyline and `Body` swept rectangle, in the
+    /// wall's local frame (X along the wall, centered on the axis).
+    ///
+    /// Returns the output and the IfcProductDefinitionShape id.
+    fn export_wall_shape(
+        &self,
+        length: f64,
+        thickness: f64,
+        height: f64,
+        entity_id: &mut u64,
+        context_id: u64,
+    ) -> (String, u64) {
+        let mut output = String::new();
+
+        let start_id = push_entity(&mut output, entity_id, "IFCCARTESIANPOINT((0.,0.))");
+        let end_id = push_entity(
+            &mut output,
+            entity_id,
+            &format!("IFCCARTESIANPOINT(({:.6},0.))", length),
+        );
+        let axis_curve_id = push_entity(
+            &mut output,
+            entity_id,
+            &format!("IFCPOLYLINE((#{},#{}))", start_id, end_id),
+        );
+        let axis_rep_id = push_entity(
+            &mut output,
+            entity_id,
+            &format!(
+                "IFCSHAPEREPRESENTATION(#{},'Axis','Curve2D',(#{}))",
+                context_id, axis_curve_id
+            ),
+        );
+
+        let center_id = push_entity(
+            &mut output,
+            entity_id,
+            &format!("IFCCARTESIANPOINT(({:.6},0.))", length / 2.0),
+        );
+        let profile_axis_id = push_entity(
+            &mut output,
+            entity_id,
+            &format!("IFCAXIS2PLACEMENT2D(#{},$)", center_id),
+        );
+        let profile_id = push_entity(
+            &mut output,
+            entity_id,
+            &format!(
+                "IFCRECTANGLEPROFILEDEF(.AREA.,$,#{},{:.6},{:.6})",
+                profile_axis_id, length, thickness
+            ),
+        );
+        let body_rep_id =
+            self.export_swept_solid(profile_id, height, &mut output, entity_id, context_id);
+
+        let shape_id = push_entity(
+            &mut output,
+            entity_id,
+            &format!("IFCPRODUCTDEFINITIONSHAPE($,$,(#{},#{}))", axis_rep_id, body_rep_id),
+        );
+        (output, shape_id)
+    }
+
+    /// Write a closed outline extruded along +Z as a `Body` representation.
+    ///
+    /// Returns the output and the IfcProductDefinitionShape id.
+    fn export_extruded_outline(
+        &self,
+        outline: &[(f64, f64)],
+        depth: f64,
+        entity_id: &mut u64,
+        context_id: u64,
+    ) -> (String, u64) {
+        let mut output = String::new();
+
+        let mut point_refs: Vec<String> = outline
+            .iter()
+            .map(|(x, y)| {
+                let id = push_entity(
+                    &mut output,
+                    entity_id,
+                    &format!("IFCCARTESIANPOINT(({:.6},{:.6}))", x, y),
+                );
+                format!("#{}", id)
+            })
+            .collect();
+        // IfcPolyline closes by repeating its first point
+        point_refs.push(point_refs[0].clone());
+        let curve_id = push_entity(
+            &mut output,
+            entity_id,
+            &format!("IFCPOLYLINE(({}))", point_refs.join(",")),
+        );
+        let profile_id = push_entity(
+            &mut output,
+            entity_id,
+            &format!("IFCARBITRARYCLOSEDPROFILEDEF(.AREA.,$,#{})", curve_id),
+        );
+        let body_rep_id =
+            self.export_swept_solid(profile_id, depth, &mut output, entity_id, context_id);
+
+        let shape_id = push_entity(
+            &mut output,
+            entity_id,
+            &format!("IFCPRODUCTDEFINITIONSHAPE($,$,(#{}))", body_rep_id),
+        );
+        (output, shape_id)
+    }
+
+    /// Write a profile extruded `depth` along +Z from the element origin, as
+    /// a `Body` shape representation. Returns the representation id.
+    fn export_swept_solid(
+        &self,
+        profile_id: u64,
+        depth: f64,
+        output: &mut String,
+        entity_id: &mut u64,
+        context_id: u64,
+    ) -> u64 {
+        let origin_id = push_entity(output, entity_id, "IFCCARTESIANPOINT((0.,0.,0.))");
+        let position_id = push_entity(
+            output,
+            entity_id,
+            &format!("IFCAXIS2PLACEMENT3D(#{},$,$)", origin_id),
+        );
+        let direction_id = push_entity(output, entity_id, "IFCDIRECTION((0.,0.,1.))");
+        let solid_id = push_entity(
+            output,
+            entity_id,
+            &format!(
+                "IFCEXTRUDEDAREASOLID(#{},#{},#{},{:.6})",
+                profile_id, position_id, direction_id, depth
+            ),
+        );
+        push_entity(
+            output,
+            entity_id,
+            &format!(
+                "IFCSHAPEREPRESENTATION(#{},'Body','SweptSolid',(#{}))",
+                context_id, solid_id
+            ),
+        )
+    }
+
     fn export_room(
         &self,
         room: &RoomExportData,
         entity_id: &mut u64,
         owner_history_id: u64,
-        _context_id: u64,
+        context_id: u64,
     ) -> String {
         let mut output = String::new();
         let room_id = *entity_id;
@@ -528,16 +905,32 @@ impl IfcExporter {
             origin_id, centroid.0, centroid.1
         ));
 
-        // Space entity
+        // Boundary extruded to the room height, relative to the centroid
+        let representation = if room.boundary_points.len() >= 3 {
+            let outline: Vec<(f64, f64)> = room
+                .boundary_points
+                .iter()
+                .map(|p| (p.x - centroid.0, p.y - centroid.1))
+                .collect();
+            let (shape, shape_id) =
+                self.export_extruded_outline(&outline, room.height, entity_id, context_id);
+            output.push_str(&shape);
+            format!("#{}", shape_id)
+        } else {
+            "$".to_string()
+        };
+
+        // Space entity: Name is the room number, LongName the room name
         output.push_str(&format!(
-            "#{}=IFCSPACE('{}',#{},'{}','{}','{}',$,#{},$,.INTERNAL.,.ELEMENT.,$);\n",
+            "#{}=IFCSPACE('{}',#{},'{}','{}',$,#{},{},'{}',.ELEMENT.,.INTERNAL.,$);\n",
             room_id,
-            format!("{:032X}", room.id.as_u128()),
+            guid::compress(room.id),
             owner_history_id,
             room.number,
-            room.name,
             format!("Area: {:.2} m²", room.area),
             placement_id,
+            representation,
+            room.name,
         ));
 
         output
@@ -548,7 +941,7 @@ impl IfcExporter {
         floor: &FloorExportData,
         entity_id: &mut u64,
         owner_history_id: u64,
-        _context_id: u64,
+        context_id: u64,
     ) -> String {
         let mut output = String::new();
         let floor_id = *entity_id;
@@ -578,14 +971,123 @@ impl IfcExporter {
             origin_id, floor.level
         ));
 
+        // Boundary extruded up by the slab thickness
+        let representation = if floor.boundary_points.len() >= 3 {
+            let outline: Vec<(f64, f64)> =
+                floor.boundary_points.iter().map(|p| (p.x, p.y)).collect();
+            let (shape, shape_id) =
+                self.export_extruded_outline(&outline, floor.thickness, entity_id, context_id);
+            output.push_str(&shape);
+            format!("#{}", shape_id)
+        } else {
+            "$".to_string()
+        };
+
         // Slab entity
         output.push_str(&format!(
-            "#{}=IFCSLAB('{}',#{},'{}','',$,#{},$,$,.FLOOR.);\n",
+            "#{}=IFCSLAB('{}',#{},'{}','',$,#{},{},$,.FLOOR.);\n",
             floor_id,
-            format!("{:032X}", floor.id.as_u128()),
+            guid::compress(floor.id),
             owner_history_id,
             floor.name,
             placement_id,
+            representation,
+        ));
+
+        output
+    }
+
+    fn export_ceiling(
+        &self,
+        ceiling: &CeilingExportData,
+        entity_id: &mut u64,
+        owner_history_id: u64,
+    ) -> String {
+        let mut output = String::new();
+        let ceiling_id = *entity_id;
+        *entity_id += 1;
+
+        // Ceiling placement at the underside elevation
+        let placement_id = *entity_id;
+        *entity_id += 1;
+        output.push_str(&format!(
+            "#{}=IFCLOCALPLACEMENT($,#{});\n",
+            placement_id, *entity_id
+        ));
+
+        let axis_id = *entity_id;
+        *entity_id += 1;
+        output.push_str(&format!(
+            "#{}=IFCAXIS2PLACEMENT3D(#{},$,$);\n",
+            axis_id, *entity_id
+        ));
+
+        let origin_id = *entity_id;
+        *entity_id += 1;
+        output.push_str(&format!(
+            "#{}=IFCCARTESIANPOINT((0.,0.,{:.6}));\n",
+            origin_id, ceiling.elevation
+        ));
+
+        // Covering entity
+        output.push_str(&format!(
+            "#{}=IFCCOVERING('{}',#{},'{}','',$,#{},$,$,.CEILING.);\n",
+            ceiling_id,
+            guid::compress(ceiling.id),
+            owner_history_id,
+            ceiling.name,
+            placement_id,
+        ));
+
+        output
+    }
+
+    fn export_railing(
+        &self,
+        railing: &RailingExportData,
+        entity_id: &mut u64,
+        owner_history_id: u64,
+    ) -> String {
+        let mut output = String::new();
+        let railing_id = *entity_id;
+        *entity_id += 1;
+
+        // Railing placement at the start of its path
+        let placement_id = *entity_id;
+        *entity_id += 1;
+        output.push_str(&format!(
+            "#{}=IFCLOCALPLACEMENT($,#{});\n",
+            placement_id, *entity_id
+        ));
+
+        let axis_id = *entity_id;
+        *entity_id += 1;
+        output.push_str(&format!(
+            "#{}=IFCAXIS2PLACEMENT3D(#{},$,$);\n",
+            axis_id, *entity_id
+        ));
+
+        let origin = railing
+            .path
+            .first()
+            .copied()
+            .unwrap_or(Point3::new(0.0, 0.0, 0.0));
+        let origin_id = *entity_id;
+        *entity_id += 1;
+        output.push_str(&format!(
+            "#{}=IFCCARTESIANPOINT(({:.6},{:.6},{:.6}));\n",
+            origin_id, origin.x, origin.y, origin.z
+        ));
+
+        // Railing entity
+        output.push_str(&format!(
+            "#{}=IFCRAILING('{}',#{},'{}','',$,#{},$,$,.{}.);\n",
+            railing_id,
+            guid::compress(railing.id),
+            owner_history_id,
+            railing.name,
+            placement_id,
+            railing.railing_type.to_uppercase(),
         ));
 
         output
@@ -796,12 +1298,22 @@ pub enum ElementValidation<'a> {
     Floor(&'a FloorExportData),
 }
 
-/// Generate an IFC GlobalId (base64-ish 22-character string).
+/// Append `#id=entity;` under the next entity id and return that id.
+fn push_entity(output: &mut String, entity_id: &mut u64, entity: &str) -> u64 {
+    let id = *entity_id;
+    *entity_id += 1;
+    output.push_str(&format!("#{}={};\n", id, entity));
+    id
+}
+
+/// Generate a fresh IFC GlobalId for an entity that has no Pensaer id.
 fn generate_global_id() -> String {
-    let uuid = Uuid::new_v4();
-    // IFC uses a custom base64 encoding for GlobalId
-    // Simplified: just use first 22 chars of hex UUID
-    format!("{:032X}", uuid.as_u128())[..22].to_string()
+    guid::compress(Uuid::new_v4())
+}
+
+/// Escape a string for a STEP string literal (quotes are doubled).
+fn escape_step_string(s: &str) -> String {
+    s.replace('\'', "''")
 }
 
 /// Get current timestamp in ISO format.
@@ -856,6 +1368,168 @@ mod tests {
         assert!(content.contains("IFCWALLSTANDARDCASE"));
     }
 
+    #[test]
+    fn export_levels_as_storeys() {
+        let mut exporter = IfcExporter::new("Test Project", "Test Author");
+        let ground = Uuid::new_v4();
+        let upper = Uuid::new_v4();
+        exporter.add_level(LevelExportData {
+            id: upper,
+            name: "Level 2".to_string(),
+            elevation: 3.0,
+        });
+        exporter.add_level(LevelExportData {
+            id: ground,
+            name: "Level 1".to_string(),
+            elevation: 0.0,
+        });
+
+        let wall_id = Uuid::new_v4();
+        exporter.add_wall(WallExportData {
+            id: wall_id,
+            name: "Wall 1".to_string(),
+            start: Point2::new(0.0, 0.0),
+            end: Point2::new(5.0, 0.0),
+            height: 3.0,
+            thickness: 0.2,
+            base_level: 0.0,
+            wall_type: "Basic".to_string(),
+        });
+        exporter.assign_to_level(wall_id, upper);
+
+        let content = exporter.export().unwrap();
+        assert_eq!(content.matches("IFCBUILDINGSTOREY").count(), 2);
+        assert!(content.find("'Level 1'").unwrap() < content.find("'Level 2'").unwrap());
+
+        // The wall is contained in the upper storey
+        let storey_line = content.lines().find(|l| l.contains("'Level 2'")).unwrap();
+        let storey_ref = storey_line.split('=').next().unwrap();
+        let containment = content
+            .lines()
+            .find(|l| l.contains("IFCRELCONTAINEDINSPATIALSTRUCTURE"))
+            .unwrap();
+        assert!(containment.ends_with(&format!("{});", storey_ref)));
+        assert_eq!(content.matches("IFCRELCONTAINEDINSPATIALSTRUCTURE").count(), 1);
+    }
+
+    #[test]
+    fn export_railing() {
+        let mut exporter = IfcExporter::new("Test Project", "Test Author");
+        exporter.add_railing(RailingExportData {
+            id: Uuid::new_v4(),
+            name: "Stair Railing".to_string(),
+            height: 1.1,
+            path: vec![Point3::new(0.0, 0.0, 0.0), Point3::new(4.0, 0.0, 3.0)],
+            railing_type: "Balustrade".to_string(),
+        });
+        assert_eq!(exporter.element_count(), 1);
+
+        let content = exporter.export().unwrap();
+        assert!(content.contains("IFCRAILING("));
+        assert!(content.contains(".BALUSTRADE.);"));
+        assert_eq!(content.matches("IFCRELCONTAINEDINSPATIALSTRUCTURE").count(), 1);
+    }
+
+    #[test]
+    fn export_ceiling() {
+        let mut exporter = IfcExporter::new("Test Project", "Test Author");
+        exporter.add_ceiling(CeilingExportData {
+            id: Uuid::new_v4(),
+            name: "Office Ceiling".to_string(),
+            thickness: 0.02,
+            elevation: 2.6,
+            boundary_points: vec![
+                Point2::new(0.0, 0.0),
+                Point2::new(5.0, 0.0),
+                Point2::new(5.0, 4.0),
+                Point2::new(0.0, 4.0),
+            ],
+        });
+        assert_eq!(exporter.element_count(), 1);
+
+        let content = exporter.export().unwrap();
+        assert!(content.contains("IFCCOVERING("));
+        assert!(content.contains(".CEILING.);"));
+        assert!(content.contains("IFCCARTESIANPOINT((0.,0.,2.600000));"));
+        assert_eq!(
+            content.matches("IFCRELCONTAINEDINSPATIALSTRUCTURE").count(),
+            1
+        );
+    }
+
+    #[test]
+    fn export_property_set() {
+        let mut exporter = IfcExporter::new("Test Project", "Test Author");
+        let wall_id = Uuid::new_v4();
+        exporter.add_wall(WallExportData {
+            id: wall_id,
+            name: "Wall 1".to_string(),
+            start: Point2::new(0.0, 0.0),
+            end: Point2::new(5.0, 0.0),
+            height: 3.0,
+            thickness: 0.2,
+            base_level: 0.0,
+            wall_type: "Basic".to_string(),
+        });
+        let props = HashMap::from([
+            ("IsExternal".to_string(), IfcValue::Boolean(true)),
+            ("FireRating".to_string(), IfcValue::String("REI 60".to_string())),
+            ("ThermalTransmittance".to_string(), IfcValue::Real(0.25)),
+            ("AcousticRatingClass".to_string(), IfcValue::Integer(3)),
+        ]);
+        exporter.add_property_set(wall_id, "Pset_WallCommon", props);
+        // Elements outside the export get no property set
+        exporter.add_property_set(Uuid::new_v4(), "Pset_DoorCommon", HashMap::new());
+
+        let content = exporter.export().unwrap();
+        assert!(content.contains("IFCPROPERTYSINGLEVALUE('IsExternal',$,IFCBOOLEAN(.T.),$);"));
+        assert!(content.contains("IFCPROPERTYSINGLEVALUE('FireRating',$,IFCLABEL('REI 60'),$);"));
+        assert!(content.contains("IFCREAL(0.250000)"));
+        assert!(content.contains("IFCINTEGER(3)"));
+        assert_eq!(content.matches("IFCPROPERTYSET(").count(), 1);
+        assert!(!content.contains("Pset_DoorCommon"));
+
+        // The relationship points at the wall and the set
+        let wall_ref = content
+            .lines()
+            .find(|l| l.contains("IFCWALLSTANDARDCASE"))
+            .and_then(|l| l.split('=').next())
+            .unwrap();
+        let pset_ref = content
+            .lines()
+            .find(|l| l.contains("'Pset_WallCommon'"))
+            .and_then(|l| l.split('=').next())
+            .unwrap();
+        let rel = content
+            .lines()
+            .find(|l| l.contains("IFCRELDEFINESBYPROPERTIES"))
+            .unwrap();
+        assert!(rel.ends_with(&format!(",({}),{});", wall_ref, pset_ref)));
+    }
+
+    #[test]
+    fn export_declares_metres() {
+        let content = IfcExporter::new("Test Project", "Test Author").export().unwrap();
+        assert!(content.contains("IFCSIUNIT(*,.LENGTHUNIT.,$,.METRE.);"));
+
+        // The project's UnitsInContext is the unit assignment
+        let entity_type = |reference: &str| {
+            let prefix = format!("{}=", reference);
+            content
+                .lines()
+                .find(|l| l.starts_with(&prefix))
+                .and_then(|l| l[prefix.len()..].split('(').next())
+                .unwrap()
+                .to_string()
+        };
+        let project = content.lines().find(|l| l.contains("IFCPROJECT(")).unwrap();
+        let units_ref = project.trim_end_matches(");").rsplit(',').next().unwrap();
+        assert_eq!(entity_type(units_ref), "IFCUNITASSIGNMENT");
+
+        let importer = crate::IfcImporter::from_string(content.clone()).unwrap();
+        assert_eq!(importer.length_scale(), 1.0);
+    }
+
     #[test]
     fn global_id_length() {
         let id = generate_global_id();
@@ -0,0 +1,79 @@
+//! IFC GlobalId encoding.
+//!
+//! IFC stores a 128-bit GUID as 22 characters from a 64-character alphabet:
+//! the first character holds the top 2 bits, each of the other 21 holds 6.
+
+use uuid::Uuid;
+
+/// Characters of the IFC base-64 alphabet, in value order.
+const ALPHABET: &[u8; 64] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz_$";
+
+/// Length of an encoded GlobalId.
+pub(crate) const GLOBAL_ID_LEN: usize = 22;
+
+/// Encode a UUID as an IFC GlobalId.
+pub(crate) fn compress(id: Uuid) -> String {
+    let n = id.as_u128();
+    (0..GLOBAL_ID_LEN)
+        .map(|i| {
+            let shift = 6 * (GLOBAL_ID_LEN - 1 - i);
+            ALPHABET[((n >> shift) & 63) as usize] as char
+        })
+        .collect()
+}
+
+/// Decode an IFC GlobalId back into a UUID.
+///
+/// Returns `None` unless the text is 22 characters of the alphabet with a
+/// first character worth at most 3.
+pub(crate) fn expand(global_id: &str) -> Option<Uuid> {
+    if global_id.len() != GLOBAL_ID_LEN {
+        return None;
+    }
+
+    let mut n: u128 = 0;
+    for (i, ch) in global_id.bytes().enumerate() {
+        let value = ALPHABET.iter().position(|&c| c == ch)? as u128;
+        if i == 0 && value > 3 {
+            return None;
+        }
+        n = (n << 6) | value;
+    }
+    Some(Uuid::from_u128(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for id in [
+            Uuid::nil(),
+            Uuid::from_u128(u128::MAX),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        ] {
+            let global_id = compress(id);
+            assert_eq!(global_id.len(), GLOBAL_ID_LEN);
+            assert_eq!(expand(&global_id), Some(id));
+        }
+    }
+
+    #[test]
+    fn known_encoding() {
+        assert_eq!(compress(Uuid::nil()), "0000000000000000000000");
+        assert_eq!(
+            compress(Uuid::from_u128(u128::MAX)),
+            "3$$$$$$$$$$$$$$$$$$$$$"
+        );
+        assert_eq!(expand("0000000000000000000001"), Some(Uuid::from_u128(1)));
+    }
+
+    #[test]
+    fn rejects_invalid_ids() {
+        assert_eq!(expand("too short"), None);
+        assert_eq!(expand("4000000000000000000000"), None);
+        assert_eq!(expand("000000000000000000000-"), None);
+    }
+}
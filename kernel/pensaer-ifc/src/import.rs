@@ -1,11 +1,16 @@
 //! IFC import functionality.
 //!
-//! Parses IFC files (STEP format) into Pensaer elements.
+//! Parses IFC files (STEP format) into Pensaer elements. Lengths are
+//! converted to metres from the project's declared length unit.
 
 use crate::error::{IfcError, Result};
-use crate::export::{FloorExportData, RoomExportData, WallExportData};
+use crate::guid;
+use crate::export::{
+    DoorExportData, FloorExportData, RoomExportData, WallExportData, WindowExportData,
+};
 use pensaer_math::Point2;
 use std::collections::HashMap;
+use std::io::BufRead;
 use std::path::Path;
 use uuid::Uuid;
 
@@ -27,6 +32,9 @@ pub struct ImportStatistics {
     pub floors_imported: usize,
     pub roofs_imported: usize,
     pub unknown_entities: usize,
+    /// Number of entities dropped by a streaming import because no
+    /// extractor reads their type
+    pub discarded_entities: usize,
     /// Number of entities skipped due to errors (self-healing mode)
     pub skipped_entities: usize,
     /// Number of entities repaired (self-healing mode)
@@ -57,59 +65,189 @@ impl ImportStatistics {
     }
 }
 
+/// Entity types a streaming import always keeps: the elements we extract,
+/// their hosting relationships, and the project's units.
+const RETAINED_ENTITY_TYPES: &[&str] = &[
+    // Elements
+    "IFCWALL",
+    "IFCWALLSTANDARDCASE",
+    "IFCDOOR",
+    "IFCDOORSTANDARDCASE",
+    "IFCWINDOW",
+    "IFCWINDOWSTANDARDCASE",
+    "IFCSLAB",
+    "IFCSPACE",
+    "IFCROOF",
+    "IFCOPENINGELEMENT",
+    // Hosting
+    "IFCRELVOIDSELEMENT",
+    "IFCRELFILLSELEMENT",
+    // Units
+    "IFCPROJECT",
+    "IFCUNITASSIGNMENT",
+    "IFCSIUNIT",
+    "IFCCONVERSIONBASEDUNIT",
+    "IFCMEASUREWITHUNIT",
+];
+
+/// Placement and shape entity types a streaming import keeps only when a
+/// retained entity references them, directly or through other entities of
+/// these types.
+const REFERENCED_ENTITY_TYPES: &[&str] = &[
+    "IFCLOCALPLACEMENT",
+    "IFCAXIS2PLACEMENT2D",
+    "IFCAXIS2PLACEMENT3D",
+    "IFCCARTESIANPOINT",
+    "IFCDIRECTION",
+    "IFCPRODUCTDEFINITIONSHAPE",
+    "IFCSHAPEREPRESENTATION",
+    "IFCPOLYLINE",
+    "IFCEXTRUDEDAREASOLID",
+    "IFCRECTANGLEPROFILEDEF",
+    "IFCARBITRARYCLOSEDPROFILEDEF",
+];
+
 /// IFC importer for parsing IFC files.
 pub struct IfcImporter {
-    content: String,
     entities: HashMap<u64, IfcEntity>,
     statistics: ImportStatistics,
+    /// UUIDs handed to imported walls, by entity id, so hosted doors and
+    /// windows link to the same walls.
+    wall_ids: HashMap<u64, Uuid>,
+    /// Metres per unit of the file's length unit.
+    length_scale: f64,
+}
+
+/// A resolved object placement: world origin and the plan direction of the
+/// local X axis. Rotations other than about Z are ignored.
+#[derive(Debug, Clone, Copy)]
+struct Placement {
+    origin: (f64, f64, f64),
+    x_dir: (f64, f64),
+}
+
+impl Placement {
+    const IDENTITY: Self = Self {
+        origin: (0.0, 0.0, 0.0),
+        x_dir: (1.0, 0.0),
+    };
+
+    /// Place `local`, given relative to this placement, in world coordinates.
+    fn compose(&self, local: &Placement) -> Placement {
+        let (cos, sin) = self.x_dir;
+        let (lx, ly, lz) = local.origin;
+        let (dx, dy) = local.x_dir;
+        Placement {
+            origin: (
+                self.origin.0 + lx * cos - ly * sin,
+                self.origin.1 + lx * sin + ly * cos,
+                self.origin.2 + lz,
+            ),
+            x_dir: (dx * cos - dy * sin, dx * sin + dy * cos),
+        }
+    }
+}
+
+/// Fields shared by doors and windows filling a wall opening.
+struct HostedFilling {
+    id: Uuid,
+    name: String,
+    object_type: Option<String>,
+    host_wall_id: Uuid,
+    width: f64,
+    height: f64,
+    /// Distance along the host wall from its start to the filling's center.
+    offset: f64,
+    /// Height of the filling's base above the host wall's base.
+    elevation: f64,
 }
 
 impl IfcImporter {
     /// Create an importer from file path.
     pub fn from_file(path: &Path) -> Result<Self> {
-        let content = std::fs::read_to_string(path)?;
-        Self::from_string(content)
+        let file = std::fs::File::open(path)?;
+        Self::parse(std::io::BufReader::new(file), |_| true)
     }
 
     /// Create an importer from IFC content string.
     pub fn from_string(content: String) -> Result<Self> {
-        let mut importer = Self {
-            content,
-            entities: HashMap::new(),
-            statistics: ImportStatistics::default(),
-        };
-        importer.parse_entities()?;
-        Ok(importer)
+        Self::parse(content.as_bytes(), |_| true)
     }
 
-    /// Parse STEP entities from the content.
-    fn parse_entities(&mut self) -> Result<()> {
-        // Find DATA section
-        let data_start = self
-            .content
-            .find("DATA;")
-            .ok_or_else(|| IfcError::InvalidStructure("Missing DATA section".to_string()))?;
+    /// Create an importer that streams IFC content from `reader`.
+    ///
+    /// The DATA section is read once, one statement at a time, so any
+    /// `BufRead` works: stdin, a socket, or a decompressing reader.
+    /// Elements, their hosting, and units are kept as they are read.
+    /// Placement and shape statements are held as text until the end of the
+    /// section, then only those the kept entities reach are parsed.
+    /// Property sets, materials, styles, tessellated geometry, and points or
+    /// placements of anything else are dropped and counted in
+    /// [`ImportStatistics::discarded_entities`]. Memory then follows the
+    /// size of the building model rather than the file, which suits large
+    /// federated models. Extraction works as for
+    /// [`IfcImporter::from_string`].
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self> {
+        let mut importer = Self::empty();
+        let mut geometry: HashMap<u64, String> = HashMap::new();
+        let mut pending: Vec<u64> = Vec::new();
+        for_each_statement(reader, |statement| match statement_header(statement) {
+            Some((_, entity_type)) if is_one_of(entity_type, RETAINED_ENTITY_TYPES) => {
+                if let Some(entity) = importer.parse_entity_line(statement) {
+                    pending.extend(statement_references(statement));
+                    importer.entities.insert(entity.id, entity);
+                }
+            }
+            Some((id, entity_type)) if is_one_of(entity_type, REFERENCED_ENTITY_TYPES) => {
+                geometry.insert(id, statement.to_string());
+            }
+            Some(_) => importer.statistics.discarded_entities += 1,
+            None => {}
+        })?;
+
+        // Follow references from the kept entities through the geometry
+        while let Some(id) = pending.pop() {
+            if let Some(statement) = geometry.remove(&id) {
+                pending.extend(statement_references(&statement));
+                if let Some(entity) = importer.parse_entity_line(&statement) {
+                    importer.entities.insert(entity.id, entity);
+                }
+            }
+        }
+        importer.statistics.discarded_entities += geometry.len();
 
-        let data_end = self.content[data_start..]
-            .find("ENDSEC;")
-            .map(|pos| data_start + pos)
-            .ok_or_else(|| IfcError::InvalidStructure("Missing ENDSEC".to_string()))?;
+        importer.length_scale = importer.detect_length_scale();
+        Ok(importer)
+    }
 
-        let data_section = &self.content[data_start + 5..data_end];
+    fn empty() -> Self {
+        Self {
+            entities: HashMap::new(),
+            statistics: ImportStatistics::default(),
+            wall_ids: HashMap::new(),
+            length_scale: 1.0,
+        }
+    }
 
-        // Parse each line
-        for line in data_section.lines() {
-            let line = line.trim();
-            if line.is_empty() || !line.starts_with('#') {
-                continue;
-            }
+    fn parse<R: BufRead>(reader: R, retain: fn(&str) -> bool) -> Result<Self> {
+        let mut importer = Self::empty();
+        importer.parse_entities(reader, retain)?;
+        importer.length_scale = importer.detect_length_scale();
+        Ok(importer)
+    }
 
-            if let Some(entity) = self.parse_entity_line(line) {
-                self.entities.insert(entity.id, entity);
+    /// Parse STEP entities from the DATA section, keeping those whose type
+    /// passes `retain`.
+    fn parse_entities<R: BufRead>(&mut self, reader: R, retain: fn(&str) -> bool) -> Result<()> {
+        for_each_statement(reader, |statement| match statement_header(statement) {
+            Some((_, entity_type)) if retain(entity_type) => {
+                if let Some(entity) = self.parse_entity_line(statement) {
+                    self.entities.insert(entity.id, entity);
+                }
             }
-        }
-
-        Ok(())
+            Some(_) => self.statistics.discarded_entities += 1,
+            None => {}
+        })
     }
 
     /// Parse a single entity line.
@@ -181,6 +319,87 @@ impl IfcImporter {
         self.entities.len()
     }
 
+    /// Metres per unit of the file's length unit, e.g. 0.001 for a file in
+    /// millimetres.
+    ///
+    /// Imported coordinates and dimensions are scaled by this factor, so
+    /// extracted elements are always in metres. Files without a length unit
+    /// are taken to be in metres.
+    pub fn length_scale(&self) -> f64 {
+        self.length_scale
+    }
+
+    /// Find the project's length unit and its size in metres.
+    fn detect_length_scale(&self) -> f64 {
+        // IfcProject parameter 8 is UnitsInContext; fall back to any unit
+        // assignment for files that do not link it
+        let project_units = self
+            .get_entities_by_type("IFCPROJECT")
+            .first()
+            .and_then(|project| project.parameters.get(8))
+            .and_then(|r| self.parse_reference(r))
+            .and_then(|id| self.entities.get(&id))
+            .filter(|e| e.entity_type == "IFCUNITASSIGNMENT");
+        let units = project_units.or_else(|| {
+            self.entities
+                .values()
+                .filter(|e| e.entity_type == "IFCUNITASSIGNMENT")
+                .min_by_key(|e| e.id)
+        });
+
+        units
+            .and_then(|assignment| assignment.parameters.first())
+            .map(|list| self.parse_reference_list(list))
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|id| self.entities.get(&id))
+            .filter(|unit| unit.parameters.get(1).map(String::as_str) == Some(".LENGTHUNIT."))
+            .find_map(|unit| self.unit_scale(unit, 0))
+            .unwrap_or(1.0)
+    }
+
+    /// Size of a length unit in metres.
+    fn unit_scale(&self, unit: &IfcEntity, depth: usize) -> Option<f64> {
+        // Conversion factors can chain through other units
+        const MAX_DEPTH: usize = 8;
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        match unit.entity_type.as_str() {
+            // Parameters: Dimensions, UnitType, Prefix, Name
+            "IFCSIUNIT" => {
+                if unit.parameters.get(3).map(String::as_str) != Some(".METRE.") {
+                    return None;
+                }
+                let prefix = unit.parameters.get(2).map(String::as_str).unwrap_or("$");
+                si_prefix_factor(prefix)
+            }
+            // Parameters: Dimensions, UnitType, Name, ConversionFactor
+            "IFCCONVERSIONBASEDUNIT" => {
+                let factor = self.entities.get(&self.parse_reference(unit.parameters.get(3)?)?)?;
+                // IfcMeasureWithUnit parameters: ValueComponent, UnitComponent
+                if factor.entity_type != "IFCMEASUREWITHUNIT" {
+                    return None;
+                }
+                let value = parse_typed_real(factor.parameters.first()?)?;
+                let base = self.entities.get(&self.parse_reference(factor.parameters.get(1)?)?)?;
+                let scale = value * self.unit_scale(base, depth + 1)?;
+                (scale.is_finite() && scale > 0.0).then_some(scale)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse a list of references, `(#1,#2)`.
+    fn parse_reference_list(&self, s: &str) -> Vec<u64> {
+        s.trim_start_matches('(')
+            .trim_end_matches(')')
+            .split(',')
+            .filter_map(|r| self.parse_reference(r.trim()))
+            .collect()
+    }
+
     /// Get entities of a specific type.
     fn get_entities_by_type(&self, entity_type: &str) -> Vec<&IfcEntity> {
         self.entities
@@ -203,6 +422,7 @@ impl IfcImporter {
 
         for entity in wall_entities {
             if let Some(wall) = self.parse_wall(&entity) {
+                self.wall_ids.insert(entity.id, wall.id);
                 walls.push(wall);
             }
         }
@@ -232,15 +452,19 @@ impl IfcImporter {
             name,
             start,
             end,
-            height: 3.0,      // Default, should be extracted from representation
-            thickness: 0.2,   // Default, should be extracted from representation
-            base_level: 0.0,
-            wall_type: "Basic".to_string(),
+            height: self.extract_wall_height(entity).unwrap_or(3.0),
+            thickness: self.extract_wall_thickness(entity).unwrap_or(0.2),
+            base_level: self.object_placement(entity).origin.2,
+            wall_type: self.extract_wall_type(entity),
         })
     }
 
     /// Extract wall geometry from placement and representation.
     fn extract_wall_geometry(&self, entity: &IfcEntity) -> Option<(Point2, Point2)> {
+        if let Some(axis) = self.wall_axis(entity) {
+            return Some(axis);
+        }
+
         // Get ObjectPlacement reference (parameter 5, 0-indexed = 5)
         let placement_ref = entity.parameters.get(5)?;
         if placement_ref == "$" {
@@ -275,7 +499,132 @@ impl IfcImporter {
         Some((Point2::new(0.0, 0.0), Point2::new(1.0, 0.0)))
     }
 
-    /// Parse a Cartesian point entity.
+    /// World start and end of a wall from its `Axis` representation, a
+    /// polyline in the wall's placement.
+    fn wall_axis(&self, entity: &IfcEntity) -> Option<(Point2, Point2)> {
+        let polyline = self.representation_item(entity, "Axis", "IFCPOLYLINE")?;
+        let points = self.polyline_points(polyline)?;
+        if points.len() < 2 {
+            return None;
+        }
+        let first = points[0];
+        let last = points[points.len() - 1];
+
+        let placement = self.object_placement(entity);
+        let to_world = |(x, y, _): (f64, f64, f64)| {
+            let local = Placement {
+                origin: (x, y, 0.0),
+                x_dir: (1.0, 0.0),
+            };
+            let world = placement.compose(&local);
+            Point2::new(world.origin.0, world.origin.1)
+        };
+        Some((to_world(first), to_world(last)))
+    }
+
+    /// First item of the given type in one of an element's shape
+    /// representations (`Axis`, `Body`, ...).
+    fn representation_item(
+        &self,
+        entity: &IfcEntity,
+        identifier: &str,
+        item_type: &str,
+    ) -> Option<&IfcEntity> {
+        // IfcProductDefinitionShape parameters: Name, Description, Representations
+        let shape = self.entities.get(&self.parse_reference(entity.parameters.get(6)?)?)?;
+        let quoted = format!("'{}'", identifier);
+
+        // IfcShapeRepresentation parameters: ContextOfItems, RepresentationIdentifier, RepresentationType, Items
+        self.parse_reference_list(shape.parameters.get(2)?)
+            .into_iter()
+            .filter_map(|id| self.entities.get(&id))
+            .filter(|rep| rep.parameters.get(1) == Some(&quoted))
+            .filter_map(|rep| rep.parameters.get(3))
+            .flat_map(|items| self.parse_reference_list(items))
+            .filter_map(|id| self.entities.get(&id))
+            .find(|item| item.entity_type == item_type)
+    }
+
+    /// Points of a polyline, in its own coordinates.
+    fn polyline_points(&self, polyline: &IfcEntity) -> Option<Vec<(f64, f64, f64)>> {
+        self.parse_reference_list(polyline.parameters.first()?)
+            .into_iter()
+            .map(|id| self.parse_cartesian_point(self.entities.get(&id)?))
+            .collect()
+    }
+
+    /// The element's `Body` extrusion, if it is a single swept solid.
+    fn body_solid(&self, entity: &IfcEntity) -> Option<&IfcEntity> {
+        self.representation_item(entity, "Body", "IFCEXTRUDEDAREASOLID")
+    }
+
+    /// Extrusion depth of the element's body, in metres.
+    fn body_depth(&self, entity: &IfcEntity) -> Option<f64> {
+        // IfcExtrudedAreaSolid parameters: SweptArea, Position, ExtrudedDirection, Depth
+        let solid = self.body_solid(entity)?;
+        Some(self.parse_positive_real(solid.parameters.get(3))? * self.length_scale)
+    }
+
+    /// World outline of the element's body, when its swept area is a closed
+    /// polyline profile.
+    fn body_outline(&self, entity: &IfcEntity) -> Option<Vec<Point2>> {
+        let solid = self.body_solid(entity)?;
+        // IfcArbitraryClosedProfileDef parameters: ProfileType, ProfileName, OuterCurve
+        let profile = self.entities.get(&self.parse_reference(solid.parameters.first()?)?)?;
+        if profile.entity_type != "IFCARBITRARYCLOSEDPROFILEDEF" {
+            return None;
+        }
+        let curve = self.entities.get(&self.parse_reference(profile.parameters.get(2)?)?)?;
+        if curve.entity_type != "IFCPOLYLINE" {
+            return None;
+        }
+        let mut points = self.polyline_points(curve)?;
+        // Drop the closing point
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+
+        let position = solid
+            .parameters
+            .get(1)
+            .and_then(|r| self.parse_reference(r))
+            .and_then(|id| self.entities.get(&id))
+            .map(|axis| self.axis_placement(axis))
+            .unwrap_or(Placement::IDENTITY);
+        let frame = self.object_placement(entity).compose(&position);
+        let outline = points
+            .into_iter()
+            .map(|(x, y, _)| {
+                let local = Placement {
+                    origin: (x, y, 0.0),
+                    x_dir: (1.0, 0.0),
+                };
+                let world = frame.compose(&local);
+                Point2::new(world.origin.0, world.origin.1)
+            })
+            .collect();
+        Some(outline)
+    }
+
+    /// Frame of a wall for locating hosted elements: origin at the wall
+    /// start, X along the wall.
+    fn wall_frame(&self, wall: &IfcEntity) -> Placement {
+        let placement = self.object_placement(wall);
+        let Some((start, end)) = self.wall_axis(wall) else {
+            return placement;
+        };
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length < 1e-12 {
+            return placement;
+        }
+        Placement {
+            origin: (start.x, start.y, placement.origin.2),
+            x_dir: (dx / length, dy / length),
+        }
+    }
+
+    /// Parse a Cartesian point entity, in metres.
     fn parse_cartesian_point(&self, entity: &IfcEntity) -> Option<(f64, f64, f64)> {
         if entity.entity_type != "IFCCARTESIANPOINT" {
             return None;
@@ -289,9 +638,10 @@ impl IfcImporter {
             .filter_map(|s| s.trim().parse().ok())
             .collect();
 
-        let x = coords.first().copied().unwrap_or(0.0);
-        let y = coords.get(1).copied().unwrap_or(0.0);
-        let z = coords.get(2).copied().unwrap_or(0.0);
+        let scale = self.length_scale;
+        let x = coords.first().copied().unwrap_or(0.0) * scale;
+        let y = coords.get(1).copied().unwrap_or(0.0) * scale;
+        let z = coords.get(2).copied().unwrap_or(0.0) * scale;
 
         Some((x, y, z))
     }
@@ -334,19 +684,26 @@ impl IfcImporter {
     /// Parse a room/space entity.
     fn parse_room(&self, entity: &IfcEntity) -> Option<RoomExportData> {
         // Parameters: GlobalId, OwnerHistory, Name, Description, ObjectType, ObjectPlacement, Representation, LongName, CompositionType, PredefinedType, ElevationWithFlooring
-        let global_id = self.parse_string(&entity.parameters.get(0).cloned().unwrap_or_default());
+        let global_id = self.parse_string(&entity.parameters.first().cloned().unwrap_or_default());
         let number = self.parse_string(&entity.parameters.get(2).cloned().unwrap_or_default());
-        let name = self.parse_string(&entity.parameters.get(3).cloned().unwrap_or_default());
+        // The room name is the LongName; older exports put it in Description
+        let name = [7, 3]
+            .iter()
+            .filter_map(|&i| entity.parameters.get(i))
+            .map(|s| self.parse_string(s))
+            .find(|s| !s.is_empty() && s != "$")
+            .unwrap_or_else(|| number.clone());
 
         let id = parse_global_id_to_uuid(&global_id).unwrap_or_else(Uuid::new_v4);
+        let boundary_points = self.body_outline(entity).unwrap_or_default();
 
         Some(RoomExportData {
             id,
-            name: if name.is_empty() { number.clone() } else { name },
+            name,
             number,
-            area: 0.0,    // Would need to be calculated from geometry
-            height: 2.7,  // Default
-            boundary_points: Vec::new(),
+            area: polygon_area(&boundary_points),
+            height: self.body_depth(entity).unwrap_or(2.7),
+            boundary_points,
         })
     }
 
@@ -373,7 +730,7 @@ impl IfcImporter {
 
     /// Parse a floor/slab entity.
     fn parse_floor(&self, entity: &IfcEntity) -> Option<FloorExportData> {
-        let global_id = self.parse_string(&entity.parameters.get(0).cloned().unwrap_or_default());
+        let global_id = self.parse_string(&entity.parameters.first().cloned().unwrap_or_default());
         let name = self.parse_string(&entity.parameters.get(2).cloned().unwrap_or_default());
 
         let id = parse_global_id_to_uuid(&global_id).unwrap_or_else(Uuid::new_v4);
@@ -381,12 +738,245 @@ impl IfcImporter {
         Some(FloorExportData {
             id,
             name,
-            thickness: 0.3,  // Default
-            level: 0.0,
-            boundary_points: Vec::new(),
+            thickness: self.body_depth(entity).unwrap_or(0.3),
+            level: self.object_placement(entity).origin.2,
+            boundary_points: self.body_outline(entity).unwrap_or_default(),
+        })
+    }
+
+    /// Extract doors hosted in walls.
+    ///
+    /// A door is linked to its host wall through `IfcRelFillsElement`
+    /// (opening to door) and `IfcRelVoidsElement` (wall to opening). Doors
+    /// without a host wall are not imported. Call [`extract_walls`] first so
+    /// `host_wall_id` matches the imported walls' ids.
+    ///
+    /// [`extract_walls`]: Self::extract_walls
+    pub fn extract_doors(&mut self) -> Result<Vec<DoorExportData>> {
+        let hosts = self.filling_hosts();
+        let doors: Vec<_> = self
+            .entities
+            .values()
+            .filter(|e| e.entity_type == "IFCDOOR" || e.entity_type == "IFCDOORSTANDARDCASE")
+            .filter_map(|e| self.parse_filling(e, &hosts, 0.9, 2.1))
+            .map(|f| DoorExportData {
+                id: f.id,
+                name: f.name,
+                host_wall_id: f.host_wall_id,
+                width: f.width,
+                height: f.height,
+                offset: f.offset,
+                door_type: f.object_type.unwrap_or_else(|| "Single".to_string()),
+            })
+            .collect();
+
+        self.statistics.doors_imported = doors.len();
+        Ok(doors)
+    }
+
+    /// Extract windows hosted in walls.
+    ///
+    /// Linked to host walls like [`extract_doors`]; the sill height is the
+    /// window's height above the base of its wall.
+    ///
+    /// [`extract_doors`]: Self::extract_doors
+    pub fn extract_windows(&mut self) -> Result<Vec<WindowExportData>> {
+        let hosts = self.filling_hosts();
+        let windows: Vec<_> = self
+            .entities
+            .values()
+            .filter(|e| e.entity_type == "IFCWINDOW" || e.entity_type == "IFCWINDOWSTANDARDCASE")
+            .filter_map(|e| self.parse_filling(e, &hosts, 1.2, 1.2))
+            .map(|f| WindowExportData {
+                id: f.id,
+                name: f.name,
+                host_wall_id: f.host_wall_id,
+                width: f.width,
+                height: f.height,
+                sill_height: f.elevation,
+                offset: f.offset,
+                window_type: f.object_type.unwrap_or_else(|| "Fixed".to_string()),
+            })
+            .collect();
+
+        self.statistics.windows_imported = windows.len();
+        Ok(windows)
+    }
+
+    /// Map each filling element (door, window) to the entity id of the wall
+    /// hosting the opening it fills.
+    fn filling_hosts(&self) -> HashMap<u64, u64> {
+        // IfcRelVoidsElement: ..., RelatingBuildingElement, RelatedOpeningElement
+        let opening_walls: HashMap<u64, u64> = self
+            .get_entities_by_type("IFCRELVOIDSELEMENT")
+            .into_iter()
+            .filter_map(|rel| {
+                let wall = self.parse_reference(rel.parameters.get(4)?)?;
+                let opening = self.parse_reference(rel.parameters.get(5)?)?;
+                Some((opening, wall))
+            })
+            .collect();
+
+        // IfcRelFillsElement: ..., RelatingOpeningElement, RelatedBuildingElement
+        self.get_entities_by_type("IFCRELFILLSELEMENT")
+            .into_iter()
+            .filter_map(|rel| {
+                let opening = self.parse_reference(rel.parameters.get(4)?)?;
+                let filling = self.parse_reference(rel.parameters.get(5)?)?;
+                Some((filling, *opening_walls.get(&opening)?))
+            })
+            .collect()
+    }
+
+    /// Parse a door or window entity and locate it in its host wall.
+    fn parse_filling(
+        &self,
+        entity: &IfcEntity,
+        hosts: &HashMap<u64, u64>,
+        default_width: f64,
+        default_height: f64,
+    ) -> Option<HostedFilling> {
+        // Parameters: GlobalId, OwnerHistory, Name, Description, ObjectType, ObjectPlacement, Representation, Tag, OverallHeight, OverallWidth, ...
+        let wall = self.entities.get(hosts.get(&entity.id)?)?;
+        let host_wall_id = match self.wall_ids.get(&wall.id) {
+            Some(id) => *id,
+            None => parse_global_id_to_uuid(&self.parse_string(wall.parameters.first()?))?,
+        };
+
+        let global_id = self.parse_string(&entity.parameters.first().cloned().unwrap_or_default());
+        let id = parse_global_id_to_uuid(&global_id).unwrap_or_else(Uuid::new_v4);
+        let name = self.parse_string(&entity.parameters.get(2).cloned().unwrap_or_default());
+        let object_type = entity
+            .parameters
+            .get(4)
+            .map(|s| self.parse_string(s))
+            .filter(|s| !s.is_empty() && s != "$");
+        let height = self
+            .parse_positive_real(entity.parameters.get(8))
+            .map_or(default_height, |h| h * self.length_scale);
+        let width = self
+            .parse_positive_real(entity.parameters.get(9))
+            .map_or(default_width, |w| w * self.length_scale);
+
+        // The filling's local origin sits at one side of the opening, with
+        // its width running along the local X axis.
+        let wall_placement = self.wall_frame(wall);
+        let placement = self.object_placement(entity);
+        let (cos, sin) = wall_placement.x_dir;
+        let dx = placement.origin.0 - wall_placement.origin.0;
+        let dy = placement.origin.1 - wall_placement.origin.1;
+        let along = dx * cos + dy * sin;
+        let facing = placement.x_dir.0 * cos + placement.x_dir.1 * sin;
+        let offset = if facing < 0.0 {
+            along - width / 2.0
+        } else {
+            along + width / 2.0
+        };
+
+        Some(HostedFilling {
+            id,
+            name,
+            object_type,
+            host_wall_id,
+            width,
+            height,
+            offset,
+            elevation: placement.origin.2 - wall_placement.origin.2,
         })
     }
 
+    /// Resolve the world placement of an element from its ObjectPlacement
+    /// (parameter 5), following `PlacementRelTo` up the chain. Missing or
+    /// unreadable placements resolve to the origin.
+    fn object_placement(&self, entity: &IfcEntity) -> Placement {
+        entity
+            .parameters
+            .get(5)
+            .and_then(|r| self.parse_reference(r))
+            .and_then(|id| self.resolve_local_placement(id, 0))
+            .unwrap_or(Placement::IDENTITY)
+    }
+
+    /// Resolve an `IfcLocalPlacement` to world coordinates.
+    fn resolve_local_placement(&self, placement_id: u64, depth: usize) -> Option<Placement> {
+        // Guard against reference cycles in malformed files
+        const MAX_DEPTH: usize = 64;
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        // Parameters: PlacementRelTo, RelativePlacement
+        let placement = self.entities.get(&placement_id)?;
+        if placement.entity_type != "IFCLOCALPLACEMENT" {
+            return None;
+        }
+
+        let parent = match placement.parameters.first().and_then(|r| self.parse_reference(r)) {
+            Some(parent_id) => self.resolve_local_placement(parent_id, depth + 1)?,
+            None => Placement::IDENTITY,
+        };
+
+        let axis = placement
+            .parameters
+            .get(1)
+            .and_then(|r| self.parse_reference(r))
+            .and_then(|id| self.entities.get(&id));
+        match axis {
+            Some(axis) => Some(parent.compose(&self.axis_placement(axis))),
+            None => Some(parent),
+        }
+    }
+
+    /// Read an `IfcAxis2Placement3D` (or 2D) as a placement.
+    fn axis_placement(&self, axis: &IfcEntity) -> Placement {
+        // Parameters: Location, Axis, RefDirection (2D: Location, RefDirection)
+        let ref_direction = if axis.entity_type == "IFCAXIS2PLACEMENT2D" { 1 } else { 2 };
+        let origin = axis
+            .parameters
+            .first()
+            .and_then(|r| self.parse_reference(r))
+            .and_then(|id| self.entities.get(&id))
+            .and_then(|point| self.parse_cartesian_point(point))
+            .unwrap_or((0.0, 0.0, 0.0));
+        let x_dir = axis
+            .parameters
+            .get(ref_direction)
+            .and_then(|r| self.parse_reference(r))
+            .and_then(|id| self.entities.get(&id))
+            .and_then(|dir| self.parse_direction(dir))
+            .unwrap_or((1.0, 0.0));
+
+        Placement { origin, x_dir }
+    }
+
+    /// Parse a direction entity into a unit vector in plan.
+    fn parse_direction(&self, entity: &IfcEntity) -> Option<(f64, f64)> {
+        if entity.entity_type != "IFCDIRECTION" {
+            return None;
+        }
+
+        let ratios_str = entity.parameters.first()?;
+        let ratios_str = ratios_str.trim_start_matches('(').trim_end_matches(')');
+        let ratios: Vec<f64> = ratios_str
+            .split(',')
+            .filter_map(|s| s.trim().parse().ok())
+            .collect();
+
+        let x = ratios.first().copied().unwrap_or(0.0);
+        let y = ratios.get(1).copied().unwrap_or(0.0);
+        let length = (x * x + y * y).sqrt();
+        if length < 1e-12 {
+            return None;
+        }
+
+        Some((x / length, y / length))
+    }
+
+    /// Parse an optional positive real parameter (`$` means unset).
+    fn parse_positive_real(&self, s: Option<&String>) -> Option<f64> {
+        s?.trim().parse::<f64>().ok().filter(|v| v.is_finite() && *v > 0.0)
+    }
+
     /// Get a summary of what was found in the IFC file.
     pub fn get_summary(&self) -> HashMap<String, usize> {
         let mut summary = HashMap::new();
@@ -439,6 +1029,7 @@ impl IfcImporter {
                         repaired += 1;
                         error_log.push(format!("#{}: geometry repaired", entity.id));
                     }
+                    self.wall_ids.insert(entity.id, wall.id);
                     elements.push(wall);
                 }
                 Err(e) => {
@@ -520,8 +1111,8 @@ impl IfcImporter {
                 end,
                 height,
                 thickness,
-                base_level: 0.0,
-                wall_type: "Basic".to_string(),
+                base_level: self.object_placement(entity).origin.2,
+                wall_type: self.extract_wall_type(entity),
             },
             was_repaired,
         ))
@@ -602,18 +1193,30 @@ impl IfcImporter {
         Ok((new_start, new_end, repaired))
     }
 
-    /// Try to extract wall height from entity (placeholder - would parse representation).
-    fn extract_wall_height(&self, _entity: &IfcEntity) -> Option<f64> {
-        // In a full implementation, this would parse the IfcExtrudedAreaSolid
-        // depth from the wall's representation
-        None
+    /// Wall height: the depth of its swept body.
+    fn extract_wall_height(&self, entity: &IfcEntity) -> Option<f64> {
+        self.body_depth(entity)
+    }
+
+    /// Wall thickness: the Y dimension of its swept rectangle profile.
+    fn extract_wall_thickness(&self, entity: &IfcEntity) -> Option<f64> {
+        // IfcRectangleProfileDef parameters: ProfileType, ProfileName, Position, XDim, YDim
+        let solid = self.body_solid(entity)?;
+        let profile = self.entities.get(&self.parse_reference(solid.parameters.first()?)?)?;
+        if profile.entity_type != "IFCRECTANGLEPROFILEDEF" {
+            return None;
+        }
+        Some(self.parse_positive_real(profile.parameters.get(4))? * self.length_scale)
     }
 
-    /// Try to extract wall thickness from entity (placeholder - would parse representation).
-    fn extract_wall_thickness(&self, _entity: &IfcEntity) -> Option<f64> {
-        // In a full implementation, this would parse the IfcRectangleProfileDef
-        // from the wall's representation
-        None
+    /// Wall type from the Description, as written by [`IfcExporter`](crate::IfcExporter).
+    fn extract_wall_type(&self, entity: &IfcEntity) -> String {
+        entity
+            .parameters
+            .get(3)
+            .map(|s| self.parse_string(s))
+            .filter(|s| !s.is_empty() && s != "$")
+            .unwrap_or_else(|| "Basic".to_string())
     }
 
     /// Import rooms with automatic error recovery.
@@ -687,18 +1290,133 @@ impl IfcImporter {
     }
 }
 
+/// Call `visit` with each `#id=TYPE(...);` statement of the DATA section.
+///
+/// Statements may span several lines; one ends at a semicolon outside a
+/// string.
+fn for_each_statement<R: BufRead>(reader: R, mut visit: impl FnMut(&str)) -> Result<()> {
+    let mut in_data = false;
+    let mut statement = String::new();
+    let mut quotes = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if !in_data {
+            in_data = line == "DATA;";
+            continue;
+        }
+        if statement.is_empty() {
+            if line == "ENDSEC;" {
+                return Ok(());
+            }
+            if !line.starts_with('#') {
+                continue;
+            }
+        }
+
+        statement.push_str(line);
+        quotes += line.matches('\'').count();
+        if !line.ends_with(';') || quotes % 2 == 1 {
+            continue;
+        }
+
+        visit(&statement);
+        statement.clear();
+        quotes = 0;
+    }
+
+    let missing = if in_data { "Missing ENDSEC" } else { "Missing DATA section" };
+    Err(IfcError::InvalidStructure(missing.to_string()))
+}
+
+/// Id and entity type of a `#id=TYPE(...);` statement, without parsing its
+/// parameters.
+fn statement_header(statement: &str) -> Option<(u64, &str)> {
+    let (id, rest) = statement.split_once('=')?;
+    let id = id.trim().strip_prefix('#')?.trim().parse().ok()?;
+    let (entity_type, _) = rest.split_once('(')?;
+    Some((id, entity_type.trim()))
+}
+
+/// Entity ids (`#123`) a statement's parameters refer to, skipping strings.
+fn statement_references(statement: &str) -> impl Iterator<Item = u64> + '_ {
+    let params = statement.split_once('(').map_or("", |(_, params)| params);
+    params
+        .split('\'')
+        .step_by(2)
+        .flat_map(|unquoted| unquoted.split('#').skip(1))
+        .filter_map(|reference| {
+            let digits = reference
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(reference, |end| &reference[..end]);
+            digits.parse().ok()
+        })
+}
+
+/// Whether `entity_type` is one of `types`, ignoring case.
+fn is_one_of(entity_type: &str, types: &[&str]) -> bool {
+    types.iter().any(|t| t.eq_ignore_ascii_case(entity_type))
+}
+
+/// Area enclosed by a polygon, by the shoelace formula.
+fn polygon_area(points: &[Point2]) -> f64 {
+    let n = points.len();
+    if n < 3 {
+        return 0.0;
+    }
+    let twice: f64 = (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a.x * b.y - b.x * a.y
+        })
+        .sum();
+    twice.abs() / 2.0
+}
+
+/// Factor of an IfcSIUnitPrefix (`$` for none).
+fn si_prefix_factor(prefix: &str) -> Option<f64> {
+    let factor = match prefix {
+        "$" => 1.0,
+        ".EXA." => 1e18,
+        ".PETA." => 1e15,
+        ".TERA." => 1e12,
+        ".GIGA." => 1e9,
+        ".MEGA." => 1e6,
+        ".KILO." => 1e3,
+        ".HECTO." => 1e2,
+        ".DECA." => 1e1,
+        ".DECI." => 1e-1,
+        ".CENTI." => 1e-2,
+        ".MILLI." => 1e-3,
+        ".MICRO." => 1e-6,
+        ".NANO." => 1e-9,
+        ".PICO." => 1e-12,
+        ".FEMTO." => 1e-15,
+        ".ATTO." => 1e-18,
+        _ => return None,
+    };
+    Some(factor)
+}
+
+/// Parse a typed measure such as `IFCLENGTHMEASURE(0.3048)`, or a bare real.
+fn parse_typed_real(s: &str) -> Option<f64> {
+    let inner = match (s.find('('), s.rfind(')')) {
+        (Some(open), Some(close)) if open < close => &s[open + 1..close],
+        _ => s,
+    };
+    inner.trim().parse().ok()
+}
+
 /// Try to parse an IFC GlobalId to a UUID.
+///
+/// Accepts standard 22-character GlobalIds and the 32-digit hex ids written
+/// by earlier Pensaer exports.
 fn parse_global_id_to_uuid(global_id: &str) -> Option<Uuid> {
-    // IFC GlobalId is a 22-character base64-encoded value
-    // Try to parse it, or return None
-    if global_id.len() >= 22 {
-        // Simplified: try parsing as hex
-        u128::from_str_radix(&global_id[..22], 16)
-            .ok()
-            .map(Uuid::from_u128)
-    } else {
-        None
+    if global_id.len() == 32 {
+        return u128::from_str_radix(global_id, 16).ok().map(Uuid::from_u128);
     }
+    guid::expand(global_id)
 }
 
 #[cfg(test)]
@@ -731,6 +1449,68 @@ END-ISO-10303-21;
         .to_string()
     }
 
+    /// One wall starting at (2, 1) with a 0.9 m door hosted 1.5 m along it.
+    fn create_hosted_door_ifc() -> String {
+        r#"ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('ViewDefinition'),'2;1');
+FILE_NAME('hosted.ifc','2026-01-16',('Author'),('Org'),'Pensaer','Pensaer','');
+FILE_SCHEMA(('IFC4'));
+ENDSEC;
+DATA;
+#2=IFCOWNERHISTORY($,$,.NOCHANGE.,$,$,$,$,0);
+#20=IFCDIRECTION((0.,0.,1.));
+#21=IFCDIRECTION((1.,0.,0.));
+#30=IFCCARTESIANPOINT((2.,1.,0.));
+#31=IFCAXIS2PLACEMENT3D(#30,#20,#21);
+#32=IFCLOCALPLACEMENT($,#31);
+#100=IFCWALLSTANDARDCASE('0000000000000000000001',#2,'Hosting Wall','','',#32,$,$,.NOTDEFINED.);
+#40=IFCCARTESIANPOINT((1.5,0.,0.));
+#41=IFCAXIS2PLACEMENT3D(#40,#20,#21);
+#42=IFCLOCALPLACEMENT(#32,#41);
+#110=IFCOPENINGELEMENT('0000000000000000000002',#2,'Opening','','',#42,$,$,.OPENING.);
+#50=IFCCARTESIANPOINT((0.,0.,0.));
+#51=IFCAXIS2PLACEMENT3D(#50,$,$);
+#52=IFCLOCALPLACEMENT(#42,#51);
+#120=IFCDOOR('0000000000000000000003',#2,'Front Door','','',#52,$,$,2.1,0.9,.DOOR.,.SINGLE_SWING_LEFT.,$);
+#130=IFCRELVOIDSELEMENT('0000000000000000000004',#2,$,$,#100,#110);
+#140=IFCRELFILLSELEMENT('0000000000000000000005',#2,$,$,#110,#120);
+ENDSEC;
+END-ISO-10303-21;
+"#
+        .to_string()
+    }
+
+    /// A 5000 mm wall starting at (1000, 2000) mm, in a millimetre project.
+    fn create_millimetre_ifc() -> String {
+        r#"ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('ViewDefinition'),'2;1');
+FILE_NAME('mm.ifc','2026-01-16',('Author'),('Org'),'Pensaer','Pensaer','');
+FILE_SCHEMA(('IFC4'));
+ENDSEC;
+DATA;
+#1=IFCPROJECT('0000000000000000000010',#2,'Millimetres',$,$,$,$,(#10),#11);
+#2=IFCOWNERHISTORY($,$,.NOCHANGE.,$,$,$,$,0);
+#10=IFCGEOMETRICREPRESENTATIONCONTEXT($,'Model',3,1.0E-05,$,$);
+#11=IFCUNITASSIGNMENT((#12,#13));
+#12=IFCSIUNIT(*,.AREAUNIT.,$,.SQUARE_METRE.);
+#13=IFCSIUNIT(*,.LENGTHUNIT.,.MILLI.,.METRE.);
+#30=IFCCARTESIANPOINT((1000.,2000.,0.));
+#31=IFCAXIS2PLACEMENT3D(#30,$,$);
+#32=IFCLOCALPLACEMENT($,#31);
+#60=IFCCARTESIANPOINT((0.,0.));
+#61=IFCCARTESIANPOINT((5000.,0.));
+#62=IFCPOLYLINE((#60,#61));
+#63=IFCSHAPEREPRESENTATION(#10,'Axis','Curve2D',(#62));
+#64=IFCPRODUCTDEFINITIONSHAPE($,$,(#63));
+#100=IFCWALLSTANDARDCASE('0000000000000000000011',#2,'Long Wall','','',#32,#64,$,.NOTDEFINED.);
+ENDSEC;
+END-ISO-10303-21;
+"#
+        .to_string()
+    }
+
     #[test]
     fn parse_ifc_content() {
         let importer = IfcImporter::from_string(create_test_ifc()).unwrap();
@@ -752,6 +1532,196 @@ END-ISO-10303-21;
         assert_eq!(rooms.len(), 1);
     }
 
+    #[test]
+    fn extract_hosted_door() {
+        let mut importer = IfcImporter::from_string(create_hosted_door_ifc()).unwrap();
+        let walls = importer.extract_walls().unwrap();
+        let doors = importer.extract_doors().unwrap();
+
+        assert_eq!(walls.len(), 1);
+        assert_eq!(doors.len(), 1);
+        let door = &doors[0];
+        assert_eq!(door.name, "Front Door");
+        assert_eq!(door.host_wall_id, walls[0].id);
+        assert!((door.width - 0.9).abs() < 1e-9);
+        assert!((door.height - 2.1).abs() < 1e-9);
+        // Opening 1.5 m along the wall, door center half its width further
+        assert!((door.offset - 1.95).abs() < 1e-9);
+
+        let stats = importer.statistics();
+        assert_eq!(stats.walls_imported, 1);
+        assert_eq!(stats.doors_imported, 1);
+        assert_eq!(stats.windows_imported, 0);
+    }
+
+    #[test]
+    fn extract_hosted_window_in_rotated_wall() {
+        // Turn the wall to run along +Y and swap the door for a window
+        // sitting 0.9 m above the wall base
+        let content = create_hosted_door_ifc()
+            .replace(
+                "#31=IFCAXIS2PLACEMENT3D(#30,#20,#21);",
+                "#22=IFCDIRECTION((0.,1.,0.));\n#31=IFCAXIS2PLACEMENT3D(#30,#20,#22);",
+            )
+            .replace("#50=IFCCARTESIANPOINT((0.,0.,0.));", "#50=IFCCARTESIANPOINT((0.,0.,0.9));")
+            .replace(
+                "IFCDOOR('0000000000000000000003',#2,'Front Door','','',#52,$,$,2.1,0.9,.DOOR.,.SINGLE_SWING_LEFT.,$)",
+                "IFCWINDOW('0000000000000000000003',#2,'Window','','',#52,$,$,1.2,1.5,.WINDOW.,.SINGLE_PANEL.,$)",
+            );
+        let mut importer = IfcImporter::from_string(content).unwrap();
+        let walls = importer.extract_walls().unwrap();
+        assert!(importer.extract_doors().unwrap().is_empty());
+        let windows = importer.extract_windows().unwrap();
+
+        assert_eq!(windows.len(), 1);
+        let window = &windows[0];
+        assert_eq!(window.host_wall_id, walls[0].id);
+        assert!((window.offset - 2.25).abs() < 1e-9);
+        assert!((window.sill_height - 0.9).abs() < 1e-9);
+        assert_eq!(importer.statistics().windows_imported, 1);
+    }
+
+    #[test]
+    fn streamed_elements_resolve_placements_defined_later() {
+        // Move every placement statement after the elements referencing it
+        let content = create_hosted_door_ifc();
+        let (placements, rest): (Vec<&str>, Vec<&str>) = content
+            .lines()
+            .partition(|line| line.contains("PLACEMENT") || line.contains("IFCCARTESIANPOINT"));
+        let reordered = rest
+            .join("\n")
+            .replace("ENDSEC;\nEND-ISO", &format!("{}\nENDSEC;\nEND-ISO", placements.join("\n")));
+
+        let mut streamed = IfcImporter::from_reader(reordered.as_bytes()).unwrap();
+        let walls = streamed.extract_walls().unwrap();
+        let doors = streamed.extract_doors().unwrap();
+
+        assert_eq!(walls.len(), 1);
+        assert!((walls[0].start.x - 2.0).abs() < 1e-9);
+        assert!((walls[0].start.y - 1.0).abs() < 1e-9);
+        assert_eq!(doors.len(), 1);
+        assert_eq!(doors[0].host_wall_id, walls[0].id);
+        assert!((doors[0].offset - 1.95).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unhosted_door_is_not_imported() {
+        let content = create_hosted_door_ifc().replace(
+            "#140=IFCRELFILLSELEMENT('0000000000000000000005',#2,$,$,#110,#120);\n",
+            "",
+        );
+        let mut importer = IfcImporter::from_string(content).unwrap();
+        importer.extract_walls().unwrap();
+        assert!(importer.extract_doors().unwrap().is_empty());
+        assert_eq!(importer.statistics().doors_imported, 0);
+    }
+
+    #[test]
+    fn millimetre_file_is_scaled_to_metres() {
+        let mut importer = IfcImporter::from_string(create_millimetre_ifc()).unwrap();
+        assert!((importer.length_scale() - 0.001).abs() < 1e-12);
+
+        let walls = importer.extract_walls().unwrap();
+        assert_eq!(walls.len(), 1);
+        let wall = &walls[0];
+        assert!((wall.start.x - 1.0).abs() < 1e-9);
+        assert!((wall.start.y - 2.0).abs() < 1e-9);
+        assert!((wall.end.x - 6.0).abs() < 1e-9);
+        assert!((wall.end.y - 2.0).abs() < 1e-9);
+        assert!((wall.start.distance_to(&wall.end) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn conversion_based_length_unit() {
+        let content = create_millimetre_ifc().replace(
+            "#13=IFCSIUNIT(*,.LENGTHUNIT.,.MILLI.,.METRE.);",
+            "#13=IFCCONVERSIONBASEDUNIT(#14,.LENGTHUNIT.,'FOOT',#15);\n\
+             #14=IFCDIMENSIONALEXPONENTS(1,0,0,0,0,0,0);\n\
+             #15=IFCMEASUREWITHUNIT(IFCLENGTHMEASURE(0.3048),#16);\n\
+             #16=IFCSIUNIT(*,.LENGTHUNIT.,$,.METRE.);",
+        );
+        let importer = IfcImporter::from_string(content).unwrap();
+        assert!((importer.length_scale() - 0.3048).abs() < 1e-12);
+    }
+
+    #[test]
+    fn metre_files_are_not_scaled() {
+        let importer = IfcImporter::from_string(create_test_ifc()).unwrap();
+        assert_eq!(importer.length_scale(), 1.0);
+    }
+
+    #[test]
+    fn stream_large_file_keeps_only_needed_entities() {
+        use crate::export::IfcExporter;
+
+        let mut exporter = IfcExporter::new("Large", "Pensaer");
+        for i in 0..200 {
+            let y = i as f64 * 3.0;
+            exporter.add_wall(WallExportData {
+                id: Uuid::new_v4(),
+                name: format!("Wall {}", i),
+                start: Point2::new(0.0, y),
+                end: Point2::new(12.0, y),
+                height: 3.0,
+                thickness: 0.2,
+                base_level: 0.0,
+                wall_type: "Basic".to_string(),
+            });
+        }
+        let exported = exporter.export().unwrap();
+
+        // Pad the DATA section with the property sets and tessellated
+        // geometry that dominate real federated models
+        let data_end = exported.rfind("ENDSEC;").unwrap();
+        let mut content = exported[..data_end].to_string();
+        // plus placements of furniture and annotations we don't import
+        let junk = 100_000;
+        for i in 0..junk / 4 {
+            let id = 1_000_000 + 4 * i;
+            content.push_str(&format!(
+                "#{}=IFCPROPERTYSINGLEVALUE('FireRating',$,IFCLABEL('EI 60; tested to EN 1364-1, report #{}'),$);\n",
+                id, i
+            ));
+            content.push_str(&format!(
+                "#{}=IFCCARTESIANPOINTLIST3D(\n  ((0.,0.,0.),(1.,0.,0.),(1.,1.,0.),\n   (0.,1.,{}.)));\n",
+                id + 1,
+                i
+            ));
+            content.push_str(&format!("#{}=IFCCARTESIANPOINT(({}.,0.,0.));\n", id + 2, i));
+            content.push_str(&format!("#{}=IFCLOCALPLACEMENT($,#{});\n", id + 3, id + 2));
+        }
+        content.push_str(&exported[data_end..]);
+        assert!(content.len() > 5_000_000);
+
+        let streamed = IfcImporter::from_reader(content.as_bytes()).unwrap();
+        let full = IfcImporter::from_string(content.clone()).unwrap();
+        assert!(streamed.statistics().discarded_entities >= junk);
+        assert!(streamed.entity_count() < full.entity_count() - junk);
+        assert!(streamed.entity_count() < 20 * 200);
+        assert!(streamed.entities.keys().all(|id| *id < 1_000_000));
+
+        let mut streamed = streamed;
+        let walls = streamed.extract_walls().unwrap();
+        assert_eq!(walls.len(), 200);
+        let last = walls.iter().find(|w| w.name == "Wall 199").unwrap();
+        assert!((last.start.y - 597.0).abs() < 1e-9);
+        assert!((last.end.x - 12.0).abs() < 1e-9);
+        assert!((last.thickness - 0.2).abs() < 1e-9);
+        assert!((last.height - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn statements_may_span_lines() {
+        let content = create_test_ifc().replace(",$,", ",\n  $,");
+        let importer = IfcImporter::from_string(content).unwrap();
+        let reference = IfcImporter::from_string(create_test_ifc()).unwrap();
+        assert_eq!(importer.entity_count(), reference.entity_count());
+
+        let missing_end = "ISO-10303-21;\nDATA;\n#1=IFCWALL('a',$);\n";
+        assert!(IfcImporter::from_reader(missing_end.as_bytes()).is_err());
+        assert!(IfcImporter::from_string("ISO-10303-21;".to_string()).is_err());
+    }
+
     #[test]
     fn get_summary() {
         let importer = IfcImporter::from_string(create_test_ifc()).unwrap();
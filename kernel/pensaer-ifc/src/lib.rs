@@ -33,17 +33,24 @@
 //! // Import IFC file
 //! let importer = IfcImporter::from_file("building.ifc")?;
 //! let walls = importer.extract_walls()?;
+//! let doors = importer.extract_doors()?; // host_wall_id links into `walls`
+//!
+//! // Stream a large model, keeping only the entities the extractors read
+//! let reader = std::io::BufReader::new(std::fs::File::open("federated.ifc")?);
+//! let importer = IfcImporter::from_reader(reader)?;
 //! ```
 
 mod error;
 mod export;
+mod guid;
 mod import;
 mod mapping;
 
 pub use error::{HealingLogEntry, HealingType, IfcError, Result};
 pub use export::{
-    DoorExportData, ElementValidation, FloorExportData, IfcExporter, ProjectMetadata,
-    RoofExportData, RoomExportData, WallExportData, WindowExportData,
+    CeilingExportData, DoorExportData, ElementValidation, FloorExportData, IfcExporter, IfcValue,
+    LevelExportData, ProjectMetadata, RailingExportData, RoofExportData, RoomExportData,
+    WallExportData, WindowExportData,
 };
 pub use import::{HealingImportResult, IfcImporter, ImportStatistics};
 pub use mapping::{ElementType, IfcEntityType, TypeMapping};
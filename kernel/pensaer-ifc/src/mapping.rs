@@ -9,11 +9,13 @@ pub enum ElementType {
     Door,
     Window,
     Floor,
+    Ceiling,
     Room,
     Roof,
     Column,
     Beam,
     Stair,
+    Railing,
     Opening,
 }
 
@@ -25,11 +27,13 @@ impl ElementType {
             "door" => Some(Self::Door),
             "window" => Some(Self::Window),
             "floor" | "slab" => Some(Self::Floor),
+            "ceiling" | "soffit" => Some(Self::Ceiling),
             "room" | "space" => Some(Self::Room),
             "roof" => Some(Self::Roof),
             "column" => Some(Self::Column),
             "beam" => Some(Self::Beam),
             "stair" | "stairs" => Some(Self::Stair),
+            "railing" | "balustrade" => Some(Self::Railing),
             "opening" => Some(Self::Opening),
             _ => None,
         }
@@ -42,11 +46,13 @@ impl ElementType {
             Self::Door => "door",
             Self::Window => "window",
             Self::Floor => "floor",
+            Self::Ceiling => "ceiling",
             Self::Room => "room",
             Self::Roof => "roof",
             Self::Column => "column",
             Self::Beam => "beam",
             Self::Stair => "stair",
+            Self::Railing => "railing",
             Self::Opening => "opening",
         }
     }
@@ -60,11 +66,13 @@ pub enum IfcEntityType {
     IfcDoor,
     IfcWindow,
     IfcSlab,
+    IfcCovering,
     IfcSpace,
     IfcRoof,
     IfcColumn,
     IfcBeam,
     IfcStair,
+    IfcRailing,
     IfcOpeningElement,
     IfcBuildingStorey,
     IfcBuilding,
@@ -81,11 +89,13 @@ impl IfcEntityType {
             Self::IfcDoor => "IfcDoor",
             Self::IfcWindow => "IfcWindow",
             Self::IfcSlab => "IfcSlab",
+            Self::IfcCovering => "IfcCovering",
             Self::IfcSpace => "IfcSpace",
             Self::IfcRoof => "IfcRoof",
             Self::IfcColumn => "IfcColumn",
             Self::IfcBeam => "IfcBeam",
             Self::IfcStair => "IfcStair",
+            Self::IfcRailing => "IfcRailing",
             Self::IfcOpeningElement => "IfcOpeningElement",
             Self::IfcBuildingStorey => "IfcBuildingStorey",
             Self::IfcBuilding => "IfcBuilding",
@@ -106,11 +116,13 @@ impl TypeMapping {
             ElementType::Door => IfcEntityType::IfcDoor,
             ElementType::Window => IfcEntityType::IfcWindow,
             ElementType::Floor => IfcEntityType::IfcSlab,
+            ElementType::Ceiling => IfcEntityType::IfcCovering,
             ElementType::Room => IfcEntityType::IfcSpace,
             ElementType::Roof => IfcEntityType::IfcRoof,
             ElementType::Column => IfcEntityType::IfcColumn,
             ElementType::Beam => IfcEntityType::IfcBeam,
             ElementType::Stair => IfcEntityType::IfcStair,
+            ElementType::Railing => IfcEntityType::IfcRailing,
             ElementType::Opening => IfcEntityType::IfcOpeningElement,
         }
     }
@@ -122,11 +134,13 @@ impl TypeMapping {
             IfcEntityType::IfcDoor => Ok(ElementType::Door),
             IfcEntityType::IfcWindow => Ok(ElementType::Window),
             IfcEntityType::IfcSlab => Ok(ElementType::Floor),
+            IfcEntityType::IfcCovering => Ok(ElementType::Ceiling),
             IfcEntityType::IfcSpace => Ok(ElementType::Room),
             IfcEntityType::IfcRoof => Ok(ElementType::Roof),
             IfcEntityType::IfcColumn => Ok(ElementType::Column),
             IfcEntityType::IfcBeam => Ok(ElementType::Beam),
             IfcEntityType::IfcStair => Ok(ElementType::Stair),
+            IfcEntityType::IfcRailing => Ok(ElementType::Railing),
             IfcEntityType::IfcOpeningElement => Ok(ElementType::Opening),
             _ => Err(IfcError::MappingError(format!(
                 "No Pensaer equivalent for {}",
@@ -135,6 +149,17 @@ impl TypeMapping {
         }
     }
 
+    /// IFC predefined type written for an element, if its entity needs one
+    /// to carry the element's meaning (e.g. a ceiling is an IfcCovering of
+    /// type CEILING).
+    pub fn predefined_type(element_type: ElementType) -> Option<&'static str> {
+        match element_type {
+            ElementType::Floor => Some("FLOOR"),
+            ElementType::Ceiling => Some("CEILING"),
+            _ => None,
+        }
+    }
+
     /// Parse IFC entity type from string.
     pub fn parse_ifc_type(s: &str) -> Option<IfcEntityType> {
         match s.to_uppercase().as_str() {
@@ -143,11 +168,13 @@ impl TypeMapping {
             "IFCDOOR" => Some(IfcEntityType::IfcDoor),
             "IFCWINDOW" => Some(IfcEntityType::IfcWindow),
             "IFCSLAB" => Some(IfcEntityType::IfcSlab),
+            "IFCCOVERING" => Some(IfcEntityType::IfcCovering),
             "IFCSPACE" => Some(IfcEntityType::IfcSpace),
             "IFCROOF" => Some(IfcEntityType::IfcRoof),
             "IFCCOLUMN" => Some(IfcEntityType::IfcColumn),
             "IFCBEAM" => Some(IfcEntityType::IfcBeam),
             "IFCSTAIR" => Some(IfcEntityType::IfcStair),
+            "IFCRAILING" => Some(IfcEntityType::IfcRailing),
             "IFCOPENINGELEMENT" => Some(IfcEntityType::IfcOpeningElement),
             "IFCBUILDINGSTOREY" => Some(IfcEntityType::IfcBuildingStorey),
             "IFCBUILDING" => Some(IfcEntityType::IfcBuilding),
@@ -201,6 +228,24 @@ mod tests {
         assert!(TypeMapping::ifc_to_pensaer(IfcEntityType::IfcProject).is_err());
     }
 
+    #[test]
+    fn ceiling_maps_to_covering() {
+        assert_eq!(ElementType::from_str("soffit"), Some(ElementType::Ceiling));
+        assert_eq!(
+            TypeMapping::pensaer_to_ifc(ElementType::Ceiling),
+            IfcEntityType::IfcCovering
+        );
+        assert_eq!(
+            TypeMapping::predefined_type(ElementType::Ceiling),
+            Some("CEILING")
+        );
+        assert_eq!(
+            TypeMapping::ifc_to_pensaer(TypeMapping::parse_ifc_type("IFCCOVERING").unwrap())
+                .unwrap(),
+            ElementType::Ceiling
+        );
+    }
+
     #[test]
     fn parse_ifc_type() {
         assert_eq!(
@@ -0,0 +1,335 @@
+//! IFC round-trip fidelity.
+//!
+//! Kernel elements are exported with [`IfcExporter`], the text is imported
+//! again with [`IfcImporter`], and the rebuilt elements must match the
+//! originals within [`COINCIDENCE_TOLERANCE`]: same count, same ids, and the
+//! same positions, thicknesses, and heights.
+//!
+//! The `assert_roundtrip_*` helpers take plain kernel elements, so a new
+//! case only needs to build its model.
+
+use pensaer_geometry::{Floor, Room, Wall};
+use pensaer_ifc::{FloorExportData, IfcExporter, IfcImporter, RoomExportData, WallExportData};
+use pensaer_math::{Point2, Polygon2, COINCIDENCE_TOLERANCE};
+
+// ---------------------------------------------------------------------------
+// Conversions between kernel elements and export data
+// ---------------------------------------------------------------------------
+
+fn wall_export_data(wall: &Wall) -> WallExportData {
+    WallExportData {
+        id: wall.id,
+        name: wall
+            .metadata
+            .name
+            .clone()
+            .unwrap_or_else(|| "Wall".to_string()),
+        start: wall.baseline.start,
+        end: wall.baseline.end,
+        height: wall.height,
+        thickness: wall.thickness,
+        base_level: wall.base_offset,
+        wall_type: format!("{:?}", wall.wall_type),
+    }
+}
+
+fn wall_from_import(data: &WallExportData) -> Wall {
+    let mut wall = Wall::new(data.start, data.end, data.height, data.thickness)
+        .expect("imported wall is valid");
+    wall.id = data.id;
+    wall.base_offset = data.base_level;
+    wall
+}
+
+fn floor_export_data(floor: &Floor) -> FloorExportData {
+    FloorExportData {
+        id: floor.id,
+        name: floor
+            .metadata
+            .name
+            .clone()
+            .unwrap_or_else(|| "Floor".to_string()),
+        thickness: floor.thickness,
+        level: floor.base_elevation,
+        boundary_points: floor.boundary.vertices.clone(),
+    }
+}
+
+fn floor_from_import(data: &FloorExportData) -> Floor {
+    let boundary = Polygon2::new(data.boundary_points.clone()).expect("imported outline");
+    let mut floor = Floor::new(boundary, data.thickness).expect("imported floor is valid");
+    floor.id = data.id;
+    floor.base_elevation = data.level;
+    floor
+}
+
+fn room_export_data(room: &Room) -> RoomExportData {
+    RoomExportData {
+        id: room.id,
+        name: room.name.clone(),
+        number: room.number.clone(),
+        area: room.boundary.area(),
+        height: room.height,
+        boundary_points: room.boundary.vertices.clone(),
+    }
+}
+
+fn room_from_import(data: &RoomExportData) -> Room {
+    let boundary = Polygon2::new(data.boundary_points.clone()).expect("imported outline");
+    let mut room =
+        Room::new(&data.name, &data.number, boundary, data.height).expect("imported room is valid");
+    room.id = data.id;
+    room
+}
+
+// ---------------------------------------------------------------------------
+// Assertions
+// ---------------------------------------------------------------------------
+
+fn reimport(exporter: &IfcExporter) -> IfcImporter {
+    let content = exporter.export().expect("export succeeds");
+    IfcImporter::from_string(content).expect("exported IFC parses")
+}
+
+fn assert_close(actual: f64, expected: f64, what: &str) {
+    assert!(
+        (actual - expected).abs() <= COINCIDENCE_TOLERANCE,
+        "{}: got {}, expected {}",
+        what,
+        actual,
+        expected
+    );
+}
+
+fn assert_points_close(actual: &[Point2], expected: &[Point2], what: &str) {
+    assert_eq!(actual.len(), expected.len(), "{}: vertex count", what);
+    for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+        assert!(
+            a.distance_to(e) <= COINCIDENCE_TOLERANCE,
+            "{} vertex {}: got ({}, {}), expected ({}, {})",
+            what,
+            i,
+            a.x,
+            a.y,
+            e.x,
+            e.y
+        );
+    }
+}
+
+/// Export `walls`, import them back, and assert every wall survives.
+///
+/// Returns the imported walls, in the order of `walls`.
+pub fn assert_roundtrip_walls(walls: &[Wall]) -> Vec<Wall> {
+    let mut exporter = IfcExporter::new("Round Trip", "Pensaer");
+    for wall in walls {
+        exporter.add_wall(wall_export_data(wall));
+    }
+
+    let mut importer = reimport(&exporter);
+    let imported = importer.extract_walls().expect("walls import");
+    assert_eq!(imported.len(), walls.len(), "wall count");
+    assert_eq!(importer.statistics().walls_imported, walls.len());
+
+    walls
+        .iter()
+        .map(|wall| {
+            let data = imported
+                .iter()
+                .find(|w| w.id == wall.id)
+                .unwrap_or_else(|| panic!("wall {} missing after import", wall.id));
+            assert_eq!(data.wall_type, format!("{:?}", wall.wall_type));
+            let back = wall_from_import(data);
+
+            let what = format!("wall {}", wall.id);
+            assert_points_close(
+                &[back.baseline.start, back.baseline.end],
+                &[wall.baseline.start, wall.baseline.end],
+                &what,
+            );
+            assert_close(
+                back.thickness,
+                wall.thickness,
+                &format!("{} thickness", what),
+            );
+            assert_close(back.height, wall.height, &format!("{} height", what));
+            assert_close(
+                back.base_offset,
+                wall.base_offset,
+                &format!("{} base", what),
+            );
+            back
+        })
+        .collect()
+}
+
+/// Export `floors`, import them back, and assert every floor survives.
+pub fn assert_roundtrip_floors(floors: &[Floor]) -> Vec<Floor> {
+    let mut exporter = IfcExporter::new("Round Trip", "Pensaer");
+    for floor in floors {
+        exporter.add_floor(floor_export_data(floor));
+    }
+
+    let mut importer = reimport(&exporter);
+    let imported = importer.extract_floors().expect("floors import");
+    assert_eq!(imported.len(), floors.len(), "floor count");
+
+    floors
+        .iter()
+        .map(|floor| {
+            let data = imported
+                .iter()
+                .find(|f| f.id == floor.id)
+                .unwrap_or_else(|| panic!("floor {} missing after import", floor.id));
+            let back = floor_from_import(data);
+
+            let what = format!("floor {}", floor.id);
+            assert_points_close(&back.boundary.vertices, &floor.boundary.vertices, &what);
+            assert_close(
+                back.thickness,
+                floor.thickness,
+                &format!("{} thickness", what),
+            );
+            assert_close(
+                back.base_elevation,
+                floor.base_elevation,
+                &format!("{} elevation", what),
+            );
+            back
+        })
+        .collect()
+}
+
+/// Export `rooms`, import them back, and assert every room survives.
+pub fn assert_roundtrip_rooms(rooms: &[Room]) -> Vec<Room> {
+    let mut exporter = IfcExporter::new("Round Trip", "Pensaer");
+    for room in rooms {
+        exporter.add_room(room_export_data(room));
+    }
+
+    let mut importer = reimport(&exporter);
+    let imported = importer.extract_rooms().expect("rooms import");
+    assert_eq!(imported.len(), rooms.len(), "room count");
+
+    rooms
+        .iter()
+        .map(|room| {
+            let data = imported
+                .iter()
+                .find(|r| r.id == room.id)
+                .unwrap_or_else(|| panic!("room {} missing after import", room.id));
+            let what = format!("room {}", room.id);
+            assert_close(data.area, room.boundary.area(), &format!("{} area", what));
+            let back = room_from_import(data);
+
+            assert_eq!(back.name, room.name);
+            assert_eq!(back.number, room.number);
+            assert_points_close(&back.boundary.vertices, &room.boundary.vertices, &what);
+            assert_close(back.height, room.height, &format!("{} height", what));
+            back
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Cases
+// ---------------------------------------------------------------------------
+
+fn rectangle_walls(min: Point2, max: Point2, height: f64, thickness: f64) -> Vec<Wall> {
+    let corners = [
+        min,
+        Point2::new(max.x, min.y),
+        max,
+        Point2::new(min.x, max.y),
+    ];
+    (0..4)
+        .map(|i| Wall::new(corners[i], corners[(i + 1) % 4], height, thickness).unwrap())
+        .collect()
+}
+
+#[test]
+fn rectangular_building_walls() {
+    let walls = rectangle_walls(Point2::new(0.0, 0.0), Point2::new(10.0, 8.0), 3.0, 0.2);
+    assert_roundtrip_walls(&walls);
+}
+
+#[test]
+fn angled_and_raised_walls() {
+    let mut diagonal =
+        Wall::new(Point2::new(-3.5, 2.25), Point2::new(4.0, 9.75), 2.7, 0.115).unwrap();
+    diagonal.metadata.name = Some("Diagonal".to_string());
+
+    let mut upper = Wall::new(Point2::new(12.0, 6.0), Point2::new(12.0, -1.5), 3.2, 0.3).unwrap();
+    upper.base_offset = 3.0;
+
+    let backwards = Wall::new(Point2::new(5.0, 0.0), Point2::new(-5.0, 0.0), 4.0, 0.25).unwrap();
+
+    assert_roundtrip_walls(&[diagonal, upper, backwards]);
+}
+
+#[test]
+fn floor_outlines_and_elevations() {
+    let l_shape = Polygon2::new(vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(8.0, 0.0),
+        Point2::new(8.0, 3.0),
+        Point2::new(3.0, 3.0),
+        Point2::new(3.0, 7.5),
+        Point2::new(0.0, 7.5),
+    ])
+    .unwrap();
+    let ground = Floor::new(l_shape, 0.25).unwrap();
+
+    let mut upper = Floor::rectangle(Point2::new(-2.0, -2.0), Point2::new(6.0, 4.0), 0.3).unwrap();
+    upper.base_elevation = 3.0;
+
+    assert_roundtrip_floors(&[ground, upper]);
+}
+
+#[test]
+fn room_outlines_names_and_heights() {
+    let office = Room::rectangle(
+        "Office",
+        "101",
+        Point2::new(0.0, 0.0),
+        Point2::new(4.5, 3.5),
+        2.7,
+    )
+    .unwrap();
+    let triangle = Polygon2::new(vec![
+        Point2::new(5.0, 0.0),
+        Point2::new(9.0, 0.0),
+        Point2::new(5.0, 6.0),
+    ])
+    .unwrap();
+    let store = Room::new("Store", "102", triangle, 3.1).unwrap();
+
+    assert_roundtrip_rooms(&[office, store]);
+}
+
+#[test]
+fn mixed_model_counts() {
+    let walls = rectangle_walls(Point2::new(0.0, 0.0), Point2::new(6.0, 5.0), 3.0, 0.2);
+    let floor = Floor::rectangle(Point2::new(0.0, 0.0), Point2::new(6.0, 5.0), 0.3).unwrap();
+    let room = Room::rectangle(
+        "Hall",
+        "001",
+        Point2::new(0.0, 0.0),
+        Point2::new(6.0, 5.0),
+        3.0,
+    )
+    .unwrap();
+
+    let mut exporter = IfcExporter::new("Round Trip", "Pensaer");
+    for wall in &walls {
+        exporter.add_wall(wall_export_data(wall));
+    }
+    exporter.add_floor(floor_export_data(&floor));
+    exporter.add_room(room_export_data(&room));
+
+    let mut importer = reimport(&exporter);
+    assert_eq!(importer.extract_walls().unwrap().len(), 4);
+    assert_eq!(importer.extract_floors().unwrap().len(), 1);
+    assert_eq!(importer.extract_rooms().unwrap().len(), 1);
+    assert_eq!(importer.statistics().total_imported(), 6);
+}
@@ -4,6 +4,7 @@
 //! - [`Point2`] and [`Point3`] - 2D and 3D point types
 //! - [`Vector2`] and [`Vector3`] - 2D and 3D vector types with full operations
 //! - [`Transform3`] - 4x4 transformation matrix
+//! - [`Quaternion`] - Unit quaternion rotations
 //! - [`BoundingBox2`] and [`BoundingBox3`] - Axis-aligned bounding boxes
 //! - [`Line2`], [`Line3`], [`LineSegment2`], [`LineSegment3`] - Line types
 //! - [`Polygon2`] - 2D polygon for floor/room boundaries
@@ -37,6 +38,7 @@ pub mod guards;
 pub mod line;
 pub mod point;
 pub mod polygon;
+pub mod quaternion;
 pub mod robust_predicates;
 pub mod transform;
 pub mod vector;
@@ -46,7 +48,8 @@ pub use bbox::{BoundingBox2, BoundingBox3};
 pub use error::{MathError, MathResult};
 pub use line::{Line2, Line3, LineSegment2, LineSegment3};
 pub use point::{Point2, Point3};
-pub use polygon::Polygon2;
+pub use polygon::{OffsetJoin, Polygon2};
+pub use quaternion::Quaternion;
 pub use robust_predicates::{
     incircle_2d, insphere_3d, is_convex_vertex, is_reflex_vertex, orientation_2d, orientation_3d,
     point_in_triangle, segments_intersect, segments_properly_intersect, CirclePosition,
@@ -173,6 +173,19 @@ impl Line2 {
     pub fn is_parallel_to(&self, other: &Line2, tolerance: f64) -> bool {
         self.direction.cross(&other.direction).abs() < tolerance
     }
+
+    /// Mirror a point across this line.
+    #[inline]
+    pub fn reflect_point(&self, p: &Point2) -> Point2 {
+        let foot = self.closest_point(p);
+        foot + (foot - *p)
+    }
+
+    /// Mirror a direction across this line.
+    #[inline]
+    pub fn reflect_vector(&self, v: &Vector2) -> Vector2 {
+        self.direction * (2.0 * v.dot(&self.direction)) - *v
+    }
 }
 
 /// A 2D line segment with start and end points.
@@ -526,6 +539,20 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn line2_reflect() {
+        // Diagonal y = x + 1
+        let line = Line2::from_points(Point2::new(0.0, 1.0), Point2::new(1.0, 2.0)).unwrap();
+
+        let p = line.reflect_point(&Point2::new(2.0, 0.0));
+        assert!((p.x - (-1.0)).abs() < EPSILON);
+        assert!((p.y - 3.0).abs() < EPSILON);
+
+        let v = line.reflect_vector(&Vector2::new(1.0, 0.0));
+        assert!(v.x.abs() < EPSILON);
+        assert!((v.y - 1.0).abs() < EPSILON);
+    }
+
     #[test]
     fn line2_intersection() {
         // Horizontal line y = 0
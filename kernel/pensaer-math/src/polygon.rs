@@ -9,10 +9,13 @@ use serde::{Deserialize, Serialize};
 
 use crate::bbox::BoundingBox2;
 use crate::error::{MathError, MathResult};
-use crate::line::LineSegment2;
+use crate::line::{Line2, LineSegment2};
 use crate::point::Point2;
-use crate::robust_predicates::{orientation_2d, segments_properly_intersect, Orientation};
+use crate::robust_predicates::{
+    orientation_2d, point_in_triangle, segments_intersect, segments_properly_intersect, Orientation,
+};
 use crate::vector::Vector2;
+use crate::{COINCIDENCE_TOLERANCE, EPSILON};
 
 /// A 2D polygon defined by an ordered list of vertices.
 ///
@@ -23,6 +26,24 @@ pub struct Polygon2 {
     pub vertices: Vec<Point2>,
 }
 
+/// How [`Polygon2::offset`] closes the gap that opens at a corner.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OffsetJoin {
+    /// Extend both edges until they meet. Corners whose mitred point would
+    /// lie more than `limit` times the offset distance from the original
+    /// vertex are bevelled instead.
+    Miter { limit: f64 },
+    /// Cut the corner with a straight edge between the two moved edges.
+    Bevel,
+}
+
+impl Default for OffsetJoin {
+    /// Mitre, bevelling corners sharper than about 29°.
+    fn default() -> Self {
+        OffsetJoin::Miter { limit: 4.0 }
+    }
+}
+
 impl Polygon2 {
     /// Create a new polygon from vertices.
     /// Requires at least 3 vertices.
@@ -215,37 +236,21 @@ impl Polygon2 {
 
     /// Check if a point is inside the polygon using winding number algorithm.
     ///
-    /// Uses robust predicates for self-correcting orientation tests.
-    /// The winding number method is more robust than ray casting for
-    /// points near edges.
+    /// Points within [`COINCIDENCE_TOLERANCE`] of an edge count as inside,
+    /// so a point exactly on the boundary gives the same answer on every
+    /// edge. Elsewhere, robust orientation tests decide each crossing of a
+    /// ray from the point; the winding number method is more robust than
+    /// ray casting for concave and self-overlapping outlines.
     pub fn contains_point(&self, p: &Point2) -> bool {
         let n = self.vertices.len();
         if n < 3 {
             return false;
         }
-
-        let mut winding = 0i32;
-
-        for i in 0..n {
-            let vi = self.vertices[i];
-            let vj = self.vertices[(i + 1) % n];
-
-            if vi.y <= p.y {
-                if vj.y > p.y {
-                    // Upward crossing - use robust orientation test
-                    if orientation_2d(vi, vj, *p) == Orientation::CounterClockwise {
-                        winding += 1;
-                    }
-                }
-            } else if vj.y <= p.y {
-                // Downward crossing - use robust orientation test
-                if orientation_2d(vi, vj, *p) == Orientation::Clockwise {
-                    winding -= 1;
-                }
-            }
+        if self.point_on_boundary(p, COINCIDENCE_TOLERANCE) {
+            return true;
         }
 
-        winding != 0
+        winding_number(&self.vertices, p) != 0
     }
 
     /// Check if a point is on the boundary of the polygon.
@@ -268,6 +273,141 @@ impl Polygon2 {
         BoundingBox2::from_points(&self.vertices)
     }
 
+    /// Convex hull of a point set, counter-clockwise.
+    ///
+    /// Uses Andrew's monotone chain with robust orientation tests; collinear
+    /// points on the hull boundary are dropped.
+    ///
+    /// # Errors
+    ///
+    /// [`MathError::InsufficientVertices`] when the points are all collinear
+    /// or fewer than 3 are distinct.
+    pub fn from_convex_hull(points: &[Point2]) -> MathResult<Self> {
+        Self::new(hull_points(points))
+    }
+
+    /// Convex hull of the polygon, counter-clockwise.
+    pub fn convex_hull(&self) -> MathResult<Self> {
+        Self::from_convex_hull(&self.vertices)
+    }
+
+    /// Minimum-area oriented bounding box.
+    ///
+    /// Returns the center, the unit direction of the box's longer side, and
+    /// the half-extents along and across that direction. One side of the
+    /// minimum box always lies on a convex hull edge, so rotating calipers
+    /// walk the hull edges, tracking the extreme points for each in a single
+    /// pass. A polygon whose vertices are all collinear gives a box of zero
+    /// width along its points.
+    pub fn min_area_obb(&self) -> (Point2, Vector2, f64, f64) {
+        let hull = hull_points(&self.vertices);
+        let n = hull.len();
+        match n {
+            0 => return (Point2::new(0.0, 0.0), Vector2::UNIT_X, 0.0, 0.0),
+            1 => return (hull[0], Vector2::UNIT_X, 0.0, 0.0),
+            _ => {}
+        }
+
+        // Along each edge's direction u (with v its inward normal), the box
+        // spans from the leftmost to the rightmost point along u and from the
+        // edge itself to the farthest point along v
+        let along = |i: usize, k: usize, dir: Vector2| (hull[k % n] - hull[i]).dot(&dir);
+        let mut right = 0;
+        let mut top = 0;
+        let mut left = 0;
+        let mut best: Option<(f64, Point2, Vector2, f64, f64)> = None;
+
+        for i in 0..n {
+            let Some(u) = (hull[(i + 1) % n] - hull[i]).try_normalize() else {
+                continue;
+            };
+            let v = u.perp();
+            if best.is_none() {
+                // Start the calipers from a full scan
+                right = (0..n)
+                    .max_by(|&a, &b| along(i, a, u).total_cmp(&along(i, b, u)))
+                    .unwrap();
+                top = (0..n)
+                    .max_by(|&a, &b| along(i, a, v).total_cmp(&along(i, b, v)))
+                    .unwrap();
+                left = (0..n)
+                    .min_by(|&a, &b| along(i, a, u).total_cmp(&along(i, b, u)))
+                    .unwrap();
+            }
+            for _ in 0..n {
+                if along(i, right + 1, u) <= along(i, right, u) {
+                    break;
+                }
+                right = (right + 1) % n;
+            }
+            for _ in 0..n {
+                if along(i, top + 1, v) <= along(i, top, v) {
+                    break;
+                }
+                top = (top + 1) % n;
+            }
+            for _ in 0..n {
+                if along(i, left + 1, u) >= along(i, left, u) {
+                    break;
+                }
+                left = (left + 1) % n;
+            }
+
+            let (min_u, max_u, max_v) = (along(i, left, u), along(i, right, u), along(i, top, v));
+            let area = (max_u - min_u) * max_v;
+            if best.as_ref().is_some_and(|b| b.0 <= area) {
+                continue;
+            }
+            let center = hull[i] + u * ((min_u + max_u) / 2.0) + v * (max_v / 2.0);
+            let (half_u, half_v) = ((max_u - min_u) / 2.0, max_v / 2.0);
+            best = Some(if half_u >= half_v {
+                (area, center, u, half_u, half_v)
+            } else {
+                (area, center, v, half_v, half_u)
+            });
+        }
+
+        match best {
+            Some((_, center, axis, half_length, half_width)) => {
+                (center, axis, half_length, half_width)
+            }
+            None => (hull[0], Vector2::UNIT_X, 0.0, 0.0),
+        }
+    }
+
+    /// Split the polygon into convex pieces.
+    ///
+    /// Hertel–Mehlhorn: the polygon is ear-clipped into triangles, then each
+    /// diagonal is dropped wherever the two pieces either side of it still
+    /// form a convex piece once merged. The pieces are counter-clockwise,
+    /// convex, and tile the polygon exactly, and there are at most four
+    /// times as many as the fewest possible. Repeated and collinear
+    /// vertices are dropped, so a convex polygon comes back as one piece.
+    /// A degenerate or self-intersecting polygon gives no pieces.
+    pub fn decompose_convex(&self) -> Vec<Polygon2> {
+        let vertices = drop_collinear(normalized_ccw(&self.vertices));
+        if vertices.len() < 3 {
+            return Vec::new();
+        }
+        let poly = Polygon2 { vertices };
+        if !poly.is_simple() {
+            return Vec::new();
+        }
+        if poly.is_convex() {
+            return vec![poly];
+        }
+        let Some(triangles) = ear_clip(&poly.vertices) else {
+            return Vec::new();
+        };
+
+        merge_convex(&poly.vertices, triangles)
+            .into_iter()
+            .map(|piece| Polygon2 {
+                vertices: drop_collinear(piece.iter().map(|&i| poly.vertices[i]).collect()),
+            })
+            .collect()
+    }
+
     /// Check if this polygon intersects with another (edges cross).
     pub fn intersects(&self, other: &Polygon2) -> bool {
         // Check if any edges intersect
@@ -290,6 +430,71 @@ impl Polygon2 {
         false
     }
 
+    /// Region covered by both polygons.
+    ///
+    /// See [`difference`](Self::difference) for how results are wound.
+    pub fn intersection(&self, other: &Polygon2) -> Vec<Polygon2> {
+        self.boolean(other, BooleanOp::Intersection)
+    }
+
+    /// Region covered by either polygon.
+    ///
+    /// Disjoint inputs come back as two polygons. A hole enclosed between
+    /// the inputs is returned as its own clockwise loop.
+    pub fn union(&self, other: &Polygon2) -> Vec<Polygon2> {
+        self.boolean(other, BooleanOp::Union)
+    }
+
+    /// Region covered by this polygon but not by `other`.
+    ///
+    /// Outer boundaries are counter-clockwise and holes clockwise, so the
+    /// signed areas of the result sum to its area. Cutting `other` out of
+    /// the middle of this polygon gives this polygon plus `other` reversed
+    /// as a hole.
+    pub fn difference(&self, other: &Polygon2) -> Vec<Polygon2> {
+        self.boolean(other, BooleanOp::Difference)
+    }
+
+    /// Shared implementation of the boolean operations.
+    ///
+    /// Both boundaries are split wherever they meet, each piece is
+    /// classified against the other polygon by its midpoint, and the pieces
+    /// the operation keeps are linked back into loops. Coincident edges are
+    /// classified by direction, so shared edges never produce slivers.
+    fn boolean(&self, other: &Polygon2, op: BooleanOp) -> Vec<Polygon2> {
+        let a = normalized_ccw(&self.vertices);
+        let b = normalized_ccw(&other.vertices);
+        let (a_pieces, b_pieces) = split_boundaries(&a, &b);
+
+        let mut fragments = Vec::new();
+        for (start, end) in a_pieces {
+            let side = classify_piece(start, end, &b);
+            let keep = match op {
+                BooleanOp::Intersection => {
+                    matches!(side, PieceSide::Inside | PieceSide::SharedSame)
+                }
+                BooleanOp::Union => matches!(side, PieceSide::Outside | PieceSide::SharedSame),
+                BooleanOp::Difference => {
+                    matches!(side, PieceSide::Outside | PieceSide::SharedOpposite)
+                }
+            };
+            if keep {
+                fragments.push((start, end));
+            }
+        }
+        // Shared edges were already taken from this polygon
+        for (start, end) in b_pieces {
+            match (op, classify_piece(start, end, &a)) {
+                (BooleanOp::Intersection, PieceSide::Inside) => fragments.push((start, end)),
+                (BooleanOp::Union, PieceSide::Outside) => fragments.push((start, end)),
+                (BooleanOp::Difference, PieceSide::Inside) => fragments.push((end, start)),
+                _ => {}
+            }
+        }
+
+        link_fragments(&fragments)
+    }
+
     /// Check if polygon has any self-intersecting edges.
     ///
     /// Uses robust predicates for self-correcting segment intersection tests.
@@ -324,6 +529,34 @@ impl Polygon2 {
         true
     }
 
+    /// Check if any two non-adjacent edges touch or cross.
+    ///
+    /// Stricter than [`is_simple`](Self::is_simple), which ignores edges
+    /// that only touch: a vertex lying on another edge counts here too.
+    pub fn is_self_intersecting(&self) -> bool {
+        let n = self.vertices.len();
+        if n < 4 {
+            return false;
+        }
+
+        for i in 0..n {
+            let a1 = self.vertices[i];
+            let a2 = self.vertices[(i + 1) % n];
+            for j in (i + 2)..n {
+                if i == 0 && j == n - 1 {
+                    continue;
+                }
+                let b1 = self.vertices[j];
+                let b2 = self.vertices[(j + 1) % n];
+                if segments_intersect(a1, a2, b1, b2) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
     /// Validate polygon for use in geometry operations.
     pub fn validate(&self) -> MathResult<()> {
         if self.vertices.len() < 3 {
@@ -336,55 +569,86 @@ impl Polygon2 {
     }
 
     /// Offset the polygon by a distance (positive = expand, negative = shrink).
-    /// Uses simple parallel offset (may produce self-intersections for concave polygons).
-    pub fn offset(&self, distance: f64) -> MathResult<Self> {
-        let n = self.vertices.len();
+    ///
+    /// Each edge moves `distance` along its outward normal and `join`
+    /// decides how the gap opening at each corner is closed. Zero-length
+    /// edges are skipped, and a vertex between collinear edges moves
+    /// straight out.
+    ///
+    /// When the moved edges still form a simple outline it is returned as
+    /// the only polygon, vertex for vertex. Otherwise (an inset past the
+    /// medial axis, or an outset folding over a narrow notch) the outline
+    /// is cut at its self-intersections and only the parts wound positively
+    /// are kept, so a collapsed polygon gives an empty list and a pinched
+    /// one gives several. Results keep the input's winding; holes closed
+    /// off by an outset wind the other way.
+    ///
+    /// # Errors
+    ///
+    /// [`MathError::InsufficientVertices`] when fewer than 3 distinct
+    /// vertices remain.
+    pub fn offset(&self, distance: f64, join: OffsetJoin) -> MathResult<Vec<Polygon2>> {
+        let mut vertices: Vec<Point2> = Vec::with_capacity(self.vertices.len());
+        for &v in &self.vertices {
+            if !vertices
+                .last()
+                .is_some_and(|last| last.distance_to(&v) <= EPSILON)
+            {
+                vertices.push(v);
+            }
+        }
+        if vertices.len() > 1 && vertices[0].distance_to(&vertices[vertices.len() - 1]) <= EPSILON {
+            vertices.pop();
+        }
+        let n = vertices.len();
         if n < 3 {
             return Err(MathError::InsufficientVertices);
         }
 
-        let mut new_vertices = Vec::with_capacity(n);
+        // Work counter-clockwise, where outward is to the right of each edge
+        let clockwise = self.is_clockwise();
+        if clockwise {
+            vertices.reverse();
+        }
+        let directions: Vec<Vector2> = (0..n)
+            .map(|i| (vertices[(i + 1) % n] - vertices[i]).normalize())
+            .collect::<MathResult<_>>()?;
 
-        // Determine winding direction for correct normal direction
-        let sign = if self.is_counter_clockwise() {
-            1.0
-        } else {
-            -1.0
+        let restore_winding = |mut polygons: Vec<Polygon2>| {
+            if clockwise {
+                polygons.iter_mut().for_each(Polygon2::reverse);
+            }
+            polygons
         };
 
-        for i in 0..n {
-            let prev = (i + n - 1) % n;
-            let next = (i + 1) % n;
-
-            // Edge vectors
-            let e1 = self.vertices[i] - self.vertices[prev];
-            let e2 = self.vertices[next] - self.vertices[i];
-
-            // Outward normals (perpendicular)
-            let n1 = Vector2::new(-e1.y, e1.x);
-            let n2 = Vector2::new(-e2.y, e2.x);
-
-            // Normalize
-            let n1 = n1.try_normalize().unwrap_or(Vector2::UNIT_X);
-            let n2 = n2.try_normalize().unwrap_or(Vector2::UNIT_X);
-
-            // Average normal (bisector direction)
-            let avg = n1 + n2;
-            let avg_normalized = avg.try_normalize().unwrap_or(n1);
+        let (outline, spans) = offset_outline(&vertices, &directions, distance, join, false);
+        let edges_kept = (0..n).all(|i| {
+            let edge = outline[spans[(i + 1) % n].0] - outline[spans[i].1];
+            edge.dot(&directions[i]) >= -EPSILON
+        });
+        let outline = Polygon2 { vertices: outline };
+        if edges_kept && outline.signed_area() > EPSILON && !outline.is_self_intersecting() {
+            return Ok(restore_winding(vec![outline]));
+        }
 
-            // Calculate offset distance at corner (accounts for angle)
-            let dot = n1.dot(&avg_normalized);
-            let corner_distance = if dot.abs() > 1e-10 {
-                distance / dot
-            } else {
-                distance
+        // Keep the boundary of the positively wound region
+        let (raw, _) = offset_outline(&vertices, &directions, distance, join, true);
+        let mut fragments = Vec::new();
+        for (start, end) in split_self(&raw) {
+            let Some(left) = (end - start).try_normalize().map(|d| d.perp()) else {
+                continue;
             };
-
-            let offset_vec = avg_normalized * (corner_distance * sign);
-            new_vertices.push(self.vertices[i] + offset_vec);
+            let probe = (start.distance_to(&end) / 4.0).min(COINCIDENCE_TOLERANCE / 2.0);
+            let mid = start.lerp(&end, 0.5);
+            let inside_left = winding_number(&raw, &(mid + left * probe)) > 0;
+            let inside_right = winding_number(&raw, &(mid - left * probe)) > 0;
+            match (inside_left, inside_right) {
+                (true, false) => fragments.push((start, end)),
+                (false, true) => fragments.push((end, start)),
+                _ => {}
+            }
         }
-
-        Polygon2::new(new_vertices)
+        Ok(restore_winding(link_fragments(&fragments)))
     }
 
     /// Simplify polygon by removing collinear points.
@@ -470,6 +734,476 @@ impl Polygon2 {
             center + rotated
         })
     }
+
+    /// Mirror polygon across a line.
+    ///
+    /// The vertex order is reversed so the winding is preserved.
+    pub fn mirror(&self, axis: &Line2) -> Self {
+        let mut mirrored = self.map_vertices(|p| axis.reflect_point(p));
+        mirrored.reverse();
+        mirrored
+    }
+}
+
+/// Counter-clockwise convex hull vertices, without collinear points.
+///
+/// Fewer than 3 points come back when the input is degenerate.
+fn hull_points(points: &[Point2]) -> Vec<Point2> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // Lower hull left to right, then upper hull right to left
+    let mut hull: Vec<Point2> = Vec::with_capacity(sorted.len() + 1);
+    for pass in [
+        &sorted[..],
+        &sorted.iter().rev().copied().collect::<Vec<_>>()[..],
+    ] {
+        let start = hull.len();
+        for &p in pass {
+            while hull.len() >= start + 2
+                && orientation_2d(hull[hull.len() - 2], hull[hull.len() - 1], p)
+                    != Orientation::CounterClockwise
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        // The last point starts the other chain
+        hull.pop();
+    }
+    hull
+}
+
+/// Winding number of a closed loop around a point.
+///
+/// Robust orientation tests decide each crossing of a ray from the point.
+fn winding_number(vertices: &[Point2], p: &Point2) -> i32 {
+    let n = vertices.len();
+    let mut winding = 0i32;
+
+    for i in 0..n {
+        let vi = vertices[i];
+        let vj = vertices[(i + 1) % n];
+
+        if vi.y <= p.y {
+            if vj.y > p.y {
+                // Upward crossing - use robust orientation test
+                if orientation_2d(vi, vj, *p) == Orientation::CounterClockwise {
+                    winding += 1;
+                }
+            }
+        } else if vj.y <= p.y {
+            // Downward crossing - use robust orientation test
+            if orientation_2d(vi, vj, *p) == Orientation::Clockwise {
+                winding -= 1;
+            }
+        }
+    }
+
+    winding
+}
+
+/// Outline of a counter-clockwise loop with every edge moved `distance`
+/// to its right.
+///
+/// Also returns, per original vertex, the indices of the first and last
+/// outline points it produced. Where the moved edges overlap instead of
+/// leaving a gap, they are normally cut at their intersection; with
+/// `loop_back` the outline runs back through the original vertex instead,
+/// which never jumps far away but leaves small reversed loops for
+/// [`Polygon2::offset`] to discard.
+fn offset_outline(
+    vertices: &[Point2],
+    directions: &[Vector2],
+    distance: f64,
+    join: OffsetJoin,
+    loop_back: bool,
+) -> (Vec<Point2>, Vec<(usize, usize)>) {
+    let n = vertices.len();
+    let mut outline = Vec::with_capacity(n);
+    let mut spans = Vec::with_capacity(n);
+
+    for i in 0..n {
+        let prev = (i + n - 1) % n;
+        let (d1, d2) = (directions[prev], directions[i]);
+        let v = vertices[i];
+        let p1 = v + Vector2::new(d1.y, -d1.x) * distance;
+        let p2 = v + Vector2::new(d2.y, -d2.x) * distance;
+        let cross = d1.cross(&d2);
+        let first = outline.len();
+
+        // Moving out past a left turn, or in past a right turn, opens a gap
+        let opens_gap = cross * distance > 0.0;
+        let mitre = || {
+            let t = (p2 - p1).cross(&d2) / cross;
+            p1 + d1 * t
+        };
+        if cross.abs() < EPSILON {
+            if d1.dot(&d2) > 0.0 {
+                // Collinear edges share one offset line
+                outline.push(p2);
+            } else {
+                // The outline doubles back on itself
+                outline.extend([p1, p2]);
+            }
+        } else if opens_gap {
+            match join {
+                OffsetJoin::Miter { limit }
+                    if mitre().distance_to(&v) <= limit * distance.abs() =>
+                {
+                    outline.push(mitre())
+                }
+                _ => outline.extend([p1, p2]),
+            }
+        } else if loop_back {
+            outline.extend([p1, v, p2]);
+        } else {
+            outline.push(mitre());
+        }
+        spans.push((first, outline.len() - 1));
+    }
+
+    (outline, spans)
+}
+
+/// Which boolean operation [`Polygon2::boolean`] performs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BooleanOp {
+    Intersection,
+    Union,
+    Difference,
+}
+
+/// Where a boundary piece of one polygon lies relative to the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PieceSide {
+    Inside,
+    Outside,
+    /// On the other boundary, running the same way.
+    SharedSame,
+    /// On the other boundary, running the opposite way.
+    SharedOpposite,
+}
+
+/// Vertices without repeats, wound counter-clockwise.
+///
+/// Fewer than 3 vertices stand for an empty region.
+fn normalized_ccw(vertices: &[Point2]) -> Vec<Point2> {
+    let mut out: Vec<Point2> = Vec::with_capacity(vertices.len());
+    for &v in vertices {
+        if !out
+            .last()
+            .is_some_and(|last| last.distance_to(&v) < COINCIDENCE_TOLERANCE)
+        {
+            out.push(v);
+        }
+    }
+    while out.len() > 1 && out[0].distance_to(&out[out.len() - 1]) < COINCIDENCE_TOLERANCE {
+        out.pop();
+    }
+    if out.len() < 3 {
+        return Vec::new();
+    }
+    let mut poly = Polygon2 { vertices: out };
+    poly.ensure_ccw();
+    poly.vertices
+}
+
+/// Split both boundaries at every point where they touch or cross.
+///
+/// A vertex of one polygon on an edge of the other splits that edge at the
+/// vertex itself, and each crossing point is computed once and shared, so
+/// the pieces of both polygons meet at identical points.
+#[allow(clippy::type_complexity)]
+fn split_boundaries(a: &[Point2], b: &[Point2]) -> (Vec<(Point2, Point2)>, Vec<(Point2, Point2)>) {
+    let mut a_splits: Vec<Vec<Point2>> = vec![Vec::new(); a.len()];
+    let mut b_splits: Vec<Vec<Point2>> = vec![Vec::new(); b.len()];
+
+    for i in 0..a.len() {
+        let edge_a = LineSegment2::new(a[i], a[(i + 1) % a.len()]);
+        for j in 0..b.len() {
+            let edge_b = LineSegment2::new(b[j], b[(j + 1) % b.len()]);
+            split_pair(&edge_a, &edge_b, &mut a_splits[i], &mut b_splits[j]);
+        }
+    }
+
+    (split_edges(a, a_splits), split_edges(b, b_splits))
+}
+
+/// Split points one loop cuts into the edges of itself.
+fn split_self(vertices: &[Point2]) -> Vec<(Point2, Point2)> {
+    let n = vertices.len();
+    let mut splits: Vec<Vec<Point2>> = vec![Vec::new(); n];
+    for j in 1..n {
+        let edge_b = LineSegment2::new(vertices[j], vertices[(j + 1) % n]);
+        let (before, from_j) = splits.split_at_mut(j);
+        for (i, a_splits) in before.iter_mut().enumerate() {
+            let edge_a = LineSegment2::new(vertices[i], vertices[i + 1]);
+            split_pair(&edge_a, &edge_b, a_splits, &mut from_j[0]);
+        }
+    }
+    split_edges(vertices, splits)
+}
+
+/// Record where two edges touch or cross as split points on each.
+///
+/// A vertex of one edge lying on the other splits it at the vertex itself;
+/// otherwise a proper crossing is computed once and shared by both.
+fn split_pair(
+    edge_a: &LineSegment2,
+    edge_b: &LineSegment2,
+    a_splits: &mut Vec<Point2>,
+    b_splits: &mut Vec<Point2>,
+) {
+    let mut touched = false;
+    for p in [edge_b.start, edge_b.end] {
+        if edge_a.distance_to_point(&p) < COINCIDENCE_TOLERANCE {
+            a_splits.push(p);
+            touched = true;
+        }
+    }
+    for p in [edge_a.start, edge_a.end] {
+        if edge_b.distance_to_point(&p) < COINCIDENCE_TOLERANCE {
+            b_splits.push(p);
+            touched = true;
+        }
+    }
+    if !touched && segments_properly_intersect(edge_a.start, edge_a.end, edge_b.start, edge_b.end) {
+        if let Some(p) = edge_a.intersect(edge_b) {
+            a_splits.push(p);
+            b_splits.push(p);
+        }
+    }
+}
+
+/// Cut each edge of a loop at its split points, in order along the edge.
+fn split_edges(vertices: &[Point2], splits: Vec<Vec<Point2>>) -> Vec<(Point2, Point2)> {
+    let n = vertices.len();
+    let mut pieces = Vec::new();
+    for (i, mut points) in splits.into_iter().enumerate() {
+        let edge = LineSegment2::new(vertices[i], vertices[(i + 1) % n]);
+        points.retain(|p| {
+            p.distance_to(&edge.start) >= COINCIDENCE_TOLERANCE
+                && p.distance_to(&edge.end) >= COINCIDENCE_TOLERANCE
+        });
+        points.sort_by(|p, q| edge.project_point(p).total_cmp(&edge.project_point(q)));
+        points.dedup_by(|p, q| p.distance_to(q) < COINCIDENCE_TOLERANCE);
+
+        let mut from = edge.start;
+        for p in points.into_iter().chain([edge.end]) {
+            pieces.push((from, p));
+            from = p;
+        }
+    }
+    pieces
+}
+
+/// Classify a boundary piece against a counter-clockwise polygon.
+fn classify_piece(start: Point2, end: Point2, other: &[Point2]) -> PieceSide {
+    if other.is_empty() {
+        return PieceSide::Outside;
+    }
+    let mid = start.lerp(&end, 0.5);
+    let n = other.len();
+    for i in 0..n {
+        let edge = LineSegment2::new(other[i], other[(i + 1) % n]);
+        if edge.distance_to_point(&mid) < COINCIDENCE_TOLERANCE {
+            return if (end - start).dot(&edge.direction()) > 0.0 {
+                PieceSide::SharedSame
+            } else {
+                PieceSide::SharedOpposite
+            };
+        }
+    }
+    let polygon = Polygon2 {
+        vertices: other.to_vec(),
+    };
+    if polygon.contains_point(&mid) {
+        PieceSide::Inside
+    } else {
+        PieceSide::Outside
+    }
+}
+
+/// Link directed boundary pieces end to start into closed loops.
+///
+/// Where several pieces leave the same point (polygons touching at a
+/// vertex), the sharpest left turn is taken so touching regions come back
+/// as separate loops. Collinear and repeated vertices are dropped, as are
+/// loops that cannot be closed or enclose no area.
+fn link_fragments(fragments: &[(Point2, Point2)]) -> Vec<Polygon2> {
+    let mut used = vec![false; fragments.len()];
+    let mut loops = Vec::new();
+
+    for first in 0..fragments.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let (origin, mut at) = fragments[first];
+        let mut from = origin;
+        let mut vertices = vec![origin];
+        let closed = loop {
+            if at.distance_to(&origin) < COINCIDENCE_TOLERANCE {
+                break true;
+            }
+            vertices.push(at);
+            let incoming = at - from;
+            let turn = |k: &usize| {
+                let outgoing = fragments[*k].1 - fragments[*k].0;
+                let angle = incoming.cross(&outgoing).atan2(incoming.dot(&outgoing));
+                // Doubling straight back is the last resort
+                if angle >= std::f64::consts::PI - EPSILON {
+                    -std::f64::consts::PI
+                } else {
+                    angle
+                }
+            };
+            let next = (0..fragments.len())
+                .filter(|&k| !used[k] && fragments[k].0.distance_to(&at) < COINCIDENCE_TOLERANCE)
+                .max_by(|k, l| turn(k).total_cmp(&turn(l)));
+            let Some(k) = next else {
+                break false;
+            };
+            used[k] = true;
+            from = fragments[k].0;
+            at = fragments[k].1;
+        };
+        if !closed {
+            continue;
+        }
+
+        let vertices = drop_collinear(vertices);
+        if vertices.len() < 3 {
+            continue;
+        }
+        let polygon = Polygon2 { vertices };
+        if polygon.area() > COINCIDENCE_TOLERANCE * COINCIDENCE_TOLERANCE {
+            loops.push(polygon);
+        }
+    }
+    loops
+}
+
+/// Remove repeated vertices and vertices lying on the line between their
+/// neighbours, until none are left.
+fn drop_collinear(mut vertices: Vec<Point2>) -> Vec<Point2> {
+    let mut changed = true;
+    while changed && vertices.len() >= 3 {
+        changed = false;
+        let n = vertices.len();
+        for i in 0..n {
+            let prev = vertices[(i + n - 1) % n];
+            let next = vertices[(i + 1) % n];
+            if LineSegment2::new(prev, next).distance_to_point(&vertices[i]) < COINCIDENCE_TOLERANCE
+            {
+                vertices.remove(i);
+                changed = true;
+                break;
+            }
+        }
+    }
+    vertices
+}
+
+/// Triangulate a simple counter-clockwise polygon by ear clipping.
+///
+/// Returns `None` if no ear can be found, which only happens for polygons
+/// that are not simple.
+fn ear_clip(vertices: &[Point2]) -> Option<Vec<[usize; 3]>> {
+    let mut ring: Vec<usize> = (0..vertices.len()).collect();
+    let mut triangles = Vec::with_capacity(vertices.len() - 2);
+
+    while ring.len() > 3 {
+        let n = ring.len();
+        let corners = |i: usize| [ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]];
+        let ear = (0..n).find(|&i| {
+            let [a, b, c] = corners(i);
+            let (pa, pb, pc) = (vertices[a], vertices[b], vertices[c]);
+            orientation_2d(pa, pb, pc) == Orientation::CounterClockwise
+                && ring.iter().all(|&k| {
+                    k == a || k == b || k == c || !point_in_triangle(vertices[k], pa, pb, pc)
+                })
+        })?;
+        triangles.push(corners(ear));
+        ring.remove(ear);
+    }
+
+    triangles.push([ring[0], ring[1], ring[2]]);
+    Some(triangles)
+}
+
+/// Merge triangles across their shared diagonals wherever the merged piece
+/// stays convex at both ends of the diagonal.
+///
+/// Pieces are index loops into `vertices`, all counter-clockwise.
+fn merge_convex(vertices: &[Point2], triangles: Vec<[usize; 3]>) -> Vec<Vec<usize>> {
+    use std::collections::HashMap;
+
+    let n = vertices.len();
+    let mut pieces: Vec<Option<Vec<usize>>> =
+        triangles.into_iter().map(|t| Some(t.to_vec())).collect();
+
+    // Owner piece of each directed edge, and the diagonals in a fixed order
+    let mut owner: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut diagonals = Vec::new();
+    for (p, piece) in pieces.iter().enumerate() {
+        let piece = piece.as_ref().unwrap();
+        for i in 0..3 {
+            let (a, b) = (piece[i], piece[(i + 1) % 3]);
+            owner.insert((a, b), p);
+            let boundary = (a + 1) % n == b || (b + 1) % n == a;
+            if !boundary && a < b {
+                diagonals.push((a, b));
+            }
+        }
+    }
+
+    for (a, b) in diagonals {
+        let (Some(&p), Some(&q)) = (owner.get(&(a, b)), owner.get(&(b, a))) else {
+            continue;
+        };
+        let (Some(first), Some(second)) = (pieces[p].as_ref(), pieces[q].as_ref()) else {
+            continue;
+        };
+
+        // `first` walks a -> b, `second` walks b -> a. Join them into one
+        // loop b ... a ... (back to b), leaving the diagonal out.
+        let from_b = rotate_to(first, b);
+        let from_a = rotate_to(second, a);
+        let mut merged = from_b.clone();
+        merged.extend(&from_a[1..from_a.len() - 1]);
+
+        let reflex_at = |i: usize| {
+            let m = merged.len();
+            let [prev, cur, next] =
+                [merged[(i + m - 1) % m], merged[i], merged[(i + 1) % m]].map(|k| vertices[k]);
+            orientation_2d(prev, cur, next) == Orientation::Clockwise
+        };
+        if reflex_at(0) || reflex_at(from_b.len() - 1) {
+            continue;
+        }
+
+        for i in 0..merged.len() {
+            owner.insert((merged[i], merged[(i + 1) % merged.len()]), p);
+        }
+        owner.remove(&(a, b));
+        owner.remove(&(b, a));
+        pieces[p] = Some(merged);
+        pieces[q] = None;
+    }
+
+    pieces.into_iter().flatten().collect()
+}
+
+/// The loop rotated to start at `start`, which it must contain.
+fn rotate_to(ring: &[usize], start: usize) -> Vec<usize> {
+    let at = ring.iter().position(|&k| k == start).unwrap_or(0);
+    ring[at..].iter().chain(&ring[..at]).copied().collect()
 }
 
 #[cfg(test)]
@@ -561,6 +1295,549 @@ mod tests {
         assert!(!poly.contains_point(&Point2::new(5.0, 15.0)));
     }
 
+    /// The L from (0,0) to (10,10) with the (5..10, 5..10) notch cut out.
+    fn l_shape() -> Polygon2 {
+        Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 5.0),
+            Point2::new(5.0, 5.0),
+            Point2::new(5.0, 10.0),
+            Point2::new(0.0, 10.0),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn concave_polygon_contains_point() {
+        let poly = l_shape();
+
+        // Inside both arms
+        assert!(poly.contains_point(&Point2::new(2.0, 8.0)));
+        assert!(poly.contains_point(&Point2::new(8.0, 2.0)));
+
+        // In the notch, including level with the reflex corner
+        assert!(!poly.contains_point(&Point2::new(7.5, 7.5)));
+        assert!(!poly.contains_point(&Point2::new(7.5, 5.0 + 0.01)));
+        assert!(!poly.contains_point(&Point2::new(5.0 + 0.01, 10.0)));
+
+        // Exactly on edges and vertices, including the notch edges
+        for p in [
+            Point2::new(5.0, 0.0),
+            Point2::new(0.0, 7.0),
+            Point2::new(10.0, 2.5),
+            Point2::new(7.5, 5.0),
+            Point2::new(5.0, 7.5),
+            Point2::new(2.5, 10.0),
+            Point2::new(5.0, 5.0),
+            Point2::new(10.0, 5.0),
+        ] {
+            assert!(poly.contains_point(&p), "({}, {}) on edge", p.x, p.y);
+        }
+
+        // Within tolerance of an edge, from outside
+        assert!(poly.contains_point(&Point2::new(7.5, 5.0 + COINCIDENCE_TOLERANCE / 2.0)));
+        assert!(!poly.contains_point(&Point2::new(-COINCIDENCE_TOLERANCE * 2.0, 5.0)));
+    }
+
+    #[test]
+    fn polygon_is_self_intersecting() {
+        assert!(!square().is_self_intersecting());
+        assert!(!l_shape().is_self_intersecting());
+        assert!(l_shape().is_simple() && !l_shape().is_convex());
+
+        let fig8 = Polygon2 {
+            vertices: vec![
+                Point2::new(0.0, 0.0),
+                Point2::new(2.0, 2.0),
+                Point2::new(2.0, 0.0),
+                Point2::new(0.0, 2.0),
+            ],
+        };
+        assert!(fig8.is_self_intersecting());
+
+        // A vertex touching a non-adjacent edge is simple but self-touching
+        let pinched = Polygon2 {
+            vertices: vec![
+                Point2::new(0.0, 0.0),
+                Point2::new(4.0, 0.0),
+                Point2::new(4.0, 4.0),
+                Point2::new(2.0, 0.0),
+                Point2::new(0.0, 4.0),
+            ],
+        };
+        assert!(pinched.is_simple());
+        assert!(pinched.is_self_intersecting());
+    }
+
+    fn assert_vertices(poly: &Polygon2, expected: &[(f64, f64)]) {
+        assert_eq!(poly.vertices.len(), expected.len());
+        for (v, &(x, y)) in poly.vertices.iter().zip(expected) {
+            assert!(
+                (v.x - x).abs() < EPSILON && (v.y - y).abs() < EPSILON,
+                "got ({}, {}), expected ({}, {})",
+                v.x,
+                v.y,
+                x,
+                y
+            );
+        }
+    }
+
+    /// Offset expected to leave a single polygon.
+    fn offset_one(poly: &Polygon2, distance: f64) -> Polygon2 {
+        let mut result = poly.offset(distance, OffsetJoin::default()).unwrap();
+        assert_eq!(result.len(), 1);
+        result.pop().unwrap()
+    }
+
+    #[test]
+    fn polygon_offset_square() {
+        let inward = offset_one(&square(), -1.0);
+        assert_vertices(&inward, &[(1.0, 1.0), (9.0, 1.0), (9.0, 9.0), (1.0, 9.0)]);
+        assert!((inward.area() - 64.0).abs() < EPSILON);
+
+        let outward = offset_one(&square(), 1.0);
+        assert!((outward.area() - 144.0).abs() < EPSILON);
+
+        // Winding does not change which way is out
+        let cw = offset_one(&square().reversed(), -1.0);
+        assert!(cw.is_clockwise());
+        assert!((cw.area() - 64.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn polygon_offset_rectangle_area_by_join() {
+        let poly = rect(0.0, 0.0, 8.0, 3.0);
+        let (area, perimeter) = (poly.area(), poly.perimeter());
+        let d = 0.75;
+
+        // Mitred corners add a full d x d square at each corner
+        let mitred = offset_one(&poly, d);
+        assert_eq!(mitred.vertex_count(), 4);
+        assert!((mitred.area() - (area + perimeter * d + 4.0 * d * d)).abs() < EPSILON);
+
+        // Bevelled corners add half of that
+        let bevelled = poly.offset(d, OffsetJoin::Bevel).unwrap();
+        assert_eq!(bevelled[0].vertex_count(), 8);
+        assert!((bevelled[0].area() - (area + perimeter * d + 2.0 * d * d)).abs() < EPSILON);
+
+        // A right angle mitres to sqrt(2) d, so a lower limit bevels it
+        let limited = poly.offset(d, OffsetJoin::Miter { limit: 1.2 }).unwrap();
+        assert_eq!(limited, bevelled);
+    }
+
+    #[test]
+    fn polygon_offset_concave_and_collinear() {
+        let inward = offset_one(&l_shape(), -1.0);
+        assert_vertices(
+            &inward,
+            &[
+                (1.0, 1.0),
+                (9.0, 1.0),
+                (9.0, 4.0),
+                (4.0, 4.0),
+                (4.0, 9.0),
+                (1.0, 9.0),
+            ],
+        );
+
+        // A vertex mid-edge and a repeated vertex
+        let poly = Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(5.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 10.0),
+            Point2::new(10.0, 10.0),
+            Point2::new(0.0, 10.0),
+        ])
+        .unwrap();
+        let inward = offset_one(&poly, -2.0);
+        assert_vertices(
+            &inward,
+            &[(2.0, 2.0), (5.0, 2.0), (8.0, 2.0), (8.0, 8.0), (2.0, 8.0)],
+        );
+    }
+
+    #[test]
+    fn polygon_offset_collapse_is_empty() {
+        let join = OffsetJoin::default();
+        assert!(square().offset(-5.0, join).unwrap().is_empty());
+        assert!(square().offset(-6.0, join).unwrap().is_empty());
+        assert!(l_shape().offset(-3.0, join).unwrap().is_empty());
+        assert_eq!(square().offset(-4.9, join).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn polygon_offset_narrow_l_past_half_width() {
+        // Arms 1 wide, the corner square 2 x 2
+        let narrow = Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(6.0, 0.0),
+            Point2::new(6.0, 1.0),
+            Point2::new(2.0, 1.0),
+            Point2::new(2.0, 6.0),
+            Point2::new(0.0, 6.0),
+        ])
+        .unwrap();
+        let join = OffsetJoin::default();
+
+        // Past half the thin arm's width only the wide arm is left
+        let inset = narrow.offset(-0.6, join).unwrap();
+        assert_clean(&inset);
+        assert_eq!(inset.len(), 1);
+        assert!(inset[0].is_counter_clockwise());
+        assert!(inset[0].area() < narrow.area());
+        let bbox = inset[0].bounding_box().unwrap();
+        assert!((bbox.min.x - 0.6).abs() < EPSILON && (bbox.max.x - 1.4).abs() < EPSILON);
+        assert!((bbox.min.y - 0.6).abs() < EPSILON && (bbox.max.y - 5.4).abs() < EPSILON);
+
+        assert!(narrow.offset(-1.0, join).unwrap().is_empty());
+    }
+
+    #[test]
+    fn polygon_offset_splits_pinched_polygon() {
+        // Two 4 x 4 rooms joined by a 1 wide corridor
+        let dumbbell = Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(4.0, 0.0),
+            Point2::new(4.0, 1.5),
+            Point2::new(6.0, 1.5),
+            Point2::new(6.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 4.0),
+            Point2::new(6.0, 4.0),
+            Point2::new(6.0, 2.5),
+            Point2::new(4.0, 2.5),
+            Point2::new(4.0, 4.0),
+            Point2::new(0.0, 4.0),
+        ])
+        .unwrap();
+        let join = OffsetJoin::default();
+
+        let inset = dumbbell.offset(-1.0, join).unwrap();
+        assert_clean(&inset);
+        assert_eq!(inset.len(), 2);
+        assert!((net_area(&inset) - 8.0).abs() < EPSILON);
+
+        // Clockwise input keeps clockwise pieces
+        let inset = dumbbell.reversed().offset(-1.0, join).unwrap();
+        assert_eq!(inset.len(), 2);
+        assert!(inset.iter().all(Polygon2::is_clockwise));
+
+        // Growing a room shell closes its 1 wide doorway, leaving a hole
+        let shell = Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 10.0),
+            Point2::new(5.5, 10.0),
+            Point2::new(5.5, 8.0),
+            Point2::new(8.0, 8.0),
+            Point2::new(8.0, 2.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(2.0, 8.0),
+            Point2::new(4.5, 8.0),
+            Point2::new(4.5, 10.0),
+            Point2::new(0.0, 10.0),
+        ])
+        .unwrap();
+        let grown = shell.offset(1.0, join).unwrap();
+        assert_clean(&grown);
+        assert_eq!(grown.len(), 2);
+        let hole = grown.iter().find(|p| p.is_clockwise()).unwrap();
+        assert!((hole.area() - 16.0).abs() < EPSILON);
+        assert!((net_area(&grown) - (144.0 - 16.0)).abs() < EPSILON);
+    }
+
+    /// Check that `pieces` are convex, counter-clockwise, inside `poly`, and
+    /// add up to its area.
+    fn assert_convex_tiling(poly: &Polygon2, pieces: &[Polygon2]) {
+        for piece in pieces {
+            assert!(piece.is_convex(), "{:?}", piece.vertices);
+            assert!(piece.is_counter_clockwise());
+            assert!(poly.contains_point(&piece.centroid()));
+        }
+        let total: f64 = pieces.iter().map(Polygon2::area).sum();
+        assert!((total - poly.area()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn decompose_l_shape_into_two_pieces() {
+        let poly = l_shape();
+        let pieces = poly.decompose_convex();
+        assert_eq!(pieces.len(), 2);
+        assert_convex_tiling(&poly, &pieces);
+
+        // Winding does not matter
+        assert_eq!(poly.reversed().decompose_convex().len(), 2);
+    }
+
+    #[test]
+    fn decompose_comb() {
+        // Three teeth rising from a base, with four reflex corners
+        let poly = Polygon2::new(
+            [
+                (0.0, 0.0),
+                (5.0, 0.0),
+                (5.0, 3.0),
+                (4.0, 3.0),
+                (4.0, 1.0),
+                (3.0, 1.0),
+                (3.0, 3.0),
+                (2.0, 3.0),
+                (2.0, 1.0),
+                (1.0, 1.0),
+                (1.0, 3.0),
+                (0.0, 3.0),
+            ]
+            .map(|(x, y)| Point2::new(x, y))
+            .to_vec(),
+        )
+        .unwrap();
+
+        let pieces = poly.decompose_convex();
+        // Hertel–Mehlhorn keeps at most two pieces per reflex corner, plus one
+        assert!((4..=9).contains(&pieces.len()), "{}", pieces.len());
+        assert_convex_tiling(&poly, &pieces);
+    }
+
+    #[test]
+    fn decompose_convex_and_degenerate_polygons() {
+        // A convex polygon comes back whole, without its collinear vertex
+        let mut poly = square();
+        poly.vertices.insert(1, Point2::new(5.0, 0.0));
+        let pieces = poly.decompose_convex();
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].vertex_count(), 4);
+
+        let bowtie = Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(0.0, 2.0),
+        ])
+        .unwrap();
+        assert!(bowtie.decompose_convex().is_empty());
+
+        let flat = Polygon2::new(vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 0.0),
+            Point2::new(2.0, 0.0),
+        ])
+        .unwrap();
+        assert!(flat.decompose_convex().is_empty());
+    }
+
+    #[test]
+    fn convex_hull_drops_interior_and_collinear_points() {
+        let hull = l_shape().convex_hull().unwrap();
+        assert_vertices(
+            &hull,
+            &[
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 5.0),
+                (5.0, 10.0),
+                (0.0, 10.0),
+            ],
+        );
+        assert!(hull.is_counter_clockwise());
+
+        let points = [
+            Point2::new(2.0, 2.0),
+            Point2::new(0.0, 0.0),
+            Point2::new(4.0, 0.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(4.0, 4.0),
+            Point2::new(0.0, 4.0),
+            Point2::new(1.0, 3.0),
+            Point2::new(4.0, 4.0),
+        ];
+        let hull = Polygon2::from_convex_hull(&points).unwrap();
+        assert_vertices(&hull, &[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]);
+
+        let collinear = [
+            Point2::new(0.0, 0.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(2.0, 2.0),
+        ];
+        assert_eq!(
+            Polygon2::from_convex_hull(&collinear),
+            Err(MathError::InsufficientVertices)
+        );
+    }
+
+    #[test]
+    fn min_area_obb_fits_diagonal_rectangle() {
+        let angle = 30f64.to_radians();
+        let center = Point2::new(5.0, -3.0);
+        let rect = Polygon2::centered_rectangle(center, 12.0, 2.0).rotate(center, angle);
+
+        let (obb_center, axis, half_length, half_width) = rect.min_area_obb();
+        let obb_area = 4.0 * half_length * half_width;
+        assert!((obb_area - 24.0).abs() < 1e-9);
+        assert!((obb_area - rect.area()).abs() < 1e-9);
+        assert!(obb_center.distance_to(&center) < 1e-9);
+        assert!((half_length - 6.0).abs() < 1e-9);
+        assert!((half_width - 1.0).abs() < 1e-9);
+        assert!((axis.cross(&Vector2::new(angle.cos(), angle.sin()))).abs() < 1e-9);
+
+        // The axis-aligned box is several times larger
+        let aabb_area = rect.bounding_box().unwrap().area();
+        assert!(aabb_area > 2.5 * obb_area);
+
+        // Extra vertices inside and along the hull do not change the box
+        let mut vertices = rect.vertices.clone();
+        vertices.insert(1, rect.vertices[0].lerp(&rect.vertices[1], 0.25));
+        let (_, _, l, w) = Polygon2::new(vertices).unwrap().min_area_obb();
+        assert!((l - half_length).abs() < 1e-9 && (w - half_width).abs() < 1e-9);
+    }
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> Polygon2 {
+        Polygon2::rectangle(Point2::new(x0, y0), Point2::new(x1, y1))
+    }
+
+    /// Net area of a boolean result, holes subtracted.
+    fn net_area(polygons: &[Polygon2]) -> f64 {
+        polygons.iter().map(Polygon2::signed_area).sum()
+    }
+
+    fn assert_clean(polygons: &[Polygon2]) {
+        for poly in polygons {
+            let n = poly.vertex_count();
+            assert!(n >= 3);
+            for i in 0..n {
+                let (prev, curr, next) = (
+                    poly.vertices[(i + n - 1) % n],
+                    poly.vertices[i],
+                    poly.vertices[(i + 1) % n],
+                );
+                assert!(curr.distance_to(&next) > EPSILON, "repeated vertex");
+                assert_ne!(orientation_2d(prev, curr, next), Orientation::Collinear);
+            }
+            assert!(poly.is_simple());
+        }
+    }
+
+    #[test]
+    fn boolean_overlapping_rectangles() {
+        let a = rect(0.0, 0.0, 4.0, 4.0);
+        let b = rect(2.0, 2.0, 6.0, 6.0);
+
+        let inter = a.intersection(&b);
+        assert_clean(&inter);
+        assert_eq!(inter.len(), 1);
+        assert!(inter[0].is_counter_clockwise());
+        assert!((inter[0].area() - 4.0).abs() < EPSILON);
+        assert!(inter[0].centroid().distance_to(&Point2::new(3.0, 3.0)) < EPSILON);
+
+        let union = a.union(&b);
+        assert_clean(&union);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].vertex_count(), 8);
+        assert!((net_area(&union) - 28.0).abs() < EPSILON);
+
+        let diff = a.difference(&b);
+        assert_clean(&diff);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].vertex_count(), 6);
+        assert!((net_area(&diff) - 12.0).abs() < EPSILON);
+
+        // Winding of the inputs does not matter
+        assert!((net_area(&a.reversed().difference(&b.reversed())) - 12.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn boolean_l_shape_minus_rectangle() {
+        // A bar across the bottom arm and up into the notch
+        let cut = rect(3.0, 2.0, 8.0, 7.0);
+        let diff = l_shape().difference(&cut);
+        assert_clean(&diff);
+        assert!((net_area(&diff) - 56.0).abs() < EPSILON);
+        for poly in &diff {
+            assert!(poly.is_counter_clockwise());
+            assert!(!poly.contains_point(&Point2::new(5.0, 4.0)));
+        }
+
+        let inter = l_shape().intersection(&cut);
+        assert_clean(&inter);
+        assert!((net_area(&inter) - 19.0).abs() < EPSILON);
+
+        // Cutting the whole bottom-right arm along shared edges
+        let arm = rect(5.0, 0.0, 10.0, 5.0);
+        let diff = l_shape().difference(&arm);
+        assert_clean(&diff);
+        assert_eq!(diff.len(), 1);
+        assert_vertices(
+            &diff[0],
+            &[(0.0, 0.0), (5.0, 0.0), (5.0, 10.0), (0.0, 10.0)],
+        );
+    }
+
+    #[test]
+    fn boolean_containment_and_disjoint() {
+        let outer = square();
+        let inner = rect(2.0, 2.0, 4.0, 4.0);
+
+        let diff = outer.difference(&inner);
+        assert_clean(&diff);
+        assert_eq!(diff.len(), 2);
+        assert!((net_area(&diff) - 96.0).abs() < EPSILON);
+        assert_eq!(diff.iter().filter(|p| p.is_clockwise()).count(), 1);
+
+        assert!(inner.difference(&outer).is_empty());
+        let inter = outer.intersection(&inner);
+        assert_eq!(inter.len(), 1);
+        assert!((inter[0].area() - 4.0).abs() < EPSILON);
+        let union = outer.union(&inner);
+        assert_eq!(union.len(), 1);
+        assert!((union[0].area() - 100.0).abs() < EPSILON);
+
+        let far = rect(20.0, 20.0, 22.0, 22.0);
+        assert!(outer.intersection(&far).is_empty());
+        assert_eq!(outer.union(&far).len(), 2);
+        let diff = outer.difference(&far);
+        assert_eq!(diff.len(), 1);
+        assert!((diff[0].area() - 100.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn boolean_shared_edges_do_not_panic() {
+        let a = rect(0.0, 0.0, 2.0, 2.0);
+
+        // Side by side: union merges, intersection is empty
+        let b = rect(2.0, 0.0, 4.0, 2.0);
+        let union = a.union(&b);
+        assert_clean(&union);
+        assert_eq!(union.len(), 1);
+        assert_vertices(&union[0], &[(0.0, 0.0), (4.0, 0.0), (4.0, 2.0), (0.0, 2.0)]);
+        assert!(a.intersection(&b).is_empty());
+        assert!((net_area(&a.difference(&b)) - 4.0).abs() < EPSILON);
+
+        // Partially shared edge
+        let c = rect(2.0, 1.0, 3.0, 3.0);
+        let union = a.union(&c);
+        assert_clean(&union);
+        assert!((net_area(&union) - 6.0).abs() < EPSILON);
+
+        // Touching at a single corner stays two loops
+        let d = rect(2.0, 2.0, 3.0, 3.0);
+        let union = a.union(&d);
+        assert_clean(&union);
+        assert_eq!(union.len(), 2);
+        assert!(a.intersection(&d).is_empty());
+
+        // Identical polygons
+        assert_eq!(a.union(&a).len(), 1);
+        assert!((net_area(&a.intersection(&a)) - 4.0).abs() < EPSILON);
+        assert!(a.difference(&a).is_empty());
+
+        // Degenerate inputs
+        let empty = Polygon2::default();
+        assert!(a.intersection(&empty).is_empty());
+        assert!((net_area(&a.union(&empty)) - 4.0).abs() < EPSILON);
+    }
+
     #[test]
     fn polygon_is_simple() {
         // Simple square
@@ -618,6 +1895,19 @@ mod tests {
         assert!((dist - std::f64::consts::SQRT_2).abs() < EPSILON);
     }
 
+    #[test]
+    fn polygon_mirror_keeps_winding() {
+        let poly = Polygon2::rectangle(Point2::new(1.0, 0.0), Point2::new(3.0, 1.0));
+        let axis = Line2::from_points(Point2::ORIGIN, Point2::new(0.0, 1.0)).unwrap();
+        let mirrored = poly.mirror(&axis);
+
+        assert!(mirrored.is_counter_clockwise());
+        assert!((mirrored.area() - poly.area()).abs() < EPSILON);
+        let bbox = mirrored.bounding_box().unwrap();
+        assert!((bbox.min.x - (-3.0)).abs() < EPSILON);
+        assert!((bbox.max.x - (-1.0)).abs() < EPSILON);
+    }
+
     #[test]
     fn polygon_edges_iterator() {
         let poly = square();
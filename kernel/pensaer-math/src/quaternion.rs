@@ -0,0 +1,277 @@
+//! Unit quaternions for 3D rotations.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{MathError, MathResult};
+use crate::transform::Transform3;
+use crate::vector::Vector3;
+
+/// A quaternion `w + xi + yj + zk`, used as a rotation when unit length.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    /// Create a quaternion from its components.
+    #[inline]
+    pub const fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// The identity rotation.
+    pub const IDENTITY: Self = Self {
+        w: 1.0,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    /// Create a rotation of `angle_rad` about `axis` (right-handed).
+    ///
+    /// The axis does not need to be unit length.
+    pub fn from_axis_angle(axis: Vector3, angle_rad: f64) -> MathResult<Self> {
+        let axis = axis.normalize()?;
+        let (sin, cos) = (angle_rad * 0.5).sin_cos();
+        Ok(Self::new(cos, axis.x * sin, axis.y * sin, axis.z * sin))
+    }
+
+    /// Create a rotation from Euler angles, applied about the fixed X, Y,
+    /// then Z axes (so the matrix is `Rz * Ry * Rx`).
+    pub fn from_euler(x_rad: f64, y_rad: f64, z_rad: f64) -> Self {
+        let (sx, cx) = (x_rad * 0.5).sin_cos();
+        let (sy, cy) = (y_rad * 0.5).sin_cos();
+        let (sz, cz) = (z_rad * 0.5).sin_cos();
+        Self::new(
+            cx * cy * cz + sx * sy * sz,
+            sx * cy * cz - cx * sy * sz,
+            cx * sy * cz + sx * cy * sz,
+            cx * cy * sz - sx * sy * cz,
+        )
+    }
+
+    /// Rotation of the upper 3x3 of a transform, which must be orthonormal
+    /// with determinant +1.
+    ///
+    /// Uses the largest of the four diagonal combinations as the pivot so
+    /// no division is by a small number.
+    pub fn from_matrix(t: &Transform3) -> Self {
+        // r(row, col) in the usual row-major sense
+        let r = |row: usize, col: usize| t.m[col][row];
+        let trace = r(0, 0) + r(1, 1) + r(2, 2);
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::new(
+                0.25 * s,
+                (r(2, 1) - r(1, 2)) / s,
+                (r(0, 2) - r(2, 0)) / s,
+                (r(1, 0) - r(0, 1)) / s,
+            )
+        } else if r(0, 0) > r(1, 1) && r(0, 0) > r(2, 2) {
+            let s = (1.0 + r(0, 0) - r(1, 1) - r(2, 2)).sqrt() * 2.0;
+            Self::new(
+                (r(2, 1) - r(1, 2)) / s,
+                0.25 * s,
+                (r(0, 1) + r(1, 0)) / s,
+                (r(0, 2) + r(2, 0)) / s,
+            )
+        } else if r(1, 1) > r(2, 2) {
+            let s = (1.0 + r(1, 1) - r(0, 0) - r(2, 2)).sqrt() * 2.0;
+            Self::new(
+                (r(0, 2) - r(2, 0)) / s,
+                (r(0, 1) + r(1, 0)) / s,
+                0.25 * s,
+                (r(1, 2) + r(2, 1)) / s,
+            )
+        } else {
+            let s = (1.0 + r(2, 2) - r(0, 0) - r(1, 1)).sqrt() * 2.0;
+            Self::new(
+                (r(1, 0) - r(0, 1)) / s,
+                (r(0, 2) + r(2, 0)) / s,
+                (r(1, 2) + r(2, 1)) / s,
+                0.25 * s,
+            )
+        };
+        q.normalize().unwrap_or(Self::IDENTITY)
+    }
+
+    /// Length of the quaternion.
+    #[inline]
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Dot product of the components.
+    #[inline]
+    pub fn dot(&self, other: &Self) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Scale to unit length.
+    pub fn normalize(&self) -> MathResult<Self> {
+        let len = self.length();
+        if len < f64::EPSILON {
+            Err(MathError::ZeroLengthVector)
+        } else {
+            Ok(Self::new(
+                self.w / len,
+                self.x / len,
+                self.y / len,
+                self.z / len,
+            ))
+        }
+    }
+
+    /// Rotation matrix of a unit quaternion.
+    pub fn to_matrix(&self) -> Transform3 {
+        let Self { w, x, y, z } = *self;
+        Transform3 {
+            m: [
+                [
+                    1.0 - 2.0 * (y * y + z * z),
+                    2.0 * (x * y + w * z),
+                    2.0 * (x * z - w * y),
+                    0.0,
+                ],
+                [
+                    2.0 * (x * y - w * z),
+                    1.0 - 2.0 * (x * x + z * z),
+                    2.0 * (y * z + w * x),
+                    0.0,
+                ],
+                [
+                    2.0 * (x * z + w * y),
+                    2.0 * (y * z - w * x),
+                    1.0 - 2.0 * (x * x + y * y),
+                    0.0,
+                ],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// Spherical interpolation between two unit quaternions.
+    ///
+    /// Takes the shorter arc; `t = 0` gives `self` and `t = 1` gives
+    /// `other` (or its negation, the same rotation).
+    pub fn slerp(&self, other: &Self, t: f64) -> Self {
+        let mut dot = self.dot(other);
+        let mut end = *other;
+        if dot < 0.0 {
+            dot = -dot;
+            end = Self::new(-end.w, -end.x, -end.y, -end.z);
+        }
+
+        // Nearly parallel: lerp avoids dividing by a tiny sine
+        let (a, b) = if dot > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let theta = dot.acos();
+            let sin = theta.sin();
+            (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+        };
+        let q = Self::new(
+            a * self.w + b * end.w,
+            a * self.x + b * end.x,
+            a * self.y + b * end.y,
+            a * self.z + b * end.z,
+        );
+        q.normalize().unwrap_or(q)
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point3;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    const EPSILON: f64 = 1e-10;
+
+    #[test]
+    fn axis_angle_rotates_point_about_z() {
+        let q = Quaternion::from_axis_angle(Vector3::new(0.0, 0.0, 2.0), FRAC_PI_2).unwrap();
+        let p = q.to_matrix().transform_point(Point3::new(1.0, 0.0, 5.0));
+        assert!((p.x).abs() < EPSILON);
+        assert!((p.y - 1.0).abs() < EPSILON);
+        assert!((p.z - 5.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn matrix_matches_axis_rotations() {
+        let angle = 0.6;
+        let cases = [
+            (Vector3::UNIT_X, Transform3::rotation_x(angle)),
+            (Vector3::UNIT_Y, Transform3::rotation_y(angle)),
+            (Vector3::UNIT_Z, Transform3::rotation_z(angle)),
+        ];
+        for (axis, expected) in cases {
+            let m = Quaternion::from_axis_angle(axis, angle)
+                .unwrap()
+                .to_matrix();
+            for col in 0..4 {
+                for row in 0..4 {
+                    assert!((m.m[col][row] - expected.m[col][row]).abs() < EPSILON);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn slerp_halfway() {
+        let a = Quaternion::IDENTITY;
+        let b = Quaternion::from_axis_angle(Vector3::UNIT_Z, FRAC_PI_2).unwrap();
+        let mid = a.slerp(&b, 0.5);
+        let expected = Quaternion::from_axis_angle(Vector3::UNIT_Z, FRAC_PI_4).unwrap();
+        assert!((mid.dot(&expected) - 1.0).abs() < EPSILON);
+        assert!((a.slerp(&b, 0.0).dot(&a) - 1.0).abs() < EPSILON);
+        assert!((a.slerp(&b, 1.0).dot(&b) - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn normalize_rejects_zero() {
+        let q = Quaternion::new(2.0, 0.0, 0.0, 0.0).normalize().unwrap();
+        assert_eq!(q, Quaternion::IDENTITY);
+        assert!(Quaternion::new(0.0, 0.0, 0.0, 0.0).normalize().is_err());
+        assert!(Quaternion::from_axis_angle(Vector3::ZERO, 1.0).is_err());
+    }
+
+    #[test]
+    fn euler_matches_composed_axis_rotations() {
+        let (x, y, z) = (0.4, -1.2, 2.1);
+        let expected = Transform3::rotation_x(x)
+            .compose(&Transform3::rotation_y(y))
+            .compose(&Transform3::rotation_z(z));
+        let m = Quaternion::from_euler(x, y, z).to_matrix();
+        for col in 0..4 {
+            for row in 0..4 {
+                assert!((m.m[col][row] - expected.m[col][row]).abs() < EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn from_matrix_round_trips() {
+        // Angles near pi exercise the non-trace pivots
+        let rotations = [
+            Quaternion::IDENTITY,
+            Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 3.0), 0.8).unwrap(),
+            Quaternion::from_axis_angle(Vector3::UNIT_X, 3.1).unwrap(),
+            Quaternion::from_axis_angle(Vector3::UNIT_Y, 3.1).unwrap(),
+            Quaternion::from_axis_angle(Vector3::new(0.2, -0.1, 1.0), 3.1).unwrap(),
+        ];
+        for q in rotations {
+            let back = Quaternion::from_matrix(&q.to_matrix());
+            assert!((back.dot(&q).abs() - 1.0).abs() < EPSILON);
+        }
+    }
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::{MathError, MathResult};
 use crate::point::Point3;
+use crate::quaternion::Quaternion;
 use crate::vector::Vector3;
 
 /// A 4x4 transformation matrix for 3D operations.
@@ -103,8 +104,107 @@ impl Transform3 {
         }
     }
 
+    /// Create rotation of `angle_rad` around an arbitrary axis (right-handed).
+    ///
+    /// The axis does not need to be unit length; a zero axis is an error.
+    pub fn rotation_axis(axis: Vector3, angle_rad: f64) -> MathResult<Self> {
+        Ok(Quaternion::from_axis_angle(axis, angle_rad)?.to_matrix())
+    }
+
+    /// Create rotation of `angle_rad` around an axis through `center`.
+    ///
+    /// The center stays fixed; a zero axis is an error.
+    pub fn rotation_about(axis: Vector3, angle_rad: f64, center: Point3) -> MathResult<Self> {
+        Ok(Self::translation(-center.x, -center.y, -center.z)
+            .compose(&Self::rotation_axis(axis, angle_rad)?)
+            .compose(&Self::translation(center.x, center.y, center.z)))
+    }
+
+    /// Create a transform that scales, then rotates, then translates.
+    ///
+    /// `rotation` should be unit length; see [`Quaternion::from_euler`] for
+    /// building one from Euler angles. [`Transform3::decompose`] inverts this.
+    pub fn from_trs(translation: Vector3, rotation: Quaternion, scale: Vector3) -> Self {
+        Self::scale(scale.x, scale.y, scale.z)
+            .compose(&rotation.to_matrix())
+            .compose(&Self::translation(
+                translation.x,
+                translation.y,
+                translation.z,
+            ))
+    }
+
+    /// Split an affine transform into translation, rotation, and scale.
+    ///
+    /// The convention matches [`Transform3::from_trs`]: scale about the
+    /// origin first, then rotate, then translate, so
+    /// `from_trs(t, r, s)` rebuilds the transform. A mirrored transform
+    /// (negative determinant) reports the reflection as a negative X scale.
+    ///
+    /// # Errors
+    ///
+    /// [`MathError::SingularMatrix`] when a scale factor is zero, and
+    /// [`MathError::DomainError`] for projective or sheared transforms,
+    /// which have no such decomposition.
+    pub fn decompose(&self) -> MathResult<(Vector3, Quaternion, Vector3)> {
+        const TOL: f64 = 1e-9;
+        let m = &self.m;
+        if m[0][3].abs() > TOL
+            || m[1][3].abs() > TOL
+            || m[2][3].abs() > TOL
+            || (m[3][3] - 1.0).abs() > TOL
+        {
+            return Err(MathError::DomainError);
+        }
+
+        let translation = Vector3::new(m[3][0], m[3][1], m[3][2]);
+        let columns = [0, 1, 2].map(|c| Vector3::new(m[c][0], m[c][1], m[c][2]));
+        let mut scale = columns.map(|c| c.length());
+        if scale.iter().any(|&s| s < TOL) {
+            return Err(MathError::SingularMatrix);
+        }
+        if self.determinant() < 0.0 {
+            scale[0] = -scale[0];
+        }
+
+        let axes = [0, 1, 2].map(|i| columns[i] / scale[i]);
+        if axes[0].dot(&axes[1]).abs() > TOL
+            || axes[1].dot(&axes[2]).abs() > TOL
+            || axes[0].dot(&axes[2]).abs() > TOL
+        {
+            return Err(MathError::DomainError);
+        }
+
+        let mut rotation = Self::identity();
+        for (c, axis) in axes.iter().enumerate() {
+            rotation.m[c] = [axis.x, axis.y, axis.z, 0.0];
+        }
+        Ok((
+            translation,
+            Quaternion::from_matrix(&rotation),
+            Vector3::new(scale[0], scale[1], scale[2]),
+        ))
+    }
+
+    /// Whether the transform only rotates and translates (no scale,
+    /// mirror, shear, or projection).
+    pub fn is_rigid(&self) -> bool {
+        const TOL: f64 = 1e-9;
+        match self.decompose() {
+            Ok((_, _, scale)) => {
+                (scale.x - 1.0).abs() < TOL
+                    && (scale.y - 1.0).abs() < TOL
+                    && (scale.z - 1.0).abs() < TOL
+            }
+            Err(_) => false,
+        }
+    }
+
     /// Compose (multiply) two transforms. Result applies self first, then other.
     ///
+    /// In matrix terms the result is `other * self`, so `self` is the right
+    /// operand and acts on points first.
+    ///
     /// Example: `translate.compose(&scale)` will first translate, then scale.
     #[inline]
     #[allow(clippy::needless_range_loop)]
@@ -157,6 +257,14 @@ impl Transform3 {
     }
 
     /// Compute the inverse transform.
+    ///
+    /// Inverts the full 4x4 matrix, so projective and non-uniform scale
+    /// transforms are handled as well as rigid ones.
+    ///
+    /// # Errors
+    ///
+    /// [`MathError::SingularMatrix`] when the determinant is zero, for
+    /// example a scale with a zero factor.
     pub fn inverse(&self) -> MathResult<Self> {
         let m = &self.m;
 
@@ -344,6 +452,58 @@ mod tests {
         assert!((result.z).abs() < EPSILON);
     }
 
+    #[test]
+    fn inverse_negates_translation() {
+        let inv = Transform3::translation(4.0, -5.0, 6.5).inverse().unwrap();
+        assert!((inv.m[3][0] + 4.0).abs() < EPSILON);
+        assert!((inv.m[3][1] - 5.0).abs() < EPSILON);
+        assert!((inv.m[3][2] + 6.5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn compose_with_inverse_is_identity() {
+        let t = Transform3::rotation_z(0.7)
+            .compose(&Transform3::scale(2.0, 0.5, 3.0))
+            .compose(&Transform3::translation(10.0, -3.0, 1.5));
+        let product = t.compose(&t.inverse().unwrap());
+        let identity = Transform3::identity();
+        for col in 0..4 {
+            for row in 0..4 {
+                assert!((product.m[col][row] - identity.m[col][row]).abs() < EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        let flatten = Transform3::scale(1.0, 1.0, 0.0);
+        assert_eq!(flatten.inverse(), Err(MathError::SingularMatrix));
+    }
+
+    #[test]
+    fn rotation_axis_z_90_degrees() {
+        let t = Transform3::rotation_axis(Vector3::UNIT_Z, std::f64::consts::FRAC_PI_2).unwrap();
+        let result = t.transform_point(Point3::new(2.0, 0.0, 1.0));
+        assert!((result.x).abs() < EPSILON);
+        assert!((result.y - 2.0).abs() < EPSILON);
+        assert!((result.z - 1.0).abs() < EPSILON);
+        assert!(Transform3::rotation_axis(Vector3::ZERO, 1.0).is_err());
+    }
+
+    #[test]
+    fn rotation_preserves_vector_length() {
+        let v = Vector3::new(3.0, -4.0, 12.0);
+        let rotations = [
+            Transform3::rotation_x(0.3),
+            Transform3::rotation_y(-1.1),
+            Transform3::rotation_z(2.5),
+            Transform3::rotation_axis(Vector3::new(1.0, 2.0, -0.5), 0.9).unwrap(),
+        ];
+        for t in rotations {
+            assert!((t.transform_vector(v).length() - 13.0).abs() < EPSILON);
+        }
+    }
+
     #[test]
     fn vector_ignores_translation() {
         let t = Transform3::translation(100.0, 100.0, 100.0);
@@ -353,4 +513,75 @@ mod tests {
         assert!((result.y).abs() < EPSILON);
         assert!((result.z).abs() < EPSILON);
     }
+
+    fn assert_matrix_eq(a: &Transform3, b: &Transform3) {
+        for col in 0..4 {
+            for row in 0..4 {
+                assert!((a.m[col][row] - b.m[col][row]).abs() < EPSILON);
+            }
+        }
+    }
+
+    #[test]
+    fn rotation_about_keeps_center_fixed() {
+        let center = Point3::new(5.0, 5.0, 0.0);
+        let t = Transform3::rotation_about(Vector3::UNIT_Z, std::f64::consts::FRAC_PI_2, center)
+            .unwrap();
+
+        let fixed = t.transform_point(center);
+        assert!((fixed.x - 5.0).abs() < EPSILON);
+        assert!((fixed.y - 5.0).abs() < EPSILON);
+
+        let moved = t.transform_point(Point3::new(6.0, 5.0, 2.0));
+        assert!((moved.x - 5.0).abs() < EPSILON);
+        assert!((moved.y - 6.0).abs() < EPSILON);
+        assert!((moved.z - 2.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn decompose_inverts_from_trs() {
+        let translation = Vector3::new(3.0, -2.0, 7.5);
+        let rotation = Quaternion::from_euler(0.3, -0.7, 1.9);
+        let scale = Vector3::new(2.0, 0.5, 1.5);
+        let t = Transform3::from_trs(translation, rotation, scale);
+
+        let (t2, r2, s2) = t.decompose().unwrap();
+        assert!((t2 - translation).length() < EPSILON);
+        assert!((r2.dot(&rotation).abs() - 1.0).abs() < EPSILON);
+        assert!((s2 - scale).length() < EPSILON);
+        assert_matrix_eq(&Transform3::from_trs(t2, r2, s2), &t);
+    }
+
+    #[test]
+    fn decompose_mirror_as_negative_x_scale() {
+        let mirror = Transform3::scale(1.0, -1.0, 1.0);
+        let (_, rotation, scale) = mirror.decompose().unwrap();
+        assert!((scale.x + 1.0).abs() < EPSILON);
+        assert_matrix_eq(
+            &Transform3::from_trs(Vector3::ZERO, rotation, scale),
+            &mirror,
+        );
+    }
+
+    #[test]
+    fn decompose_rejects_shear_and_singular() {
+        let mut shear = Transform3::identity();
+        shear.m[1][0] = 0.5;
+        assert_eq!(shear.decompose(), Err(MathError::DomainError));
+        assert_eq!(
+            Transform3::scale(1.0, 0.0, 1.0).decompose(),
+            Err(MathError::SingularMatrix)
+        );
+    }
+
+    #[test]
+    fn rigid_transforms() {
+        let rigid = Transform3::rotation_axis(Vector3::new(1.0, 1.0, 0.0), 0.4)
+            .unwrap()
+            .compose(&Transform3::translation(1.0, 2.0, 3.0));
+        assert!(rigid.is_rigid());
+        assert!((rigid.determinant() - 1.0).abs() < EPSILON);
+        assert!(!Transform3::scale_uniform(2.0).is_rigid());
+        assert!(!Transform3::scale(-1.0, 1.0, 1.0).is_rigid());
+    }
 }
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 
 use crate::error::{MathError, MathResult};
+use crate::guards::safe_acos;
 
 /// A vector in 2D space.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -120,6 +121,17 @@ impl Vector2 {
         cross.atan2(dot)
     }
 
+    /// Unsigned angle between two vectors in radians (0 to π), or zero if
+    /// either has zero length.
+    #[inline]
+    pub fn angle_between(&self, other: &Self) -> f64 {
+        let lengths = self.length() * other.length();
+        if lengths < 1e-15 {
+            return 0.0;
+        }
+        safe_acos(self.dot(other) / lengths)
+    }
+
     /// Angle from positive X axis in radians (-π to π).
     #[inline]
     pub fn angle(&self) -> f64 {
@@ -383,6 +395,17 @@ impl Vector3 {
         Ok(*self - n * (2.0 * self.dot(&n)))
     }
 
+    /// Unsigned angle between two vectors in radians (0 to π), or zero if
+    /// either has zero length.
+    #[inline]
+    pub fn angle_between(&self, other: &Self) -> f64 {
+        let lengths = self.length() * other.length();
+        if lengths < 1e-15 {
+            return 0.0;
+        }
+        safe_acos(self.dot(other) / lengths)
+    }
+
     /// Project to 2D by dropping Z component.
     #[inline]
     pub fn to_vector2(&self) -> Vector2 {
@@ -731,4 +754,27 @@ mod tests {
         let proj = v.project_onto(&onto).unwrap();
         assert!(proj.approx_eq(&Vector3::new(3.0, 0.0, 0.0), EPSILON));
     }
+
+    #[test]
+    fn vector3_reflect() {
+        let v = Vector3::new(1.0, -1.0, 0.0);
+        let reflected = v.reflect(&Vector3::UNIT_Y).unwrap();
+        assert!(reflected.approx_eq(&Vector3::new(1.0, 1.0, 0.0), EPSILON));
+    }
+
+    #[test]
+    fn angle_between() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+
+        let angle = Vector3::UNIT_X.angle_between(&Vector3::UNIT_Z);
+        assert!((angle - FRAC_PI_2).abs() < EPSILON);
+        let angle = Vector3::UNIT_Y.angle_between(&Vector3::new(0.0, -2.0, 0.0));
+        assert!((angle - PI).abs() < EPSILON);
+        assert_eq!(Vector3::UNIT_X.angle_between(&Vector3::ZERO), 0.0);
+
+        // Unsigned, unlike angle_to
+        let (a, b) = (Vector2::UNIT_X, Vector2::new(1.0, -1.0));
+        assert!((a.angle_between(&b) - FRAC_PI_2 / 2.0).abs() < EPSILON);
+        assert!((a.angle_to(&b) + FRAC_PI_2 / 2.0).abs() < EPSILON);
+    }
 }
@@ -0,0 +1,458 @@
+//! Pensaer Sync Module
+//!
+//! Bridges the CRDT log and the geometry kernel. Edits travel between
+//! replicas as [`GeometryOperation`] payloads with typed geometry, inside
+//! the same [`Operation`] envelope, [`OperationLog`] ordering, and dedup as
+//! string-valued operations. A [`GraphReplica`] replays them into a
+//! [`TopologyGraph`].
+//!
+//! # Determinism
+//!
+//! - Operations serialize to the same JSON on every replica: payload fields
+//!   are written in declaration order and vector clocks sorted by replica.
+//! - Replay sorts operations with [`causal_order`], so replicas holding the
+//!   same operations build the same geometry whatever order they received
+//!   them in. Graph element IDs are local to each replica; walls are
+//!   addressed by their wall ID and nodes by position.
+//!
+//! # Example
+//!
+//! ```
+//! use pensaer_crdt::{OperationLog, ReplicaId, Session};
+//! use pensaer_math::Point2;
+//! use pensaer_sync::{GeometryOperation, GraphReplica};
+//!
+//! let mut session = Session::new(ReplicaId::new("user-1"));
+//!
+//! let mut log = OperationLog::new();
+//! log.add(session.operation(
+//!     "op-1",
+//!     GeometryOperation::CreateWall {
+//!         id: "wall-1".to_string(),
+//!         start: Point2::new(0.0, 0.0),
+//!         end: Point2::new(5000.0, 0.0),
+//!         height: 2700.0,
+//!         thickness: 200.0,
+//!     },
+//! ));
+//!
+//! let replica = GraphReplica::from_log(&log);
+//! assert_eq!(replica.graph.edge_count(), 1);
+//! ```
+
+use std::collections::HashMap;
+
+use pensaer_crdt::apply::causal_order;
+use pensaer_crdt::{Operation, OperationLog, Payload};
+use pensaer_geometry::{EdgeData, EdgeId, OpeningRef, TopologyGraph, WallOpening};
+use pensaer_math::Point2;
+use serde::{Deserialize, Serialize};
+
+/// Millimetres per metre: elements are in metres, the graph in millimetres.
+const M_TO_MM: f64 = 1000.0;
+
+/// A geometry operation carried in the CRDT log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GeometryOperation {
+    /// Create a wall between two points
+    CreateWall {
+        id: String,
+        start: Point2,
+        end: Point2,
+        height: f64,
+        thickness: f64,
+    },
+    /// Add an opening to a wall, or replace the one with the same ID
+    ///
+    /// `opening` is in metres like every `WallOpening`; replay converts it to
+    /// the graph's millimetres.
+    SetOpening {
+        wall_id: String,
+        opening: WallOpening,
+    },
+    /// Move the node at `from` to `to`
+    ///
+    /// `node_id` names the node on the replica that made the move; replay
+    /// finds it by `from`, since node IDs are local to each graph.
+    MoveNode {
+        node_id: String,
+        from: Point2,
+        to: Point2,
+    },
+    /// Delete a wall or an opening
+    DeleteElement {
+        id: String,
+        element_type: ElementKind,
+    },
+}
+
+/// Kinds of element a [`GeometryOperation::DeleteElement`] can remove.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElementKind {
+    Wall,
+    Opening,
+}
+
+impl Payload for GeometryOperation {
+    fn element_id(&self) -> &str {
+        match self {
+            Self::CreateWall { id, .. } | Self::DeleteElement { id, .. } => id,
+            // An opening edits its host wall, so deleting the wall
+            // tombstones it too
+            Self::SetOpening { wall_id, .. } => wall_id,
+            Self::MoveNode { node_id, .. } => node_id,
+        }
+    }
+
+    fn is_create(&self) -> bool {
+        matches!(self, Self::CreateWall { .. })
+    }
+
+    fn is_delete(&self) -> bool {
+        matches!(self, Self::DeleteElement { .. })
+    }
+}
+
+/// An operation with a geometry payload.
+pub type GeometryOp = Operation<GeometryOperation>;
+
+/// A topology graph rebuilt from geometry operations.
+#[derive(Debug, Default)]
+pub struct GraphReplica {
+    /// The rebuilt wall network
+    pub graph: TopologyGraph,
+    /// IDs of operations that could not be applied, in replay order
+    pub skipped: Vec<String>,
+    /// Edge of each live wall, by wall ID
+    walls: HashMap<String, EdgeId>,
+}
+
+impl GraphReplica {
+    /// Create an empty replica.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replay every operation in a log.
+    pub fn from_log(log: &OperationLog<GeometryOperation>) -> Self {
+        Self::replay(&log.operations_ordered())
+    }
+
+    /// Replay operations into a new graph.
+    ///
+    /// The input order does not matter: operations are sorted with
+    /// [`causal_order`] first. No healing passes run, so each wall stays
+    /// one edge.
+    pub fn replay(ops: &[&GeometryOp]) -> Self {
+        let mut ordered = ops.to_vec();
+        ordered.sort_by(|a, b| causal_order(a, b));
+
+        let mut replica = Self::new();
+        for op in ordered {
+            if !replica.apply(&op.op_type) {
+                replica.skipped.push(op.id.clone());
+            }
+        }
+        replica
+    }
+
+    /// Edge holding a wall.
+    pub fn wall_edge(&self, wall_id: &str) -> Option<EdgeId> {
+        self.walls.get(wall_id).copied()
+    }
+
+    /// Apply one operation; returns false if it does not apply to the graph.
+    fn apply(&mut self, op: &GeometryOperation) -> bool {
+        match op {
+            GeometryOperation::CreateWall {
+                id,
+                start,
+                end,
+                height,
+                thickness,
+            } => {
+                if self.walls.contains_key(id) {
+                    return false;
+                }
+                let data = EdgeData::wall(*thickness, *height);
+                match self.graph.add_edge(xy(start), xy(end), data) {
+                    Some(edge_id) => {
+                        self.walls.insert(id.clone(), edge_id);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            GeometryOperation::SetOpening { wall_id, opening } => {
+                let Some(edge) = self
+                    .walls
+                    .get(wall_id)
+                    .and_then(|id| self.graph.get_edge_mut(*id))
+                else {
+                    return false;
+                };
+                let opening_ref = OpeningRef {
+                    element_id: opening.id,
+                    offset: opening.start_offset() * M_TO_MM,
+                    width: opening.width * M_TO_MM,
+                    height: opening.height * M_TO_MM,
+                    sill_height: opening.base_height * M_TO_MM,
+                };
+                let openings = &mut edge.data.openings;
+                match openings.iter_mut().find(|o| o.element_id == opening.id) {
+                    Some(existing) => *existing = opening_ref,
+                    None => openings.push(opening_ref),
+                }
+                true
+            }
+            GeometryOperation::MoveNode { from, to, .. } => {
+                let tolerance = self.graph.snap_tolerance();
+                match self.graph.nodes_within(xy(from), tolerance).first() {
+                    Some(&node_id) => self.graph.move_node(node_id, xy(to)),
+                    None => false,
+                }
+            }
+            GeometryOperation::DeleteElement {
+                id,
+                element_type: ElementKind::Wall,
+            } => match self.walls.remove(id) {
+                Some(edge_id) => self.graph.remove_edge(edge_id).is_some(),
+                None => false,
+            },
+            GeometryOperation::DeleteElement {
+                id,
+                element_type: ElementKind::Opening,
+            } => {
+                let Ok(opening_id) = id.parse::<uuid::Uuid>() else {
+                    return false;
+                };
+                let mut removed = false;
+                for edge_id in self.walls.values() {
+                    if let Some(edge) = self.graph.get_edge_mut(*edge_id) {
+                        let before = edge.data.openings.len();
+                        edge.data.openings.retain(|o| o.element_id != opening_id);
+                        removed |= edge.data.openings.len() != before;
+                    }
+                }
+                removed
+            }
+        }
+    }
+}
+
+fn xy(p: &Point2) -> [f64; 2] {
+    [p.x, p.y]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pensaer_crdt::{ManualClock, ReplicaId, VectorClock};
+    use pensaer_geometry::OpeningType;
+
+    /// A replica's editing session: its ID, clock, and the ops it made.
+    struct Session {
+        replica: ReplicaId,
+        clock: VectorClock,
+        log: OperationLog<GeometryOperation>,
+    }
+
+    impl Session {
+        fn new(name: &str) -> Self {
+            Self {
+                replica: ReplicaId::new(name),
+                clock: VectorClock::new(),
+                log: OperationLog::new(),
+            }
+        }
+
+        fn edit(&mut self, id: &str, op: GeometryOperation, time: &ManualClock) {
+            self.clock.increment(&self.replica);
+            time.advance(10);
+            self.log.add(Operation::with_clock_source(
+                id,
+                op,
+                self.replica.clone(),
+                self.clock.clone(),
+                time,
+            ));
+        }
+
+        /// Receive another session's ops.
+        fn sync_from(&mut self, other: &Session) {
+            self.log.merge(&other.log);
+            self.clock.merge(&other.clock);
+        }
+    }
+
+    fn wall(id: &str, start: (f64, f64), end: (f64, f64)) -> GeometryOperation {
+        GeometryOperation::CreateWall {
+            id: id.to_string(),
+            start: Point2::new(start.0, start.1),
+            end: Point2::new(end.0, end.1),
+            height: 2700.0,
+            thickness: 200.0,
+        }
+    }
+
+    /// Walls as sorted (start, end, thickness, height, openings) tuples.
+    type WallGeometry = ([f64; 2], [f64; 2], f64, f64, Vec<(uuid::Uuid, f64, f64)>);
+
+    fn geometry(replica: &GraphReplica) -> Vec<WallGeometry> {
+        let mut walls: Vec<WallGeometry> = replica
+            .graph
+            .edges()
+            .map(|e| {
+                let (start, end) = replica.graph.edge_positions(e.id).unwrap();
+                let openings = e
+                    .data
+                    .openings
+                    .iter()
+                    .map(|o| (o.element_id, o.offset, o.width))
+                    .collect();
+                (start, end, e.data.thickness, e.data.height, openings)
+            })
+            .collect();
+        walls.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        walls
+    }
+
+    #[test]
+    fn replicas_converge_whatever_the_delivery_order() {
+        let time = ManualClock::new(1_000);
+        let mut alice = Session::new("alice");
+        let mut bob = Session::new("bob");
+
+        alice.edit("a1", wall("w1", (0.0, 0.0), (5000.0, 0.0)), &time);
+        alice.edit("a2", wall("w2", (5000.0, 0.0), (5000.0, 4000.0)), &time);
+        bob.sync_from(&alice);
+
+        // Concurrently: Alice adds a door and a window, Bob drags the
+        // corner, adds a wall, and deletes the window's wall
+        let door = WallOpening::new(1.0, 0.0, 0.9, 2.1, OpeningType::Door);
+        let window = WallOpening::new(2.0, 0.9, 1.2, 1.5, OpeningType::Window);
+        alice.edit(
+            "a3",
+            GeometryOperation::SetOpening {
+                wall_id: "w1".to_string(),
+                opening: door.clone(),
+            },
+            &time,
+        );
+        alice.edit(
+            "a4",
+            GeometryOperation::SetOpening {
+                wall_id: "w2".to_string(),
+                opening: window,
+            },
+            &time,
+        );
+        bob.edit(
+            "b1",
+            GeometryOperation::MoveNode {
+                node_id: "corner".to_string(),
+                from: Point2::new(5000.0, 0.0),
+                to: Point2::new(6000.0, 0.0),
+            },
+            &time,
+        );
+        bob.edit("b2", wall("w3", (0.0, 0.0), (0.0, 4000.0)), &time);
+        bob.edit(
+            "b3",
+            GeometryOperation::DeleteElement {
+                id: "w2".to_string(),
+                element_type: ElementKind::Wall,
+            },
+            &time,
+        );
+
+        // Each replica receives the other's ops after its own
+        let mut first = Session::new("replica-1");
+        first.sync_from(&alice);
+        first.sync_from(&bob);
+        let mut second = Session::new("replica-2");
+        second.sync_from(&bob);
+        second.sync_from(&alice);
+        assert_eq!(first.log.len(), 7);
+        assert_eq!(second.log.len(), 7);
+        assert!(first.log.is_deleted("w2") && second.log.is_deleted("w2"));
+
+        let one = GraphReplica::from_log(&first.log);
+        let two = GraphReplica::from_log(&second.log);
+        assert_eq!(geometry(&one), geometry(&two));
+        assert_eq!(one.skipped, two.skipped);
+
+        let walls = geometry(&one);
+        assert_eq!(walls.len(), 2);
+        let w1 = one.wall_edge("w1").unwrap();
+        assert_eq!(
+            one.graph.edge_positions(w1),
+            Some(([0.0, 0.0], [6000.0, 0.0]))
+        );
+        let openings = &one.graph.get_edge(w1).unwrap().data.openings;
+        assert_eq!(openings.len(), 1);
+        assert_eq!(openings[0].element_id, door.id);
+        // Centred 1m along the wall: the near side is 550mm from the start
+        assert!((openings[0].offset - 550.0).abs() < 1e-9);
+        assert!((openings[0].width - 900.0).abs() < 1e-9);
+        assert!((openings[0].height - 2100.0).abs() < 1e-9);
+        assert_eq!(openings[0].sill_height, 0.0);
+        assert!(one.wall_edge("w2").is_none());
+    }
+
+    #[test]
+    fn operations_serialize_deterministically() {
+        let alice = ReplicaId::new("alice");
+        let bob = ReplicaId::new("bob");
+        let time = ManualClock::new(5_000);
+
+        // The same clock reached through different increment orders
+        let mut clock_a = VectorClock::new();
+        clock_a.increment(&alice);
+        clock_a.increment(&bob);
+        let mut clock_b = VectorClock::new();
+        clock_b.increment(&bob);
+        clock_b.increment(&alice);
+
+        let opening = WallOpening::new(1.0, 0.0, 0.9, 2.1, OpeningType::Door);
+        let payload = GeometryOperation::SetOpening {
+            wall_id: "w1".to_string(),
+            opening,
+        };
+        let a =
+            Operation::with_clock_source("op-1", payload.clone(), alice.clone(), clock_a, &time);
+        let b = Operation::with_clock_source("op-1", payload, alice, clock_b, &time);
+
+        let json = a.to_json().unwrap();
+        assert_eq!(json, b.to_json().unwrap());
+        assert!(json.contains(r#""kind":"set_opening""#));
+
+        let back = GeometryOp::from_json(&json).unwrap();
+        assert_eq!(back.op_type, a.op_type);
+        assert_eq!(back.to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn typed_log_keeps_dedup_and_tombstones() {
+        let time = ManualClock::new(0);
+        let mut session = Session::new("alice");
+        session.edit("a1", wall("w1", (0.0, 0.0), (3000.0, 0.0)), &time);
+        session.edit(
+            "a2",
+            GeometryOperation::DeleteElement {
+                id: "w1".to_string(),
+                element_type: ElementKind::Wall,
+            },
+            &time,
+        );
+
+        let mut log = OperationLog::new();
+        assert_eq!(log.merge(&session.log), 2);
+        assert_eq!(log.merge(&session.log), 0);
+        assert!(log.is_deleted("w1"));
+        assert_eq!(log.operations_for_element("w1").len(), 2);
+        assert_eq!(GraphReplica::from_log(&log).graph.edge_count(), 0);
+    }
+}
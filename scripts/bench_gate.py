@@ -0,0 +1,129 @@
+#!/usr/bin/env python3
+"""Performance regression gate for the kernel benchmarks.
+
+Compares the means criterion wrote for the last `cargo bench` run against
+the committed baseline, and exits non-zero on a regression.
+
+    cd kernel && cargo bench -p pensaer-geometry --bench kernel
+    python3 scripts/bench_gate.py                  # gate
+    python3 scripts/bench_gate.py --tolerance 0.5  # looser, e.g. on a noisy laptop
+    python3 scripts/bench_gate.py --record         # re-record the baseline
+
+A benchmark fails when its mean exceeds the baseline mean by more than the
+tolerance, or exceeds its published target (`target_ns`), or is missing
+from the run. Benchmarks not in the baseline are reported but not gated;
+record them to start gating.
+
+Re-recording keeps each benchmark's target and the default tolerance, so
+commit the updated baseline.json along with the change that moved the
+numbers, and record on the same kind of quiet machine the gate runs on.
+"""
+
+import argparse
+import json
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+DEFAULT_CRITERION_DIR = ROOT / "kernel" / "target" / "criterion"
+DEFAULT_BASELINE = ROOT / "kernel" / "pensaer-geometry" / "benches" / "baseline.json"
+
+
+def read_results(criterion_dir):
+    """Map "group/benchmark" to the mean time in nanoseconds."""
+    results = {}
+    for estimates in sorted(criterion_dir.glob("*/*/new/estimates.json")):
+        bench_dir = estimates.parent.parent
+        name = f"{bench_dir.parent.name}/{bench_dir.name}"
+        with estimates.open() as f:
+            results[name] = json.load(f)["mean"]["point_estimate"]
+    return results
+
+
+def format_ns(ns):
+    for unit, scale in (("s", 1e9), ("ms", 1e6), ("µs", 1e3)):
+        if ns >= scale:
+            return f"{ns / scale:.2f} {unit}"
+    return f"{ns:.0f} ns"
+
+
+def gate(baseline, results, tolerance):
+    """Print a comparison table and return the number of failures."""
+    failures = 0
+    for name, entry in sorted(baseline["benchmarks"].items()):
+        mean = results.get(name)
+        if mean is None:
+            print(f"FAIL  {name}: missing from the benchmark run")
+            failures += 1
+            continue
+
+        problems = []
+        limit = entry["mean_ns"] * (1.0 + tolerance)
+        if mean > limit:
+            change = mean / entry["mean_ns"] - 1.0
+            problems.append(f"{change:+.0%} over baseline {format_ns(entry['mean_ns'])}")
+        target = entry.get("target_ns")
+        if target is not None and mean > target:
+            problems.append(f"over target {format_ns(target)}")
+
+        status = "FAIL" if problems else "ok  "
+        detail = "; ".join(problems) if problems else f"baseline {format_ns(entry['mean_ns'])}"
+        print(f"{status}  {name}: {format_ns(mean)} ({detail})")
+        failures += bool(problems)
+
+    for name in sorted(set(results) - set(baseline["benchmarks"])):
+        print(f"new   {name}: {format_ns(results[name])} (not in baseline)")
+    return failures
+
+
+def record(baseline, results):
+    benchmarks = {}
+    for name, mean in sorted(results.items()):
+        entry = {"mean_ns": round(mean, 1)}
+        target = baseline["benchmarks"].get(name, {}).get("target_ns")
+        if target is not None:
+            entry["target_ns"] = target
+        benchmarks[name] = entry
+    baseline["benchmarks"] = benchmarks
+    return baseline
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--criterion-dir", type=Path, default=DEFAULT_CRITERION_DIR)
+    parser.add_argument("--baseline", type=Path, default=DEFAULT_BASELINE)
+    parser.add_argument(
+        "--tolerance",
+        type=float,
+        help="allowed slowdown as a fraction (default: the baseline's)",
+    )
+    parser.add_argument("--record", action="store_true", help="overwrite the baseline")
+    args = parser.parse_args()
+
+    results = read_results(args.criterion_dir)
+    if not results:
+        print(f"no benchmark results under {args.criterion_dir}; run cargo bench first")
+        return 2
+
+    with args.baseline.open() as f:
+        baseline = json.load(f)
+
+    if args.record:
+        record(baseline, results)
+        with args.baseline.open("w") as f:
+            json.dump(baseline, f, indent=2, sort_keys=True)
+            f.write("\n")
+        print(f"recorded {len(results)} benchmarks in {args.baseline}")
+        return 0
+
+    tolerance = args.tolerance if args.tolerance is not None else baseline["tolerance"]
+    failures = gate(baseline, results, tolerance)
+    if failures:
+        print(f"{failures} benchmark(s) regressed (tolerance {tolerance:.0%})")
+        return 1
+    print(f"no regressions (tolerance {tolerance:.0%})")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,70 @@
+"""Tests for parallel batch mesh generation.
+
+These tests verify that:
+1. generate_building_mesh merges every element with a triangle range each
+2. The merged mesh matches meshing each element one by one
+3. merge=False returns one mesh per element
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+if not hasattr(pg, "generate_building_mesh"):
+    pytest.skip("built without the parallel feature", allow_module_level=True)
+
+
+def house():
+    """Four walls with a door and a window, a floor, and a roof."""
+    walls = pg.create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
+    door = pg.place_door(walls[0], offset=2.0, width=0.9, height=2.1)["door"]
+    window = pg.place_window(walls[1], offset=4.0, width=1.2, height=1.2, sill_height=0.9)["window"]
+    floor = pg.create_floor((0, 0), (10, 8), thickness=0.3)
+    roof = pg.create_roof((0, 0), (10, 8), 0.25, roof_type="gable")
+    return walls, [floor], [roof], [door], [window]
+
+
+class TestBatchMesh:
+    """Test generate_building_mesh."""
+
+    def test_merged_ranges(self):
+        """Each element gets a triangle range, and the ranges tile the mesh."""
+        walls, floors, roofs, doors, windows = house()
+
+        result = pg.generate_building_mesh(walls, floors, roofs, doors, windows)
+
+        mesh = result["mesh"]
+        ranges = sorted(result["ranges"].values())
+        assert len(ranges) == 8
+        assert ranges[0][0] == 0
+        assert all(a[1] == b[0] for a, b in zip(ranges, ranges[1:]))
+        assert ranges[-1][1] == mesh.triangle_count()
+        assert result["errors"] == {}
+
+    def test_matches_sequential(self):
+        """Per-element ranges hold exactly that element's triangles."""
+        walls, floors, roofs, doors, windows = house()
+
+        result = pg.generate_building_mesh(walls, floors, roofs, doors, windows)
+
+        ranges = result["ranges"]
+        for wall in walls:
+            start, end = ranges[wall.id]
+            assert end - start == wall.to_mesh().triangle_count()
+        start, end = ranges[doors[0].id]
+        assert end - start == doors[0].to_mesh(walls[0]).triangle_count()
+        expected = sum(w.to_mesh().surface_area() for w in walls)
+        expected += floors[0].to_mesh().surface_area() + roofs[0].to_mesh().surface_area()
+        expected += doors[0].to_mesh(walls[0]).surface_area()
+        expected += windows[0].to_mesh(walls[1]).surface_area()
+        assert result["mesh"].surface_area() == pytest.approx(expected)
+
+    def test_unmerged(self):
+        """Without merging, each element maps to its own mesh."""
+        walls, floors, _, _, _ = house()
+
+        result = pg.generate_building_mesh(walls, floors, merge=False)
+
+        meshes = result["meshes"]
+        assert set(meshes) == {w.id for w in walls} | {floors[0].id}
+        assert meshes[floors[0].id].triangle_count() == floors[0].to_mesh().triangle_count()
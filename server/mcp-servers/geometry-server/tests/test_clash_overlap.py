@@ -0,0 +1,53 @@
+"""Tests for the overlap box reported by clash detection.
+
+These tests verify that:
+1. Hard clashes report the intersection of the two bounding boxes
+2. The overlap volume matches the overlap box
+3. Clearance clashes have no overlap box
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+def wall_element(wall):
+    box = wall.bounding_box()
+    return (
+        str(wall.id),
+        "wall",
+        (box.min.x, box.min.y, box.min.z),
+        (box.max.x, box.max.y, box.max.z),
+    )
+
+
+class TestClashOverlap:
+    """Test overlap boxes on detected clashes."""
+
+    def test_overlapping_walls(self):
+        """Two crossing walls overlap in a 0.2 x 0.2 x 3.0 box."""
+        a = pg.create_wall((0, 0.1), (4, 0.1), 3.0, 0.2)
+        b = pg.create_wall((3.6, -1), (3.6, 2), 3.0, 0.2)
+
+        clashes = pg.detect_clashes([wall_element(a), wall_element(b)])
+
+        assert len(clashes) == 1
+        clash = clashes[0]
+        assert clash["clash_type"] == "Hard Clash"
+        (x0, y0, z0), (x1, y1, z1) = clash["overlap_min"], clash["overlap_max"]
+        assert x1 - x0 == pytest.approx(0.2)
+        assert y1 - y0 == pytest.approx(0.2)
+        assert z1 - z0 == pytest.approx(3.0)
+        assert clash["overlap_volume"] == pytest.approx(0.12)
+
+    def test_clearance_has_no_overlap(self):
+        """A clearance violation reports no overlap box."""
+        a = pg.create_wall((0, 0.1), (4, 0.1), 3.0, 0.2)
+        b = pg.create_wall((0, 0.6), (4, 0.6), 3.0, 0.2)
+
+        clashes = pg.detect_clashes([wall_element(a), wall_element(b)], clearance=0.5)
+
+        assert len(clashes) == 1
+        assert clashes[0]["clash_type"] == "Clearance Violation"
+        assert clashes[0]["overlap_min"] is None
+        assert clashes[0]["overlap_max"] is None
@@ -0,0 +1,48 @@
+"""Tests for deterministic element IDs.
+
+These tests verify that:
+1. Two runs under the same namespace give byte-identical model JSON
+2. Different namespaces give different IDs
+3. Passing None switches back to random IDs
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+def _simple_model_json():
+    building = pg.create_simple_building((0, 0), (10, 8), 3.0, 0.2, 0.3, "Hall", "001")
+    return pg.save_model(
+        walls=building["walls"],
+        floors=[building["floor"]],
+        rooms=[building["room"]],
+        joins=building["joins"],
+    )
+
+
+class TestDeterministicIds:
+    """set_deterministic_ids and reproducible model output."""
+
+    def teardown_method(self):
+        pg.set_deterministic_ids(None)
+
+    def test_same_namespace_gives_identical_json(self):
+        """Re-running a script under one namespace reproduces the JSON."""
+        pg.set_deterministic_ids("pipeline")
+        first = _simple_model_json()
+        pg.set_deterministic_ids("pipeline")
+        assert _simple_model_json() == first
+
+    def test_namespaces_differ(self):
+        """The namespace changes the derived IDs."""
+        pg.set_deterministic_ids("a")
+        first = pg.create_wall((0, 0), (5, 0), 3.0, 0.2).id
+        pg.set_deterministic_ids("b")
+        assert pg.create_wall((0, 0), (5, 0), 3.0, 0.2).id != first
+
+    def test_none_restores_random_ids(self):
+        """Without a namespace, identical walls get distinct IDs."""
+        pg.set_deterministic_ids(None)
+        first = pg.create_wall((0, 0), (5, 0), 3.0, 0.2).id
+        assert pg.create_wall((0, 0), (5, 0), 3.0, 0.2).id != first
@@ -0,0 +1,92 @@
+"""Tests for door and window type catalogs.
+
+These tests verify that:
+1. default_catalog ships the standard door and window sizes
+2. place_door and place_window take their size from a catalog type
+3. Dimensions and a type_name cannot be mixed, and unknown types are rejected
+4. Editing a type and applying updates resizes the instances in a building
+5. Catalogs round-trip through JSON
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestCatalog:
+    """Test catalog-driven door and window placement."""
+
+    def test_default_catalog_sizes(self):
+        """The default catalog has 762, 838, and 926 mm doors."""
+        catalog = pg.default_catalog()
+
+        assert catalog.door_types == ["Door 762x1981", "Door 838x1981", "Door 926x2040"]
+        assert len(catalog.window_types) == 3
+
+    def test_place_from_type(self):
+        """Typed placement sizes the element and its opening from the type."""
+        catalog = pg.default_catalog()
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+
+        result = pg.place_door(
+            wall, offset=1.0, type_name="Door 926x2040", catalog=catalog, opening_side="right"
+        )
+        window = pg.place_window(
+            wall, offset=3.5, type_name="Window 1200x1050", catalog=catalog
+        )["window"]
+
+        door = result["door"]
+        assert door.width == pytest.approx(0.926)
+        assert door.height == pytest.approx(2.04)
+        assert door.type_name == "Door 926x2040"
+        assert door.opening_side == "right"
+        assert result["opening"].width == pytest.approx(0.926)
+        assert window.sill_height == pytest.approx(0.9)
+        assert window.window_type == "casement"
+        assert len(wall.openings) == 2
+
+    def test_bad_type_arguments(self):
+        """Mixed, catalog-less, unknown, and missing sizes all raise."""
+        catalog = pg.default_catalog()
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+
+        with pytest.raises(ValueError):
+            pg.place_door(wall, offset=1.0, width=0.9, type_name="Door 838x1981", catalog=catalog)
+        with pytest.raises(ValueError):
+            pg.place_door(wall, offset=1.0, type_name="Door 838x1981")
+        with pytest.raises(ValueError):
+            pg.place_window(wall, offset=1.0, type_name="Bay", catalog=catalog)
+        with pytest.raises(ValueError):
+            pg.place_door(wall, offset=1.0, width=0.9)
+        assert wall.openings == []
+
+    def test_apply_updates_resizes_instances(self):
+        """Widening a type widens its doors in the building."""
+        catalog = pg.default_catalog()
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        door = pg.place_door(wall, offset=2.0, type_name="Door 838x1981", catalog=catalog)["door"]
+        building = pg.Building("Test")
+        building.add_wall(wall)
+        building.add_door(door)
+
+        assert catalog.apply_updates(building) == 0
+        catalog.add_door_type("Door 838x1981", 1.0, 2.1, swing="right")
+        assert catalog.apply_updates(building) == 1
+
+        box = building.element_mesh(door.id).bounding_box()
+        assert box.max.x - box.min.x == pytest.approx(1.0)
+        assert box.max.z == pytest.approx(2.1)
+
+    def test_json_round_trip(self):
+        """A catalog saved to JSON loads back with the same types."""
+        catalog = pg.load_catalog(
+            '{"doors": [{"name": "D1", "width": 0.9, "height": 2.1, "swing": "Right"}]}'
+        )
+        catalog.add_window_type("W1", 1.2, 1.0, 0.9, window_type="awning")
+
+        loaded = pg.load_catalog(catalog.to_json())
+
+        assert loaded.door_types == ["D1"]
+        assert loaded.window_types == ["W1"]
+        with pytest.raises(ValueError):
+            pg.load_catalog('{"doors": [{"name": "D1", "width": -1, "height": 2.1}]}')
@@ -0,0 +1,76 @@
+"""Tests for door swing symbols in plan.
+
+These tests verify that:
+1. A left-swing door hinges on the jamb nearer the wall start
+2. A right-swing door opening to the right hinges on the far jamb
+3. Double doors give two leaves and sliding doors none
+4. swing_arc gives the quarter circle as a single polyline
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestDoorSwing:
+    """Test door.swing_geometry against the host wall."""
+
+    def test_left_swing(self):
+        """The hinge sits at offset - width/2 and the leaf opens to the left."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        door = pg.place_door(wall, offset=2.0, width=0.9, height=2.1)["door"]
+
+        [(hinge, leaf_end, arc)] = door.swing_geometry(wall, segments=8)
+
+        assert hinge == pytest.approx((1.55, 0.0))
+        assert leaf_end == pytest.approx((1.55, 0.9))
+        assert len(arc) == 9
+        assert arc[0] == pytest.approx((2.45, 0.0))
+        assert arc[-1] == pytest.approx(leaf_end)
+
+    def test_right_swing_to_the_right(self):
+        """The hinge sits at offset + width/2 and the leaf opens to the right."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        door = pg.place_door(
+            wall, offset=2.0, width=0.9, height=2.1, swing="right", opening_side="right"
+        )["door"]
+
+        [(hinge, leaf_end, _)] = door.swing_geometry(wall)
+
+        assert door.opening_side == "right"
+        assert hinge == pytest.approx((2.45, 0.0))
+        assert leaf_end == pytest.approx((2.45, -0.9))
+
+    def test_double_and_sliding(self):
+        """Double doors have a half-width leaf per jamb; no swing, no leaves."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        double = pg.place_door(wall, offset=1.0, width=1.6, height=2.1, swing="both")["door"]
+        sliding = pg.place_door(wall, offset=3.5, width=0.9, height=2.1, swing="none")["door"]
+
+        leaves = double.swing_geometry(wall)
+
+        assert [leaf[0] for leaf in leaves] == [pytest.approx((0.2, 0.0)), pytest.approx((1.8, 0.0))]
+        assert leaves[0][1] == pytest.approx((0.2, 0.8))
+        assert sliding.swing_geometry(wall) == []
+
+    def test_swing_arc(self):
+        """The arc keeps the door width from the hinge and ends perpendicular."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        door = pg.place_door(wall, offset=2.0, width=0.9, height=2.1)["door"]
+        sliding = pg.place_door(wall, offset=3.8, width=0.9, height=2.1, swing="none")["door"]
+
+        arc = door.swing_arc(wall)
+
+        assert arc[0] == pytest.approx((2.45, 0.0))
+        assert arc[-1] == pytest.approx((1.55, 0.9))
+        for x, y in arc:
+            assert ((x - 1.55) ** 2 + y**2) ** 0.5 == pytest.approx(0.9)
+        assert sliding.swing_arc(wall) == []
+
+    def test_bad_opening_side_leaves_wall_untouched(self):
+        """An unknown opening side raises before the opening is cut."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+
+        with pytest.raises(ValueError):
+            pg.place_door(wall, offset=2.0, width=0.9, height=2.1, opening_side="up")
+        assert wall.openings == []
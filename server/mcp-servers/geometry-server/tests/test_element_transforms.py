@@ -0,0 +1,66 @@
+"""Tests for moving, rotating, and mirroring elements from Python.
+
+These tests verify that:
+1. Transforms update elements in place and report their IDs
+2. Mirroring a wall flips its hosted door's offset and swing
+3. Unsupported objects and missing host walls are rejected
+"""
+
+import math
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestElementTransforms:
+    """Test move_elements, rotate_elements, and mirror_elements."""
+
+    def test_move_reports_modified_ids(self):
+        """Every moved element is listed as modified."""
+        walls = pg.create_rectangular_walls((0, 0), (10, 8), 3.0, 0.2)
+        floor = pg.create_floor((0, 0), (10, 8), 0.3)
+
+        result = pg.move_elements(walls + [floor], (5.0, 0.0))
+
+        assert result["modified"] == [w.id for w in walls] + [floor.id]
+        assert walls[0].start.x == pytest.approx(5.0)
+        assert floor.area() == pytest.approx(80.0)
+
+    def test_rotate_keeps_wall_length(self):
+        """Rotating a wall turns its baseline without resizing it."""
+        wall = pg.create_wall((0, 0), (4, 0), 3.0, 0.2)
+
+        pg.rotate_elements([wall], (0, 0), math.pi / 2)
+
+        assert wall.end.x == pytest.approx(0.0)
+        assert wall.end.y == pytest.approx(4.0)
+        assert wall.length() == pytest.approx(4.0)
+
+    def test_mirror_flips_door_swing(self):
+        """A left-swing door becomes a right-swing door at the mirrored offset."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        door = pg.place_door(wall, 1.0, 0.9, 2.1, swing="left")["door"]
+
+        result = pg.mirror_elements([wall, door], (0, 0), (0, 1))
+
+        assert door.swing == "right"
+        assert door.offset_along_wall == pytest.approx(4.0)
+        assert result["modified"] == [wall.id, door.id]
+
+    def test_mirror_requires_host_wall(self):
+        """Mirroring a door without its wall raises ValueError."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        door = pg.place_door(wall, 1.0, 0.9, 2.1)["door"]
+
+        with pytest.raises(ValueError):
+            pg.mirror_elements([door], (0, 0), (0, 1))
+        assert door.swing == "left"
+
+    def test_unsupported_object_rejected(self):
+        """Objects that are not elements raise TypeError."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+
+        with pytest.raises(TypeError):
+            pg.move_elements([wall, "not an element"], (1.0, 0.0))
+        assert wall.start.x == pytest.approx(0.0)
@@ -0,0 +1,54 @@
+"""Tests for generating floor slabs from the rooms enclosed by walls.
+
+These tests verify that:
+1. Each enclosed room gets its own floor, and the floors tile the building
+2. room_floors maps every room ID to the ID of its floor
+3. Walls that enclose no room yield no floors, and bad thickness raises
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestFloorsFromWalls:
+    """Test per-room floor generation from walls."""
+
+    def test_two_rooms_tile_the_interior(self):
+        """A partitioned rectangle yields two floors summing to its area."""
+        walls = pg.create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
+        walls.append(pg.create_wall((4, 0), (4, 8), 3.0, 0.1))
+
+        result = pg.create_floors_from_walls(walls, 0.25)
+
+        floors = result["floors"]
+        assert len(floors) == 2
+        assert sorted(f.area() for f in floors) == [pytest.approx(32.0), pytest.approx(48.0)]
+        assert sum(f.area() for f in floors) == pytest.approx(80.0)
+        assert all(f.base_elevation == 0.0 for f in floors)
+        assert result["warnings"] == []
+
+    def test_room_mapping(self):
+        """Each detected room maps to a distinct generated floor."""
+        walls = pg.create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
+        walls.append(pg.create_wall((4, 0), (4, 8), 3.0, 0.1))
+
+        result = pg.create_floors_from_walls(walls, 0.25)
+
+        mapping = result["room_floors"]
+        assert len(mapping) == 2
+        assert sorted(mapping.values()) == sorted(f.id for f in result["floors"])
+
+    def test_open_walls_and_bad_thickness(self):
+        """An open chain has no rooms; non-positive thickness raises."""
+        walls = [
+            pg.create_wall((0, 0), (5, 0), 3.0, 0.2),
+            pg.create_wall((5, 0), (5, 4), 3.0, 0.2),
+        ]
+
+        result = pg.create_floors_from_walls(walls, 0.25)
+
+        assert result["floors"] == []
+        assert result["room_floors"] == {}
+        with pytest.raises(ValueError):
+            pg.create_floors_from_walls(walls, 0.0)
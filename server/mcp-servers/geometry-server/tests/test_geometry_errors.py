@@ -0,0 +1,39 @@
+"""Tests for structured kernel errors.
+
+These tests verify that:
+1. Kernel errors raise PensaerGeometryError, still a ValueError
+2. Errors carry a stable code and the offending values in details
+3. Messages stay human-readable
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestGeometryErrors:
+    """PensaerGeometryError codes and details."""
+
+    def test_non_positive_thickness(self):
+        """A zero-thickness floor reports the thickness it was given."""
+        with pytest.raises(pg.PensaerGeometryError) as info:
+            pg.create_floor((0, 0), (5, 5), 0.0)
+        assert info.value.code == "non_positive_thickness"
+        assert info.value.details == {"value": 0.0}
+        assert "thickness must be positive" in str(info.value)
+
+    def test_invalid_floor_bounds(self):
+        """Inverted floor bounds report both corners."""
+        with pytest.raises(ValueError) as info:
+            pg.create_floor((5, 0), (0, 5), 0.3)
+        assert info.value.code == "invalid_floor_bounds"
+        assert info.value.details == {"min": [5.0, 0.0], "max": [0.0, 5.0]}
+
+    def test_opening_out_of_bounds(self):
+        """An opening past the wall end reports its offset, width, and the wall length."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        opening = pg.WallOpening(5.0, 0.0, 1.0, 2.0, "window")
+        with pytest.raises(pg.PensaerGeometryError) as info:
+            wall.add_opening(opening)
+        assert info.value.code == "opening_out_of_bounds"
+        assert info.value.details == {"offset": 5.0, "width": 1.0, "wall_length": 5.0}
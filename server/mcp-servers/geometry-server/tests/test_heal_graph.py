@@ -0,0 +1,40 @@
+"""Tests for running the healing passes from Python.
+
+These tests verify that:
+1. Each healing pass reports its own count
+2. Crossing walls are split and enclose rooms
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestHealGraph:
+    """Test heal_graph on small wall layouts."""
+
+    def test_crossing_walls_report_one_split(self):
+        """Two walls crossing in an X are split once at the crossing."""
+        walls = [
+            pg.create_wall((0, 2), (4, 2), 3.0, 0.2),
+            pg.create_wall((2, 0), (2, 4), 3.0, 0.2),
+        ]
+
+        result = pg.heal_graph(walls)
+
+        assert result["split_crossings"] == 1
+        assert result["merged_nodes"] == 0
+        assert result["node_count"] == 5
+        assert result["edge_count"] == 4
+        assert result["rooms"] == []
+
+    def test_rectangle_with_cross_wall_has_two_rooms(self):
+        """A wall crossing a rectangle divides it into two rooms."""
+        walls = pg.create_rectangular_walls((0, 0), (10, 8), 3.0, 0.2)
+        walls.append(pg.create_wall((5, -1), (5, 9), 3.0, 0.2))
+
+        result = pg.heal_graph(walls)
+
+        assert result["split_crossings"] == 2
+        assert len(result["rooms"]) == 2
+        assert sorted(r["area"] for r in result["rooms"]) == pytest.approx([40.0, 40.0])
@@ -0,0 +1,101 @@
+"""Tests for door and window meshes placed in their host walls.
+
+These tests verify that:
+1. door.to_mesh(wall) fills the door opening in world space
+2. window.to_mesh(wall) starts at the sill and stays within the wall thickness
+3. Meshing against the wrong wall is rejected
+4. building_meshes returns each wall followed by its hosted elements
+5. building_meshes cuts the openings out of the wall meshes
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestHostedMeshes:
+    """Test door and window meshing against their host walls."""
+
+    def test_door_fills_its_opening(self):
+        """The door's box spans the opening width, height, and wall thickness."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        door = pg.place_door(wall, offset=2.5, width=0.9, height=2.1)["door"]
+
+        mesh = door.to_mesh(wall)
+        box = door.bounding_box(wall)
+
+        assert mesh.is_valid()
+        assert box.min.x == pytest.approx(2.05)
+        assert box.max.x == pytest.approx(2.95)
+        assert box.min.y == pytest.approx(-0.1)
+        assert box.max.y == pytest.approx(0.1)
+        assert box.min.z == pytest.approx(0.0)
+        assert box.max.z == pytest.approx(2.1)
+
+    def test_window_sits_at_sill(self):
+        """The window starts at its sill height along a vertical wall."""
+        wall = pg.create_wall((1, 0), (1, 4), 3.0, 0.3)
+        window = pg.place_window(wall, offset=1.0, width=1.2, height=1.0, sill_height=0.9)[
+            "window"
+        ]
+
+        box = window.bounding_box(wall)
+
+        assert window.frame_thickness == pytest.approx(0.05)
+        assert box.min.x == pytest.approx(0.85)
+        assert box.max.x == pytest.approx(1.15)
+        assert box.min.y == pytest.approx(0.4)
+        assert box.max.y == pytest.approx(1.6)
+        assert box.min.z == pytest.approx(0.9)
+        assert box.max.z == pytest.approx(1.9)
+
+    def test_wrong_host_is_rejected(self):
+        """A door can only be meshed against the wall that hosts it."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        other = pg.create_wall((0, 2), (5, 2), 3.0, 0.2)
+        door = pg.place_door(wall, offset=2.5, width=0.9, height=2.1)["door"]
+
+        with pytest.raises(ValueError):
+            door.to_mesh(other)
+
+    def test_building_meshes_names_hosted_elements(self):
+        """Walls come first, each followed by its doors and then windows."""
+        a = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        b = pg.create_wall((5, 0), (5, 4), 3.0, 0.2)
+        door = pg.place_door(b, offset=2.0, width=0.9, height=2.1)["door"]
+        window = pg.place_window(a, offset=2.5, width=1.2, height=1.0, sill_height=0.9)[
+            "window"
+        ]
+
+        meshes = pg.building_meshes([a, b], doors=[door], windows=[window])
+
+        assert [m["name"] for m in meshes] == [
+            "wall_0",
+            "wall_0/window_0",
+            "wall_1",
+            "wall_1/door_0",
+        ]
+        assert meshes[1]["element_id"] == window.id
+        assert meshes[1]["host_wall_id"] == a.id
+        assert meshes[0]["host_wall_id"] is None
+        assert meshes[3]["element_type"] == "door"
+        assert all(m["mesh"].is_valid() for m in meshes)
+
+        with pytest.raises(ValueError):
+            pg.building_meshes([a], doors=[door])
+
+    def test_building_meshes_cut_wall_openings(self):
+        """A ray through a door opening passes the wall and hits only the door."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        door = pg.place_door(wall, offset=2.5, width=0.9, height=2.1)["door"]
+
+        meshes = pg.building_meshes([wall], doors=[door])
+        wall_mesh = meshes[0]["mesh"]
+        tagged = [(m["element_id"], m["mesh"]) for m in meshes]
+
+        assert pg.pick([(wall.id, wall_mesh)], (2.5, -1.0, 1.0), (0, 1, 0)) is None
+        hit = pg.pick(tagged, (2.5, -1.0, 1.0), (0, 1, 0))
+        assert hit["element_id"] == door.id
+
+        above = pg.pick(tagged, (2.5, -1.0, 2.5), (0, 1, 0))
+        assert above["element_id"] == wall.id
@@ -0,0 +1,53 @@
+"""Tests for openings near wall joins.
+
+These tests verify that:
+1. A door reaching into a corner's trimmed wall end makes the join fail
+2. With heal_openings, the door is reported shrunk away from the corner
+3. Openings clear of the corner leave the join untouched
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+def corner_with_door(offset):
+    """An L corner at (5, 0) with a 0.9 m door on the first wall."""
+    a = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+    b = pg.create_wall((5, 0), (5, 4), 3.0, 0.2)
+    door = pg.place_door(a, offset=offset, width=0.9, height=2.1)["door"]
+    [join] = pg.detect_joins([a, b])
+    return a, b, door, join
+
+
+class TestJoinOpenings:
+    """Test compute_join_geometry against hosted openings."""
+
+    def test_door_at_corner_fails(self):
+        """A door 50 mm from the corner overlaps the 100 mm miter."""
+        a, b, _, join = corner_with_door(4.5)
+
+        with pytest.raises(RuntimeError, match="0.050"):
+            pg.compute_join_geometry([a, b], join)
+
+    def test_door_at_corner_heals(self):
+        """Healing narrows the door so its jamb clears the miter."""
+        a, b, _, join = corner_with_door(4.5)
+
+        geometry = pg.compute_join_geometry([a, b], join, heal_openings=True)
+
+        [adjustment] = geometry["opening_adjustments"]
+        assert adjustment["wall_id"] == a.id
+        assert adjustment["opening_id"] == a.openings[0].id
+        assert adjustment["shrink"] == pytest.approx(0.05)
+        assert adjustment["width"] == pytest.approx(0.85)
+        assert adjustment["offset_along_wall"] == pytest.approx(4.475)
+
+    def test_clear_door_untouched(self):
+        """A door well away from the corner needs no adjustment."""
+        a, b, _, join = corner_with_door(2.0)
+
+        geometry = pg.compute_join_geometry([a, b], join)
+
+        assert geometry["opening_adjustments"] == []
+        assert geometry["profile_count"] == 2
@@ -0,0 +1,57 @@
+"""Tests for mansard roofs.
+
+These tests verify that:
+1. create_roof(roof_type="mansard") meshes a closed two-slope roof
+2. The mansard stands taller than a flat roof and lower than a single steep slope
+3. Invalid break insets are rejected
+"""
+
+import math
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestMansardRoof:
+    """Test mansard roof creation and meshing."""
+
+    def test_mansard_mesh(self):
+        """The mesh is a closed solid with a break line on every side."""
+        roof = pg.create_roof(
+            (0, 0), (10, 8), 0.3, roof_type="mansard", lower_slope=70, break_inset=1.2
+        )
+
+        mesh = roof.to_mesh()
+
+        assert roof.roof_type == "mansard"
+        assert roof.lower_slope_degrees == pytest.approx(70.0)
+        assert roof.break_inset == pytest.approx(1.2)
+        assert pg.validate_mesh(mesh)["valid"]
+        assert mesh.vertex_count() == 20
+        assert mesh.triangle_count() == 36
+
+    def test_mansard_height(self):
+        """A mansard is taller than flat and lower than a gable at its lower pitch."""
+        def height(roof):
+            box = roof.to_mesh().bounding_box()
+            return box.max.z - box.min.z
+
+        mansard = pg.create_roof((0, 0), (10, 8), 0.3, roof_type="mansard", break_inset=1.2)
+        flat = pg.create_roof((0, 0), (10, 8), 0.3)
+        gable = pg.create_roof((0, 0), (10, 8), 0.3, roof_type="gable", slope_degrees=70)
+
+        assert height(flat) < height(mansard) < height(gable)
+        # The slab hangs below the eaves by the lower slope's depth
+        eave_depth = 0.3 / math.cos(math.radians(70))
+        assert mansard.to_dict()["ridge_height"] == pytest.approx(height(mansard) - eave_depth)
+
+    def test_invalid_break_inset(self):
+        """Non-positive insets raise, and an inset past the ridge fails to mesh."""
+        with pytest.raises(ValueError):
+            pg.create_roof((0, 0), (10, 8), 0.3, roof_type="mansard", break_inset=0.0)
+
+        roof = pg.create_roof((0, 0), (10, 8), 0.3, roof_type="mansard")
+        roof.set_break_inset(4.5)
+        with pytest.raises(RuntimeError):
+            roof.to_mesh()
@@ -0,0 +1,76 @@
+"""Tests for numpy mesh buffers.
+
+These tests verify that:
+1. vertices_array, normals_array, and indices_array match the tuple API
+2. The arrays are read-only contiguous views over one Rust allocation
+3. from_arrays round-trips a mesh and rejects bad shapes and indices
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+np = pytest.importorskip("numpy")
+
+
+def _mesh():
+    return pg.create_floor((0, 0), (10, 8), 0.3).to_mesh()
+
+
+class TestMeshArrays:
+    """TriangleMesh numpy accessors and from_arrays."""
+
+    def test_arrays_match_tuples(self):
+        """Each array holds the same values as its tuple method."""
+        mesh = _mesh()
+        vertices = mesh.vertices_array()
+        normals = mesh.normals_array()
+        indices = mesh.indices_array()
+
+        assert vertices.dtype == np.float64
+        assert vertices.shape == (mesh.vertex_count(), 3)
+        assert vertices.tolist() == [list(v) for v in mesh.vertices()]
+        assert normals.dtype == np.float64
+        assert normals.tolist() == [list(n) for n in mesh.normals()]
+        assert indices.dtype == np.uint32
+        assert indices.shape == (mesh.triangle_count(), 3)
+        assert indices.tolist() == [list(t) for t in mesh.indices()]
+
+    def test_arrays_share_one_buffer(self):
+        """The array wraps the kernel's buffer rather than copying rows."""
+        vertices = _mesh().vertices_array()
+
+        assert vertices.flags.c_contiguous
+        assert not vertices.flags.owndata
+        assert not vertices.flags.writeable
+        with pytest.raises(ValueError):
+            vertices[0, 0] = 1.0
+        # Still valid after the mesh is gone
+        assert tuple(vertices[0].tolist()) == _mesh().vertices()[0]
+
+    def test_from_arrays_round_trip(self):
+        """A mesh rebuilt from its arrays is the same mesh."""
+        mesh = _mesh()
+        copy = pg.TriangleMesh.from_arrays(
+            mesh.vertices_array().tolist(),
+            mesh.indices_array().astype(np.int64),
+            normals=mesh.normals_array(),
+        )
+
+        assert copy.vertices() == mesh.vertices()
+        assert copy.indices() == mesh.indices()
+        assert copy.normals() == mesh.normals()
+        assert pg.TriangleMesh.from_arrays(
+            mesh.vertices_array(), mesh.indices_array()
+        ).normals() == []
+
+    def test_from_arrays_rejects_bad_input(self):
+        """Wrong shapes and out-of-range indices raise ValueError."""
+        vertices = np.zeros((3, 3))
+
+        with pytest.raises(ValueError, match=r"\(n, 3\)"):
+            pg.TriangleMesh.from_arrays(np.zeros((3, 2)), [[0, 1, 2]])
+        with pytest.raises(ValueError, match="normals"):
+            pg.TriangleMesh.from_arrays(vertices, [[0, 1, 2]], normals=np.zeros((2, 3)))
+        with pytest.raises(pg.PensaerGeometryError) as excinfo:
+            pg.TriangleMesh.from_arrays(vertices, [[0, 1, 3]])
+        assert excinfo.value.code == "invalid_mesh_indices"
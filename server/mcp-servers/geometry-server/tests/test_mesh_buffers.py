@@ -0,0 +1,29 @@
+"""Tests for the flat mesh buffer accessors.
+
+These tests verify that:
+1. Flat buffers have three entries per vertex, normal, and triangle
+2. Flat buffers hold the same values as the tuple accessors
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestMeshBuffers:
+    """Test vertices_flat, normals_flat, and indices_flat."""
+
+    def test_flat_buffer_lengths(self):
+        """Each flat buffer has three values per element."""
+        mesh = pg.create_wall((0, 0), (5, 0), 3.0, 0.2).to_mesh()
+
+        assert len(mesh.vertices_flat()) == 3 * mesh.vertex_count()
+        assert len(mesh.normals_flat()) == 3 * len(mesh.normals())
+        assert len(mesh.indices_flat()) == 3 * mesh.triangle_count()
+
+    def test_flat_buffers_match_tuples(self):
+        """Flat buffers list the tuple values in order."""
+        mesh = pg.create_wall((0, 0), (5, 0), 3.0, 0.2).to_mesh()
+
+        assert mesh.vertices_flat() == [c for v in mesh.vertices() for c in v]
+        assert mesh.indices_flat() == [i for t in mesh.indices() for i in t]
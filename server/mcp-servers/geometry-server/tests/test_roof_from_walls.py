@@ -0,0 +1,59 @@
+"""Tests for fitting roofs to walls.
+
+These tests verify that:
+1. A roof fitted to four rectangular walls matches create_roof on the same rectangle
+2. An L-shaped plan gives an L-shaped boundary with the plan's area
+3. Gable roofs follow non-rectangular plans; hip roofs are flagged as meshed over the bounding box
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestRoofFromWalls:
+    """Test create_roof_from_walls."""
+
+    def test_rectangle_matches_create_roof(self):
+        """Four walls round a rectangle give the same roof as its corners."""
+        walls = pg.create_rectangular_walls((0, 0), (10, 8), height=3.0, thickness=0.2)
+
+        result = pg.create_roof_from_walls(walls, 0.25, roof_type="gable")
+        roof = result["roof"]
+        expected = pg.create_roof((0, 0), (10, 8), 0.25, roof_type="gable")
+        expected.set_elevation(3.0)
+
+        assert not result["uses_bounding_box"]
+        assert roof.footprint_area() == pytest.approx(80.0)
+        assert roof.base_elevation == pytest.approx(3.0)
+        assert len(roof.attached_wall_ids()) == 4
+        mesh, expected_mesh = roof.to_mesh(), expected.to_mesh()
+        assert mesh.vertex_count() == expected_mesh.vertex_count()
+        assert mesh.surface_area() == pytest.approx(expected_mesh.surface_area())
+
+    def test_l_shaped_plan(self):
+        """The boundary of an L-shaped plan has the plan's area."""
+        corners = [(0, 0), (10, 0), (10, 4), (4, 4), (4, 8), (0, 8)]
+        walls = [
+            pg.create_wall(corners[i], corners[(i + 1) % 6], 3.0, 0.2) for i in range(6)
+        ]
+
+        flat = pg.create_roof_from_walls(walls, 0.25, eave_overhang=0.0)
+        gable = pg.create_roof_from_walls(walls, 0.25, roof_type="gable")
+        hip = pg.create_roof_from_walls(walls, 0.25, roof_type="hip")
+
+        assert flat["roof"].footprint_area() == pytest.approx(56.0)
+        assert not flat["uses_bounding_box"]
+        assert not gable["uses_bounding_box"]
+        assert hip["uses_bounding_box"]
+        assert pg.validate_mesh(gable["roof"].to_mesh())["valid"]
+
+    def test_open_walls_rejected(self):
+        """Walls that do not close around an area raise."""
+        walls = [
+            pg.create_wall((0, 0), (10, 0), 3.0, 0.2),
+            pg.create_wall((10, 0), (10, 8), 3.0, 0.2),
+        ]
+
+        with pytest.raises(ValueError):
+            pg.create_roof_from_walls(walls, 0.25)
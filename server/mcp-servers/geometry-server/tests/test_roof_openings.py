@@ -0,0 +1,61 @@
+"""Tests for roof openings and skylights.
+
+These tests verify that:
+1. add_roof_opening cuts flat and pitched roof meshes, reducing their area
+2. Openings across a ridge, outside the boundary, or over each other are rejected
+3. place_skylight links a skylight to its opening and meshes it on the slope
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestRoofOpenings:
+    """Test roof openings and skylights hosted in them."""
+
+    def test_opening_reduces_area(self):
+        """Each roof type meshes around the opening with less surface."""
+        for roof_type in ["flat", "gable", "hip", "shed"]:
+            roof = pg.create_roof((0, 0), (10, 8), 0.25, roof_type=roof_type)
+            before = roof.to_mesh().surface_area()
+
+            result = pg.add_roof_opening(roof, (5, 2), 0.6, 0.6, opening_type="chimney")
+
+            mesh = roof.to_mesh()
+            assert pg.validate_mesh(mesh)["valid"]
+            assert mesh.surface_area() < before
+            assert result["opening"].opening_type == "chimney"
+            assert result["roof_id"] == roof.id
+            assert len(roof.openings) == 1
+
+    def test_invalid_openings(self):
+        """Ridge-spanning, overhanging, and overlapping openings raise."""
+        roof = pg.create_roof((0, 0), (10, 8), 0.25, roof_type="gable")
+
+        with pytest.raises(ValueError, match="ridge"):
+            pg.add_roof_opening(roof, (5, 4), 1.0, 1.0)
+        with pytest.raises(ValueError):
+            pg.add_roof_opening(roof, (9.9, 2), 1.0, 1.0)
+        pg.add_roof_opening(roof, (5, 2), 1.0, 1.0)
+        with pytest.raises(ValueError):
+            pg.add_roof_opening(roof, (5.5, 2), 1.0, 1.0)
+        assert len(roof.openings) == 1
+
+    def test_place_skylight(self):
+        """A skylight's opening points back at it and its curb follows the slope."""
+        roof = pg.create_roof((0, 0), (10, 8), 0.25, roof_type="gable")
+
+        result = pg.place_skylight(roof, (5, 2), 1.0, 1.2)
+
+        skylight = result["skylight"]
+        assert skylight.host_roof_id == roof.id
+        assert result["opening"].hosted_element_id == skylight.id
+        assert roof.openings[0].opening_type == "skylight"
+
+        box = skylight.bounding_box(roof)
+        assert box.max.z == pytest.approx(
+            roof.surface_elevation((5, 2.6)) + skylight.upstand_height
+        )
+        assert skylight.to_mesh(roof).triangle_count() == 12
+        assert roof.remove_opening(roof.openings[0].id)
@@ -0,0 +1,61 @@
+"""Tests for cutting meshes with a section plane.
+
+These tests verify that:
+1. A cut along a wall gives its outline as one closed loop of four corners
+2. Curves are in the plane's (u, v) coordinates, with v as height
+3. A plane that misses a mesh gives no curves for it
+4. A zero normal is rejected
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+def extents(points):
+    us = [p[0] for p in points]
+    vs = [p[1] for p in points]
+    return (min(us), min(vs), max(us), max(vs))
+
+
+class TestSectionMeshes:
+    """Test section_meshes."""
+
+    def test_wall_outline(self):
+        """A cut along the wall's centerline gives its elevation outline."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+
+        [curves] = pg.section_meshes([wall.to_mesh()], (0, 0, 0), (0, -1, 0))
+
+        assert len(curves) == 1
+        [outline] = curves
+        assert len(outline) == 5
+        assert outline[0] == outline[-1]
+        assert extents(outline) == pytest.approx([0.0, 0.0, 5.0, 3.0])
+
+    def test_plan_cut(self):
+        """A horizontal cut is in plan coordinates, u along x and v along y."""
+        wall = pg.create_wall((1, 2), (1, 6), 3.0, 0.2)
+
+        [[outline]] = pg.section_meshes([wall.to_mesh()], (0, 0, 1.0), (0, 0, 1))
+
+        assert extents(outline) == pytest.approx([0.9, 2.0, 1.1, 6.0])
+
+    def test_one_result_per_mesh(self):
+        """Meshes the plane misses get an empty list."""
+        near = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+        far = pg.create_wall((0, 4), (5, 4), 3.0, 0.2)
+
+        near_curves, far_curves = pg.section_meshes(
+            [near.to_mesh(), far.to_mesh()], (0, 0, 0), (0, -1, 0)
+        )
+
+        assert len(near_curves) == 1
+        assert far_curves == []
+
+    def test_zero_normal(self):
+        """A zero normal raises ValueError."""
+        wall = pg.create_wall((0, 0), (5, 0), 3.0, 0.2)
+
+        with pytest.raises(ValueError):
+            pg.section_meshes([wall.to_mesh()], (0, 0, 0), (0, 0, 0))
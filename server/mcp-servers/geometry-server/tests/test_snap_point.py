@@ -0,0 +1,79 @@
+"""Tests for cursor snapping from Python.
+
+These tests verify that:
+1. Higher-priority snaps win over nearer ones
+2. Walls and a TopologyGraph give the same snaps
+3. Grid snapping applies only when nothing else is in range
+4. None is returned when nothing is within tolerance
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestSnapPoint:
+    """Test snap_point on small wall layouts."""
+
+    def test_midpoint_beats_nearer_perpendicular(self):
+        """The wall midpoint wins over the nearer foot on the wall."""
+        walls = [pg.create_wall((0, 0), (4, 0), 3.0, 0.2)]
+
+        snap = pg.snap_point(walls, (2.003, 0.004))
+
+        assert snap["kind"] == "midpoint"
+        assert snap["point"] == pytest.approx((2.0, 0.0))
+        assert snap["distance"] == pytest.approx(0.005)
+        assert len(snap["sources"]) == 1
+
+    def test_corner_and_free_end(self):
+        """A junction is a node; the end of a single wall is an endpoint."""
+        graph = pg.TopologyGraph()
+        graph.add_wall((0, 0), (4, 0))
+        graph.add_wall((4, 0), (4, 3))
+
+        corner = pg.snap_point(graph, (3.995, 0.002))
+        end = pg.snap_point(graph, (4.004, 2.997))
+
+        assert corner["kind"] == "node"
+        assert corner["point"] == pytest.approx((4.0, 0.0))
+        assert end["kind"] == "endpoint"
+        assert end["priority"] > corner["priority"]
+
+    def test_walls_and_graph_agree(self):
+        """A list of walls snaps the same as a graph built from them."""
+        walls = [
+            pg.create_wall((0, 0), (4, 0), 3.0, 0.2),
+            pg.create_wall((6, 1), (6, 3), 3.0, 0.2),
+        ]
+        graph = pg.TopologyGraph()
+        graph.add_wall((0, 0), (4, 0))
+        graph.add_wall((6, 1), (6, 3))
+
+        # The walls' lines meet at (6, 0), beyond both of them
+        from_walls = pg.snap_point(walls, (6.004, 0.003))
+        from_graph = pg.snap_point(graph, (6.004, 0.003))
+
+        assert from_walls["kind"] == from_graph["kind"] == "extension"
+        assert from_walls["point"] == pytest.approx((6.0, 0.0))
+        assert from_graph["point"] == pytest.approx((6.0, 0.0))
+
+    def test_grid_only_when_nothing_else(self):
+        """The grid applies away from walls, not next to them."""
+        walls = [pg.create_wall((0, 0), (4, 0), 3.0, 0.2)]
+
+        off_wall = pg.snap_point(walls, (1.503, 20.998), grid_spacing=0.5)
+        on_wall = pg.snap_point(walls, (1.503, 0.002), grid_spacing=0.5)
+
+        assert off_wall["kind"] == "grid"
+        assert off_wall["point"] == pytest.approx((1.5, 21.0))
+        assert off_wall["sources"] == []
+        assert on_wall["kind"] != "grid"
+
+    def test_nothing_in_range(self):
+        """A cursor far from everything does not snap."""
+        walls = [pg.create_wall((0, 0), (4, 0), 3.0, 0.2)]
+
+        assert pg.snap_point(walls, (2.3, 1.7)) is None
+        with pytest.raises(TypeError):
+            pg.snap_point(42, (0.0, 0.0))
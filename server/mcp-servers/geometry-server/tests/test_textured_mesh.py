@@ -0,0 +1,54 @@
+"""Tests for mesh texture coordinates.
+
+These tests verify that:
+1. Slab meshes carry one normal and one UV per vertex
+2. Textured pitched roofs keep their faces apart with developed UVs
+3. OBJ output includes the UVs and normals
+"""
+
+import math
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestTexturedMesh:
+    """Test normals and UVs on generated meshes."""
+
+    def test_floor_mesh_has_plan_uvs(self):
+        """Floor tops use plan coordinates as UVs."""
+        mesh = pg.create_floor((0, 0), (6, 4), 0.3).to_mesh()
+
+        uvs = mesh.uvs()
+        assert len(uvs) == mesh.vertex_count() == len(mesh.normals())
+        for (x, y, _), (u, v), (_, _, nz) in zip(mesh.vertices(), uvs, mesh.normals()):
+            if nz > 0.5:
+                assert (u, v) == pytest.approx((x, y))
+
+    def test_pitched_roof_textured_mesh(self):
+        """Each slope's UVs span its true length up the slope."""
+        roof = pg.create_roof((0, 0), (10, 8), 0.3, roof_type="gable", slope_degrees=30.0)
+
+        mesh = roof.to_textured_mesh()
+        assert len(mesh.uvs()) == mesh.vertex_count()
+        assert mesh.vertex_count() > roof.to_mesh().vertex_count()
+
+        slope = [
+            (p, uv)
+            for p, n, uv in zip(mesh.vertices(), mesh.normals(), mesh.uvs())
+            if n[2] > 0.1 and n[1] > 0
+        ]
+        ys = [p[1] for p, _ in slope]
+        zs = [p[2] for p, _ in slope]
+        vs = [uv[1] for _, uv in slope]
+        length = math.hypot(max(ys) - min(ys), max(zs) - min(zs))
+        assert max(vs) - min(vs) == pytest.approx(length)
+
+    def test_obj_includes_uvs(self):
+        """OBJ faces reference texture coordinates and normals."""
+        mesh = pg.create_floor((0, 0), (6, 4), 0.3).to_mesh()
+
+        obj = mesh.to_obj()
+        assert "\nvt " in obj
+        assert any(line.count("/") == 6 for line in obj.splitlines() if line.startswith("f "))
@@ -0,0 +1,85 @@
+"""Tests for the persistent TopologyGraph exposed by the Rust kernel.
+
+These tests verify that:
+1. Rooms are detected as walls are added one at a time
+2. Node queries and edge removal work on the live graph
+3. Healing reports its changes
+4. Rooms sharing a wall are reported as adjacent
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestTopologyGraph:
+    """Test building and querying a graph incrementally."""
+
+    def test_rectangle_built_incrementally_has_one_room(self):
+        """Closing the fourth wall encloses exactly one interior room."""
+        graph = pg.TopologyGraph()
+        corners = [(0, 0), (4, 0), (4, 3), (0, 3)]
+
+        for i in range(3):
+            graph.add_wall(corners[i], corners[i + 1], 0.2, 3.0)
+            graph.rebuild_rooms()
+            assert graph.interior_room_count == 0
+
+        graph.add_wall(corners[3], corners[0], 0.2, 3.0)
+        graph.rebuild_rooms()
+
+        assert graph.interior_room_count == 1
+        rooms = graph.rooms()
+        assert len(rooms) == 1
+        assert rooms[0]["area"] == pytest.approx(12.0)
+
+    def test_nodes_within_and_remove_edge(self):
+        """Removing a wall of the rectangle opens the room."""
+        graph = pg.TopologyGraph()
+        corners = [(0, 0), (4, 0), (4, 3), (0, 3)]
+        ids = [graph.add_wall(corners[i], corners[(i + 1) % 4]) for i in range(4)]
+        graph.rebuild_rooms()
+
+        assert graph.node_count == 4
+        assert len(graph.nodes_within((4, 3), 0.01)) == 1
+
+        assert graph.remove_edge(ids[0])
+        assert not graph.remove_edge(ids[0])
+        graph.rebuild_rooms()
+        assert graph.interior_room_count == 0
+
+    def test_zero_length_wall_rejected(self):
+        """A wall whose ends coincide raises ValueError."""
+        graph = pg.TopologyGraph()
+        with pytest.raises(ValueError):
+            graph.add_wall((1, 1), (1, 1))
+
+    def test_heal_reports_room_count(self):
+        """Healing splits crossing walls and rebuilds rooms."""
+        graph = pg.TopologyGraph()
+        corners = [(0, 0), (4000, 0), (4000, 3000), (0, 3000)]
+        for i in range(4):
+            graph.add_wall(corners[i], corners[(i + 1) % 4], 200, 2700)
+        graph.add_wall((2000, -500), (2000, 3500), 200, 2700)
+
+        result = graph.heal()
+
+        assert result["room_count"] == 3
+        assert graph.interior_room_count == 2
+        assert len(result["deleted"]) > 0
+
+    def test_room_adjacency_across_middle_wall(self):
+        """Two rooms split by a wall are adjacent across that wall only."""
+        graph = pg.TopologyGraph()
+        corners = [(0, 0), (2, 0), (4, 0), (4, 2), (2, 2), (0, 2)]
+        for i in range(6):
+            graph.add_wall(corners[i], corners[(i + 1) % 6])
+        middle = graph.add_wall((2, 0), (2, 2))
+        graph.rebuild_rooms()
+
+        adjacency = graph.room_adjacency()
+        assert len(adjacency) == 1
+        a, b, edge = adjacency[0]
+        assert edge == middle
+        assert graph.rooms_sharing_wall(a) == [b]
+        assert graph.rooms_sharing_wall(b) == [a]
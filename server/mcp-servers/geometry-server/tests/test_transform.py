@@ -0,0 +1,56 @@
+"""Tests for the Transform bindings exposed by the Rust kernel.
+
+These tests verify that:
+1. from_trs and decompose round-trip
+2. Rotation about a point keeps that point fixed
+3. Meshes can be transformed
+"""
+
+import math
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestTransform:
+    """Test constructing and decomposing transforms."""
+
+    def test_trs_round_trip(self):
+        """decompose() returns what from_trs() was given."""
+        t = pg.Transform.from_trs((1.0, 2.0, 3.0), [0.0, 0.0, math.pi / 2], (2.0, 3.0, 4.0))
+
+        translation, rotation, scale = t.decompose()
+
+        assert translation == pytest.approx((1.0, 2.0, 3.0))
+        assert scale == pytest.approx((2.0, 3.0, 4.0))
+        half = math.sqrt(0.5)
+        assert [abs(c) for c in rotation] == pytest.approx([half, 0.0, 0.0, half])
+        assert not t.is_rigid()
+
+    def test_rotation_about_center(self):
+        """The center of rotation does not move."""
+        t = pg.Transform.rotation_about((0, 0, 1), math.pi / 2, (5.0, 5.0, 0.0))
+
+        assert t.transform_point((5.0, 5.0, 0.0)) == pytest.approx((5.0, 5.0, 0.0))
+        assert t.transform_point((6.0, 5.0, 0.0)) == pytest.approx((5.0, 6.0, 0.0))
+        assert t.is_rigid()
+
+    def test_invalid_rotation_rejected(self):
+        """A rotation tuple of the wrong length raises ValueError."""
+        with pytest.raises(ValueError):
+            pg.Transform.from_trs((0, 0, 0), [1.0, 2.0])
+
+    def test_singular_inverse_rejected(self):
+        """Inverting a zero scale raises ValueError."""
+        with pytest.raises(ValueError):
+            pg.Transform.scale(0.0, 1.0, 1.0).inverse()
+
+    def test_mesh_transformed(self):
+        """Translating a wall mesh moves its bounds."""
+        wall = pg.create_wall((0, 0), (4, 0), 3.0, 0.2)
+        mesh = wall.to_mesh()
+        moved = mesh.transformed(pg.Transform.translation(0.0, 0.0, 10.0))
+
+        assert moved.vertex_count() == mesh.vertex_count()
+        assert moved.bounding_box().min.z == pytest.approx(10.0)
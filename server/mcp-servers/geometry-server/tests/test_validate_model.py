@@ -0,0 +1,55 @@
+"""Tests for whole-model validation.
+
+These tests verify that:
+1. A clean building reports no issues
+2. Issues carry a severity, a stable rule id, the element ids, and a message
+3. Unclosed walls are reported only when the model has rooms, with or
+   without a topology graph
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+def _building():
+    return pg.create_simple_building((0, 0), (10, 8), 3.0, 0.2, 0.3, "Hall", "001")
+
+
+class TestValidateModel:
+    """validate_model and its report dict."""
+
+    def test_clean_building(self):
+        """A simple building passes every rule."""
+        building = _building()
+        report = pg.validate_model(
+            walls=building["walls"], floors=[building["floor"]], rooms=[building["room"]]
+        )
+
+        assert report == {"issues": []}
+
+    def test_missing_host_wall(self):
+        """A door whose wall is left out is reported as an error."""
+        building = _building()
+        door = pg.place_door(building["walls"][0], 2.0, 0.9, 2.1)["door"]
+        report = pg.validate_model(walls=building["walls"][1:], doors=[door])
+
+        issues = [i for i in report["issues"] if i["rule"] == "missing_host_wall"]
+        assert len(issues) == 1
+        assert issues[0]["severity"] == "error"
+        assert issues[0]["element_ids"] == [door.id, building["walls"][0].id]
+        assert "not in the model" in issues[0]["message"]
+
+    def test_unclosed_walls_with_rooms(self):
+        """Open wall ends matter only when rooms are expected."""
+        building = _building()
+        walls = building["walls"][:3]
+        graph = pg.TopologyGraph()
+        for wall in walls:
+            graph.add_wall((wall.start.x, wall.start.y), (wall.end.x, wall.end.y))
+
+        for kwargs in ({}, {"graph": graph}):
+            report = pg.validate_model(walls=walls, rooms=[building["room"]], **kwargs)
+            rules = [i["rule"] for i in report["issues"]]
+            assert rules == ["unclosed_walls", "unclosed_walls"]
+        assert pg.validate_model(walls=walls, graph=graph)["issues"] == []
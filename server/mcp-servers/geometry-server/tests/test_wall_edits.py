@@ -0,0 +1,66 @@
+"""Tests for the wall alignment and distribution helpers.
+
+These tests verify that:
+1. align_walls moves parallel walls onto one line, in place
+2. distribute_walls_evenly spaces inner walls between the outer two
+3. make_perpendicular squares a wall up about its fixed end
+4. Non-parallel walls are rejected without moving anything
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestWallEdits:
+    """Test the align, distribute, and perpendicular edits."""
+
+    def test_align_to_first(self):
+        """Walls move onto the first wall's line and report their moves."""
+        a = pg.create_wall((0, 0), (4, 0), 3.0, 0.2)
+        b = pg.create_wall((5, 0.3), (9, 0.3), 3.0, 0.2)
+
+        result = pg.align_walls([a, b], "first")
+
+        assert a.start.y == pytest.approx(0.0)
+        assert b.start.y == pytest.approx(0.0)
+        assert b.start.x == pytest.approx(5.0)
+        assert [m["end"] for m in result["moves"]] == ["start", "end"]
+        assert result["moves"][0]["wall_id"] == b.id
+        assert result["moves"][0]["from"] == pytest.approx((5.0, 0.3))
+        assert result["moves"][0]["to"] == pytest.approx((5.0, 0.0))
+
+    def test_align_rejects_non_parallel(self):
+        """Walls at an angle raise and stay where they were."""
+        a = pg.create_wall((0, 0), (4, 0), 3.0, 0.2)
+        b = pg.create_wall((0, 1), (4, 2), 3.0, 0.2)
+
+        with pytest.raises(ValueError):
+            pg.align_walls([a, b])
+
+        assert b.start.y == pytest.approx(1.0)
+
+    def test_distribute_evenly(self):
+        """Inner walls are spaced equally; the outer walls stay put."""
+        walls = [pg.create_wall((x, 0), (x, 3), 3.0, 0.2) for x in (0, 1, 6)]
+
+        result = pg.distribute_walls_evenly(walls)
+
+        assert [w.start.x for w in walls] == pytest.approx([0.0, 3.0, 6.0])
+        assert {m["wall_id"] for m in result["moves"]} == {walls[1].id}
+
+    def test_make_perpendicular(self):
+        """The wall turns about its start to meet the reference square."""
+        reference = pg.create_wall((0, 0), (10, 0), 3.0, 0.2)
+        wall = pg.create_wall((4, 0), (4.5, 3), 3.0, 0.2)
+        length = wall.length()
+
+        result = pg.make_perpendicular(wall, reference, end="start")
+
+        assert wall.start.x == pytest.approx(4.0)
+        assert wall.end.x == pytest.approx(4.0)
+        assert wall.length() == pytest.approx(length)
+        assert [m["end"] for m in result["moves"]] == ["end"]
+
+        with pytest.raises(ValueError):
+            pg.make_perpendicular(wall, reference, end="middle")
@@ -0,0 +1,47 @@
+"""Tests for configurable wall end caps.
+
+These tests verify that:
+1. Walls default to flush ends
+2. Square caps extend the wall by half its thickness at each end
+3. Round caps add arc geometry and unknown caps are rejected
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestWallEndCaps:
+    """Wall end cap configuration and meshing."""
+
+    def _wall(self):
+        return pg.create_wall((0.0, 0.0), (4.0, 0.0), height=3.0, thickness=0.2)
+
+    def test_default_caps_are_flush(self):
+        """A new wall has flush caps at both ends."""
+        wall = self._wall()
+        assert wall.start_cap == "flush"
+        assert wall.end_cap == "flush"
+
+    def test_square_caps_extend_bounding_box(self):
+        """Square caps reach half the thickness past each end."""
+        wall = self._wall()
+        wall.set_end_caps("square")
+        assert wall.start_cap == "square"
+        bbox = wall.bounding_box()
+        assert bbox.min.x == pytest.approx(-0.1)
+        assert bbox.max.x == pytest.approx(4.1)
+
+    def test_round_caps_add_triangles(self):
+        """Round caps mesh more triangles than flush ends."""
+        wall = self._wall()
+        flush = wall.to_mesh().triangle_count()
+        wall.set_end_caps("round", segments=8)
+        assert wall.end_cap == "round"
+        assert wall.to_mesh().triangle_count() > flush
+
+    def test_unknown_cap_is_rejected(self):
+        """An unknown cap name raises ValueError."""
+        wall = self._wall()
+        with pytest.raises(ValueError):
+            wall.set_end_caps("pointed")
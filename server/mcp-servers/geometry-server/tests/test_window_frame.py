@@ -0,0 +1,49 @@
+"""Tests for window frame and mullion meshes.
+
+These tests verify that:
+1. to_frame_mesh meshes the frame members without the glazing
+2. Double-hung and casement windows get a mullion a fixed window lacks
+3. The frame mesh needs the window's own host wall
+"""
+
+import pytest
+
+pg = pytest.importorskip("pensaer_geometry")
+
+
+class TestWindowFrame:
+    """Test window frame sub-geometry."""
+
+    def test_mullion_by_type(self):
+        """Windows that open have more frame triangles than a fixed one."""
+        wall = pg.create_wall((0, 0), (6, 0), 3.0, 0.2)
+        fixed = pg.place_window(wall, 1.5, 1.2, 1.5, 0.9, window_type="fixed")["window"]
+        hung = pg.place_window(wall, 4.5, 1.2, 1.5, 0.9, window_type="double_hung")["window"]
+
+        fixed_mesh = fixed.to_frame_mesh(wall)
+        hung_mesh = hung.to_frame_mesh(wall)
+
+        assert pg.validate_mesh(hung_mesh)["valid"]
+        assert hung_mesh.triangle_count() > fixed_mesh.triangle_count()
+        assert fixed_mesh.triangle_count() < fixed.to_mesh(wall).triangle_count()
+
+    def test_frame_fills_opening_outline(self):
+        """The frame spans the opening from sill to head."""
+        wall = pg.create_wall((0, 0), (6, 0), 3.0, 0.2)
+        window = pg.place_window(wall, 3.0, 1.2, 1.5, 0.9, window_type="casement")["window"]
+
+        box = window.to_frame_mesh(wall).bounding_box()
+
+        assert box.min.x == pytest.approx(2.4)
+        assert box.max.x == pytest.approx(3.6)
+        assert box.min.z == pytest.approx(0.9)
+        assert box.max.z == pytest.approx(2.4)
+
+    def test_needs_host_wall(self):
+        """Meshing against another wall raises."""
+        wall = pg.create_wall((0, 0), (6, 0), 3.0, 0.2)
+        other = pg.create_wall((0, 5), (6, 5), 3.0, 0.2)
+        window = pg.place_window(wall, 3.0, 1.2, 1.5, 0.9)["window"]
+
+        with pytest.raises(ValueError):
+            window.to_frame_mesh(other)